@@ -0,0 +1,59 @@
+use std::{cell::RefCell, rc::Rc, time::Instant};
+
+use shared::{
+    lexer, parser,
+    type_checker::{self, TypeCheckerConfig, TypeEnvironment},
+};
+
+const MATCH_COUNT: usize = 100;
+const ARM_COUNT: usize = 12;
+const ITERATIONS: usize = 5;
+
+fn synthetic_program() -> String {
+    // Interleaved ranges and comparisons make every arm test overlap with the others,
+    // which is what makes decision trees expensive to compile
+    let arms = (0..ARM_COUNT)
+        .map(|i| match i % 3 {
+            0 => format!("| {}..{} => x + {},\n", i * 10, i * 10 + 15, i),
+            1 => format!("| >= {} => x - {},\n", i * 10, i),
+            _ => format!("| {} => x * {},\n", i * 10 + 5, i),
+        })
+        .collect::<String>();
+
+    (0..MATCH_COUNT)
+        .map(|i| format!("fun f{}(x: Int): Int => x match\n{}| _ => x;\n", i, arms))
+        .collect()
+}
+
+fn check(ast: parser::Statement, decision_tree_cache: bool) {
+    let type_environment = Rc::new(RefCell::new(TypeEnvironment::new(false)));
+
+    let config = TypeCheckerConfig {
+        decision_tree_cache,
+        ..Default::default()
+    };
+
+    type_checker::create_typed_ast(ast, type_environment, config).unwrap();
+}
+
+fn main() {
+    let tokens = lexer::tokenize(&synthetic_program()).unwrap();
+    let ast = parser::create_ast(tokens, false).unwrap();
+
+    for decision_tree_cache in [false, true] {
+        let start = Instant::now();
+
+        for _ in 0..ITERATIONS {
+            check(ast.clone(), decision_tree_cache);
+        }
+
+        let elapsed = start.elapsed() / ITERATIONS as u32;
+
+        println!(
+            "{} same-shaped matches, cache {}: {:?}",
+            MATCH_COUNT,
+            if decision_tree_cache { "on " } else { "off" },
+            elapsed
+        );
+    }
+}
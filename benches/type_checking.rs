@@ -0,0 +1,135 @@
+use std::{cell::RefCell, rc::Rc};
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+
+use shared::{
+    lexer,
+    parser::{self, build::int},
+    span::Span,
+    type_checker::{
+        self,
+        ast::{Literal, TypedExpression, TypedMatchArm},
+        decision_tree::{self, Pattern},
+        TypeCheckerConfig, TypeEnvironment,
+    },
+};
+
+const SIZES: [usize; 3] = [10, 100, 1000];
+const MATCH_DEPTHS: [usize; 3] = [2, 4, 8];
+const ARM_COUNTS: [usize; 3] = [4, 16, 64];
+
+fn structs(count: usize) -> String {
+    (0..count)
+        .map(|i| format!("struct Type{} {{ value: Int, other: Float }}\n", i))
+        .collect()
+}
+
+fn functions(count: usize) -> String {
+    (0..count)
+        .map(|i| format!("fun f{}(a: Int, b: Int): Int => a * {} + b\n", i, i))
+        .collect()
+}
+
+/// One function whose body is `depth` matches, each nested in the fallback arm of the one before
+fn nested_matches(depth: usize) -> String {
+    let body = (0..depth).fold("x".to_string(), |inner, i| {
+        format!("x match\n| {} => x + {},\n| _ => ({})", i, i, inner)
+    });
+
+    format!("fun f(x: Int): Int => {}", body)
+}
+
+fn parse(source: &str) -> parser::Statement {
+    let tokens = lexer::tokenize(source).unwrap();
+    parser::create_ast(tokens, false).unwrap()
+}
+
+fn check(ast: parser::Statement) {
+    let type_environment = Rc::new(RefCell::new(TypeEnvironment::new(false)));
+    type_checker::create_typed_ast(ast, type_environment, TypeCheckerConfig::default()).unwrap();
+}
+
+/// Measure checking the program `program` makes at each size, with a throughput of `size`
+/// declarations or matches
+fn bench_programs(
+    criterion: &mut Criterion,
+    name: &str,
+    sizes: &[usize],
+    program: fn(usize) -> String,
+) {
+    let mut group = criterion.benchmark_group(format!("type_checking/{}", name));
+
+    for &size in sizes {
+        let ast = parse(&program(size));
+
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &ast, |bencher, ast| {
+            bencher.iter_batched(|| ast.clone(), check, BatchSize::SmallInput)
+        });
+    }
+
+    group.finish();
+}
+
+fn type_checking(criterion: &mut Criterion) {
+    bench_programs(criterion, "structs", &SIZES, structs);
+    bench_programs(criterion, "functions", &SIZES, functions);
+    bench_programs(criterion, "nested_matches", &MATCH_DEPTHS, nested_matches);
+}
+
+/// Arms which test overlapping ranges and comparisons of an `Int`, ending with a wildcard
+fn overlapping_arms(count: usize) -> Vec<TypedMatchArm> {
+    let type_environment = Rc::new(RefCell::new(TypeEnvironment::new(false)));
+
+    (0..count)
+        .map(|i| {
+            let value = i as i64 * 10;
+
+            let pattern = match i {
+                _ if i == count - 1 => Pattern::Wildcard,
+                _ if i % 3 == 0 => Pattern::Range(
+                    Box::new(Pattern::Int(value)),
+                    Box::new(Pattern::Int(value + 15)),
+                    false,
+                ),
+                _ if i % 3 == 1 => Pattern::GreaterThanOrEqual(Box::new(Pattern::Int(value))),
+                _ => Pattern::Int(value + 5),
+            };
+
+            TypedMatchArm {
+                pattern,
+                expression: int(value),
+                type_environment: type_environment.clone(),
+            }
+        })
+        .collect()
+}
+
+fn decision_trees(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("decision_tree");
+    let matchee = TypedExpression::Literal(Literal::Int(0), Span::default());
+
+    for count in ARM_COUNTS {
+        let arms = overlapping_arms(count);
+
+        group.throughput(Throughput::Elements(count as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(count),
+            &arms,
+            |bencher, arms| {
+                bencher.iter_batched(
+                    || (matchee.clone(), arms.clone()),
+                    |(matchee, arms)| {
+                        decision_tree::create_decision_tree(matchee, arms, &vec![], None).unwrap()
+                    },
+                    BatchSize::SmallInput,
+                )
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, type_checking, decision_trees);
+criterion_main!(benches);
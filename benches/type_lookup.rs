@@ -0,0 +1,96 @@
+use std::{cell::RefCell, collections::HashMap, hint::black_box, rc::Rc, time::Instant};
+
+use shared::{
+    lexer, parser,
+    type_checker::{self, Rcrc, Type, TypeCheckerConfig, TypeEnvironment},
+    types::ToKey,
+};
+
+const TYPE_COUNT: usize = 5000;
+const DEPTH: usize = 32;
+const ITERATIONS: usize = 20;
+
+/// The environment chain as it was before interning, keyed by strings
+struct StringKeyedEnvironment {
+    parent: Option<Rcrc<StringKeyedEnvironment>>,
+    types: HashMap<String, Type>,
+}
+
+impl StringKeyedEnvironment {
+    fn get_type<K: ToKey>(&self, key: K) -> Option<Type> {
+        self.types
+            .get(&key.to_key())
+            .cloned()
+            .or_else(|| self.parent.as_ref().and_then(|p| p.borrow().get_type(key)))
+    }
+}
+
+fn synthetic_program() -> String {
+    (0..TYPE_COUNT)
+        .map(|i| format!("struct Type{} {{ value: Int }}\n", i))
+        .collect()
+}
+
+fn main() {
+    let tokens = lexer::tokenize(&synthetic_program()).unwrap();
+    let ast = parser::create_ast(tokens, false).unwrap();
+    let root = Rc::new(RefCell::new(TypeEnvironment::new(false)));
+
+    type_checker::create_typed_ast(ast, root.clone(), TypeCheckerConfig::default()).unwrap();
+
+    // Look the types up from a deeply nested scope so every lookup walks the parent chain
+    let mut environment = root.clone();
+
+    let mut string_keyed = Rc::new(RefCell::new(StringKeyedEnvironment {
+        parent: None,
+        types: root.borrow().get_types(),
+    }));
+
+    for _ in 0..DEPTH {
+        environment = Rc::new(RefCell::new(TypeEnvironment::new_parent(environment)));
+
+        string_keyed = Rc::new(RefCell::new(StringKeyedEnvironment {
+            parent: Some(string_keyed),
+            types: HashMap::new(),
+        }));
+    }
+
+    let names = (0..TYPE_COUNT)
+        .map(|i| format!("Type{}", i))
+        .collect::<Vec<_>>();
+
+    let start = Instant::now();
+
+    for _ in 0..ITERATIONS {
+        for name in &names {
+            black_box(environment.borrow().get_type(name));
+        }
+    }
+
+    let interned_time = start.elapsed();
+    let start = Instant::now();
+
+    for _ in 0..ITERATIONS {
+        for name in &names {
+            black_box(string_keyed.borrow().get_type(name));
+        }
+    }
+
+    let string_keyed_time = start.elapsed();
+    let lookups = (ITERATIONS * TYPE_COUNT) as u32;
+
+    println!(
+        "{} lookups through {} scopes over {} types",
+        lookups, DEPTH, TYPE_COUNT
+    );
+    println!(
+        "interned:     {:?} ({:?} per lookup)",
+        interned_time,
+        interned_time / lookups
+    );
+    println!(
+        "string keyed: {:?} ({:?} per lookup)",
+        string_keyed_time,
+        string_keyed_time / lookups
+    );
+}
@@ -0,0 +1,22 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use shared::{lexer, parser};
+
+/// Longer inputs are slower to run and rarely reach anything a shorter one doesn't
+const MAX_INPUT_LENGTH: usize = 4096;
+
+// Lexing and parsing may fail with an error on any input, but they should never panic
+fuzz_target!(|data: &[u8]| {
+    if data.len() > MAX_INPUT_LENGTH {
+        return;
+    }
+
+    let Ok(source) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    if let Ok(tokens) = lexer::tokenize(source) {
+        let _ = parser::create_ast(tokens, false);
+    }
+});
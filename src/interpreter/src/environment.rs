@@ -1,7 +1,7 @@
 use std::{cell::RefCell, collections::HashMap, rc::Rc};
 
 use shared::{
-    type_checker::ast::Member,
+    type_checker::ast::{Member, TypedExpression},
     types::{ToKey, TypeAnnotation},
 };
 
@@ -195,9 +195,37 @@ impl Environment {
         }
     }
 
+    /// Find a method of a type, either its own or one given to it by a protocol it implements
+    pub fn get_method(
+        &self,
+        type_annotation: &TypeAnnotation,
+        name: &str,
+    ) -> Option<Rcrc<Variable>> {
+        if let Some(method) = self.get_static_member(type_annotation, name) {
+            return Some(method);
+        }
+
+        let protocol_method = self.static_members.iter().find_map(|(owner, members)| {
+            let TypeAnnotation::ConcreteType(_, types) = owner else {
+                return None;
+            };
+
+            match types.as_slice() {
+                [implementor] if implementor == type_annotation => members.get(name).cloned(),
+                _ => None,
+            }
+        });
+
+        protocol_method.or_else(|| {
+            self.parent
+                .as_ref()
+                .and_then(|p| p.borrow().get_method(type_annotation, name))
+        })
+    }
+
     pub fn set_variable(&mut self, member: Member, value: Value) -> Result<Value, String> {
         match member {
-            Member::Identifier { symbol, type_: _ } => {
+            Member::Identifier { symbol, .. } => {
                 let variable = self
                     .resolve(&symbol)
                     .ok_or(format!("Variable '{}' not found", symbol))?
@@ -213,8 +241,20 @@ impl Environment {
                 Ok(value)
             }
             Member::StaticMemberAccess { .. } => Err("Cannot assign to static member".to_owned()),
-            Member::MemberAccess { member, .. } => {
-                self.set_variable(*member.clone(), value.clone())
+            Member::MemberAccess { .. } => {
+                let (symbol, path) = field_path(&member)?;
+
+                let variable = self
+                    .resolve(&symbol)
+                    .ok_or(format!("Variable '{}' not found", symbol))?
+                    .clone();
+
+                if !variable.borrow().mutable {
+                    return Err(format!("Cannot assign to immutable variable '{}'", symbol));
+                }
+
+                set_field(&mut variable.borrow_mut().value, &path, value.clone())?;
+                Ok(value)
             }
         }
     }
@@ -243,3 +283,40 @@ impl Environment {
         }
     }
 }
+
+/// The variable at the root of a field access like `a.b.c`, and the fields leading from it
+fn field_path(member: &Member) -> Result<(String, Vec<String>), String> {
+    match member {
+        Member::Identifier { symbol, .. } => Ok((symbol.clone(), vec![])),
+        Member::MemberAccess { object, symbol, .. } => {
+            let TypedExpression::Member(object) = object.as_ref() else {
+                return Err(format!(
+                    "Cannot assign to field '{}' of a temporary value",
+                    symbol
+                ));
+            };
+
+            let (root, mut path) = field_path(object)?;
+            path.push(symbol.clone());
+            Ok((root, path))
+        }
+        Member::StaticMemberAccess { .. } => Err("Cannot assign to static member".to_owned()),
+    }
+}
+
+fn set_field(target: &mut Value, path: &[String], value: Value) -> Result<(), String> {
+    let Some((field, rest)) = path.split_first() else {
+        *target = value;
+        return Ok(());
+    };
+
+    let Value::Struct { fields, .. } = target else {
+        return Err(format!("Cannot assign to field '{}' of {}", field, target));
+    };
+
+    let target = fields
+        .get_mut(field)
+        .ok_or(format!("Field '{}' not found", field))?;
+
+    set_field(target, rest, value)
+}
@@ -243,6 +243,9 @@ fn evaluate_equal(left: Value, right: Value) -> Result<Value, String> {
         ) => Ok(Value::Bool(
             left_enum_member == right_enum_member && left_fields == right_fields,
         )),
+        (left @ Value::Struct { .. }, right @ Value::Struct { .. }) => {
+            Ok(Value::Bool(left == right))
+        }
         (left, right) => Err(format!("Cannot equal {:?} and {:?}", left, right)),
     }
 }
@@ -259,6 +262,21 @@ fn evaluate_not_equal(left: Value, right: Value) -> Result<Value, String> {
         },
         (Value::Char(left), Value::Char(right)) => Ok(Value::Bool(left != right)),
         (Value::String(left), Value::String(right)) => Ok(Value::Bool(left != right)),
+        (
+            Value::Enum {
+                enum_member: left_enum_member,
+                fields: left_fields,
+            },
+            Value::Enum {
+                enum_member: right_enum_member,
+                fields: right_fields,
+            },
+        ) => Ok(Value::Bool(
+            left_enum_member != right_enum_member || left_fields != right_fields,
+        )),
+        (left @ Value::Struct { .. }, right @ Value::Struct { .. }) => {
+            Ok(Value::Bool(left != right))
+        }
         (left, right) => Err(format!("Cannot not equal {:?} and {:?}", left, right)),
     }
 }
@@ -342,7 +360,7 @@ fn evaluate_range(left: Value, right: Value, inclusive: bool) -> Result<Value, S
                     .collect(),
             )),
             (Number::UInt(left), Number::UInt(right), true) => Ok(Value::Array(
-                (left..right)
+                (left..=right)
                     .map(|v| Value::Number(Number::UInt(v)))
                     .collect(),
             )),
@@ -4,7 +4,7 @@ use shared::{
     type_checker::{
         ast::{Block, *},
         decision_tree::{Accessor, Constructor, Decision, FieldPattern, Pattern},
-        type_annotation_equals, Type,
+        protocol_member_owner, Type,
     },
     types::{TypeAnnotation, TypeIdentifier},
 };
@@ -29,19 +29,29 @@ pub fn evaluate(
             environment.borrow_mut().add_module(module_path);
             Ok(Value::Void)
         }
-        TypedStatement::Use { .. } => {
-            todo!("Use statement evaluation")
-        }
+        // Imports are resolved by the type checker, and every item is still reachable by its name
+        TypedStatement::Use { .. } => Ok(Value::Void),
         TypedStatement::StructDeclaration { .. } => Ok(Value::Void),
         TypedStatement::EnumDeclaration { .. } => Ok(Value::Void),
         TypedStatement::UnionDeclaration { .. } => Ok(Value::Void),
         TypedStatement::TypeAliasDeclaration { .. } => Ok(Value::Void),
+        TypedStatement::NewtypeDeclaration { .. } => Ok(Value::Void),
         TypedStatement::ProtocolDeclaration { .. } => Ok(Value::Void),
         TypedStatement::ImplementationDeclaration {
+            protocol_annotation,
             type_annotation,
             functions,
             ..
-        } => evaluate_implementation_declaration(environment, type_annotation, functions),
+        } => {
+            let owner = match protocol_annotation {
+                Some(protocol_annotation) => {
+                    protocol_member_owner(&protocol_annotation.name(), type_annotation)
+                }
+                None => type_annotation,
+            };
+
+            evaluate_implementation_declaration(environment, owner, functions)
+        }
         TypedStatement::FunctionDeclaration {
             identifier,
             param,
@@ -61,10 +71,14 @@ fn evaluate_implementation_declaration(
     type_annotation: TypeAnnotation,
     functions: Vec<(String, TypedStatement)>,
 ) -> Result<Value, String> {
+    // The functions are only reachable through the type, so they must not shadow global ones
+    let implementation_environment =
+        Rc::new(RefCell::new(Environment::new_parent(environment.clone())));
+
     for (function_name, function) in functions {
-        evaluate(function, environment.clone())?;
+        evaluate(function, implementation_environment.clone())?;
 
-        let variable = environment
+        let variable = implementation_environment
             .borrow()
             .get_function(&function_name)
             .ok_or(format!("Function '{}' not found", function_name))?
@@ -91,6 +105,7 @@ fn evaluate_function_declaration(
     let function_environment = Rc::new(RefCell::new(environment.deref().clone().borrow().clone()));
 
     let function = Value::Function {
+        param_mutable: param.as_ref().is_some_and(|p| p.mutable),
         param_name: param.map(|p| p.identifier),
         body,
         environment: function_environment.clone(),
@@ -136,8 +151,9 @@ fn evaluate_expression(
             ..
         } => evaluate_assignment(member, initializer, environment),
         TypedExpression::Member(m) => evaluate_member(m, environment),
-        TypedExpression::Literal(l) => evaluate_literal(l, environment),
+        TypedExpression::Literal(l, _) => evaluate_literal(l, environment),
         TypedExpression::Tuple { elements, .. } => evaluate_tuple(elements, environment),
+        TypedExpression::Record { fields, .. } => evaluate_record(fields, environment),
         TypedExpression::Closure {
             param,
             return_type: _,
@@ -148,6 +164,7 @@ fn evaluate_expression(
             callee,
             argument,
             type_,
+            ..
         } => evaluate_call(callee, argument, type_, environment),
         TypedExpression::Index {
             callee, argument, ..
@@ -163,6 +180,9 @@ fn evaluate_expression(
             right,
             ..
         } => evaluate_binary(left, operator, right, environment),
+        TypedExpression::Cast {
+            expression, type_, ..
+        } => evaluate_cast(expression, type_, environment),
         TypedExpression::Block(Block { statements, .. }) => evaluate_block(statements, environment),
         TypedExpression::Print { value } => {
             let value = evaluate_expression(*value, environment)?;
@@ -180,10 +200,11 @@ fn evaluate_expression(
         TypedExpression::For {
             identifier,
             iterable,
+            next,
             body,
             else_body,
             ..
-        } => evaluate_for(identifier, iterable, body, else_body, environment),
+        } => evaluate_for(identifier, iterable, next, body, else_body, environment),
         TypedExpression::Break(e) => evaluate_break(e, environment),
         TypedExpression::Continue => evaluate_continue(environment),
         TypedExpression::Return(e) => evaluate_return(e, environment),
@@ -203,6 +224,22 @@ fn evaluate_tuple(
     Ok(Value::Tuple(tuple))
 }
 
+fn evaluate_record(
+    fields: Vec<FieldInitializer>,
+    environment: Rcrc<Environment>,
+) -> Result<Value, String> {
+    let mut record = Vec::new();
+
+    for field in fields {
+        record.push((
+            field.identifier.unwrap(),
+            evaluate_expression(field.initializer, environment.clone())?,
+        ));
+    }
+
+    Ok(Value::Record(record))
+}
+
 fn evaluate_program(
     statements: Vec<TypedStatement>,
     environment: Rcrc<Environment>,
@@ -318,21 +355,6 @@ fn evaluate_decision_tree(
             fallback,
             type_: _,
         } => {
-            let switch_value = match variable.accessor {
-                Accessor::Environment => value.clone(),
-                Accessor::Expression(expression) => {
-                    let argument_value = evaluate_expression(*expression, environment.clone())?;
-
-                    environment.borrow_mut().add_variable(
-                        variable.identifier.clone(),
-                        argument_value.clone(),
-                        false,
-                    );
-
-                    argument_value
-                }
-            };
-
             for case in cases {
                 let pattern = case.pattern;
                 let arguments = case.arguments;
@@ -364,6 +386,23 @@ fn evaluate_decision_tree(
                             .add_variable(identifier, value, false);
                     }
 
+                    // The field may only exist on the member the case matched
+                    let switch_value = match variable.accessor {
+                        Accessor::Environment => value,
+                        Accessor::Expression(expression) => {
+                            let argument_value =
+                                evaluate_expression(*expression, environment.clone())?;
+
+                            environment.borrow_mut().add_variable(
+                                variable.identifier.clone(),
+                                argument_value.clone(),
+                                false,
+                            );
+
+                            argument_value
+                        }
+                    };
+
                     return evaluate_decision_tree(body, switch_value, case_environment);
                 }
             }
@@ -467,14 +506,11 @@ fn evaluate_pattern(
                     enum_member,
                     fields,
                 } => {
-                    let member_type_annotation =
-                        TypeAnnotation::Type(enum_member.member_name.clone());
-
-                    if !type_annotation_equals(&member_type_annotation, &type_annotation) {
-                        return Err(format!(
-                            "Expected enum '{}', found '{}'",
-                            type_annotation, enum_member
-                        ));
+                    // Enum members may be written with their enum as a prefix
+                    if type_annotation.name().rsplit("::").next()
+                        != Some(enum_member.member_name.as_str())
+                    {
+                        return Ok(None);
                     }
 
                     match fields {
@@ -942,6 +978,10 @@ fn evaluate_member_access(
     environment: Rcrc<Environment>,
     member: Box<Member>,
 ) -> Result<Value, String> {
+    if let Type::Newtype(_) = object.get_type() {
+        return evaluate_expression(*object, environment);
+    }
+
     let value = evaluate_expression(*object, environment.clone())?;
 
     match value {
@@ -965,6 +1005,15 @@ fn evaluate_member_access(
                 evaluate_member_access(object, environment, member)
             }
         },
+        Value::Record(fields) => {
+            let symbol = member.get_symbol();
+
+            fields
+                .into_iter()
+                .find(|(identifier, _)| *identifier == symbol)
+                .map(|(_, value)| value)
+                .ok_or(format!("Field '{}' not found in record", symbol))
+        }
         Value::Enum {
             enum_member,
             fields,
@@ -1066,6 +1115,7 @@ fn evaluate_literal(literal: Literal, environment: Rcrc<Environment>) -> Result<
         Literal::Struct {
             type_annotation,
             field_initializers,
+            spread,
             ..
         } => {
             let mut fields = std::collections::HashMap::new();
@@ -1077,6 +1127,21 @@ fn evaluate_literal(literal: Literal, environment: Rcrc<Environment>) -> Result<
                 );
             }
 
+            if let Some(spread) = spread {
+                let Value::Struct {
+                    fields: spread_fields,
+                    ..
+                } = evaluate_expression(*spread, environment.clone())?
+                else {
+                    return Err("Spread in a struct literal is not a struct".to_string());
+                };
+
+                // Fields listed in the literal override the ones from the spread
+                for (identifier, value) in spread_fields {
+                    fields.entry(identifier).or_insert(value);
+                }
+            }
+
             Ok(Value::Struct {
                 struct_name: type_annotation,
                 fields,
@@ -1122,6 +1187,7 @@ fn evaluate_closure(
 ) -> Result<Value, String> {
     Ok(Value::Function {
         param_name: param.map(|p| p.identifier),
+        param_mutable: false,
         body,
         environment,
     })
@@ -1133,15 +1199,62 @@ fn evaluate_call(
     type_: Type,
     environment: Rcrc<Environment>,
 ) -> Result<Value, String> {
-    let callee_value = evaluate_expression(*callee, environment.clone())?;
+    // Newtypes share the runtime representation of their underlying type
+    if let Type::Newtype(_) = callee.get_type() {
+        let Some(argument) = argument else {
+            return Err(format!("Newtype '{}' must be called with a value", callee));
+        };
+
+        return evaluate_expression(*argument, environment);
+    }
+
+    // A protocol function called on a value of a generic type is looked up on the type of the
+    // value once it is known
+    let generic_method = match callee.as_ref() {
+        TypedExpression::Member(Member::StaticMemberAccess {
+            type_annotation,
+            symbol,
+            ..
+        }) if environment
+            .borrow()
+            .get_static_member(type_annotation, symbol.as_str())
+            .is_none() =>
+        {
+            Some(symbol.clone())
+        }
+        _ => None,
+    };
+
+    let callee_value = match generic_method {
+        Some(_) => None,
+        None => Some(evaluate_expression(*callee, environment.clone())?),
+    };
 
     let evaluated_arg = argument
         .map(|arg| evaluate_expression(*arg, environment.clone()))
         .transpose()?;
 
+    let callee_value = match (callee_value, generic_method) {
+        (Some(callee_value), _) => callee_value,
+        (None, Some(symbol)) => {
+            let method = evaluated_arg
+                .as_ref()
+                .and_then(Value::type_annotation)
+                .and_then(|type_annotation| {
+                    environment.borrow().get_method(&type_annotation, &symbol)
+                })
+                .ok_or(format!("Method '{}' not found", symbol))?;
+
+            let value = method.borrow().value.clone();
+            value
+        }
+        (None, None) => unreachable!("The callee is evaluated unless it is a generic method"),
+    };
+
     match callee_value {
         Value::Function {
             param_name,
+            param_mutable,
             body,
             environment,
         } => {
@@ -1154,7 +1267,7 @@ fn evaluate_call(
                 function_environment.borrow_mut().add_variable(
                     param_name.clone().unwrap(),
                     evaluated_arg.clone(),
-                    false,
+                    param_mutable,
                 );
             }
 
@@ -1171,6 +1284,9 @@ fn evaluate_call(
 
                         value = v.clone();
                     }
+                    None if type_ == Type::Unit => {
+                        value = Value::Unit;
+                    }
                     None => {
                         if type_ != Type::Void {
                             return Err(format!(
@@ -1188,6 +1304,11 @@ fn evaluate_call(
                 return Ok(Value::Void);
             }
 
+            // A Unit function with a body that produces no value returns unit
+            if type_ == Type::Unit && value == Value::Void {
+                return Ok(Value::Unit);
+            }
+
             Ok(value)
         }
         _ => Err(format!("Cannot call non-function value '{}'", callee_value)),
@@ -1201,22 +1322,75 @@ fn evaluate_index(
 ) -> Result<Value, String> {
     let callee_value = evaluate_expression(*callee, environment.clone())?;
 
-    let evaluated_argument = evaluate_expression(*argument, environment.clone())?;
-
-    let Value::Number(Number::UInt(index)) = evaluated_argument else {
-        unreachable!("Type is known after type checking, this should never happen")
+    // A string is indexed like an array of its characters
+    let (values, is_string) = match callee_value {
+        Value::Array(values) => (values, false),
+        Value::String(value) => (value.chars().map(Value::Char).collect(), true),
+        _ => return Err(format!("Cannot index non-array value '{}'", callee_value)),
     };
 
-    match callee_value {
-        Value::Array(values) => {
-            let value = values
-                .get(index as usize)
-                .cloned()
-                .ok_or(format!("Index out of bounds '{}'", index))?;
+    if let TypedExpression::Binary {
+        left,
+        operator: operator @ (BinaryOperator::Range | BinaryOperator::RangeInclusive),
+        right,
+        ..
+    } = *argument
+    {
+        let start = evaluate_index_value(*left, environment.clone())?;
+        let end = evaluate_index_value(*right, environment.clone())?;
+
+        let range_end = match operator {
+            BinaryOperator::RangeInclusive => end + 1,
+            _ => end,
+        };
+
+        if start < 0 || start > range_end || range_end > values.len() as i128 {
+            return Err(format!(
+                "Slice {}{}{} is out of range for a{} of length {}",
+                start,
+                operator,
+                end,
+                if is_string { " string" } else { "n array" },
+                values.len()
+            ));
+        }
 
-            Ok(value)
+        let values = &values[start as usize..range_end as usize];
+
+        if is_string {
+            return Ok(Value::String(
+                values
+                    .iter()
+                    .map(|value| match value {
+                        Value::Char(c) => *c,
+                        _ => unreachable!("The values of a string are its characters"),
+                    })
+                    .collect(),
+            ));
         }
-        _ => Err(format!("Cannot index non-array value '{}'", callee_value)),
+
+        return Ok(Value::Array(values.to_vec()));
+    }
+
+    let index = evaluate_index_value(*argument, environment)?;
+
+    let value = usize::try_from(index)
+        .ok()
+        .and_then(|index| values.get(index))
+        .cloned()
+        .ok_or(format!("Index out of bounds '{}'", index))?;
+
+    Ok(value)
+}
+
+fn evaluate_index_value(
+    expression: TypedExpression,
+    environment: Rcrc<Environment>,
+) -> Result<i128, String> {
+    match evaluate_expression(expression, environment)? {
+        Value::Number(Number::Int(index)) => Ok(index as i128),
+        Value::Number(Number::UInt(index)) => Ok(index as i128),
+        _ => unreachable!("Type is known after type checking, this should never happen"),
     }
 }
 
@@ -1280,11 +1454,49 @@ fn evaluate_binary(
     environment: Rcrc<Environment>,
 ) -> Result<Value, String> {
     let left = evaluate_expression(*left, environment.clone())?;
+
+    // The right side of && and || is only evaluated if the left side doesn't decide the result
+    match (&operator, &left) {
+        (BinaryOperator::LogicalAnd, Value::Bool(false)) => return Ok(Value::Bool(false)),
+        (BinaryOperator::LogicalOr, Value::Bool(true)) => return Ok(Value::Bool(true)),
+        _ => {}
+    }
+
     let right = evaluate_expression(*right, environment)?;
 
     evaluate_binop::evaluate_binop(left, operator, right)
 }
 
+fn evaluate_cast(
+    expression: Box<TypedExpression>,
+    type_: Type,
+    environment: Rcrc<Environment>,
+) -> Result<Value, String> {
+    let value = evaluate_expression(*expression, environment)?;
+
+    match (value, type_) {
+        (Value::Number(Number::Int(v)), Type::UInt) => Ok(Value::Number(Number::UInt(v as u64))),
+        (Value::Number(Number::Int(v)), Type::Float) => Ok(Value::Number(Number::Float(v as f64))),
+        (Value::Number(Number::UInt(v)), Type::Int) => Ok(Value::Number(Number::Int(v as i64))),
+        (Value::Number(Number::UInt(v)), Type::Float) => Ok(Value::Number(Number::Float(v as f64))),
+        (Value::Number(Number::Float(v)), Type::Int) => Ok(Value::Number(Number::Int(v as i64))),
+        (Value::Number(Number::Float(v)), Type::UInt) => Ok(Value::Number(Number::UInt(v as u64))),
+        (Value::Char(c), Type::Int) => Ok(Value::Number(Number::Int(c as i64))),
+        (Value::Char(c), Type::UInt) => Ok(Value::Number(Number::UInt(c as u64))),
+        (Value::Number(Number::Int(v)), Type::Char) => u32::try_from(v)
+            .ok()
+            .and_then(char::from_u32)
+            .map(Value::Char)
+            .ok_or(format!("Cannot cast {} to a char", v)),
+        (Value::Number(Number::UInt(v)), Type::Char) => u32::try_from(v)
+            .ok()
+            .and_then(char::from_u32)
+            .map(Value::Char)
+            .ok_or(format!("Cannot cast {} to a char", v)),
+        (value, _) => Ok(value),
+    }
+}
+
 fn evaluate_block(
     statements: Vec<TypedStatement>,
     environment: Rcrc<Environment>,
@@ -1435,6 +1647,7 @@ fn evaluate_while(
 fn evaluate_for(
     identifier: String,
     iterable: Box<TypedExpression>,
+    next: Option<Box<TypedExpression>>,
     body: Box<TypedExpression>,
     else_body: Option<Box<TypedExpression>>,
     environment: Rcrc<Environment>,
@@ -1444,37 +1657,21 @@ fn evaluate_for(
         [ScopeType::Break, ScopeType::Continue],
     )));
 
-    let value = evaluate_expression(*iterable.clone(), for_environment.clone())?;
-    let array = match value {
-        Value::Array(array) => array,
-        _ => return Err(format!("For iterable must be an array '{}'", value)),
-    };
-
-    if array.is_empty() {
-        match else_body {
-            Some(else_body) => {
-                let mut value = evaluate_expression(*else_body.clone(), for_environment.clone())?;
-
-                if for_environment
-                    .borrow()
-                    .get_scope(&ScopeType::Return)
-                    .is_some()
-                {
-                    value = Value::Void;
-                }
-
-                return Ok(value);
-            }
-            None => return Ok(Value::Void),
-        }
-    }
-
+    let mut iterator = evaluate_expression(*iterable, for_environment.clone())?;
     let mut index = 0;
 
     let break_value;
 
     loop {
-        if index >= array.len() {
+        let element = next_element(
+            &identifier,
+            &mut iterator,
+            &mut index,
+            next.as_deref(),
+            for_environment.clone(),
+        )?;
+
+        let Some(value) = element else {
             break_value = match else_body {
                 Some(else_body) => {
                     let mut value =
@@ -1494,14 +1691,7 @@ fn evaluate_for(
             };
 
             break;
-        }
-
-        let value = array
-            .get(index)
-            .cloned()
-            .unwrap_or_else(|| panic!("Index out of bounds: {}", index));
-
-        index += 1;
+        };
 
         for_environment
             .borrow_mut()
@@ -1538,6 +1728,61 @@ fn evaluate_for(
     Ok(break_value)
 }
 
+/// Take the next element of the iterable of a for loop and move the iterator past it. Arrays are
+/// walked by index, other iterators are replaced by the iterator their `next` gives back.
+fn next_element(
+    identifier: &str,
+    iterator: &mut Value,
+    index: &mut usize,
+    next: Option<&TypedExpression>,
+    environment: Rcrc<Environment>,
+) -> Result<Option<Value>, String> {
+    let Some(next) = next else {
+        let Value::Array(array) = iterator else {
+            return Err(format!("For iterable must be an array '{}'", iterator));
+        };
+
+        let value = array.get(*index).cloned();
+        *index += 1;
+        return Ok(value);
+    };
+
+    environment
+        .borrow_mut()
+        .add_variable(identifier.to_owned(), iterator.clone(), false);
+
+    let option = evaluate_expression(next.clone(), environment)?;
+
+    let Value::Enum {
+        enum_member,
+        fields,
+    } = &option
+    else {
+        return Err(format!("'next' must return an option, found '{}'", option));
+    };
+
+    if enum_member.member_name == "None" {
+        return Ok(None);
+    }
+
+    match fields {
+        EnumFields::Named(fields) => match fields.get("v") {
+            Some(Value::Tuple(values)) if values.len() == 2 => {
+                *iterator = values[1].clone();
+                Ok(Some(values[0].clone()))
+            }
+            _ => Err(format!(
+                "'next' must return an element and an iterator, found '{}'",
+                option
+            )),
+        },
+        _ => Err(format!(
+            "'next' must return an element and an iterator, found '{}'",
+            option
+        )),
+    }
+}
+
 fn evaluate_break(
     expression: Option<Box<TypedExpression>>,
     environment: Rcrc<Environment>,
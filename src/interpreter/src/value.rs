@@ -5,6 +5,7 @@ use shared::{type_checker::ast::TypedExpression, types::TypeAnnotation};
 use crate::{environment::Rcrc, Environment};
 
 #[derive(Debug, Clone, PartialEq)]
+#[allow(clippy::large_enum_variant)]
 pub enum Value {
     Uninitialized,
     Void,
@@ -15,6 +16,7 @@ pub enum Value {
     String(String),
     Array(Vec<Value>),
     Tuple(Vec<Value>),
+    Record(Vec<(String, Value)>),
     Struct {
         struct_name: TypeAnnotation,
         fields: HashMap<String, Value>,
@@ -25,6 +27,9 @@ pub enum Value {
     },
     Function {
         param_name: Option<String>,
+        /// Whether the parameter is `mut self`, which the function can assign to. The argument is
+        /// a copy, so assigning to it doesn't change the value the function was called on.
+        param_mutable: bool,
         body: TypedExpression,
         environment: Rcrc<Environment>,
     },
@@ -53,6 +58,43 @@ impl Value {
             fields: EnumFields::None,
         }
     }
+
+    pub fn result_ok(value: Value) -> Value {
+        Value::Enum {
+            enum_member: EnumMember {
+                enum_name: TypeAnnotation::ConcreteType("Result".to_owned(), vec![]),
+                member_name: "Ok".to_owned(),
+            },
+            fields: EnumFields::Named(HashMap::from([("value".to_owned(), value)])),
+        }
+    }
+
+    pub fn result_err(error: Value) -> Value {
+        Value::Enum {
+            enum_member: EnumMember {
+                enum_name: TypeAnnotation::ConcreteType("Result".to_owned(), vec![]),
+                member_name: "Err".to_owned(),
+            },
+            fields: EnumFields::Named(HashMap::from([("error".to_owned(), error)])),
+        }
+    }
+
+    /// The type whose static members hold the methods of the value, if it has any
+    pub fn type_annotation(&self) -> Option<TypeAnnotation> {
+        let name = match self {
+            Value::Bool(_) => "Bool",
+            Value::Number(Number::Int(_)) => "Int",
+            Value::Number(Number::UInt(_)) => "UInt",
+            Value::Number(Number::Float(_)) => "Float",
+            Value::Char(_) => "Char",
+            Value::String(_) => "String",
+            Value::Struct { struct_name, .. } => return Some(struct_name.clone()),
+            Value::Enum { enum_member, .. } => return Some(enum_member.type_annotation()),
+            _ => return None,
+        };
+
+        Some(TypeAnnotation::Type(name.to_owned()))
+    }
 }
 
 impl Display for Value {
@@ -91,6 +133,19 @@ impl Display for Value {
 
                 write!(f, ")")
             }
+            Value::Record(fields) => {
+                write!(f, "{{ ")?;
+
+                for (index, (identifier, value)) in fields.iter().enumerate() {
+                    write!(f, "{}: {}", identifier, value)?;
+
+                    if index < fields.len() - 1 {
+                        write!(f, ", ")?;
+                    }
+                }
+
+                write!(f, " }}")
+            }
             Value::Struct {
                 struct_name,
                 fields,
@@ -130,6 +185,7 @@ impl Display for Value {
             },
             Value::Function {
                 param_name,
+                param_mutable: _,
                 body: _,
                 environment: _,
             } => write!(f, "fun({})", param_name.clone().unwrap_or("".to_owned())),
@@ -14,7 +14,7 @@ use interpreter::Environment;
 use shared::{
     parser::{self, create_ast},
     pretty_print::PrettyPrint,
-    type_checker::{create_typed_ast, TypeEnvironment},
+    type_checker::{create_typed_ast, TypeCheckerConfig, TypeEnvironment},
 };
 
 const STACK_SIZE: usize = 4 * 1024 * 1024;
@@ -167,7 +167,16 @@ pub fn read_input(
         eprintln!("{}\n", program.prettify());
     }
 
-    let typed_program = create_typed_ast(program, type_environment)?;
+    let typed_program = create_typed_ast(
+        program,
+        type_environment.clone(),
+        TypeCheckerConfig::default(),
+    )?;
+
+    for warning in type_environment.borrow_mut().take_warnings() {
+        eprintln!("warning: {}", warning);
+    }
+
     if print_type_checker_ast {
         eprintln!("{}\n", typed_program.prettify());
     }
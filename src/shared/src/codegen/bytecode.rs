@@ -0,0 +1,1049 @@
+use std::{collections::HashMap, fmt::Display};
+
+use crate::type_checker::{
+    ast::{
+        BinaryOperator, Block, EnumMemberFieldInitializers, Literal, Member, Typed,
+        TypedExpression, TypedStatement, UnaryOperator,
+    },
+    decision_tree::{Accessor, Constructor, Decision, Pattern},
+    visit::{walk_statement, TypedVisitor},
+    Type,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Constant {
+    Void,
+    Unit,
+    Bool(bool),
+    Int(i64),
+    UInt(u64),
+    Float(f64),
+    Char(char),
+    String(String),
+}
+
+impl Display for Constant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Constant::Void => write!(f, "void"),
+            Constant::Unit => write!(f, "()"),
+            Constant::Bool(v) => write!(f, "{}", v),
+            Constant::Int(v) => write!(f, "{}", v),
+            Constant::UInt(v) => write!(f, "{}", v),
+            Constant::Float(v) => write!(f, "{}", v),
+            Constant::Char(v) => write!(f, "'{}'", v),
+            Constant::String(v) => write!(f, "\"{}\"", v),
+        }
+    }
+}
+
+/// An instruction for a stack machine where every expression leaves exactly one value on the stack.
+///
+/// Locals are numbered per call frame and addresses index into the whole program.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Instr {
+    Push(Constant),
+    Pop,
+    Dup,
+    Load(usize),
+    Store(usize),
+    /// Push the function at `address`.
+    /// One value is popped for every capture and stored in that local whenever the function is called.
+    Function {
+        address: usize,
+        captures: Vec<usize>,
+    },
+    Unary(UnaryOperator),
+    Binary(BinaryOperator),
+    Tuple(usize),
+    Array(usize),
+    Struct {
+        name: String,
+        fields: Vec<String>,
+    },
+    Enum {
+        member: String,
+        fields: Vec<String>,
+    },
+    Field(String),
+    Index,
+    /// Pop a value and push whether it matches the pattern
+    Test(Pattern),
+    /// Call with 0 or 1 arguments. The argument is stored in local 0 of the new frame
+    Call(usize),
+    Return,
+    Jump(usize),
+    JumpIfFalse(usize),
+    JumpIfTrue(usize),
+    Print,
+    Fail(String),
+    Halt,
+}
+
+impl Instr {
+    fn stack_effect(&self) -> isize {
+        match self {
+            Instr::Push(_) | Instr::Dup | Instr::Load(_) => 1,
+            Instr::Pop | Instr::Store(_) | Instr::Binary(_) | Instr::Index => -1,
+            Instr::Function { captures, .. } => 1 - captures.len() as isize,
+            Instr::Tuple(count) | Instr::Array(count) => 1 - *count as isize,
+            Instr::Struct { fields, .. } | Instr::Enum { fields, .. } => 1 - fields.len() as isize,
+            Instr::Call(arguments) => -(*arguments as isize),
+            Instr::JumpIfFalse(_) | Instr::JumpIfTrue(_) | Instr::Return | Instr::Halt => -1,
+            Instr::Unary(_)
+            | Instr::Field(_)
+            | Instr::Test(_)
+            | Instr::Jump(_)
+            | Instr::Print
+            | Instr::Fail(_) => 0,
+        }
+    }
+}
+
+impl Display for Instr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Instr::Push(constant) => write!(f, "push {}", constant),
+            Instr::Pop => write!(f, "pop"),
+            Instr::Dup => write!(f, "dup"),
+            Instr::Load(slot) => write!(f, "load {}", slot),
+            Instr::Store(slot) => write!(f, "store {}", slot),
+            Instr::Function { address, captures } => write!(
+                f,
+                "function {} [{}]",
+                address,
+                captures
+                    .iter()
+                    .map(|c| c.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+            Instr::Unary(operator) => write!(f, "unary {}", operator),
+            Instr::Binary(operator) => write!(f, "binary {}", operator),
+            Instr::Tuple(count) => write!(f, "tuple {}", count),
+            Instr::Array(count) => write!(f, "array {}", count),
+            Instr::Struct { name, fields } => {
+                write!(f, "struct {} {{{}}}", name, fields.join(", "))
+            }
+            Instr::Enum { member, fields } => {
+                write!(f, "enum {} {{{}}}", member, fields.join(", "))
+            }
+            Instr::Field(name) => write!(f, "field {}", name),
+            Instr::Index => write!(f, "index"),
+            Instr::Test(pattern) => write!(f, "test {}", pattern),
+            Instr::Call(arguments) => write!(f, "call {}", arguments),
+            Instr::Return => write!(f, "return"),
+            Instr::Jump(address) => write!(f, "jump {}", address),
+            Instr::JumpIfFalse(address) => write!(f, "jump_if_false {}", address),
+            Instr::JumpIfTrue(address) => write!(f, "jump_if_true {}", address),
+            Instr::Print => write!(f, "print"),
+            Instr::Fail(message) => write!(f, "fail \"{}\"", message),
+            Instr::Halt => write!(f, "halt"),
+        }
+    }
+}
+
+/// Lower a type checked program to bytecode.
+///
+/// The top level code comes first and ends with `Halt`, followed by the code of every function.
+pub fn compile(statement: &TypedStatement) -> Result<Vec<Instr>, String> {
+    let mut collector = FunctionCollector::default();
+    collector.visit_statement(statement);
+
+    let mut emitter = Emitter {
+        chunks: vec![Vec::new(); collector.functions.len() + 1],
+        functions: HashMap::new(),
+        states: Vec::new(),
+    };
+
+    for (index, (name, _, _)) in collector.functions.iter().enumerate() {
+        if emitter.functions.insert(name.clone(), index + 1).is_some() {
+            return Err(format!("Function '{}' is declared more than once", name));
+        }
+    }
+
+    emitter.states.push(FunctionState::new(false, false));
+    emitter.statement(statement)?;
+    emitter.emit(Instr::Halt);
+    emitter.chunks[0] = emitter.states.pop().expect("main state").code;
+
+    for (index, (_, param, body)) in collector.functions.into_iter().enumerate() {
+        emitter.states.push(FunctionState::new(true, false));
+        emitter.function_body(param, &body)?;
+        emitter.chunks[index + 1] = emitter.states.pop().expect("function state").code;
+    }
+
+    Ok(link(emitter.chunks))
+}
+
+pub fn disassemble(instructions: &[Instr]) -> String {
+    instructions
+        .iter()
+        .enumerate()
+        .map(|(address, instr)| format!("{:04} {}", address, instr))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+// Jumps are relative to their chunk and functions refer to chunk indices until the chunks are laid out
+fn link(chunks: Vec<Vec<Instr>>) -> Vec<Instr> {
+    let mut bases = Vec::with_capacity(chunks.len());
+    let mut address = 0;
+
+    for chunk in &chunks {
+        bases.push(address);
+        address += chunk.len();
+    }
+
+    let mut instructions = Vec::with_capacity(address);
+
+    for (chunk, base) in chunks.into_iter().zip(&bases) {
+        for instr in chunk {
+            instructions.push(match instr {
+                Instr::Jump(target) => Instr::Jump(target + base),
+                Instr::JumpIfFalse(target) => Instr::JumpIfFalse(target + base),
+                Instr::JumpIfTrue(target) => Instr::JumpIfTrue(target + base),
+                Instr::Function { address, captures } => Instr::Function {
+                    address: bases[address],
+                    captures,
+                },
+                instr => instr,
+            });
+        }
+    }
+
+    instructions
+}
+
+#[derive(Default)]
+struct FunctionCollector {
+    functions: Vec<(String, Option<String>, TypedExpression)>,
+}
+
+impl TypedVisitor for FunctionCollector {
+    fn visit_statement(&mut self, statement: &TypedStatement) {
+        match statement {
+            TypedStatement::FunctionDeclaration {
+                identifier,
+                param,
+                body: Some(body),
+                ..
+            } => {
+                self.functions.push((
+                    identifier.name().to_string(),
+                    param.as_ref().map(|p| p.identifier.clone()),
+                    body.clone(),
+                ));
+
+                walk_statement(self, statement);
+            }
+            // Their functions are only reachable through static member access
+            TypedStatement::ProtocolDeclaration { .. }
+            | TypedStatement::ImplementationDeclaration { .. } => {}
+            _ => walk_statement(self, statement),
+        }
+    }
+}
+
+struct LoopState {
+    start: usize,
+    depth: isize,
+    result: usize,
+    breaks: Vec<usize>,
+}
+
+struct FunctionState {
+    code: Vec<Instr>,
+    scopes: Vec<HashMap<String, usize>>,
+    slots: usize,
+    depth: isize,
+    loops: Vec<LoopState>,
+    captures: Vec<(usize, usize)>,
+    is_function: bool,
+    is_closure: bool,
+}
+
+impl FunctionState {
+    fn new(is_function: bool, is_closure: bool) -> Self {
+        Self {
+            code: Vec::new(),
+            scopes: vec![HashMap::new()],
+            slots: 0,
+            depth: 0,
+            loops: Vec::new(),
+            captures: Vec::new(),
+            is_function,
+            is_closure,
+        }
+    }
+
+    fn allocate(&mut self) -> usize {
+        self.slots += 1;
+        self.slots - 1
+    }
+
+    fn bind(&mut self, identifier: &str) -> usize {
+        let slot = self.allocate();
+
+        self.scopes
+            .last_mut()
+            .expect("at least one scope")
+            .insert(identifier.to_string(), slot);
+
+        slot
+    }
+
+    fn lookup(&self, identifier: &str) -> Option<usize> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(identifier).copied())
+    }
+}
+
+struct Emitter {
+    chunks: Vec<Vec<Instr>>,
+    functions: HashMap<String, usize>,
+    states: Vec<FunctionState>,
+}
+
+impl Emitter {
+    fn state(&mut self) -> &mut FunctionState {
+        self.states
+            .last_mut()
+            .expect("a function is being compiled")
+    }
+
+    fn emit(&mut self, instr: Instr) -> usize {
+        let state = self.state();
+        state.depth += instr.stack_effect();
+        state.code.push(instr);
+        state.code.len() - 1
+    }
+
+    fn here(&mut self) -> usize {
+        self.state().code.len()
+    }
+
+    fn patch(&mut self, jump: usize) {
+        let here = self.here();
+
+        match &mut self.state().code[jump] {
+            Instr::Jump(target) | Instr::JumpIfFalse(target) | Instr::JumpIfTrue(target) => {
+                *target = here
+            }
+            instr => unreachable!("Only jumps can be patched, found {}", instr),
+        }
+    }
+
+    fn push_scope(&mut self) {
+        self.state().scopes.push(HashMap::new());
+    }
+
+    fn pop_scope(&mut self) {
+        self.state().scopes.pop();
+    }
+
+    fn resolve(&mut self, identifier: &str) -> Option<usize> {
+        self.resolve_at(self.states.len() - 1, identifier)
+    }
+
+    // Closures capture by value, so a variable from an enclosing function gets a local copy
+    fn resolve_at(&mut self, level: usize, identifier: &str) -> Option<usize> {
+        if let Some(slot) = self.states[level].lookup(identifier) {
+            return Some(slot);
+        }
+
+        if level == 0 || !self.states[level].is_closure {
+            return None;
+        }
+
+        let outer = self.resolve_at(level - 1, identifier)?;
+        let state = &mut self.states[level];
+        let inner = state.allocate();
+
+        state.scopes[0].insert(identifier.to_string(), inner);
+        state.captures.push((outer, inner));
+
+        Some(inner)
+    }
+
+    fn function_body(
+        &mut self,
+        param: Option<String>,
+        body: &TypedExpression,
+    ) -> Result<(), String> {
+        if let Some(param) = param {
+            self.state().bind(&param);
+        }
+
+        self.expression(body)?;
+        self.emit(Instr::Return);
+        Ok(())
+    }
+
+    fn statement(&mut self, statement: &TypedStatement) -> Result<(), String> {
+        match statement {
+            TypedStatement::Program { statements } => self.sequence(statements),
+            TypedStatement::Semi(statement) => {
+                self.statement(statement)?;
+                self.emit(Instr::Pop);
+                self.emit(Instr::Push(Constant::Void));
+                Ok(())
+            }
+            TypedStatement::Expression(expression) => self.expression(expression),
+            // Declarations produce no code, functions are compiled separately
+            _ => {
+                self.emit(Instr::Push(Constant::Void));
+                Ok(())
+            }
+        }
+    }
+
+    fn sequence(&mut self, statements: &[TypedStatement]) -> Result<(), String> {
+        let Some((last, rest)) = statements.split_last() else {
+            self.emit(Instr::Push(Constant::Void));
+            return Ok(());
+        };
+
+        for statement in rest {
+            match statement {
+                TypedStatement::Semi(statement) => self.statement(statement)?,
+                statement => self.statement(statement)?,
+            }
+
+            self.emit(Instr::Pop);
+        }
+
+        self.statement(last)
+    }
+
+    fn block(&mut self, block: &Block) -> Result<(), String> {
+        self.push_scope();
+        self.sequence(&block.statements)?;
+        self.pop_scope();
+        Ok(())
+    }
+
+    fn expression(&mut self, expression: &TypedExpression) -> Result<(), String> {
+        match expression {
+            TypedExpression::VariableDeclaration {
+                pattern,
+                initializer,
+                ..
+            } => {
+                match initializer {
+                    Some(initializer) => self.expression(initializer)?,
+                    None => {
+                        self.emit(Instr::Push(Constant::Void));
+                    }
+                }
+
+                match pattern {
+                    Pattern::Variable(identifier) => {
+                        self.emit(Instr::Dup);
+                        let slot = self.state().bind(identifier);
+                        self.emit(Instr::Store(slot));
+                    }
+                    Pattern::Wildcard => {}
+                    pattern => {
+                        return Err(format!(
+                            "Cannot declare variables with the pattern '{}' in bytecode",
+                            pattern
+                        ))
+                    }
+                }
+
+                Ok(())
+            }
+            TypedExpression::If {
+                condition,
+                true_expression,
+                false_expression,
+                ..
+            } => self.if_(condition, true_expression, false_expression.as_deref()),
+            TypedExpression::Match {
+                expression,
+                decision_tree,
+                ..
+            } => self.match_(expression, decision_tree),
+            TypedExpression::Assignment {
+                member,
+                initializer,
+                ..
+            } => {
+                let Member::Identifier { symbol, .. } = member.as_ref() else {
+                    return Err(format!("Cannot assign to '{}' in bytecode", member));
+                };
+
+                self.expression(initializer)?;
+
+                let slot = self
+                    .resolve(symbol)
+                    .ok_or(format!("Unknown variable '{}'", symbol))?;
+
+                self.emit(Instr::Dup);
+                self.emit(Instr::Store(slot));
+                Ok(())
+            }
+            TypedExpression::Member(member) => self.member(member),
+            TypedExpression::Literal(literal, _) => self.literal(literal),
+            TypedExpression::Tuple { elements, .. } => {
+                for element in elements {
+                    self.expression(element)?;
+                }
+
+                self.emit(Instr::Tuple(elements.len()));
+                Ok(())
+            }
+            TypedExpression::Record { .. } => {
+                Err("Records are not supported in bytecode".to_string())
+            }
+            TypedExpression::Closure { param, body, .. } => {
+                let index = self.chunks.len();
+                self.chunks.push(Vec::new());
+
+                self.states.push(FunctionState::new(true, true));
+                self.function_body(param.as_ref().map(|p| p.identifier.clone()), body)?;
+
+                let state = self.states.pop().expect("closure state");
+                self.chunks[index] = state.code;
+
+                for (outer, _) in &state.captures {
+                    self.emit(Instr::Load(*outer));
+                }
+
+                self.emit(Instr::Function {
+                    address: index,
+                    captures: state.captures.iter().map(|(_, inner)| *inner).collect(),
+                });
+
+                Ok(())
+            }
+            TypedExpression::Call {
+                callee, argument, ..
+            } => {
+                self.expression(callee)?;
+
+                match argument {
+                    Some(argument) => {
+                        self.expression(argument)?;
+                        self.emit(Instr::Call(1));
+                    }
+                    None => {
+                        self.emit(Instr::Call(0));
+                    }
+                }
+
+                Ok(())
+            }
+            TypedExpression::Index {
+                callee, argument, ..
+            } => {
+                self.expression(callee)?;
+                self.expression(argument)?;
+                self.emit(Instr::Index);
+                Ok(())
+            }
+            TypedExpression::Unary {
+                operator,
+                expression,
+                ..
+            } => {
+                self.expression(expression)?;
+                self.emit(Instr::Unary(operator.clone()));
+                Ok(())
+            }
+            TypedExpression::Binary {
+                left,
+                operator,
+                right,
+                ..
+            } => self.binary(left, operator, right),
+            TypedExpression::Cast { .. } => Err("Casts are not supported in bytecode".to_string()),
+            TypedExpression::Block(block) => self.block(block),
+            #[cfg(feature = "interpreter")]
+            TypedExpression::Print { value } => {
+                self.expression(value)?;
+                self.emit(Instr::Print);
+                Ok(())
+            }
+            #[cfg(feature = "interpreter")]
+            TypedExpression::Drop { identifier, .. } => {
+                for scope in self.state().scopes.iter_mut().rev() {
+                    if scope.remove(identifier).is_some() {
+                        break;
+                    }
+                }
+
+                self.emit(Instr::Push(Constant::Void));
+                Ok(())
+            }
+            TypedExpression::Loop { body, .. } => {
+                let result = self.state().allocate();
+                self.begin_loop(result);
+
+                self.expression(body)?;
+                self.emit(Instr::Pop);
+                let start = self.current_loop().start;
+                self.emit(Instr::Jump(start));
+
+                self.end_loop(result);
+                Ok(())
+            }
+            TypedExpression::While {
+                condition,
+                body,
+                else_body,
+                ..
+            } => {
+                let result = self.state().allocate();
+                self.begin_loop(result);
+
+                self.expression(condition)?;
+                let exit = self.emit(Instr::JumpIfFalse(0));
+
+                self.expression(body)?;
+                self.emit(Instr::Pop);
+                let start = self.current_loop().start;
+                self.emit(Instr::Jump(start));
+
+                self.patch(exit);
+
+                // The else body runs when the condition fails, so a break inside it belongs to an outer loop
+                let state = self.state().loops.pop().expect("loop state");
+
+                match else_body {
+                    Some(else_body) => self.expression(else_body)?,
+                    None => {
+                        self.emit(Instr::Push(Constant::Void));
+                    }
+                }
+
+                self.emit(Instr::Store(result));
+                self.state().loops.push(state);
+                self.end_loop(result);
+                Ok(())
+            }
+            TypedExpression::For { .. } => {
+                Err("For loops are not supported in bytecode".to_string())
+            }
+            TypedExpression::Break(value) => {
+                let depth = self.state().depth;
+
+                let Some(loop_) = self.state().loops.last() else {
+                    return Err("Cannot break outside of a loop".to_string());
+                };
+
+                let (loop_depth, result) = (loop_.depth, loop_.result);
+
+                match value {
+                    Some(value) => self.expression(value)?,
+                    None => {
+                        self.emit(Instr::Push(Constant::Void));
+                    }
+                }
+
+                self.emit(Instr::Store(result));
+
+                for _ in loop_depth..depth {
+                    self.emit(Instr::Pop);
+                }
+
+                let jump = self.emit(Instr::Jump(0));
+                self.current_loop().breaks.push(jump);
+                self.state().depth = depth + 1;
+                Ok(())
+            }
+            TypedExpression::Continue => {
+                let depth = self.state().depth;
+
+                let Some(loop_) = self.state().loops.last() else {
+                    return Err("Cannot continue outside of a loop".to_string());
+                };
+
+                let (loop_depth, start) = (loop_.depth, loop_.start);
+
+                for _ in loop_depth..depth {
+                    self.emit(Instr::Pop);
+                }
+
+                self.emit(Instr::Jump(start));
+                self.state().depth = depth + 1;
+                Ok(())
+            }
+            TypedExpression::Return(value) => {
+                let depth = self.state().depth;
+
+                if !self.state().is_function {
+                    return Err("Cannot return outside of a function".to_string());
+                }
+
+                match value {
+                    Some(value) => self.expression(value)?,
+                    None => {
+                        self.emit(Instr::Push(Constant::Void));
+                    }
+                }
+
+                self.emit(Instr::Return);
+                self.state().depth = depth + 1;
+                Ok(())
+            }
+        }
+    }
+
+    fn current_loop(&mut self) -> &mut LoopState {
+        self.state().loops.last_mut().expect("inside a loop")
+    }
+
+    fn begin_loop(&mut self, result: usize) {
+        let start = self.here();
+        let depth = self.state().depth;
+
+        self.state().loops.push(LoopState {
+            start,
+            depth,
+            result,
+            breaks: Vec::new(),
+        });
+
+        self.push_scope();
+    }
+
+    fn end_loop(&mut self, result: usize) {
+        self.pop_scope();
+
+        let loop_ = self.state().loops.pop().expect("loop state");
+
+        for jump in loop_.breaks {
+            self.patch(jump);
+        }
+
+        self.state().depth = loop_.depth;
+        self.emit(Instr::Load(result));
+    }
+
+    fn if_(
+        &mut self,
+        condition: &TypedExpression,
+        true_expression: &TypedExpression,
+        false_expression: Option<&TypedExpression>,
+    ) -> Result<(), String> {
+        self.expression(condition)?;
+        let else_jump = self.emit(Instr::JumpIfFalse(0));
+        let depth = self.state().depth;
+
+        // Without an else branch the value is optional, unless there is no value at all
+        let is_optional =
+            false_expression.is_none() && !matches!(true_expression.get_type(), Type::Void);
+
+        self.push_scope();
+        self.expression(true_expression)?;
+        self.pop_scope();
+
+        if is_optional {
+            self.emit(Instr::Enum {
+                member: "Some".to_string(),
+                fields: vec!["v".to_string()],
+            });
+        }
+
+        let end_jump = self.emit(Instr::Jump(0));
+        self.state().depth = depth;
+        self.patch(else_jump);
+
+        self.push_scope();
+
+        match false_expression {
+            Some(false_expression) => self.expression(false_expression)?,
+            None if is_optional => {
+                self.emit(Instr::Enum {
+                    member: "None".to_string(),
+                    fields: vec![],
+                });
+            }
+            None => {
+                self.emit(Instr::Push(Constant::Void));
+            }
+        }
+
+        self.pop_scope();
+        self.patch(end_jump);
+        Ok(())
+    }
+
+    fn match_(
+        &mut self,
+        expression: &TypedExpression,
+        decision_tree: &Decision,
+    ) -> Result<(), String> {
+        self.push_scope();
+        self.expression(expression)?;
+
+        let matchee = self.state().allocate();
+        self.emit(Instr::Store(matchee));
+
+        let depth = self.state().depth;
+        let mut ends = Vec::new();
+        self.decision(decision_tree, matchee, &mut ends)?;
+
+        for end in ends {
+            self.patch(end);
+        }
+
+        self.state().depth = depth + 1;
+        self.pop_scope();
+        Ok(())
+    }
+
+    // Every path through the tree either fails or leaves the value of an arm and jumps to the end of the match
+    fn decision(
+        &mut self,
+        decision: &Decision,
+        value: usize,
+        ends: &mut Vec<usize>,
+    ) -> Result<(), String> {
+        let depth = self.state().depth;
+
+        match decision {
+            Decision::Success { expression, .. } => {
+                self.expression(expression)?;
+                ends.push(self.emit(Instr::Jump(0)));
+                self.state().depth = depth;
+            }
+            Decision::Failure { error_message } => {
+                self.emit(Instr::Fail(error_message.clone()));
+            }
+            Decision::Guard {
+                condition,
+                consequence,
+                alternative,
+                ..
+            } => {
+                self.expression(condition)?;
+                let alternative_jump = self.emit(Instr::JumpIfFalse(0));
+                self.decision(consequence, value, ends)?;
+                self.patch(alternative_jump);
+                self.decision(alternative, value, ends)?;
+            }
+            Decision::Switch {
+                variable,
+                cases,
+                fallback,
+                ..
+            } => {
+                for case in cases {
+                    self.push_scope();
+
+                    for argument in &case.arguments {
+                        if let Accessor::Expression(expression) = &argument.accessor {
+                            self.expression(expression)?;
+                            let slot = self.state().bind(&argument.identifier);
+                            self.emit(Instr::Store(slot));
+                        }
+                    }
+
+                    self.emit(Instr::Load(value));
+                    self.emit(Instr::Test(case.pattern.clone()));
+                    let next_case = self.emit(Instr::JumpIfFalse(0));
+
+                    let mut bindings = Vec::new();
+                    pattern_bindings(&case.pattern, &mut Vec::new(), &mut bindings);
+
+                    for (identifier, path) in bindings {
+                        self.emit(Instr::Load(value));
+
+                        for field in path {
+                            self.emit(Instr::Field(field));
+                        }
+
+                        let slot = self.state().bind(&identifier);
+                        self.emit(Instr::Store(slot));
+                    }
+
+                    // The field may only exist on the member the case matched
+                    let switch_value = match &variable.accessor {
+                        Accessor::Environment => value,
+                        Accessor::Expression(expression) => {
+                            self.expression(expression)?;
+                            let slot = self.state().bind(&variable.identifier);
+                            self.emit(Instr::Store(slot));
+                            slot
+                        }
+                    };
+
+                    self.decision(&case.body, switch_value, ends)?;
+                    self.pop_scope();
+                    self.patch(next_case);
+                }
+
+                self.decision(fallback, value, ends)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn binary(
+        &mut self,
+        left: &TypedExpression,
+        operator: &BinaryOperator,
+        right: &TypedExpression,
+    ) -> Result<(), String> {
+        self.expression(left)?;
+
+        let short_circuit = match operator {
+            BinaryOperator::LogicalAnd => Some(Instr::JumpIfFalse(0)),
+            BinaryOperator::LogicalOr => Some(Instr::JumpIfTrue(0)),
+            BinaryOperator::Range | BinaryOperator::RangeInclusive => {
+                return Err("Ranges are not supported in bytecode".to_string())
+            }
+            _ => None,
+        };
+
+        let Some(jump) = short_circuit else {
+            self.expression(right)?;
+            self.emit(Instr::Binary(operator.clone()));
+            return Ok(());
+        };
+
+        // The left value is the result if it decides the outcome
+        self.emit(Instr::Dup);
+        let end = self.emit(jump);
+        self.emit(Instr::Pop);
+        self.expression(right)?;
+        self.patch(end);
+        Ok(())
+    }
+
+    fn member(&mut self, member: &Member) -> Result<(), String> {
+        match member {
+            Member::Identifier { symbol, .. } => {
+                if let Some(slot) = self.resolve(symbol) {
+                    self.emit(Instr::Load(slot));
+                    return Ok(());
+                }
+
+                let Some(index) = self.functions.get(symbol).copied() else {
+                    return Err(format!("Unknown variable '{}'", symbol));
+                };
+
+                self.emit(Instr::Function {
+                    address: index,
+                    captures: vec![],
+                });
+
+                Ok(())
+            }
+            Member::MemberAccess { object, symbol, .. } => {
+                self.expression(object)?;
+                self.emit(Instr::Field(symbol.clone()));
+                Ok(())
+            }
+            Member::StaticMemberAccess { .. } => Err(format!(
+                "Static member access '{}' is not supported in bytecode",
+                member
+            )),
+        }
+    }
+
+    fn literal(&mut self, literal: &Literal) -> Result<(), String> {
+        let constant = match literal {
+            Literal::Void => Constant::Void,
+            Literal::Unit => Constant::Unit,
+            Literal::Int(v) => Constant::Int(*v),
+            Literal::UInt(v) => Constant::UInt(*v),
+            Literal::Float(v) => Constant::Float(*v),
+            Literal::String(v) => Constant::String(v.clone()),
+            Literal::Char(v) => Constant::Char(*v),
+            Literal::Bool(v) => Constant::Bool(*v),
+            Literal::Array { values, .. } => {
+                for value in values {
+                    self.expression(value)?;
+                }
+
+                self.emit(Instr::Array(values.len()));
+                return Ok(());
+            }
+            Literal::Struct {
+                type_annotation,
+                field_initializers,
+                spread,
+                ..
+            } => {
+                if spread.is_some() {
+                    return Err(
+                        "Spreads in struct literals are not supported in bytecode".to_string()
+                    );
+                }
+
+                let mut fields = Vec::new();
+
+                for (index, field_initializer) in field_initializers.iter().enumerate() {
+                    self.expression(&field_initializer.initializer)?;
+
+                    fields.push(
+                        field_initializer
+                            .identifier
+                            .clone()
+                            .unwrap_or_else(|| index.to_string()),
+                    );
+                }
+
+                self.emit(Instr::Struct {
+                    name: type_annotation.name(),
+                    fields,
+                });
+
+                return Ok(());
+            }
+            Literal::Enum {
+                member,
+                field_initializers,
+                ..
+            } => {
+                let mut fields = Vec::new();
+
+                if let EnumMemberFieldInitializers::Named(field_initializers) = field_initializers {
+                    // Sort the fields so the same program always compiles to the same bytecode
+                    let mut field_initializers = field_initializers.iter().collect::<Vec<_>>();
+                    field_initializers.sort_by_key(|(identifier, _)| *identifier);
+
+                    for (identifier, initializer) in field_initializers {
+                        self.expression(initializer)?;
+                        fields.push(identifier.clone());
+                    }
+                }
+
+                self.emit(Instr::Enum {
+                    member: member.clone(),
+                    fields,
+                });
+
+                return Ok(());
+            }
+        };
+
+        self.emit(Instr::Push(constant));
+        Ok(())
+    }
+}
+
+fn pattern_bindings(
+    pattern: &Pattern,
+    path: &mut Vec<String>,
+    bindings: &mut Vec<(String, Vec<String>)>,
+) {
+    match pattern {
+        Pattern::Variable(identifier) => bindings.push((identifier.clone(), path.clone())),
+        Pattern::Constructor(Constructor::Struct { field_patterns, .. }) => {
+            for field_pattern in field_patterns {
+                path.push(field_pattern.identifier.clone());
+                pattern_bindings(&field_pattern.pattern, path, bindings);
+                path.pop();
+            }
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,1663 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    type_checker::{
+        ast::{
+            BinaryOperator, Block, EnumMember, EnumMemberFieldInitializers, Literal, Member,
+            StructField, Typed, TypedExpression, TypedStatement, UnaryOperator,
+        },
+        decision_tree::{Accessor, Case, Constructor, Decision, Pattern, Variable},
+        Enum, Struct, Type,
+    },
+    types::TypeIdentifier,
+};
+
+const INCLUDES: [&str; 5] = ["stdbool.h", "stdint.h", "stdio.h", "stdlib.h", "string.h"];
+
+const KEYWORDS: [&str; 37] = [
+    "auto", "bool", "break", "case", "char", "const", "continue", "default", "do", "double",
+    "else", "enum", "extern", "float", "for", "goto", "if", "inline", "int", "long", "main",
+    "register", "restrict", "return", "short", "signed", "sizeof", "static", "struct", "switch",
+    "typedef", "union", "unsigned", "void", "volatile", "while", "exit",
+];
+
+/// Transpile a type checked program to C99.
+///
+/// Structs become C structs and enums become tagged unions with a `tag` and an `as` union of their members.
+/// Functions with several parameters are uncurried, but closures and generics are not supported.
+/// Top level statements are placed in `main`, which is only emitted if there are any.
+pub fn emit(statement: &TypedStatement) -> Result<String, String> {
+    let statements = match statement {
+        TypedStatement::Program { statements } => statements.as_slice(),
+        statement => std::slice::from_ref(statement),
+    };
+
+    // A declaration ending in a match is terminated by its semicolon
+    let statements = statements
+        .iter()
+        .map(|statement| match statement {
+            TypedStatement::Semi(declaration)
+                if !matches!(
+                    declaration.as_ref(),
+                    TypedStatement::Semi(_) | TypedStatement::Expression(_)
+                ) =>
+            {
+                declaration.as_ref()
+            }
+            statement => statement,
+        })
+        .collect::<Vec<_>>();
+
+    let mut emitter = CEmitter::default();
+
+    for statement in &statements {
+        if let TypedStatement::FunctionDeclaration {
+            identifier, body, ..
+        } = statement
+        {
+            let arity = body
+                .as_ref()
+                .map(|body| flatten_function(statement_param(statement), body).0.len())
+                .unwrap_or(0);
+
+            emitter.arities.insert(c_type_name(identifier)?, arity);
+        }
+    }
+
+    let mut main = Body::default();
+
+    for statement in statements {
+        match statement {
+            TypedStatement::StructDeclaration {
+                type_identifier,
+                fields,
+                ..
+            } => emitter.struct_declaration(type_identifier, fields)?,
+            TypedStatement::EnumDeclaration {
+                type_identifier,
+                shared_fields,
+                members,
+                ..
+            } => emitter.enum_declaration(type_identifier, shared_fields, members)?,
+            TypedStatement::FunctionDeclaration {
+                identifier,
+                body: Some(body),
+                ..
+            } => emitter.function(identifier, statement_param(statement), body)?,
+            TypedStatement::ImplementationDeclaration { .. } => {
+                return Err("Implementations are not supported in C".to_string())
+            }
+            TypedStatement::Semi(_) | TypedStatement::Expression(_) => {
+                std::mem::swap(&mut emitter.body, &mut main);
+                emitter.statement(statement, &Sink::Discard)?;
+                std::mem::swap(&mut emitter.body, &mut main);
+            }
+            // These have no representation at runtime
+            _ => {}
+        }
+    }
+
+    Ok(emitter.finish(main))
+}
+
+fn statement_param(statement: &TypedStatement) -> Option<(String, Type)> {
+    match statement {
+        TypedStatement::FunctionDeclaration {
+            param: Some(param), ..
+        } => Some((param.identifier.clone(), *param.type_.clone())),
+        _ => None,
+    }
+}
+
+// Functions with several parameters are parsed as a function returning closures
+fn flatten_function(
+    param: Option<(String, Type)>,
+    body: &TypedExpression,
+) -> (Vec<(String, Type)>, &TypedExpression, Type) {
+    let mut params = param.into_iter().collect::<Vec<_>>();
+    let mut body = body;
+    let mut return_type = body.get_type();
+
+    while let TypedExpression::Closure {
+        param: Some(param),
+        return_type: closure_return_type,
+        body: closure_body,
+        ..
+    } = body
+    {
+        if params.is_empty() {
+            break;
+        }
+
+        params.push((param.identifier.clone(), *param.type_.clone()));
+        return_type = closure_return_type.clone();
+        body = closure_body;
+    }
+
+    (params, body, return_type)
+}
+
+/// The fields of a tuple struct are named by their index, which isn't a C identifier
+fn c_field_name(field: &str) -> String {
+    if field.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{}", field)
+    } else {
+        field.to_string()
+    }
+}
+
+fn c_type_name(type_identifier: &TypeIdentifier) -> Result<String, String> {
+    match type_identifier {
+        TypeIdentifier::Type(name) => Ok(name.clone()),
+        type_identifier => Err(format!(
+            "Generic type '{}' is not supported in C",
+            type_identifier
+        )),
+    }
+}
+
+/// The C type of a value of this type, or `None` if the type has no values
+fn c_type(type_: &Type) -> Result<Option<String>, String> {
+    let c_type = match type_ {
+        Type::Substitution { actual_type, .. } => return c_type(actual_type),
+        Type::Literal { type_, .. } => return c_type(type_),
+        Type::Void | Type::Unit => return Ok(None),
+        Type::Int => "int64_t".to_string(),
+        Type::UInt => "uint64_t".to_string(),
+        Type::Float => "double".to_string(),
+        Type::Bool => "bool".to_string(),
+        Type::Char => "uint32_t".to_string(),
+        Type::String => "const char *".to_string(),
+        Type::Struct(Struct {
+            type_identifier, ..
+        })
+        | Type::Enum(Enum {
+            type_identifier, ..
+        }) => c_type_name(type_identifier)?,
+        Type::EnumMember(member) => c_type_name(&member.enum_name)?,
+        type_ => return Err(format!("The type {} is not supported in C", type_)),
+    };
+
+    Ok(Some(c_type))
+}
+
+/// The type without the substitutions and literal values the type checker wraps it in
+fn underlying(type_: &Type) -> Type {
+    match type_ {
+        Type::Substitution { actual_type, .. } => underlying(actual_type),
+        Type::Literal { type_, .. } => underlying(type_),
+        type_ => type_.clone(),
+    }
+}
+
+fn c_value_type(type_: &Type) -> Result<String, String> {
+    c_type(type_)?.ok_or(format!("The type {} has no value in C", type_))
+}
+
+fn tag(enum_name: &str, member: &str) -> String {
+    format!("{}_{}", enum_name, member)
+}
+
+// Patterns name enum members with the enum name in front
+fn member_name(name: &str) -> &str {
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+fn escape(string: &str) -> String {
+    let mut escaped = String::new();
+
+    for c in string.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => escaped.push_str(&format!("\\{:03o}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+
+    format!("\"{}\"", escaped)
+}
+
+fn is_plain(value: &str) -> bool {
+    value
+        .chars()
+        .all(|c| c.is_alphanumeric() || c == '_' || c == '.')
+}
+
+enum Sink {
+    Discard,
+    Assign(String),
+    Return,
+}
+
+struct LoopLabel {
+    name: String,
+    result: Option<String>,
+    continued: bool,
+}
+
+#[derive(Default)]
+struct Body {
+    lines: Vec<String>,
+    indent: usize,
+    scopes: Vec<HashMap<String, String>>,
+    declared: HashSet<String>,
+    loops: Vec<LoopLabel>,
+    matchees: Vec<(TypedExpression, String)>,
+    in_guard: bool,
+    is_function: bool,
+}
+
+#[derive(Default)]
+struct CEmitter {
+    types: Vec<(String, Vec<String>, String)>,
+    prototypes: Vec<String>,
+    functions: Vec<String>,
+    arities: HashMap<String, usize>,
+    body: Body,
+    counter: usize,
+}
+
+impl CEmitter {
+    fn finish(self, main: Body) -> String {
+        let mut output = INCLUDES
+            .iter()
+            .map(|include| format!("#include <{}>\n", include))
+            .collect::<String>();
+
+        for (_, _, definition) in self.sorted_types() {
+            output.push('\n');
+            output.push_str(definition);
+        }
+
+        if !self.prototypes.is_empty() {
+            output.push('\n');
+
+            for prototype in &self.prototypes {
+                output.push_str(&format!("{};\n", prototype));
+            }
+        }
+
+        for function in &self.functions {
+            output.push('\n');
+            output.push_str(function);
+        }
+
+        if !main.lines.is_empty() {
+            output.push_str("\nint main(void) {\n");
+
+            for line in main.lines {
+                output.push_str(&format!("    {}\n", line));
+            }
+
+            output.push_str("    return 0;\n}\n");
+        }
+
+        output
+    }
+
+    // Types containing other types by value must come after them
+    fn sorted_types(&self) -> Vec<&(String, Vec<String>, String)> {
+        fn visit<'a>(
+            name: &str,
+            types: &'a [(String, Vec<String>, String)],
+            visited: &mut HashSet<String>,
+            sorted: &mut Vec<&'a (String, Vec<String>, String)>,
+        ) {
+            if !visited.insert(name.to_string()) {
+                return;
+            }
+
+            let Some(type_) = types.iter().find(|(n, _, _)| n == name) else {
+                return;
+            };
+
+            for dependency in &type_.1 {
+                visit(dependency, types, visited, sorted);
+            }
+
+            sorted.push(type_);
+        }
+
+        let mut visited = HashSet::new();
+        let mut sorted = Vec::new();
+
+        for (name, _, _) in &self.types {
+            visit(name, &self.types, &mut visited, &mut sorted);
+        }
+
+        sorted
+    }
+
+    fn fresh(&mut self, prefix: &str) -> String {
+        self.counter += 1;
+        format!("{}_{}", prefix, self.counter)
+    }
+
+    fn line(&mut self, line: impl AsRef<str>) {
+        let indent = "    ".repeat(self.body.indent);
+        self.body.lines.push(format!("{}{}", indent, line.as_ref()));
+    }
+
+    fn indented<T>(&mut self, f: impl FnOnce(&mut Self) -> T) -> T {
+        self.body.indent += 1;
+        self.body.scopes.push(HashMap::new());
+        let result = f(self);
+        self.body.scopes.pop();
+        self.body.indent -= 1;
+        result
+    }
+
+    fn declare(&mut self, identifier: &str) -> String {
+        let mut name = identifier.to_string();
+        let mut suffix = 0;
+
+        while self.body.declared.contains(&name)
+            || KEYWORDS.contains(&name.as_str())
+            || self.arities.contains_key(&name)
+        {
+            suffix += 1;
+            name = format!("{}_{}", identifier, suffix);
+        }
+
+        self.body.declared.insert(name.clone());
+
+        if self.body.scopes.is_empty() {
+            self.body.scopes.push(HashMap::new());
+        }
+
+        self.body
+            .scopes
+            .last_mut()
+            .expect("at least one scope")
+            .insert(identifier.to_string(), name.clone());
+
+        name
+    }
+
+    fn lookup(&self, identifier: &str) -> Option<String> {
+        self.body
+            .scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(identifier).cloned())
+    }
+
+    fn temporary(&mut self, prefix: &str, type_: &Type) -> Result<Option<String>, String> {
+        let Some(c_type) = c_type(type_)? else {
+            return Ok(None);
+        };
+
+        let name = self.fresh(prefix);
+        self.body.declared.insert(name.clone());
+        self.line(format!("{} {};", c_type, name));
+        Ok(Some(name))
+    }
+
+    fn struct_declaration(
+        &mut self,
+        type_identifier: &TypeIdentifier,
+        fields: &[StructField],
+    ) -> Result<(), String> {
+        let name = c_type_name(type_identifier)?;
+        let mut definition = format!("typedef struct {} {{\n", name);
+        let mut dependencies = Vec::new();
+
+        for field in fields {
+            definition.push_str(&format!(
+                "    {} {};\n",
+                c_value_type(&field.type_)?,
+                c_field_name(&field.identifier)
+            ));
+
+            dependencies.push(c_value_type(&field.type_)?);
+        }
+
+        // Empty structs are not valid C
+        if fields.is_empty() {
+            definition.push_str("    char empty;\n");
+        }
+
+        definition.push_str(&format!("}} {};\n", name));
+        self.types.push((name, dependencies, definition));
+        Ok(())
+    }
+
+    fn enum_declaration(
+        &mut self,
+        type_identifier: &TypeIdentifier,
+        shared_fields: &[StructField],
+        members: &[EnumMember],
+    ) -> Result<(), String> {
+        let name = c_type_name(type_identifier)?;
+        let mut dependencies = Vec::new();
+        let mut definition = format!("typedef enum {}_Tag {{\n", name);
+
+        // C counts up from the tag before like the checker does, so only the values it wouldn't
+        // count to are written
+        let mut next = Some(0);
+
+        for member in members {
+            let tag = tag(&name, &member.discriminant_name);
+
+            if next == Some(member.discriminant) {
+                definition.push_str(&format!("    {},\n", tag));
+            } else {
+                definition.push_str(&format!("    {} = {},\n", tag, member.discriminant));
+            }
+
+            next = member.discriminant.checked_add(1);
+        }
+
+        definition.push_str(&format!("}} {}_Tag;\n\n", name));
+        definition.push_str(&format!("typedef struct {} {{\n", name));
+        definition.push_str(&format!("    {}_Tag tag;\n", name));
+
+        for field in shared_fields {
+            definition.push_str(&format!(
+                "    {} {};\n",
+                c_value_type(&field.type_)?,
+                field.identifier
+            ));
+
+            dependencies.push(c_value_type(&field.type_)?);
+        }
+
+        let members_with_fields = members
+            .iter()
+            .filter(|member| !member.fields.is_empty())
+            .collect::<Vec<_>>();
+
+        if !members_with_fields.is_empty() {
+            definition.push_str("    union {\n");
+
+            for member in members_with_fields {
+                definition.push_str("        struct {\n");
+
+                for field in &member.fields {
+                    definition.push_str(&format!(
+                        "            {} {};\n",
+                        c_value_type(&field.type_)?,
+                        field.identifier
+                    ));
+
+                    dependencies.push(c_value_type(&field.type_)?);
+                }
+
+                definition.push_str(&format!("        }} {};\n", member.discriminant_name));
+            }
+
+            definition.push_str("    } as;\n");
+        }
+
+        definition.push_str(&format!("}} {};\n", name));
+        self.types.push((name, dependencies, definition));
+        Ok(())
+    }
+
+    fn function(
+        &mut self,
+        identifier: &TypeIdentifier,
+        param: Option<(String, Type)>,
+        body: &TypedExpression,
+    ) -> Result<(), String> {
+        let name = c_type_name(identifier)?;
+        let (params, body, return_type) = flatten_function(param, body);
+
+        self.body = Body {
+            is_function: true,
+            ..Default::default()
+        };
+
+        let mut c_params = Vec::new();
+
+        for (param, type_) in &params {
+            let c_param = self.declare(param);
+            c_params.push(format!("{} {}", c_value_type(type_)?, c_param));
+        }
+
+        let c_return_type = c_type(&return_type)?;
+
+        let signature = format!(
+            "{} {}({})",
+            c_return_type.as_deref().unwrap_or("void"),
+            name,
+            if c_params.is_empty() {
+                "void".to_string()
+            } else {
+                c_params.join(", ")
+            }
+        );
+
+        let sink = if c_return_type.is_some() {
+            Sink::Return
+        } else {
+            Sink::Discard
+        };
+
+        self.body.indent = 1;
+        self.sink_expression(body, &sink)?;
+
+        let body = std::mem::take(&mut self.body);
+        let mut function = format!("{} {{\n", signature);
+
+        for line in body.lines {
+            function.push_str(&line);
+            function.push('\n');
+        }
+
+        function.push_str("}\n");
+
+        self.prototypes.push(signature);
+        self.functions.push(function);
+        Ok(())
+    }
+
+    fn statement(&mut self, statement: &TypedStatement, sink: &Sink) -> Result<(), String> {
+        match statement {
+            TypedStatement::Semi(statement) => self.statement(statement, &Sink::Discard),
+            TypedStatement::Expression(expression) => self.sink_expression(expression, sink),
+            TypedStatement::None => Ok(()),
+            statement => Err(format!(
+                "Nested declaration '{}' is not supported in C",
+                statement
+            )),
+        }
+    }
+
+    fn sink(&mut self, value: Option<String>, sink: &Sink) {
+        match (value, sink) {
+            (Some(value), Sink::Discard) if !is_plain(&value) => self.line(format!("{};", value)),
+            (Some(value), Sink::Assign(target)) => self.line(format!("{} = {};", target, value)),
+            (Some(value), Sink::Return) => self.line(format!("return {};", value)),
+            _ => {}
+        }
+    }
+
+    // Control flow is written directly into the sink, so it needs no temporary
+    fn sink_expression(&mut self, expression: &TypedExpression, sink: &Sink) -> Result<(), String> {
+        match expression {
+            TypedExpression::If {
+                condition,
+                true_expression,
+                false_expression: Some(false_expression),
+                ..
+            } => {
+                let condition = self.value(condition)?;
+                self.line(format!("if ({}) {{", condition));
+                self.indented(|e| e.sink_expression(true_expression, sink))?;
+                self.line("} else {");
+                self.indented(|e| e.sink_expression(false_expression, sink))?;
+                self.line("}");
+                Ok(())
+            }
+            TypedExpression::Block(block) => self.block(block, sink),
+            TypedExpression::Match {
+                expression,
+                decision_tree,
+                ..
+            } => self.match_(expression, decision_tree, sink),
+            expression => {
+                let value = self.expression(expression)?;
+                self.sink(value, sink);
+                Ok(())
+            }
+        }
+    }
+
+    fn block(&mut self, block: &Block, sink: &Sink) -> Result<(), String> {
+        self.line("{");
+
+        self.indented(|e| {
+            let Some((last, rest)) = block.statements.split_last() else {
+                return Ok(());
+            };
+
+            for statement in rest {
+                e.statement(statement, &Sink::Discard)?;
+            }
+
+            e.statement(last, sink)
+        })?;
+
+        self.line("}");
+        Ok(())
+    }
+
+    fn value(&mut self, expression: &TypedExpression) -> Result<String, String> {
+        self.expression(expression)?
+            .ok_or(format!("'{}' has no value in C", expression))
+    }
+
+    fn expression(&mut self, expression: &TypedExpression) -> Result<Option<String>, String> {
+        if let Some((_, value)) = self
+            .body
+            .matchees
+            .iter()
+            .rev()
+            .find(|(matchee, _)| self.body.in_guard && matchee == expression)
+        {
+            return Ok(Some(value.clone()));
+        }
+
+        match expression {
+            TypedExpression::VariableDeclaration {
+                pattern,
+                initializer,
+                type_,
+                ..
+            } => {
+                let Pattern::Variable(identifier) = pattern else {
+                    return Err(format!(
+                        "Cannot declare variables with the pattern '{}' in C",
+                        pattern
+                    ));
+                };
+
+                let c_type = c_value_type(type_)?;
+
+                let initializer = match initializer {
+                    Some(initializer) => Some(self.value(initializer)?),
+                    None => None,
+                };
+
+                let name = self.declare(identifier);
+
+                match initializer {
+                    Some(initializer) => {
+                        self.line(format!("{} {} = {};", c_type, name, initializer))
+                    }
+                    None => self.line(format!("{} {};", c_type, name)),
+                }
+
+                Ok(Some(name))
+            }
+            TypedExpression::If {
+                true_expression,
+                false_expression: None,
+                ..
+            } if c_type(&true_expression.get_type())?.is_some() => Err(
+                "An 'if' without 'else' produces an Option, which is not supported in C"
+                    .to_string(),
+            ),
+            TypedExpression::If {
+                condition,
+                true_expression,
+                false_expression: None,
+                ..
+            } => {
+                let condition = self.value(condition)?;
+                self.line(format!("if ({}) {{", condition));
+                self.indented(|e| e.sink_expression(true_expression, &Sink::Discard))?;
+                self.line("}");
+                Ok(None)
+            }
+            TypedExpression::If { type_, .. } | TypedExpression::Match { type_, .. } => {
+                self.temporary_expression(expression, type_)
+            }
+            TypedExpression::Block(block) => self.temporary_expression(expression, &block.type_),
+            TypedExpression::Assignment {
+                member,
+                initializer,
+                ..
+            } => {
+                let initializer = self.value(initializer)?;
+                let target = self.member(member)?;
+                self.line(format!("{} = {};", target, initializer));
+                Ok(Some(target))
+            }
+            TypedExpression::Member(member) => self.member(member).map(Some),
+            TypedExpression::Literal(literal, _) => self.literal(literal),
+            TypedExpression::Call { .. } => self.call(expression).map(Some),
+            TypedExpression::Unary {
+                operator,
+                expression,
+                ..
+            } => {
+                let value = self.value(expression)?;
+
+                let operator = match operator {
+                    UnaryOperator::Identity => "+",
+                    UnaryOperator::Negate => "-",
+                    UnaryOperator::LogicalNot => "!",
+                    UnaryOperator::BitwiseNot => "~",
+                };
+
+                Ok(Some(format!("({}{})", operator, value)))
+            }
+            TypedExpression::Binary {
+                left,
+                operator,
+                right,
+                ..
+            } => self.binary(left, operator, right).map(Some),
+            TypedExpression::Cast {
+                expression, type_, ..
+            } => {
+                let value = self.value(expression)?;
+                Ok(Some(format!("(({}){})", c_value_type(type_)?, value)))
+            }
+            #[cfg(feature = "interpreter")]
+            TypedExpression::Print { value } => {
+                let type_ = underlying(&value.get_type());
+                let value = self.value(value)?;
+
+                let (format, value) = match type_ {
+                    Type::Int => ("%lld", format!("(long long){}", value)),
+                    Type::UInt => ("%llu", format!("(unsigned long long){}", value)),
+                    Type::Float => ("%g", value),
+                    Type::Bool => ("%s", format!("{} ? \"true\" : \"false\"", value)),
+                    Type::Char => ("%c", format!("(int){}", value)),
+                    Type::String => ("%s", value),
+                    type_ => return Err(format!("Cannot print values of type {} in C", type_)),
+                };
+
+                self.line(format!("printf(\"{}\\n\", {});", format, value));
+                Ok(None)
+            }
+            TypedExpression::Loop { body, type_ } => self.loop_(None, body, None, type_),
+            TypedExpression::While {
+                condition,
+                body,
+                else_body,
+                type_,
+            } => self.loop_(Some(condition), body, else_body.as_deref(), type_),
+            TypedExpression::Break(value) => {
+                let Some(label) = self.body.loops.last() else {
+                    return Err("Cannot break outside of a loop".to_string());
+                };
+
+                let (name, result) = (label.name.clone(), label.result.clone());
+
+                if let Some(value) = value {
+                    let value = self.expression(value)?;
+
+                    match result {
+                        Some(result) => self.sink(value, &Sink::Assign(result)),
+                        None => self.sink(value, &Sink::Discard),
+                    }
+                }
+
+                self.line(format!("goto {}_break;", name));
+                Ok(None)
+            }
+            TypedExpression::Continue => {
+                let Some(label) = self.body.loops.last_mut() else {
+                    return Err("Cannot continue outside of a loop".to_string());
+                };
+
+                label.continued = true;
+                let name = label.name.clone();
+                self.line(format!("goto {}_continue;", name));
+                Ok(None)
+            }
+            TypedExpression::Return(value) => {
+                if !self.body.is_function {
+                    return Err("Cannot return outside of a function".to_string());
+                }
+
+                match value {
+                    Some(value) => match self.expression(value)? {
+                        Some(value) => self.line(format!("return {};", value)),
+                        None => self.line("return;"),
+                    },
+                    None => self.line("return;"),
+                }
+
+                Ok(None)
+            }
+            TypedExpression::Closure { .. } => Err("Closures are not supported in C".to_string()),
+            expression => Err(format!("'{}' is not supported in C", expression)),
+        }
+    }
+
+    fn temporary_expression(
+        &mut self,
+        expression: &TypedExpression,
+        type_: &Type,
+    ) -> Result<Option<String>, String> {
+        let prefix = match expression {
+            TypedExpression::If { .. } => "if",
+            TypedExpression::Match { .. } => "match",
+            _ => "block",
+        };
+
+        let result = self.temporary(prefix, type_)?;
+
+        match &result {
+            Some(result) => self.sink_expression(expression, &Sink::Assign(result.clone()))?,
+            None => self.sink_expression(expression, &Sink::Discard)?,
+        }
+
+        Ok(result)
+    }
+
+    fn loop_(
+        &mut self,
+        condition: Option<&TypedExpression>,
+        body: &TypedExpression,
+        else_body: Option<&TypedExpression>,
+        type_: &Type,
+    ) -> Result<Option<String>, String> {
+        let result = self.temporary("loop", type_)?;
+        let name = self.fresh("loop");
+
+        self.line("for (;;) {");
+
+        self.indented(|e| {
+            if let Some(condition) = condition {
+                let condition = e.value(condition)?;
+                e.line(format!("if (!{}) {{", condition));
+
+                // The else body runs when the condition fails, so a break inside it belongs to an outer loop
+                e.indented(|e| {
+                    if let Some(else_body) = else_body {
+                        match &result {
+                            Some(result) => {
+                                e.sink_expression(else_body, &Sink::Assign(result.clone()))?
+                            }
+                            None => e.sink_expression(else_body, &Sink::Discard)?,
+                        }
+                    }
+
+                    e.line(format!("goto {}_break;", name));
+                    Ok::<(), String>(())
+                })?;
+
+                e.line("}");
+            }
+
+            e.body.loops.push(LoopLabel {
+                name: name.clone(),
+                result: result.clone(),
+                continued: false,
+            });
+
+            e.line("{");
+            e.indented(|e| e.sink_expression(body, &Sink::Discard))?;
+            e.line("}");
+
+            let label = e.body.loops.pop().expect("loop label");
+
+            if label.continued {
+                e.line(format!("{}_continue:;", name));
+            }
+
+            Ok::<(), String>(())
+        })?;
+
+        self.line("}");
+        self.line(format!("{}_break:;", name));
+        Ok(result)
+    }
+
+    fn member(&mut self, member: &Member) -> Result<String, String> {
+        match member {
+            Member::Identifier { symbol, .. } => Ok(self.lookup(symbol).unwrap_or(symbol.clone())),
+            Member::MemberAccess { object, symbol, .. } => {
+                let object_type = object.get_type();
+                let object = self.value(object)?;
+                Ok(field_access(&object, &object_type, None, symbol)?.0)
+            }
+            Member::StaticMemberAccess { .. } => Err(format!(
+                "Static member access '{}' is not supported in C",
+                member
+            )),
+        }
+    }
+
+    fn call(&mut self, expression: &TypedExpression) -> Result<String, String> {
+        let mut arguments = Vec::new();
+        let mut calls = 0;
+        let mut callee = expression;
+
+        while let TypedExpression::Call {
+            callee: inner,
+            argument,
+            ..
+        } = callee
+        {
+            calls += 1;
+            arguments.extend(argument.iter().map(|argument| argument.as_ref()));
+            callee = inner;
+        }
+
+        arguments.reverse();
+
+        let TypedExpression::Member(Member::Identifier { symbol, .. }) = callee else {
+            return Err(format!(
+                "Only named functions can be called in C, found '{}'",
+                callee
+            ));
+        };
+
+        match self.arities.get(symbol) {
+            Some(arity) if *arity == arguments.len() && calls == (*arity).max(1) => {}
+            _ => {
+                return Err(format!(
+                    "'{}' must be a function called with all of its arguments in C",
+                    symbol
+                ))
+            }
+        }
+
+        let mut values = Vec::new();
+
+        for argument in arguments {
+            values.push(self.value(argument)?);
+        }
+
+        Ok(format!("{}({})", symbol, values.join(", ")))
+    }
+
+    fn binary(
+        &mut self,
+        left: &TypedExpression,
+        operator: &BinaryOperator,
+        right: &TypedExpression,
+    ) -> Result<String, String> {
+        let left_type = underlying(&left.get_type());
+        let left_value = self.value(left)?;
+
+        if let BinaryOperator::LogicalAnd | BinaryOperator::LogicalOr = operator {
+            return self.short_circuit(left_value, operator, right);
+        }
+
+        let right_value = self.value(right)?;
+
+        let c_operator = match operator {
+            BinaryOperator::Add => "+",
+            BinaryOperator::Subtract => "-",
+            BinaryOperator::Multiply => "*",
+            BinaryOperator::Divide => "/",
+            BinaryOperator::Modulo => "%",
+            BinaryOperator::BitwiseAnd => "&",
+            BinaryOperator::BitwiseOr => "|",
+            BinaryOperator::BitwiseXor => "^",
+            BinaryOperator::BitwiseLeftShift => "<<",
+            BinaryOperator::BitwiseRightShift => ">>",
+            BinaryOperator::Equal => "==",
+            BinaryOperator::NotEqual => "!=",
+            BinaryOperator::LessThan => "<",
+            BinaryOperator::LessThanOrEqual => "<=",
+            BinaryOperator::GreaterThan => ">",
+            BinaryOperator::GreaterThanOrEqual => ">=",
+            operator => return Err(format!("The operator '{}' is not supported in C", operator)),
+        };
+
+        let is_comparison = matches!(
+            operator,
+            BinaryOperator::Equal
+                | BinaryOperator::NotEqual
+                | BinaryOperator::LessThan
+                | BinaryOperator::LessThanOrEqual
+                | BinaryOperator::GreaterThan
+                | BinaryOperator::GreaterThanOrEqual
+        );
+
+        match (&left_type, right) {
+            (Type::String, _) if is_comparison => Ok(format!(
+                "(strcmp({}, {}) {} 0)",
+                left_value, right_value, c_operator
+            )),
+            (Type::Float | Type::Int | Type::UInt | Type::Bool | Type::Char, _) => {
+                Ok(format!("({} {} {})", left_value, c_operator, right_value))
+            }
+            // Matching a fieldless enum member compares it with a literal
+            (
+                Type::Enum(Enum {
+                    type_identifier, ..
+                }),
+                TypedExpression::Literal(
+                    Literal::Enum {
+                        member,
+                        field_initializers: EnumMemberFieldInitializers::None,
+                        ..
+                    },
+                    _,
+                ),
+            ) if matches!(operator, BinaryOperator::Equal | BinaryOperator::NotEqual) => {
+                Ok(format!(
+                    "({}.tag {} {})",
+                    left_value,
+                    c_operator,
+                    tag(&c_type_name(type_identifier)?, member)
+                ))
+            }
+            // Matching a fieldless struct compares it with an empty literal, which always succeeds
+            (
+                Type::Struct(_),
+                TypedExpression::Literal(
+                    Literal::Struct {
+                        field_initializers, ..
+                    },
+                    _,
+                ),
+            ) if field_initializers.is_empty() && *operator == BinaryOperator::Equal => {
+                Ok("true".to_string())
+            }
+            (type_, _) => Err(format!(
+                "The operator '{}' is not supported for {} in C",
+                operator, type_
+            )),
+        }
+    }
+
+    fn short_circuit(
+        &mut self,
+        left: String,
+        operator: &BinaryOperator,
+        right: &TypedExpression,
+    ) -> Result<String, String> {
+        let start = self.body.lines.len();
+        let right_value = self.indented(|e| e.value(right))?;
+
+        // The right side is evaluated conditionally, so the statements it needs can't be hoisted
+        if self.body.lines.len() == start {
+            let c_operator = if *operator == BinaryOperator::LogicalAnd {
+                "&&"
+            } else {
+                "||"
+            };
+
+            return Ok(format!("({} {} {})", left, c_operator, right_value));
+        }
+
+        let statements = self.body.lines.split_off(start);
+        let result = self.fresh("logical");
+        self.body.declared.insert(result.clone());
+
+        self.line(format!("bool {} = {};", result, left));
+
+        if *operator == BinaryOperator::LogicalAnd {
+            self.line(format!("if ({}) {{", result));
+        } else {
+            self.line(format!("if (!{}) {{", result));
+        }
+
+        self.body.lines.extend(statements);
+        self.indented(|e| e.line(format!("{} = {};", result, right_value)));
+        self.line("}");
+        Ok(result)
+    }
+
+    fn literal(&mut self, literal: &Literal) -> Result<Option<String>, String> {
+        let value = match literal {
+            Literal::Void | Literal::Unit => return Ok(None),
+            Literal::Int(v) if *v == i64::MIN => "INT64_MIN".to_string(),
+            Literal::Int(v) if i32::try_from(*v).is_ok() => v.to_string(),
+            Literal::Int(v) => format!("INT64_C({})", v),
+            Literal::UInt(v) if u32::try_from(*v).is_ok() => format!("{}u", v),
+            Literal::UInt(v) => format!("UINT64_C({})", v),
+            Literal::Float(v) if v.is_finite() => format!("{:?}", v),
+            Literal::Float(v) => return Err(format!("The float {} is not supported in C", v)),
+            Literal::String(v) => escape(v),
+            Literal::Char(v) => (*v as u32).to_string(),
+            Literal::Bool(v) => v.to_string(),
+            Literal::Array { .. } => return Err("Arrays are not supported in C".to_string()),
+            Literal::Struct {
+                field_initializers,
+                spread,
+                type_,
+                ..
+            } => {
+                if spread.is_some() {
+                    return Err("Spreads in struct literals are not supported in C".to_string());
+                }
+
+                let Type::Struct(Struct { fields, .. }) = type_ else {
+                    return Err(format!("Expected a struct type, found {}", type_));
+                };
+
+                let mut initializers = Vec::new();
+
+                for (index, field_initializer) in field_initializers.iter().enumerate() {
+                    let identifier = match &field_initializer.identifier {
+                        Some(identifier) => identifier.clone(),
+                        None => fields
+                            .get(index)
+                            .map(|field| field.field_name.clone())
+                            .ok_or(format!("The struct {} has no field {}", type_, index))?,
+                    };
+
+                    let value = self.value(&field_initializer.initializer)?;
+                    initializers.push(format!(".{} = {}", c_field_name(&identifier), value));
+                }
+
+                format!(
+                    "({}){{ {} }}",
+                    c_value_type(type_)?,
+                    initializers.join(", ")
+                )
+            }
+            Literal::Enum {
+                member,
+                field_initializers,
+                type_,
+                ..
+            } => {
+                let enum_name = c_value_type(type_)?;
+                let mut initializers = vec![format!(".tag = {}", tag(&enum_name, member))];
+
+                if let EnumMemberFieldInitializers::Named(field_initializers) = field_initializers {
+                    let shared_fields = match type_ {
+                        Type::Enum(Enum { shared_fields, .. }) => shared_fields.clone(),
+                        _ => Vec::new(),
+                    };
+
+                    let mut field_initializers = field_initializers.iter().collect::<Vec<_>>();
+                    field_initializers.sort_by_key(|(identifier, _)| *identifier);
+
+                    for (identifier, initializer) in field_initializers {
+                        let value = self.value(initializer)?;
+
+                        if shared_fields.iter().any(|f| &f.field_name == identifier) {
+                            initializers.push(format!(".{} = {}", identifier, value));
+                        } else {
+                            initializers.push(format!(".as.{}.{} = {}", member, identifier, value));
+                        }
+                    }
+                }
+
+                format!("({}){{ {} }}", enum_name, initializers.join(", "))
+            }
+        };
+
+        Ok(Some(value))
+    }
+
+    fn match_(
+        &mut self,
+        expression: &TypedExpression,
+        decision_tree: &Decision,
+        sink: &Sink,
+    ) -> Result<(), String> {
+        let matchee_type = underlying(&expression.get_type());
+        let value = self.value(expression)?;
+        let matchee = self.fresh("matchee");
+
+        self.body.declared.insert(matchee.clone());
+        self.line(format!(
+            "{} {} = {};",
+            c_value_type(&matchee_type)?,
+            matchee,
+            value
+        ));
+
+        // Guards compare the matchee expression, which must only be evaluated once
+        self.body
+            .matchees
+            .push((expression.clone(), matchee.clone()));
+        let result = self.decision(decision_tree, &matchee, &matchee_type, sink);
+        self.body.matchees.pop();
+
+        result
+    }
+
+    fn decision(
+        &mut self,
+        decision: &Decision,
+        value: &str,
+        value_type: &Type,
+        sink: &Sink,
+    ) -> Result<(), String> {
+        if let Some((cases, fallback)) = self.switch_cases(decision, value_type)? {
+            if cases.len() > 1 {
+                return self.switch(cases, fallback, value, value_type, sink);
+            }
+        }
+
+        match decision {
+            Decision::Success { expression, .. } => self.sink_expression(expression, sink),
+            Decision::Failure { error_message } => {
+                self.line(format!(
+                    "fprintf(stderr, \"%s\\n\", {});",
+                    escape(error_message)
+                ));
+
+                self.line("exit(1);");
+                Ok(())
+            }
+            Decision::Guard {
+                condition,
+                consequence,
+                alternative,
+                ..
+            } => {
+                self.body.in_guard = true;
+                let condition = self.value(condition);
+                self.body.in_guard = false;
+
+                self.line(format!("if ({}) {{", condition?));
+                self.indented(|e| e.decision(consequence, value, value_type, sink))?;
+                self.line("} else {");
+                self.indented(|e| e.decision(alternative, value, value_type, sink))?;
+                self.line("}");
+                Ok(())
+            }
+            Decision::Switch {
+                variable,
+                cases,
+                fallback,
+                ..
+            } => {
+                let mut closing = 0;
+
+                for case in cases {
+                    let condition = pattern_condition(&case.pattern, value, value_type)?;
+
+                    match &condition {
+                        Some(condition) => self.line(format!("if ({}) {{", condition)),
+                        None => self.line("{"),
+                    }
+
+                    self.indented(|e| {
+                        e.case(variable, &case.pattern, &case.body, value, value_type, sink)
+                    })?;
+
+                    if condition.is_none() {
+                        self.line("}");
+
+                        for _ in 0..closing {
+                            self.body.indent -= 1;
+                            self.line("}");
+                        }
+
+                        return Ok(());
+                    }
+
+                    self.line("} else {");
+                    self.body.indent += 1;
+                    closing += 1;
+                }
+
+                self.decision(fallback, value, value_type, sink)?;
+
+                for _ in 0..closing {
+                    self.body.indent -= 1;
+                    self.line("}");
+                }
+
+                Ok(())
+            }
+        }
+    }
+
+    fn case(
+        &mut self,
+        variable: &Variable,
+        pattern: &Pattern,
+        body: &Decision,
+        value: &str,
+        value_type: &Type,
+        sink: &Sink,
+    ) -> Result<(), String> {
+        let mut bindings = Vec::new();
+        pattern_bindings(pattern, value, value_type, &mut bindings)?;
+
+        // The rest of the tree matches on the field the switch selected, and binds it itself
+        let selected = match &variable.accessor {
+            Accessor::Expression(expression) => {
+                let member = match pattern {
+                    Pattern::Constructor(Constructor::Struct {
+                        type_annotation, ..
+                    }) => Some(type_annotation.name()),
+                    _ => None,
+                };
+
+                let (field, field_type) =
+                    field_access(value, value_type, member.as_deref(), &variable.identifier)?;
+
+                Some((expression, field, field_type))
+            }
+            Accessor::Environment => None,
+        };
+
+        for (identifier, binding, type_) in bindings {
+            if selected
+                .as_ref()
+                .is_some_and(|(_, field, _)| *field == binding)
+            {
+                continue;
+            }
+
+            let c_type = c_value_type(&type_)?;
+            let name = self.declare(&identifier);
+            self.line(format!("{} {} = {};", c_type, name, binding));
+        }
+
+        let Some((expression, field, field_type)) = selected else {
+            return self.decision(body, value, value_type, sink);
+        };
+
+        self.body
+            .matchees
+            .push((*expression.clone(), field.clone()));
+        let result = self.decision(body, &field, &field_type, sink);
+        self.body.matchees.pop();
+
+        result
+    }
+
+    /// The cases of a chain of equality tests against constants, which can be a C switch
+    fn switch_cases<'a>(
+        &self,
+        decision: &'a Decision,
+        value_type: &Type,
+    ) -> Result<Option<SwitchChain<'a>>, String> {
+        let Some((matchee, _)) = self.body.matchees.last() else {
+            return Ok(None);
+        };
+
+        let mut cases: Vec<(String, SwitchCase<'a>)> = Vec::new();
+        let mut decision = decision;
+
+        loop {
+            let (label, case, alternative) = match decision {
+                Decision::Guard {
+                    condition,
+                    consequence,
+                    alternative,
+                    ..
+                } => {
+                    let TypedExpression::Binary {
+                        left,
+                        operator: BinaryOperator::Equal,
+                        right,
+                        ..
+                    } = condition.as_ref()
+                    else {
+                        break;
+                    };
+
+                    if left.as_ref() != matchee {
+                        break;
+                    }
+
+                    let label = match right.as_ref() {
+                        TypedExpression::Literal(Literal::Int(v), _) => v.to_string(),
+                        TypedExpression::Literal(Literal::UInt(v), _) => format!("{}u", v),
+                        TypedExpression::Literal(Literal::Char(v), _) => (*v as u32).to_string(),
+                        TypedExpression::Literal(
+                            Literal::Enum {
+                                member,
+                                field_initializers: EnumMemberFieldInitializers::None,
+                                ..
+                            },
+                            _,
+                        ) => tag(&c_value_type(value_type)?, member),
+                        _ => break,
+                    };
+
+                    (label, SwitchCase::Decision(consequence), alternative)
+                }
+                Decision::Switch {
+                    variable,
+                    cases: switch_cases,
+                    fallback,
+                    ..
+                } if matches!(value_type, Type::Enum(_)) && switch_cases.len() == 1 => {
+                    let case = &switch_cases[0];
+
+                    let Pattern::Constructor(Constructor::Struct {
+                        type_annotation,
+                        field_patterns,
+                    }) = &case.pattern
+                    else {
+                        break;
+                    };
+
+                    let irrefutable = field_patterns.iter().all(|field_pattern| {
+                        matches!(
+                            field_pattern.pattern,
+                            Pattern::Variable(_) | Pattern::Wildcard
+                        )
+                    });
+
+                    if !irrefutable {
+                        break;
+                    }
+
+                    let label = tag(
+                        &c_value_type(value_type)?,
+                        member_name(&type_annotation.name()),
+                    );
+                    (label, SwitchCase::Case(variable, case), fallback)
+                }
+                _ => break,
+            };
+
+            // A repeated label can never be reached
+            if !cases.iter().any(|(existing, _)| *existing == label) {
+                cases.push((label, case));
+            }
+
+            decision = alternative;
+        }
+
+        Ok(Some((cases, decision)))
+    }
+
+    fn switch(
+        &mut self,
+        cases: Vec<(String, SwitchCase)>,
+        fallback: &Decision,
+        value: &str,
+        value_type: &Type,
+        sink: &Sink,
+    ) -> Result<(), String> {
+        let scrutinee = if matches!(value_type, Type::Enum(_)) {
+            format!("{}.tag", value)
+        } else {
+            value.to_string()
+        };
+
+        self.line(format!("switch ({}) {{", scrutinee));
+
+        for (label, case) in cases {
+            self.line(format!("case {}: {{", label));
+
+            self.indented(|e| {
+                match case {
+                    SwitchCase::Decision(decision) => {
+                        e.decision(decision, value, value_type, sink)?
+                    }
+                    SwitchCase::Case(variable, case) => {
+                        e.case(variable, &case.pattern, &case.body, value, value_type, sink)?
+                    }
+                }
+
+                e.line("break;");
+                Ok::<(), String>(())
+            })?;
+
+            self.line("}");
+        }
+
+        self.line("default: {");
+
+        self.indented(|e| {
+            e.decision(fallback, value, value_type, sink)?;
+            e.line("break;");
+            Ok::<(), String>(())
+        })?;
+
+        self.line("}");
+        self.line("}");
+        Ok(())
+    }
+}
+
+type SwitchChain<'a> = (Vec<(String, SwitchCase<'a>)>, &'a Decision);
+
+enum SwitchCase<'a> {
+    Decision(&'a Decision),
+    Case(&'a Variable, &'a Case),
+}
+
+/// The C expression for a field and the type of the field.
+/// The fields of an enum member live in the `as` union unless they are shared by all members.
+fn field_access(
+    value: &str,
+    type_: &Type,
+    member: Option<&str>,
+    field: &str,
+) -> Result<(String, Type), String> {
+    let find = |fields: &[crate::type_checker::StructField]| {
+        fields
+            .iter()
+            .find(|f| f.field_name == field)
+            .map(|f| f.field_type.clone())
+    };
+
+    let not_found = || format!("{} has no field '{}'", type_, field);
+
+    match type_ {
+        Type::Substitution { .. } | Type::Literal { .. } => {
+            field_access(value, &underlying(type_), member, field)
+        }
+        Type::Struct(Struct { fields, .. }) => {
+            let field_type = find(fields).ok_or_else(not_found)?;
+            Ok((format!("{}.{}", value, c_field_name(field)), field_type))
+        }
+        Type::Enum(Enum {
+            shared_fields,
+            members,
+            ..
+        }) => {
+            if let Some(field_type) = find(shared_fields) {
+                return Ok((format!("{}.{}", value, field), field_type));
+            }
+
+            let member = member.map(member_name).ok_or_else(not_found)?;
+
+            let Some(Type::EnumMember(member_type)) = members.get(member) else {
+                return Err(not_found());
+            };
+
+            let field_type = find(&member_type.fields).ok_or_else(not_found)?;
+
+            Ok((format!("{}.as.{}.{}", value, member, field), field_type))
+        }
+        Type::EnumMember(member_type) => {
+            let field_type = find(&member_type.fields).ok_or_else(not_found)?;
+
+            Ok((
+                format!("{}.as.{}.{}", value, member_type.discriminant_name, field),
+                field_type,
+            ))
+        }
+        _ => Err(not_found()),
+    }
+}
+
+fn pattern_constant(pattern: &Pattern) -> Result<String, String> {
+    match pattern {
+        Pattern::Bool(v) => Ok(v.to_string()),
+        Pattern::Int(v) => Ok(v.to_string()),
+        Pattern::UInt(v) => Ok(format!("{}u", v)),
+        Pattern::Float(v) => Ok(format!("{:?}", v)),
+        Pattern::Char(v) => Ok((*v as u32).to_string()),
+        pattern => Err(format!("The pattern '{}' is not a constant", pattern)),
+    }
+}
+
+/// The C condition for a value matching the pattern, or `None` if every value matches
+fn pattern_condition(
+    pattern: &Pattern,
+    value: &str,
+    type_: &Type,
+) -> Result<Option<String>, String> {
+    let condition = match pattern {
+        Pattern::Wildcard | Pattern::Variable(_) | Pattern::Unit => return Ok(None),
+        Pattern::String(v) => format!("(strcmp({}, {}) == 0)", value, escape(v)),
+        Pattern::Bool(_)
+        | Pattern::Int(_)
+        | Pattern::UInt(_)
+        | Pattern::Float(_)
+        | Pattern::Char(_) => {
+            format!("({} == {})", value, pattern_constant(pattern)?)
+        }
+        Pattern::LessThan(p) => format!("({} < {})", value, pattern_constant(p)?),
+        Pattern::GreaterThan(p) => format!("({} > {})", value, pattern_constant(p)?),
+        Pattern::LessThanOrEqual(p) => format!("({} <= {})", value, pattern_constant(p)?),
+        Pattern::GreaterThanOrEqual(p) => format!("({} >= {})", value, pattern_constant(p)?),
+        Pattern::Range(start, end, inclusive) => format!(
+            "({} >= {} && {} {} {})",
+            value,
+            pattern_constant(start)?,
+            value,
+            if *inclusive { "<=" } else { "<" },
+            pattern_constant(end)?
+        ),
+        Pattern::Constructor(Constructor::Struct {
+            type_annotation,
+            field_patterns,
+        }) => {
+            let member = type_annotation.name();
+            let mut conditions = Vec::new();
+
+            if let Type::Enum(Enum {
+                type_identifier, ..
+            }) = type_
+            {
+                conditions.push(format!(
+                    "{}.tag == {}",
+                    value,
+                    tag(&c_type_name(type_identifier)?, member_name(&member))
+                ));
+            }
+
+            for field_pattern in field_patterns {
+                let (field, field_type) =
+                    field_access(value, type_, Some(&member), &field_pattern.identifier)?;
+
+                if let Some(condition) =
+                    pattern_condition(&field_pattern.pattern, &field, &field_type)?
+                {
+                    conditions.push(condition);
+                }
+            }
+
+            if conditions.is_empty() {
+                return Ok(None);
+            }
+
+            format!("({})", conditions.join(" && "))
+        }
+    };
+
+    Ok(Some(condition))
+}
+
+fn pattern_bindings(
+    pattern: &Pattern,
+    value: &str,
+    type_: &Type,
+    bindings: &mut Vec<(String, String, Type)>,
+) -> Result<(), String> {
+    match pattern {
+        Pattern::Variable(identifier) => {
+            bindings.push((identifier.clone(), value.to_string(), type_.clone()))
+        }
+        Pattern::Constructor(Constructor::Struct {
+            type_annotation,
+            field_patterns,
+        }) => {
+            let member = type_annotation.name();
+
+            for field_pattern in field_patterns {
+                let (field, field_type) =
+                    field_access(value, type_, Some(&member), &field_pattern.identifier)?;
+
+                pattern_bindings(&field_pattern.pattern, &field, &field_type, bindings)?;
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
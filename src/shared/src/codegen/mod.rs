@@ -0,0 +1,3 @@
+pub mod bytecode;
+pub mod c;
+pub mod vm;
@@ -0,0 +1,496 @@
+use std::{cmp::Ordering, fmt::Display};
+
+use crate::type_checker::{
+    ast::{BinaryOperator, UnaryOperator},
+    decision_tree::{Constructor, Pattern},
+};
+
+use super::bytecode::{Constant, Instr};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Void,
+    Unit,
+    Bool(bool),
+    Int(i64),
+    UInt(u64),
+    Float(f64),
+    Char(char),
+    String(String),
+    Tuple(Vec<Value>),
+    Array(Vec<Value>),
+    Struct {
+        name: String,
+        fields: Vec<(String, Value)>,
+    },
+    Enum {
+        member: String,
+        fields: Vec<(String, Value)>,
+    },
+    Function {
+        address: usize,
+        captures: Vec<(usize, Value)>,
+    },
+}
+
+impl From<Constant> for Value {
+    fn from(value: Constant) -> Self {
+        match value {
+            Constant::Void => Value::Void,
+            Constant::Unit => Value::Unit,
+            Constant::Bool(v) => Value::Bool(v),
+            Constant::Int(v) => Value::Int(v),
+            Constant::UInt(v) => Value::UInt(v),
+            Constant::Float(v) => Value::Float(v),
+            Constant::Char(v) => Value::Char(v),
+            Constant::String(v) => Value::String(v),
+        }
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Void => write!(f, "void"),
+            Value::Unit => write!(f, "()"),
+            Value::Bool(v) => write!(f, "{}", v),
+            Value::Int(v) => write!(f, "{}", v),
+            Value::UInt(v) => write!(f, "{}", v),
+            Value::Float(v) => write!(f, "{}", v),
+            Value::Char(v) => write!(f, "{}", v),
+            Value::String(v) => write!(f, "{}", v),
+            Value::Tuple(values) => write!(
+                f,
+                "({})",
+                values
+                    .iter()
+                    .map(|v| v.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+            Value::Array(values) => write!(
+                f,
+                "[{}]",
+                values
+                    .iter()
+                    .map(|v| v.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+            Value::Struct {
+                name: member,
+                fields,
+            }
+            | Value::Enum { member, fields } => write!(
+                f,
+                "{} {{ {} }}",
+                member,
+                fields
+                    .iter()
+                    .map(|(name, value)| format!("{}: {}", name, value))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+            Value::Function { address, .. } => write!(f, "function {}", address),
+        }
+    }
+}
+
+struct Frame {
+    locals: Vec<Value>,
+    return_address: usize,
+    stack_base: usize,
+}
+
+/// Run bytecode from address 0 until it halts and return the value on top of the stack.
+pub fn run(instructions: &[Instr]) -> Result<Value, String> {
+    let mut stack = Vec::new();
+    let mut pc = 0;
+
+    let mut frames = vec![Frame {
+        locals: Vec::new(),
+        return_address: 0,
+        stack_base: 0,
+    }];
+
+    loop {
+        let instr = instructions
+            .get(pc)
+            .ok_or(format!("Address {} is outside the program", pc))?;
+
+        pc += 1;
+
+        match instr {
+            Instr::Push(constant) => stack.push(Value::from(constant.clone())),
+            Instr::Pop => {
+                pop(&mut stack)?;
+            }
+            Instr::Dup => {
+                let value = stack.last().ok_or("The stack is empty")?.clone();
+                stack.push(value);
+            }
+            Instr::Load(slot) => {
+                let frame = frames.last().expect("at least one frame");
+
+                let value = frame
+                    .locals
+                    .get(*slot)
+                    .ok_or(format!("Local {} is not initialized", slot))?;
+
+                stack.push(value.clone());
+            }
+            Instr::Store(slot) => {
+                let value = pop(&mut stack)?;
+                let frame = frames.last_mut().expect("at least one frame");
+                store(&mut frame.locals, *slot, value);
+            }
+            Instr::Function { address, captures } => {
+                let values = pop_many(&mut stack, captures.len())?;
+
+                stack.push(Value::Function {
+                    address: *address,
+                    captures: captures.iter().copied().zip(values).collect(),
+                });
+            }
+            Instr::Unary(operator) => {
+                let value = pop(&mut stack)?;
+                stack.push(unary(operator, value)?);
+            }
+            Instr::Binary(operator) => {
+                let right = pop(&mut stack)?;
+                let left = pop(&mut stack)?;
+                stack.push(binary(left, operator, right)?);
+            }
+            Instr::Tuple(count) => {
+                let values = pop_many(&mut stack, *count)?;
+                stack.push(Value::Tuple(values));
+            }
+            Instr::Array(count) => {
+                let values = pop_many(&mut stack, *count)?;
+                stack.push(Value::Array(values));
+            }
+            Instr::Struct { name, fields } => {
+                let values = pop_many(&mut stack, fields.len())?;
+
+                stack.push(Value::Struct {
+                    name: name.clone(),
+                    fields: fields.iter().cloned().zip(values).collect(),
+                });
+            }
+            Instr::Enum { member, fields } => {
+                let values = pop_many(&mut stack, fields.len())?;
+
+                stack.push(Value::Enum {
+                    member: member.clone(),
+                    fields: fields.iter().cloned().zip(values).collect(),
+                });
+            }
+            Instr::Field(name) => {
+                let object = pop(&mut stack)?;
+                stack.push(field(&object, name)?.clone());
+            }
+            Instr::Index => {
+                let index = pop(&mut stack)?;
+                let array = pop(&mut stack)?;
+
+                let position = match index {
+                    Value::Int(i) => usize::try_from(i).ok(),
+                    Value::UInt(i) => usize::try_from(i).ok(),
+                    _ => return Err(format!("Cannot index '{}' with '{}'", array, index)),
+                };
+
+                let value = match &array {
+                    Value::Array(values) => position.and_then(|i| values.get(i)).cloned(),
+                    // Strings are indexed by character, not by byte
+                    Value::String(string) => position
+                        .and_then(|i| string.chars().nth(i))
+                        .map(Value::Char),
+                    _ => return Err(format!("Cannot index '{}' with '{}'", array, index)),
+                };
+
+                let value = value.ok_or(format!("Index {} is out of bounds", index))?;
+                stack.push(value);
+            }
+            Instr::Test(pattern) => {
+                let value = pop(&mut stack)?;
+                stack.push(Value::Bool(matches(pattern, &value)?));
+            }
+            Instr::Call(arguments) => {
+                let arguments = pop_many(&mut stack, *arguments)?;
+                let callee = pop(&mut stack)?;
+
+                let Value::Function { address, captures } = callee else {
+                    return Err(format!("'{}' is not a function", callee));
+                };
+
+                let mut locals = arguments;
+
+                for (slot, value) in captures {
+                    store(&mut locals, slot, value);
+                }
+
+                frames.push(Frame {
+                    locals,
+                    return_address: pc,
+                    stack_base: stack.len(),
+                });
+
+                pc = address;
+            }
+            Instr::Return => {
+                let value = pop(&mut stack)?;
+
+                if frames.len() == 1 {
+                    return Err("Cannot return outside of a function".to_string());
+                }
+
+                let frame = frames.pop().expect("a function frame");
+                stack.truncate(frame.stack_base);
+                stack.push(value);
+                pc = frame.return_address;
+            }
+            Instr::Jump(address) => pc = *address,
+            Instr::JumpIfFalse(address) => {
+                if !pop_bool(&mut stack)? {
+                    pc = *address;
+                }
+            }
+            Instr::JumpIfTrue(address) => {
+                if pop_bool(&mut stack)? {
+                    pc = *address;
+                }
+            }
+            Instr::Print => {
+                let value = pop(&mut stack)?;
+                println!("{}", value);
+                stack.push(Value::Void);
+            }
+            Instr::Fail(message) => return Err(message.clone()),
+            Instr::Halt => return Ok(stack.pop().unwrap_or(Value::Void)),
+        }
+    }
+}
+
+fn pop(stack: &mut Vec<Value>) -> Result<Value, String> {
+    stack.pop().ok_or("The stack is empty".to_string())
+}
+
+fn pop_many(stack: &mut Vec<Value>, count: usize) -> Result<Vec<Value>, String> {
+    if stack.len() < count {
+        return Err(format!("Expected {} values on the stack", count));
+    }
+
+    Ok(stack.split_off(stack.len() - count))
+}
+
+fn pop_bool(stack: &mut Vec<Value>) -> Result<bool, String> {
+    match pop(stack)? {
+        Value::Bool(v) => Ok(v),
+        value => Err(format!("Expected a boolean, found '{}'", value)),
+    }
+}
+
+fn store(locals: &mut Vec<Value>, slot: usize, value: Value) {
+    if locals.len() <= slot {
+        locals.resize(slot + 1, Value::Void);
+    }
+
+    locals[slot] = value;
+}
+
+fn field<'a>(object: &'a Value, name: &str) -> Result<&'a Value, String> {
+    let value = match object {
+        Value::Struct { fields, .. } | Value::Enum { fields, .. } => fields
+            .iter()
+            .find(|(field_name, _)| field_name == name)
+            .map(|(_, value)| value),
+        Value::Tuple(values) => name.parse::<usize>().ok().and_then(|i| values.get(i)),
+        _ => None,
+    };
+
+    value.ok_or(format!("'{}' has no field '{}'", object, name))
+}
+
+fn unary(operator: &UnaryOperator, value: Value) -> Result<Value, String> {
+    match (operator, value) {
+        (UnaryOperator::Identity, value) => Ok(value),
+        (UnaryOperator::Negate, Value::Int(v)) => v
+            .checked_neg()
+            .map(Value::Int)
+            .ok_or("Integer overflow".to_string()),
+        (UnaryOperator::Negate, Value::Float(v)) => Ok(Value::Float(-v)),
+        (UnaryOperator::LogicalNot, Value::Bool(v)) => Ok(Value::Bool(!v)),
+        (UnaryOperator::BitwiseNot, Value::Int(v)) => Ok(Value::Int(!v)),
+        (UnaryOperator::BitwiseNot, Value::UInt(v)) => Ok(Value::UInt(!v)),
+        (operator, value) => Err(format!("Cannot apply '{}' to '{}'", operator, value)),
+    }
+}
+
+fn compare(ordering: Option<Ordering>, operator: &BinaryOperator) -> Option<Value> {
+    let ordering = ordering?;
+
+    let result = match operator {
+        BinaryOperator::LessThan => ordering.is_lt(),
+        BinaryOperator::LessThanOrEqual => ordering.is_le(),
+        BinaryOperator::GreaterThan => ordering.is_gt(),
+        BinaryOperator::GreaterThanOrEqual => ordering.is_ge(),
+        _ => return None,
+    };
+
+    Some(Value::Bool(result))
+}
+
+macro_rules! integer_binary {
+    ($variant:ident, $left:expr, $operator:expr, $right:expr) => {{
+        let (left, right) = ($left, $right);
+
+        let result = match $operator {
+            BinaryOperator::Add => left.checked_add(right),
+            BinaryOperator::Subtract => left.checked_sub(right),
+            BinaryOperator::Multiply => left.checked_mul(right),
+            BinaryOperator::Divide | BinaryOperator::Modulo if right == 0 => {
+                return Err("Division by zero".to_string())
+            }
+            BinaryOperator::Divide => left.checked_div(right),
+            BinaryOperator::Modulo => left.checked_rem(right),
+            BinaryOperator::BitwiseAnd => Some(left & right),
+            BinaryOperator::BitwiseOr => Some(left | right),
+            BinaryOperator::BitwiseXor => Some(left ^ right),
+            BinaryOperator::BitwiseLeftShift => u32::try_from(right)
+                .ok()
+                .and_then(|right| left.checked_shl(right)),
+            BinaryOperator::BitwiseRightShift => u32::try_from(right)
+                .ok()
+                .and_then(|right| left.checked_shr(right)),
+            operator => {
+                return compare(left.partial_cmp(&right), operator).ok_or(format!(
+                    "Cannot apply '{}' to '{}' and '{}'",
+                    operator, left, right
+                ))
+            }
+        };
+
+        result
+            .map(Value::$variant)
+            .ok_or("Integer overflow".to_string())
+    }};
+}
+
+fn binary(left: Value, operator: &BinaryOperator, right: Value) -> Result<Value, String> {
+    match operator {
+        BinaryOperator::Equal => return Ok(Value::Bool(left == right)),
+        BinaryOperator::NotEqual => return Ok(Value::Bool(left != right)),
+        _ => {}
+    }
+
+    match (left, right) {
+        (Value::Int(left), Value::Int(right)) => integer_binary!(Int, left, operator, right),
+        (Value::UInt(left), Value::UInt(right)) => integer_binary!(UInt, left, operator, right),
+        (Value::Float(left), Value::Float(right)) => match operator {
+            BinaryOperator::Add => Ok(Value::Float(left + right)),
+            BinaryOperator::Subtract => Ok(Value::Float(left - right)),
+            BinaryOperator::Multiply => Ok(Value::Float(left * right)),
+            BinaryOperator::Divide => Ok(Value::Float(left / right)),
+            BinaryOperator::Modulo => Ok(Value::Float(left % right)),
+            operator => compare(left.partial_cmp(&right), operator).ok_or(format!(
+                "Cannot apply '{}' to '{}' and '{}'",
+                operator, left, right
+            )),
+        },
+        (Value::Bool(left), Value::Bool(right)) => match operator {
+            BinaryOperator::LogicalAnd | BinaryOperator::BitwiseAnd => {
+                Ok(Value::Bool(left & right))
+            }
+            BinaryOperator::LogicalOr | BinaryOperator::BitwiseOr => Ok(Value::Bool(left | right)),
+            BinaryOperator::BitwiseXor => Ok(Value::Bool(left ^ right)),
+            operator => Err(format!(
+                "Cannot apply '{}' to '{}' and '{}'",
+                operator, left, right
+            )),
+        },
+        (Value::String(left), Value::String(right)) => match operator {
+            BinaryOperator::Add => Ok(Value::String(left + &right)),
+            operator => compare(left.partial_cmp(&right), operator).ok_or(format!(
+                "Cannot apply '{}' to '{}' and '{}'",
+                operator, left, right
+            )),
+        },
+        (Value::Char(left), Value::Char(right)) => compare(left.partial_cmp(&right), operator)
+            .ok_or(format!(
+                "Cannot apply '{}' to '{}' and '{}'",
+                operator, left, right
+            )),
+        (left, right) => Err(format!(
+            "Cannot apply '{}' to '{}' and '{}'",
+            operator, left, right
+        )),
+    }
+}
+
+fn compare_with_pattern(value: &Value, pattern: &Pattern) -> Result<Ordering, String> {
+    let ordering = match (value, pattern) {
+        (Value::Int(v), Pattern::Int(p)) => v.partial_cmp(p),
+        (Value::UInt(v), Pattern::UInt(p)) => v.partial_cmp(p),
+        (Value::Float(v), Pattern::Float(p)) => v.partial_cmp(p),
+        (Value::Char(v), Pattern::Char(p)) => v.partial_cmp(p),
+        _ => None,
+    };
+
+    ordering.ok_or(format!("Cannot compare '{}' with '{}'", value, pattern))
+}
+
+fn matches(pattern: &Pattern, value: &Value) -> Result<bool, String> {
+    let is_match = match (pattern, value) {
+        (Pattern::Wildcard, _) | (Pattern::Variable(_), _) => true,
+        (Pattern::Unit, Value::Unit) => true,
+        (Pattern::Bool(p), Value::Bool(v)) => p == v,
+        (Pattern::Int(p), Value::Int(v)) => p == v,
+        (Pattern::UInt(p), Value::UInt(v)) => p == v,
+        (Pattern::Float(p), Value::Float(v)) => p == v,
+        (Pattern::Char(p), Value::Char(v)) => p == v,
+        (Pattern::String(p), Value::String(v)) => p == v,
+        (
+            Pattern::Constructor(Constructor::Struct {
+                type_annotation,
+                field_patterns,
+            }),
+            Value::Struct { name, .. } | Value::Enum { member: name, .. },
+        ) => {
+            let pattern_name = type_annotation.name();
+
+            // Enum members may be written with their enum as a prefix
+            if pattern_name.rsplit("::").next() != Some(name.as_str()) {
+                return Ok(false);
+            }
+
+            for field_pattern in field_patterns {
+                if !matches(
+                    &field_pattern.pattern,
+                    field(value, &field_pattern.identifier)?,
+                )? {
+                    return Ok(false);
+                }
+            }
+
+            true
+        }
+        (Pattern::LessThan(p), value) => compare_with_pattern(value, p)?.is_lt(),
+        (Pattern::GreaterThan(p), value) => compare_with_pattern(value, p)?.is_gt(),
+        (Pattern::LessThanOrEqual(p), value) => compare_with_pattern(value, p)?.is_le(),
+        (Pattern::GreaterThanOrEqual(p), value) => compare_with_pattern(value, p)?.is_ge(),
+        (Pattern::Range(start, end, inclusive), value) => {
+            let end = compare_with_pattern(value, end)?;
+
+            compare_with_pattern(value, start)?.is_ge()
+                && if *inclusive { end.is_le() } else { end.is_lt() }
+        }
+        (pattern, value) => {
+            return Err(format!(
+                "Cannot match '{}' against the pattern '{}'",
+                value, pattern
+            ))
+        }
+    };
+
+    Ok(is_match)
+}
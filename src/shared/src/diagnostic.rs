@@ -0,0 +1,118 @@
+use std::fmt::Display;
+
+use crate::span::Span;
+
+/// How serious a diagnostic is. Only errors stop a program from running.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Info => write!(f, "info"),
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// A problem with the source and where it was found
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// The name of the check a warning comes from, like `unused_variable`, which its severity can
+    /// be configured by. Errors have no category.
+    pub category: Option<String>,
+    pub message: String,
+    pub span: Span,
+}
+
+impl Diagnostic {
+    /// An error
+    pub fn new(message: impl Into<String>, span: Span) -> Self {
+        Self {
+            severity: Severity::Error,
+            category: None,
+            message: message.into(),
+            span,
+        }
+    }
+
+    pub fn warning(category: impl Into<String>, message: impl Into<String>, span: Span) -> Self {
+        Self {
+            severity: Severity::Warning,
+            category: Some(category.into()),
+            message: message.into(),
+            span,
+        }
+    }
+}
+
+/// Render the diagnostic with its severity and the line of the source it was found on, underlining the span with
+/// `^` like this:
+///
+/// ```text
+/// error: Unexpected variable: lenth
+///  --> 1:17
+///   |
+/// 1 | let length = 5; lenth + 1
+///   |                 ^^^^^
+/// ```
+///
+/// A span over several lines is underlined to the end of its first line, followed by `...`.
+/// An empty span is a single `^` where it starts.
+pub fn render_diagnostic(source: &str, diagnostic: &Diagnostic) -> String {
+    let start = floor_char_boundary(source, diagnostic.span.start);
+    let end = floor_char_boundary(source, diagnostic.span.end.max(start));
+
+    let line_start = source[..start].rfind('\n').map_or(0, |index| index + 1);
+    let line_end = source[start..]
+        .find('\n')
+        .map_or(source.len(), |index| start + index);
+
+    let line = source[line_start..line_end].trim_end_matches('\r');
+    let line_number = source[..start].matches('\n').count() + 1;
+    let column = source[line_start..start].chars().count() + 1;
+
+    // Tabs are kept so the underline lines up however wide they are shown
+    let indent: String = source[line_start..start]
+        .chars()
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect();
+
+    let underlined = source[start..end.min(line_end)].chars().count().max(1);
+    let continuation = if end > line_end { "..." } else { "" };
+
+    let gutter = " ".repeat(line_number.to_string().len());
+
+    format!(
+        "{}: {}\n{}--> {}:{}\n{} |\n{} | {}\n{} | {}{}{}",
+        diagnostic.severity,
+        diagnostic.message,
+        gutter,
+        line_number,
+        column,
+        gutter,
+        line_number,
+        line,
+        gutter,
+        indent,
+        "^".repeat(underlined),
+        continuation,
+    )
+}
+
+/// The largest offset up to `offset` which is in the source and starts a character
+fn floor_char_boundary(source: &str, offset: usize) -> usize {
+    let mut offset = offset.min(source.len());
+
+    while !source.is_char_boundary(offset) {
+        offset -= 1;
+    }
+
+    offset
+}
@@ -1,10 +1,11 @@
 use crate::{
     parser::{
-        AccessModifier, Assignment, AssociatedType, Binary, BinaryOperator, Call, ClosureParameter,
-        EnumDeclaration, EnumMember, EnumMemberField, EnumMemberFieldInitializers, Expression,
-        FieldInitializer, FlagsMember, For, FunctionDeclaration, If, ImplementationDeclaration,
-        Literal, Match, MatchArm, Member, ModuleDeclaration, Parameter, ProtocolDeclaration,
-        Statement, StructDeclaration, StructField, TypeAliasDeclaration, Unary, UnaryOperator,
+        AccessModifier, Assignment, AssociatedType, Attribute, Binary, BinaryOperator, Call, Cast,
+        ClosureParameter, Coalesce, EnumDeclaration, EnumMember, EnumMemberField,
+        EnumMemberFieldInitializers, Expression, FieldInitializer, FlagsMember, For,
+        FunctionDeclaration, If, ImplementationDeclaration, Index, Literal, Match, MatchArm,
+        Member, ModuleDeclaration, NewtypeDeclaration, Parameter, ProtocolDeclaration, Statement,
+        StructDeclaration, StructField, TypeAliasDeclaration, Unary, UnaryOperator,
         UnionDeclaration, Use, UseItem, VariableDeclaration, While,
     },
     type_checker::{
@@ -19,13 +20,175 @@ use crate::{
     types::{GenericConstraint, GenericType, TypeAnnotation, TypeIdentifier},
 };
 
-pub struct Indent {
+/// Marks where the display of a node put off by `Indent::display` goes. U+FFFF is a
+/// noncharacter, so it isn't written in source text.
+const DEFERRED: char = '\u{FFFF}';
+
+pub struct Indent<'a> {
     levels: Vec<bool>,
+    /// The nodes reached while `display` works through a tree, with the levels they are displayed
+    /// at. Their displays are put together once every node is displayed.
+    deferred: Option<Vec<(&'a dyn IndentDisplay, Vec<bool>)>>,
+    /// Whether integers are displayed with a `_` between every three digits
+    thousands_separators: bool,
+    /// Whether the names of declared types are prefixed by the path of their module
+    module_paths: bool,
+    /// The module the program being displayed declares its items in
+    module: Vec<String>,
+    /// Whether the next type name displayed is the name of a declaration
+    declaration: bool,
 }
 
-impl Indent {
-    pub fn new() -> Indent {
-        Indent { levels: vec![] }
+impl<'a> Indent<'a> {
+    pub fn new() -> Indent<'a> {
+        Indent {
+            levels: vec![],
+            deferred: None,
+            thousands_separators: false,
+            module_paths: false,
+            module: vec![],
+            declaration: false,
+        }
+    }
+
+    pub fn with_thousands_separators(self, thousands_separators: bool) -> Indent<'a> {
+        Indent {
+            thousands_separators,
+            ..self
+        }
+    }
+
+    /// Display the names of declared types as `a::b::Point` instead of `Point`, which tells apart
+    /// the types of different modules with the same name
+    pub fn with_module_paths(self, module_paths: bool) -> Indent<'a> {
+        Indent {
+            module_paths,
+            ..self
+        }
+    }
+
+    fn owned<'b>(&self) -> Indent<'b> {
+        Indent {
+            levels: self.levels.clone(),
+            deferred: None,
+            thousands_separators: self.thousands_separators,
+            module_paths: self.module_paths,
+            module: self.module.clone(),
+            declaration: false,
+        }
+    }
+
+    /// Display a node made while displaying the tree, which doesn't live as long as the nodes
+    /// of the tree do
+    fn display_owned(&self, node: &impl IndentDisplay) -> String {
+        self.owned().display(node)
+    }
+
+    /// Display the name a type is declared with, in the module of the program
+    fn display_declared(&self, type_identifier: &TypeIdentifier) -> String {
+        let mut indent = self.owned();
+        indent.declaration = true;
+        indent.display(type_identifier)
+    }
+
+    /// The name of a type, prefixed by the module path when it is the name of a declaration and
+    /// module paths are displayed
+    fn type_name(&mut self, type_identifier: &TypeIdentifier) -> String {
+        let declaration = std::mem::take(&mut self.declaration);
+
+        if declaration && self.module_paths && !self.module.is_empty() {
+            format!("{}::{}", self.module.join("::"), type_identifier)
+        } else {
+            type_identifier.to_string()
+        }
+    }
+
+    fn int(&self, value: impl ToString) -> String {
+        let digits = value.to_string();
+
+        if !self.thousands_separators {
+            return digits;
+        }
+
+        let (sign, digits) = match digits.strip_prefix('-') {
+            Some(digits) => ("-", digits),
+            None => ("", digits.as_str()),
+        };
+
+        let mut result = sign.to_string();
+
+        for (i, digit) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i) % 3 == 0 {
+                result.push('_');
+            }
+
+            result.push(digit);
+        }
+
+        result
+    }
+
+    /// Display a tree without recursing, so a deep tree can't overflow the stack.
+    ///
+    /// A node reached while a tree is displayed is put on the work list and a marker is written
+    /// in its place. Once every node is displayed, the markers are replaced by the displays of
+    /// their nodes, working from a stack.
+    fn display(&mut self, node: &'a dyn IndentDisplay) -> String {
+        if let Some(deferred) = &mut self.deferred {
+            deferred.push((node, self.levels.clone()));
+            return deferred_marker(deferred.len() - 1);
+        }
+
+        let levels = self.levels.clone();
+        self.deferred = Some(vec![(node, levels.clone())]);
+
+        // The nodes deferred while displaying a node get the ids after the nodes deferred
+        // before it, so the children of every node have consecutive ids.
+        let mut displays = vec![];
+        let mut children = vec![];
+
+        while displays.len() < self.deferred.as_ref().map_or(0, Vec::len) {
+            let deferred = self
+                .deferred
+                .as_mut()
+                .expect("Deferred nodes are being displayed");
+            let (node, levels) = &mut deferred[displays.len()];
+            let node = *node;
+
+            self.levels = std::mem::take(levels);
+            let first_child = deferred.len();
+            displays.push(node.display_node(self));
+            children.push(first_child..self.deferred.as_ref().map_or(0, Vec::len));
+        }
+
+        self.deferred = None;
+        self.levels = levels;
+
+        let mut result = String::new();
+        let mut stack = vec![(0, 0, children[0].clone())];
+
+        while let Some((id, position, remaining)) = stack.last_mut() {
+            let display = &displays[*id];
+
+            let Some(child) = remaining.next() else {
+                result.push_str(&display[*position..]);
+                stack.pop();
+                continue;
+            };
+
+            let marker = deferred_marker(child);
+
+            // A node which doesn't use the display of a child has no marker for it
+            let Some(offset) = display[*position..].find(&marker) else {
+                continue;
+            };
+
+            result.push_str(&display[*position..*position + offset]);
+            *position += offset + marker.len();
+            stack.push((child, 0, children[child].clone()));
+        }
+
+        result
     }
 
     fn increase(&mut self) {
@@ -75,23 +238,62 @@ impl Indent {
     }
 }
 
-impl Default for Indent {
+impl Default for Indent<'_> {
     fn default() -> Self {
         Self::new()
     }
 }
 
+fn attributes_display(attributes: &[Attribute]) -> String {
+    attributes
+        .iter()
+        .map(|attribute| attribute.to_string())
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Display a float with a decimal point, so `1.0` doesn't read as the integer `1`
+fn float(value: f64) -> String {
+    let formatted = value.to_string();
+
+    if formatted.contains('.') || !value.is_finite() {
+        formatted
+    } else {
+        format!("{}.0", formatted)
+    }
+}
+
+fn deferred_marker(id: usize) -> String {
+    format!("{DEFERRED}{id}{DEFERRED}")
+}
+
 pub trait IndentDisplay {
-    fn indent_display(&self, indent: &mut Indent) -> String;
+    /// Display this node at the current indentation. The nodes in it are displayed through
+    /// `indent_display`, which leaves them for later instead of recursing.
+    fn display_node<'a>(&'a self, indent: &mut Indent<'a>) -> String;
+
+    fn indent_display<'a>(&'a self, indent: &mut Indent<'a>) -> String
+    where
+        Self: Sized,
+    {
+        indent.display(self)
+    }
 }
 
 impl IndentDisplay for Statement {
-    fn indent_display(&self, indent: &mut Indent) -> String {
+    fn display_node<'a>(&'a self, indent: &mut Indent<'a>) -> String {
         match self {
             Statement::Program { statements } => {
                 let mut result = String::new();
+                indent.module.clear();
 
                 for (i, statement) in statements.iter().enumerate() {
+                    if let Statement::ModuleDeclaration(ModuleDeclaration { module_path, .. }) =
+                        statement
+                    {
+                        indent.module = module_path.clone();
+                    }
+
                     result.push_str(&statement.indent_display(indent));
                     if i < statements.len() - 1 {
                         result.push_str("\n\n");
@@ -103,6 +305,7 @@ impl IndentDisplay for Statement {
             Statement::ModuleDeclaration(ModuleDeclaration {
                 access_modifier,
                 module_path,
+                ..
             }) => {
                 let mut result = String::new();
                 result.push_str("<module statement>\n");
@@ -143,20 +346,42 @@ impl IndentDisplay for Statement {
                 result
             }
             Statement::StructDeclaration(StructDeclaration {
+                doc,
+                attributes,
                 access_modifier,
                 type_identifier,
                 where_clause,
                 fields,
+                ..
             }) => {
                 let mut result = String::new();
-                result.push_str("<struct declaration>\n");
+
+                if fields.first().is_some_and(StructField::is_positional) {
+                    result.push_str("<tuple struct declaration>\n");
+                } else {
+                    result.push_str("<struct declaration>\n");
+                }
+
                 indent.increase();
+                if let Some(doc) = doc {
+                    result.push_str(format!("{}doc: {:?}\n", indent.dash(), doc).as_str());
+                }
+                if !attributes.is_empty() {
+                    result.push_str(
+                        format!(
+                            "{}attributes: {}\n",
+                            indent.dash(),
+                            attributes_display(attributes)
+                        )
+                        .as_str(),
+                    );
+                }
 
                 result.push_str(
                     format!(
                         "{}type_name: {}\n",
                         indent.dash(),
-                        type_identifier.indent_display(indent)
+                        indent.display_declared(type_identifier)
                     )
                     .as_str(),
                 );
@@ -205,21 +430,37 @@ impl IndentDisplay for Statement {
                 result
             }
             Statement::EnumDeclaration(EnumDeclaration {
+                doc,
+                attributes,
                 access_modifier,
                 type_identifier,
                 where_clause,
                 shared_fields,
                 members,
+                ..
             }) => {
                 let mut result = String::new();
                 result.push_str("<enum declaration>\n");
                 indent.increase();
+                if let Some(doc) = doc {
+                    result.push_str(format!("{}doc: {:?}\n", indent.dash(), doc).as_str());
+                }
+                if !attributes.is_empty() {
+                    result.push_str(
+                        format!(
+                            "{}attributes: {}\n",
+                            indent.dash(),
+                            attributes_display(attributes)
+                        )
+                        .as_str(),
+                    );
+                }
 
                 result.push_str(
                     format!(
                         "{}type_name: {}\n",
                         indent.dash(),
-                        type_identifier.indent_display(indent)
+                        indent.display_declared(type_identifier)
                     )
                     .as_str(),
                 );
@@ -283,6 +524,8 @@ impl IndentDisplay for Statement {
                 result
             }
             Statement::UnionDeclaration(UnionDeclaration {
+                doc,
+                attributes,
                 access_modifier,
                 type_identifier,
                 literals,
@@ -290,6 +533,19 @@ impl IndentDisplay for Statement {
                 let mut result = String::new();
                 result.push_str("<union declaration>");
                 indent.increase();
+                if let Some(doc) = doc {
+                    result.push_str(format!("\n{}doc: {:?}", indent.dash(), doc).as_str());
+                }
+                if !attributes.is_empty() {
+                    result.push_str(
+                        format!(
+                            "\n{}attributes: {}",
+                            indent.dash(),
+                            attributes_display(attributes)
+                        )
+                        .as_str(),
+                    );
+                }
 
                 result.push_str(
                     format!(
@@ -304,7 +560,7 @@ impl IndentDisplay for Statement {
                     format!(
                         "\n{}type_name: {}",
                         indent.dash(),
-                        type_identifier.indent_display(indent)
+                        indent.display_declared(type_identifier)
                     )
                     .as_str(),
                 );
@@ -342,6 +598,8 @@ impl IndentDisplay for Statement {
             //     result
             // },
             Statement::TypeAliasDeclaration(TypeAliasDeclaration {
+                doc,
+                attributes,
                 access_modifier,
                 type_identifier,
                 type_annotations,
@@ -349,6 +607,19 @@ impl IndentDisplay for Statement {
                 let mut result = String::new();
                 result.push_str("<type alias declaration>");
                 indent.increase();
+                if let Some(doc) = doc {
+                    result.push_str(format!("\n{}doc: {:?}", indent.dash(), doc).as_str());
+                }
+                if !attributes.is_empty() {
+                    result.push_str(
+                        format!(
+                            "\n{}attributes: {}",
+                            indent.dash(),
+                            attributes_display(attributes)
+                        )
+                        .as_str(),
+                    );
+                }
 
                 result.push_str(
                     format!(
@@ -363,7 +634,7 @@ impl IndentDisplay for Statement {
                     format!(
                         "\n{}type_name: {}",
                         indent.dash(),
-                        type_identifier.indent_display(indent)
+                        indent.display_declared(type_identifier)
                     )
                     .as_str(),
                 );
@@ -386,7 +657,64 @@ impl IndentDisplay for Statement {
                 indent.decrease();
                 result
             }
+            Statement::NewtypeDeclaration(NewtypeDeclaration {
+                doc,
+                attributes,
+                access_modifier,
+                type_identifier,
+                type_annotation,
+            }) => {
+                let mut result = String::new();
+                result.push_str("<newtype declaration>");
+                indent.increase();
+                if let Some(doc) = doc {
+                    result.push_str(format!("\n{}doc: {:?}", indent.dash(), doc).as_str());
+                }
+                if !attributes.is_empty() {
+                    result.push_str(
+                        format!(
+                            "\n{}attributes: {}",
+                            indent.dash(),
+                            attributes_display(attributes)
+                        )
+                        .as_str(),
+                    );
+                }
+
+                result.push_str(
+                    format!(
+                        "\n{}access_modifier: {}",
+                        indent.dash(),
+                        access_modifier.indent_display(indent)
+                    )
+                    .as_str(),
+                );
+
+                result.push_str(
+                    format!(
+                        "\n{}type_name: {}",
+                        indent.dash(),
+                        indent.display_declared(type_identifier)
+                    )
+                    .as_str(),
+                );
+
+                indent.end_current();
+                result.push_str(
+                    format!(
+                        "\n{}type_annotation: {}",
+                        indent.dash_end(),
+                        type_annotation.indent_display(indent)
+                    )
+                    .as_str(),
+                );
+
+                indent.decrease();
+                result
+            }
             Statement::ProtocolDeclaration(ProtocolDeclaration {
+                doc,
+                attributes,
                 access_modifier,
                 type_identifier,
                 associated_types,
@@ -395,6 +723,19 @@ impl IndentDisplay for Statement {
                 let mut result = String::new();
                 result.push_str("<protocol declaration>");
                 indent.increase();
+                if let Some(doc) = doc {
+                    result.push_str(format!("\n{}doc: {:?}", indent.dash(), doc).as_str());
+                }
+                if !attributes.is_empty() {
+                    result.push_str(
+                        format!(
+                            "\n{}attributes: {}",
+                            indent.dash(),
+                            attributes_display(attributes)
+                        )
+                        .as_str(),
+                    );
+                }
 
                 result.push_str(
                     format!(
@@ -409,7 +750,7 @@ impl IndentDisplay for Statement {
                     format!(
                         "\n{}type_name: {}",
                         indent.dash(),
-                        type_identifier.indent_display(indent)
+                        indent.display_declared(type_identifier)
                     )
                     .as_str(),
                 );
@@ -443,6 +784,8 @@ impl IndentDisplay for Statement {
                 result
             }
             Statement::ImplementationDeclaration(ImplementationDeclaration {
+                doc,
+                attributes,
                 scoped_generics,
                 protocol_annotation,
                 type_annotation,
@@ -452,6 +795,19 @@ impl IndentDisplay for Statement {
                 let mut result = String::new();
                 result.push_str("<implementation declaration>");
                 indent.increase();
+                if let Some(doc) = doc {
+                    result.push_str(format!("\n{}doc: {:?}", indent.dash(), doc).as_str());
+                }
+                if !attributes.is_empty() {
+                    result.push_str(
+                        format!(
+                            "\n{}attributes: {}",
+                            indent.dash(),
+                            attributes_display(attributes)
+                        )
+                        .as_str(),
+                    );
+                }
 
                 result.push_str(
                     format!(
@@ -533,12 +889,13 @@ impl IndentDisplay for Statement {
                 result
             }
             Statement::Expression(e) => e.indent_display(indent),
+            Statement::Error(_) => "<error>".to_string(),
         }
     }
 }
 
 impl IndentDisplay for AssociatedType {
-    fn indent_display(&self, indent: &mut Indent) -> String {
+    fn display_node<'a>(&'a self, indent: &mut Indent<'a>) -> String {
         let mut result = String::new();
         result.push_str("<associated type>\n");
         indent.increase();
@@ -558,9 +915,10 @@ impl IndentDisplay for AssociatedType {
 }
 
 impl IndentDisplay for UseItem {
-    fn indent_display(&self, indent: &mut Indent) -> String {
+    fn display_node<'a>(&'a self, indent: &mut Indent<'a>) -> String {
         match self {
             UseItem::Item(item) => item.clone(),
+            UseItem::Glob => "<glob>".to_string(),
             UseItem::Navigation(item, next) => {
                 let mut result = String::new();
                 result.push_str(format!("<navigation> {}\n", item).as_str());
@@ -599,7 +957,7 @@ impl IndentDisplay for UseItem {
 }
 
 impl IndentDisplay for FunctionDeclaration {
-    fn indent_display(&self, indent: &mut Indent) -> String {
+    fn display_node<'a>(&'a self, indent: &mut Indent<'a>) -> String {
         let identifier = &self.type_identifier;
         let access_modifier = &self.access_modifier;
         let param = &self.param;
@@ -609,6 +967,19 @@ impl IndentDisplay for FunctionDeclaration {
         let mut result = String::new();
         result.push_str("<function declaration>\n");
         indent.increase();
+        if let Some(doc) = &self.doc {
+            result.push_str(format!("{}doc: {:?}\n", indent.dash(), doc).as_str());
+        }
+        if !self.attributes.is_empty() {
+            result.push_str(
+                format!(
+                    "{}attributes: {}\n",
+                    indent.dash(),
+                    attributes_display(&self.attributes)
+                )
+                .as_str(),
+            );
+        }
 
         result.push_str(format!("{}identifier: {}\n", indent.dash(), identifier).as_str());
         result.push_str(
@@ -643,7 +1014,7 @@ impl IndentDisplay for FunctionDeclaration {
 }
 
 impl IndentDisplay for Expression {
-    fn indent_display(&self, indent: &mut Indent) -> String {
+    fn display_node<'a>(&'a self, indent: &mut Indent<'a>) -> String {
         match self {
             // Expression::None => String::new(),
             Expression::VariableDeclaration(VariableDeclaration {
@@ -651,6 +1022,7 @@ impl IndentDisplay for Expression {
                 type_annotation,
                 pattern,
                 initializer,
+                ..
             }) => {
                 let mut result = String::new();
                 result.push_str("<variable declaration>\n");
@@ -721,7 +1093,9 @@ impl IndentDisplay for Expression {
                 indent.decrease();
                 result
             }
-            Expression::Match(Match { expression, arms }) => {
+            Expression::Match(Match {
+                expression, arms, ..
+            }) => {
                 let mut result = String::new();
                 result.push_str("<match>");
                 indent.increase();
@@ -753,6 +1127,7 @@ impl IndentDisplay for Expression {
             }
             Expression::Assignment(Assignment {
                 member,
+                operator,
                 initializer,
             }) => {
                 let mut result = String::new();
@@ -766,6 +1141,18 @@ impl IndentDisplay for Expression {
                     )
                     .as_str(),
                 );
+
+                if let Some(operator) = operator {
+                    result.push_str(
+                        format!(
+                            "{}operator: {}=\n",
+                            indent.dash(),
+                            operator.indent_display(indent)
+                        )
+                        .as_str(),
+                    );
+                }
+
                 indent.end_current();
                 result.push_str(
                     format!(
@@ -779,7 +1166,7 @@ impl IndentDisplay for Expression {
                 result
             }
             Expression::Member(m) => m.indent_display(indent),
-            Expression::Literal(l) => l.indent_display(indent),
+            Expression::Literal(l, _) => l.indent_display(indent),
             Expression::Tuple(e) => {
                 let mut result = String::new();
                 result.push_str("<tuple>");
@@ -794,6 +1181,20 @@ impl IndentDisplay for Expression {
 
                 result
             }
+            Expression::Record(fields) => {
+                let mut result = String::new();
+                result.push_str("<record>");
+                result.push_str(
+                    format!(
+                        "\n{}{}",
+                        indent.dash(),
+                        indent_display_slice(fields, "fields", "field", indent).as_str()
+                    )
+                    .as_str(),
+                );
+
+                result
+            }
             Expression::Closure(c) => {
                 let mut result = String::new();
                 result.push_str("<closure>\n");
@@ -826,7 +1227,9 @@ impl IndentDisplay for Expression {
                 indent.decrease();
                 result
             }
-            Expression::Call(Call { callee, argument }) => {
+            Expression::Call(Call {
+                callee, argument, ..
+            }) => {
                 let mut result = String::new();
                 result.push_str("<call>\n");
                 indent.increase();
@@ -847,9 +1250,33 @@ impl IndentDisplay for Expression {
                 indent.decrease();
                 result
             }
+            Expression::Index(Index {
+                callee, argument, ..
+            }) => {
+                let mut result = String::new();
+                result.push_str("<index>\n");
+                indent.increase();
+                result.push_str(
+                    format!("{}callee: {}", indent.dash(), callee.indent_display(indent)).as_str(),
+                );
+
+                indent.end_current();
+                result.push_str(
+                    format!(
+                        "\n{}index: {}",
+                        indent.dash_end(),
+                        argument.indent_display(indent)
+                    )
+                    .as_str(),
+                );
+
+                indent.decrease();
+                result
+            }
             Expression::Unary(Unary {
                 operator,
                 expression,
+                ..
             }) => {
                 let mut result = String::new();
                 result.push_str("<unary>\n");
@@ -878,6 +1305,7 @@ impl IndentDisplay for Expression {
                 left,
                 operator,
                 right,
+                ..
             }) => {
                 let mut result = String::new();
                 result.push_str("<binary>\n");
@@ -905,6 +1333,51 @@ impl IndentDisplay for Expression {
                 indent.decrease();
                 result
             }
+            Expression::Coalesce(Coalesce { value, default }) => {
+                let mut result = String::new();
+                result.push_str("<coalesce>\n");
+                indent.increase();
+                result.push_str(
+                    format!("{}value: {}\n", indent.dash(), value.indent_display(indent)).as_str(),
+                );
+                indent.end_current();
+                result.push_str(
+                    format!(
+                        "{}default: {}",
+                        indent.dash_end(),
+                        default.indent_display(indent)
+                    )
+                    .as_str(),
+                );
+                indent.decrease();
+                result
+            }
+            Expression::Cast(Cast {
+                expression, target, ..
+            }) => {
+                let mut result = String::new();
+                result.push_str("<cast>\n");
+                indent.increase();
+                result.push_str(
+                    format!(
+                        "{}expression: {}\n",
+                        indent.dash(),
+                        expression.indent_display(indent)
+                    )
+                    .as_str(),
+                );
+                indent.end_current();
+                result.push_str(
+                    format!(
+                        "{}target: {}",
+                        indent.dash_end(),
+                        target.indent_display(indent)
+                    )
+                    .as_str(),
+                );
+                indent.decrease();
+                result
+            }
             Expression::Block(statements) => {
                 let mut result = String::new();
                 result.push_str("<block>");
@@ -949,6 +1422,21 @@ impl IndentDisplay for Expression {
                 result.push_str(format!("<drop> {}", identifier).as_str());
                 result
             }
+            Expression::Try(expression) => {
+                let mut result = String::new();
+                result.push_str("<try>");
+                indent.increase_leaf();
+                result.push_str(
+                    format!(
+                        "\n{}expression: {}",
+                        indent.dash_end(),
+                        expression.indent_display(indent)
+                    )
+                    .as_str(),
+                );
+                indent.decrease();
+                result
+            }
             Expression::Loop(body) => {
                 let mut result = String::new();
                 result.push_str("<loop>");
@@ -1013,6 +1501,7 @@ impl IndentDisplay for Expression {
                 iterable,
                 body,
                 else_body,
+                ..
             }) => {
                 let mut result = String::new();
                 result.push_str("<for>");
@@ -1096,9 +1585,28 @@ impl IndentDisplay for Expression {
 }
 
 impl IndentDisplay for Member {
-    fn indent_display(&self, indent: &mut Indent) -> String {
+    fn display_node<'a>(&'a self, indent: &mut Indent<'a>) -> String {
         match self {
-            Member::Identifier { symbol, generics } => {
+            Member::Path(path, _) => {
+                let mut result = String::new();
+                result.push_str("<path>");
+                indent.increase();
+
+                for (i, segment) in path.iter().enumerate() {
+                    if i < path.len() - 1 {
+                        result.push_str(format!("\n{}{}", indent.dash(), segment).as_str());
+                    } else {
+                        indent.end_current();
+                        result.push_str(format!("\n{}{}", indent.dash_end(), segment).as_str());
+                    }
+                }
+
+                indent.decrease();
+                result
+            }
+            Member::Identifier {
+                symbol, generics, ..
+            } => {
                 let mut result = String::new();
                 result.push_str(format!("<identifier> {}", symbol).as_str());
 
@@ -1170,6 +1678,7 @@ impl IndentDisplay for Member {
                 member,
                 symbol,
                 generics,
+                optional,
             } => {
                 let mut result = String::new();
                 result.push_str("<member access>\n");
@@ -1193,6 +1702,7 @@ impl IndentDisplay for Member {
                 );
 
                 result.push_str(format!("{}symbol: {}\n", indent.dash(), symbol).as_str());
+                result.push_str(format!("{}optional: {}\n", indent.dash(), optional).as_str());
 
                 indent.end_current();
 
@@ -1247,15 +1757,34 @@ impl IndentDisplay for Member {
 }
 
 impl IndentDisplay for Literal {
-    fn indent_display(&self, indent: &mut Indent) -> String {
+    fn display_node<'a>(&'a self, indent: &mut Indent<'a>) -> String {
         match self {
             Literal::Unit => "unit".to_string(),
-            Literal::Int(v) => v.to_string(),
-            Literal::UInt(v) => v.to_string(),
-            Literal::Float(v) => v.to_string(),
+            Literal::Int(v) => indent.int(v),
+            Literal::UInt(v) => indent.int(v),
+            Literal::Float(v) => float(*v),
             Literal::String(s) => s.to_string(),
             Literal::Char(c) => c.to_string(),
             Literal::Bool(b) => b.to_string(),
+            Literal::ArrayRepeat { value, length } => {
+                let mut result = String::new();
+                result.push_str("<array repeat>\n");
+                indent.increase();
+                result.push_str(
+                    format!("{}value: {}\n", indent.dash(), value.indent_display(indent)).as_str(),
+                );
+                indent.end_current();
+                result.push_str(
+                    format!(
+                        "{}length: {}",
+                        indent.dash_end(),
+                        length.indent_display(indent)
+                    )
+                    .as_str(),
+                );
+                indent.decrease();
+                result
+            }
             Literal::Array(expressions) => {
                 let mut result = String::new();
                 result.push_str("<array>");
@@ -1286,6 +1815,7 @@ impl IndentDisplay for Literal {
             Literal::Struct {
                 type_annotation: type_identifier,
                 field_initializers,
+                spread,
             } => {
                 let mut result = String::new();
                 result.push_str("<struct literal>\n");
@@ -1300,7 +1830,7 @@ impl IndentDisplay for Literal {
                 );
 
                 for (i, field) in field_initializers.iter().enumerate() {
-                    if i < field_initializers.len() - 1 {
+                    if i < field_initializers.len() - 1 || spread.is_some() {
                         result.push_str(
                             format!("\n{}{},", indent.dash(), field.indent_display(indent))
                                 .as_str(),
@@ -1314,6 +1844,18 @@ impl IndentDisplay for Literal {
                     }
                 }
 
+                if let Some(spread) = spread {
+                    indent.end_current();
+                    result.push_str(
+                        format!(
+                            "\n{}<spread> {}",
+                            indent.dash_end(),
+                            spread.indent_display(indent)
+                        )
+                        .as_str(),
+                    );
+                }
+
                 indent.decrease();
                 result
             }
@@ -1333,6 +1875,13 @@ impl IndentDisplay for Literal {
                     )
                     .as_str(),
                 );
+                // A member without fields ends the literal
+                if let EnumMemberFieldInitializers::None = field_initializers {
+                    result.push_str(format!("{}member: {}", indent.dash_end(), member).as_str());
+                    indent.decrease();
+                    return result;
+                }
+
                 result.push_str(format!("{}member: {}\n", indent.dash_end(), member).as_str());
                 indent.increase_leaf();
                 result.push_str(
@@ -1352,7 +1901,7 @@ impl IndentDisplay for Literal {
 }
 
 impl IndentDisplay for StructField {
-    fn indent_display(&self, indent: &mut Indent) -> String {
+    fn display_node<'a>(&'a self, indent: &mut Indent<'a>) -> String {
         let mut result = String::new();
         result.push_str(format!("<struct field> {}\n", self.identifier).as_str());
         indent.increase();
@@ -1378,15 +1927,29 @@ impl IndentDisplay for StructField {
             )
             .as_str(),
         );
+        result.push_str(format!("{}mutable: {}\n", indent.dash(), self.mutable).as_str());
         indent.end_current();
-        result.push_str(format!("{}mutable: {}", indent.dash_end(), self.mutable).as_str());
+
+        if let Some(default) = &self.default {
+            result.push_str(
+                format!(
+                    "{}default: {}",
+                    indent.dash_end(),
+                    default.indent_display(indent)
+                )
+                .as_str(),
+            );
+        } else {
+            result.push_str(format!("{}default: None", indent.dash_end()).as_str());
+        }
+
         indent.decrease();
         result
     }
 }
 
 impl IndentDisplay for EnumMember {
-    fn indent_display(&self, indent: &mut Indent) -> String {
+    fn display_node<'a>(&'a self, indent: &mut Indent<'a>) -> String {
         let mut result = String::new();
         result.push_str(format!("<enum member> {}", self.identifier).as_str());
         indent.increase();
@@ -1410,7 +1973,7 @@ impl IndentDisplay for EnumMember {
 }
 
 impl IndentDisplay for EnumMemberField {
-    fn indent_display(&self, indent: &mut Indent) -> String {
+    fn display_node<'a>(&'a self, indent: &mut Indent<'a>) -> String {
         let mut result = String::new();
         result.push_str("<enum member field>\n");
         indent.increase_leaf();
@@ -1429,7 +1992,7 @@ impl IndentDisplay for EnumMemberField {
 }
 
 impl IndentDisplay for FlagsMember {
-    fn indent_display(&self, indent: &mut Indent) -> String {
+    fn display_node<'a>(&'a self, indent: &mut Indent<'a>) -> String {
         let mut result = String::new();
         result.push_str("<flags member>\n");
         indent.increase_leaf();
@@ -1441,7 +2004,7 @@ impl IndentDisplay for FlagsMember {
 }
 
 impl IndentDisplay for AccessModifier {
-    fn indent_display(&self, _indent: &mut Indent) -> String {
+    fn display_node<'a>(&'a self, _indent: &mut Indent<'a>) -> String {
         match self {
             AccessModifier::Public => "public".to_string(),
             AccessModifier::Module => "module".to_string(),
@@ -1451,7 +2014,7 @@ impl IndentDisplay for AccessModifier {
 }
 
 impl IndentDisplay for FieldInitializer {
-    fn indent_display(&self, indent: &mut Indent) -> String {
+    fn display_node<'a>(&'a self, indent: &mut Indent<'a>) -> String {
         let mut result = String::new();
         result.push_str("<field initializer>\n");
         indent.increase();
@@ -1478,7 +2041,7 @@ impl IndentDisplay for FieldInitializer {
 }
 
 impl IndentDisplay for EnumMemberFieldInitializers {
-    fn indent_display(&self, indent: &mut Indent) -> String {
+    fn display_node<'a>(&'a self, indent: &mut Indent<'a>) -> String {
         match self {
             EnumMemberFieldInitializers::None => "".to_string(),
             EnumMemberFieldInitializers::Named(field_initializers) => {
@@ -1519,7 +2082,7 @@ impl IndentDisplay for EnumMemberFieldInitializers {
 }
 
 impl IndentDisplay for Parameter {
-    fn indent_display(&self, indent: &mut Indent) -> String {
+    fn display_node<'a>(&'a self, indent: &mut Indent<'a>) -> String {
         let mut result = String::new();
         result.push_str("<parameter>\n");
         indent.increase_leaf();
@@ -1545,7 +2108,7 @@ impl IndentDisplay for Parameter {
 }
 
 impl IndentDisplay for ClosureParameter {
-    fn indent_display(&self, indent: &mut Indent) -> String {
+    fn display_node<'a>(&'a self, indent: &mut Indent<'a>) -> String {
         let mut result = String::new();
         result.push_str("<parameter>\n");
         indent.increase_leaf();
@@ -1571,7 +2134,7 @@ impl IndentDisplay for ClosureParameter {
 }
 
 impl IndentDisplay for UnaryOperator {
-    fn indent_display(&self, _indent: &mut Indent) -> String {
+    fn display_node<'a>(&'a self, _indent: &mut Indent<'a>) -> String {
         match self {
             UnaryOperator::Identity => "+".to_string(),
             UnaryOperator::Negate => "-".to_string(),
@@ -1582,7 +2145,7 @@ impl IndentDisplay for UnaryOperator {
 }
 
 impl IndentDisplay for BinaryOperator {
-    fn indent_display(&self, _indent: &mut Indent) -> String {
+    fn display_node<'a>(&'a self, _indent: &mut Indent<'a>) -> String {
         match self {
             BinaryOperator::Add => "+".to_string(),
             BinaryOperator::Subtract => "-".to_string(),
@@ -1609,7 +2172,7 @@ impl IndentDisplay for BinaryOperator {
 }
 
 impl IndentDisplay for MatchArm {
-    fn indent_display(&self, indent: &mut Indent) -> String {
+    fn display_node<'a>(&'a self, indent: &mut Indent<'a>) -> String {
         let mut result = String::new();
         result.push_str("<match arm>");
         indent.increase();
@@ -1636,14 +2199,14 @@ impl IndentDisplay for MatchArm {
 }
 
 impl IndentDisplay for Pattern {
-    fn indent_display(&self, indent: &mut Indent) -> String {
+    fn display_node<'a>(&'a self, indent: &mut Indent<'a>) -> String {
         match self {
             Pattern::Wildcard => "_".to_string(),
             Pattern::Unit => "unit".to_string(),
             Pattern::Bool(b) => b.to_string(),
-            Pattern::Int(v) => v.to_string(),
-            Pattern::UInt(v) => v.to_string(),
-            Pattern::Float(v) => v.to_string(),
+            Pattern::Int(v) => indent.int(v),
+            Pattern::UInt(v) => indent.int(v),
+            Pattern::Float(v) => float(*v),
             Pattern::Char(c) => c.to_string(),
             Pattern::String(s) => s.to_string(),
             Pattern::Variable(v) => v.to_string(),
@@ -1655,6 +2218,21 @@ impl IndentDisplay for Pattern {
 
                 result.push_str("<struct pattern>");
                 indent.increase();
+
+                if field_patterns.is_empty() {
+                    indent.end_current();
+                    result.push_str(
+                        format!(
+                            "\n{}type_annotation: {}",
+                            indent.dash_end(),
+                            type_annotation.indent_display(indent)
+                        )
+                        .as_str(),
+                    );
+                    indent.decrease();
+                    return result;
+                }
+
                 result.push_str(
                     format!(
                         "\n{}type_annotation: {}",
@@ -1752,7 +2330,7 @@ impl IndentDisplay for Pattern {
 }
 
 impl IndentDisplay for FieldPattern {
-    fn indent_display(&self, indent: &mut Indent) -> String {
+    fn display_node<'a>(&'a self, indent: &mut Indent<'a>) -> String {
         let mut result = String::new();
         result.push_str("<field pattern>\n");
         indent.increase_leaf();
@@ -1779,13 +2357,18 @@ impl IndentDisplay for FieldPattern {
 }
 
 impl IndentDisplay for TypedStatement {
-    fn indent_display(&self, indent: &mut Indent) -> String {
+    fn display_node<'a>(&'a self, indent: &mut Indent<'a>) -> String {
         match self {
             TypedStatement::None => String::new(),
             TypedStatement::Program { statements } => {
                 let mut result = String::new();
+                indent.module.clear();
 
                 for (i, statement) in statements.iter().enumerate() {
+                    if let TypedStatement::ModuleDeclaration { module_path, .. } = statement {
+                        indent.module = module_path.clone();
+                    }
+
                     result.push_str(&statement.indent_display(indent));
                     if i < statements.len() - 1 {
                         result.push_str("\n\n");
@@ -1838,14 +2421,23 @@ impl IndentDisplay for TypedStatement {
                 type_,
             } => {
                 let mut result = String::new();
-                result.push_str(format!("<struct declaration> {}\n", type_).as_str());
+
+                if fields
+                    .first()
+                    .is_some_and(type_checker::ast::StructField::is_positional)
+                {
+                    result.push_str(format!("<tuple struct declaration> {}\n", type_).as_str());
+                } else {
+                    result.push_str(format!("<struct declaration> {}\n", type_).as_str());
+                }
+
                 indent.increase();
 
                 result.push_str(
                     format!(
                         "{}type_name: {}",
                         indent.dash(),
-                        type_identifier.indent_display(indent)
+                        indent.display_declared(type_identifier)
                     )
                     .as_str(),
                 );
@@ -1883,6 +2475,7 @@ impl IndentDisplay for TypedStatement {
                 shared_fields,
                 members,
                 type_,
+                ..
             } => {
                 let mut result = String::new();
                 result.push_str(format!("<enum declaration> {}\n", type_).as_str());
@@ -1892,7 +2485,7 @@ impl IndentDisplay for TypedStatement {
                     format!(
                         "{}type_name: {}",
                         indent.dash(),
-                        type_identifier.indent_display(indent)
+                        indent.display_declared(type_identifier)
                     )
                     .as_str(),
                 );
@@ -1951,7 +2544,7 @@ impl IndentDisplay for TypedStatement {
                     format!(
                         "\n{}type_name: {}",
                         indent.dash(),
-                        type_identifier.indent_display(indent)
+                        indent.display_declared(type_identifier)
                     )
                     .as_str(),
                 );
@@ -1988,7 +2581,7 @@ impl IndentDisplay for TypedStatement {
                     format!(
                         "{}type_name: {}\n",
                         indent.dash(),
-                        type_identifier.indent_display(indent)
+                        indent.display_declared(type_identifier)
                     )
                     .as_str(),
                 );
@@ -2085,11 +2678,7 @@ impl IndentDisplay for TypedStatement {
                         "{}functions: {}",
                         indent.dash_end(),
                         indent_display_slice(
-                            &functions
-                                .clone()
-                                .into_iter()
-                                .map(|f| f.1)
-                                .collect::<Vec<_>>(),
+                            functions.iter().map(|(_, function)| function),
                             "functions",
                             "function",
                             indent
@@ -2114,7 +2703,7 @@ impl IndentDisplay for TypedStatement {
                     format!(
                         "\n{}type_name: {}",
                         indent.dash(),
-                        type_identifier.indent_display(indent)
+                        indent.display_declared(type_identifier)
                     )
                     .as_str(),
                 );
@@ -2137,6 +2726,37 @@ impl IndentDisplay for TypedStatement {
                 indent.decrease();
                 result
             }
+            TypedStatement::NewtypeDeclaration {
+                type_identifier,
+                type_annotation,
+                type_,
+            } => {
+                let mut result = String::new();
+                result.push_str(format!("<newtype declaration> {}", type_).as_str());
+                indent.increase();
+
+                result.push_str(
+                    format!(
+                        "\n{}type_name: {}",
+                        indent.dash(),
+                        indent.display_declared(type_identifier)
+                    )
+                    .as_str(),
+                );
+
+                indent.end_current();
+                result.push_str(
+                    format!(
+                        "\n{}type_annotation: {}",
+                        indent.dash_end(),
+                        type_annotation.indent_display(indent)
+                    )
+                    .as_str(),
+                );
+
+                indent.decrease();
+                result
+            }
             TypedStatement::FunctionDeclaration {
                 identifier,
                 param,
@@ -2187,7 +2807,7 @@ impl IndentDisplay for TypedStatement {
 }
 
 impl IndentDisplay for TypedExpression {
-    fn indent_display(&self, indent: &mut Indent) -> String {
+    fn display_node<'a>(&'a self, indent: &mut Indent<'a>) -> String {
         match self {
             // TypedExpression::None => String::new(),
             TypedExpression::VariableDeclaration {
@@ -2195,6 +2815,7 @@ impl IndentDisplay for TypedExpression {
                 pattern,
                 initializer,
                 type_,
+                ..
             } => {
                 let mut result = String::new();
                 result.push_str(format!("<variable declaration>: {}\n", type_).as_str());
@@ -2278,6 +2899,7 @@ impl IndentDisplay for TypedExpression {
                 arms,
                 decision_tree,
                 type_,
+                ..
             } => {
                 let mut result = String::new();
                 result.push_str(format!("<match>: {}\n", type_).as_str());
@@ -2342,7 +2964,7 @@ impl IndentDisplay for TypedExpression {
                 result
             }
             TypedExpression::Member(m) => m.indent_display(indent),
-            TypedExpression::Literal(l) => l.indent_display(indent),
+            TypedExpression::Literal(l, _) => l.indent_display(indent),
             TypedExpression::Tuple { elements, type_ } => {
                 let mut result = String::new();
                 result.push_str(format!("<tuple>: ({})", type_).as_str());
@@ -2358,6 +2980,21 @@ impl IndentDisplay for TypedExpression {
 
                 result
             }
+            TypedExpression::Record { fields, type_ } => {
+                let mut result = String::new();
+                result.push_str(format!("<record>: {}", type_).as_str());
+
+                result.push_str(
+                    format!(
+                        "\n{}{}",
+                        indent.dash(),
+                        indent_display_slice(fields, "fields", "field", indent)
+                    )
+                    .as_str(),
+                );
+
+                result
+            }
             TypedExpression::Closure {
                 param,
                 return_type,
@@ -2374,7 +3011,7 @@ impl IndentDisplay for TypedExpression {
                     format!(
                         "{}return_type: {}\n",
                         indent.dash(),
-                        return_type.type_annotation().indent_display(indent)
+                        indent.display_owned(&return_type.type_annotation())
                     )
                     .as_str(),
                 );
@@ -2388,10 +3025,13 @@ impl IndentDisplay for TypedExpression {
             TypedExpression::Call {
                 callee,
                 argument,
+                tail_call,
                 type_,
+                ..
             } => {
                 let mut result = String::new();
-                result.push_str(format!("<call>: {}\n", type_).as_str());
+                let name = if *tail_call { "tail call" } else { "call" };
+                result.push_str(format!("<{}>: {}\n", name, type_).as_str());
                 indent.increase();
                 result.push_str(
                     format!("{}callee: {}", indent.dash(), callee.indent_display(indent)).as_str(),
@@ -2414,6 +3054,7 @@ impl IndentDisplay for TypedExpression {
                 callee,
                 argument,
                 type_,
+                ..
             } => {
                 let mut result = String::new();
                 result.push_str(format!("<index>: {}\n", type_).as_str());
@@ -2441,6 +3082,7 @@ impl IndentDisplay for TypedExpression {
                 operator,
                 expression,
                 type_,
+                ..
             } => {
                 let mut result = String::new();
                 result.push_str(format!("<unary>: {}\n", type_).as_str());
@@ -2470,6 +3112,7 @@ impl IndentDisplay for TypedExpression {
                 operator,
                 right,
                 type_,
+                ..
             } => {
                 let mut result = String::new();
                 result.push_str(format!("<binary>: {}\n", type_).as_str());
@@ -2497,6 +3140,35 @@ impl IndentDisplay for TypedExpression {
                 indent.decrease();
                 result
             }
+            TypedExpression::Cast {
+                expression,
+                target,
+                type_,
+                ..
+            } => {
+                let mut result = String::new();
+                result.push_str(format!("<cast>: {}\n", type_).as_str());
+                indent.increase();
+                result.push_str(
+                    format!(
+                        "{}expression: {}\n",
+                        indent.dash(),
+                        expression.indent_display(indent)
+                    )
+                    .as_str(),
+                );
+                indent.end_current();
+                result.push_str(
+                    format!(
+                        "{}target: {}",
+                        indent.dash_end(),
+                        target.indent_display(indent)
+                    )
+                    .as_str(),
+                );
+                indent.decrease();
+                result
+            }
             TypedExpression::Block(Block { statements, type_ }) => {
                 let mut result = String::new();
                 result.push_str(format!("<block>: {}", type_).as_str());
@@ -2595,9 +3267,11 @@ impl IndentDisplay for TypedExpression {
             TypedExpression::For {
                 identifier,
                 iterable,
+                next,
                 body,
                 else_body,
                 type_,
+                ..
             } => {
                 let mut result = String::new();
                 result.push_str(format!("<for>: {}\n", type_).as_str());
@@ -2619,6 +3293,13 @@ impl IndentDisplay for TypedExpression {
                     .as_str(),
                 );
 
+                if let Some(next) = next {
+                    result.push_str(
+                        format!("{}next: {}\n", indent.dash(), next.indent_display(indent))
+                            .as_str(),
+                    );
+                }
+
                 result.push_str(
                     format!(
                         "\n{}body: {}",
@@ -2688,7 +3369,7 @@ impl IndentDisplay for TypedExpression {
 }
 
 impl IndentDisplay for TypedParameter {
-    fn indent_display(&self, indent: &mut Indent) -> String {
+    fn display_node<'a>(&'a self, indent: &mut Indent<'a>) -> String {
         let mut result = String::new();
         result.push_str("<parameter>\n");
         indent.increase_leaf();
@@ -2714,7 +3395,7 @@ impl IndentDisplay for TypedParameter {
 }
 
 impl IndentDisplay for TypedClosureParameter {
-    fn indent_display(&self, indent: &mut Indent) -> String {
+    fn display_node<'a>(&'a self, indent: &mut Indent<'a>) -> String {
         let mut result = String::new();
         result.push_str("<parameter>\n");
         indent.increase_leaf();
@@ -2740,9 +3421,9 @@ impl IndentDisplay for TypedClosureParameter {
 }
 
 impl IndentDisplay for type_checker::ast::Member {
-    fn indent_display(&self, indent: &mut Indent) -> String {
+    fn display_node<'a>(&'a self, indent: &mut Indent<'a>) -> String {
         match self {
-            type_checker::ast::Member::Identifier { symbol, type_ } => {
+            type_checker::ast::Member::Identifier { symbol, type_, .. } => {
                 let mut result = String::new();
                 result.push_str(format!("<identifier> {}: {}", symbol, type_).as_str());
                 result
@@ -2812,13 +3493,13 @@ impl IndentDisplay for type_checker::ast::Member {
 }
 
 impl IndentDisplay for type_checker::ast::Literal {
-    fn indent_display(&self, indent: &mut Indent) -> String {
+    fn display_node<'a>(&'a self, indent: &mut Indent<'a>) -> String {
         match self {
             type_checker::ast::Literal::Void => "void".to_string(),
             type_checker::ast::Literal::Unit => "unit".to_string(),
-            type_checker::ast::Literal::Int(v) => v.to_string(),
-            type_checker::ast::Literal::UInt(v) => v.to_string(),
-            type_checker::ast::Literal::Float(v) => v.to_string(),
+            type_checker::ast::Literal::Int(v) => indent.int(v),
+            type_checker::ast::Literal::UInt(v) => indent.int(v),
+            type_checker::ast::Literal::Float(v) => float(*v),
             type_checker::ast::Literal::String(s) => s.to_string(),
             type_checker::ast::Literal::Char(c) => c.to_string(),
             type_checker::ast::Literal::Bool(b) => b.to_string(),
@@ -2852,6 +3533,7 @@ impl IndentDisplay for type_checker::ast::Literal {
             type_checker::ast::Literal::Struct {
                 type_annotation: type_identifier,
                 field_initializers,
+                spread,
                 type_,
             } => {
                 let mut result = String::new();
@@ -2867,7 +3549,7 @@ impl IndentDisplay for type_checker::ast::Literal {
                 );
 
                 for (i, field) in field_initializers.iter().enumerate() {
-                    if i < field_initializers.len() - 1 {
+                    if i < field_initializers.len() - 1 || spread.is_some() {
                         result.push_str(
                             format!("\n{}{},", indent.dash(), field.indent_display(indent))
                                 .as_str(),
@@ -2881,6 +3563,18 @@ impl IndentDisplay for type_checker::ast::Literal {
                     }
                 }
 
+                if let Some(spread) = spread {
+                    indent.end_current();
+                    result.push_str(
+                        format!(
+                            "\n{}<spread> {}",
+                            indent.dash_end(),
+                            spread.indent_display(indent)
+                        )
+                        .as_str(),
+                    );
+                }
+
                 indent.decrease();
                 result
             }
@@ -2901,6 +3595,13 @@ impl IndentDisplay for type_checker::ast::Literal {
                     )
                     .as_str(),
                 );
+                // A member without fields ends the literal
+                if let type_checker::ast::EnumMemberFieldInitializers::None = field_initializers {
+                    result.push_str(format!("{}member: {}", indent.dash_end(), member).as_str());
+                    indent.decrease();
+                    return result;
+                }
+
                 result.push_str(format!("{}member: {}\n", indent.dash_end(), member).as_str());
                 indent.increase_leaf();
                 result.push_str(
@@ -2920,18 +3621,33 @@ impl IndentDisplay for type_checker::ast::Literal {
 }
 
 impl IndentDisplay for type_checker::ast::StructField {
-    fn indent_display(&self, indent: &mut Indent) -> String {
+    fn display_node<'a>(&'a self, indent: &mut Indent<'a>) -> String {
         let mut result = String::new();
         result.push_str(format!("<struct field> {}: {}\n", self.identifier, self.type_).as_str());
-        indent.increase_leaf();
-        result.push_str(format!("{}mutable: {}", indent.dash_end(), self.mutable).as_str());
+        indent.increase();
+        result.push_str(format!("{}mutable: {}\n", indent.dash(), self.mutable).as_str());
+        indent.end_current();
+
+        if let Some(default) = &self.default {
+            result.push_str(
+                format!(
+                    "{}default: {}",
+                    indent.dash_end(),
+                    default.indent_display(indent)
+                )
+                .as_str(),
+            );
+        } else {
+            result.push_str(format!("{}default: None", indent.dash_end()).as_str());
+        }
+
         indent.decrease();
         result
     }
 }
 
 impl IndentDisplay for type_checker::ast::EnumMember {
-    fn indent_display(&self, indent: &mut Indent) -> String {
+    fn display_node<'a>(&'a self, indent: &mut Indent<'a>) -> String {
         let mut result = String::new();
         result.push_str(format!("<enum member> {}", self.type_).as_str());
         indent.increase();
@@ -2955,7 +3671,7 @@ impl IndentDisplay for type_checker::ast::EnumMember {
 }
 
 impl IndentDisplay for type_checker::ast::EnumMemberField {
-    fn indent_display(&self, indent: &mut Indent) -> String {
+    fn display_node<'a>(&'a self, indent: &mut Indent<'a>) -> String {
         let mut result = String::new();
         result.push_str(format!("<enum member field>: {}\n", self.type_).as_str());
         indent.increase_leaf();
@@ -2967,7 +3683,7 @@ impl IndentDisplay for type_checker::ast::EnumMemberField {
 }
 
 impl IndentDisplay for type_checker::ast::UnaryOperator {
-    fn indent_display(&self, _indent: &mut Indent) -> String {
+    fn display_node<'a>(&'a self, _indent: &mut Indent<'a>) -> String {
         match self {
             type_checker::ast::UnaryOperator::Identity => "+".to_string(),
             type_checker::ast::UnaryOperator::Negate => "-".to_string(),
@@ -2978,7 +3694,7 @@ impl IndentDisplay for type_checker::ast::UnaryOperator {
 }
 
 impl IndentDisplay for type_checker::ast::BinaryOperator {
-    fn indent_display(&self, _indent: &mut Indent) -> String {
+    fn display_node<'a>(&'a self, _indent: &mut Indent<'a>) -> String {
         match self {
             type_checker::ast::BinaryOperator::Add => "+".to_string(),
             type_checker::ast::BinaryOperator::Subtract => "-".to_string(),
@@ -3005,7 +3721,7 @@ impl IndentDisplay for type_checker::ast::BinaryOperator {
 }
 
 impl IndentDisplay for type_checker::ast::AccessModifier {
-    fn indent_display(&self, _indent: &mut Indent) -> String {
+    fn display_node<'a>(&'a self, _indent: &mut Indent<'a>) -> String {
         match self {
             type_checker::ast::AccessModifier::Public => "public".to_string(),
             type_checker::ast::AccessModifier::Module => "module".to_string(),
@@ -3015,7 +3731,7 @@ impl IndentDisplay for type_checker::ast::AccessModifier {
 }
 
 impl IndentDisplay for type_checker::ast::FieldInitializer {
-    fn indent_display(&self, indent: &mut Indent) -> String {
+    fn display_node<'a>(&'a self, indent: &mut Indent<'a>) -> String {
         let mut result = String::new();
         result.push_str("<field initializer>\n");
         indent.increase();
@@ -3042,7 +3758,7 @@ impl IndentDisplay for type_checker::ast::FieldInitializer {
 }
 
 impl IndentDisplay for type_checker::ast::EnumMemberFieldInitializers {
-    fn indent_display(&self, indent: &mut Indent) -> String {
+    fn display_node<'a>(&'a self, indent: &mut Indent<'a>) -> String {
         match self {
             type_checker::ast::EnumMemberFieldInitializers::None => "".to_string(),
             type_checker::ast::EnumMemberFieldInitializers::Named(field_initializers) => {
@@ -3083,9 +3799,9 @@ impl IndentDisplay for type_checker::ast::EnumMemberFieldInitializers {
 }
 
 impl IndentDisplay for TypeIdentifier {
-    fn indent_display(&self, indent: &mut Indent) -> String {
+    fn display_node<'a>(&'a self, indent: &mut Indent<'a>) -> String {
         let mut result = String::new();
-        result.push_str(format!("<type name>: {}\n", self).as_str());
+        result.push_str(format!("<type name>: {}\n", indent.type_name(self)).as_str());
 
         match self {
             TypeIdentifier::Type(type_name) => {
@@ -3182,7 +3898,7 @@ impl IndentDisplay for TypeIdentifier {
 }
 
 impl IndentDisplay for GenericType {
-    fn indent_display(&self, indent: &mut Indent) -> String {
+    fn display_node<'a>(&'a self, indent: &mut Indent<'a>) -> String {
         let mut result = String::new();
         result.push_str("<generic type>\n");
         indent.increase_leaf();
@@ -3193,7 +3909,7 @@ impl IndentDisplay for GenericType {
 }
 
 impl IndentDisplay for GenericConstraint {
-    fn indent_display(&self, indent: &mut Indent) -> String {
+    fn display_node<'a>(&'a self, indent: &mut Indent<'a>) -> String {
         let mut result = String::new();
         result.push_str("<generic constraint>\n");
         indent.increase_leaf();
@@ -3238,7 +3954,7 @@ impl IndentDisplay for GenericConstraint {
 }
 
 impl IndentDisplay for TypeAnnotation {
-    fn indent_display(&self, indent: &mut Indent) -> String {
+    fn display_node<'a>(&'a self, indent: &mut Indent<'a>) -> String {
         let mut result = String::new();
         result.push_str("<type annotation>\n");
         indent.increase_leaf();
@@ -3303,6 +4019,9 @@ impl IndentDisplay for TypeAnnotation {
                     .as_str(),
                 );
             }
+            TypeAnnotation::Record(_) => {
+                result.push_str(format!("{}record: {}", indent.dash_end(), self).as_str());
+            }
             TypeAnnotation::Function(param, return_type) => {
                 result.push_str(
                     format!(
@@ -3333,7 +4052,7 @@ impl IndentDisplay for TypeAnnotation {
 }
 
 impl IndentDisplay for TypedMatchArm {
-    fn indent_display(&self, indent: &mut Indent) -> String {
+    fn display_node<'a>(&'a self, indent: &mut Indent<'a>) -> String {
         let mut result = String::new();
         result.push_str("<match arm>");
         indent.increase();
@@ -3360,7 +4079,7 @@ impl IndentDisplay for TypedMatchArm {
 }
 
 impl IndentDisplay for Decision {
-    fn indent_display(&self, indent: &mut Indent) -> String {
+    fn display_node<'a>(&'a self, indent: &mut Indent<'a>) -> String {
         let mut result = String::new();
         result.push_str("<decision>");
         indent.increase();
@@ -3473,7 +4192,7 @@ impl IndentDisplay for Decision {
 }
 
 impl IndentDisplay for Case {
-    fn indent_display(&self, indent: &mut Indent) -> String {
+    fn display_node<'a>(&'a self, indent: &mut Indent<'a>) -> String {
         let mut result = String::new();
         result.push_str("<case>");
         indent.increase();
@@ -3512,7 +4231,7 @@ impl IndentDisplay for Case {
 }
 
 impl IndentDisplay for Variable {
-    fn indent_display(&self, indent: &mut Indent) -> String {
+    fn display_node<'a>(&'a self, indent: &mut Indent<'a>) -> String {
         let mut result = String::new();
         result.push_str("<variable>");
         result.push_str(format!(": {}", self.type_.full_name()).as_str());
@@ -3526,8 +4245,15 @@ impl IndentDisplay for Variable {
             )
             .as_str(),
         );
-        result
-            .push_str(format!("\n{}type_: {}", indent.dash_end(), self.type_.full_name()).as_str());
+        result.push_str(format!("\n{}type_: {}", indent.dash(), self.type_.full_name()).as_str());
+        result.push_str(
+            format!(
+                "\n{}provenance: {}",
+                indent.dash_end(),
+                self.provenance_path()
+            )
+            .as_str(),
+        );
 
         indent.decrease();
 
@@ -3536,7 +4262,7 @@ impl IndentDisplay for Variable {
 }
 
 impl<T: IndentDisplay> IndentDisplay for Option<T> {
-    fn indent_display(&self, indent: &mut Indent) -> String {
+    fn display_node<'a>(&'a self, indent: &mut Indent<'a>) -> String {
         match self {
             Some(v) => v.indent_display(indent),
             None => "None".to_string(),
@@ -3545,42 +4271,45 @@ impl<T: IndentDisplay> IndentDisplay for Option<T> {
 }
 
 impl<T: IndentDisplay> IndentDisplay for Box<T> {
-    fn indent_display(&self, indent: &mut Indent) -> String {
+    fn display_node<'a>(&'a self, indent: &mut Indent<'a>) -> String {
         self.as_ref().indent_display(indent)
     }
 }
 
 impl<T: IndentDisplay> IndentDisplay for &T {
-    fn indent_display(&self, indent: &mut Indent) -> String {
+    fn display_node<'a>(&'a self, indent: &mut Indent<'a>) -> String {
         (*self).indent_display(indent)
     }
 }
 
 impl<T: IndentDisplay> IndentDisplay for Vec<T> {
-    fn indent_display(&self, indent: &mut Indent) -> String {
+    fn display_node<'a>(&'a self, indent: &mut Indent<'a>) -> String {
         indent_display_slice(self, "vec", "item", indent)
     }
 }
 
 impl IndentDisplay for String {
-    fn indent_display(&self, _indent: &mut Indent) -> String {
+    fn display_node<'a>(&'a self, _indent: &mut Indent<'a>) -> String {
         self.clone()
     }
 }
 
-fn indent_display_slice<T: IndentDisplay>(
-    slice: &[T],
+fn indent_display_slice<'a, T: IndentDisplay + 'a>(
+    items: impl IntoIterator<Item = &'a T, IntoIter: ExactSizeIterator>,
     parent_type_name: &str,
     item_field_name: &str,
-    indent: &mut Indent,
+    indent: &mut Indent<'a>,
 ) -> String {
     let mut result = String::new();
 
     result.push_str(format!("<{}>", parent_type_name).as_str());
     indent.increase();
 
-    for (i, item) in slice.iter().enumerate() {
-        if i < slice.len() - 1 {
+    let items = items.into_iter();
+    let len = items.len();
+
+    for (i, item) in items.enumerate() {
+        if i < len - 1 {
             result.push_str(
                 format!(
                     "\n{}{}: {},",
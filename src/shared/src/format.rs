@@ -0,0 +1,1442 @@
+use crate::{
+    parser::{
+        comments::Comments, AccessModifier, AssociatedType, Attribute, Binary, BinaryOperator,
+        Call, Closure, EnumDeclaration, EnumMemberFieldInitializers, Expression, FieldInitializer,
+        For, FunctionDeclaration, If, ImplementationDeclaration, Literal, Match, Member,
+        ProtocolDeclaration, Statement, StructDeclaration, StructField, Unary, UnaryOperator,
+        VariableDeclaration, While,
+    },
+    type_checker::{
+        ast,
+        decision_tree::{Constructor, Pattern},
+    },
+    types::{GenericConstraint, GenericType, TypeAnnotation, TypeIdentifier},
+};
+
+const INDENT: &str = "    ";
+
+// How tightly an expression binds, from the loosest to the tightest level of the parser.
+// An expression is wrapped in parentheses when it is placed somewhere that needs a tighter level.
+const STATEMENT: u8 = 0;
+const TYPE_LITERAL: u8 = 1;
+const RANGE: u8 = 2;
+const ASSIGNMENT: u8 = 3;
+const CLOSURE: u8 = 4;
+const MATCH: u8 = 5;
+const COALESCE: u8 = 6;
+const LOGICAL: u8 = 7;
+const COMPARISON: u8 = 8;
+const BITWISE: u8 = 9;
+const ADDITIVE: u8 = 10;
+const MULTIPLICATIVE: u8 = 11;
+const CAST: u8 = 12;
+const UNARY: u8 = 13;
+const POSTFIX: u8 = 14;
+const ATOM: u8 = 15;
+
+/// Format a parsed program as canonical source code.
+///
+/// Parsing the output gives back the same tree, so formatting already formatted code is a no-op.
+/// Parentheses are only added where the parser would otherwise group the code differently.
+pub fn format(statement: &Statement) -> String {
+    format_with_comments(statement, &Comments::default())
+}
+
+/// Format like `format`, writing back the comments parsed with `parser::create_ast_with_comments`
+/// around the statements they are attached to.
+///
+/// Leading comments are each put on a line of their own. Trailing comments stay at the end of the
+/// line of their statement unless they were on a line of their own.
+pub fn format_with_comments(statement: &Statement, comments: &Comments) -> String {
+    let mut formatter = Formatter {
+        depth: 0,
+        comments,
+        next_statement: 0,
+    };
+
+    match statement {
+        Statement::Program { statements } if statements.is_empty() => comments
+            .dangling
+            .iter()
+            .map(|comment| format!("{}\n", comment.text))
+            .collect(),
+        Statement::Program { statements } => format!("{}\n", formatter.lines(statements)),
+        statement => formatter.statement(statement),
+    }
+}
+
+/// Format a single parsed expression as canonical source code
+pub fn format_expression(expression: &Expression) -> String {
+    let comments = Comments::default();
+
+    let mut formatter = Formatter {
+        depth: 0,
+        comments: &comments,
+        next_statement: 0,
+    };
+
+    formatter.expression(expression, STATEMENT)
+}
+
+struct Formatter<'a> {
+    depth: usize,
+    comments: &'a Comments,
+    /// The number of the next statement in a program or block, which its comments are keyed by
+    next_statement: usize,
+}
+
+impl Formatter<'_> {
+    fn indentation(&self) -> String {
+        INDENT.repeat(self.depth)
+    }
+
+    fn lines(&mut self, statements: &[Statement]) -> String {
+        let mut lines = String::new();
+
+        for (i, statement) in statements.iter().enumerate() {
+            if i > 0 {
+                lines.push('\n');
+
+                if is_declaration(&statements[i - 1]) || is_declaration(statement) {
+                    lines.push('\n');
+                }
+            }
+
+            // Taken before formatting the statement, which numbers the ones nested in it
+            let id = self.next_statement;
+            self.next_statement += 1;
+
+            lines.push_str(&self.indentation());
+            lines.push_str(&self.leading_comments(id));
+            lines.push_str(&self.doc_comment(doc(statement)));
+            lines.push_str(&self.attributes(attributes(statement)));
+            lines.push_str(&self.statement(statement));
+            lines.push_str(&self.trailing_comments(id));
+        }
+
+        lines
+    }
+
+    /// One line per comment in front of the statement numbered `id`
+    fn leading_comments(&self, id: usize) -> String {
+        self.comments
+            .leading
+            .get(&id)
+            .into_iter()
+            .flatten()
+            .map(|comment| format!("{}\n{}", comment.text, self.indentation()))
+            .collect()
+    }
+
+    fn trailing_comments(&self, id: usize) -> String {
+        self.comments
+            .trailing
+            .get(&id)
+            .into_iter()
+            .flatten()
+            .map(|comment| match comment.own_line {
+                true => format!("\n{}{}", self.indentation(), comment.text),
+                false => format!(" {}", comment.text),
+            })
+            .collect()
+    }
+
+    /// One `///` line per doc line, leaving the next line indented for the declaration
+    fn doc_comment(&self, doc: Option<&String>) -> String {
+        let Some(doc) = doc else {
+            return String::new();
+        };
+
+        doc.split('\n')
+            .map(|line| match line {
+                "" => format!("///\n{}", self.indentation()),
+                line => format!("/// {}\n{}", line, self.indentation()),
+            })
+            .collect()
+    }
+
+    /// One attribute per line, in the same way as the doc comment
+    fn attributes(&self, attributes: &[Attribute]) -> String {
+        attributes
+            .iter()
+            .map(|attribute| {
+                if attribute.args.is_empty() {
+                    return format!("@{}\n{}", attribute.name, self.indentation());
+                }
+
+                format!(
+                    "@{}({})\n{}",
+                    attribute.name,
+                    attribute
+                        .args
+                        .iter()
+                        .map(simple_literal)
+                        .collect::<Vec<String>>()
+                        .join(", "),
+                    self.indentation()
+                )
+            })
+            .collect()
+    }
+
+    fn statement(&mut self, statement: &Statement) -> String {
+        match statement {
+            Statement::Program { statements } => self.lines(statements),
+            Statement::ModuleDeclaration(module_declaration) => format!(
+                "{}mod {};",
+                access_modifier(&module_declaration.access_modifier),
+                module_declaration.module_path.join("::")
+            ),
+            Statement::Use(r#use) => format!("use {};", r#use.use_item),
+            Statement::StructDeclaration(struct_declaration) => {
+                self.struct_declaration(struct_declaration)
+            }
+            Statement::EnumDeclaration(enum_declaration) => self.enum_declaration(enum_declaration),
+            Statement::UnionDeclaration(union_declaration) => {
+                let literals = union_declaration
+                    .literals
+                    .iter()
+                    .map(simple_literal)
+                    .collect::<Vec<String>>();
+
+                let body = if literals.is_empty() {
+                    "{}".to_string()
+                } else {
+                    format!("{{ {} }}", literals.join(", "))
+                };
+
+                format!(
+                    "{}union {} {}",
+                    access_modifier(&union_declaration.access_modifier),
+                    type_identifier(&union_declaration.type_identifier),
+                    body
+                )
+            }
+            Statement::TypeAliasDeclaration(type_alias_declaration) => format!(
+                "{}type {} = {};",
+                access_modifier(&type_alias_declaration.access_modifier),
+                type_identifier(&type_alias_declaration.type_identifier),
+                type_alias_declaration
+                    .type_annotations
+                    .iter()
+                    .map(type_annotation)
+                    .collect::<Vec<String>>()
+                    .join(" or ")
+            ),
+            Statement::NewtypeDeclaration(newtype_declaration) => format!(
+                "{}newtype {} = {};",
+                access_modifier(&newtype_declaration.access_modifier),
+                type_identifier(&newtype_declaration.type_identifier),
+                type_annotation(&newtype_declaration.type_annotation)
+            ),
+            Statement::ProtocolDeclaration(protocol_declaration) => {
+                self.protocol_declaration(protocol_declaration)
+            }
+            Statement::ImplementationDeclaration(implementation_declaration) => {
+                self.implementation_declaration(implementation_declaration)
+            }
+            Statement::FunctionDeclaration(function_declaration) => {
+                self.function_declaration(function_declaration)
+            }
+            Statement::Semi(statement) => format!("{};", self.statement(statement)),
+            Statement::Expression(expression) => self.expression(expression, STATEMENT),
+            // The source of a statement which failed to parse isn't kept, so it can't be written
+            Statement::Error(_) => String::new(),
+        }
+    }
+
+    fn struct_declaration(&mut self, struct_declaration: &StructDeclaration) -> String {
+        if struct_declaration
+            .fields
+            .first()
+            .is_some_and(StructField::is_positional)
+        {
+            let fields = struct_declaration
+                .fields
+                .iter()
+                .map(|field| {
+                    format!(
+                        "{}{}{}",
+                        access_modifier(&field.access_modifier),
+                        if field.mutable { "mut " } else { "" },
+                        type_annotation(&field.type_annotation)
+                    )
+                })
+                .collect::<Vec<String>>();
+
+            return format!(
+                "{}struct {}({}){}",
+                access_modifier(&struct_declaration.access_modifier),
+                type_identifier(&struct_declaration.type_identifier),
+                fields.join(", "),
+                where_clause(&struct_declaration.where_clause)
+            );
+        }
+
+        let header = format!(
+            "{}struct {}{}",
+            access_modifier(&struct_declaration.access_modifier),
+            type_identifier(&struct_declaration.type_identifier),
+            where_clause(&struct_declaration.where_clause)
+        );
+
+        let fields = struct_declaration
+            .fields
+            .iter()
+            .map(|field| match &field.default {
+                Some(default) => format!(
+                    "{} = {}",
+                    struct_field(field),
+                    self.expression(default, STATEMENT)
+                ),
+                None => struct_field(field),
+            })
+            .collect::<Vec<String>>();
+
+        format!("{} {}", header, self.item_list(&fields, ","))
+    }
+
+    fn enum_declaration(&mut self, enum_declaration: &EnumDeclaration) -> String {
+        let header = format!(
+            "{}enum {}{}",
+            access_modifier(&enum_declaration.access_modifier),
+            type_identifier(&enum_declaration.type_identifier),
+            where_clause(&enum_declaration.where_clause)
+        );
+
+        // Every member starts with the shared fields declared before it, so the shared fields
+        // are placed in between the members to give each member back the same fields.
+        let shared_fields = &enum_declaration.shared_fields;
+        let mut items = vec![];
+        let mut shared = 0;
+
+        for member in &enum_declaration.members {
+            while shared < shared_fields.len()
+                && member.fields.get(shared).is_some_and(|field| {
+                    field.identifier == shared_fields[shared].identifier
+                        && field.type_annotation == shared_fields[shared].type_annotation
+                })
+            {
+                items.push(struct_field(&shared_fields[shared]));
+                shared += 1;
+            }
+
+            let fields = member.fields[shared..]
+                .iter()
+                .map(|field| {
+                    format!(
+                        "{}: {}",
+                        field.identifier,
+                        type_annotation(&field.type_annotation)
+                    )
+                })
+                .collect::<Vec<String>>();
+
+            let discriminant = member
+                .discriminant
+                .map(|discriminant| format!(" = {}", discriminant))
+                .unwrap_or_default();
+
+            if fields.is_empty() {
+                items.push(format!("{}{}", member.identifier, discriminant));
+            } else {
+                items.push(format!(
+                    "{} {{ {} }}{}",
+                    member.identifier,
+                    fields.join(", "),
+                    discriminant
+                ));
+            }
+        }
+
+        for field in &shared_fields[shared..] {
+            items.push(struct_field(field));
+        }
+
+        format!("{} {}", header, self.item_list(&items, ","))
+    }
+
+    fn protocol_declaration(&mut self, protocol_declaration: &ProtocolDeclaration) -> String {
+        let header = format!(
+            "{}proto {}",
+            access_modifier(&protocol_declaration.access_modifier),
+            type_identifier(&protocol_declaration.type_identifier)
+        );
+
+        let body = self.associated_items(
+            &protocol_declaration.associated_types,
+            &protocol_declaration.functions,
+        );
+
+        format!("{} {}", header, body)
+    }
+
+    fn implementation_declaration(
+        &mut self,
+        implementation_declaration: &ImplementationDeclaration,
+    ) -> String {
+        let scoped_generics = if implementation_declaration.scoped_generics.is_empty() {
+            String::new()
+        } else {
+            generics(&implementation_declaration.scoped_generics)
+        };
+
+        let header = match &implementation_declaration.protocol_annotation {
+            Some(protocol_annotation) => format!(
+                "imp{} {} for {}",
+                scoped_generics,
+                type_annotation(protocol_annotation),
+                type_annotation(&implementation_declaration.type_annotation)
+            ),
+            None => format!(
+                "imp{} {}",
+                scoped_generics,
+                type_annotation(&implementation_declaration.type_annotation)
+            ),
+        };
+
+        let body = self.associated_items(
+            &implementation_declaration.associated_types,
+            &implementation_declaration.functions,
+        );
+
+        format!("{} {}", header, body)
+    }
+
+    fn associated_items(
+        &mut self,
+        associated_types: &[AssociatedType],
+        functions: &[FunctionDeclaration],
+    ) -> String {
+        let mut items = associated_types
+            .iter()
+            .map(
+                |associated_type| match &associated_type.default_type_annotation {
+                    Some(default) => format!(
+                        "type {} = {};",
+                        type_identifier(&associated_type.type_identifier),
+                        type_annotation(default)
+                    ),
+                    None => format!(
+                        "type {};",
+                        type_identifier(&associated_type.type_identifier)
+                    ),
+                },
+            )
+            .collect::<Vec<String>>();
+
+        self.depth += 1;
+
+        for function in functions {
+            let doc_comment = self.doc_comment(function.doc.as_ref());
+            items.push(format!(
+                "{}{}",
+                doc_comment,
+                self.function_declaration(function)
+            ));
+        }
+
+        self.depth -= 1;
+
+        self.item_list(&items, "")
+    }
+
+    /// Place each item on its own line inside braces, one level deeper than the current line
+    fn item_list(&self, items: &[String], separator: &str) -> String {
+        if items.is_empty() {
+            return "{}".to_string();
+        }
+
+        let indentation = format!("{}{}", self.indentation(), INDENT);
+
+        format!(
+            "{{\n{}\n{}}}",
+            items
+                .iter()
+                .map(|item| format!("{}{}{}", indentation, item, separator))
+                .collect::<Vec<String>>()
+                .join("\n"),
+            self.indentation()
+        )
+    }
+
+    fn function_declaration(&mut self, function_declaration: &FunctionDeclaration) -> String {
+        let mut params = vec![];
+        let mut return_type_annotation = function_declaration.return_type_annotation.clone();
+        let mut body = function_declaration.body.as_ref();
+
+        if let Some(param) = &function_declaration.param {
+            params.push(format!(
+                "{}{}: {}",
+                if param.mutable { "mut " } else { "" },
+                param.identifier,
+                type_annotation(&param.type_annotation)
+            ));
+
+            // Functions with several parameters are parsed into a closure per extra parameter
+            while let Some(Expression::Closure(closure)) = body {
+                let Some(param_type_annotation) = closure
+                    .param
+                    .as_ref()
+                    .and_then(|param| param.type_annotation.as_ref())
+                else {
+                    break;
+                };
+
+                if !is_curried(
+                    &return_type_annotation,
+                    Some(param_type_annotation),
+                    &closure.return_type_annotation,
+                ) {
+                    break;
+                }
+
+                let param = closure.param.as_ref().expect("Checked above");
+
+                params.push(format!(
+                    "{}: {}",
+                    param.identifier,
+                    type_annotation(param_type_annotation)
+                ));
+
+                return_type_annotation = closure.return_type_annotation.clone();
+                body = Some(&closure.body);
+            }
+        }
+
+        let return_type_annotation = match &return_type_annotation {
+            Some(return_type_annotation) => {
+                format!(": {}", type_annotation(return_type_annotation))
+            }
+            None => String::new(),
+        };
+
+        let signature = format!(
+            "{}fun {}({}){}{}",
+            access_modifier(&function_declaration.access_modifier),
+            type_identifier(&function_declaration.type_identifier),
+            params.join(", "),
+            return_type_annotation,
+            where_clause(&function_declaration.where_clause)
+        );
+
+        match body {
+            Some(body) if !function_declaration.signature_only => {
+                format!("{} => {}", signature, self.expression(body, STATEMENT))
+            }
+            _ => format!("{};", signature),
+        }
+    }
+
+    fn expression(&mut self, expression: &Expression, min_precedence: u8) -> String {
+        let formatted = self.unwrapped_expression(expression);
+
+        if precedence(expression) < min_precedence {
+            format!("({})", formatted)
+        } else {
+            formatted
+        }
+    }
+
+    fn unwrapped_expression(&mut self, expression: &Expression) -> String {
+        match expression {
+            Expression::VariableDeclaration(variable_declaration) => {
+                self.variable_declaration(variable_declaration)
+            }
+            Expression::If(r#if) => self.r#if(r#if),
+            Expression::Match(r#match) => self.r#match(r#match),
+            Expression::Assignment(assignment) => format!(
+                "{} {}= {}",
+                self.member(&assignment.member),
+                assignment
+                    .operator
+                    .clone()
+                    .map_or(String::new(), |operator| ast::BinaryOperator::from(
+                        operator
+                    )
+                    .to_string()),
+                self.expression(&assignment.initializer, STATEMENT)
+            ),
+            Expression::Member(member) => self.member(member),
+            Expression::Literal(literal, _) => self.literal(literal),
+            Expression::Tuple(elements) => format!("({})", self.expressions(elements)),
+            Expression::Record(fields) => {
+                let fields = fields
+                    .iter()
+                    .map(|field| self.field_initializer(field))
+                    .collect::<Vec<String>>();
+
+                inline_braces(&fields)
+            }
+            Expression::Closure(closure) => self.closure(closure),
+            Expression::Call(call) => self.call(call),
+            Expression::Index(index) => format!(
+                "{}[{}]",
+                self.expression(&index.callee, POSTFIX),
+                self.expression(&index.argument, STATEMENT)
+            ),
+            Expression::Unary(unary) => self.unary(unary),
+            Expression::Binary(binary) => self.binary(binary),
+            Expression::Cast(cast) => format!(
+                "{} as {}",
+                self.expression(&cast.expression, CAST),
+                type_annotation(&cast.target)
+            ),
+            Expression::Coalesce(coalesce) => format!(
+                "{} ?? {}",
+                self.expression(&coalesce.value, COALESCE),
+                self.expression(&coalesce.default, LOGICAL)
+            ),
+            Expression::Block(statements) => self.block(statements),
+            Expression::Try(expression) => format!("{}?", self.expression(expression, POSTFIX)),
+            Expression::Loop(body) => format!("loop {}", self.expression(body, STATEMENT)),
+            Expression::While(r#while) => self.r#while(r#while),
+            Expression::For(r#for) => self.r#for(r#for),
+            Expression::Break(Some(expression)) => {
+                format!("break {}", self.expression(expression, STATEMENT))
+            }
+            // Without a value the semicolon belongs to the break itself
+            Expression::Break(None) => "break;".to_string(),
+            Expression::Continue => "continue".to_string(),
+            Expression::Return(Some(expression)) => {
+                format!("return {}", self.expression(expression, STATEMENT))
+            }
+            Expression::Return(None) => "return;".to_string(),
+            #[cfg(feature = "interpreter")]
+            Expression::Print(expression) => {
+                format!("print({})", self.expression(expression, STATEMENT))
+            }
+            #[cfg(feature = "interpreter")]
+            Expression::Drop(identifier) => format!("drop({})", identifier),
+        }
+    }
+
+    fn expressions(&mut self, expressions: &[Expression]) -> String {
+        expressions
+            .iter()
+            .map(|expression| self.expression(expression, STATEMENT))
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+
+    fn block(&mut self, statements: &[Statement]) -> String {
+        if statements.is_empty() {
+            return "{}".to_string();
+        }
+
+        self.depth += 1;
+        let lines = self.lines(statements);
+        self.depth -= 1;
+
+        format!("{{\n{}\n{}}}", lines, self.indentation())
+    }
+
+    fn variable_declaration(&mut self, variable_declaration: &VariableDeclaration) -> String {
+        let mut formatted = "let ".to_string();
+
+        if variable_declaration.mutable {
+            formatted.push_str("mut ");
+        }
+
+        formatted.push_str(&pattern(&variable_declaration.pattern));
+
+        if let Some(annotation) = &variable_declaration.type_annotation {
+            formatted.push_str(&format!(": {}", type_annotation(annotation)));
+        }
+
+        if let Some(initializer) = &variable_declaration.initializer {
+            formatted.push_str(&format!(" = {}", self.expression(initializer, STATEMENT)));
+        }
+
+        formatted
+    }
+
+    fn r#if(&mut self, r#if: &If) -> String {
+        let condition = self.expression(&r#if.condition, RANGE);
+
+        let Some(false_expression) = &r#if.false_expression else {
+            let true_expression = self.expression(&r#if.true_expression, STATEMENT);
+            return format!("if {} => {}", condition, true_expression);
+        };
+
+        let true_expression = self.branch_before_else(&r#if.true_expression);
+
+        let false_expression = match false_expression.as_ref() {
+            Expression::If(else_if) => self.r#if(else_if),
+            false_expression => format!("=> {}", self.expression(false_expression, TYPE_LITERAL)),
+        };
+
+        format!(
+            "if {} => {} else {}",
+            condition, true_expression, false_expression
+        )
+    }
+
+    fn r#while(&mut self, r#while: &While) -> String {
+        let condition = self.expression(&r#while.condition, RANGE);
+
+        match &r#while.else_body {
+            Some(else_body) => format!(
+                "while {} => {} else => {}",
+                condition,
+                self.branch_before_else(&r#while.body),
+                self.expression(else_body, STATEMENT)
+            ),
+            None => format!(
+                "while {} => {}",
+                condition,
+                self.expression(&r#while.body, STATEMENT)
+            ),
+        }
+    }
+
+    fn r#for(&mut self, r#for: &For) -> String {
+        let iterable = self.expression(&r#for.iterable, RANGE);
+
+        match &r#for.else_body {
+            Some(else_body) => format!(
+                "for {} in {} => {} else => {}",
+                r#for.identifier,
+                iterable,
+                self.branch_before_else(&r#for.body),
+                self.expression(else_body, STATEMENT)
+            ),
+            None => format!(
+                "for {} in {} => {}",
+                r#for.identifier,
+                iterable,
+                self.expression(&r#for.body, STATEMENT)
+            ),
+        }
+    }
+
+    /// A branch followed by `else` must not end in an expression that would take the `else` itself
+    fn branch_before_else(&mut self, branch: &Expression) -> String {
+        let formatted = self.expression(branch, STATEMENT);
+
+        if ends_with(branch, &|expression| {
+            matches!(
+                expression,
+                Expression::If(If {
+                    false_expression: None,
+                    ..
+                }) | Expression::While(While {
+                    else_body: None,
+                    ..
+                }) | Expression::For(For {
+                    else_body: None,
+                    ..
+                })
+            )
+        }) {
+            format!("({})", formatted)
+        } else {
+            formatted
+        }
+    }
+
+    fn r#match(&mut self, r#match: &Match) -> String {
+        let expression = self.expression(&r#match.expression, LOGICAL);
+
+        self.depth += 1;
+
+        let mut arms = vec![];
+
+        for (i, arm) in r#match.arms.iter().enumerate() {
+            let mut body = self.expression(&arm.expression, STATEMENT);
+
+            if i + 1 < r#match.arms.len() {
+                // A match at the end of an arm would take the following arms as its own
+                if ends_with(&arm.expression, &|expression| {
+                    matches!(expression, Expression::Match(_))
+                }) {
+                    body = format!("({})", body);
+                }
+
+                body.push(',');
+            }
+
+            arms.push(format!(
+                "{}| {} => {}",
+                self.indentation(),
+                pattern(&arm.pattern),
+                body
+            ));
+        }
+
+        self.depth -= 1;
+
+        format!("{} match\n{}", expression, arms.join("\n"))
+    }
+
+    fn closure(&mut self, closure: &Closure) -> String {
+        let mut params = vec![];
+        let mut return_type_annotation = closure.return_type_annotation.clone();
+        let mut body = closure.body.as_ref();
+
+        if let Some(param) = &closure.param {
+            params.push(closure_parameter(
+                &param.identifier,
+                param.type_annotation.as_ref(),
+            ));
+
+            // Closures with several parameters are parsed into a closure per parameter
+            while let Expression::Closure(Closure {
+                param: Some(param),
+                return_type_annotation: inner_return_type_annotation,
+                body: inner_body,
+            }) = body
+            {
+                if !is_curried(
+                    &return_type_annotation,
+                    param.type_annotation.as_ref(),
+                    inner_return_type_annotation,
+                ) {
+                    break;
+                }
+
+                params.push(closure_parameter(
+                    &param.identifier,
+                    param.type_annotation.as_ref(),
+                ));
+
+                return_type_annotation = inner_return_type_annotation.clone();
+                body = inner_body;
+            }
+        }
+
+        let body = self.expression(body, STATEMENT);
+
+        match &return_type_annotation {
+            Some(return_type_annotation) => format!(
+                "|{}|: {} => {}",
+                params.join(", "),
+                type_annotation(return_type_annotation),
+                body
+            ),
+            None => format!("|{}| {}", params.join(", "), body),
+        }
+    }
+
+    fn call(&mut self, call: &Call) -> String {
+        // Calls with several arguments are parsed into a call per argument
+        let mut arguments = vec![];
+        let mut callee = call;
+
+        while let Some(argument) = &callee.argument {
+            arguments.push(argument.as_ref().clone());
+
+            match callee.callee.as_ref() {
+                Expression::Call(inner) if inner.argument.is_some() => callee = inner,
+                _ => break,
+            }
+        }
+
+        arguments.reverse();
+
+        if arguments.is_empty() {
+            return format!("{}()", self.expression(&callee.callee, POSTFIX));
+        }
+
+        format!(
+            "{}({})",
+            self.expression(&callee.callee, POSTFIX),
+            self.expressions(&arguments)
+        )
+    }
+
+    fn unary(&mut self, unary: &Unary) -> String {
+        let operator = match unary.operator {
+            UnaryOperator::Identity => "+",
+            UnaryOperator::Negate => "-",
+            UnaryOperator::LogicalNot => "!",
+            UnaryOperator::BitwiseNot => "~",
+        };
+
+        let mut operand = self.expression(&unary.expression, UNARY);
+
+        // Keep repeated operators apart so they don't read as a different operator
+        if operand.starts_with(operator) {
+            operand = format!("({})", operand);
+        }
+
+        format!("{}{}", operator, operand)
+    }
+
+    fn binary(&mut self, binary: &Binary) -> String {
+        let level = binary_precedence(&binary.operator);
+
+        let (left_precedence, right_precedence) = match level {
+            RANGE => (RANGE, ASSIGNMENT),
+            LOGICAL => (LOGICAL, COMPARISON),
+            COMPARISON => (COMPARISON, ADDITIVE),
+            BITWISE => (ADDITIVE, LOGICAL),
+            level => (level, level + 1),
+        };
+
+        let mut left = self.expression(&binary.left, left_precedence);
+
+        // The right operand of a bitwise operator takes everything up to the next logical
+        // operator, so a bitwise left operand has to be closed off
+        if level >= LOGICAL
+            && matches!(binary.left.as_ref(), Expression::Binary(left) if binary_precedence(&left.operator) == BITWISE)
+        {
+            left = format!("({})", left);
+        }
+
+        // An identifier directly followed by < at the start of an expression is parsed as
+        // the start of a generic type
+        if matches!(
+            binary.operator,
+            BinaryOperator::LessThan | BinaryOperator::BitwiseLeftShift
+        ) && matches!(
+            binary.left.as_ref(),
+            Expression::Member(Member::Identifier { generics: None, .. })
+        ) {
+            left = format!("({})", left);
+        }
+
+        let right = self.expression(&binary.right, right_precedence);
+        let operator = ast::BinaryOperator::from(binary.operator.clone());
+
+        match level {
+            RANGE => format!("{}{}{}", left, operator, right),
+            _ => format!("{} {} {}", left, operator, right),
+        }
+    }
+
+    fn member(&mut self, member: &Member) -> String {
+        match member {
+            Member::Identifier {
+                symbol, generics, ..
+            } => {
+                format!("{}{}", symbol, member_generics(generics))
+            }
+            Member::Path(path, _) => path.join("::"),
+            Member::StaticMemberAccess {
+                type_annotation: static_type_annotation,
+                symbol,
+                generics,
+                ..
+            } => format!(
+                "{}::{}{}",
+                type_annotation(static_type_annotation),
+                symbol,
+                member_generics(generics)
+            ),
+            Member::MemberAccess {
+                object,
+                symbol,
+                generics,
+                optional,
+                ..
+            } => format!(
+                "{}{}.{}{}",
+                self.expression(object, ATOM),
+                if *optional { "?" } else { "" },
+                symbol,
+                member_generics(generics)
+            ),
+            Member::ParamPropagation {
+                object,
+                symbol,
+                generics,
+                ..
+            } => format!(
+                "{}:{}{}",
+                self.expression(object, POSTFIX),
+                symbol,
+                member_generics(generics)
+            ),
+        }
+    }
+
+    fn literal(&mut self, literal: &Literal) -> String {
+        match literal {
+            Literal::Array(elements) => format!("[{}]", self.expressions(elements)),
+            Literal::ArrayRepeat { value, length } => format!(
+                "[{}; {}]",
+                self.expression(value, STATEMENT),
+                self.expression(length, STATEMENT)
+            ),
+            Literal::Struct {
+                type_annotation: struct_type_annotation,
+                field_initializers,
+                spread,
+            } => {
+                let mut fields = field_initializers
+                    .iter()
+                    .map(|field_initializer| self.field_initializer(field_initializer))
+                    .collect::<Vec<String>>();
+
+                if let Some(spread) = spread {
+                    fields.push(format!("..{}", self.expression(spread, STATEMENT)));
+                }
+
+                format!(
+                    "{} {}",
+                    type_annotation(struct_type_annotation),
+                    inline_braces(&fields)
+                )
+            }
+            Literal::Enum {
+                type_annotation: enum_type_annotation,
+                field_initializers,
+                ..
+            } => match field_initializers {
+                EnumMemberFieldInitializers::None => type_annotation(enum_type_annotation),
+                EnumMemberFieldInitializers::Named(field_initializers) => {
+                    let mut names = field_initializers.keys().collect::<Vec<&String>>();
+                    names.sort();
+
+                    let fields = names
+                        .into_iter()
+                        .map(|name| {
+                            format!(
+                                "{}: {}",
+                                name,
+                                self.expression(&field_initializers[name], STATEMENT)
+                            )
+                        })
+                        .collect::<Vec<String>>();
+
+                    format!(
+                        "{} {}",
+                        type_annotation(enum_type_annotation),
+                        inline_braces(&fields)
+                    )
+                }
+            },
+            literal => simple_literal(literal),
+        }
+    }
+
+    fn field_initializer(&mut self, field_initializer: &FieldInitializer) -> String {
+        let initializer = self.expression(&field_initializer.initializer, STATEMENT);
+
+        match &field_initializer.identifier {
+            Some(identifier) => format!("{}: {}", identifier, initializer),
+            None => initializer,
+        }
+    }
+}
+
+fn precedence(expression: &Expression) -> u8 {
+    match expression {
+        Expression::VariableDeclaration(_)
+        | Expression::If(_)
+        | Expression::Loop(_)
+        | Expression::While(_)
+        | Expression::For(_)
+        | Expression::Break(_)
+        | Expression::Continue
+        | Expression::Return(_) => STATEMENT,
+        #[cfg(feature = "interpreter")]
+        Expression::Print(_) | Expression::Drop(_) => STATEMENT,
+        Expression::Block(_)
+        | Expression::Literal(Literal::Struct { .. }, _)
+        | Expression::Literal(Literal::Enum { .. }, _) => TYPE_LITERAL,
+        Expression::Assignment(_) => ASSIGNMENT,
+        Expression::Closure(_) => CLOSURE,
+        Expression::Match(_) => MATCH,
+        Expression::Binary(binary) => binary_precedence(&binary.operator),
+        Expression::Cast(_) => CAST,
+        Expression::Coalesce(_) => COALESCE,
+        Expression::Unary(_) => UNARY,
+        Expression::Literal(Literal::Int(value), _) if *value < 0 => UNARY,
+        Expression::Literal(Literal::Float(value), _) if value.is_sign_negative() => UNARY,
+        // A dot directly after a number is read as part of the number
+        Expression::Literal(Literal::Int(_), _)
+        | Expression::Literal(Literal::UInt(_), _)
+        | Expression::Literal(Literal::Float(_), _) => POSTFIX,
+        Expression::Call(_)
+        | Expression::Index(_)
+        | Expression::Try(_)
+        | Expression::Member(Member::ParamPropagation { .. }) => POSTFIX,
+        Expression::Member(Member::Identifier {
+            generics: Some(_), ..
+        })
+        | Expression::Member(Member::StaticMemberAccess {
+            generics: Some(_), ..
+        })
+        | Expression::Member(Member::MemberAccess {
+            generics: Some(_), ..
+        }) => POSTFIX,
+        Expression::Member(_)
+        | Expression::Literal(..)
+        | Expression::Tuple(_)
+        | Expression::Record(_) => ATOM,
+    }
+}
+
+fn binary_precedence(operator: &BinaryOperator) -> u8 {
+    match operator {
+        BinaryOperator::Range | BinaryOperator::RangeInclusive => RANGE,
+        BinaryOperator::LogicalAnd | BinaryOperator::LogicalOr => LOGICAL,
+        BinaryOperator::Equal
+        | BinaryOperator::NotEqual
+        | BinaryOperator::LessThan
+        | BinaryOperator::LessThanOrEqual
+        | BinaryOperator::GreaterThan
+        | BinaryOperator::GreaterThanOrEqual => COMPARISON,
+        BinaryOperator::BitwiseAnd
+        | BinaryOperator::BitwiseOr
+        | BinaryOperator::BitwiseXor
+        | BinaryOperator::BitwiseLeftShift
+        | BinaryOperator::BitwiseRightShift => BITWISE,
+        BinaryOperator::Add | BinaryOperator::Subtract => ADDITIVE,
+        BinaryOperator::Multiply | BinaryOperator::Divide | BinaryOperator::Modulo => {
+            MULTIPLICATIVE
+        }
+    }
+}
+
+/// Whether the expression or anything along its right edge matches the predicate.
+/// Everything along the right edge keeps parsing until it reaches a token it can't use.
+fn ends_with(expression: &Expression, predicate: &dyn Fn(&Expression) -> bool) -> bool {
+    if predicate(expression) {
+        return true;
+    }
+
+    match expression {
+        Expression::VariableDeclaration(VariableDeclaration {
+            initializer: Some(tail),
+            ..
+        })
+        | Expression::If(If {
+            false_expression: Some(tail),
+            ..
+        })
+        | Expression::While(While {
+            else_body: Some(tail),
+            ..
+        })
+        | Expression::For(For {
+            else_body: Some(tail),
+            ..
+        })
+        | Expression::Break(Some(tail))
+        | Expression::Return(Some(tail))
+        | Expression::Loop(tail) => ends_with(tail, predicate),
+        Expression::If(r#if) => ends_with(&r#if.true_expression, predicate),
+        Expression::While(r#while) => ends_with(&r#while.body, predicate),
+        Expression::For(r#for) => ends_with(&r#for.body, predicate),
+        Expression::Match(r#match) => r#match
+            .arms
+            .last()
+            .is_some_and(|arm| ends_with(&arm.expression, predicate)),
+        Expression::Assignment(assignment) => ends_with(&assignment.initializer, predicate),
+        Expression::Closure(closure) => ends_with(&closure.body, predicate),
+        Expression::Unary(unary) => ends_with(&unary.expression, predicate),
+        Expression::Binary(binary) => ends_with(&binary.right, predicate),
+        Expression::Coalesce(coalesce) => ends_with(&coalesce.default, predicate),
+        _ => false,
+    }
+}
+
+fn doc(statement: &Statement) -> Option<&String> {
+    match statement {
+        Statement::StructDeclaration(struct_declaration) => struct_declaration.doc.as_ref(),
+        Statement::EnumDeclaration(enum_declaration) => enum_declaration.doc.as_ref(),
+        Statement::UnionDeclaration(union_declaration) => union_declaration.doc.as_ref(),
+        Statement::TypeAliasDeclaration(type_alias_declaration) => {
+            type_alias_declaration.doc.as_ref()
+        }
+        Statement::NewtypeDeclaration(newtype_declaration) => newtype_declaration.doc.as_ref(),
+        Statement::ProtocolDeclaration(protocol_declaration) => protocol_declaration.doc.as_ref(),
+        Statement::ImplementationDeclaration(implementation_declaration) => {
+            implementation_declaration.doc.as_ref()
+        }
+        Statement::FunctionDeclaration(function_declaration) => function_declaration.doc.as_ref(),
+        Statement::Semi(statement) => doc(statement),
+        _ => None,
+    }
+}
+
+fn attributes(statement: &Statement) -> &[Attribute] {
+    match statement {
+        Statement::StructDeclaration(struct_declaration) => &struct_declaration.attributes,
+        Statement::EnumDeclaration(enum_declaration) => &enum_declaration.attributes,
+        Statement::UnionDeclaration(union_declaration) => &union_declaration.attributes,
+        Statement::TypeAliasDeclaration(type_alias_declaration) => {
+            &type_alias_declaration.attributes
+        }
+        Statement::NewtypeDeclaration(newtype_declaration) => &newtype_declaration.attributes,
+        Statement::ProtocolDeclaration(protocol_declaration) => &protocol_declaration.attributes,
+        Statement::ImplementationDeclaration(implementation_declaration) => {
+            &implementation_declaration.attributes
+        }
+        Statement::FunctionDeclaration(function_declaration) => &function_declaration.attributes,
+        Statement::Semi(statement) => attributes(statement),
+        _ => &[],
+    }
+}
+
+fn is_declaration(statement: &Statement) -> bool {
+    match statement {
+        Statement::Expression(_) | Statement::Error(_) => false,
+        Statement::Semi(statement) => is_declaration(statement),
+        _ => true,
+    }
+}
+
+/// Whether the inner return type is what is left of the outer return type after taking the parameter
+fn is_curried(
+    outer_return_type_annotation: &Option<TypeAnnotation>,
+    param_type_annotation: Option<&TypeAnnotation>,
+    inner_return_type_annotation: &Option<TypeAnnotation>,
+) -> bool {
+    *outer_return_type_annotation
+        == inner_return_type_annotation.clone().map(|inner| {
+            TypeAnnotation::Function(
+                param_type_annotation.cloned().map(Box::new),
+                Some(Box::new(inner)),
+            )
+        })
+}
+
+fn access_modifier(access_modifier: &Option<AccessModifier>) -> &'static str {
+    match access_modifier {
+        Some(AccessModifier::Public) => "pub ",
+        Some(AccessModifier::Super) => "sup ",
+        Some(AccessModifier::Module) => "mod ",
+        None => "",
+    }
+}
+
+fn struct_field(field: &StructField) -> String {
+    format!(
+        "{}{}: {}{}",
+        access_modifier(&field.access_modifier),
+        field.identifier,
+        if field.mutable { "mut " } else { "" },
+        type_annotation(&field.type_annotation)
+    )
+}
+
+fn closure_parameter(identifier: &str, annotation: Option<&TypeAnnotation>) -> String {
+    match annotation {
+        Some(annotation) => format!("{}: {}", identifier, type_annotation(annotation)),
+        None => identifier.to_string(),
+    }
+}
+
+fn where_clause(where_clause: &Option<Vec<GenericConstraint>>) -> String {
+    let Some(constraints) = where_clause else {
+        return String::new();
+    };
+
+    let constraints = constraints
+        .iter()
+        .map(|constraint| {
+            format!(
+                "{}: {}",
+                constraint.generic,
+                constraint
+                    .constraints
+                    .iter()
+                    .map(type_annotation)
+                    .collect::<Vec<String>>()
+                    .join(" and ")
+            )
+        })
+        .collect::<Vec<String>>();
+
+    format!(" where {}", constraints.join(" "))
+}
+
+fn generics(generics: &[GenericType]) -> String {
+    format!(
+        "<{}>",
+        generics
+            .iter()
+            .map(|generic| generic.type_name.clone())
+            .collect::<Vec<String>>()
+            .join(", ")
+    )
+}
+
+fn member_generics(member_generics: &Option<Vec<GenericType>>) -> String {
+    match member_generics {
+        Some(member_generics) => format!("::{}", generics(member_generics)),
+        None => String::new(),
+    }
+}
+
+fn inline_braces(items: &[String]) -> String {
+    if items.is_empty() {
+        "{}".to_string()
+    } else {
+        format!("{{ {} }}", items.join(", "))
+    }
+}
+
+fn type_identifier(type_identifier: &TypeIdentifier) -> String {
+    match type_identifier {
+        TypeIdentifier::Type(type_name) => type_name.clone(),
+        TypeIdentifier::GenericType(type_name, type_generics) => {
+            format!("{}{}", type_name, generics(type_generics))
+        }
+        TypeIdentifier::ConcreteType(type_name, type_annotations) => {
+            format!("{}<{}>", type_name, type_annotation_list(type_annotations))
+        }
+        TypeIdentifier::MemberType(parent, member) => {
+            format!("{}::{}", self::type_identifier(parent), member)
+        }
+    }
+}
+
+fn type_annotation(annotation: &TypeAnnotation) -> String {
+    match annotation {
+        TypeAnnotation::Type(type_name) => type_name.clone(),
+        // Generics are written on the enum, before the member name
+        TypeAnnotation::ConcreteType(type_name, type_annotations) => {
+            match type_name.split_once("::") {
+                Some((type_name, member)) => format!(
+                    "{}<{}>::{}",
+                    type_name,
+                    type_annotation_list(type_annotations),
+                    member
+                ),
+                None => format!("{}<{}>", type_name, type_annotation_list(type_annotations)),
+            }
+        }
+        TypeAnnotation::Array(element) => format!("[{}]", type_annotation(element)),
+        TypeAnnotation::Literal(literal) => format!("#{}", simple_literal(literal)),
+        TypeAnnotation::Tuple(type_annotations) => {
+            format!("({})", type_annotation_list(type_annotations))
+        }
+        TypeAnnotation::Record(fields) => inline_braces(
+            &fields
+                .iter()
+                .map(|(name, annotation)| format!("{}: {}", name, type_annotation(annotation)))
+                .collect::<Vec<String>>(),
+        ),
+        TypeAnnotation::Function(param, return_type_annotation) => {
+            let param = param.as_deref().map(type_annotation).unwrap_or_default();
+
+            match return_type_annotation {
+                Some(return_type_annotation) => {
+                    format!(
+                        "fun({}): {}",
+                        param,
+                        type_annotation(return_type_annotation)
+                    )
+                }
+                None => format!("fun({})", param),
+            }
+        }
+    }
+}
+
+fn type_annotation_list(type_annotations: &[TypeAnnotation]) -> String {
+    type_annotations
+        .iter()
+        .map(type_annotation)
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+/// Literals that don't contain other expressions
+fn simple_literal(literal: &Literal) -> String {
+    match literal {
+        Literal::Unit => "unit".to_string(),
+        Literal::Int(value) => value.to_string(),
+        Literal::UInt(value) => format!("{}u", value),
+        Literal::Float(value) => float(*value),
+        Literal::String(value) => format!("\"{}\"", escape(value, '"')),
+        Literal::Char(value) => format!("'{}'", escape(&value.to_string(), '\'')),
+        Literal::Bool(value) => value.to_string(),
+        Literal::Array(_)
+        | Literal::ArrayRepeat { .. }
+        | Literal::Struct { .. }
+        | Literal::Enum { .. } => {
+            let mut formatter = Formatter {
+                depth: 0,
+                comments: &Comments::default(),
+                next_statement: 0,
+            };
+
+            formatter.literal(literal)
+        }
+    }
+}
+
+fn pattern(pattern: &Pattern) -> String {
+    match pattern {
+        Pattern::Wildcard => "_".to_string(),
+        Pattern::Unit => "unit".to_string(),
+        Pattern::Bool(value) => value.to_string(),
+        Pattern::Int(value) => value.to_string(),
+        Pattern::UInt(value) => format!("{}u", value),
+        Pattern::Float(value) => float(*value),
+        Pattern::Char(value) => format!("'{}'", escape(&value.to_string(), '\'')),
+        Pattern::String(value) => format!("\"{}\"", escape(value, '"')),
+        Pattern::Variable(identifier) => identifier.clone(),
+        Pattern::Constructor(Constructor::Struct {
+            type_annotation: constructor_type_annotation,
+            field_patterns,
+        }) => {
+            if field_patterns.is_empty() {
+                return type_annotation(constructor_type_annotation);
+            }
+
+            let fields = field_patterns
+                .iter()
+                .map(|field_pattern| match &field_pattern.pattern {
+                    Pattern::Variable(variable) if *variable == field_pattern.identifier => {
+                        variable.clone()
+                    }
+                    field => format!("{}: {}", field_pattern.identifier, self::pattern(field)),
+                })
+                .collect::<Vec<String>>();
+
+            format!(
+                "{} {}",
+                type_annotation(constructor_type_annotation),
+                inline_braces(&fields)
+            )
+        }
+        Pattern::LessThan(pattern) => format!("<{}", self::pattern(pattern)),
+        Pattern::GreaterThan(pattern) => format!(">{}", self::pattern(pattern)),
+        Pattern::LessThanOrEqual(pattern) => format!("<={}", self::pattern(pattern)),
+        Pattern::GreaterThanOrEqual(pattern) => format!(">={}", self::pattern(pattern)),
+        Pattern::Range(start, end, inclusive) => format!(
+            "{}{}{}",
+            self::pattern(start),
+            if *inclusive { "..=" } else { ".." },
+            self::pattern(end)
+        ),
+    }
+}
+
+fn float(value: f64) -> String {
+    let formatted = value.to_string();
+
+    if formatted.contains('.') {
+        formatted
+    } else {
+        format!("{}.0", formatted)
+    }
+}
+
+fn escape(value: &str, quote: char) -> String {
+    let mut escaped = String::new();
+
+    for c in value.chars() {
+        match c {
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            '\0' => escaped.push_str("\\0"),
+            '\\' => escaped.push_str("\\\\"),
+            c if c == quote => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
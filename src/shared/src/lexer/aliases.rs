@@ -0,0 +1,62 @@
+use std::collections::HashMap;
+
+use super::{
+    get_reserved_keyword, is_identifier_continue, is_identifier_start, lex,
+    token::{Token, TokenKind},
+};
+
+/// Alternative spellings of keywords and operators, which are read as the same tokens as the
+/// spelling they stand for.
+///
+/// An alias is a word like `func`, so it can stand for an operator like `&&` as well as for a
+/// keyword. It can't be a keyword itself, as that would make it unclear which token is meant.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Aliases {
+    tokens: HashMap<String, TokenKind>,
+}
+
+impl Aliases {
+    pub fn new() -> Aliases {
+        Aliases::default()
+    }
+
+    /// Read `alias` as the token `spelling` is read as, like `add("func", "fun")`
+    pub fn add(&mut self, alias: &str, spelling: &str) -> Result<(), String> {
+        let mut chars = alias.chars();
+
+        if !chars.next().is_some_and(is_identifier_start) || !chars.all(is_identifier_continue) {
+            return Err(format!("Alias '{}' is not a word", alias));
+        }
+
+        if get_reserved_keyword(alias).is_some() {
+            return Err(format!("Alias '{}' is already a keyword", alias));
+        }
+
+        if self.tokens.contains_key(alias) {
+            return Err(format!("Alias '{}' is already defined", alias));
+        }
+
+        let kind = match lex(spelling, &Aliases::default()).as_deref() {
+            // Identifiers are words already, so only the tokens the parser gives a meaning are
+            // allowed
+            Ok([Token { kind, .. }])
+                if !kind.is_trivia() && !matches!(kind, TokenKind::Identifier(_)) =>
+            {
+                kind.clone()
+            }
+            _ => {
+                return Err(format!(
+                    "Alias '{}' has to stand for a keyword or operator, not '{}'",
+                    alias, spelling
+                ))
+            }
+        };
+
+        self.tokens.insert(alias.to_string(), kind);
+        Ok(())
+    }
+
+    pub(super) fn get(&self, word: &str) -> Option<&TokenKind> {
+        self.tokens.get(word)
+    }
+}
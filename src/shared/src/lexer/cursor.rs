@@ -2,6 +2,7 @@ use std::str::Chars;
 
 #[derive(Debug, Clone)]
 pub struct Cursor<'a> {
+    source: &'a str,
     chars: Chars<'a>,
     length_remaining: usize,
 }
@@ -11,6 +12,7 @@ pub(crate) const END_OF_FILE_CHAR: char = '\0';
 impl<'a> Cursor<'a> {
     pub fn new(input: &'a str) -> Cursor<'a> {
         Cursor {
+            source: input,
             length_remaining: input.len(),
             chars: input.chars(),
         }
@@ -26,6 +28,31 @@ impl<'a> Cursor<'a> {
         iter.next().unwrap_or(END_OF_FILE_CHAR)
     }
 
+    pub(crate) fn third(&self) -> char {
+        let mut iter = self.chars.clone();
+        iter.next();
+        iter.next();
+        iter.next().unwrap_or(END_OF_FILE_CHAR)
+    }
+
+    /// The byte offset of the next character
+    pub(crate) fn position(&self) -> usize {
+        self.source.len() - self.chars.as_str().len()
+    }
+
+    /// The line and column of the next character, both starting at 1
+    pub(crate) fn line_column(&self) -> (usize, usize) {
+        let consumed = &self.source[..self.position()];
+        let line = consumed.matches('\n').count() + 1;
+        let column = consumed
+            .rsplit('\n')
+            .next()
+            .map_or(0, |last_line| last_line.chars().count())
+            + 1;
+
+        (line, column)
+    }
+
     pub(crate) fn is_end_of_file(&self) -> bool {
         self.chars.as_str().is_empty()
     }
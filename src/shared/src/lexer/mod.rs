@@ -1,26 +1,125 @@
 use cursor::Cursor;
-use num_lit::parse_float_literal_starting_with_dot;
-use token::{Literal, Token, TokenKind};
+use num_lit::{parse_float_literal_starting_with_dot, parse_tuple_index};
+use token::{Literal, SpannedToken, Token, TokenKind};
 
-use self::{num_lit::parse_numeric_literal, token::Keyword};
+use crate::{diagnostic::Diagnostic, span::Span};
 
+use self::{aliases::Aliases, num_lit::parse_numeric_literal, token::Keyword};
+
+pub mod aliases;
 pub mod cursor;
 mod num_lit;
 pub mod token;
 
+/// Source which isn't made up of tokens, and where the token it failed at starts
+#[derive(Debug, Clone, PartialEq)]
+pub struct LexError {
+    pub message: String,
+    pub span: Span,
+}
+
+impl From<LexError> for Diagnostic {
+    fn from(error: LexError) -> Self {
+        Diagnostic::new(error.message, error.span)
+    }
+}
+
 pub fn tokenize(source_code: &str) -> Result<Vec<Token>, String> {
+    lex(source_code, &Aliases::default()).map_err(|error| error.message)
+}
+
+/// Tokenize like `tokenize`, reading each of the `aliases` as the token it stands for, so the
+/// parsed tree is the same as with the canonical spellings
+pub fn tokenize_with_aliases(source_code: &str, aliases: &Aliases) -> Result<Vec<Token>, String> {
+    lex(source_code, aliases).map_err(|error| error.message)
+}
+
+/// Tokenize like `tokenize`, giving every token the span it is written at instead of its length.
+///
+/// Whitespace and comments are only kept when `trivia` is set, for tools like formatters which
+/// have to write them back.
+pub fn tokenize_with_spans(source_code: &str, trivia: bool) -> Result<Vec<SpannedToken>, LexError> {
+    let mut start = 0;
+    let mut tokens = vec![];
+
+    for token in lex(source_code, &Aliases::default())? {
+        let span = Span::new(start, start + token.length as usize);
+        start = span.end;
+
+        if trivia || !token.kind.is_trivia() {
+            tokens.push(SpannedToken {
+                kind: token.kind,
+                span,
+            });
+        }
+    }
+
+    Ok(tokens)
+}
+
+fn lex(source_code: &str, aliases: &Aliases) -> Result<Vec<Token>, LexError> {
     let mut tokens = Vec::new();
     let mut cursor = cursor::Cursor::new(source_code);
 
     while !cursor.is_end_of_file() {
-        let token = tokenize_next(&mut cursor);
+        let start = cursor.position();
+
+        let error = |message: String, cursor: &Cursor| {
+            // The failed token reaches at least over the character it starts with
+            let first = source_code[start..]
+                .chars()
+                .next()
+                .map_or(0, char::len_utf8);
+            let end = cursor.position().max(start + first);
+
+            LexError {
+                message,
+                span: Span::new(start, end),
+            }
+        };
+
+        if cursor.first() == '.' && cursor.second().is_ascii_digit() && ends_with_object(&tokens) {
+            cursor.reset_position_within_token();
+            tokens.push(create_token(TokenKind::Dot, &mut cursor));
+            cursor.reset_position_within_token();
+            let index = parse_tuple_index(&mut cursor).map_err(|e| error(e, &cursor))?;
+            tokens.push(index);
+            continue;
+        }
+
+        let token = tokenize_next(&mut cursor, aliases).map_err(|e| error(e, &cursor))?;
         tokens.push(token);
     }
 
-    transpose(tokens)
+    Ok(tokens)
+}
+
+/// Whether a `.` directly after the tokens accesses a member of what they end with, so the
+/// digits after it are a tuple index and `pair.0.1` doesn't read as two floats
+fn ends_with_object(tokens: &[Token]) -> bool {
+    matches!(
+        tokens,
+        [
+            ..,
+            Token {
+                kind: TokenKind::Identifier(_) | TokenKind::CloseParen | TokenKind::CloseBracket,
+                ..
+            }
+        ] | [
+            ..,
+            Token {
+                kind: TokenKind::Dot,
+                ..
+            },
+            Token {
+                kind: TokenKind::Literal(Literal::Int(_)),
+                ..
+            }
+        ]
+    )
 }
 
-fn tokenize_next(cursor: &mut Cursor) -> Result<Token, String> {
+fn tokenize_next(cursor: &mut Cursor, aliases: &Aliases) -> Result<Token, String> {
     cursor.reset_position_within_token();
 
     match cursor.first() {
@@ -40,7 +139,10 @@ fn tokenize_next(cursor: &mut Cursor) -> Result<Token, String> {
         '[' => Ok(create_token(TokenKind::OpenBracket, cursor)),
         ']' => Ok(create_token(TokenKind::CloseBracket, cursor)),
         ',' => Ok(create_token(TokenKind::Comma, cursor)),
-        '_' => Ok(create_token(TokenKind::Underscore, cursor)),
+        // `_name` is an identifier, only a lone `_` is the wildcard
+        '_' if !is_identifier_continue(cursor.second()) => {
+            Ok(create_token(TokenKind::Underscore, cursor))
+        }
         '#' => Ok(create_token(TokenKind::Hash, cursor)),
         ':' => match cursor.second() {
             ':' => {
@@ -59,11 +161,16 @@ fn tokenize_next(cursor: &mut Cursor) -> Result<Token, String> {
             _ => Ok(create_token(TokenKind::Dot, cursor)),
         },
         '?' => Ok(create_token(TokenKind::QuestionMark, cursor)),
+        '@' => Ok(create_token(TokenKind::At, cursor)),
         '+' => match cursor.second() {
             '=' => {
                 cursor.bump();
                 Ok(create_token(TokenKind::PlusEqual, cursor))
             }
+            '+' => {
+                cursor.bump();
+                Ok(create_token(TokenKind::DoublePlus, cursor))
+            }
             _ => Ok(create_token(TokenKind::Plus, cursor)),
         },
         '-' => match cursor.second() {
@@ -75,6 +182,10 @@ fn tokenize_next(cursor: &mut Cursor) -> Result<Token, String> {
                 cursor.bump();
                 Ok(create_token(TokenKind::MinusEqual, cursor))
             }
+            '-' => {
+                cursor.bump();
+                Ok(create_token(TokenKind::DoubleMinus, cursor))
+            }
             _ => Ok(create_token(TokenKind::Minus, cursor)),
         },
         '*' => match cursor.second() {
@@ -86,9 +197,32 @@ fn tokenize_next(cursor: &mut Cursor) -> Result<Token, String> {
         },
         '/' => match cursor.second() {
             '/' => {
-                cursor.eat_while(|c| !is_end_of_line_comment(c));
+                cursor.bump();
+                cursor.bump();
+
+                // Exactly three slashes start a doc comment, more are a regular comment
+                if cursor.first() != '/' || cursor.second() == '/' {
+                    cursor.eat_while(|c| !is_end_of_line_comment(c));
+                    return Ok(Token {
+                        kind: TokenKind::LineComment,
+                        length: cursor.position_within_token(),
+                    });
+                }
+
+                cursor.bump(); // Consume the third /
+
+                if cursor.first() == ' ' {
+                    cursor.bump();
+                }
+
+                let mut doc = String::new();
+
+                while !is_end_of_line_comment(cursor.first()) {
+                    doc.push(cursor.bump().unwrap());
+                }
+
                 Ok(Token {
-                    kind: TokenKind::LineComment,
+                    kind: TokenKind::DocComment(doc),
                     length: cursor.position_within_token(),
                 })
             }
@@ -202,40 +336,21 @@ fn tokenize_next(cursor: &mut Cursor) -> Result<Token, String> {
             }
             _ => Ok(create_token(TokenKind::Greater, cursor)),
         },
-        '0'..='9' => Ok(parse_numeric_literal(cursor)),
-        '"' => {
-            cursor.bump(); // Consume the "
-            let mut string = String::new();
-
-            while cursor.first() != '"' {
-                if cursor.first() == '\\' {
-                    match escapable_is_string(cursor.second()) {
-                        Some(c) => {
-                            string.push(c);
-                            cursor.bump();
-                            cursor.bump();
-                        }
-                        None => {
-                            string.push(cursor.bump().unwrap());
-                        }
-                    }
-
-                    continue;
-                }
-
-                string.push(cursor.bump().unwrap());
-            }
-
-            cursor.bump();
-            Ok(Token {
-                kind: TokenKind::Literal(Literal::String(string)),
-                length: cursor.position_within_token(),
-            })
+        '0'..='9' => parse_numeric_literal(cursor),
+        '"' if cursor.second() == '"' && cursor.third() == '"' => {
+            tokenize_multi_line_string(cursor)
         }
+        '"' => tokenize_string(cursor),
+        'r' if cursor.second() == '"' => tokenize_raw_string(cursor),
         '\'' => {
+            let start = cursor.line_column();
             cursor.bump();
             let mut string = String::new();
 
+            if cursor.is_end_of_file() {
+                return Err(unterminated_character(start));
+            }
+
             if cursor.first() == '\\' {
                 match escapable_is_char(cursor.second()) {
                     Some(c) => {
@@ -257,7 +372,7 @@ fn tokenize_next(cursor: &mut Cursor) -> Result<Token, String> {
                 length: cursor.position_within_token(),
             })
         }
-        '\0' => Ok(Token {
+        '\0' if cursor.is_end_of_file() => Ok(Token {
             kind: TokenKind::EndOfFile,
             length: cursor.position_within_token(),
         }),
@@ -276,6 +391,13 @@ fn tokenize_next(cursor: &mut Cursor) -> Result<Token, String> {
                     });
                 }
 
+                if let Some(kind) = aliases.get(&string) {
+                    return Ok(Token {
+                        kind: kind.clone(),
+                        length: cursor.position_within_token(),
+                    });
+                }
+
                 return Ok(Token {
                     kind: TokenKind::Identifier(string),
                     length: cursor.position_within_token(),
@@ -287,6 +409,124 @@ fn tokenize_next(cursor: &mut Cursor) -> Result<Token, String> {
     }
 }
 
+/// A string between `"`, where a `\` escapes the next character
+fn tokenize_string(cursor: &mut Cursor) -> Result<Token, String> {
+    let start = cursor.line_column();
+    cursor.bump(); // Consume the "
+    let mut string = String::new();
+
+    while cursor.first() != '"' {
+        if cursor.is_end_of_file() {
+            return Err(unterminated_string(start));
+        }
+
+        if cursor.first() == '\\' {
+            match escapable_is_string(cursor.second()) {
+                Some(c) => {
+                    string.push(c);
+                    cursor.bump();
+                    cursor.bump();
+                }
+                None => {
+                    string.push(cursor.bump().unwrap());
+                }
+            }
+
+            continue;
+        }
+
+        string.push(cursor.bump().unwrap());
+    }
+
+    cursor.bump(); // Consume the "
+    Ok(Token {
+        kind: TokenKind::Literal(Literal::String(string)),
+        length: cursor.position_within_token(),
+    })
+}
+
+/// A string like `r"C:\path"`, which has no escapes and ends at the next `"`
+fn tokenize_raw_string(cursor: &mut Cursor) -> Result<Token, String> {
+    let start = cursor.line_column();
+    cursor.bump(); // Consume the r
+    cursor.bump(); // Consume the "
+    let mut string = String::new();
+
+    while cursor.first() != '"' {
+        if cursor.is_end_of_file() {
+            return Err(unterminated_string(start));
+        }
+
+        string.push(cursor.bump().unwrap());
+    }
+
+    cursor.bump(); // Consume the "
+    Ok(Token {
+        kind: TokenKind::Literal(Literal::String(string)),
+        length: cursor.position_within_token(),
+    })
+}
+
+/// A string between `"""`, which may hold single quotes. The line break right after the opening
+/// quotes is left out, so the text can start on its own line.
+fn tokenize_multi_line_string(cursor: &mut Cursor) -> Result<Token, String> {
+    let start = cursor.line_column();
+
+    for _ in 0..3 {
+        cursor.bump(); // Consume the """
+    }
+
+    if cursor.first() == '\r' && cursor.second() == '\n' {
+        cursor.bump();
+    }
+
+    if cursor.first() == '\n' {
+        cursor.bump();
+    }
+
+    let mut string = String::new();
+
+    while !(cursor.first() == '"' && cursor.second() == '"' && cursor.third() == '"') {
+        if cursor.is_end_of_file() {
+            return Err(unterminated_string(start));
+        }
+
+        if cursor.first() == '\\' {
+            if let Some(c) = escapable_is_string(cursor.second()) {
+                string.push(c);
+                cursor.bump();
+                cursor.bump();
+                continue;
+            }
+        }
+
+        string.push(cursor.bump().unwrap());
+    }
+
+    for _ in 0..3 {
+        cursor.bump(); // Consume the """
+    }
+
+    Ok(Token {
+        kind: TokenKind::Literal(Literal::String(string)),
+        length: cursor.position_within_token(),
+    })
+}
+
+fn unterminated_string((line, column): (usize, usize)) -> String {
+    format!(
+        "Unterminated string starting at line {}, column {}",
+        line, column
+    )
+}
+
+fn unterminated_character((line, column): (usize, usize)) -> String {
+    format!(
+        "Unterminated character starting at line {}, column {}",
+        line, column
+    )
+}
+
 fn is_identifier_start(c: char) -> bool {
     c.is_alphabetic() || c == '_'
 }
@@ -333,6 +573,7 @@ fn get_reserved_keyword(string: &str) -> Option<TokenKind> {
         // Access modifiers
         "pub" => Some(TokenKind::Keyword(Keyword::Pub)),
         "mod" => Some(TokenKind::Keyword(Keyword::Mod)),
+        "use" => Some(TokenKind::Keyword(Keyword::Use)),
         "sup" => Some(TokenKind::Keyword(Keyword::Sup)),
 
         // Variable declarations
@@ -348,6 +589,8 @@ fn get_reserved_keyword(string: &str) -> Option<TokenKind> {
         "imp" => Some(TokenKind::Keyword(Keyword::Imp)),
         "proto" => Some(TokenKind::Keyword(Keyword::Proto)),
         "type" => Some(TokenKind::Keyword(Keyword::Type)),
+        "newtype" => Some(TokenKind::Keyword(Keyword::Newtype)),
+        "as" => Some(TokenKind::Keyword(Keyword::As)),
 
         // Generics
         "where" => Some(TokenKind::Keyword(Keyword::Where)),
@@ -389,16 +632,3 @@ fn create_token(kind: TokenKind, cursor: &mut Cursor<'_>) -> Token {
         length: cursor.position_within_token(),
     }
 }
-
-fn transpose(tokens: Vec<Result<Token, String>>) -> Result<Vec<Token>, String> {
-    let mut tks = Vec::new();
-
-    for token in tokens {
-        match token {
-            Ok(token) => tks.push(token),
-            Err(err) => return Err(err),
-        }
-    }
-
-    Ok(tks)
-}
@@ -27,7 +27,29 @@ pub fn parse_float_literal_starting_with_dot(cursor: &mut Cursor) -> Result<Toke
     })
 }
 
-pub fn parse_numeric_literal(cursor: &mut Cursor) -> Token {
+/// Lex the digits of a tuple index like the `0` of `pair.0`, which never continue into a float
+pub fn parse_tuple_index(cursor: &mut Cursor) -> Result<Token, String> {
+    let mut value = String::new();
+
+    while cursor.first().is_ascii_digit() {
+        value.push(cursor.first());
+        cursor.bump(); // consume the digit
+    }
+
+    let kind = TokenKind::Literal(Literal::Int(IntLiteral::<i64> {
+        value: value
+            .parse::<i64>()
+            .map_err(|_| format!("Invalid tuple index: {}", value))?,
+        base: IntLiteralBase::None,
+    }));
+
+    Ok(Token {
+        kind,
+        length: cursor.position_within_token(),
+    })
+}
+
+pub fn parse_numeric_literal(cursor: &mut Cursor) -> Result<Token, String> {
     let base: IntLiteralBase = parse_base_prefix(cursor).unwrap_or(IntLiteralBase::None);
     let value = parse_numeric_literal_value(cursor, base.clone());
     let suffix = parse_suffix(cursor).unwrap_or_else(|| {
@@ -42,24 +64,26 @@ pub fn parse_numeric_literal(cursor: &mut Cursor) -> Token {
         }
     });
 
+    let invalid = || format!("Invalid numeric literal: {}{}", base, value);
+
     let kind = match suffix {
         NumericLiteralType::Int => TokenKind::Literal(Literal::Int(IntLiteral::<i64> {
-            value: value.parse::<i64>().unwrap(),
-            base,
+            value: value.parse::<i64>().map_err(|_| invalid())?,
+            base: base.clone(),
         })),
         NumericLiteralType::UInt => TokenKind::Literal(Literal::UInt(IntLiteral::<u64> {
-            value: value.parse::<u64>().unwrap(),
-            base,
+            value: value.parse::<u64>().map_err(|_| invalid())?,
+            base: base.clone(),
         })),
-        NumericLiteralType::Float => TokenKind::Literal(Literal::Float(
-            value.parse::<f64>().expect("Failed to parse float literal"),
-        )),
+        NumericLiteralType::Float => {
+            TokenKind::Literal(Literal::Float(value.parse::<f64>().map_err(|_| invalid())?))
+        }
     };
 
-    Token {
+    Ok(Token {
         kind,
         length: cursor.position_within_token(),
-    }
+    })
 }
 
 fn parse_base_prefix(cursor: &mut Cursor) -> Option<IntLiteralBase> {
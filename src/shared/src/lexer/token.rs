@@ -4,7 +4,7 @@ use regex;
 
 use num_traits::int::PrimInt;
 
-use crate::{parser::AccessModifier, pretty_print::PrettyPrint};
+use crate::{parser::AccessModifier, pretty_print::PrettyPrint, span::Span};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Token {
@@ -12,6 +12,13 @@ pub struct Token {
     pub length: u32,
 }
 
+/// A token and where it is written
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpannedToken {
+    pub kind: TokenKind,
+    pub span: Span,
+}
+
 impl<T: IntoIterator<Item = Token> + Clone> PrettyPrint for T {
     fn prettify(&self) -> String {
         self.clone()
@@ -26,6 +33,7 @@ impl<T: IntoIterator<Item = Token> + Clone> PrettyPrint for T {
 pub enum TokenKind {
     // Comments
     LineComment,
+    DocComment(String),
     BlockComment,
 
     // Whitespace
@@ -34,8 +42,10 @@ pub enum TokenKind {
     // Operators
     Plus,
     PlusEqual,
+    DoublePlus,
     Minus,
     MinusEqual,
+    DoubleMinus,
     Star,
     StarEqual,
     Slash,
@@ -84,6 +94,7 @@ pub enum TokenKind {
     Dot,
     DoubleDot,
     QuestionMark,
+    At,
     Arrow,
     FatArrow,
 
@@ -91,6 +102,17 @@ pub enum TokenKind {
 }
 
 impl TokenKind {
+    /// Whether the token is whitespace or a comment, which only matters to tools like formatters
+    pub fn is_trivia(&self) -> bool {
+        matches!(
+            self,
+            TokenKind::WhiteSpace
+                | TokenKind::LineComment
+                | TokenKind::DocComment(_)
+                | TokenKind::BlockComment
+        )
+    }
+
     pub fn is_access_modifier(&self) -> Option<AccessModifier> {
         match self {
             TokenKind::Keyword(Keyword::Pub) => Some(AccessModifier::Public),
@@ -189,6 +211,8 @@ pub enum Keyword {
     Imp,
     Proto,
     Type,
+    Newtype,
+    As,
 
     // Generics
     Where,
@@ -1,6 +1,10 @@
+pub mod codegen;
+pub mod diagnostic;
 pub mod display;
+pub mod format;
 pub mod lexer;
 pub mod parser;
 pub mod pretty_print;
+pub mod span;
 pub mod type_checker;
 pub mod types;
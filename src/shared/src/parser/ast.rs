@@ -2,24 +2,32 @@ use std::hash::Hash;
 use std::{collections::HashMap, fmt::Display};
 
 use crate::display::{Indent, IndentDisplay};
+use crate::format::format_expression;
 use crate::pretty_print::PrettyPrint;
+use crate::span::Span;
 use crate::type_checker::decision_tree::Pattern;
 use crate::types::{GenericConstraint, GenericType, ToKey, TypeAnnotation, TypeIdentifier};
 
 #[derive(Debug, Clone, PartialEq)]
+#[allow(clippy::large_enum_variant)]
 pub enum Statement {
-    Program { statements: Vec<Statement> },
+    Program {
+        statements: Vec<Statement>,
+    },
     ModuleDeclaration(ModuleDeclaration),
     Use(Use),
     StructDeclaration(StructDeclaration),
     EnumDeclaration(EnumDeclaration),
     UnionDeclaration(UnionDeclaration),
     TypeAliasDeclaration(TypeAliasDeclaration),
+    NewtypeDeclaration(NewtypeDeclaration),
     ProtocolDeclaration(ProtocolDeclaration),
     ImplementationDeclaration(ImplementationDeclaration),
     FunctionDeclaration(FunctionDeclaration),
     Semi(Box<Statement>),
     Expression(Expression),
+    /// A placeholder for a statement which failed to parse, where the parser recovered from it
+    Error(Span),
 }
 
 impl PrettyPrint for Statement {
@@ -39,12 +47,19 @@ pub enum Expression {
     Match(Match),
     Assignment(Assignment),
     Member(Member),
-    Literal(Literal),
+    /// A literal and where it is written
+    Literal(Literal, Span),
     Tuple(Vec<Expression>),
+    Record(Vec<FieldInitializer>),
     Closure(Closure),
     Call(Call),
+    Index(Index),
     Unary(Unary),
     Binary(Binary),
+    Cast(Cast),
+    Coalesce(Coalesce),
+    /// `expression?`, the value of an `Ok` or an early return of the `Err`
+    Try(Box<Expression>),
     Block(Block),
     Loop(Box<Expression>),
     While(While),
@@ -71,6 +86,8 @@ impl PrettyPrint for Expression {
 pub struct ModuleDeclaration {
     pub access_modifier: Option<AccessModifier>,
     pub module_path: Vec<String>,
+    /// Where the module path is written
+    pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -83,12 +100,14 @@ pub enum UseItem {
     Item(String),
     Navigation(String, Box<UseItem>),
     List(Vec<UseItem>),
+    Glob,
 }
 
 impl Display for UseItem {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             UseItem::Item(item) => write!(f, "{}", item),
+            UseItem::Glob => write!(f, "*"),
             UseItem::Navigation(item, next) => write!(f, "{}::{}", item, next),
             UseItem::List(items) => write!(
                 f,
@@ -105,16 +124,24 @@ impl Display for UseItem {
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct StructDeclaration {
+    pub doc: Option<String>,
+    pub attributes: Vec<Attribute>,
     pub access_modifier: Option<AccessModifier>,
     pub type_identifier: TypeIdentifier,
+    /// Where the type identifier is written
+    pub span: Span,
     pub where_clause: Option<Vec<GenericConstraint>>,
     pub fields: Vec<StructField>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct EnumDeclaration {
+    pub doc: Option<String>,
+    pub attributes: Vec<Attribute>,
     pub access_modifier: Option<AccessModifier>,
     pub type_identifier: TypeIdentifier,
+    /// Where the type identifier is written
+    pub span: Span,
     pub where_clause: Option<Vec<GenericConstraint>>,
     pub shared_fields: Vec<StructField>,
     pub members: Vec<EnumMember>,
@@ -122,6 +149,8 @@ pub struct EnumDeclaration {
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct UnionDeclaration {
+    pub doc: Option<String>,
+    pub attributes: Vec<Attribute>,
     pub access_modifier: Option<AccessModifier>,
     pub type_identifier: TypeIdentifier,
     pub literals: Vec<Literal>,
@@ -129,13 +158,26 @@ pub struct UnionDeclaration {
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct TypeAliasDeclaration {
+    pub doc: Option<String>,
+    pub attributes: Vec<Attribute>,
     pub access_modifier: Option<AccessModifier>,
     pub type_identifier: TypeIdentifier,
     pub type_annotations: Vec<TypeAnnotation>,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewtypeDeclaration {
+    pub doc: Option<String>,
+    pub attributes: Vec<Attribute>,
+    pub access_modifier: Option<AccessModifier>,
+    pub type_identifier: TypeIdentifier,
+    pub type_annotation: TypeAnnotation,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct ProtocolDeclaration {
+    pub doc: Option<String>,
+    pub attributes: Vec<Attribute>,
     pub access_modifier: Option<AccessModifier>,
     pub type_identifier: TypeIdentifier,
     pub associated_types: Vec<AssociatedType>,
@@ -144,8 +186,10 @@ pub struct ProtocolDeclaration {
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct ImplementationDeclaration {
+    pub doc: Option<String>,
+    pub attributes: Vec<Attribute>,
     pub scoped_generics: Vec<GenericType>,
-    pub protocol_annotation: TypeAnnotation,
+    pub protocol_annotation: Option<TypeAnnotation>,
     pub type_annotation: TypeAnnotation,
     pub associated_types: Vec<AssociatedType>,
     pub functions: Vec<FunctionDeclaration>,
@@ -163,10 +207,39 @@ impl Display for AssociatedType {
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attribute {
+    pub name: String,
+    pub args: Vec<Literal>,
+}
+
+impl Display for Attribute {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.args.is_empty() {
+            return write!(f, "@{}", self.name);
+        }
+
+        write!(
+            f,
+            "@{}({})",
+            self.name,
+            self.args
+                .iter()
+                .map(|arg| arg.to_string())
+                .collect::<Vec<String>>()
+                .join(", ")
+        )
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct FunctionDeclaration {
+    pub doc: Option<String>,
+    pub attributes: Vec<Attribute>,
     pub access_modifier: Option<AccessModifier>,
     pub type_identifier: TypeIdentifier,
+    /// Where the type identifier is written
+    pub span: Span,
     pub param: Option<Parameter>,
     pub return_type_annotation: Option<TypeAnnotation>,
     pub where_clause: Option<Vec<GenericConstraint>>,
@@ -178,12 +251,20 @@ pub struct FunctionDeclaration {
 pub struct Parameter {
     pub identifier: String,
     pub type_annotation: TypeAnnotation,
+    /// Whether the parameter is `mut self`, which lets the function assign to the fields of its
+    /// receiver. The receiver is passed by value like any argument, so the function changes its
+    /// own copy and the value it was called on is left as it was.
+    pub mutable: bool,
+    /// Where the identifier is written
+    pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct ClosureParameter {
     pub identifier: String,
     pub type_annotation: Option<TypeAnnotation>,
+    /// Where the identifier is written
+    pub span: Span,
 }
 
 impl From<Parameter> for ClosureParameter {
@@ -191,6 +272,7 @@ impl From<Parameter> for ClosureParameter {
         Self {
             identifier: param.identifier,
             type_annotation: Some(param.type_annotation),
+            span: param.span,
         }
     }
 }
@@ -205,10 +287,16 @@ pub enum Literal {
     Char(char),
     Bool(bool),
     Array(Vec<Expression>),
+    /// `[value; length]`, an array holding the value as many times as the constant length says
+    ArrayRepeat {
+        value: Box<Expression>,
+        length: Box<Expression>,
+    },
 
     Struct {
         type_annotation: TypeAnnotation,
         field_initializers: Vec<FieldInitializer>,
+        spread: Option<Box<Expression>>,
     },
     Enum {
         type_annotation: TypeAnnotation,
@@ -240,7 +328,7 @@ impl Display for Literal {
                 array
                     .iter()
                     .map(|e| {
-                        if let Expression::Literal(literal) = e {
+                        if let Expression::Literal(literal, _) = e {
                             literal.to_string()
                         } else {
                             panic!("Array element is not a literal")
@@ -249,6 +337,12 @@ impl Display for Literal {
                     .collect::<Vec<String>>()
                     .join(", ")
             ),
+            Literal::ArrayRepeat { value, length } => write!(
+                f,
+                "[{}; {}]",
+                format_expression(value),
+                format_expression(length)
+            ),
             Literal::Struct { .. } => todo!(),
             Literal::Enum { .. } => todo!(),
         }
@@ -270,7 +364,7 @@ impl ToKey for Literal {
                 array
                     .iter()
                     .map(|e| {
-                        if let Expression::Literal(literal) = e {
+                        if let Expression::Literal(literal, _) = e {
                             literal.to_key()
                         } else {
                             panic!("Array element is not a literal")
@@ -279,6 +373,9 @@ impl ToKey for Literal {
                     .collect::<Vec<String>>()
                     .join(", ")
             ),
+            Literal::ArrayRepeat { value, length } => {
+                format!("[{}; {}]", expression_key(value), expression_key(length))
+            }
             Literal::Struct {
                 type_annotation, ..
             } => type_annotation.to_key(),
@@ -291,6 +388,14 @@ impl ToKey for Literal {
     }
 }
 
+/// The key of a literal expression, or its source for any other expression
+fn expression_key(expression: &Expression) -> String {
+    match expression {
+        Expression::Literal(literal, _) => literal.to_key(),
+        expression => format_expression(expression),
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct While {
     pub condition: Box<Expression>,
@@ -301,6 +406,8 @@ pub struct While {
 #[derive(Debug, Clone, PartialEq)]
 pub struct For {
     pub identifier: String,
+    /// Where the identifier is written
+    pub identifier_span: Span,
     pub iterable: Box<Expression>,
     pub body: Box<Expression>,
     pub else_body: Option<Box<Expression>>,
@@ -311,7 +418,17 @@ pub struct StructField {
     pub access_modifier: Option<AccessModifier>,
     pub mutable: bool,
     pub identifier: String,
+    /// Where the identifier is written, or the type of a field of a tuple struct
+    pub span: Span,
     pub type_annotation: TypeAnnotation,
+    pub default: Option<Expression>,
+}
+
+impl StructField {
+    /// Whether the field is a field of a tuple struct, which is named by its index
+    pub fn is_positional(&self) -> bool {
+        self.identifier.starts_with(|c: char| c.is_ascii_digit())
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -323,7 +440,11 @@ pub struct FieldInitializer {
 #[derive(Debug, Clone, PartialEq)]
 pub struct EnumMember {
     pub identifier: String,
+    /// Where the identifier is written
+    pub span: Span,
     pub fields: Vec<EnumMemberField>,
+    /// The discriminant given with `= 1`, which members without one count up from
+    pub discriminant: Option<i64>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -371,6 +492,7 @@ pub enum Member {
     Identifier {
         symbol: String,
         generics: Option<Vec<GenericType>>,
+        span: Span,
     },
     StaticMemberAccess {
         type_annotation: TypeAnnotation,
@@ -383,6 +505,8 @@ pub enum Member {
         member: Box<Member>,
         symbol: String,
         generics: Option<Vec<GenericType>>,
+        /// `?.` which only accesses the member when the object is Some
+        optional: bool,
     },
     ParamPropagation {
         object: Box<Expression>,
@@ -390,6 +514,9 @@ pub enum Member {
         symbol: String,
         generics: Option<Vec<GenericType>>,
     },
+    /// A value reached through modules like `math::abs`, resolved against the module tree. The
+    /// span is where the last segment is written.
+    Path(Vec<String>, Span),
 }
 
 impl Member {
@@ -399,14 +526,16 @@ impl Member {
             Member::StaticMemberAccess { symbol, .. } => symbol.clone(),
             Member::MemberAccess { symbol, .. } => symbol.clone(),
             Member::ParamPropagation { symbol, .. } => symbol.clone(),
+            Member::Path(path, _) => path.last().cloned().unwrap_or_default(),
         }
     }
 
     pub fn with_generics(self, generics: Vec<GenericType>) -> Self {
         match self {
-            Member::Identifier { symbol, .. } => Member::Identifier {
+            Member::Identifier { symbol, span, .. } => Member::Identifier {
                 symbol,
                 generics: Some(generics),
+                span,
             },
             Member::StaticMemberAccess {
                 type_annotation,
@@ -423,12 +552,14 @@ impl Member {
                 object,
                 member,
                 symbol,
+                optional,
                 ..
             } => Member::MemberAccess {
                 object,
                 member: Box::new(member.with_generics(generics.clone())),
                 symbol,
                 generics: Some(generics),
+                optional,
             },
             Member::ParamPropagation {
                 object,
@@ -441,6 +572,7 @@ impl Member {
                 symbol,
                 generics: Some(generics),
             },
+            Member::Path(path, span) => Member::Path(path, span),
         }
     }
 }
@@ -448,7 +580,9 @@ impl Member {
 impl Display for Member {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Member::Identifier { symbol, generics } => {
+            Member::Identifier {
+                symbol, generics, ..
+            } => {
                 if let Some(generics) = generics {
                     write!(
                         f,
@@ -469,12 +603,15 @@ impl Display for Member {
                 member,
                 ..
             } => write!(f, "{}::{}", type_annotation, member),
-            Member::MemberAccess { member, .. } => {
-                write!(f, ".{}", member)
+            Member::MemberAccess {
+                member, optional, ..
+            } => {
+                write!(f, "{}.{}", if *optional { "?" } else { "" }, member)
             }
             Member::ParamPropagation { member, .. } => {
                 write!(f, ":{}", member)
             }
+            Member::Path(path, _) => write!(f, "{}", path.join("::")),
         }
     }
 }
@@ -482,7 +619,9 @@ impl Display for Member {
 impl ToKey for Member {
     fn to_key(&self) -> String {
         match self {
-            Member::Identifier { symbol, generics } => {
+            Member::Identifier {
+                symbol, generics, ..
+            } => {
                 if let Some(generics) = generics {
                     format!(
                         "{}<{}>",
@@ -502,12 +641,15 @@ impl ToKey for Member {
                 member,
                 ..
             } => format!("{}::{}", type_annotation.to_key(), member.to_key()),
-            Member::MemberAccess { member, .. } => {
-                format!(".{}", member.to_key())
+            Member::MemberAccess {
+                member, optional, ..
+            } => {
+                format!("{}.{}", if *optional { "?" } else { "" }, member.to_key())
             }
             Member::ParamPropagation { member, .. } => {
                 format!(":{}", member.to_key())
             }
+            Member::Path(path, _) => path.join("::"),
         }
     }
 }
@@ -517,6 +659,8 @@ pub struct VariableDeclaration {
     pub mutable: bool,
     pub type_annotation: Option<TypeAnnotation>,
     pub pattern: Pattern,
+    /// Where the pattern is written
+    pub pattern_span: Span,
     pub initializer: Option<Box<Expression>>,
 }
 
@@ -531,6 +675,8 @@ pub struct If {
 pub struct Match {
     pub expression: Box<Expression>,
     pub arms: Vec<MatchArm>,
+    /// Where the whole expression is written
+    pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -542,6 +688,9 @@ pub struct MatchArm {
 #[derive(Debug, Clone, PartialEq)]
 pub struct Assignment {
     pub member: Box<Member>,
+    /// The operator of a compound assignment like `x += 1`, which leaves only the right side in
+    /// the initializer
+    pub operator: Option<BinaryOperator>,
     pub initializer: Box<Expression>,
 }
 
@@ -556,12 +705,25 @@ pub struct Closure {
 pub struct Call {
     pub callee: Box<Expression>,
     pub argument: Option<Box<Expression>>,
+    /// Where the whole expression is written
+    pub span: Span,
+}
+
+/// `callee[argument]`, an element of an array or a slice of it when the argument is a range
+#[derive(Debug, Clone, PartialEq)]
+pub struct Index {
+    pub callee: Box<Expression>,
+    pub argument: Box<Expression>,
+    /// Where the whole expression is written
+    pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Unary {
     pub operator: UnaryOperator,
     pub expression: Box<Expression>,
+    /// Where the whole expression is written
+    pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -569,6 +731,23 @@ pub struct Binary {
     pub left: Box<Expression>,
     pub operator: BinaryOperator,
     pub right: Box<Expression>,
+    /// Where the whole expression is written
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cast {
+    pub expression: Box<Expression>,
+    pub target: TypeAnnotation,
+    /// Where the whole expression is written
+    pub span: Span,
+}
+
+/// `value ?? default`, the inner value of an option or the default when it is None
+#[derive(Debug, Clone, PartialEq)]
+pub struct Coalesce {
+    pub value: Box<Expression>,
+    pub default: Box<Expression>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -0,0 +1,217 @@
+//! Constructors for building syntax trees without parsing source, like
+//! `binary(int(1), BinaryOperator::Add, identifier("x"))` for `1 + x`.
+//!
+//! The nodes are shaped the way the parser shapes them, so calls and functions with several
+//! parameters are curried. Nothing built here is written anywhere, so every span is empty.
+
+use crate::{
+    span::Span,
+    type_checker::decision_tree::Pattern,
+    types::{TypeAnnotation, TypeIdentifier},
+};
+
+use super::{
+    statements::unwrap_parameters, Binary, BinaryOperator, Call, Expression, FieldInitializer,
+    Literal, Member, Parameter, Statement, StructDeclaration, StructField, Unary, UnaryOperator,
+    VariableDeclaration,
+};
+
+pub fn program(statements: Vec<Statement>) -> Statement {
+    Statement::Program { statements }
+}
+
+/// An expression statement. Its value is the value of the block it is the last statement of.
+pub fn statement(expression: Expression) -> Statement {
+    Statement::Expression(expression)
+}
+
+/// An expression statement ending with `;`, which throws its value away
+pub fn semi(expression: Expression) -> Statement {
+    Statement::Semi(Box::new(statement(expression)))
+}
+
+/// The type named `name`, like `Int` or `Point`
+pub fn type_(name: &str) -> TypeAnnotation {
+    TypeAnnotation::Type(name.to_string())
+}
+
+pub fn unit() -> Expression {
+    literal(Literal::Unit)
+}
+
+pub fn int(value: i64) -> Expression {
+    literal(Literal::Int(value))
+}
+
+pub fn uint(value: u64) -> Expression {
+    literal(Literal::UInt(value))
+}
+
+pub fn float(value: f64) -> Expression {
+    literal(Literal::Float(value))
+}
+
+pub fn bool(value: bool) -> Expression {
+    literal(Literal::Bool(value))
+}
+
+pub fn char(value: char) -> Expression {
+    literal(Literal::Char(value))
+}
+
+pub fn string(value: &str) -> Expression {
+    literal(Literal::String(value.to_string()))
+}
+
+pub fn array(elements: Vec<Expression>) -> Expression {
+    literal(Literal::Array(elements))
+}
+
+/// `Name { field: value, .. }`
+pub fn struct_literal(name: &str, fields: Vec<(&str, Expression)>) -> Expression {
+    literal(Literal::Struct {
+        type_annotation: type_(name),
+        field_initializers: fields
+            .into_iter()
+            .map(|(identifier, initializer)| FieldInitializer {
+                identifier: Some(identifier.to_string()),
+                initializer,
+            })
+            .collect(),
+        spread: None,
+    })
+}
+
+fn literal(literal: Literal) -> Expression {
+    Expression::Literal(literal, Span::default())
+}
+
+/// A variable or function read by its name
+pub fn identifier(name: &str) -> Expression {
+    Expression::Member(member(name))
+}
+
+/// `object.name`
+pub fn field(object: Expression, name: &str) -> Expression {
+    Expression::Member(Member::MemberAccess {
+        object: Box::new(object),
+        member: Box::new(member(name)),
+        symbol: name.to_string(),
+        generics: None,
+        optional: false,
+    })
+}
+
+fn member(name: &str) -> Member {
+    Member::Identifier {
+        symbol: name.to_string(),
+        generics: None,
+        span: Span::default(),
+    }
+}
+
+pub fn unary(operator: UnaryOperator, expression: Expression) -> Expression {
+    Expression::Unary(Unary {
+        operator,
+        expression: Box::new(expression),
+        span: Span::default(),
+    })
+}
+
+pub fn binary(left: Expression, operator: BinaryOperator, right: Expression) -> Expression {
+    Expression::Binary(Binary {
+        left: Box::new(left),
+        operator,
+        right: Box::new(right),
+        span: Span::default(),
+    })
+}
+
+/// `callee(arguments..)`, which calls the callee with the first argument and then each result
+/// with the next one
+pub fn call(callee: Expression, arguments: Vec<Expression>) -> Expression {
+    let mut arguments = arguments.into_iter();
+
+    let mut call = Expression::Call(Call {
+        callee: Box::new(callee),
+        argument: arguments.next().map(Box::new),
+        span: Span::default(),
+    });
+
+    for argument in arguments {
+        call = Expression::Call(Call {
+            callee: Box::new(call),
+            argument: Some(Box::new(argument)),
+            span: Span::default(),
+        });
+    }
+
+    call
+}
+
+/// `let name = initializer`, with the type of the initializer
+pub fn let_decl(name: &str, initializer: Expression) -> Expression {
+    Expression::VariableDeclaration(VariableDeclaration {
+        mutable: false,
+        type_annotation: None,
+        pattern: Pattern::Variable(name.to_string()),
+        pattern_span: Span::default(),
+        initializer: Some(Box::new(initializer)),
+    })
+}
+
+pub fn block(statements: Vec<Statement>) -> Expression {
+    Expression::Block(statements)
+}
+
+/// `fun name(parameters..): return_type => body`
+pub fn function_decl(
+    name: &str,
+    parameters: Vec<(&str, TypeAnnotation)>,
+    return_type: TypeAnnotation,
+    body: Expression,
+) -> Statement {
+    let parameters = parameters
+        .into_iter()
+        .map(|(identifier, type_annotation)| Parameter {
+            identifier: identifier.to_string(),
+            type_annotation,
+            mutable: false,
+            span: Span::default(),
+        })
+        .collect();
+
+    unwrap_parameters(
+        None,
+        TypeIdentifier::Type(name.to_string()),
+        Span::default(),
+        parameters,
+        Some(return_type),
+        None,
+        body,
+    )
+    .expect("Unwrapping parameters into closures doesn't fail")
+}
+
+/// `struct Name { field: Type, .. }`
+pub fn struct_decl(name: &str, fields: Vec<(&str, TypeAnnotation)>) -> Statement {
+    Statement::StructDeclaration(StructDeclaration {
+        doc: None,
+        attributes: vec![],
+        access_modifier: None,
+        type_identifier: TypeIdentifier::Type(name.to_string()),
+        span: Span::default(),
+        where_clause: None,
+        fields: fields
+            .into_iter()
+            .map(|(identifier, type_annotation)| StructField {
+                access_modifier: None,
+                mutable: false,
+                identifier: identifier.to_string(),
+                span: Span::default(),
+                type_annotation,
+                default: None,
+            })
+            .collect(),
+    })
+}
@@ -0,0 +1,135 @@
+use std::{cmp::Reverse, collections::HashMap};
+
+use crate::{
+    lexer::token::{Token, TokenKind},
+    span::Span,
+};
+
+/// A comment which isn't a doc comment, kept so the formatter can write it back
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comment {
+    pub kind: CommentKind,
+    /// The comment as it is written, including the `//` or `/-` and `-/`
+    pub text: String,
+    /// Whether the comment is on a line after the statement it is attached to, instead of at the
+    /// end of its last line. Only trailing comments are ever written after their statement.
+    pub own_line: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommentKind {
+    /// A `//` comment, which runs to the end of the line
+    Line,
+    /// A `/- -/` comment
+    Block,
+}
+
+/// The comments of a program, attached to the statements around them.
+///
+/// Statements are numbered in the order they start in, counting both those of the program and
+/// those of every block, so a statement comes before the ones nested in it. This is the order the
+/// formatter writes them in.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Comments {
+    /// The comments on the lines in front of each statement
+    pub leading: HashMap<usize, Vec<Comment>>,
+    /// The comments after each statement which aren't in front of another one, either on its last
+    /// line or at the end of the block it is in
+    pub trailing: HashMap<usize, Vec<Comment>>,
+    /// The comments of a program without statements to attach them to
+    pub dangling: Vec<Comment>,
+}
+
+/// Where each comment in `tokens` is written
+pub(crate) fn comment_spans(tokens: &[Token]) -> Vec<(CommentKind, Span)> {
+    let mut offset = 0;
+    let mut spans = vec![];
+
+    for token in tokens {
+        let span = Span::new(offset, offset + token.length as usize);
+        offset = span.end;
+
+        match token.kind {
+            TokenKind::LineComment => spans.push((CommentKind::Line, span)),
+            TokenKind::BlockComment => spans.push((CommentKind::Block, span)),
+            _ => {}
+        }
+    }
+
+    spans
+}
+
+/// Attach each comment to a statement written in the same block as it.
+///
+/// A comment on the last line of a statement trails it. Any other comment leads the next
+/// statement, or trails the last one when nothing comes after it in the block. A comment which
+/// isn't between statements is an error, since it has no statement to be written back with.
+pub(crate) fn attach(
+    source: &str,
+    comment_spans: Vec<(CommentKind, Span)>,
+    statements: &[Span],
+    blocks: &[Span],
+) -> Result<Comments, String> {
+    let mut comments = Comments::default();
+
+    for (kind, span) in comment_spans {
+        let mut comment = Comment {
+            kind,
+            text: source[span.start..span.end].to_string(),
+            own_line: false,
+        };
+
+        // The innermost statement the comment is written inside of, such as a function whose body
+        // it is in
+        let parent = statements
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| s.start < span.start && span.end <= s.end)
+            .max_by_key(|(_, s)| s.start);
+
+        let block = blocks
+            .iter()
+            .filter(|b| b.start <= span.start && span.end <= b.end)
+            .max_by_key(|b| b.start);
+
+        if let Some((_, p)) = parent {
+            if block.is_none_or(|b| b.start <= p.start) {
+                return Err(format!(
+                    "Comment at {} is inside of a statement, comments can only be kept between statements",
+                    span
+                ));
+            }
+        }
+
+        let inside = |s: &Span| parent.is_none_or(|(_, p)| p.start < s.start && s.end <= p.end);
+
+        let previous = statements
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| inside(s) && s.end <= span.start)
+            .max_by_key(|(_, s)| (s.end, Reverse(s.start)));
+
+        let next = statements
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| inside(s) && span.end <= s.start)
+            .min_by_key(|(_, s)| (s.start, Reverse(s.end)));
+
+        match (previous, next) {
+            (Some((id, s)), _) if !source[s.end..span.start].contains('\n') => {
+                comments.trailing.entry(id).or_default().push(comment);
+            }
+            (_, Some((id, _))) => comments.leading.entry(id).or_default().push(comment),
+            (_, None) => {
+                comment.own_line = true;
+
+                match previous.or(parent) {
+                    Some((id, _)) => comments.trailing.entry(id).or_default().push(comment),
+                    None => comments.dangling.push(comment),
+                }
+            }
+        }
+    }
+
+    Ok(comments)
+}
@@ -1,10 +1,34 @@
-use crate::lexer::token::{Token, TokenKind};
+use crate::{
+    lexer::token::{Keyword, Token, TokenKind},
+    span::Span,
+};
+
+use super::{ParseError, Statement, DEFAULT_MAX_DEPTH};
 
 #[derive(Debug, Clone)]
 pub struct Cursor {
     tokens: Vec<Token>,
     prev: Token,
+    prev_span: Span,
+    /// The byte offset of the next token, whether it is skipped or not
+    offset: usize,
     verbose: bool,
+    /// How many expressions the one being parsed is nested in
+    depth: usize,
+    max_depth: usize,
+    /// How many braces the consumed tokens leave open
+    braces: usize,
+    /// The errors of the statements which were recovered from, if parsing recovers from them
+    errors: Option<Vec<ParseError>>,
+    /// Where each statement of a program or block is written, in the order they start
+    statement_spans: Vec<Span>,
+    /// Where the statements of each block are written, between its braces
+    block_spans: Vec<Span>,
+    /// The offset of every newline in the source, if a newline can end a statement in place of a
+    /// `;`
+    newlines: Option<Vec<usize>>,
+    /// The newline which was last consumed in place of a `;`
+    inferred_semicolon: Option<usize>,
 }
 
 const END_OF_FILE_TOKEN: Token = Token {
@@ -19,14 +43,254 @@ impl Cursor {
         Cursor {
             tokens,
             prev: END_OF_FILE_TOKEN,
+            prev_span: Span::default(),
+            offset: 0,
             verbose,
+            depth: 0,
+            max_depth: DEFAULT_MAX_DEPTH,
+            braces: 0,
+            errors: None,
+            statement_spans: vec![],
+            block_spans: vec![],
+            newlines: None,
+            inferred_semicolon: None,
+        }
+    }
+
+    pub fn with_max_depth(self, max_depth: usize) -> Cursor {
+        Cursor { max_depth, ..self }
+    }
+
+    /// Recover from statements which fail to parse instead of failing, see `recover_statement`
+    pub fn with_recovery(self) -> Cursor {
+        Cursor {
+            errors: Some(vec![]),
+            ..self
+        }
+    }
+
+    /// Let a newline end a statement where a `;` could, see `bump_semicolon`. The newlines are
+    /// found in `source`, which the tokens have to be read from.
+    pub fn with_semicolon_inference(self, source: &str) -> Cursor {
+        Cursor {
+            newlines: Some(source.match_indices('\n').map(|(i, _)| i).collect()),
+            ..self
+        }
+    }
+
+    /// Consume the `;` ending a statement, or a newline in its place when inferring semicolons.
+    ///
+    /// A newline in front of a `}` doesn't stand for a `;`, so the last expression of a block is
+    /// still its value. A line starting with a binary operator also continues the expression on the
+    /// line before it, as only the points where a statement could end with a `;` check for one.
+    pub(crate) fn bump_semicolon(&mut self) -> Result<bool, String> {
+        if self.first().kind == TokenKind::Semicolon {
+            self.bump()?; // Consume the ;
+            return Ok(true);
+        }
+
+        match self.following_newline() {
+            Some(newline) if self.first().kind != TokenKind::CloseBrace => {
+                self.inferred_semicolon = Some(newline);
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    /// Consume the `;` ending a statement like `bump_semicolon`, failing without one
+    pub(crate) fn expect_semicolon(&mut self) -> Result<(), String> {
+        match self.bump_semicolon()? {
+            true => Ok(()),
+            false => self.expect(TokenKind::Semicolon).map(|_| ()),
+        }
+    }
+
+    /// Whether a line ends right after the last consumed token, which could stand for a `;`
+    pub(crate) fn newline_follows(&self) -> bool {
+        self.following_newline().is_some()
+    }
+
+    /// The first newline between the last consumed token and the next one, unless it was already
+    /// consumed in place of a `;` or the last token was one
+    fn following_newline(&self) -> Option<usize> {
+        let newlines = self.newlines.as_ref()?;
+
+        if self.prev.kind == TokenKind::Semicolon {
+            return None;
+        }
+
+        let index = newlines.partition_point(|newline| *newline < self.prev_span.end);
+
+        newlines
+            .get(index)
+            .copied()
+            .filter(|newline| *newline < self.first_span().start)
+            .filter(|newline| self.inferred_semicolon != Some(*newline))
+    }
+
+    /// The errors of the statements which were recovered from, in the order they were found
+    pub(crate) fn take_errors(&mut self) -> Vec<ParseError> {
+        self.errors.as_mut().map(std::mem::take).unwrap_or_default()
+    }
+
+    /// Where each statement parsed with `recover_statement` is written, indexed by the order they
+    /// start in. A statement comes before the ones nested in it.
+    pub(crate) fn take_statement_spans(&mut self) -> Vec<Span> {
+        std::mem::take(&mut self.statement_spans)
+    }
+
+    pub(crate) fn add_block_span(&mut self, span: Span) {
+        self.block_spans.push(span);
+    }
+
+    /// Where the statements of each block parsed with `parse_block_statements` are written
+    pub(crate) fn take_block_spans(&mut self) -> Vec<Span> {
+        std::mem::take(&mut self.block_spans)
+    }
+
+    /// Parse a statement with `parse`. When recovering and it fails, its error is kept, the rest of
+    /// the statement is skipped and an error placeholder is returned in its place.
+    pub(crate) fn recover_statement(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> Result<Statement, String>,
+    ) -> Result<Statement, String> {
+        let start = self.first_span().start;
+        let braces = self.braces;
+
+        // Nothing is left to skip to at the end of the file, so the error is left to the statement
+        // around this one
+        let recovering = self.errors.is_some() && !self.is_end_of_file();
+
+        let id = self.statement_spans.len();
+        self.statement_spans.push(Span::new(start, start));
+
+        let result = match parse(self) {
+            Err(message) if recovering => {
+                let error = self.synchronize(message, start, braces);
+
+                if let Some(errors) = &mut self.errors {
+                    errors.push(error);
+                }
+
+                Ok(Statement::Error(self.span_from(start)))
+            }
+            result => result,
+        };
+
+        self.statement_spans[id] = self.span_from(start);
+        result
+    }
+
+    /// Skip the rest of the statement starting at `start` which failed to parse with `message`,
+    /// and which `braces` braces were open around. Tokens are skipped up to and including the `;`
+    /// ending it, or up to the `}` closing the block it is in or the next declaration.
+    pub(crate) fn synchronize(
+        &mut self,
+        message: String,
+        start: usize,
+        braces: usize,
+    ) -> ParseError {
+        let span = self.first_span();
+
+        // A statement which fails at its first token would otherwise be tried again forever
+        if span.start == start {
+            let skipped = self.bump().map(|token| token.kind);
+
+            if skipped == Ok(TokenKind::Semicolon) && self.braces <= braces {
+                return ParseError { message, span };
+            }
+        }
+
+        while !self.is_end_of_file() {
+            let kind = self.first().kind;
+
+            if self.braces <= braces {
+                if kind == TokenKind::Semicolon {
+                    let _ = self.bump();
+                    break;
+                }
+
+                if kind == TokenKind::CloseBrace || starts_declaration(&kind) {
+                    break;
+                }
+            }
+
+            let _ = self.bump();
+        }
+
+        ParseError { message, span }
+    }
+
+    /// Parse a nested expression with `parse`, failing instead when expressions are nested deeper
+    /// than the limit so the recursion can't overflow the stack
+    pub(crate) fn nested<T>(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> Result<T, String>,
+    ) -> Result<T, String> {
+        if self.depth >= self.max_depth {
+            return Err(format!(
+                "Expression nesting is too deep, the limit is {} levels",
+                self.max_depth
+            ));
         }
+
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+        result
     }
 
     pub fn prev(&self) -> Token {
         self.prev.clone()
     }
 
+    /// Where the last consumed token was written
+    pub(crate) fn prev_span(&self) -> Span {
+        self.prev_span
+    }
+
+    /// The span from `start` to the end of the last consumed token
+    pub(crate) fn span_from(&self, start: usize) -> Span {
+        Span::new(start, self.prev_span.end.max(start))
+    }
+
+    /// Where the next token which isn't skipped is written
+    pub(crate) fn first_span(&self) -> Span {
+        let mut start = self.offset;
+
+        for token in self.tokens.iter().rev() {
+            if !is_skipped(&token.kind) {
+                return Span::new(start, start + token.length as usize);
+            }
+
+            start += token.length as usize;
+        }
+
+        Span::new(start, start)
+    }
+
+    fn pop(&mut self) -> Option<Token> {
+        let token = self.tokens.pop()?;
+        self.offset += token.length as usize;
+        Some(token)
+    }
+
+    fn pop_consumed(&mut self) -> Result<Token, String> {
+        let start = self.offset;
+        let token = self.pop().ok_or("Unexpected end of file".to_string())?;
+
+        match token.kind {
+            TokenKind::OpenBrace => self.braces += 1,
+            TokenKind::CloseBrace => self.braces = self.braces.saturating_sub(1),
+            _ => {}
+        }
+
+        self.prev_span = Span::new(start, self.offset);
+        self.prev = token.clone();
+        Ok(token)
+    }
+
     pub(crate) fn first(&self) -> Token {
         let mut clone = self.tokens.clone();
 
@@ -35,7 +299,10 @@ impl Cursor {
 
             if matches!(
                 current.kind,
-                TokenKind::WhiteSpace | TokenKind::LineComment | TokenKind::BlockComment
+                TokenKind::WhiteSpace
+                    | TokenKind::LineComment
+                    | TokenKind::DocComment(_)
+                    | TokenKind::BlockComment
             ) {
                 continue;
             }
@@ -57,7 +324,10 @@ impl Cursor {
 
                 if matches!(
                     current.kind,
-                    TokenKind::WhiteSpace | TokenKind::LineComment | TokenKind::BlockComment
+                    TokenKind::WhiteSpace
+                        | TokenKind::LineComment
+                        | TokenKind::DocComment(_)
+                        | TokenKind::BlockComment
                 ) {
                     continue;
                 }
@@ -82,7 +352,10 @@ impl Cursor {
 
                 if matches!(
                     current.kind,
-                    TokenKind::WhiteSpace | TokenKind::LineComment | TokenKind::BlockComment
+                    TokenKind::WhiteSpace
+                        | TokenKind::LineComment
+                        | TokenKind::DocComment(_)
+                        | TokenKind::BlockComment
                 ) {
                     continue;
                 }
@@ -98,6 +371,25 @@ impl Cursor {
         unreachable!()
     }
 
+    /// The doc comment lines in front of the next token, joined by newlines
+    pub(crate) fn doc_comment(&self) -> Option<String> {
+        let mut lines = vec![];
+
+        for token in self.tokens.iter().rev() {
+            match &token.kind {
+                TokenKind::DocComment(line) => lines.push(line.clone()),
+                TokenKind::WhiteSpace | TokenKind::LineComment | TokenKind::BlockComment => {}
+                _ => break,
+            }
+        }
+
+        if lines.is_empty() {
+            None
+        } else {
+            Some(lines.join("\n"))
+        }
+    }
+
     pub(crate) fn is_end_of_file(&self) -> bool {
         self.tokens.is_empty() || self.first().kind == crate::lexer::token::TokenKind::EndOfFile
     }
@@ -106,13 +398,16 @@ impl Cursor {
         loop {
             if matches!(
                 self.first_no_skip().kind,
-                TokenKind::WhiteSpace | TokenKind::LineComment | TokenKind::BlockComment
+                TokenKind::WhiteSpace
+                    | TokenKind::LineComment
+                    | TokenKind::DocComment(_)
+                    | TokenKind::BlockComment
             ) {
                 if self.verbose {
                     println!("Skipping: {:?}", self.first_no_skip());
                 }
 
-                self.tokens.pop();
+                self.pop();
                 continue;
             }
 
@@ -120,14 +415,7 @@ impl Cursor {
                 println!("Bumping: {:?}", self.first_no_skip());
             }
 
-            let token = self
-                .tokens
-                .pop()
-                .ok_or("Unexpected end of file".to_string())?;
-
-            self.prev = token.clone();
-
-            return Ok(token);
+            return self.pop_consumed();
         }
     }
 
@@ -135,13 +423,16 @@ impl Cursor {
         loop {
             if matches!(
                 self.first_no_skip().kind,
-                TokenKind::WhiteSpace | TokenKind::LineComment | TokenKind::BlockComment
+                TokenKind::WhiteSpace
+                    | TokenKind::LineComment
+                    | TokenKind::DocComment(_)
+                    | TokenKind::BlockComment
             ) {
                 if self.verbose {
                     println!("Skipping: {:?}", self.first_no_skip());
                 }
 
-                self.tokens.pop();
+                self.pop();
                 continue;
             }
 
@@ -153,13 +444,7 @@ impl Cursor {
                 println!("Bumping: {:?}", self.first_no_skip());
             }
 
-            let token = self
-                .tokens
-                .pop()
-                .ok_or("Unexpected end of file".to_string())?;
-            self.prev = token.clone();
-
-            return Ok(Some(token));
+            return self.pop_consumed().map(Some);
         }
     }
 
@@ -167,13 +452,16 @@ impl Cursor {
         loop {
             if matches!(
                 self.first_no_skip().kind,
-                TokenKind::WhiteSpace | TokenKind::LineComment | TokenKind::BlockComment
+                TokenKind::WhiteSpace
+                    | TokenKind::LineComment
+                    | TokenKind::DocComment(_)
+                    | TokenKind::BlockComment
             ) {
                 if self.verbose {
                     println!("Skipping: {:?}", self.first_no_skip());
                 }
 
-                self.tokens.pop();
+                self.pop();
                 continue;
             }
 
@@ -193,3 +481,32 @@ impl Cursor {
         }
     }
 }
+
+fn is_skipped(kind: &TokenKind) -> bool {
+    matches!(
+        kind,
+        TokenKind::WhiteSpace
+            | TokenKind::LineComment
+            | TokenKind::DocComment(_)
+            | TokenKind::BlockComment
+    )
+}
+
+fn starts_declaration(kind: &TokenKind) -> bool {
+    kind.is_access_modifier().is_some()
+        || matches!(
+            kind,
+            TokenKind::At
+                | TokenKind::Keyword(
+                    Keyword::Use
+                        | Keyword::Fun
+                        | Keyword::Struct
+                        | Keyword::Enum
+                        | Keyword::Union
+                        | Keyword::Imp
+                        | Keyword::Proto
+                        | Keyword::Type
+                        | Keyword::Newtype
+                )
+        )
+}
@@ -2,24 +2,26 @@ use std::collections::HashMap;
 
 use crate::{
     lexer::token::{self, IdentifierType, Keyword, TokenKind},
+    span::Span,
     type_checker::decision_tree::{Constructor, FieldPattern, Pattern},
     types::{parse_generics_in_type_name, parse_optional_type_annotation, TypeAnnotation},
 };
 
 use super::{
-    cursor::Cursor, statements::parse_statement, Assignment, Binary, BinaryOperator, Call, Closure,
-    ClosureParameter, EnumMemberFieldInitializers, Expression, FieldInitializer, For, If, Literal,
-    Match, MatchArm, Member, Statement, Unary, UnaryOperator, VariableDeclaration, While,
+    cursor::Cursor, statements::parse_statement, Assignment, Binary, BinaryOperator, Call, Cast,
+    Closure, ClosureParameter, Coalesce, EnumMemberFieldInitializers, Expression, FieldInitializer,
+    For, If, Index, Literal, Match, MatchArm, Member, Statement, Unary, UnaryOperator,
+    VariableDeclaration, While,
 };
 
 use crate::types::parse_type_annotation;
 
 pub fn parse_expression(cursor: &mut Cursor) -> Result<Expression, String> {
     #[cfg(feature = "interpreter")]
-    let expression = parse_print(cursor);
+    let expression = cursor.nested(parse_print);
 
     #[cfg(not(feature = "interpreter"))]
-    let expression = parse_break(cursor);
+    let expression = cursor.nested(parse_break);
 
     expression
 }
@@ -78,8 +80,8 @@ fn parse_break(cursor: &mut Cursor) -> Result<Expression, String> {
 
     cursor.bump()?; // Consume the break
 
-    let expression = if cursor.first().kind == TokenKind::Semicolon {
-        cursor.bump()?;
+    // A newline in front of a } ends the expression as well, but is left for the block to end with
+    let expression = if cursor.bump_semicolon()? || cursor.newline_follows() {
         None
     } else {
         Some(parse_expression(cursor)?)
@@ -104,8 +106,7 @@ fn parse_return(cursor: &mut Cursor) -> Result<Expression, String> {
 
     cursor.bump()?; // Consume the return
 
-    let expression = if cursor.first().kind == TokenKind::Semicolon {
-        cursor.bump()?; // Consume the ;
+    let expression = if cursor.bump_semicolon()? || cursor.newline_follows() {
         None
     } else {
         Some(parse_expression(cursor)?)
@@ -115,6 +116,7 @@ fn parse_return(cursor: &mut Cursor) -> Result<Expression, String> {
 }
 
 fn parse_trailing_closure(cursor: &mut Cursor) -> Result<Expression, String> {
+    let start = cursor.first_span().start;
     let mut expression = parse_loop(cursor)?;
 
     while cursor.first().kind == TokenKind::Arrow {
@@ -141,6 +143,7 @@ fn parse_trailing_closure(cursor: &mut Cursor) -> Result<Expression, String> {
 
         let Some(params) = params else {
             expression = Expression::Call(Call {
+                span: cursor.span_from(start),
                 callee: Box::new(expression),
                 argument: Some(Box::new(Expression::Closure(Closure {
                     param: None,
@@ -152,6 +155,7 @@ fn parse_trailing_closure(cursor: &mut Cursor) -> Result<Expression, String> {
         };
 
         expression = Expression::Call(Call {
+            span: cursor.span_from(start),
             callee: Box::new(expression),
             argument: Some(Box::new(unwrap_arguments(
                 params,
@@ -223,6 +227,7 @@ pub fn parse_for(cursor: &mut Cursor) -> Result<Expression, String> {
     };
 
     cursor.bump()?; // Consume the identifier
+    let identifier_span = cursor.prev_span();
 
     cursor.expect(TokenKind::Keyword(Keyword::In))?;
 
@@ -233,6 +238,7 @@ pub fn parse_for(cursor: &mut Cursor) -> Result<Expression, String> {
     if cursor.first().kind != TokenKind::Keyword(Keyword::Else) {
         return Ok(Expression::For(For {
             identifier,
+            identifier_span,
             iterable: Box::new(iterable),
             body: Box::new(body),
             else_body: None,
@@ -246,6 +252,7 @@ pub fn parse_for(cursor: &mut Cursor) -> Result<Expression, String> {
 
     Ok(Expression::For(For {
         identifier,
+        identifier_span,
         iterable: Box::new(iterable),
         body: Box::new(body),
         else_body: Some(Box::new(else_block)),
@@ -257,9 +264,48 @@ pub fn parse_block(cursor: &mut Cursor) -> Result<Expression, String> {
         return parse_type_literal(cursor);
     }
 
+    // A name followed by a colon starts a record rather than a block, so a block which starts with
+    // a propagated call like `x:f` has to wrap it in parentheses
+    if let (TokenKind::Identifier(_), TokenKind::Colon) =
+        (cursor.second().kind, cursor.third().kind)
+    {
+        return parse_record(cursor);
+    }
+
     parse_block_statements(cursor).map(Expression::Block)
 }
 
+fn parse_record(cursor: &mut Cursor) -> Result<Expression, String> {
+    cursor.bump()?; // Consume the {
+
+    let mut fields: Vec<FieldInitializer> = vec![];
+
+    while cursor.first().kind != TokenKind::CloseBrace {
+        let field = parse_field_initializer(cursor)?;
+
+        let Some(identifier) = &field.identifier else {
+            return Err("Expected field name in record".to_string());
+        };
+
+        if fields
+            .iter()
+            .any(|f| f.identifier.as_ref() == Some(identifier))
+        {
+            return Err(format!("Duplicate field {} in record", identifier));
+        }
+
+        fields.push(field);
+
+        if !list_continues(cursor, TokenKind::CloseBrace)? {
+            break;
+        }
+    }
+
+    cursor.bump()?; // Consume the }
+
+    Ok(Expression::Record(fields))
+}
+
 pub fn parse_block_statements(cursor: &mut Cursor) -> Result<Vec<Statement>, String> {
     if cursor.first().kind != TokenKind::OpenBrace {
         return Err(format!("Expected {{ but found {:?}", cursor.first().kind));
@@ -267,12 +313,15 @@ pub fn parse_block_statements(cursor: &mut Cursor) -> Result<Vec<Statement>, Str
 
     cursor.bump()?; // Consume the {
 
+    let start = cursor.prev_span().end;
     let mut statements = vec![];
 
     while cursor.first().kind != TokenKind::CloseBrace {
-        statements.push(parse_statement(cursor)?);
+        statements.push(cursor.recover_statement(parse_statement)?);
     }
 
+    let end = cursor.first_span().start;
+    cursor.add_block_span(Span::new(start, end));
     cursor.bump()?; // Consume the }
 
     Ok(statements)
@@ -295,60 +344,79 @@ fn parse_type_literal(cursor: &mut Cursor) -> Result<Expression, String> {
         (_, TokenKind::Less) => {
             return parse_range(cursor);
         }
+        // A member like `Result::Ok` which starts upper case is an enum member, even though it
+        // contains lower case letters
         (TokenKind::DoubleColon, TokenKind::Identifier(name))
-            if name.is_function_identifier_name() =>
+            if name.is_function_identifier_name() && !name.starts_with(char::is_uppercase) =>
         {
-            return parse_member_access(cursor);
+            return parse_range(cursor);
         }
         _ => {}
     }
 
+    let start = cursor.first_span().start;
     let type_annotation = parse_type_annotation(cursor, false)?;
 
     if type_annotation.has_double_colon() {
-        parse_enum_literal(cursor, type_annotation)
+        parse_enum_literal(cursor, type_annotation, start)
     } else {
-        parse_struct_literal(cursor, type_annotation)
+        parse_struct_literal(cursor, type_annotation, start)
     }
 }
 
 fn parse_struct_literal(
     cursor: &mut Cursor,
     type_annotation: TypeAnnotation,
+    start: usize,
 ) -> Result<Expression, String> {
     if cursor.first().kind != TokenKind::OpenBrace {
-        return Ok(Expression::Literal(Literal::Struct {
-            type_annotation,
-            field_initializers: vec![],
-        }));
+        return Ok(Expression::Literal(
+            Literal::Struct {
+                type_annotation,
+                field_initializers: vec![],
+                spread: None,
+            },
+            cursor.span_from(start),
+        ));
     }
 
     cursor.bump()?; // Consume the {
 
     let mut field_initializers = vec![];
-    let mut has_comma = true;
+    let mut spread = None;
 
     while cursor.first().kind != TokenKind::CloseBrace {
-        if !has_comma {
-            return Err(format!("Expected , but found {:?}", cursor.first().kind));
+        if cursor.first().kind == TokenKind::DoubleDot {
+            cursor.bump()?; // Consume the ..
+            spread = Some(Box::new(parse_expression(cursor)?));
+
+            if list_continues(cursor, TokenKind::CloseBrace)? {
+                return Err(format!(
+                    "Expected }} after spread but found {:?}",
+                    cursor.first().kind
+                ));
+            }
+
+            break;
         }
 
-        has_comma = true;
         field_initializers.push(parse_field_initializer(cursor)?);
 
-        if cursor.first().kind == TokenKind::Comma {
-            cursor.bump()?; // Consume the ,
-        } else {
-            has_comma = false;
+        if !list_continues(cursor, TokenKind::CloseBrace)? {
+            break;
         }
     }
 
     cursor.bump()?; // Consume the }
 
-    Ok(Expression::Literal(Literal::Struct {
-        type_annotation,
-        field_initializers,
-    }))
+    Ok(Expression::Literal(
+        Literal::Struct {
+            type_annotation,
+            field_initializers,
+            spread,
+        },
+        cursor.span_from(start),
+    ))
 }
 
 fn parse_field_initializer(cursor: &mut Cursor) -> Result<FieldInitializer, String> {
@@ -380,6 +448,7 @@ fn parse_field_initializer(cursor: &mut Cursor) -> Result<FieldInitializer, Stri
 fn parse_enum_literal(
     cursor: &mut Cursor,
     type_annotation: TypeAnnotation,
+    start: usize,
 ) -> Result<Expression, String> {
     let field_initializers = {
         if cursor.first().kind == TokenKind::OpenBrace {
@@ -392,16 +461,19 @@ fn parse_enum_literal(
         }
     };
 
-    Ok(Expression::Literal(Literal::Enum {
-        type_annotation: type_annotation.clone(),
-        member: type_annotation
-            .to_string()
-            .split("::")
-            .last()
-            .unwrap()
-            .to_string(),
-        field_initializers,
-    }))
+    Ok(Expression::Literal(
+        Literal::Enum {
+            type_annotation: type_annotation.clone(),
+            member: type_annotation
+                .to_string()
+                .split("::")
+                .last()
+                .unwrap()
+                .to_string(),
+            field_initializers,
+        },
+        cursor.span_from(start),
+    ))
 }
 
 fn parse_named_enum_member_field_initializers(
@@ -425,18 +497,18 @@ fn parse_named_enum_member_field_initializers(
         cursor.bump()?; // Consume the :
 
         let initializer = parse_expression(cursor)?;
+        field_initializers.insert(identifier, initializer);
 
-        if cursor.first().kind == TokenKind::Comma {
-            cursor.bump()?; // Consume the ,
+        if !list_continues(cursor, TokenKind::CloseBrace)? {
+            break;
         }
-
-        field_initializers.insert(identifier, initializer);
     }
 
     Ok(EnumMemberFieldInitializers::Named(field_initializers))
 }
 
 fn parse_range(cursor: &mut Cursor) -> Result<Expression, String> {
+    let start = cursor.first_span().start;
     let mut expression = parse_assignment(cursor)?;
 
     while cursor.first().kind == TokenKind::DoubleDot {
@@ -451,6 +523,7 @@ fn parse_range(cursor: &mut Cursor) -> Result<Expression, String> {
         let right = parse_assignment(cursor)?;
 
         expression = Expression::Binary(Binary {
+            span: cursor.span_from(start),
             left: Box::new(expression),
             right: Box::new(right),
             operator: match (&operator, inclusive) {
@@ -477,6 +550,7 @@ fn parse_assignment(cursor: &mut Cursor) -> Result<Expression, String> {
 
         expression = Expression::Assignment(Assignment {
             member: Box::new(member),
+            operator: None,
             initializer: Box::new(initializer),
         });
     }
@@ -485,7 +559,7 @@ fn parse_assignment(cursor: &mut Cursor) -> Result<Expression, String> {
 }
 
 fn parse_compound_assignment(cursor: &mut Cursor) -> Result<Expression, String> {
-    let mut expression = parse_closure(cursor)?;
+    let mut expression = parse_increment(cursor)?;
 
     while matches!(
         cursor.first().kind,
@@ -501,36 +575,67 @@ fn parse_compound_assignment(cursor: &mut Cursor) -> Result<Expression, String>
         let operator = cursor.bump()?.kind; // Consume the +=, -=, *=, /=, %=, &=, |=, ^=
         let initializer = parse_expression(cursor)?;
 
-        let Expression::Member(ref member) = expression else {
+        let Expression::Member(member) = expression else {
             return Err(format!("Expected member but found {:?}", expression));
         };
 
         expression = Expression::Assignment(Assignment {
-            member: Box::new(member.clone()),
-            initializer: Box::new(Expression::Binary(Binary {
-                left: Box::new(expression),
-                right: Box::new(initializer),
-                operator: match operator {
-                    TokenKind::PlusEqual => BinaryOperator::Add,
-                    TokenKind::MinusEqual => BinaryOperator::Subtract,
-                    TokenKind::StarEqual => BinaryOperator::Multiply,
-                    TokenKind::SlashEqual => BinaryOperator::Divide,
-                    TokenKind::PercentEqual => BinaryOperator::Modulo,
-                    TokenKind::AmpersandEqual => BinaryOperator::BitwiseAnd,
-                    TokenKind::PipeEqual => BinaryOperator::BitwiseOr,
-                    TokenKind::CaretEqual => BinaryOperator::BitwiseXor,
-                    _ => unreachable!(
-                        "Expected +=, -=, *=, /=, %=, &=, |=, or ^=, but found {:?}",
-                        operator
-                    ),
-                },
-            })),
+            member: Box::new(member),
+            operator: Some(match operator {
+                TokenKind::PlusEqual => BinaryOperator::Add,
+                TokenKind::MinusEqual => BinaryOperator::Subtract,
+                TokenKind::StarEqual => BinaryOperator::Multiply,
+                TokenKind::SlashEqual => BinaryOperator::Divide,
+                TokenKind::PercentEqual => BinaryOperator::Modulo,
+                TokenKind::AmpersandEqual => BinaryOperator::BitwiseAnd,
+                TokenKind::PipeEqual => BinaryOperator::BitwiseOr,
+                TokenKind::CaretEqual => BinaryOperator::BitwiseXor,
+                _ => unreachable!(
+                    "Expected +=, -=, *=, /=, %=, &=, |=, or ^=, but found {:?}",
+                    operator
+                ),
+            }),
+            initializer: Box::new(initializer),
         });
     }
 
     Ok(expression)
 }
 
+/// Parse `x++` and `x--`, which are the same as `x += 1` and `x -= 1`
+fn parse_increment(cursor: &mut Cursor) -> Result<Expression, String> {
+    let expression = parse_closure(cursor)?;
+
+    let operator = match cursor.first().kind {
+        TokenKind::DoublePlus => BinaryOperator::Add,
+        TokenKind::DoubleMinus => BinaryOperator::Subtract,
+        _ => return Ok(expression),
+    };
+
+    let token = cursor.bump()?.kind; // Consume the ++ or --
+
+    let Expression::Member(member) = expression else {
+        return Err(format!(
+            "Expected a variable or field before {} but found {:?}",
+            increment_symbol(&token),
+            expression
+        ));
+    };
+
+    Ok(Expression::Assignment(Assignment {
+        member: Box::new(member),
+        operator: Some(operator),
+        initializer: Box::new(Expression::Literal(Literal::Int(1), Span::default())),
+    }))
+}
+
+fn increment_symbol(token: &TokenKind) -> &'static str {
+    match token {
+        TokenKind::DoublePlus => "++",
+        _ => "--",
+    }
+}
+
 fn parse_closure(cursor: &mut Cursor) -> Result<Expression, String> {
     if cursor.first().kind != TokenKind::Pipe {
         return parse_match(cursor);
@@ -568,12 +673,14 @@ fn parse_comma_separated_closure_params(
         };
 
         cursor.bump()?; // Consume the identifier
+        let span = cursor.prev_span();
 
         let type_annotation = parse_optional_type_annotation(cursor, false)?;
 
         params.push(ClosureParameter {
             identifier,
             type_annotation,
+            span,
         });
 
         if cursor.first().kind == TokenKind::Comma {
@@ -639,7 +746,8 @@ fn unwrap_arguments_recurse(
 }
 
 fn parse_match(cursor: &mut Cursor) -> Result<Expression, String> {
-    let expression = parse_boolean_logical(cursor)?;
+    let start = cursor.first_span().start;
+    let expression = parse_coalesce(cursor)?;
 
     if cursor.first().kind != TokenKind::Keyword(Keyword::Match) {
         return Ok(expression);
@@ -692,10 +800,32 @@ fn parse_match(cursor: &mut Cursor) -> Result<Expression, String> {
     Ok(Expression::Match(Match {
         expression: Box::new(expression),
         arms,
+        span: cursor.span_from(start),
     }))
 }
 
+fn parse_coalesce(cursor: &mut Cursor) -> Result<Expression, String> {
+    let mut expression = parse_boolean_logical(cursor)?;
+
+    while let (TokenKind::QuestionMark, TokenKind::QuestionMark) =
+        (cursor.first().kind, cursor.second().kind)
+    {
+        cursor.bump()?; // Consume the first ?
+        cursor.bump()?; // Consume the second ?
+
+        let default = parse_boolean_logical(cursor)?;
+
+        expression = Expression::Coalesce(Coalesce {
+            value: Box::new(expression),
+            default: Box::new(default),
+        });
+    }
+
+    Ok(expression)
+}
+
 fn parse_boolean_logical(cursor: &mut Cursor) -> Result<Expression, String> {
+    let start = cursor.first_span().start;
     let mut expression = parse_comparison(cursor)?;
 
     while matches!(
@@ -711,6 +841,7 @@ fn parse_boolean_logical(cursor: &mut Cursor) -> Result<Expression, String> {
         let right = parse_comparison(cursor)?;
 
         expression = Expression::Binary(Binary {
+            span: cursor.span_from(start),
             left: Box::new(expression),
             right: Box::new(right),
             operator: match operator {
@@ -725,6 +856,7 @@ fn parse_boolean_logical(cursor: &mut Cursor) -> Result<Expression, String> {
 }
 
 fn parse_comparison(cursor: &mut Cursor) -> Result<Expression, String> {
+    let start = cursor.first_span().start;
     let mut expression = parse_bitwise_logical(cursor)?;
 
     while matches!(
@@ -740,6 +872,7 @@ fn parse_comparison(cursor: &mut Cursor) -> Result<Expression, String> {
         let right = parse_additive(cursor)?;
 
         expression = Expression::Binary(Binary {
+            span: cursor.span_from(start),
             left: Box::new(expression),
             right: Box::new(right),
             operator: match operator {
@@ -758,6 +891,7 @@ fn parse_comparison(cursor: &mut Cursor) -> Result<Expression, String> {
 }
 
 fn parse_bitwise_logical(cursor: &mut Cursor) -> Result<Expression, String> {
+    let start = cursor.first_span().start;
     let mut expression = parse_additive(cursor)?;
 
     while matches!(
@@ -779,6 +913,7 @@ fn parse_bitwise_logical(cursor: &mut Cursor) -> Result<Expression, String> {
         let right = parse_boolean_logical(cursor)?;
 
         expression = Expression::Binary(Binary {
+            span: cursor.span_from(start),
             left: Box::new(expression),
             right: Box::new(right),
             operator: match operator {
@@ -796,13 +931,28 @@ fn parse_bitwise_logical(cursor: &mut Cursor) -> Result<Expression, String> {
 }
 
 fn parse_additive(cursor: &mut Cursor) -> Result<Expression, String> {
+    let start = cursor.first_span().start;
     let mut expression = parse_multiplicative(cursor)?;
 
     while matches!(cursor.first().kind, TokenKind::Plus | TokenKind::Minus) {
+        // A + or - starting a line could just as well start a new statement with a unary operator
+        if cursor.newline_follows() {
+            let symbol = match cursor.first().kind {
+                TokenKind::Plus => "+",
+                _ => "-",
+            };
+
+            return Err(format!(
+                "Line starting with {} is ambiguous, end the line before it with ; or with {} instead",
+                symbol, symbol
+            ));
+        }
+
         let operator = cursor.bump()?.kind; // Consume the + or -
         let right = parse_multiplicative(cursor)?;
 
         expression = Expression::Binary(Binary {
+            span: cursor.span_from(start),
             left: Box::new(expression),
             right: Box::new(right),
             operator: match operator {
@@ -817,16 +967,18 @@ fn parse_additive(cursor: &mut Cursor) -> Result<Expression, String> {
 }
 
 fn parse_multiplicative(cursor: &mut Cursor) -> Result<Expression, String> {
-    let mut expression = parse_variable_declaration(cursor)?;
+    let start = cursor.first_span().start;
+    let mut expression = parse_cast(cursor)?;
 
     while matches!(
         cursor.first().kind,
         TokenKind::Star | TokenKind::Slash | TokenKind::Percent
     ) {
         let operator = cursor.bump()?.kind; // Consume the *, /, or %
-        let right = parse_variable_declaration(cursor)?;
+        let right = parse_cast(cursor)?;
 
         expression = Expression::Binary(Binary {
+            span: cursor.span_from(start),
             left: Box::new(expression),
             right: Box::new(right),
             operator: match operator {
@@ -841,6 +993,24 @@ fn parse_multiplicative(cursor: &mut Cursor) -> Result<Expression, String> {
     Ok(expression)
 }
 
+fn parse_cast(cursor: &mut Cursor) -> Result<Expression, String> {
+    let start = cursor.first_span().start;
+    let mut expression = parse_variable_declaration(cursor)?;
+
+    while cursor.first().kind == TokenKind::Keyword(Keyword::As) {
+        cursor.bump()?; // Consume the as
+        let target = parse_type_annotation(cursor, false)?;
+
+        expression = Expression::Cast(Cast {
+            span: cursor.span_from(start),
+            expression: Box::new(expression),
+            target,
+        });
+    }
+
+    Ok(expression)
+}
+
 fn parse_variable_declaration(cursor: &mut Cursor) -> Result<Expression, String> {
     if cursor.first().kind != TokenKind::Keyword(Keyword::Let) {
         return parse_if(cursor);
@@ -854,7 +1024,9 @@ fn parse_variable_declaration(cursor: &mut Cursor) -> Result<Expression, String>
         cursor.bump()?; // Consume the mutable
     }
 
+    let pattern_start = cursor.first_span();
     let pattern = parse_pattern(cursor)?;
+    let pattern_span = pattern_start.to(cursor.prev_span());
 
     let type_annotation = parse_optional_type_annotation(cursor, false)?;
 
@@ -868,15 +1040,19 @@ fn parse_variable_declaration(cursor: &mut Cursor) -> Result<Expression, String>
                 mutable,
                 type_annotation,
                 pattern,
+                pattern_span,
                 initializer: Some(Box::new(initializer)),
             }))
         }
-        TokenKind::Semicolon => Ok(Expression::VariableDeclaration(VariableDeclaration {
-            mutable,
-            type_annotation,
-            pattern,
-            initializer: None,
-        })),
+        kind if kind == TokenKind::Semicolon || cursor.newline_follows() => {
+            Ok(Expression::VariableDeclaration(VariableDeclaration {
+                mutable,
+                type_annotation,
+                pattern,
+                pattern_span,
+                initializer: None,
+            }))
+        }
         _ => Err(format!(
             "Expected = or ; but found {:?}",
             cursor.first().kind
@@ -917,27 +1093,43 @@ fn parse_if(cursor: &mut Cursor) -> Result<Expression, String> {
 }
 
 fn parse_unary(cursor: &mut Cursor) -> Result<Expression, String> {
+    if matches!(
+        cursor.first().kind,
+        TokenKind::DoublePlus | TokenKind::DoubleMinus
+    ) {
+        let symbol = increment_symbol(&cursor.bump()?.kind); // Consume the ++ or --
+        let operator = if symbol == "++" { "+=" } else { "-=" };
+
+        return Err(match parse_unary(cursor)? {
+            Expression::Member(member) => format!(
+                "Prefix {} is not supported, write {} {} 1 instead",
+                symbol, member, operator
+            ),
+            _ => format!("Prefix {} is not supported", symbol),
+        });
+    }
+
     if matches!(
         cursor.first().kind,
         TokenKind::Plus | TokenKind::Minus | TokenKind::Bang | TokenKind::Tilde
     ) {
+        let start = cursor.first_span().start;
         let operator = cursor.bump()?.kind; // Consume the +, -, !, or ~
-        let right = parse_unary(cursor)?;
+        let right = cursor.nested(parse_unary)?;
+        let span = cursor.span_from(start);
 
-        if matches!(operator, TokenKind::Minus)
-            && matches!(
-                right,
-                Expression::Literal(Literal::Int(_)) | Expression::Literal(Literal::Float(_))
-            )
-        {
+        if matches!(operator, TokenKind::Minus) {
             match right {
-                Expression::Literal(Literal::Int(value)) => {
-                    return Ok(Expression::Literal(Literal::Int(-value)));
+                Expression::Literal(Literal::Int(value), _) => {
+                    return Ok(Expression::Literal(Literal::Int(-value), span));
+                }
+                Expression::Literal(Literal::Float(value), _) => {
+                    return Ok(Expression::Literal(Literal::Float(-value), span));
                 }
-                Expression::Literal(Literal::Float(value)) => {
-                    return Ok(Expression::Literal(Literal::Float(-value)));
+                Expression::Literal(Literal::UInt(value), _) => {
+                    return Err(negated_unsigned_literal(value));
                 }
-                _ => unreachable!("Checked in previous if"),
+                _ => {}
             }
         }
 
@@ -950,6 +1142,7 @@ fn parse_unary(cursor: &mut Cursor) -> Result<Expression, String> {
                 _ => unreachable!("Expected +, -, !, or ~ but found {:?}", operator),
             },
             expression: Box::new(right),
+            span,
         }));
     }
 
@@ -957,6 +1150,7 @@ fn parse_unary(cursor: &mut Cursor) -> Result<Expression, String> {
 }
 
 fn parse_call_or_param_propagation(cursor: &mut Cursor) -> Result<Expression, String> {
+    let start = cursor.first_span().start;
     let mut expression = parse_member_access(cursor)?;
 
     if cursor.first().kind == TokenKind::DoubleColon && cursor.second().kind == TokenKind::Less {
@@ -974,9 +1168,23 @@ fn parse_call_or_param_propagation(cursor: &mut Cursor) -> Result<Expression, St
 
     loop {
         match cursor.first().kind {
+            // A line starting with ( or [ starts a new statement instead of calling or indexing
+            TokenKind::OpenParen | TokenKind::OpenBracket if cursor.newline_follows() => break,
             // Call expression
             TokenKind::OpenParen => {
-                expression = parse_call_expression(expression, cursor)?;
+                expression = parse_call_expression(expression, start, cursor)?;
+            }
+            // Index or slice expression
+            TokenKind::OpenBracket => {
+                cursor.bump()?; // Consume the [
+                let argument = parse_expression(cursor)?;
+                cursor.expect(TokenKind::CloseBracket)?; // Consume the ]
+
+                expression = Expression::Index(Index {
+                    callee: Box::new(expression),
+                    argument: Box::new(argument),
+                    span: cursor.span_from(start),
+                });
             }
             // Param propagation
             TokenKind::Colon => {
@@ -1003,6 +1211,16 @@ fn parse_call_or_param_propagation(cursor: &mut Cursor) -> Result<Expression, St
                     generics: None,
                 });
             }
+            // Try, as long as the ? doesn't start a ?? or ?.
+            TokenKind::QuestionMark
+                if !matches!(
+                    cursor.second().kind,
+                    TokenKind::QuestionMark | TokenKind::Dot
+                ) =>
+            {
+                cursor.bump()?; // Consume the ?
+                expression = Expression::Try(Box::new(expression));
+            }
             _ => break,
         }
     }
@@ -1010,24 +1228,65 @@ fn parse_call_or_param_propagation(cursor: &mut Cursor) -> Result<Expression, St
     Ok(expression)
 }
 
-fn parse_call_expression(callee: Expression, cursor: &mut Cursor) -> Result<Expression, String> {
+/// Parse the rest of a path which starts with a module name, like `geometry::shapes::origin`
+fn parse_path(cursor: &mut Cursor, module_name: String, span: Span) -> Result<Expression, String> {
+    let mut path = vec![module_name];
+    let mut last_span = span;
+
+    while cursor.first().kind == TokenKind::DoubleColon {
+        let TokenKind::Identifier(segment) = cursor.second().kind else {
+            break;
+        };
+
+        cursor.bump()?; // Consume the ::
+        cursor.bump()?; // Consume the identifier
+        path.push(segment);
+        last_span = cursor.prev_span();
+    }
+
+    if path.len() == 1 {
+        return Ok(Expression::Member(Member::Identifier {
+            symbol: path.remove(0),
+            generics: None,
+            span,
+        }));
+    }
+
+    Ok(Expression::Member(Member::Path(path, last_span)))
+}
+
+/// Parse the arguments of a call to `callee`, which starts at `start`. A call with more than one
+/// argument applies them one at a time, and only the last application spans the whole call.
+fn parse_call_expression(
+    callee: Expression,
+    start: usize,
+    cursor: &mut Cursor,
+) -> Result<Expression, String> {
     let arguments = parse_args(cursor)?;
     cursor.bump()?; // Consume the )
 
+    let span = cursor.span_from(start);
+    let partial_span = |index: usize| match index + 1 < arguments.len() {
+        true => Span::default(),
+        false => span,
+    };
+
     let mut call = Expression::Call(Call {
         callee: Box::new(callee.clone()),
         argument: arguments.first().map(|a| Box::new(a.clone())),
+        span: partial_span(0),
     });
 
-    for arg in arguments.into_iter().skip(1) {
+    for (index, arg) in arguments.iter().enumerate().skip(1) {
         call = Expression::Call(Call {
             callee: Box::new(call),
-            argument: Some(Box::new(arg)),
+            argument: Some(Box::new(arg.clone())),
+            span: partial_span(index),
         })
     }
 
-    if let TokenKind::OpenParen = cursor.first().kind {
-        call = parse_call_expression(call, cursor)?;
+    if cursor.first().kind == TokenKind::OpenParen && !cursor.newline_follows() {
+        call = parse_call_expression(call, start, cursor)?;
     }
 
     Ok(call)
@@ -1048,8 +1307,7 @@ fn parse_args(cursor: &mut Cursor) -> Result<Vec<Expression>, String> {
 fn parse_args_list(cursor: &mut Cursor) -> Result<Vec<Expression>, String> {
     let mut args = parse_expression(cursor).map(|e| vec![e])?;
 
-    while let TokenKind::Comma = cursor.first().kind {
-        cursor.bump()?; // Consume the ,
+    while list_continues(cursor, TokenKind::CloseParen)? {
         let expression = parse_expression(cursor)?;
         args.push(expression);
     }
@@ -1057,11 +1315,49 @@ fn parse_args_list(cursor: &mut Cursor) -> Result<Vec<Expression>, String> {
     Ok(args)
 }
 
+/// Consume the `,` after an element of a list which `close` ends, and tell if another element
+/// follows it. The last element may be followed by a `,` as well, but only by one.
+fn list_continues(cursor: &mut Cursor, close: TokenKind) -> Result<bool, String> {
+    if cursor.first().kind == close {
+        return Ok(false);
+    }
+
+    if cursor.first().kind != TokenKind::Comma {
+        return Err(format!(
+            "Expected , or {:?} but found {:?}",
+            close,
+            cursor.first().kind
+        ));
+    }
+
+    cursor.bump()?; // Consume the ,
+
+    match cursor.first().kind {
+        kind if kind == close => Ok(false),
+        TokenKind::Comma => Err(format!("Expected {:?} after trailing , but found ,", close)),
+        _ => Ok(true),
+    }
+}
+
 fn parse_member_access(cursor: &mut Cursor) -> Result<Expression, String> {
     let mut object = parse_literal(cursor)?;
 
+    if let Expression::Member(Member::Identifier {
+        symbol,
+        generics: None,
+        span,
+    }) = &object
+    {
+        if !symbol.is_type_identifier_name() {
+            object = parse_path(cursor, symbol.clone(), *span)?;
+        }
+    }
+
     while let TokenKind::DoubleColon = cursor.first().kind {
-        let Expression::Member(Member::Identifier { symbol, generics }) = &object else {
+        let Expression::Member(Member::Identifier {
+            symbol, generics, ..
+        }) = &object
+        else {
             break;
         };
 
@@ -1109,8 +1405,37 @@ fn parse_member_access(cursor: &mut Cursor) -> Result<Expression, String> {
         });
     }
 
-    while let TokenKind::Dot = cursor.first().kind {
-        cursor.bump()?; // Consume the .
+    while let (TokenKind::Dot, _) | (TokenKind::QuestionMark, TokenKind::Dot) =
+        (cursor.first().kind, cursor.second().kind)
+    {
+        let optional = cursor.bump()?.kind == TokenKind::QuestionMark; // Consume the . or ?
+
+        if optional {
+            cursor.bump()?; // Consume the .
+        }
+
+        // A field of a tuple struct is accessed by its index
+        if let TokenKind::Literal(token::Literal::Int(token::IntLiteral {
+            value,
+            base: token::IntLiteralBase::None,
+        })) = cursor.first().kind
+        {
+            cursor.bump()?; // Consume the index
+
+            object = Expression::Member(Member::MemberAccess {
+                object: Box::new(object),
+                member: Box::new(Member::Identifier {
+                    symbol: value.to_string(),
+                    generics: None,
+                    span: cursor.prev_span(),
+                }),
+                symbol: value.to_string(),
+                generics: None,
+                optional,
+            });
+
+            continue;
+        }
 
         let TokenKind::Identifier(identifier) = cursor.first().kind else {
             return Err(format!(
@@ -1131,6 +1456,7 @@ fn parse_member_access(cursor: &mut Cursor) -> Result<Expression, String> {
             member: Box::new(member),
             symbol: identifier,
             generics: None,
+            optional,
         });
     }
 
@@ -1168,7 +1494,7 @@ pub fn parse_literal(cursor: &mut Cursor) -> Result<Expression, String> {
     };
 
     cursor.bump()?; // Consume the literal
-    to_expression_literal(literal)
+    to_expression_literal(literal, cursor.prev_span())
 }
 
 fn parse_primary(cursor: &mut Cursor) -> Result<Expression, String> {
@@ -1178,6 +1504,7 @@ fn parse_primary(cursor: &mut Cursor) -> Result<Expression, String> {
             Ok(Expression::Member(Member::Identifier {
                 symbol: identifier,
                 generics: None,
+                span: cursor.prev_span(),
             }))
         }
         TokenKind::OpenParen => {
@@ -1193,9 +1520,7 @@ fn parse_primary(cursor: &mut Cursor) -> Result<Expression, String> {
                 TokenKind::Comma => {
                     let mut elements = vec![expression];
 
-                    while cursor.first().kind == TokenKind::Comma {
-                        cursor.bump()?; // Consume the ,
-
+                    while list_continues(cursor, TokenKind::CloseParen)? {
                         elements.push(parse_expression(cursor)?);
                     }
 
@@ -1210,6 +1535,7 @@ fn parse_primary(cursor: &mut Cursor) -> Result<Expression, String> {
             }
         }
         TokenKind::OpenBracket => {
+            let start = cursor.first_span().start;
             cursor.bump()?; // Consume the [
 
             let mut elements = vec![];
@@ -1217,14 +1543,32 @@ fn parse_primary(cursor: &mut Cursor) -> Result<Expression, String> {
             while cursor.first().kind != TokenKind::CloseBracket {
                 elements.push(parse_expression(cursor)?);
 
-                if cursor.first().kind == TokenKind::Comma {
-                    cursor.bump()?; // Consume the ,
+                if elements.len() == 1 && cursor.first().kind == TokenKind::Semicolon {
+                    cursor.bump()?; // Consume the ;
+
+                    let length = parse_expression(cursor)?;
+                    cursor.expect(TokenKind::CloseBracket)?; // Consume the ]
+
+                    return Ok(Expression::Literal(
+                        Literal::ArrayRepeat {
+                            value: Box::new(elements.remove(0)),
+                            length: Box::new(length),
+                        },
+                        cursor.span_from(start),
+                    ));
+                }
+
+                if !list_continues(cursor, TokenKind::CloseBracket)? {
+                    break;
                 }
             }
 
             cursor.bump()?; // Consume the ]
 
-            Ok(Expression::Literal(Literal::Array(elements)))
+            Ok(Expression::Literal(
+                Literal::Array(elements),
+                cursor.span_from(start),
+            ))
         }
         _ => Err(format!(
             "Expected primary expression but found {:?}",
@@ -1283,6 +1627,27 @@ fn parse_single_pattern(cursor: &mut Cursor) -> Result<Pattern, String> {
             cursor.bump()?; // Consume the literal
             Ok(Pattern::Float(v))
         }
+        TokenKind::Minus => {
+            cursor.bump()?; // Consume the -
+
+            match cursor.first().kind {
+                TokenKind::Literal(token::Literal::Int(v)) => {
+                    cursor.bump()?; // Consume the literal
+                    Ok(Pattern::Int(-v.value))
+                }
+                TokenKind::Literal(token::Literal::Float(v)) => {
+                    cursor.bump()?; // Consume the literal
+                    Ok(Pattern::Float(-v))
+                }
+                TokenKind::Literal(token::Literal::UInt(v)) => {
+                    Err(negated_unsigned_literal(v.value))
+                }
+                kind => Err(format!(
+                    "Expected a number literal after - in pattern but found {:?}",
+                    kind
+                )),
+            }
+        }
         TokenKind::Literal(token::Literal::Char(v)) => {
             cursor.bump()?; // Consume the literal
             Ok(Pattern::Char(
@@ -1382,17 +1747,26 @@ fn parse_single_pattern(cursor: &mut Cursor) -> Result<Pattern, String> {
     }
 }
 
-fn to_expression_literal(literal: token::Literal) -> Result<Expression, String> {
-    match literal {
-        token::Literal::Void => Err("Void literals are not allowed".to_string()),
-        token::Literal::Unit => Ok(Expression::Literal(Literal::Unit)),
-        token::Literal::Int(literal) => Ok(Expression::Literal(Literal::Int(literal.value))),
-        token::Literal::UInt(literal) => Ok(Expression::Literal(Literal::UInt(literal.value))),
-        token::Literal::Float(value) => Ok(Expression::Literal(Literal::Float(value))),
-        token::Literal::String(value) => Ok(Expression::Literal(Literal::String(value))),
-        token::Literal::Char(value) => Ok(Expression::Literal(Literal::Char(
-            value.parse::<char>().expect("Failed to parse char literal"),
-        ))),
-        token::Literal::Bool(value) => Ok(Expression::Literal(Literal::Bool(value))),
-    }
+fn negated_unsigned_literal(value: u64) -> String {
+    format!(
+        "Cannot negate the unsigned literal {}u, write -{} for a negative int",
+        value, value
+    )
+}
+
+fn to_expression_literal(literal: token::Literal, span: Span) -> Result<Expression, String> {
+    let literal = match literal {
+        token::Literal::Void => return Err("Void literals are not allowed".to_string()),
+        token::Literal::Unit => Literal::Unit,
+        token::Literal::Int(literal) => Literal::Int(literal.value),
+        token::Literal::UInt(literal) => Literal::UInt(literal.value),
+        token::Literal::Float(value) => Literal::Float(value),
+        token::Literal::String(value) => Literal::String(value),
+        token::Literal::Char(value) => {
+            Literal::Char(value.parse::<char>().expect("Failed to parse char literal"))
+        }
+        token::Literal::Bool(value) => Literal::Bool(value),
+    };
+
+    Ok(Expression::Literal(literal, span))
 }
@@ -1,22 +1,182 @@
 pub(crate) mod ast;
+pub mod build;
+pub mod comments;
 pub mod cursor;
 mod expressions;
 mod statements;
 
 pub use ast::*;
 
-use crate::lexer::token::Token;
+use crate::{diagnostic::Diagnostic, lexer, lexer::token::Token, span::Span};
 
-use self::cursor::Cursor;
+use self::{comments::Comments, cursor::Cursor};
+
+/// How deeply expressions can be nested before parsing or checking them fails, as the recursion
+/// would otherwise overflow the stack. Deep enough for hand-written programs while staying within
+/// a 2 MiB stack, like the one tests run on, in unoptimized builds.
+/// Chains of binary operators like `1 + 2 + 3` are parsed and checked without nesting, so a chain
+/// of any length is one level.
+pub const DEFAULT_MAX_DEPTH: usize = 32;
 
 pub fn create_ast(tokens: Vec<Token>, verbose: bool) -> Result<Statement, String> {
+    create_ast_with_max_depth(tokens, verbose, DEFAULT_MAX_DEPTH)
+}
+
+/// Parse like `create_ast`, failing when expressions are nested deeper than `max_depth`
+pub fn create_ast_with_max_depth(
+    tokens: Vec<Token>,
+    verbose: bool,
+    max_depth: usize,
+) -> Result<Statement, String> {
+    let mut cursor = Cursor::new(tokens, verbose).with_max_depth(max_depth);
+
+    let statements = statements::parse_file(&mut cursor)?;
+
+    Ok(Statement::Program { statements })
+}
+
+/// Parse like `create_ast`, but skip the rest of a statement which fails to parse and keep going,
+/// so every syntax error is found in one pass.
+///
+/// Statements are recovered from both at the top level and in blocks. Each one which failed is
+/// replaced with a `Statement::Error` placeholder spanning the skipped source.
+pub fn create_ast_collecting_errors(
+    tokens: Vec<Token>,
+    verbose: bool,
+) -> (Statement, Vec<ParseError>) {
+    let mut cursor = Cursor::new(tokens, verbose).with_recovery();
+
+    let statements = match statements::parse_file(&mut cursor) {
+        Ok(statements) => statements,
+        // Only the module declaration of a file isn't recovered from
+        Err(message) => {
+            let span = cursor.first_span();
+            let mut errors = cursor.take_errors();
+            errors.push(ParseError { message, span });
+
+            return (Statement::Program { statements: vec![] }, errors);
+        }
+    };
+
+    (Statement::Program { statements }, cursor.take_errors())
+}
+
+/// Tokenize and parse `source` like `create_ast`, also keeping the comments which aren't doc
+/// comments for `format::format_with_comments` to write back.
+///
+/// Comments are only kept between the statements of the program or of a block, so a comment
+/// written inside of a statement, like between the fields of a struct, is an error.
+pub fn create_ast_with_comments(
+    source: &str,
+    verbose: bool,
+) -> Result<(Statement, Comments), String> {
+    let tokens = lexer::tokenize(source)?;
+    let comment_spans = comments::comment_spans(&tokens);
     let mut cursor = Cursor::new(tokens, verbose);
 
+    let statements = statements::parse_file(&mut cursor)?;
+    let comments = comments::attach(
+        source,
+        comment_spans,
+        &cursor.take_statement_spans(),
+        &cursor.take_block_spans(),
+    )?;
+
+    Ok((Statement::Program { statements }, comments))
+}
+
+/// Tokenize and parse `source` like `create_ast`, letting a newline end a statement where a `;`
+/// could. The tree is the same as if the semicolons were written out.
+///
+/// A line starting with a binary operator continues the expression before it, and so does a line
+/// ending with one. A line starting with `(` or `[` starts a new statement instead of calling or
+/// indexing the line before it, and a line starting with `+` or `-` is an error, as it could do
+/// either. A `return` or `break` at the end of a line doesn't return or break with a value.
+pub fn create_ast_inferring_semicolons(source: &str, verbose: bool) -> Result<Statement, String> {
+    let tokens = lexer::tokenize(source)?;
+    let mut cursor = Cursor::new(tokens, verbose).with_semicolon_inference(source);
+
     let statements = statements::parse_file(&mut cursor)?;
 
     Ok(Statement::Program { statements })
 }
 
+/// A syntax error and where it was found
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub message: String,
+    /// Where the token parsing failed at is written, or the empty span if the source couldn't be
+    /// tokenized
+    pub span: Span,
+}
+
+impl From<ParseError> for Diagnostic {
+    fn from(error: ParseError) -> Self {
+        Diagnostic::new(error.message, error.span)
+    }
+}
+
+/// Parse the top-level statements of `source` one at a time, instead of the whole program at once
+/// like `create_ast`.
+///
+/// After a statement fails to parse, the rest of it is skipped and parsing continues after it, so
+/// a source with several broken declarations gives an error for each.
+pub fn parse_statements(source: &str) -> Statements {
+    let (tokens, lex_error) = match lexer::tokenize(source) {
+        Ok(tokens) => (tokens, None),
+        Err(message) => (vec![], Some(message)),
+    };
+
+    Statements {
+        cursor: Cursor::new(tokens, false),
+        lex_error,
+        started: false,
+    }
+}
+
+/// The top-level statements of a source, see `parse_statements`
+pub struct Statements {
+    cursor: Cursor,
+    lex_error: Option<String>,
+    /// Whether the module declaration a file may start with has been looked for
+    started: bool,
+}
+
+impl Iterator for Statements {
+    type Item = Result<Statement, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(message) = self.lex_error.take() {
+            return Some(Err(ParseError {
+                message,
+                span: Span::default(),
+            }));
+        }
+
+        let start = self.cursor.first_span().start;
+
+        if !self.started {
+            self.started = true;
+
+            match statements::parse_module_declaration(&mut self.cursor) {
+                Ok(Some(module)) => return Some(Ok(Statement::ModuleDeclaration(module))),
+                Ok(None) => {}
+                Err(message) => return Some(Err(self.cursor.synchronize(message, start, 0))),
+            }
+        }
+
+        if self.cursor.is_end_of_file() {
+            return None;
+        }
+
+        match statements::parse_statement(&mut self.cursor) {
+            Ok(statement) => Some(Ok(statement)),
+            Err(message) => Some(Err(self.cursor.synchronize(message, start, 0))),
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
 pub fn discover_module(
     tokens: Vec<Token>,
 ) -> Result<Option<(Option<AccessModifier>, Vec<String>)>, String> {
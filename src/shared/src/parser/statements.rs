@@ -1,6 +1,7 @@
 use crate::{
-    lexer::token::{IdentifierType, Keyword, TokenKind},
+    lexer::token::{self, IdentifierType, IntLiteral, Keyword, TokenKind},
     parser::AssociatedType,
+    span::Span,
     types::{
         can_be_type_annotation, parse_generic_type_parameters, parse_type_annotation,
         parse_type_identifier, GenericConstraint, GenericType, TypeAnnotation, TypeIdentifier,
@@ -10,15 +11,35 @@ use crate::{
 use super::{
     cursor::Cursor,
     expressions::{self, parse_expression},
-    AccessModifier, Closure, EnumDeclaration, EnumMember, EnumMemberField, Expression,
-    FunctionDeclaration, ImplementationDeclaration, Literal, ModuleDeclaration, Parameter,
-    ProtocolDeclaration, Statement, StructDeclaration, StructField, TypeAliasDeclaration,
-    UnionDeclaration, Use, UseItem,
+    AccessModifier, Attribute, Closure, EnumDeclaration, EnumMember, EnumMemberField, Expression,
+    FunctionDeclaration, ImplementationDeclaration, Literal, ModuleDeclaration, NewtypeDeclaration,
+    Parameter, ProtocolDeclaration, Statement, StructDeclaration, StructField,
+    TypeAliasDeclaration, UnionDeclaration, Use, UseItem,
 };
 
+#[allow(clippy::type_complexity)]
 pub fn parse_module_only(
     cursor: &mut Cursor,
 ) -> Result<Option<(Option<AccessModifier>, Vec<String>)>, String> {
+    let module = parse_module_declaration(cursor)?;
+    Ok(module.map(|module| (module.access_modifier, module.module_path)))
+}
+
+pub fn parse_file(cursor: &mut Cursor) -> Result<Vec<Statement>, String> {
+    let mut statements: Vec<Statement> = parse_module_declaration(cursor)?
+        .map(Statement::ModuleDeclaration)
+        .into_iter()
+        .collect();
+
+    while !cursor.is_end_of_file() {
+        statements.push(cursor.recover_statement(parse_statement)?);
+    }
+
+    Ok(statements)
+}
+
+/// Parse the `mod a::b;` declaration a file may start with
+pub fn parse_module_declaration(cursor: &mut Cursor) -> Result<Option<ModuleDeclaration>, String> {
     let mut access_modifier = None;
 
     if let Some(am) = cursor.first().kind.is_access_modifier() {
@@ -36,6 +57,8 @@ pub fn parse_module_only(
 
     cursor.bump()?; // Consume the mod keyword
 
+    let start = cursor.first_span().start;
+
     let TokenKind::Identifier(module_name) = cursor.first().kind else {
         return Err(format!(
             "Expected identifier but found {:?}",
@@ -66,97 +89,135 @@ pub fn parse_module_only(
         module_path.push(module_name);
     }
 
-    cursor.expect(TokenKind::Semicolon)?;
+    let span = cursor.span_from(start);
+    cursor.expect_semicolon()?;
 
-    Ok(Some((access_modifier, module_path)))
-}
-
-pub fn parse_file(cursor: &mut Cursor) -> Result<Vec<Statement>, String> {
-    parse_mod_statement(cursor)
+    Ok(Some(ModuleDeclaration {
+        access_modifier,
+        module_path,
+        span,
+    }))
 }
 
-fn parse_mod_statement(cursor: &mut Cursor) -> Result<Vec<Statement>, String> {
-    let mut access_modifier = None;
-
-    if let Some(am) = cursor.first().kind.is_access_modifier() {
-        if cursor.second().kind != TokenKind::Keyword(Keyword::Mod) {
-            return parse_statement(cursor).map(|s| vec![s]);
-        }
+pub fn parse_statement(cursor: &mut Cursor) -> Result<Statement, String> {
+    let doc = cursor.doc_comment();
+    let attributes = parse_attributes(cursor)?;
 
-        cursor.bump()?; // Consume the access modifier
-        access_modifier = Some(am);
-    }
+    match parse_use(cursor) {
+        Ok(s) => {
+            let s = attach_attributes(attach_doc(s, doc), attributes)?;
 
-    if cursor.first().kind != TokenKind::Keyword(Keyword::Mod) {
-        let mut statements = vec![];
+            if let TokenKind::Semicolon = cursor.first().kind {
+                cursor.bump()?; // Consume the ;
+                return Ok(Statement::Semi(Box::new(s)));
+            }
 
-        while !cursor.is_end_of_file() {
-            statements.push(parse_statement(cursor)?);
+            Ok(s)
         }
-
-        return Ok(statements);
+        Err(e) => Err(e),
     }
+}
 
-    cursor.bump()?; // Consume the mod keyword
-
-    let TokenKind::Identifier(module_name) = cursor.first().kind else {
-        return Err(format!(
-            "Expected identifier but found {:?}",
-            cursor.first().kind
-        ));
-    };
+/// Attach a doc comment to a declaration, doc comments in front of anything else are ignored
+fn attach_doc(statement: Statement, doc: Option<String>) -> Statement {
+    let mut statement = statement;
+
+    match &mut statement {
+        Statement::StructDeclaration(declaration) => declaration.doc = doc,
+        Statement::EnumDeclaration(declaration) => declaration.doc = doc,
+        Statement::UnionDeclaration(declaration) => declaration.doc = doc,
+        Statement::TypeAliasDeclaration(declaration) => declaration.doc = doc,
+        Statement::NewtypeDeclaration(declaration) => declaration.doc = doc,
+        Statement::ProtocolDeclaration(declaration) => declaration.doc = doc,
+        Statement::ImplementationDeclaration(declaration) => declaration.doc = doc,
+        Statement::FunctionDeclaration(declaration) => declaration.doc = doc,
+        _ => {}
+    }
 
-    cursor.bump()?; // Consume the identifier
+    statement
+}
 
-    if !module_name.is_module_identifier_name() {
-        return Err(format!("Invalid module name: {}", module_name));
-    }
+/// Parse any number of `@name` or `@name(args...)` attributes, the arguments must be literals
+fn parse_attributes(cursor: &mut Cursor) -> Result<Vec<Attribute>, String> {
+    let mut attributes = vec![];
 
-    let mut module_path: Vec<String> = vec![];
-    module_path.push(module_name);
+    while cursor.first().kind == TokenKind::At {
+        cursor.bump()?; // Consume the @
 
-    while cursor.first().kind == TokenKind::DoubleColon {
-        cursor.bump()?; // Consume the ::
-        let TokenKind::Identifier(module_name) = cursor.first().kind else {
+        let TokenKind::Identifier(name) = cursor.bump()?.kind else {
             return Err(format!(
-                "Expected identifier but found {:?}",
-                cursor.first().kind
+                "Expected attribute name but found {:?}",
+                cursor.prev().kind
             ));
         };
 
-        cursor.bump()?; // Consume the identifier
+        let mut args = vec![];
 
-        module_path.push(module_name);
-    }
+        if cursor.first().kind == TokenKind::OpenParen {
+            cursor.bump()?; // Consume the (
 
-    cursor.expect(TokenKind::Semicolon)?;
+            while cursor.first().kind != TokenKind::CloseParen {
+                match expressions::parse_literal(cursor)? {
+                    Expression::Literal(literal, _) => args.push(literal),
+                    arg => {
+                        return Err(format!(
+                            "Expected literal attribute argument but found {:?}",
+                            arg
+                        ))
+                    }
+                }
 
-    let module_declaration = Statement::ModuleDeclaration(ModuleDeclaration {
-        access_modifier,
-        module_path,
-    });
+                if cursor.first().kind == TokenKind::Comma {
+                    cursor.bump()?; // Consume the ,
+                } else if cursor.first().kind != TokenKind::CloseParen {
+                    return Err(format!(
+                        "Expected , or ) but found {:?}",
+                        cursor.first().kind
+                    ));
+                }
+            }
 
-    let mut statements = vec![module_declaration];
+            cursor.bump()?; // Consume the )
+        }
 
-    while !cursor.is_end_of_file() {
-        statements.push(parse_statement(cursor)?);
+        attributes.push(Attribute { name, args });
     }
 
-    Ok(statements)
+    Ok(attributes)
 }
 
-pub fn parse_statement(cursor: &mut Cursor) -> Result<Statement, String> {
-    match parse_use(cursor) {
-        Ok(s) => {
-            if let TokenKind::Semicolon = cursor.first().kind {
-                cursor.bump()?; // Consume the ;
-                return Ok(Statement::Semi(Box::new(s)));
-            }
+fn attach_attributes(
+    statement: Statement,
+    attributes: Vec<Attribute>,
+) -> Result<Statement, String> {
+    if attributes.is_empty() {
+        return Ok(statement);
+    }
 
-            Ok(s)
+    let mut statement = statement;
+
+    match &mut statement {
+        Statement::StructDeclaration(declaration) => declaration.attributes = attributes,
+        Statement::EnumDeclaration(declaration) => declaration.attributes = attributes,
+        Statement::UnionDeclaration(declaration) => declaration.attributes = attributes,
+        Statement::TypeAliasDeclaration(declaration) => declaration.attributes = attributes,
+        Statement::NewtypeDeclaration(declaration) => declaration.attributes = attributes,
+        Statement::ProtocolDeclaration(declaration) => declaration.attributes = attributes,
+        Statement::ImplementationDeclaration(declaration) => declaration.attributes = attributes,
+        Statement::FunctionDeclaration(declaration) => declaration.attributes = attributes,
+        _ => {
+            return Err(format!(
+                "Attributes can only be placed on declarations, found {}",
+                attributes
+                    .iter()
+                    .map(|attribute| attribute.to_string())
+                    .collect::<Vec<String>>()
+                    .join(" ")
+            ))
         }
-        Err(e) => Err(e),
     }
+
+    Ok(statement)
 }
 
 fn parse_use(cursor: &mut Cursor) -> Result<Statement, String> {
@@ -167,7 +228,7 @@ fn parse_use(cursor: &mut Cursor) -> Result<Statement, String> {
     cursor.bump()?; // Consume the use keyword
 
     let use_item = parse_use_item(cursor)?;
-    cursor.expect(TokenKind::Semicolon)?;
+    cursor.expect_semicolon()?;
     Ok(Statement::Use(Use { use_item }))
 }
 
@@ -214,8 +275,12 @@ fn parse_use_item(cursor: &mut Cursor) -> Result<UseItem, String> {
 
             Ok(UseItem::List(use_items))
         }
+        TokenKind::Star => {
+            cursor.bump()?; // Consume the *
+            Ok(UseItem::Glob)
+        }
         _ => Err(format!(
-            "Expected identifier or {{ but found {:?}",
+            "Expected identifier, {{ or * but found {:?}",
             cursor.first().kind
         )),
     }
@@ -239,7 +304,9 @@ fn parse_function_declaration_statement(cursor: &mut Cursor) -> Result<Statement
 
     cursor.bump()?; // Consume the fun keyword
 
+    let start = cursor.first_span().start;
     let type_identifier = parse_type_identifier(cursor, false)?;
+    let span = cursor.span_from(start);
 
     if !type_identifier.name().is_function_identifier_name() {
         return Err(format!("Invalid function name: {}", type_identifier.name()));
@@ -270,6 +337,8 @@ fn parse_function_declaration_statement(cursor: &mut Cursor) -> Result<Statement
         return_type_annotation = Some(parse_type_annotation(cursor, true)?);
     }
 
+    let where_clause = parse_where_clause(cursor)?;
+
     if cursor.first().kind == TokenKind::Semicolon {
         cursor.bump()?; // Consume the ;
 
@@ -277,18 +346,19 @@ fn parse_function_declaration_statement(cursor: &mut Cursor) -> Result<Statement
             unwrap_parameters_only(params, return_type_annotation)?;
 
         return Ok(Statement::FunctionDeclaration(FunctionDeclaration {
+            doc: None,
+            attributes: vec![],
             access_modifier,
             type_identifier,
+            span,
             param,
             return_type_annotation,
-            where_clause: None,
+            where_clause,
             body: None,
             signature_only: true,
         }));
     }
 
-    let where_clause = parse_where_clause(cursor)?;
-
     let TokenKind::FatArrow = cursor.bump()?.kind else {
         return Err(format!("Expected => but found {:?}", cursor.first().kind));
     };
@@ -297,6 +367,7 @@ fn parse_function_declaration_statement(cursor: &mut Cursor) -> Result<Statement
     let body = unwrap_parameters(
         access_modifier,
         type_identifier,
+        span,
         params,
         return_type_annotation.clone(),
         where_clause,
@@ -329,9 +400,10 @@ fn unwrap_parameters_only(
     }
 }
 
-fn unwrap_parameters(
+pub fn unwrap_parameters(
     access_modifier: Option<AccessModifier>,
     type_identifier: TypeIdentifier,
+    span: Span,
     params: Vec<Parameter>,
     return_type_annotation: Option<TypeAnnotation>,
     where_clause: Option<Vec<GenericConstraint>>,
@@ -339,8 +411,11 @@ fn unwrap_parameters(
 ) -> Result<Statement, String> {
     match params.first().cloned() {
         None => Ok(Statement::FunctionDeclaration(FunctionDeclaration {
+            doc: None,
+            attributes: vec![],
             access_modifier,
             type_identifier,
+            span,
             param: None,
             return_type_annotation,
             where_clause,
@@ -355,8 +430,11 @@ fn unwrap_parameters(
             )?;
 
             Ok(Statement::FunctionDeclaration(FunctionDeclaration {
+                doc: None,
+                attributes: vec![],
                 access_modifier,
                 type_identifier,
+                span,
                 param: Some(first),
                 return_type_annotation: new_return_type_annotation,
                 where_clause,
@@ -412,18 +490,38 @@ fn parse_struct_declaration_statement(cursor: &mut Cursor) -> Result<Statement,
 
     cursor.bump()?; // Consume the struct keyword
 
+    let start = cursor.first_span().start;
     let type_identifier = parse_type_identifier(cursor, false)?;
+    let span = cursor.span_from(start);
 
     if !type_identifier.name().is_type_identifier_name() {
         return Err(format!("Invalid type name: {}", type_identifier.name()));
     }
 
+    if cursor.first().kind == TokenKind::OpenParen {
+        let fields = parse_tuple_struct_fields(cursor)?;
+        let where_clause = parse_where_clause(cursor)?;
+
+        return Ok(Statement::StructDeclaration(StructDeclaration {
+            doc: None,
+            attributes: vec![],
+            access_modifier,
+            type_identifier,
+            span,
+            where_clause,
+            fields,
+        }));
+    }
+
     let where_clause = parse_where_clause(cursor)?;
 
     let TokenKind::OpenBrace = cursor.first().kind else {
         return Ok(Statement::StructDeclaration(StructDeclaration {
+            doc: None,
+            attributes: vec![],
             access_modifier,
             type_identifier,
+            span,
             where_clause,
             fields: vec![],
         }));
@@ -440,7 +538,7 @@ fn parse_struct_declaration_statement(cursor: &mut Cursor) -> Result<Statement,
         }
 
         has_comma = true;
-        fields.push(parse_struct_field(cursor, true)?);
+        fields.push(parse_struct_field(cursor, true, true)?);
 
         if cursor.first().kind == TokenKind::Comma {
             cursor.bump()?; // Consume the ,
@@ -452,13 +550,74 @@ fn parse_struct_declaration_statement(cursor: &mut Cursor) -> Result<Statement,
     cursor.bump()?; // Consume the }
 
     Ok(Statement::StructDeclaration(StructDeclaration {
+        doc: None,
+        attributes: vec![],
         access_modifier,
         type_identifier,
+        span,
         where_clause,
         fields,
     }))
 }
 
+/// Parse the fields of a tuple struct like `(Int, mut Float)`, which are named by their index
+fn parse_tuple_struct_fields(cursor: &mut Cursor) -> Result<Vec<StructField>, String> {
+    cursor.bump()?; // Consume the (
+
+    let mut fields = vec![];
+    let mut has_comma = true;
+
+    while cursor.first().kind != TokenKind::CloseParen {
+        if !has_comma {
+            return Err(format!("Expected , but found {:?}", cursor.first().kind));
+        }
+
+        let mut access_modifier = None;
+
+        if let Some(am) = cursor.first().kind.is_access_modifier() {
+            cursor.bump()?; // Consume the access modifier
+            access_modifier = Some(am);
+        }
+
+        let mutable = match cursor.first().kind {
+            TokenKind::Keyword(Keyword::Mut) => {
+                cursor.bump()?; // Consume the mutable keyword
+                true
+            }
+            _ => false,
+        };
+
+        if !can_be_type_annotation(cursor) {
+            return Err(format!(
+                "Expected type identifier but found {:?}",
+                cursor.first().kind
+            ));
+        }
+
+        let start = cursor.first_span().start;
+        let type_annotation = parse_type_annotation(cursor, false)?;
+
+        fields.push(StructField {
+            access_modifier,
+            mutable,
+            identifier: fields.len().to_string(),
+            span: cursor.span_from(start),
+            type_annotation,
+            default: None,
+        });
+
+        has_comma = cursor.first().kind == TokenKind::Comma;
+
+        if has_comma {
+            cursor.bump()?; // Consume the ,
+        }
+    }
+
+    cursor.bump()?; // Consume the )
+
+    Ok(fields)
+}
+
 fn parse_enum_declaration_statement(cursor: &mut Cursor) -> Result<Statement, String> {
     let mut access_modifier = None;
 
@@ -477,7 +636,9 @@ fn parse_enum_declaration_statement(cursor: &mut Cursor) -> Result<Statement, St
 
     cursor.bump()?; // Consume the enum keyword
 
+    let start = cursor.first_span().start;
     let type_name = parse_type_identifier(cursor, false)?;
+    let span = cursor.span_from(start);
 
     if !type_name.name().is_type_identifier_name() {
         return Err(format!("Invalid type name: {}", type_name.name()));
@@ -488,8 +649,11 @@ fn parse_enum_declaration_statement(cursor: &mut Cursor) -> Result<Statement, St
     if cursor.first().kind == TokenKind::Semicolon {
         cursor.bump()?; // Consume the ;
         return Ok(Statement::EnumDeclaration(EnumDeclaration {
+            doc: None,
+            attributes: vec![],
             access_modifier,
             type_identifier: type_name,
+            span,
             where_clause: None,
             shared_fields: vec![],
             members: vec![],
@@ -514,7 +678,7 @@ fn parse_enum_declaration_statement(cursor: &mut Cursor) -> Result<Statement, St
         if let (TokenKind::Identifier(_), TokenKind::Colon) =
             (cursor.first().kind, cursor.second().kind)
         {
-            shared_fields.push(parse_struct_field(cursor, false)?);
+            shared_fields.push(parse_struct_field(cursor, false, false)?);
         } else {
             members.push(parse_enum_member(cursor, shared_fields.clone())?);
         }
@@ -529,8 +693,11 @@ fn parse_enum_declaration_statement(cursor: &mut Cursor) -> Result<Statement, St
     cursor.bump()?; // Consume the }
 
     Ok(Statement::EnumDeclaration(EnumDeclaration {
+        doc: None,
+        attributes: vec![],
         access_modifier,
         type_identifier: type_name,
+        span,
         where_clause,
         shared_fields,
         members,
@@ -593,12 +760,14 @@ fn parse_union_declaration_statement(cursor: &mut Cursor) -> Result<Statement, S
     let literals: Result<Vec<Literal>, String> = literals
         .iter()
         .map(|l| match l {
-            Expression::Literal(l) => Ok(l.clone()),
+            Expression::Literal(l, _) => Ok(l.clone()),
             _ => Err(format!("Expected literal but found {:?}", l)),
         })
         .collect();
 
     Ok(Statement::UnionDeclaration(UnionDeclaration {
+        doc: None,
+        attributes: vec![],
         access_modifier,
         type_identifier: TypeIdentifier::Type(type_name),
         literals: literals?,
@@ -609,7 +778,7 @@ fn parse_type_alias_declaration(cursor: &mut Cursor) -> Result<Statement, String
     let mut access_modifier = None;
     if let Some(am) = cursor.first().kind.is_access_modifier() {
         if cursor.second().kind != TokenKind::Keyword(Keyword::Type) {
-            return parse_protocol_declaration(cursor);
+            return parse_newtype_declaration(cursor);
         }
 
         cursor.bump()?; // Consume the access modifier
@@ -617,7 +786,7 @@ fn parse_type_alias_declaration(cursor: &mut Cursor) -> Result<Statement, String
     }
 
     if cursor.first().kind != TokenKind::Keyword(Keyword::Type) {
-        return parse_protocol_declaration(cursor);
+        return parse_newtype_declaration(cursor);
     }
 
     cursor.bump()?; // Consume the type keyword
@@ -640,15 +809,57 @@ fn parse_type_alias_declaration(cursor: &mut Cursor) -> Result<Statement, String
         type_annotations.push(parse_type_annotation(cursor, false)?);
     }
 
-    cursor.expect(TokenKind::Semicolon)?;
+    cursor.expect_semicolon()?;
 
     Ok(Statement::TypeAliasDeclaration(TypeAliasDeclaration {
+        doc: None,
+        attributes: vec![],
         access_modifier,
         type_identifier,
         type_annotations,
     }))
 }
 
+fn parse_newtype_declaration(cursor: &mut Cursor) -> Result<Statement, String> {
+    let mut access_modifier = None;
+    if let Some(am) = cursor.first().kind.is_access_modifier() {
+        if cursor.second().kind != TokenKind::Keyword(Keyword::Newtype) {
+            return parse_protocol_declaration(cursor);
+        }
+
+        cursor.bump()?; // Consume the access modifier
+        access_modifier = Some(am);
+    }
+
+    if cursor.first().kind != TokenKind::Keyword(Keyword::Newtype) {
+        return parse_protocol_declaration(cursor);
+    }
+
+    cursor.bump()?; // Consume the newtype keyword
+
+    let type_identifier = parse_type_identifier(cursor, false)?;
+
+    if !type_identifier.name().is_type_identifier_name() {
+        return Err(format!("Invalid type name: {}", type_identifier.name()));
+    }
+
+    let TokenKind::Equal = cursor.bump()?.kind else {
+        return Err(format!("Expected = but found {:?}", cursor.first().kind));
+    };
+
+    let type_annotation = parse_type_annotation(cursor, false)?;
+
+    cursor.expect_semicolon()?;
+
+    Ok(Statement::NewtypeDeclaration(NewtypeDeclaration {
+        doc: None,
+        attributes: vec![],
+        access_modifier,
+        type_identifier,
+        type_annotation,
+    }))
+}
+
 fn parse_protocol_declaration(cursor: &mut Cursor) -> Result<Statement, String> {
     let mut access_modifier = None;
 
@@ -677,6 +888,8 @@ fn parse_protocol_declaration(cursor: &mut Cursor) -> Result<Statement, String>
         cursor.bump()?; // Consume the ;
 
         return Ok(Statement::ProtocolDeclaration(ProtocolDeclaration {
+            doc: None,
+            attributes: vec![],
             access_modifier,
             type_identifier,
             associated_types: vec![],
@@ -732,9 +945,17 @@ fn parse_protocol_declaration(cursor: &mut Cursor) -> Result<Statement, String>
 
     let mut functions = vec![];
 
-    while cursor.first().kind == TokenKind::Keyword(Keyword::Fun) {
-        let Statement::FunctionDeclaration(function) =
-            parse_function_declaration_statement(cursor)?
+    while matches!(
+        cursor.first().kind,
+        TokenKind::Keyword(Keyword::Fun) | TokenKind::At
+    ) {
+        let doc = cursor.doc_comment();
+        let attributes = parse_attributes(cursor)?;
+
+        let Statement::FunctionDeclaration(function) = attach_attributes(
+            attach_doc(parse_function_declaration_statement(cursor)?, doc),
+            attributes,
+        )?
         else {
             return Err(format!(
                 "Expected function declaration but found {:?}",
@@ -748,6 +969,8 @@ fn parse_protocol_declaration(cursor: &mut Cursor) -> Result<Statement, String>
     cursor.expect(TokenKind::CloseBrace)?;
 
     Ok(Statement::ProtocolDeclaration(ProtocolDeclaration {
+        doc: None,
+        attributes: vec![],
         access_modifier,
         type_identifier,
         associated_types,
@@ -768,20 +991,24 @@ fn parse_implementation_declaration(cursor: &mut Cursor) -> Result<Statement, St
         vec![]
     };
 
-    let protocol_annotation = parse_type_annotation(cursor, false)?;
+    let annotation = parse_type_annotation(cursor, false)?;
 
-    if !protocol_annotation.name().is_type_identifier_name() {
-        return Err(format!("Invalid type name: {}", protocol_annotation.name()));
-    }
+    // Without a for keyword the methods belong to the type itself
+    let (protocol_annotation, type_annotation) =
+        if cursor.first().kind == TokenKind::Keyword(Keyword::For) {
+            cursor.bump()?; // Consume the for keyword
 
-    let TokenKind::Keyword(Keyword::For) = cursor.bump()?.kind else {
-        return Err(format!("Expected for but found {:?}", cursor.first().kind));
-    };
+            if !annotation.name().is_type_identifier_name() {
+                return Err(format!("Invalid protocol name: {}", annotation.name()));
+            }
 
-    let type_annotation = parse_type_annotation(cursor, false)?;
+            (Some(annotation), parse_type_annotation(cursor, false)?)
+        } else {
+            (None, annotation)
+        };
 
     if !type_annotation.name().is_type_identifier_name() {
-        return Err(format!("Invalid protocol name: {}", type_annotation.name()));
+        return Err(format!("Invalid type name: {}", type_annotation.name()));
     }
 
     cursor.expect(TokenKind::OpenBrace)?;
@@ -832,9 +1059,17 @@ fn parse_implementation_declaration(cursor: &mut Cursor) -> Result<Statement, St
 
     let mut functions = vec![];
 
-    while cursor.first().kind == TokenKind::Keyword(Keyword::Fun) {
-        let Statement::FunctionDeclaration(function) =
-            parse_function_declaration_statement(cursor)?
+    while matches!(
+        cursor.first().kind,
+        TokenKind::Keyword(Keyword::Fun) | TokenKind::At
+    ) {
+        let doc = cursor.doc_comment();
+        let attributes = parse_attributes(cursor)?;
+
+        let Statement::FunctionDeclaration(function) = attach_attributes(
+            attach_doc(parse_function_declaration_statement(cursor)?, doc),
+            attributes,
+        )?
         else {
             return Err(format!(
                 "Expected function declaration but found {:?}",
@@ -849,6 +1084,8 @@ fn parse_implementation_declaration(cursor: &mut Cursor) -> Result<Statement, St
 
     Ok(Statement::ImplementationDeclaration(
         ImplementationDeclaration {
+            doc: None,
+            attributes: vec![],
             scoped_generics,
             protocol_annotation,
             type_annotation,
@@ -861,8 +1098,7 @@ fn parse_implementation_declaration(cursor: &mut Cursor) -> Result<Statement, St
 fn parse_expression_map(cursor: &mut Cursor) -> Result<Statement, String> {
     match expressions::parse_expression(cursor) {
         Ok(e) => {
-            if let TokenKind::Semicolon = cursor.first().kind {
-                cursor.bump()?; // Consume the ;
+            if cursor.bump_semicolon()? {
                 return Ok(Statement::Semi(Box::new(Statement::Expression(e))));
             }
 
@@ -883,7 +1119,13 @@ fn parse_parameters(cursor: &mut Cursor) -> Result<Vec<Parameter>, String> {
         }
 
         has_comma = true;
-        parameters.push(parse_parameter(cursor)?);
+        let parameter = parse_parameter(cursor)?;
+
+        if parameter.identifier == "self" && !parameters.is_empty() {
+            return Err("self must be the first parameter".to_string());
+        }
+
+        parameters.push(parameter);
 
         if cursor.first().kind == TokenKind::Comma {
             cursor.bump()?; // Consume the ,
@@ -896,6 +1138,12 @@ fn parse_parameters(cursor: &mut Cursor) -> Result<Vec<Parameter>, String> {
 }
 
 fn parse_parameter(cursor: &mut Cursor) -> Result<Parameter, String> {
+    let mutable = cursor.first().kind == TokenKind::Keyword(Keyword::Mut);
+
+    if mutable {
+        cursor.bump()?; // Consume the mut
+    }
+
     let TokenKind::Identifier(identifier) = cursor.bump()?.kind else {
         return Err(format!(
             "Expected identifier but found {:?}",
@@ -903,10 +1151,30 @@ fn parse_parameter(cursor: &mut Cursor) -> Result<Parameter, String> {
         ));
     };
 
+    let span = cursor.prev_span();
+
     if !identifier.is_variable_identifier_name() {
         return Err(format!("Invalid variable name: {}", identifier));
     }
 
+    // Only the receiver can be mutable, as the other parameters are taken by closures
+    if mutable && identifier != "self" {
+        return Err(format!(
+            "Only self can be a mutable parameter, found mut {}",
+            identifier
+        ));
+    }
+
+    // A bare self receiver is shorthand for self: Self
+    if identifier == "self" && cursor.first().kind != TokenKind::Colon {
+        return Ok(Parameter {
+            identifier,
+            type_annotation: TypeAnnotation::Type("Self".to_owned()),
+            mutable,
+            span,
+        });
+    }
+
     let TokenKind::Colon = cursor.bump()?.kind else {
         return Err(format!("Expected : but found {:?}", cursor.first().kind));
     };
@@ -923,12 +1191,15 @@ fn parse_parameter(cursor: &mut Cursor) -> Result<Parameter, String> {
     Ok(Parameter {
         identifier,
         type_annotation,
+        mutable,
+        span,
     })
 }
 
 fn parse_struct_field(
     cursor: &mut Cursor,
     allow_access_modifier: bool,
+    allow_default: bool,
 ) -> Result<StructField, String> {
     let mut access_modifier = None;
 
@@ -952,6 +1223,7 @@ fn parse_struct_field(
     };
 
     cursor.bump()?; // Consume the identifier
+    let span = cursor.prev_span();
 
     if !identifier.is_variable_identifier_name() {
         return Err(format!("Invalid field name: {}", identifier));
@@ -978,11 +1250,24 @@ fn parse_struct_field(
 
     let type_annotation = parse_type_annotation(cursor, false)?;
 
+    let default = if cursor.first().kind == TokenKind::Equal {
+        if !allow_default {
+            return Err(format!("Field {} cannot have a default value", identifier));
+        }
+
+        cursor.bump()?; // Consume the =
+        Some(parse_expression(cursor)?)
+    } else {
+        None
+    };
+
     Ok(StructField {
         access_modifier,
         mutable,
         identifier,
+        span,
         type_annotation,
+        default,
     })
 }
 
@@ -997,56 +1282,75 @@ fn parse_enum_member(
         ));
     };
 
+    let span = cursor.prev_span();
+
     if !identifier.is_type_identifier_name() {
         return Err(format!("Invalid type name: {}", identifier));
     }
 
-    match cursor.first().kind {
-        TokenKind::OpenBrace => {
-            cursor.bump()?; // Consume the (
+    let mut fields = vec![];
 
-            let mut fields = vec![];
+    for shared_field in shared_fields {
+        fields.push(EnumMemberField {
+            identifier: shared_field.identifier,
+            type_annotation: shared_field.type_annotation,
+        });
+    }
 
-            for shared_field in shared_fields {
-                fields.push(EnumMemberField {
-                    identifier: shared_field.identifier,
-                    type_annotation: shared_field.type_annotation,
-                });
-            }
+    if cursor.first().kind == TokenKind::OpenBrace {
+        cursor.bump()?; // Consume the {
 
-            let mut has_comma = true;
+        let mut has_comma = true;
 
-            while cursor.first().kind != TokenKind::CloseBrace {
-                if !has_comma {
-                    return Err(format!("Expected , but found {:?}", cursor.first().kind));
-                }
+        while cursor.first().kind != TokenKind::CloseBrace {
+            if !has_comma {
+                return Err(format!("Expected , but found {:?}", cursor.first().kind));
+            }
 
-                has_comma = true;
-                fields.push(parse_enum_field(cursor)?);
+            has_comma = true;
+            fields.push(parse_enum_field(cursor)?);
 
-                if cursor.first().kind == TokenKind::Comma {
-                    cursor.bump()?; // Consume the ,
-                } else {
-                    has_comma = false;
-                }
+            if cursor.first().kind == TokenKind::Comma {
+                cursor.bump()?; // Consume the ,
+            } else {
+                has_comma = false;
             }
+        }
 
-            cursor.bump()?; // Consume the )
+        cursor.bump()?; // Consume the }
+    }
 
-            Ok(EnumMember { identifier, fields })
+    let discriminant = match cursor.first().kind {
+        TokenKind::Equal => {
+            cursor.bump()?; // Consume the =
+            Some(parse_discriminant(cursor)?)
         }
-        _ => {
-            let mut fields = vec![];
+        _ => None,
+    };
 
-            for shared_field in shared_fields {
-                fields.push(EnumMemberField {
-                    identifier: shared_field.identifier,
-                    type_annotation: shared_field.type_annotation,
-                });
-            }
+    Ok(EnumMember {
+        identifier,
+        span,
+        fields,
+        discriminant,
+    })
+}
 
-            Ok(EnumMember { identifier, fields })
-        }
+/// An integer literal, which may be negated
+fn parse_discriminant(cursor: &mut Cursor) -> Result<i64, String> {
+    let negative = cursor.first().kind == TokenKind::Minus;
+
+    if negative {
+        cursor.bump()?; // Consume the -
+    }
+
+    match cursor.bump()?.kind {
+        TokenKind::Literal(token::Literal::Int(IntLiteral { value, .. })) if negative => Ok(-value),
+        TokenKind::Literal(token::Literal::Int(IntLiteral { value, .. })) => Ok(value),
+        kind => Err(format!(
+            "Expected an integer discriminant but found {:?}",
+            kind
+        )),
     }
 }
 
@@ -4,6 +4,7 @@ use std::{collections::HashMap, fmt::Display};
 use crate::display::{Indent, IndentDisplay};
 use crate::parser::{AssociatedType, Expression, UseItem};
 use crate::pretty_print::PrettyPrint;
+use crate::span::Span;
 use crate::types::{GenericType, ToKey};
 use crate::{
     parser,
@@ -19,6 +20,7 @@ pub trait Typed {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[allow(clippy::large_enum_variant)]
 pub enum TypedStatement {
     None,
     Program {
@@ -43,6 +45,8 @@ pub enum TypedStatement {
         type_identifier: TypeIdentifier,
         shared_fields: Vec<StructField>,
         members: Vec<EnumMember>,
+        /// The integer type the discriminants are stored as, chosen with `@repr`
+        repr: Type,
         type_: Type,
     },
     UnionDeclaration {
@@ -55,6 +59,11 @@ pub enum TypedStatement {
         type_annotations: Vec<TypeAnnotation>,
         type_: Type,
     },
+    NewtypeDeclaration {
+        type_identifier: TypeIdentifier,
+        type_annotation: TypeAnnotation,
+        type_: Type,
+    },
     ProtocolDeclaration {
         type_identifier: TypeIdentifier,
         associated_types: Vec<AssociatedType>,
@@ -63,7 +72,7 @@ pub enum TypedStatement {
     },
     ImplementationDeclaration {
         scoped_generics: Vec<GenericType>,
-        protocol_annotation: TypeAnnotation,
+        protocol_annotation: Option<TypeAnnotation>,
         type_annotation: TypeAnnotation,
         associated_types: Vec<AssociatedType>,
         functions: Vec<(String, TypedStatement)>,
@@ -100,6 +109,7 @@ impl Typed for TypedStatement {
             TypedStatement::EnumDeclaration { type_, .. } => type_.clone(),
             TypedStatement::UnionDeclaration { type_, .. } => type_.clone(),
             TypedStatement::TypeAliasDeclaration { type_, .. } => type_.clone(),
+            TypedStatement::NewtypeDeclaration { type_, .. } => type_.clone(),
             TypedStatement::ProtocolDeclaration { type_, .. } => type_.clone(),
             TypedStatement::ImplementationDeclaration { type_, .. } => type_.clone(),
             TypedStatement::FunctionDeclaration { type_, .. } => type_.clone(),
@@ -118,6 +128,7 @@ impl Typed for TypedStatement {
             TypedStatement::EnumDeclaration { type_, .. } => type_.clone(),
             TypedStatement::UnionDeclaration { type_, .. } => type_.clone(),
             TypedStatement::TypeAliasDeclaration { type_, .. } => type_.clone(),
+            TypedStatement::NewtypeDeclaration { type_, .. } => type_.clone(),
             TypedStatement::ProtocolDeclaration { type_, .. } => type_.clone(),
             TypedStatement::ImplementationDeclaration { type_, .. } => type_.clone(),
             TypedStatement::FunctionDeclaration { type_, .. } => type_.clone(),
@@ -223,7 +234,7 @@ impl Display for TypedStatement {
                 ..
             } => write!(
                 f,
-                "imp{} {} for {} {{ associated types: {}, functions: {} }}",
+                "imp{} {}{} {{ associated types: {}, functions: {} }}",
                 format_args!(
                     "<{}>",
                     scoped_generics
@@ -232,7 +243,10 @@ impl Display for TypedStatement {
                         .collect::<Vec<String>>()
                         .join(", ")
                 ),
-                protocol_annotation,
+                protocol_annotation
+                    .as_ref()
+                    .map(|p| format!("{} for ", p))
+                    .unwrap_or_default(),
                 type_annotation,
                 associated_types
                     .iter()
@@ -259,6 +273,11 @@ impl Display for TypedStatement {
                     .collect::<Vec<String>>()
                     .join(" | ")
             ),
+            TypedStatement::NewtypeDeclaration {
+                type_identifier,
+                type_annotation,
+                ..
+            } => write!(f, "newtype {} = {}", type_identifier, type_annotation),
             TypedStatement::FunctionDeclaration {
                 identifier,
                 param,
@@ -301,6 +320,10 @@ pub struct TypedParameter {
     pub identifier: String,
     pub type_annotation: TypeAnnotation,
     pub type_: Box<Type>,
+    /// Whether the parameter is `mut self`
+    pub mutable: bool,
+    /// Where the identifier is written
+    pub span: Span,
 }
 
 impl Typed for TypedParameter {
@@ -315,6 +338,10 @@ impl Typed for TypedParameter {
 
 impl Display for TypedParameter {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.mutable {
+            write!(f, "mut ")?;
+        }
+
         write!(f, "{}: {}", self.identifier, self.type_annotation)
     }
 }
@@ -324,6 +351,8 @@ pub struct TypedClosureParameter {
     pub identifier: String,
     pub type_annotation: Option<TypeAnnotation>,
     pub type_: Box<Type>,
+    /// Where the identifier is written
+    pub span: Span,
 }
 
 impl Typed for TypedClosureParameter {
@@ -384,7 +413,10 @@ pub enum TypedExpression {
     // None, // For testing purposes
     VariableDeclaration {
         mutable: bool,
+        type_annotation: Option<TypeAnnotation>,
         pattern: Pattern,
+        /// Where the pattern is written
+        pattern_span: Span,
         initializer: Option<Box<TypedExpression>>,
         type_: Type,
     },
@@ -399,6 +431,7 @@ pub enum TypedExpression {
         arms: Vec<TypedMatchArm>,
         decision_tree: Decision,
         type_: Type,
+        span: Span,
     },
     Assignment {
         member: Box<Member>,
@@ -406,11 +439,16 @@ pub enum TypedExpression {
         type_: Type,
     },
     Member(Member),
-    Literal(Literal),
+    /// A literal and where it is written
+    Literal(Literal, Span),
     Tuple {
         elements: Vec<TypedExpression>,
         type_: Type,
     },
+    Record {
+        fields: Vec<FieldInitializer>,
+        type_: Type,
+    },
     Closure {
         param: Option<TypedClosureParameter>,
         return_type: Type,
@@ -420,22 +458,44 @@ pub enum TypedExpression {
     Call {
         callee: Box<TypedExpression>,
         argument: Option<Box<TypedExpression>>,
+        /// Whether this applies the last argument of the enclosing function to itself in tail
+        /// position, so that a backend can turn the recursion into a loop
+        tail_call: bool,
+        /// Where the whole expression is written
+        span: Span,
         type_: Type,
     },
     Index {
         callee: Box<TypedExpression>,
         argument: Box<TypedExpression>,
+        /// Where the whole expression is written
+        span: Span,
         type_: Type,
     },
     Unary {
         operator: UnaryOperator,
         expression: Box<TypedExpression>,
+        /// The value of the expression if it is an integer constant, folded with the overflow policy
+        constant: Option<Literal>,
+        /// Where the whole expression is written
+        span: Span,
         type_: Type,
     },
     Binary {
         left: Box<TypedExpression>,
         operator: BinaryOperator,
         right: Box<TypedExpression>,
+        /// The value of the expression if it is an integer constant, folded with the overflow policy
+        constant: Option<Literal>,
+        /// Where the whole expression is written
+        span: Span,
+        type_: Type,
+    },
+    Cast {
+        expression: Box<TypedExpression>,
+        target: TypeAnnotation,
+        /// Where the whole expression is written
+        span: Span,
         type_: Type,
     },
     Block(Block),
@@ -460,7 +520,12 @@ pub enum TypedExpression {
     },
     For {
         identifier: String,
+        /// Where the identifier is written
+        identifier_span: Span,
         iterable: Box<TypedExpression>,
+        /// The `next` call of an iterable which isn't an array. The iterator it is called on is
+        /// read from the loop identifier before the identifier is bound to the element.
+        next: Option<Box<TypedExpression>>,
         body: Box<TypedExpression>,
         else_body: Option<Box<TypedExpression>>,
         type_: Type,
@@ -470,6 +535,24 @@ pub enum TypedExpression {
     Return(Option<Box<TypedExpression>>),
 }
 
+impl TypedExpression {
+    /// Where the expression is written. Expressions which don't track where they are written,
+    /// and the ones made by the type checker, have an empty span.
+    pub fn span(&self) -> Span {
+        match self {
+            TypedExpression::Member(Member::Identifier { span, .. })
+            | TypedExpression::Literal(_, span)
+            | TypedExpression::Call { span, .. }
+            | TypedExpression::Index { span, .. }
+            | TypedExpression::Unary { span, .. }
+            | TypedExpression::Binary { span, .. }
+            | TypedExpression::Cast { span, .. }
+            | TypedExpression::Match { span, .. } => *span,
+            _ => Span::default(),
+        }
+    }
+}
+
 impl Typed for TypedExpression {
     fn get_type(&self) -> Type {
         match self {
@@ -479,13 +562,15 @@ impl Typed for TypedExpression {
             TypedExpression::Match { type_, .. } => type_.clone(),
             TypedExpression::Assignment { type_, .. } => type_.clone(),
             TypedExpression::Member(member) => member.get_type(),
-            TypedExpression::Literal(literal) => literal.get_type(),
+            TypedExpression::Literal(literal, _) => literal.get_type(),
             TypedExpression::Tuple { type_, .. } => type_.clone(),
+            TypedExpression::Record { type_, .. } => type_.clone(),
             TypedExpression::Closure { type_, .. } => type_.clone(),
             TypedExpression::Call { type_, .. } => type_.clone(),
             TypedExpression::Index { type_, .. } => type_.clone(),
             TypedExpression::Unary { type_, .. } => type_.clone(),
             TypedExpression::Binary { type_, .. } => type_.clone(),
+            TypedExpression::Cast { type_, .. } => type_.clone(),
             TypedExpression::Block(Block { type_, .. }) => type_.clone(),
             TypedExpression::Loop { type_, .. } => type_.clone(),
             TypedExpression::While { type_, .. } => type_.clone(),
@@ -507,13 +592,15 @@ impl Typed for TypedExpression {
             TypedExpression::Match { type_, .. } => type_.clone(),
             TypedExpression::Assignment { type_, .. } => type_.clone(),
             TypedExpression::Member(member) => member.get_deep_type(),
-            TypedExpression::Literal(literal) => literal.get_deep_type(),
+            TypedExpression::Literal(literal, _) => literal.get_deep_type(),
             TypedExpression::Tuple { type_, .. } => type_.clone(),
+            TypedExpression::Record { type_, .. } => type_.clone(),
             TypedExpression::Closure { type_, .. } => type_.clone(),
             TypedExpression::Call { type_, .. } => type_.clone(),
             TypedExpression::Index { type_, .. } => type_.clone(),
             TypedExpression::Unary { type_, .. } => type_.clone(),
             TypedExpression::Binary { type_, .. } => type_.clone(),
+            TypedExpression::Cast { type_, .. } => type_.clone(),
             TypedExpression::Block(Block { type_, .. }) => type_.clone(),
             TypedExpression::Loop { type_, .. } => type_.clone(),
             TypedExpression::While { type_, .. } => type_.clone(),
@@ -586,7 +673,7 @@ impl Display for TypedExpression {
                 ..
             } => write!(f, "{} = {}", member, initializer),
             TypedExpression::Member(member) => write!(f, "{}", member),
-            TypedExpression::Literal(literal) => write!(f, "{}", literal),
+            TypedExpression::Literal(literal, _) => write!(f, "{}", literal),
             TypedExpression::Tuple { elements, .. } => {
                 write!(
                     f,
@@ -598,6 +685,20 @@ impl Display for TypedExpression {
                         .join(", ")
                 )
             }
+            TypedExpression::Record { fields, .. } => {
+                write!(
+                    f,
+                    "{{ {} }}",
+                    fields
+                        .iter()
+                        .map(|fi| match &fi.identifier {
+                            Some(identifier) => format!("{}: {}", identifier, fi.initializer),
+                            None => fi.initializer.to_string(),
+                        })
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                )
+            }
             TypedExpression::Closure {
                 param,
                 return_type,
@@ -639,6 +740,9 @@ impl Display for TypedExpression {
                 right,
                 ..
             } => write!(f, "{} {} {}", left, operator, right),
+            TypedExpression::Cast {
+                expression, target, ..
+            } => write!(f, "{} as {}", expression, target),
             TypedExpression::Block(block) => write!(f, "{}", block),
             TypedExpression::Drop { identifier, .. } => write!(f, "drop {}", identifier),
             TypedExpression::Print { value, .. } => write!(f, "print {}", value),
@@ -696,6 +800,14 @@ pub struct StructField {
     pub mutable: bool,
     pub identifier: String,
     pub type_: Type,
+    pub default: Option<TypedExpression>,
+}
+
+impl StructField {
+    /// Whether the field is a field of a tuple struct, which is named by its index
+    pub fn is_positional(&self) -> bool {
+        self.identifier.starts_with(|c: char| c.is_ascii_digit())
+    }
 }
 
 impl Display for StructField {
@@ -706,7 +818,13 @@ impl Display for StructField {
             if self.mutable { "mut " } else { "" },
             self.identifier,
             self.type_
-        )
+        )?;
+
+        if let Some(default) = &self.default {
+            write!(f, " = {}", default)?;
+        }
+
+        Ok(())
     }
 }
 
@@ -731,6 +849,8 @@ impl From<parser::AccessModifier> for AccessModifier {
 pub struct EnumMember {
     pub enum_name: TypeIdentifier,
     pub discriminant_name: String,
+    /// The value which tells the member apart from the other members of the enum
+    pub discriminant: i64,
     pub fields: Vec<EnumMemberField>,
     pub type_: Type,
 }
@@ -781,6 +901,7 @@ pub enum Literal {
     Struct {
         type_annotation: TypeAnnotation,
         field_initializers: Vec<FieldInitializer>,
+        spread: Option<Box<TypedExpression>>,
         type_: Type,
     },
     Enum {
@@ -871,7 +992,9 @@ impl Display for Literal {
                     .join(", ")
             ),
             Literal::Struct {
-                field_initializers, ..
+                field_initializers,
+                spread,
+                ..
             } => write!(
                 f,
                 "{{{}}}",
@@ -884,6 +1007,7 @@ impl Display for Literal {
                             fi.initializer.to_string()
                         }
                     })
+                    .chain(spread.iter().map(|spread| format!("..{}", spread)))
                     .collect::<Vec<String>>()
                     .join(", ")
             ),
@@ -938,6 +1062,8 @@ pub enum Member {
     Identifier {
         symbol: String,
         type_: Type,
+        /// Where the identifier is written
+        span: Span,
     },
     StaticMemberAccess {
         type_annotation: TypeAnnotation,
@@ -1104,6 +1230,55 @@ impl Display for BinaryOperator {
     }
 }
 
+impl BinaryOperator {
+    pub fn category(&self) -> BinaryOperatorCategory {
+        match self {
+            BinaryOperator::Add
+            | BinaryOperator::Subtract
+            | BinaryOperator::Multiply
+            | BinaryOperator::Divide
+            | BinaryOperator::Modulo => BinaryOperatorCategory::Arithmetic,
+            BinaryOperator::BitwiseAnd
+            | BinaryOperator::BitwiseOr
+            | BinaryOperator::BitwiseXor
+            | BinaryOperator::BitwiseLeftShift
+            | BinaryOperator::BitwiseRightShift => BinaryOperatorCategory::Bitwise,
+            BinaryOperator::LogicalAnd | BinaryOperator::LogicalOr => {
+                BinaryOperatorCategory::Logical
+            }
+            BinaryOperator::Equal | BinaryOperator::NotEqual => BinaryOperatorCategory::Equality,
+            BinaryOperator::LessThan
+            | BinaryOperator::LessThanOrEqual
+            | BinaryOperator::GreaterThan
+            | BinaryOperator::GreaterThanOrEqual => BinaryOperatorCategory::Comparison,
+            BinaryOperator::Range | BinaryOperator::RangeInclusive => BinaryOperatorCategory::Range,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BinaryOperatorCategory {
+    Arithmetic,
+    Comparison,
+    Equality,
+    Logical,
+    Bitwise,
+    Range,
+}
+
+impl Display for BinaryOperatorCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BinaryOperatorCategory::Arithmetic => write!(f, "arithmetic"),
+            BinaryOperatorCategory::Comparison => write!(f, "comparison"),
+            BinaryOperatorCategory::Equality => write!(f, "equality"),
+            BinaryOperatorCategory::Logical => write!(f, "logical"),
+            BinaryOperatorCategory::Bitwise => write!(f, "bitwise"),
+            BinaryOperatorCategory::Range => write!(f, "range"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct TypedMatchArm {
     pub pattern: Pattern,
@@ -0,0 +1,174 @@
+use std::cmp::Ordering;
+
+use super::{
+    ast::{
+        BinaryOperator, BinaryOperatorCategory, Literal, Member, Typed, TypedExpression,
+        TypedStatement,
+    },
+    constant::evaluate_constant,
+    decision_tree::Decision,
+    visit::{self, TypedVisitor},
+    OverflowPolicy, Type,
+};
+
+/// Collect warnings for comparisons whose result is known before the program runs, either because
+/// both operands are constants like `5 > 10`, or because both sides are the same variable like
+/// `x < x`. Floats are never warned about since `x == x` is false when `x` is NaN.
+pub fn find_constant_comparisons(
+    program: &TypedStatement,
+    overflow_policy: OverflowPolicy,
+) -> Vec<String> {
+    let mut checker = ConstantComparisons {
+        overflow_policy,
+        functions: vec![],
+        warnings: vec![],
+    };
+
+    checker.visit_statement(program);
+    checker.warnings
+}
+
+struct ConstantComparisons {
+    overflow_policy: OverflowPolicy,
+    /// The functions being checked, innermost last
+    functions: Vec<String>,
+    warnings: Vec<String>,
+}
+
+impl ConstantComparisons {
+    fn location(&self) -> String {
+        match self.functions.last() {
+            Some(function) => format!("function '{}'", function),
+            None => "the top level".to_string(),
+        }
+    }
+
+    fn check_comparison(
+        &mut self,
+        left: &TypedExpression,
+        operator: &BinaryOperator,
+        right: &TypedExpression,
+    ) {
+        if is_float(&left.get_type()) || is_float(&right.get_type()) {
+            return;
+        }
+
+        let ordering = match (self.constant(left), self.constant(right)) {
+            (Some(left), Some(right)) => compare(&left, &right),
+            _ if left == right && is_pure(left) => Some(Ordering::Equal),
+            _ => None,
+        };
+
+        let Some(ordering) = ordering else {
+            return;
+        };
+
+        let result = match operator {
+            BinaryOperator::Equal => ordering == Ordering::Equal,
+            BinaryOperator::NotEqual => ordering != Ordering::Equal,
+            BinaryOperator::LessThan => ordering == Ordering::Less,
+            BinaryOperator::LessThanOrEqual => ordering != Ordering::Greater,
+            BinaryOperator::GreaterThan => ordering == Ordering::Greater,
+            BinaryOperator::GreaterThanOrEqual => ordering != Ordering::Less,
+            _ => return,
+        };
+
+        self.warnings.push(format!(
+            "Comparison {} {} {} in {} is always {}",
+            left,
+            operator,
+            right,
+            self.location(),
+            result
+        ));
+    }
+
+    fn constant(&self, expression: &TypedExpression) -> Option<Literal> {
+        match expression {
+            TypedExpression::Literal(
+                literal @ (Literal::Bool(_) | Literal::Char(_) | Literal::String(_)),
+                _,
+            ) => Some(literal.clone()),
+            _ => evaluate_constant(expression, self.overflow_policy)
+                .ok()
+                .flatten(),
+        }
+    }
+}
+
+fn compare(left: &Literal, right: &Literal) -> Option<Ordering> {
+    match (left, right) {
+        (Literal::Int(left), Literal::Int(right)) => Some(left.cmp(right)),
+        (Literal::UInt(left), Literal::UInt(right)) => Some(left.cmp(right)),
+        (Literal::Bool(left), Literal::Bool(right)) => Some(left.cmp(right)),
+        (Literal::Char(left), Literal::Char(right)) => Some(left.cmp(right)),
+        (Literal::String(left), Literal::String(right)) => Some(left.cmp(right)),
+        _ => None,
+    }
+}
+
+fn is_float(type_: &Type) -> bool {
+    match type_ {
+        Type::Float => true,
+        Type::Literal { type_, .. } => is_float(type_),
+        Type::Substitution { actual_type, .. } => is_float(actual_type),
+        _ => false,
+    }
+}
+
+/// Whether the expression gives the same value every time it is evaluated in the same place
+fn is_pure(expression: &TypedExpression) -> bool {
+    match expression {
+        TypedExpression::Member(Member::Identifier { .. }) => true,
+        TypedExpression::Member(Member::MemberAccess { object, .. }) => is_pure(object),
+        _ => false,
+    }
+}
+
+impl TypedVisitor for ConstantComparisons {
+    fn visit_statement(&mut self, statement: &TypedStatement) {
+        match statement {
+            TypedStatement::FunctionDeclaration { identifier, .. } => {
+                self.functions.push(identifier.name().to_string());
+                visit::walk_statement(self, statement);
+                self.functions.pop();
+            }
+            _ => visit::walk_statement(self, statement),
+        }
+    }
+
+    fn visit_expression(&mut self, expression: &TypedExpression) {
+        if let TypedExpression::Binary {
+            left,
+            operator,
+            right,
+            ..
+        } = expression
+        {
+            if matches!(
+                operator.category(),
+                BinaryOperatorCategory::Comparison | BinaryOperatorCategory::Equality
+            ) {
+                self.check_comparison(left, operator, right);
+            }
+        }
+
+        visit::walk_expression(self, expression);
+    }
+
+    fn visit_decision(&mut self, decision: &Decision) {
+        match decision {
+            // The conditions of guards compare the matchee to the patterns, they aren't written
+            // by the user
+            Decision::Guard {
+                consequence,
+                alternative,
+                ..
+            } => {
+                self.visit_decision(consequence);
+                self.visit_decision(alternative);
+            }
+            _ => visit::walk_decision(self, decision),
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use super::{
+    get_field_by_name,
+    type_environment::{protocol_member_owner, TypeEnvironment},
+    Enum, EnumMember, Function, Parameter, Struct, StructField, Type,
+};
+
+/// Find what can be written after `object.` for an object of the given type, such as the
+/// fields of a struct and the methods of its implementations.
+///
+/// Fields come first in the order they are declared, followed by the methods sorted by name.
+/// Fields which aren't visible from `module_path` are left out. Methods are the functions of an
+/// implementation for the type which take `self`, including the ones of protocols it implements.
+pub fn member_completions(
+    object_type: &Type,
+    type_environment: &TypeEnvironment,
+    module_path: &[String],
+) -> Vec<(String, Type)> {
+    let object_type = object_type.clone().unsubstitute();
+
+    let mut completions: Vec<(String, Type)> = match &object_type {
+        Type::Struct(Struct {
+            type_identifier,
+            fields,
+        })
+        | Type::Enum(Enum {
+            type_identifier,
+            shared_fields: fields,
+            ..
+        }) => visible_fields(
+            type_identifier.name(),
+            fields,
+            type_environment,
+            module_path,
+        ),
+        Type::EnumMember(EnumMember {
+            enum_name, fields, ..
+        }) => {
+            let mut completions =
+                visible_fields(enum_name.name(), fields, type_environment, module_path);
+
+            // The members of an enum have the shared fields of the enum as well
+            if let Some(Type::Enum(Enum { shared_fields, .. })) =
+                type_environment.get_type(enum_name.name())
+            {
+                for field in visible_fields(
+                    enum_name.name(),
+                    &shared_fields,
+                    type_environment,
+                    module_path,
+                ) {
+                    if get_field_by_name(fields, &field.0).is_none() {
+                        completions.push(field);
+                    }
+                }
+            }
+
+            completions
+        }
+        Type::Record(fields) => fields.clone(),
+        _ => vec![],
+    };
+
+    let mut methods: Vec<(String, Type)> = type_environment
+        .get_static_members(&object_type.type_annotation())
+        .into_iter()
+        .chain(
+            type_environment
+                .get_implementations(&object_type)
+                .into_iter()
+                .flat_map(|protocol_name| {
+                    type_environment.get_static_members(&protocol_member_owner(
+                        &protocol_name,
+                        object_type.type_annotation(),
+                    ))
+                }),
+        )
+        .filter(|(_, method_type)| takes_self(method_type))
+        .filter(|(name, _)| !completions.iter().any(|(field, _)| field == name))
+        .collect();
+
+    methods.sort_by(|(a, _), (b, _)| a.cmp(b));
+    methods.dedup_by(|(a, _), (b, _)| a == b);
+
+    completions.extend(methods);
+    completions
+}
+
+fn visible_fields(
+    owner: &str,
+    fields: &[StructField],
+    type_environment: &TypeEnvironment,
+    module_path: &[String],
+) -> Vec<(String, Type)> {
+    fields
+        .iter()
+        .filter(|field| {
+            type_environment
+                .is_visible_from(&format!("{}.{}", owner, field.field_name), module_path)
+        })
+        .map(|field| (field.field_name.clone(), field.field_type.clone()))
+        .collect()
+}
+
+fn takes_self(method_type: &Type) -> bool {
+    matches!(
+        method_type,
+        Type::Function(Function {
+            param: Some(Parameter { identifier, .. }),
+            ..
+        }) if identifier == "self"
+    )
+}
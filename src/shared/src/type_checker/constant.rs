@@ -0,0 +1,712 @@
+use std::{collections::HashMap, fmt::Display};
+
+use num_traits::{CheckedRem, PrimInt, SaturatingMul, WrappingAdd, WrappingMul, WrappingSub};
+
+use crate::{diagnostic::Diagnostic, span::Span};
+
+use super::{
+    ast::{BinaryOperator, Block, Literal, Member, TypedExpression, TypedStatement, UnaryOperator},
+    decision_tree::Decision,
+    transform::{self, TypedTransformer},
+    OverflowPolicy, Type,
+};
+
+/// Evaluate an integer constant expression using the given overflow policy.
+///
+/// Only the expression itself is evaluated. The values of its operands are the literals and the
+/// values folded into unary and binary expressions when they were checked.
+///
+/// Returns `Ok(None)` if the expression is not a constant expression.
+/// Returns an error if the expression overflows and the policy is `OverflowPolicy::Checked`.
+pub fn evaluate_constant(
+    expression: &TypedExpression,
+    overflow_policy: OverflowPolicy,
+) -> Result<Option<Literal>, String> {
+    match expression {
+        TypedExpression::Unary {
+            operator,
+            expression,
+            ..
+        } => fold_unary(operator, expression, overflow_policy),
+        TypedExpression::Binary {
+            left,
+            operator,
+            right,
+            ..
+        } => fold_binary(left, operator, right, overflow_policy),
+        expression => Ok(constant_value(expression)),
+    }
+}
+
+/// Fold a unary expression whose operand is already checked
+pub fn fold_unary(
+    operator: &UnaryOperator,
+    operand: &TypedExpression,
+    overflow_policy: OverflowPolicy,
+) -> Result<Option<Literal>, String> {
+    let Some(operand) = constant_value(operand) else {
+        return Ok(None);
+    };
+
+    evaluate_constant_unary(operator, operand, overflow_policy)
+}
+
+/// Fold a binary expression whose operands are already checked
+pub fn fold_binary(
+    left: &TypedExpression,
+    operator: &BinaryOperator,
+    right: &TypedExpression,
+    overflow_policy: OverflowPolicy,
+) -> Result<Option<Literal>, String> {
+    let (Some(left), Some(right)) = (constant_value(left), constant_value(right)) else {
+        return Ok(None);
+    };
+
+    evaluate_constant_binary(left, operator, right, overflow_policy)
+}
+
+/// The integer value of a literal, or the value folded into a unary or binary expression
+pub fn constant_value(expression: &TypedExpression) -> Option<Literal> {
+    match expression {
+        TypedExpression::Literal(literal @ (Literal::Int(_) | Literal::UInt(_)), _) => {
+            Some(literal.clone())
+        }
+        TypedExpression::Unary { constant, .. } | TypedExpression::Binary { constant, .. } => {
+            constant.clone()
+        }
+        _ => None,
+    }
+}
+
+/// Reject an integer division or remainder whose divisor is a constant zero, like `x / 0` or
+/// `x % (2 - 2)`, which would always fail when evaluated.
+pub fn check_division_by_zero(expression: &TypedExpression) -> Result<(), String> {
+    let TypedExpression::Binary {
+        left,
+        operator: operator @ (BinaryOperator::Divide | BinaryOperator::Modulo),
+        right,
+        ..
+    } = expression
+    else {
+        return Ok(());
+    };
+
+    if !matches!(
+        constant_value(right),
+        Some(Literal::Int(0) | Literal::UInt(0))
+    ) {
+        return Ok(());
+    }
+
+    Err(match operator {
+        BinaryOperator::Modulo => {
+            format!("Cannot take the remainder of {} divided by zero", left)
+        }
+        _ => format!("Cannot divide {} by zero", left),
+    })
+}
+
+fn evaluate_constant_unary(
+    operator: &UnaryOperator,
+    operand: Literal,
+    overflow_policy: OverflowPolicy,
+) -> Result<Option<Literal>, String> {
+    match (operator, operand) {
+        (UnaryOperator::Identity, operand) => Ok(Some(operand)),
+        (UnaryOperator::Negate, Literal::Int(v)) => {
+            let result = match overflow_policy {
+                OverflowPolicy::Checked => v
+                    .checked_neg()
+                    .ok_or(format!("Constant expression -{} overflows Int", v))?,
+                OverflowPolicy::Wrapping => v.wrapping_neg(),
+                OverflowPolicy::Saturating => v.saturating_neg(),
+            };
+
+            Ok(Some(Literal::Int(result)))
+        }
+        _ => Ok(None),
+    }
+}
+
+fn evaluate_constant_binary(
+    left: Literal,
+    operator: &BinaryOperator,
+    right: Literal,
+    overflow_policy: OverflowPolicy,
+) -> Result<Option<Literal>, String> {
+    match (left, right) {
+        (Literal::Int(left), Literal::Int(right)) => {
+            evaluate_constant_integer(left, operator, right, overflow_policy, "Int")
+                .map(|v| v.map(Literal::Int))
+        }
+        (Literal::UInt(left), Literal::UInt(right)) => {
+            evaluate_constant_integer(left, operator, right, overflow_policy, "UInt")
+                .map(|v| v.map(Literal::UInt))
+        }
+        _ => Ok(None),
+    }
+}
+
+fn evaluate_constant_integer<T>(
+    left: T,
+    operator: &BinaryOperator,
+    right: T,
+    overflow_policy: OverflowPolicy,
+    type_name: &str,
+) -> Result<Option<T>, String>
+where
+    T: PrimInt + CheckedRem + WrappingAdd + WrappingSub + WrappingMul + SaturatingMul + Display,
+{
+    // Division by zero is reported by check_division_by_zero
+    if matches!(operator, BinaryOperator::Divide | BinaryOperator::Modulo) && right.is_zero() {
+        return Ok(None);
+    }
+
+    let result = match (operator, overflow_policy) {
+        (BinaryOperator::Add, OverflowPolicy::Checked) => left.checked_add(&right),
+        (BinaryOperator::Add, OverflowPolicy::Wrapping) => Some(left.wrapping_add(&right)),
+        (BinaryOperator::Add, OverflowPolicy::Saturating) => Some(left.saturating_add(right)),
+        (BinaryOperator::Subtract, OverflowPolicy::Checked) => left.checked_sub(&right),
+        (BinaryOperator::Subtract, OverflowPolicy::Wrapping) => Some(left.wrapping_sub(&right)),
+        (BinaryOperator::Subtract, OverflowPolicy::Saturating) => Some(left.saturating_sub(right)),
+        (BinaryOperator::Multiply, OverflowPolicy::Checked) => left.checked_mul(&right),
+        (BinaryOperator::Multiply, OverflowPolicy::Wrapping) => Some(left.wrapping_mul(&right)),
+        (BinaryOperator::Multiply, OverflowPolicy::Saturating) => Some(left.saturating_mul(&right)),
+        // The only overflowing division is the minimum value divided by -1
+        (BinaryOperator::Divide, OverflowPolicy::Checked) => left.checked_div(&right),
+        (BinaryOperator::Divide, OverflowPolicy::Wrapping) => {
+            Some(left.checked_div(&right).unwrap_or(T::min_value()))
+        }
+        (BinaryOperator::Divide, OverflowPolicy::Saturating) => {
+            Some(left.checked_div(&right).unwrap_or(T::max_value()))
+        }
+        (BinaryOperator::Modulo, OverflowPolicy::Checked) => left.checked_rem(&right),
+        (BinaryOperator::Modulo, _) => Some(left.checked_rem(&right).unwrap_or(T::zero())),
+        (BinaryOperator::BitwiseAnd, _) => Some(left & right),
+        (BinaryOperator::BitwiseOr, _) => Some(left | right),
+        (BinaryOperator::BitwiseXor, _) => Some(left ^ right),
+        _ => return Ok(None),
+    };
+
+    match result {
+        Some(result) => Ok(Some(result)),
+        None => Err(format!(
+            "Constant expression {} {} {} overflows {}",
+            left, operator, right, type_name
+        )),
+    }
+}
+
+/// The value of a constant expression
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConstValue {
+    Unit,
+    Int(i64),
+    UInt(u64),
+    Float(f64),
+    String(String),
+    Char(char),
+    Bool(bool),
+}
+
+impl ConstValue {
+    fn from_literal(literal: &Literal) -> Option<ConstValue> {
+        match literal {
+            Literal::Unit => Some(ConstValue::Unit),
+            Literal::Int(v) => Some(ConstValue::Int(*v)),
+            Literal::UInt(v) => Some(ConstValue::UInt(*v)),
+            Literal::Float(v) => Some(ConstValue::Float(*v)),
+            Literal::String(v) => Some(ConstValue::String(v.clone())),
+            Literal::Char(v) => Some(ConstValue::Char(*v)),
+            Literal::Bool(v) => Some(ConstValue::Bool(*v)),
+            _ => None,
+        }
+    }
+
+    pub fn into_literal(self) -> Literal {
+        match self {
+            ConstValue::Unit => Literal::Unit,
+            ConstValue::Int(v) => Literal::Int(v),
+            ConstValue::UInt(v) => Literal::UInt(v),
+            ConstValue::Float(v) => Literal::Float(v),
+            ConstValue::String(v) => Literal::String(v),
+            ConstValue::Char(v) => Literal::Char(v),
+            ConstValue::Bool(v) => Literal::Bool(v),
+        }
+    }
+}
+
+impl Display for ConstValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.clone().into_literal())
+    }
+}
+
+/// Why a constant expression has no value
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EvalErrorKind {
+    /// Some part of the expression, like a call or a read of a variable, is only known when the
+    /// program runs
+    NotConstant,
+    /// The expression is constant, but evaluating it fails, like when it overflows or divides by
+    /// zero
+    Failed,
+}
+
+/// A constant expression which has no value, and where the part of it which failed is written
+#[derive(Debug, Clone, PartialEq)]
+pub struct EvalError {
+    pub kind: EvalErrorKind,
+    pub message: String,
+    pub span: Span,
+}
+
+impl EvalError {
+    fn not_constant(message: String, span: Span) -> EvalError {
+        EvalError {
+            kind: EvalErrorKind::NotConstant,
+            message,
+            span,
+        }
+    }
+
+    fn failed(message: String, span: Span) -> EvalError {
+        EvalError {
+            kind: EvalErrorKind::Failed,
+            message,
+            span,
+        }
+    }
+}
+
+impl From<EvalError> for Diagnostic {
+    fn from(error: EvalError) -> Self {
+        Diagnostic::new(error.message, error.span)
+    }
+}
+
+/// The constants a constant expression can read, and how it handles overflow
+#[derive(Debug, Clone, Default)]
+pub struct ConstEnvironment {
+    constants: HashMap<String, ConstValue>,
+    overflow_policy: OverflowPolicy,
+}
+
+impl ConstEnvironment {
+    pub fn new(overflow_policy: OverflowPolicy) -> ConstEnvironment {
+        ConstEnvironment {
+            constants: HashMap::new(),
+            overflow_policy,
+        }
+    }
+
+    /// Let constant expressions read `name` as `value`
+    pub fn declare(&mut self, name: impl Into<String>, value: ConstValue) {
+        self.constants.insert(name.into(), value);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&ConstValue> {
+        self.constants.get(name)
+    }
+}
+
+/// Evaluate a constant expression of any literal type, like `2 * 3 + 1` or `'a' < 'b' && !false`.
+///
+/// The expression may only be made of literals, operators, casts between primitive types and
+/// reads of the constants in `environment`. Every part of it is evaluated, so `false && f()` is
+/// rejected for calling `f` even though the call is never needed.
+pub fn eval_const(
+    expression: &TypedExpression,
+    environment: &ConstEnvironment,
+) -> Result<ConstValue, EvalError> {
+    match expression {
+        TypedExpression::Literal(literal, span) => ConstValue::from_literal(literal).ok_or(
+            EvalError::not_constant(format!("{} is not a constant expression", literal), *span),
+        ),
+        TypedExpression::Member(Member::Identifier { symbol, span, .. }) => environment
+            .get(symbol)
+            .cloned()
+            .ok_or(EvalError::not_constant(
+                format!("{} is not a constant", symbol),
+                *span,
+            )),
+        TypedExpression::Unary {
+            operator,
+            expression: operand,
+            span,
+            ..
+        } => {
+            let operand = eval_const(operand, environment)?;
+            eval_const_unary(operator, operand, environment.overflow_policy, *span)
+        }
+        TypedExpression::Binary {
+            left,
+            operator,
+            right,
+            span,
+            ..
+        } => {
+            let left = eval_const(left, environment)?;
+            let right = eval_const(right, environment)?;
+            eval_const_binary(left, operator, right, environment.overflow_policy, *span)
+        }
+        TypedExpression::Cast {
+            expression: operand,
+            span,
+            type_,
+            ..
+        } => {
+            let operand = eval_const(operand, environment)?;
+            eval_const_cast(operand, type_, *span)
+        }
+        TypedExpression::Call { .. } => Err(EvalError::not_constant(
+            format!("Cannot call {} in a constant expression", expression),
+            expression.span(),
+        )),
+        expression => Err(EvalError::not_constant(
+            format!("{} is not a constant expression", expression),
+            expression.span(),
+        )),
+    }
+}
+
+fn eval_const_unary(
+    operator: &UnaryOperator,
+    operand: ConstValue,
+    overflow_policy: OverflowPolicy,
+    span: Span,
+) -> Result<ConstValue, EvalError> {
+    match (operator, operand) {
+        (UnaryOperator::Identity, operand) => Ok(operand),
+        (UnaryOperator::Negate, ConstValue::Float(v)) => Ok(ConstValue::Float(-v)),
+        (UnaryOperator::LogicalNot, ConstValue::Bool(v)) => Ok(ConstValue::Bool(!v)),
+        (UnaryOperator::BitwiseNot, ConstValue::Int(v)) => Ok(ConstValue::Int(!v)),
+        (UnaryOperator::BitwiseNot, ConstValue::UInt(v)) => Ok(ConstValue::UInt(!v)),
+        (operator, operand) => {
+            match evaluate_constant_unary(operator, operand.clone().into_literal(), overflow_policy)
+            {
+                Ok(Some(literal)) => Ok(ConstValue::from_literal(&literal)
+                    .expect("Folding an integer makes an integer")),
+                Ok(None) => Err(EvalError::failed(
+                    format!("Cannot apply {} to {}", operator, operand),
+                    span,
+                )),
+                Err(error) => Err(EvalError::failed(error, span)),
+            }
+        }
+    }
+}
+
+fn eval_const_binary(
+    left: ConstValue,
+    operator: &BinaryOperator,
+    right: ConstValue,
+    overflow_policy: OverflowPolicy,
+    span: Span,
+) -> Result<ConstValue, EvalError> {
+    if let Some(ordering) = compare_const(&left, &right) {
+        let result = match operator {
+            BinaryOperator::Equal => Some(ordering.is_eq()),
+            BinaryOperator::NotEqual => Some(ordering.is_ne()),
+            BinaryOperator::LessThan => Some(ordering.is_lt()),
+            BinaryOperator::LessThanOrEqual => Some(ordering.is_le()),
+            BinaryOperator::GreaterThan => Some(ordering.is_gt()),
+            BinaryOperator::GreaterThanOrEqual => Some(ordering.is_ge()),
+            _ => None,
+        };
+
+        if let Some(result) = result {
+            return Ok(ConstValue::Bool(result));
+        }
+    }
+
+    let result = match (&left, operator, &right) {
+        (ConstValue::Bool(l), BinaryOperator::LogicalAnd, ConstValue::Bool(r)) => {
+            Some(ConstValue::Bool(*l && *r))
+        }
+        (ConstValue::Bool(l), BinaryOperator::LogicalOr, ConstValue::Bool(r)) => {
+            Some(ConstValue::Bool(*l || *r))
+        }
+        (ConstValue::Float(l), operator, ConstValue::Float(r)) => match operator {
+            BinaryOperator::Add => Some(ConstValue::Float(l + r)),
+            BinaryOperator::Subtract => Some(ConstValue::Float(l - r)),
+            BinaryOperator::Multiply => Some(ConstValue::Float(l * r)),
+            BinaryOperator::Divide => Some(ConstValue::Float(l / r)),
+            BinaryOperator::Modulo => Some(ConstValue::Float(l % r)),
+            _ => None,
+        },
+        (ConstValue::Int(_), _, ConstValue::Int(_))
+        | (ConstValue::UInt(_), _, ConstValue::UInt(_)) => {
+            if matches!(operator, BinaryOperator::Divide | BinaryOperator::Modulo)
+                && matches!(right, ConstValue::Int(0) | ConstValue::UInt(0))
+            {
+                return Err(EvalError::failed(
+                    format!("Cannot divide {} by zero", left),
+                    span,
+                ));
+            }
+
+            evaluate_constant_binary(
+                left.clone().into_literal(),
+                operator,
+                right.clone().into_literal(),
+                overflow_policy,
+            )
+            .map_err(|error| EvalError::failed(error, span))?
+            .and_then(|literal| ConstValue::from_literal(&literal))
+        }
+        _ => None,
+    };
+
+    result.ok_or(EvalError::failed(
+        format!("Cannot apply {} to {} and {}", operator, left, right),
+        span,
+    ))
+}
+
+/// How two constants of the same type are ordered, or None when they can't be compared
+fn compare_const(left: &ConstValue, right: &ConstValue) -> Option<std::cmp::Ordering> {
+    match (left, right) {
+        (ConstValue::Unit, ConstValue::Unit) => Some(std::cmp::Ordering::Equal),
+        (ConstValue::Int(l), ConstValue::Int(r)) => l.partial_cmp(r),
+        (ConstValue::UInt(l), ConstValue::UInt(r)) => l.partial_cmp(r),
+        (ConstValue::Float(l), ConstValue::Float(r)) => l.partial_cmp(r),
+        (ConstValue::String(l), ConstValue::String(r)) => l.partial_cmp(r),
+        (ConstValue::Char(l), ConstValue::Char(r)) => l.partial_cmp(r),
+        (ConstValue::Bool(l), ConstValue::Bool(r)) => l.partial_cmp(r),
+        _ => None,
+    }
+}
+
+/// Cast a constant the way the interpreter casts values
+fn eval_const_cast(operand: ConstValue, type_: &Type, span: Span) -> Result<ConstValue, EvalError> {
+    let result = match (operand, type_) {
+        (ConstValue::Int(v), Type::UInt) => ConstValue::UInt(v as u64),
+        (ConstValue::Int(v), Type::Float) => ConstValue::Float(v as f64),
+        (ConstValue::UInt(v), Type::Int) => ConstValue::Int(v as i64),
+        (ConstValue::UInt(v), Type::Float) => ConstValue::Float(v as f64),
+        (ConstValue::Float(v), Type::Int) => ConstValue::Int(v as i64),
+        (ConstValue::Float(v), Type::UInt) => ConstValue::UInt(v as u64),
+        (ConstValue::Char(c), Type::Int) => ConstValue::Int(c as i64),
+        (ConstValue::Char(c), Type::UInt) => ConstValue::UInt(c as u64),
+        (ConstValue::Int(v), Type::Char) => u32::try_from(v)
+            .ok()
+            .and_then(char::from_u32)
+            .map(ConstValue::Char)
+            .ok_or(EvalError::failed(
+                format!("Cannot cast {} to a char", v),
+                span,
+            ))?,
+        (ConstValue::UInt(v), Type::Char) => u32::try_from(v)
+            .ok()
+            .and_then(char::from_u32)
+            .map(ConstValue::Char)
+            .ok_or(EvalError::failed(
+                format!("Cannot cast {} to a char", v),
+                span,
+            ))?,
+        (value, _) => value,
+    };
+
+    Ok(result)
+}
+
+/// Replace reads of immutable bindings like `let x = 5` with their constant value, and fold the
+/// integer expressions which become constant because of it.
+///
+/// Bindings declared `mut` or without an initializer are left alone, since they can be assigned.
+/// Returns an error if a folded expression overflows and the policy is `OverflowPolicy::Checked`.
+pub fn propagate_constants(
+    program: TypedStatement,
+    overflow_policy: OverflowPolicy,
+) -> Result<TypedStatement, String> {
+    let mut propagation = ConstantPropagation {
+        scopes: vec![HashMap::new()],
+        overflow_policy,
+        error: None,
+    };
+
+    let program = propagation.transform_statement(program);
+
+    match propagation.error {
+        Some(error) => Err(error),
+        None => Ok(program),
+    }
+}
+
+struct ConstantPropagation {
+    /// The value of every binding in scope, or `None` for bindings which aren't constant
+    scopes: Vec<HashMap<String, Option<Literal>>>,
+    overflow_policy: OverflowPolicy,
+    error: Option<String>,
+}
+
+impl ConstantPropagation {
+    /// Transform in a new scope where `shadowed` aren't constant
+    fn with_scope<T>(&mut self, shadowed: &[&str], f: impl FnOnce(&mut Self) -> T) -> T {
+        self.scopes.push(
+            shadowed
+                .iter()
+                .map(|name| (name.to_string(), None))
+                .collect(),
+        );
+
+        let result = f(self);
+        self.scopes.pop();
+        result
+    }
+
+    fn declare(&mut self, name: &str, value: Option<Literal>) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name.to_owned(), value);
+        }
+    }
+
+    fn lookup(&self, name: &str) -> Option<Literal> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name))
+            .cloned()
+            .flatten()
+    }
+
+    fn fold(&mut self, expression: TypedExpression) -> TypedExpression {
+        // A propagated constant can make the divisor zero
+        if let Err(error) = check_division_by_zero(&expression) {
+            self.error.get_or_insert(error);
+            return expression;
+        }
+
+        match evaluate_constant(&expression, self.overflow_policy) {
+            Ok(Some(literal)) => TypedExpression::Literal(literal, expression.span()),
+            Ok(None) => expression,
+            Err(error) => {
+                self.error.get_or_insert(error);
+                expression
+            }
+        }
+    }
+}
+
+fn is_constant(literal: &Literal) -> bool {
+    matches!(
+        literal,
+        Literal::Int(_)
+            | Literal::UInt(_)
+            | Literal::Float(_)
+            | Literal::String(_)
+            | Literal::Char(_)
+            | Literal::Bool(_)
+    )
+}
+
+impl TypedTransformer for ConstantPropagation {
+    fn transform_statement(&mut self, statement: TypedStatement) -> TypedStatement {
+        match statement {
+            TypedStatement::FunctionDeclaration { ref param, .. } => {
+                let param = param.as_ref().map(|param| param.identifier.clone());
+                let shadowed: Vec<&str> = param.iter().map(|param| param.as_str()).collect();
+
+                self.with_scope(&shadowed, |propagation| {
+                    transform::fold_statement(propagation, statement)
+                })
+            }
+            statement => transform::fold_statement(self, statement),
+        }
+    }
+
+    fn transform_expression(&mut self, expression: TypedExpression) -> TypedExpression {
+        match expression {
+            TypedExpression::Member(Member::Identifier {
+                ref symbol, span, ..
+            }) => match self.lookup(symbol) {
+                Some(literal) => TypedExpression::Literal(literal, span),
+                None => expression,
+            },
+            TypedExpression::VariableDeclaration { .. } => {
+                let TypedExpression::VariableDeclaration {
+                    mutable,
+                    type_annotation,
+                    pattern,
+                    pattern_span,
+                    initializer,
+                    type_,
+                } = transform::fold_expression(self, expression)
+                else {
+                    unreachable!("A variable declaration folds to a variable declaration");
+                };
+
+                let value = match (mutable, &initializer) {
+                    (false, Some(initializer)) => match initializer.as_ref() {
+                        TypedExpression::Literal(literal, _) if is_constant(literal) => {
+                            Some(literal.clone())
+                        }
+                        _ => None,
+                    },
+                    _ => None,
+                };
+
+                for name in pattern.variables() {
+                    self.declare(name, value.clone());
+                }
+
+                TypedExpression::VariableDeclaration {
+                    mutable,
+                    type_annotation,
+                    pattern,
+                    pattern_span,
+                    initializer,
+                    type_,
+                }
+            }
+            TypedExpression::Closure { ref param, .. } => {
+                let param = param.as_ref().map(|param| param.identifier.clone());
+                let shadowed: Vec<&str> = param.iter().map(|param| param.as_str()).collect();
+
+                self.with_scope(&shadowed, |propagation| {
+                    transform::fold_expression(propagation, expression)
+                })
+            }
+            TypedExpression::For { ref identifier, .. } => {
+                let identifier = identifier.clone();
+
+                self.with_scope(&[&identifier], |propagation| {
+                    transform::fold_expression(propagation, expression)
+                })
+            }
+            TypedExpression::Unary { .. } | TypedExpression::Binary { .. } => {
+                let expression = transform::fold_expression(self, expression);
+                self.fold(expression)
+            }
+            expression => transform::fold_expression(self, expression),
+        }
+    }
+
+    fn transform_block(&mut self, block: Block) -> Block {
+        self.with_scope(&[], |propagation| transform::fold_block(propagation, block))
+    }
+
+    fn transform_decision(&mut self, decision: Decision) -> Decision {
+        let Decision::Switch {
+            variable, cases, ..
+        } = &decision
+        else {
+            return transform::fold_decision(self, decision);
+        };
+
+        // Every name a case binds is shadowed in all of them, which only gives up some folding
+        let mut shadowed = vec![variable.identifier.clone()];
+
+        for case in cases {
+            shadowed.extend(case.arguments.iter().map(|a| a.identifier.clone()));
+            shadowed.extend(case.pattern.variables().into_iter().map(str::to_owned));
+        }
+
+        let shadowed: Vec<&str> = shadowed.iter().map(String::as_str).collect();
+
+        self.with_scope(&shadowed, |propagation| {
+            transform::fold_decision(propagation, decision)
+        })
+    }
+}
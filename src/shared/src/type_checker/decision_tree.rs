@@ -1,11 +1,13 @@
 use std::fmt::Display;
 
 use crate::{
+    parser::{Expression, Literal},
+    span::Span,
     type_checker::{
         ast::{BinaryOperator, Member, Typed},
         expressions::check_type,
         get_field_by_name, type_annotation_equals, type_equals, type_equals_coerce, Enum,
-        EnumMember, Struct, Type,
+        EnumMember, Struct, StructField, Type, Union,
     },
     types::TypeAnnotation,
 };
@@ -42,6 +44,38 @@ pub enum Pattern {
     Range(Box<Pattern>, Box<Pattern>, bool),
 }
 
+impl Pattern {
+    /// The names of the variables the pattern binds
+    pub fn variables(&self) -> Vec<&str> {
+        match self {
+            Pattern::Variable(name) => vec![name],
+            Pattern::Constructor(Constructor::Struct { field_patterns, .. }) => field_patterns
+                .iter()
+                .flat_map(|field_pattern| field_pattern.pattern.variables())
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    /// The literal type of the value a literal pattern matches, like `#Int: 1` for `1`
+    pub fn literal_type(&self) -> Option<Type> {
+        let (name, type_) = match self {
+            Pattern::Bool(v) => (v.to_string(), Type::Bool),
+            Pattern::Int(v) => (v.to_string(), Type::Int),
+            Pattern::UInt(v) => (v.to_string(), Type::UInt),
+            Pattern::Float(v) => (v.to_string(), Type::Float),
+            Pattern::Char(v) => (format!("'{}'", v), Type::Char),
+            Pattern::String(v) => (format!("\"{}\"", v), Type::String),
+            _ => return None,
+        };
+
+        Some(Type::Literal {
+            name,
+            type_: Box::new(type_),
+        })
+    }
+}
+
 impl Display for Pattern {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -106,7 +140,7 @@ pub enum Decision {
         type_: Type,
     },
     Switch {
-        variable: Variable,
+        variable: Box<Variable>,
         cases: Vec<Case>,
         fallback: Box<Decision>,
         type_: Type,
@@ -128,6 +162,84 @@ impl Typed for Decision {
     }
 }
 
+impl Decision {
+    /// Render the decision tree as a Graphviz DOT digraph.
+    /// Every decision becomes a node, and edges are labeled with the case pattern or guard outcome.
+    pub fn to_dot(&self) -> String {
+        let mut lines = vec!["digraph decision_tree {".to_string()];
+        let mut next_id = 0;
+
+        dot_node(self, &mut lines, &mut next_id);
+
+        lines.push("}".to_string());
+        lines.join("\n")
+    }
+}
+
+fn dot_node(decision: &Decision, lines: &mut Vec<String>, next_id: &mut usize) -> usize {
+    let id = *next_id;
+    *next_id += 1;
+
+    let (label, shape) = match decision {
+        Decision::Success { expression, .. } => (expression.to_string(), "box"),
+        Decision::Failure { error_message } => (error_message.clone(), "octagon"),
+        Decision::Guard { condition, .. } => (format!("if {}", condition), "diamond"),
+        Decision::Switch { variable, .. } => (format!("switch {}", variable.identifier), "ellipse"),
+    };
+
+    lines.push(format!(
+        "    n{} [label={}, shape={}];",
+        id,
+        dot_string(&label),
+        shape
+    ));
+
+    let mut edges = vec![];
+
+    match decision {
+        Decision::Success { .. } | Decision::Failure { .. } => {}
+        Decision::Guard {
+            consequence,
+            alternative,
+            ..
+        } => {
+            edges.push(("true".to_string(), consequence.as_ref()));
+            edges.push(("false".to_string(), alternative.as_ref()));
+        }
+        Decision::Switch {
+            cases, fallback, ..
+        } => {
+            for case in cases {
+                edges.push((case.pattern.to_string(), &case.body));
+            }
+
+            edges.push(("fallback".to_string(), fallback.as_ref()));
+        }
+    }
+
+    for (label, child) in edges {
+        let child_id = dot_node(child, lines, next_id);
+
+        lines.push(format!(
+            "    n{} -> n{} [label={}];",
+            id,
+            child_id,
+            dot_string(&label)
+        ));
+    }
+
+    id
+}
+
+fn dot_string(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+
+    format!("\"{}\"", escaped)
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Case {
     pub pattern: Pattern,
@@ -150,6 +262,26 @@ pub struct Variable {
     pub identifier: String,
     pub accessor: Accessor,
     pub type_: Type,
+    /// The fields the variable was extracted from, starting at the matched value
+    pub provenance: Vec<Projection>,
+}
+
+impl Variable {
+    /// Where the variable came from in the matched value, like `scrutinee.Circle.radius`
+    pub fn provenance_path(&self) -> String {
+        self.provenance
+            .iter()
+            .fold("scrutinee".to_string(), |path, projection| {
+                format!("{}.{}.{}", path, projection.constructor, projection.field)
+            })
+    }
+}
+
+/// A field of a constructor, which a value was extracted from
+#[derive(Debug, Clone, PartialEq)]
+pub struct Projection {
+    pub constructor: String,
+    pub field: String,
 }
 
 impl Typed for Variable {
@@ -168,11 +300,367 @@ pub enum Accessor {
     Environment,
 }
 
+/// Lower the leading literal arms of a match on a union to one switch over its literals.
+///
+/// Every literal has to be a member of the union. The arms after the literals make up the
+/// fallback, and when there are none the literals have to cover the whole union.
+fn create_union_switch(
+    matchee: TypedExpression,
+    union: &Union,
+    arms: Vec<TypedMatchArm>,
+    discovered_types: &Vec<DiscoveredType>,
+    mut body_type: Option<Type>,
+    provenance: &[Projection],
+) -> Result<Decision, String> {
+    let literal_arms = arms
+        .iter()
+        .take_while(|arm| arm.pattern.literal_type().is_some())
+        .count();
+
+    let mut cases: Vec<Case> = vec![];
+    let mut matched: Vec<Type> = vec![];
+
+    for arm in &arms[..literal_arms] {
+        let literal = arm
+            .pattern
+            .literal_type()
+            .expect("literal arms have a literal type");
+
+        if !union.literals.contains(&literal) {
+            return Err(format!(
+                "Pattern {} is not a member of union '{}'",
+                literal_name(&literal),
+                union.type_identifier
+            ));
+        }
+
+        let expression = check_type(
+            &arm.expression,
+            discovered_types,
+            arm.type_environment.clone(),
+            None,
+        )?;
+
+        let type_ = expression.get_type();
+
+        if let Some(body_type) = &body_type {
+            if !type_equals_coerce(body_type, &type_) {
+                return Err(format!("Expected type {:?} but got {:?}", body_type, type_));
+            }
+        }
+
+        body_type.get_or_insert(type_.clone());
+
+        // A repeated literal is already matched by the earlier arm
+        if matched.contains(&literal) {
+            continue;
+        }
+
+        matched.push(literal);
+
+        cases.push(Case {
+            pattern: arm.pattern.clone(),
+            arguments: vec![],
+            body: Decision::Success {
+                expression: Box::new(expression),
+                type_: type_.clone(),
+            },
+        });
+    }
+
+    let type_ = body_type.clone().unwrap_or(Type::Unknown);
+    let rest: Vec<TypedMatchArm> = arms.into_iter().skip(literal_arms).collect();
+
+    if rest.is_empty() {
+        let missing: Vec<String> = union
+            .literals
+            .iter()
+            .filter(|literal| !matched.contains(literal))
+            .map(literal_name)
+            .collect();
+
+        if !missing.is_empty() {
+            return Err(format!(
+                "Match on union '{}' is not exhaustive, {} not matched",
+                union.type_identifier,
+                missing.join(", ")
+            ));
+        }
+    }
+
+    let fallback = create_nested_decision_tree(
+        matchee.clone(),
+        rest,
+        discovered_types,
+        body_type,
+        provenance,
+    )?;
+
+    Ok(Decision::Switch {
+        variable: Box::new(Variable {
+            identifier: union.type_identifier.to_string(),
+            accessor: Accessor::Environment,
+            type_: matchee.get_type(),
+            provenance: provenance.to_vec(),
+        }),
+        cases,
+        fallback: Box::new(fallback),
+        type_,
+    })
+}
+
+fn literal_name(literal: &Type) -> String {
+    match literal {
+        Type::Literal { name, .. } => name.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// The member a pattern names without matching any of its fields, which is how a member without
+/// fields is matched
+fn nullary_member(pattern: &Pattern, enum_: &Enum) -> Option<String> {
+    let Pattern::Constructor(Constructor::Struct {
+        type_annotation,
+        field_patterns,
+    }) = pattern
+    else {
+        return None;
+    };
+
+    if !field_patterns.is_empty() {
+        return None;
+    }
+
+    enum_
+        .members
+        .iter()
+        .find(|(_, member)| type_annotation_equals(type_annotation, &member.type_annotation()))
+        .map(|(name, _)| name.clone())
+}
+
+/// Lower the leading arms of a match on an enum which name a member without matching its fields
+/// to one switch over the members, like the literals of a union.
+///
+/// Whether the members are all covered is checked once for the whole match, since arms which
+/// match fields come before and after the switch.
+fn create_enum_switch(
+    matchee: TypedExpression,
+    enum_: &Enum,
+    arms: Vec<TypedMatchArm>,
+    discovered_types: &Vec<DiscoveredType>,
+    mut body_type: Option<Type>,
+    provenance: &[Projection],
+) -> Result<Decision, String> {
+    let nullary_arms = arms
+        .iter()
+        .take_while(|arm| nullary_member(&arm.pattern, enum_).is_some())
+        .count();
+
+    let mut cases: Vec<Case> = vec![];
+    let mut matched: Vec<String> = vec![];
+
+    for arm in &arms[..nullary_arms] {
+        let member = nullary_member(&arm.pattern, enum_).expect("nullary arms name a member");
+
+        let expression = check_type(
+            &arm.expression,
+            discovered_types,
+            arm.type_environment.clone(),
+            None,
+        )?;
+
+        let type_ = expression.get_type();
+
+        if let Some(body_type) = &body_type {
+            if !type_equals_coerce(body_type, &type_) {
+                return Err(format!("Expected type {:?} but got {:?}", body_type, type_));
+            }
+        }
+
+        body_type.get_or_insert(type_.clone());
+
+        // A repeated member is already matched by the earlier arm
+        if matched.contains(&member) {
+            continue;
+        }
+
+        // Members are matched by their own name, like the value of a member is named
+        cases.push(Case {
+            pattern: Pattern::Constructor(Constructor::Struct {
+                type_annotation: TypeAnnotation::Type(member.clone()),
+                field_patterns: vec![],
+            }),
+            arguments: vec![],
+            body: Decision::Success {
+                expression: Box::new(expression),
+                type_: type_.clone(),
+            },
+        });
+
+        matched.push(member);
+    }
+
+    let type_ = body_type.clone().unwrap_or(Type::Unknown);
+
+    let fallback = create_nested_decision_tree(
+        matchee.clone(),
+        arms.into_iter().skip(nullary_arms).collect(),
+        discovered_types,
+        body_type,
+        provenance,
+    )?;
+
+    Ok(Decision::Switch {
+        variable: Box::new(Variable {
+            identifier: enum_.type_identifier.to_string(),
+            accessor: Accessor::Environment,
+            type_: matchee.get_type(),
+            provenance: provenance.to_vec(),
+        }),
+        cases,
+        fallback: Box::new(fallback),
+        type_,
+    })
+}
+
+/// Check that the arms of a match on an enum cover every member. A member is covered by an arm
+/// naming it whose field patterns can't fail, which a member without fields always is.
+fn check_enum_exhaustive(enum_: &Enum, arms: &[TypedMatchArm]) -> Result<(), String> {
+    if arms
+        .iter()
+        .any(|arm| matches!(arm.pattern, Pattern::Wildcard | Pattern::Variable(_)))
+    {
+        return Ok(());
+    }
+
+    let mut missing: Vec<String> = enum_
+        .members
+        .values()
+        .filter(|member| {
+            !arms
+                .iter()
+                .any(|arm| is_irrefutable_for(&arm.pattern, member, &enum_.shared_fields))
+        })
+        .map(|member| member.type_annotation().to_string())
+        .collect();
+
+    if missing.is_empty() {
+        return Ok(());
+    }
+
+    missing.sort();
+
+    Err(format!(
+        "Match on enum '{}' is not exhaustive, {} not matched",
+        enum_.type_identifier,
+        missing.join(", ")
+    ))
+}
+
+/// Whether `pattern` matches every value of the struct or enum member `type_`
+fn is_irrefutable_for(pattern: &Pattern, type_: &Type, shared_fields: &[StructField]) -> bool {
+    match pattern {
+        Pattern::Wildcard | Pattern::Variable(_) => true,
+        Pattern::Unit => matches!(type_, Type::Unit),
+        Pattern::Constructor(Constructor::Struct {
+            type_annotation,
+            field_patterns,
+        }) => {
+            let fields = match type_ {
+                Type::Struct(Struct { fields, .. }) => fields.clone(),
+                Type::EnumMember(EnumMember { fields, .. }) => {
+                    fields.iter().chain(shared_fields).cloned().collect()
+                }
+                _ => return false,
+            };
+
+            type_annotation_equals(type_annotation, &type_.type_annotation())
+                && field_patterns.iter().all(|field_pattern| {
+                    get_field_by_name(&fields, &field_pattern.identifier).is_some_and(|field| {
+                        is_irrefutable_for(&field_pattern.pattern, &field.field_type, &[])
+                    })
+                })
+        }
+        _ => false,
+    }
+}
+
+/// Expand the arms of a match on an enum whose pattern names the enum itself, like `Shape { id }`,
+/// to one arm per member. Such a pattern matches any member, so it can only match the fields
+/// every member shares.
+fn expand_enum_arms(enum_: &Enum, arms: Vec<TypedMatchArm>) -> Result<Vec<TypedMatchArm>, String> {
+    let enum_annotation = enum_.type_annotation();
+
+    // Members are sorted so the same match always lowers to the same tree
+    let mut members: Vec<&Type> = enum_.members.values().collect();
+    members.sort_by_key(|member| member.type_annotation().to_string());
+
+    let mut expanded = vec![];
+
+    for arm in arms {
+        let Pattern::Constructor(Constructor::Struct {
+            type_annotation,
+            field_patterns,
+        }) = &arm.pattern
+        else {
+            expanded.push(arm);
+            continue;
+        };
+
+        if !type_annotation_equals(type_annotation, &enum_annotation) {
+            expanded.push(arm);
+            continue;
+        }
+
+        if let Some(field_pattern) = field_patterns.iter().find(|field_pattern| {
+            get_field_by_name(&enum_.shared_fields, &field_pattern.identifier).is_none()
+        }) {
+            return Err(format!(
+                "Field '{}' is not shared by every member of enum '{}'",
+                field_pattern.identifier, enum_.type_identifier
+            ));
+        }
+
+        for member in &members {
+            expanded.push(TypedMatchArm {
+                pattern: Pattern::Constructor(Constructor::Struct {
+                    type_annotation: member.type_annotation(),
+                    field_patterns: field_patterns.clone(),
+                }),
+                expression: arm.expression.clone(),
+                type_environment: arm.type_environment.clone(),
+            });
+        }
+    }
+
+    Ok(expanded)
+}
+
 pub fn create_decision_tree(
     matchee: TypedExpression,
     arms: Vec<TypedMatchArm>,
     discovered_types: &Vec<DiscoveredType>,
     body_type: Option<Type>,
+) -> Result<Decision, String> {
+    let arms = match matchee.get_type() {
+        Type::Enum(enum_) => {
+            let arms = expand_enum_arms(&enum_, arms)?;
+            check_enum_exhaustive(&enum_, &arms)?;
+            arms
+        }
+        _ => arms,
+    };
+
+    create_nested_decision_tree(matchee, arms, discovered_types, body_type, &[])
+}
+
+/// Create the decision tree of a value found at `provenance` in the matched value
+fn create_nested_decision_tree(
+    matchee: TypedExpression,
+    arms: Vec<TypedMatchArm>,
+    discovered_types: &Vec<DiscoveredType>,
+    body_type: Option<Type>,
+    provenance: &[Projection],
 ) -> Result<Decision, String> {
     if arms.is_empty() {
         return Ok(Decision::Failure {
@@ -180,8 +668,39 @@ pub fn create_decision_tree(
         });
     }
 
+    let arms = match matchee.get_type() {
+        Type::Enum(enum_) => expand_enum_arms(&enum_, arms)?,
+        _ => arms,
+    };
+
     let arm = arms.first().expect("testing matches");
 
+    if let Type::Union(union) = matchee.get_type() {
+        if arm.pattern.literal_type().is_some() {
+            return create_union_switch(
+                matchee,
+                &union,
+                arms,
+                discovered_types,
+                body_type,
+                provenance,
+            );
+        }
+    }
+
+    if let Type::Enum(enum_) = matchee.get_type() {
+        if nullary_member(&arm.pattern, &enum_).is_some() {
+            return create_enum_switch(
+                matchee,
+                &enum_,
+                arms,
+                discovered_types,
+                body_type,
+                provenance,
+            );
+        }
+    }
+
     let decision = match arm.pattern.clone() {
         Pattern::Wildcard => {
             let expression = &arm.expression;
@@ -224,11 +743,12 @@ pub fn create_decision_tree(
                 }
             }
 
-            let alternative = create_decision_tree(
+            let alternative = create_nested_decision_tree(
                 matchee.clone(),
                 arms.into_iter().skip(1).collect(),
                 discovered_types,
                 Some(type_.clone()),
+                provenance,
             )?;
 
             let decision = Decision::Guard {
@@ -237,8 +757,11 @@ pub fn create_decision_tree(
                     operator: BinaryOperator::Equal,
                     right: Box::new(TypedExpression::Literal(
                         crate::type_checker::ast::Literal::Unit,
+                        Span::default(),
                     )),
+                    constant: None,
                     type_: Type::Bool,
+                    span: Span::default(),
                 }),
                 consequence: Box::new(Decision::Success {
                     expression: Box::new(expression),
@@ -272,11 +795,12 @@ pub fn create_decision_tree(
                 }
             }
 
-            let alternative = create_decision_tree(
+            let alternative = create_nested_decision_tree(
                 matchee.clone(),
                 arms.into_iter().skip(1).collect(),
                 discovered_types,
                 Some(type_.clone()),
+                provenance,
             )?;
 
             let decision = Decision::Guard {
@@ -285,8 +809,11 @@ pub fn create_decision_tree(
                     operator: BinaryOperator::Equal,
                     right: Box::new(TypedExpression::Literal(
                         crate::type_checker::ast::Literal::Bool(v),
+                        Span::default(),
                     )),
+                    constant: None,
                     type_: Type::Bool,
+                    span: Span::default(),
                 }),
                 consequence: Box::new(Decision::Success {
                     expression: Box::new(expression),
@@ -319,11 +846,12 @@ pub fn create_decision_tree(
                 }
             }
 
-            let alternative = create_decision_tree(
+            let alternative = create_nested_decision_tree(
                 matchee.clone(),
                 arms.into_iter().skip(1).collect(),
                 discovered_types,
                 Some(type_.clone()),
+                provenance,
             )?;
 
             let decision = Decision::Guard {
@@ -332,8 +860,11 @@ pub fn create_decision_tree(
                     operator: BinaryOperator::Equal,
                     right: Box::new(TypedExpression::Literal(
                         crate::type_checker::ast::Literal::Int(v),
+                        Span::default(),
                     )),
+                    constant: None,
                     type_: Type::Bool,
+                    span: Span::default(),
                 }),
                 consequence: Box::new(Decision::Success {
                     expression: Box::new(expression),
@@ -366,11 +897,12 @@ pub fn create_decision_tree(
                 }
             }
 
-            let alternative = create_decision_tree(
+            let alternative = create_nested_decision_tree(
                 matchee.clone(),
                 arms.into_iter().skip(1).collect(),
                 discovered_types,
                 Some(type_.clone()),
+                provenance,
             )?;
 
             let decision = Decision::Guard {
@@ -379,8 +911,11 @@ pub fn create_decision_tree(
                     operator: BinaryOperator::Equal,
                     right: Box::new(TypedExpression::Literal(
                         crate::type_checker::ast::Literal::UInt(v),
+                        Span::default(),
                     )),
+                    constant: None,
                     type_: Type::Bool,
+                    span: Span::default(),
                 }),
                 consequence: Box::new(Decision::Success {
                     expression: Box::new(expression),
@@ -413,11 +948,12 @@ pub fn create_decision_tree(
                 }
             }
 
-            let alternative = create_decision_tree(
+            let alternative = create_nested_decision_tree(
                 matchee.clone(),
                 arms.into_iter().skip(1).collect(),
                 discovered_types,
                 Some(type_.clone()),
+                provenance,
             )?;
 
             let decision = Decision::Guard {
@@ -426,8 +962,11 @@ pub fn create_decision_tree(
                     operator: BinaryOperator::Equal,
                     right: Box::new(TypedExpression::Literal(
                         crate::type_checker::ast::Literal::Float(v),
+                        Span::default(),
                     )),
+                    constant: None,
                     type_: Type::Bool,
+                    span: Span::default(),
                 }),
                 consequence: Box::new(Decision::Success {
                     expression: Box::new(expression),
@@ -460,11 +999,12 @@ pub fn create_decision_tree(
                 }
             }
 
-            let alternative = create_decision_tree(
+            let alternative = create_nested_decision_tree(
                 matchee.clone(),
                 arms.into_iter().skip(1).collect(),
                 discovered_types,
                 Some(type_.clone()),
+                provenance,
             )?;
 
             let decision = Decision::Guard {
@@ -473,8 +1013,11 @@ pub fn create_decision_tree(
                     operator: BinaryOperator::Equal,
                     right: Box::new(TypedExpression::Literal(
                         crate::type_checker::ast::Literal::Char(v),
+                        Span::default(),
                     )),
+                    constant: None,
                     type_: Type::Bool,
+                    span: Span::default(),
                 }),
                 consequence: Box::new(Decision::Success {
                     expression: Box::new(expression),
@@ -507,11 +1050,12 @@ pub fn create_decision_tree(
                 }
             }
 
-            let alternative = create_decision_tree(
+            let alternative = create_nested_decision_tree(
                 matchee.clone(),
                 arms.into_iter().skip(1).collect(),
                 discovered_types,
                 Some(type_.clone()),
+                provenance,
             )?;
 
             let decision = Decision::Guard {
@@ -520,8 +1064,11 @@ pub fn create_decision_tree(
                     operator: BinaryOperator::Equal,
                     right: Box::new(TypedExpression::Literal(
                         crate::type_checker::ast::Literal::String(v.clone()),
+                        Span::default(),
                     )),
+                    constant: None,
                     type_: Type::Bool,
+                    span: Span::default(),
                 }),
                 consequence: Box::new(Decision::Success {
                     expression: Box::new(expression),
@@ -557,6 +1104,7 @@ pub fn create_decision_tree(
                 identifier: identifier.clone(),
                 accessor: Accessor::Environment,
                 type_: matchee_type.clone(),
+                provenance: provenance.to_vec(),
             };
 
             let case = Case {
@@ -569,7 +1117,7 @@ pub fn create_decision_tree(
             };
 
             Ok(Decision::Switch {
-                variable,
+                variable: Box::new(variable),
                 cases: vec![case],
                 fallback: Box::new(Decision::Failure {
                     error_message: "No match found".to_string(),
@@ -597,13 +1145,12 @@ pub fn create_decision_tree(
                 }
                 Type::EnumMember(EnumMember {
                     discriminant_name, ..
-                }) => {
-                    if type_annotation_equals(
-                        &type_annotation,
-                        &TypeAnnotation::Type(discriminant_name.clone()),
-                    ) {
-                        is_enum_member = true;
-                    }
+                }) if type_annotation_equals(
+                    &type_annotation,
+                    &TypeAnnotation::Type(discriminant_name.clone()),
+                ) =>
+                {
+                    is_enum_member = true;
                 }
                 _ => {}
             }
@@ -624,6 +1171,14 @@ pub fn create_decision_tree(
             let expression = &arm.expression;
             let type_environment = arm.type_environment.clone();
 
+            // Enum member patterns are written with the enum name, but members are keyed by their own name
+            let member_name = type_annotation
+                .name()
+                .rsplit("::")
+                .next()
+                .unwrap_or_default()
+                .to_string();
+
             let fields = match matchee_type.clone() {
                 Type::Struct(Struct { fields, .. }) => fields,
                 Type::EnumMember(EnumMember { fields, .. }) => fields,
@@ -632,7 +1187,7 @@ pub fn create_decision_tree(
                     members,
                     ..
                 }) => {
-                    let member = members.get(&type_annotation.name()).expect(
+                    let member = members.get(&member_name).expect(
                         "Already checked if the constructor name exists in the member list",
                     );
 
@@ -650,77 +1205,43 @@ pub fn create_decision_tree(
                 }
             };
 
-            if fields.is_empty() {
-                let expression =
-                    check_type(expression, discovered_types, type_environment.clone(), None)?;
+            // Shared fields come first, as every member has them to read before the member is known
+            let mut field_patterns = field_patterns;
 
-                let type_ = body_type.unwrap_or_else(|| expression.get_type());
+            if let Type::Enum(Enum { shared_fields, .. }) = &matchee_type {
+                field_patterns.sort_by_key(|field_pattern| {
+                    get_field_by_name(shared_fields, &field_pattern.identifier).is_none()
+                });
+            }
 
-                let alternative = create_decision_tree(
-                    matchee.clone(),
-                    arms.into_iter().skip(1).collect(),
-                    discovered_types,
-                    Some(type_.clone()),
-                )?;
+            // Naming a struct or a known enum member without matching its fields always matches
+            if field_patterns.is_empty() {
+                let expression =
+                    check_type(expression, discovered_types, type_environment.clone(), None)?;
 
-                let condition = if is_enum_member {
-                    let Type::Enum(Enum {
-                        type_identifier, ..
-                    }) = &matchee_type
-                    else {
-                        return Err("Expected matchee to be an enum type".to_owned());
-                    };
+                let type_ = expression.get_type();
 
-                    Box::new(TypedExpression::Binary {
-                        left: Box::new(matchee),
-                        operator: BinaryOperator::Equal,
-                        right: Box::new(TypedExpression::Literal(
-                            crate::type_checker::ast::Literal::Enum {
-                                type_annotation: TypeAnnotation::from(
-                                    format!(
-                                        "{}::{}",
-                                        type_identifier.name(),
-                                        type_annotation.name()
-                                    )
-                                    .as_str(),
-                                ),
-                                field_initializers:
-                                    crate::type_checker::ast::EnumMemberFieldInitializers::None,
-                                member: type_annotation.name(),
-                                type_: matchee_type.clone(),
-                            },
-                        )),
-                        type_: Type::Bool,
-                    })
-                } else {
-                    Box::new(TypedExpression::Binary {
-                        left: Box::new(matchee),
-                        operator: BinaryOperator::Equal,
-                        right: Box::new(TypedExpression::Literal(
-                            crate::type_checker::ast::Literal::Struct {
-                                type_annotation: type_annotation.clone(),
-                                field_initializers: vec![],
-                                type_: matchee_type.clone(),
-                            },
-                        )),
-                        type_: Type::Bool,
-                    })
-                };
+                if let Some(body_type) = body_type {
+                    if !type_equals_coerce(&body_type, &type_) {
+                        return Err(format!("Expected type {:?} but got {:?}", body_type, type_));
+                    }
+                }
 
-                return Ok(Decision::Guard {
-                    condition,
-                    consequence: Box::new(Decision::Success {
-                        expression: Box::new(expression),
-                        type_: type_.clone(),
-                    }),
-                    alternative: Box::new(alternative),
+                return Ok(Decision::Success {
+                    expression: Box::new(expression),
                     type_,
                 });
             }
 
             let field_name = field_patterns.first().unwrap().identifier.clone();
             let field_type = get_field_by_name(&fields, &field_name)
-                .expect("testing matches")
+                .ok_or_else(|| {
+                    format!(
+                        "Field '{}' does not exist on {}",
+                        field_name,
+                        matchee_type.type_annotation()
+                    )
+                })?
                 .clone()
                 .field_type;
 
@@ -729,11 +1250,75 @@ pub fn create_decision_tree(
                 member: Box::new(Member::Identifier {
                     symbol: field_name.clone(),
                     type_: field_type.clone(),
+                    span: Span::default(),
                 }),
                 symbol: field_name.clone(),
                 type_: field_type.clone(),
             });
 
+            let field_provenance = |field: &str| {
+                let mut field_provenance = provenance.to_vec();
+
+                field_provenance.push(Projection {
+                    constructor: member_name.clone(),
+                    field: field.to_string(),
+                });
+
+                field_provenance
+            };
+
+            // The case pattern tests every field, but the tree only continues with the first one,
+            // so the others are checked on their own to give their variables the right types
+            for field_pattern in field_patterns.iter().skip(1) {
+                let field_type = get_field_by_name(&fields, &field_pattern.identifier)
+                    .ok_or_else(|| {
+                        format!(
+                            "Field '{}' does not exist on {}",
+                            field_pattern.identifier,
+                            matchee_type.type_annotation()
+                        )
+                    })?
+                    .clone()
+                    .field_type;
+
+                let field_expr = TypedExpression::Member(Member::MemberAccess {
+                    object: Box::new(matchee.clone()),
+                    member: Box::new(Member::Identifier {
+                        symbol: field_pattern.identifier.clone(),
+                        type_: field_type.clone(),
+                        span: Span::default(),
+                    }),
+                    symbol: field_pattern.identifier.clone(),
+                    type_: field_type,
+                });
+
+                create_nested_decision_tree(
+                    field_expr,
+                    vec![TypedMatchArm {
+                        pattern: field_pattern.pattern.clone(),
+                        expression: Expression::Literal(Literal::Unit, Span::default()),
+                        type_environment: type_environment.clone(),
+                    }],
+                    discovered_types,
+                    None,
+                    &field_provenance(&field_pattern.identifier),
+                )?;
+            }
+
+            let body = create_nested_decision_tree(
+                expr.clone(),
+                vec![TypedMatchArm {
+                    pattern: field_patterns[0].pattern.clone(),
+                    expression: expression.clone(),
+                    type_environment: type_environment.clone(),
+                }],
+                discovered_types,
+                body_type.clone(),
+                &field_provenance(&field_name),
+            )?;
+
+            let type_ = body.get_type();
+
             let case = Case {
                 pattern: Pattern::Constructor(Constructor::Struct {
                     type_annotation: type_annotation.clone(),
@@ -745,39 +1330,30 @@ pub fn create_decision_tree(
                         identifier: struct_field.field_name.clone(),
                         accessor: Accessor::Environment,
                         type_: struct_field.field_type.clone(),
+                        provenance: field_provenance(&struct_field.field_name),
                     })
                     .collect(),
-                body: create_decision_tree(
-                    expr.clone(),
-                    field_patterns
-                        .into_iter()
-                        .map(|field_pattern| TypedMatchArm {
-                            pattern: field_pattern.pattern,
-                            expression: expression.clone(),
-                            type_environment: type_environment.clone(),
-                        })
-                        .collect(),
-                    discovered_types,
-                    body_type.clone(),
-                )?,
+                body,
             };
 
-            let fallback = create_decision_tree(
+            let fallback = create_nested_decision_tree(
                 matchee.clone(),
                 arms.into_iter().skip(1).collect(),
                 discovered_types,
                 body_type,
+                provenance,
             )?;
 
             Ok(Decision::Switch {
-                variable: Variable {
+                variable: Box::new(Variable {
                     identifier: field_name.clone(),
                     accessor: Accessor::Expression(Box::new(expr.clone())),
                     type_: field_type.clone(),
-                },
+                    provenance: field_provenance(&field_name),
+                }),
                 cases: vec![case],
                 fallback: Box::new(fallback),
-                type_: field_type.clone(),
+                type_,
             })
         }
         Pattern::LessThan(value) => {
@@ -801,11 +1377,12 @@ pub fn create_decision_tree(
                 }
             }
 
-            let alternative = create_decision_tree(
+            let alternative = create_nested_decision_tree(
                 matchee.clone(),
                 arms.clone().into_iter().skip(1).collect(),
                 discovered_types,
                 Some(type_.clone()),
+                provenance,
             )?;
 
             let decision = Decision::Guard {
@@ -815,12 +1392,15 @@ pub fn create_decision_tree(
                     right: match *value {
                         Pattern::Int(v) => Box::new(TypedExpression::Literal(
                             crate::type_checker::ast::Literal::Int(v),
+                            Span::default(),
                         )),
                         Pattern::UInt(v) => Box::new(TypedExpression::Literal(
                             crate::type_checker::ast::Literal::UInt(v),
+                            Span::default(),
                         )),
                         Pattern::Float(v) => Box::new(TypedExpression::Literal(
                             crate::type_checker::ast::Literal::Float(v),
+                            Span::default(),
                         )),
                         Pattern::Variable(v) => {
                             let variable_type = type_environment
@@ -831,13 +1411,16 @@ pub fn create_decision_tree(
                             Box::new(TypedExpression::Member(Member::Identifier {
                                 symbol: v.clone(),
                                 type_: variable_type.clone(),
+                                span: Span::default(),
                             }))
                         }
                         _ => unreachable!(
                             "Already checked if the value is Int, UInt, Float or Identifier"
                         ),
                     },
+                    constant: None,
                     type_: Type::Bool,
+                    span: Span::default(),
                 }),
                 consequence: Box::new(Decision::Success {
                     expression: Box::new(expression),
@@ -870,11 +1453,12 @@ pub fn create_decision_tree(
                 }
             }
 
-            let alternative = create_decision_tree(
+            let alternative = create_nested_decision_tree(
                 matchee.clone(),
                 arms.clone().into_iter().skip(1).collect(),
                 discovered_types,
                 Some(type_.clone()),
+                provenance,
             )?;
 
             let decision = Decision::Guard {
@@ -884,12 +1468,15 @@ pub fn create_decision_tree(
                     right: match *value {
                         Pattern::Int(v) => Box::new(TypedExpression::Literal(
                             crate::type_checker::ast::Literal::Int(v),
+                            Span::default(),
                         )),
                         Pattern::UInt(v) => Box::new(TypedExpression::Literal(
                             crate::type_checker::ast::Literal::UInt(v),
+                            Span::default(),
                         )),
                         Pattern::Float(v) => Box::new(TypedExpression::Literal(
                             crate::type_checker::ast::Literal::Float(v),
+                            Span::default(),
                         )),
                         Pattern::Variable(v) => {
                             let variable_type = type_environment
@@ -900,13 +1487,16 @@ pub fn create_decision_tree(
                             Box::new(TypedExpression::Member(Member::Identifier {
                                 symbol: v.clone(),
                                 type_: variable_type.clone(),
+                                span: Span::default(),
                             }))
                         }
                         _ => unreachable!(
                             "Already checked if the value is Int, UInt, Float or Identifier"
                         ),
                     },
+                    constant: None,
                     type_: Type::Bool,
+                    span: Span::default(),
                 }),
                 consequence: Box::new(Decision::Success {
                     expression: Box::new(expression),
@@ -939,11 +1529,12 @@ pub fn create_decision_tree(
                 }
             }
 
-            let alternative = create_decision_tree(
+            let alternative = create_nested_decision_tree(
                 matchee.clone(),
                 arms.clone().into_iter().skip(1).collect(),
                 discovered_types,
                 Some(type_.clone()),
+                provenance,
             )?;
 
             let decision = Decision::Guard {
@@ -953,12 +1544,15 @@ pub fn create_decision_tree(
                     right: match *value {
                         Pattern::Int(v) => Box::new(TypedExpression::Literal(
                             crate::type_checker::ast::Literal::Int(v),
+                            Span::default(),
                         )),
                         Pattern::UInt(v) => Box::new(TypedExpression::Literal(
                             crate::type_checker::ast::Literal::UInt(v),
+                            Span::default(),
                         )),
                         Pattern::Float(v) => Box::new(TypedExpression::Literal(
                             crate::type_checker::ast::Literal::Float(v),
+                            Span::default(),
                         )),
                         Pattern::Variable(v) => {
                             let variable_type = type_environment
@@ -969,13 +1563,16 @@ pub fn create_decision_tree(
                             Box::new(TypedExpression::Member(Member::Identifier {
                                 symbol: v.clone(),
                                 type_: variable_type.clone(),
+                                span: Span::default(),
                             }))
                         }
                         _ => unreachable!(
                             "Already checked if the value is Int, UInt, Float or Identifier"
                         ),
                     },
+                    constant: None,
                     type_: Type::Bool,
+                    span: Span::default(),
                 }),
                 consequence: Box::new(Decision::Success {
                     expression: Box::new(expression),
@@ -1008,11 +1605,12 @@ pub fn create_decision_tree(
                 }
             }
 
-            let alternative = create_decision_tree(
+            let alternative = create_nested_decision_tree(
                 matchee.clone(),
                 arms.clone().into_iter().skip(1).collect(),
                 discovered_types,
                 Some(type_.clone()),
+                provenance,
             )?;
 
             let decision = Decision::Guard {
@@ -1022,12 +1620,15 @@ pub fn create_decision_tree(
                     right: match *value {
                         Pattern::Int(v) => Box::new(TypedExpression::Literal(
                             crate::type_checker::ast::Literal::Int(v),
+                            Span::default(),
                         )),
                         Pattern::UInt(v) => Box::new(TypedExpression::Literal(
                             crate::type_checker::ast::Literal::UInt(v),
+                            Span::default(),
                         )),
                         Pattern::Float(v) => Box::new(TypedExpression::Literal(
                             crate::type_checker::ast::Literal::Float(v),
+                            Span::default(),
                         )),
                         Pattern::Variable(v) => {
                             let variable_type = type_environment
@@ -1038,13 +1639,16 @@ pub fn create_decision_tree(
                             Box::new(TypedExpression::Member(Member::Identifier {
                                 symbol: v.clone(),
                                 type_: variable_type.clone(),
+                                span: Span::default(),
                             }))
                         }
                         _ => unreachable!(
                             "Already checked if the value is Int, UInt, Float or Identifier"
                         ),
                     },
+                    constant: None,
                     type_: Type::Bool,
+                    span: Span::default(),
                 }),
                 consequence: Box::new(Decision::Success {
                     expression: Box::new(expression),
@@ -1086,11 +1690,12 @@ pub fn create_decision_tree(
                 }
             }
 
-            let alternative = create_decision_tree(
+            let alternative = create_nested_decision_tree(
                 matchee.clone(),
                 arms.clone().into_iter().skip(1).collect(),
                 discovered_types,
                 Some(type_.clone()),
+                provenance,
             )?;
 
             let decision = Decision::Guard {
@@ -1101,12 +1706,15 @@ pub fn create_decision_tree(
                         right: match *left {
                             Pattern::Int(v) => Box::new(TypedExpression::Literal(
                                 crate::type_checker::ast::Literal::Int(v),
+                                Span::default(),
                             )),
                             Pattern::UInt(v) => Box::new(TypedExpression::Literal(
                                 crate::type_checker::ast::Literal::UInt(v),
+                                Span::default(),
                             )),
                             Pattern::Float(v) => Box::new(TypedExpression::Literal(
                                 crate::type_checker::ast::Literal::Float(v),
+                                Span::default(),
                             )),
                             Pattern::Variable(v) => {
                                 let variable_type = type_environment
@@ -1117,13 +1725,16 @@ pub fn create_decision_tree(
                                 Box::new(TypedExpression::Member(Member::Identifier {
                                     symbol: v.clone(),
                                     type_: variable_type.clone(),
+                                    span: Span::default(),
                                 }))
                             }
                             _ => unreachable!(
                                 "Already checked if the value is Int, UInt, Float or Identifier"
                             ),
                         },
+                        constant: None,
                         type_: Type::Bool,
+                        span: Span::default(),
                     }),
                     operator: BinaryOperator::LogicalAnd,
                     right: Box::new(TypedExpression::Binary {
@@ -1136,12 +1747,15 @@ pub fn create_decision_tree(
                         right: match *right {
                             Pattern::Int(v) => Box::new(TypedExpression::Literal(
                                 crate::type_checker::ast::Literal::Int(v),
+                                Span::default(),
                             )),
                             Pattern::UInt(v) => Box::new(TypedExpression::Literal(
                                 crate::type_checker::ast::Literal::UInt(v),
+                                Span::default(),
                             )),
                             Pattern::Float(v) => Box::new(TypedExpression::Literal(
                                 crate::type_checker::ast::Literal::Float(v),
+                                Span::default(),
                             )),
                             Pattern::Variable(v) => {
                                 let variable_type = type_environment
@@ -1152,15 +1766,20 @@ pub fn create_decision_tree(
                                 Box::new(TypedExpression::Member(Member::Identifier {
                                     symbol: v.clone(),
                                     type_: variable_type.clone(),
+                                    span: Span::default(),
                                 }))
                             }
                             _ => unreachable!(
                                 "Already checked if the value is Int, UInt, Float or Identifier"
                             ),
                         },
+                        constant: None,
                         type_: Type::Bool,
+                        span: Span::default(),
                     }),
+                    constant: None,
                     type_: Type::Bool,
+                    span: Span::default(),
                 }),
                 consequence: Box::new(Decision::Success {
                     expression: Box::new(expression),
@@ -0,0 +1,296 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use crate::{
+    parser::{self, Expression},
+    span::Span,
+};
+
+use super::{
+    ast::{Member, Typed, TypedExpression, TypedMatchArm},
+    decision_tree::{create_decision_tree, Decision, Pattern},
+    expressions::check_type,
+    transform::{fold_decision, fold_expression, TypedTransformer},
+    type_equals_coerce,
+    visit::{walk_expression, TypedVisitor},
+    DiscoveredType, Rcrc, Type, TypeEnvironment,
+};
+
+// Neither placeholder is a valid identifier, so they can't collide with user code
+const MATCHEE_PLACEHOLDER: &str = "$matchee";
+const ARM_PLACEHOLDER: &str = "$arm";
+
+/// Compiled decision trees keyed by the shape of their match, which is the matchee type and the arm patterns.
+///
+/// The cached trees are skeletons with placeholders for the matchee and the arm bodies.
+/// Only matches whose patterns bind or reference no variables are cached,
+/// since their trees don't depend on anything but the shape.
+/// Variable and constructor patterns add their bindings to the arm environments while the tree is compiled,
+/// which a cached skeleton can't replay for the arms it's spliced into.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DecisionTreeCache {
+    skeletons: HashMap<String, Decision>,
+    hits: usize,
+    misses: usize,
+}
+
+impl DecisionTreeCache {
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+
+    pub fn len(&self) -> usize {
+        self.skeletons.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.skeletons.is_empty()
+    }
+}
+
+pub(super) fn create_cached_decision_tree(
+    cache: &Rcrc<DecisionTreeCache>,
+    matchee: TypedExpression,
+    arms: Vec<TypedMatchArm>,
+    discovered_types: &Vec<DiscoveredType>,
+    match_environment: Rcrc<TypeEnvironment>,
+) -> Result<Decision, String> {
+    if !arms.iter().all(|arm| is_cacheable(&arm.pattern)) {
+        return create_decision_tree(matchee, arms, discovered_types, None);
+    }
+
+    let matchee_type = matchee.get_type();
+
+    let key = format!(
+        "{:?} {:?}",
+        matchee_type,
+        arms.iter().map(|arm| &arm.pattern).collect::<Vec<_>>()
+    );
+
+    let cached = cache.borrow().skeletons.get(&key).cloned();
+
+    let skeleton = match cached {
+        Some(skeleton) => {
+            cache.borrow_mut().hits += 1;
+            skeleton
+        }
+        None => {
+            // Let the uncached compilation report errors in the patterns
+            let Ok(skeleton) =
+                create_skeleton(&matchee_type, &arms, discovered_types, match_environment)
+            else {
+                return create_decision_tree(matchee, arms, discovered_types, None);
+            };
+
+            let mut cache = cache.borrow_mut();
+            cache.misses += 1;
+            cache.skeletons.insert(key, skeleton.clone());
+            skeleton
+        }
+    };
+
+    splice(skeleton, matchee, &arms, discovered_types)
+}
+
+fn is_cacheable(pattern: &Pattern) -> bool {
+    match pattern {
+        Pattern::Wildcard
+        | Pattern::Unit
+        | Pattern::Bool(_)
+        | Pattern::Int(_)
+        | Pattern::UInt(_)
+        | Pattern::Float(_)
+        | Pattern::Char(_)
+        | Pattern::String(_) => true,
+        Pattern::LessThan(pattern)
+        | Pattern::GreaterThan(pattern)
+        | Pattern::LessThanOrEqual(pattern)
+        | Pattern::GreaterThanOrEqual(pattern) => is_cacheable(pattern),
+        Pattern::Range(start, end, _) => is_cacheable(start) && is_cacheable(end),
+        Pattern::Variable(_) | Pattern::Constructor(_) => false,
+    }
+}
+
+fn arm_placeholder(index: usize) -> String {
+    format!("{}{}", ARM_PLACEHOLDER, index)
+}
+
+fn arm_index(symbol: &str) -> Option<usize> {
+    symbol.strip_prefix(ARM_PLACEHOLDER)?.parse().ok()
+}
+
+fn create_skeleton(
+    matchee_type: &Type,
+    arms: &[TypedMatchArm],
+    discovered_types: &Vec<DiscoveredType>,
+    match_environment: Rcrc<TypeEnvironment>,
+) -> Result<Decision, String> {
+    let placeholder_arms = arms
+        .iter()
+        .enumerate()
+        .map(|(index, arm)| {
+            let arm_environment = Rc::new(RefCell::new(TypeEnvironment::new_parent(
+                match_environment.clone(),
+            )));
+
+            arm_environment
+                .borrow_mut()
+                .add_variable(arm_placeholder(index), Type::Unit);
+
+            TypedMatchArm {
+                pattern: arm.pattern.clone(),
+                expression: Expression::Member(parser::Member::Identifier {
+                    symbol: arm_placeholder(index),
+                    generics: None,
+                    span: Span::default(),
+                }),
+                type_environment: arm_environment,
+            }
+        })
+        .collect();
+
+    let matchee = TypedExpression::Member(Member::Identifier {
+        symbol: MATCHEE_PLACEHOLDER.to_string(),
+        type_: matchee_type.clone(),
+        span: Span::default(),
+    });
+
+    create_decision_tree(matchee, placeholder_arms, discovered_types, None)
+}
+
+fn splice(
+    skeleton: Decision,
+    matchee: TypedExpression,
+    arms: &[TypedMatchArm],
+    discovered_types: &Vec<DiscoveredType>,
+) -> Result<Decision, String> {
+    let mut collector = ArmCollector::default();
+    collector.visit_decision(&skeleton);
+
+    let mut indices = collector.indices;
+    indices.sort();
+    indices.dedup();
+
+    // Check the reachable bodies in source order, like the uncached compilation does
+    let mut bodies = HashMap::new();
+    let mut body_type: Option<Type> = None;
+
+    for index in indices {
+        let arm = &arms[index];
+        let body = check_type(
+            &arm.expression,
+            discovered_types,
+            arm.type_environment.clone(),
+            None,
+        )?;
+
+        let type_ = body.get_type();
+
+        if let Some(body_type) = body_type {
+            if !type_equals_coerce(&body_type, &type_) {
+                return Err(format!("Expected type {:?} but got {:?}", body_type, type_));
+            }
+        }
+
+        body_type = Some(type_);
+        bodies.insert(index, body);
+    }
+
+    Ok(Splice { matchee, bodies }.transform_decision(skeleton))
+}
+
+#[derive(Default)]
+struct ArmCollector {
+    indices: Vec<usize>,
+}
+
+impl TypedVisitor for ArmCollector {
+    fn visit_expression(&mut self, expression: &TypedExpression) {
+        if let TypedExpression::Member(Member::Identifier { symbol, .. }) = expression {
+            if let Some(index) = arm_index(symbol) {
+                self.indices.push(index);
+            }
+        }
+
+        walk_expression(self, expression);
+    }
+}
+
+struct Splice {
+    matchee: TypedExpression,
+    bodies: HashMap<usize, TypedExpression>,
+}
+
+impl TypedTransformer for Splice {
+    fn transform_expression(&mut self, expression: TypedExpression) -> TypedExpression {
+        if let TypedExpression::Member(Member::Identifier { symbol, .. }) = &expression {
+            if symbol == MATCHEE_PLACEHOLDER {
+                return self.matchee.clone();
+            }
+
+            if let Some(body) = arm_index(symbol).and_then(|index| self.bodies.get(&index)) {
+                return body.clone();
+            }
+        }
+
+        fold_expression(self, expression)
+    }
+
+    // The skeleton was compiled with Unit bodies, so the types are taken from the spliced bodies
+    fn transform_decision(&mut self, decision: Decision) -> Decision {
+        match fold_decision(self, decision) {
+            Decision::Success { expression, .. } => {
+                let type_ = expression.get_type();
+                Decision::Success { expression, type_ }
+            }
+            Decision::Guard {
+                condition,
+                consequence,
+                alternative,
+                ..
+            } => {
+                let type_ = branch_type([&*consequence, &*alternative]);
+
+                Decision::Guard {
+                    condition,
+                    consequence,
+                    alternative,
+                    type_,
+                }
+            }
+            Decision::Switch {
+                variable,
+                cases,
+                fallback,
+                ..
+            } => {
+                let type_ = branch_type(
+                    cases
+                        .iter()
+                        .map(|case| &case.body)
+                        .chain(std::iter::once(&*fallback)),
+                );
+
+                Decision::Switch {
+                    variable,
+                    cases,
+                    fallback,
+                    type_,
+                }
+            }
+            decision => decision,
+        }
+    }
+}
+
+/// The type of the first branch which doesn't fail, which is the type of the whole decision.
+fn branch_type<'a>(branches: impl IntoIterator<Item = &'a Decision>) -> Type {
+    branches
+        .into_iter()
+        .map(Decision::get_type)
+        .find(|type_| *type_ != Type::Unknown)
+        .unwrap_or(Type::Unknown)
+}
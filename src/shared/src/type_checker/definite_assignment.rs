@@ -0,0 +1,466 @@
+use std::collections::HashMap;
+
+use super::{
+    ast::{BinaryOperator, Block, Member, TypedExpression, TypedStatement},
+    decision_tree::Decision,
+    visit::{self, TypedVisitor},
+};
+
+/// Reject reads of variables declared without an initializer, like `let x;`, before they are
+/// assigned on every path leading to the read.
+///
+/// An immutable variable declared without an initializer can be assigned once on every path, so
+/// assigning it when it might already be assigned, or in a loop or closure, is rejected too.
+pub fn check_definite_assignment(program: &TypedStatement) -> Result<(), String> {
+    let mut checker = DefiniteAssignment::default();
+    checker.visit_statement(program);
+
+    match checker.error {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Assigned {
+    No,
+    OnSomePaths,
+    Yes,
+}
+
+impl Assigned {
+    fn join(self, other: Assigned) -> Assigned {
+        if self == other {
+            self
+        } else {
+            Assigned::OnSomePaths
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Variable {
+    assigned: Assigned,
+    mutable: bool,
+}
+
+/// The variables in scope at one point of the program. A state which diverged has returned,
+/// broken or continued, so nothing after it runs and it doesn't weaken the states it joins.
+#[derive(Debug, Clone, Default)]
+struct State {
+    scopes: Vec<HashMap<String, Variable>>,
+    diverged: bool,
+}
+
+impl State {
+    fn join(self, other: State) -> State {
+        if self.diverged {
+            return other;
+        }
+
+        if other.diverged {
+            return self;
+        }
+
+        let scopes = self
+            .scopes
+            .into_iter()
+            .zip(other.scopes)
+            .map(|(scope, other)| {
+                scope
+                    .into_iter()
+                    .map(|(name, variable)| {
+                        let other = other.get(&name).copied().unwrap_or(variable);
+
+                        let variable = Variable {
+                            assigned: variable.assigned.join(other.assigned),
+                            ..variable
+                        };
+
+                        (name, variable)
+                    })
+                    .collect()
+            })
+            .collect();
+
+        State {
+            scopes,
+            diverged: false,
+        }
+    }
+
+    fn lookup(&mut self, name: &str) -> Option<&mut Variable> {
+        self.scopes
+            .iter_mut()
+            .rev()
+            .find_map(|scope| scope.get_mut(name))
+    }
+
+    /// How many scopes are outside the one the variable is declared in
+    fn depth(&self, name: &str) -> Option<usize> {
+        self.scopes
+            .iter()
+            .rposition(|scope| scope.contains_key(name))
+    }
+
+    fn declare(&mut self, name: &str, variable: Variable) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name.to_owned(), variable);
+        }
+    }
+}
+
+#[derive(Default)]
+struct DefiniteAssignment {
+    state: State,
+    /// The states at the `break`s of every loop being checked, innermost last
+    breaks: Vec<Vec<State>>,
+    /// How many scopes are outside every loop and closure being checked, and which it is,
+    /// innermost last. Their bodies can run more than once.
+    repeated: Vec<(usize, &'static str)>,
+    error: Option<String>,
+}
+
+impl DefiniteAssignment {
+    fn with_scope(&mut self, names: &[&str], f: impl FnOnce(&mut Self)) {
+        self.state.scopes.push(
+            names
+                .iter()
+                .map(|name| {
+                    let variable = Variable {
+                        assigned: Assigned::Yes,
+                        mutable: true,
+                    };
+
+                    (name.to_string(), variable)
+                })
+                .collect(),
+        );
+
+        f(self);
+        self.state.scopes.pop();
+    }
+
+    /// Check an expression which might not run, returning the state after it
+    fn branch(&mut self, start: &State, expression: &TypedExpression) -> State {
+        self.state = start.clone();
+        self.visit_expression(expression);
+        self.state.clone()
+    }
+
+    fn branch_decision(&mut self, start: &State, decision: &Decision) -> State {
+        self.state = start.clone();
+        self.visit_decision(decision);
+        self.state.clone()
+    }
+
+    /// Check a loop whose body might run any number of times. `exit` is the state when the loop
+    /// ends without breaking, or `None` if it only ends by breaking.
+    fn check_loop(
+        &mut self,
+        identifier: Option<&str>,
+        body: &TypedExpression,
+        exit: Option<State>,
+        else_body: Option<&TypedExpression>,
+    ) {
+        let start = self.state.clone();
+        let depth = start.scopes.len();
+
+        self.breaks.push(vec![]);
+        self.repeated.push((depth, "in a loop"));
+        self.with_scope(identifier.as_slice(), |checker| {
+            checker.visit_expression(body)
+        });
+        self.repeated.pop();
+        let breaks = self.breaks.pop().unwrap_or_default();
+
+        let exit = match (exit, else_body) {
+            (Some(exit), Some(else_body)) => Some(self.branch(&exit, else_body)),
+            (exit, _) => exit,
+        };
+
+        let diverged = State {
+            diverged: true,
+            ..start
+        };
+
+        self.state = breaks
+            .into_iter()
+            .map(|mut state| {
+                state.scopes.truncate(depth);
+                state
+            })
+            .chain(exit)
+            .fold(diverged, State::join);
+    }
+
+    fn read(&mut self, name: &str) {
+        if self.state.diverged || self.error.is_some() {
+            return;
+        }
+
+        self.error = match self.state.lookup(name).map(|variable| variable.assigned) {
+            Some(Assigned::No) => {
+                Some(format!("Variable '{}' is used before it is assigned", name))
+            }
+            Some(Assigned::OnSomePaths) => Some(format!(
+                "Variable '{}' is used before it is assigned on every path",
+                name
+            )),
+            _ => None,
+        };
+    }
+
+    fn assign(&mut self, name: &str) {
+        if self.state.diverged || self.error.is_some() {
+            return;
+        }
+
+        let depth = self.state.depth(name);
+
+        let Some(variable) = self.state.lookup(name) else {
+            return;
+        };
+
+        if !variable.mutable {
+            let repeated = self
+                .repeated
+                .iter()
+                .rev()
+                .find(|(outside, _)| depth.is_some_and(|depth| depth < *outside));
+
+            let reason = match (variable.assigned, repeated) {
+                (Assigned::No, Some((_, place))) => Some(*place),
+                (Assigned::No, None) => None,
+                _ => Some("more than once"),
+            };
+
+            if let Some(reason) = reason {
+                self.error = Some(format!(
+                    "Cannot assign to immutable variable '{}' {}, declare it with 'let mut {}'",
+                    name, reason, name
+                ));
+
+                return;
+            }
+        }
+
+        variable.assigned = Assigned::Yes;
+    }
+}
+
+impl TypedVisitor for DefiniteAssignment {
+    fn visit_statement(&mut self, statement: &TypedStatement) {
+        match statement {
+            TypedStatement::Program { .. } => {
+                self.with_scope(&[], |checker| visit::walk_statement(checker, statement));
+            }
+            // Functions start with a state of their own, they can run at any point
+            TypedStatement::FunctionDeclaration { .. } => {
+                let state = std::mem::take(&mut self.state);
+                let breaks = std::mem::take(&mut self.breaks);
+
+                self.with_scope(&[], |checker| visit::walk_statement(checker, statement));
+
+                self.state = state;
+                self.breaks = breaks;
+            }
+            _ => visit::walk_statement(self, statement),
+        }
+    }
+
+    fn visit_expression(&mut self, expression: &TypedExpression) {
+        match expression {
+            TypedExpression::VariableDeclaration {
+                mutable,
+                pattern,
+                initializer,
+                ..
+            } => {
+                if let Some(initializer) = initializer {
+                    self.visit_expression(initializer);
+                }
+
+                let assigned = match initializer {
+                    Some(_) => Assigned::Yes,
+                    None => Assigned::No,
+                };
+
+                let variable = Variable {
+                    assigned,
+                    mutable: *mutable,
+                };
+
+                for name in pattern.variables() {
+                    self.state.declare(name, variable);
+                }
+            }
+            TypedExpression::Assignment {
+                member,
+                initializer,
+                ..
+            } => {
+                self.visit_expression(initializer);
+
+                match member.as_ref() {
+                    Member::Identifier { symbol, .. } => self.assign(symbol),
+                    member => self.visit_member(member),
+                }
+            }
+            TypedExpression::If {
+                condition,
+                true_expression,
+                false_expression,
+                ..
+            } => {
+                self.visit_expression(condition);
+
+                let start = self.state.clone();
+                let true_state = self.branch(&start, true_expression);
+
+                let false_state = match false_expression {
+                    Some(false_expression) => self.branch(&start, false_expression),
+                    None => start,
+                };
+
+                self.state = true_state.join(false_state);
+            }
+            TypedExpression::Match {
+                expression,
+                decision_tree,
+                ..
+            } => {
+                self.visit_expression(expression);
+                self.visit_decision(decision_tree);
+            }
+            TypedExpression::Binary {
+                left,
+                operator: BinaryOperator::LogicalAnd | BinaryOperator::LogicalOr,
+                right,
+                ..
+            } => {
+                self.visit_expression(left);
+
+                let start = self.state.clone();
+                let right_state = self.branch(&start, right);
+                self.state = start.join(right_state);
+            }
+            // The body runs whenever the closure is called, so it only sees what is assigned
+            // when the closure is created, and its assignments don't count after it
+            TypedExpression::Closure { param, body, .. } => {
+                let state = self.state.clone();
+                let breaks = std::mem::take(&mut self.breaks);
+                let param = param.as_ref().map(|param| param.identifier.as_str());
+
+                self.repeated.push((state.scopes.len(), "in a closure"));
+                self.with_scope(param.as_slice(), |checker| checker.visit_expression(body));
+                self.repeated.pop();
+
+                self.state = state;
+                self.breaks = breaks;
+            }
+            TypedExpression::Block(block) => self.visit_block(block),
+            TypedExpression::Loop { body, .. } => self.check_loop(None, body, None, None),
+            TypedExpression::While {
+                condition,
+                body,
+                else_body,
+                ..
+            } => {
+                self.visit_expression(condition);
+
+                let exit = self.state.clone();
+                self.check_loop(None, body, Some(exit), else_body.as_deref());
+            }
+            TypedExpression::For {
+                identifier,
+                iterable,
+                next,
+                body,
+                else_body,
+                ..
+            } => {
+                self.visit_expression(iterable);
+
+                if let Some(next) = next {
+                    self.with_scope(&[identifier.as_str()], |checker| {
+                        checker.visit_expression(next)
+                    });
+                }
+
+                let exit = self.state.clone();
+                self.check_loop(Some(identifier), body, Some(exit), else_body.as_deref());
+            }
+            TypedExpression::Break(value) => {
+                if let Some(value) = value {
+                    self.visit_expression(value);
+                }
+
+                let state = self.state.clone();
+
+                if let Some(breaks) = self.breaks.last_mut() {
+                    breaks.push(state);
+                }
+
+                self.state.diverged = true;
+            }
+            TypedExpression::Return(value) => {
+                if let Some(value) = value {
+                    self.visit_expression(value);
+                }
+
+                self.state.diverged = true;
+            }
+            TypedExpression::Continue => self.state.diverged = true,
+            _ => visit::walk_expression(self, expression),
+        }
+    }
+
+    fn visit_member(&mut self, member: &Member) {
+        match member {
+            Member::Identifier { symbol, .. } => self.read(symbol),
+            // Only the object is a variable, the member is one of its fields or methods
+            Member::MemberAccess { object, .. } => self.visit_expression(object),
+            Member::StaticMemberAccess { .. } => {}
+        }
+    }
+
+    fn visit_block(&mut self, block: &Block) {
+        self.with_scope(&[], |checker| visit::walk_block(checker, block));
+    }
+
+    fn visit_decision(&mut self, decision: &Decision) {
+        match decision {
+            Decision::Success { expression, .. } => {
+                self.with_scope(&[], |checker| checker.visit_expression(expression));
+            }
+            Decision::Failure { .. } => self.state.diverged = true,
+            Decision::Guard {
+                condition,
+                consequence,
+                alternative,
+                ..
+            } => {
+                self.visit_expression(condition);
+
+                let start = self.state.clone();
+                let consequence = self.branch_decision(&start, consequence);
+                let alternative = self.branch_decision(&start, alternative);
+                self.state = consequence.join(alternative);
+            }
+            Decision::Switch {
+                cases, fallback, ..
+            } => {
+                let start = self.state.clone();
+                let fallback = self.branch_decision(&start, fallback);
+
+                self.state = cases
+                    .iter()
+                    .map(|case| self.branch_decision(&start, &case.body))
+                    .collect::<Vec<State>>()
+                    .into_iter()
+                    .fold(fallback, State::join);
+            }
+        }
+    }
+}
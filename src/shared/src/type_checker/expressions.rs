@@ -1,22 +1,33 @@
 use std::{cell::RefCell, collections::HashMap, rc::Rc};
 
 use crate::{
-    parser::{self, Assignment, Binary, Expression, For, If, Match, VariableDeclaration, While},
+    parser::{
+        self, Assignment, Binary, Cast, Coalesce, Expression, For, If, Match, VariableDeclaration,
+        While,
+    },
+    span::Span,
     type_checker::{ast::Literal, type_annotation_equals, StructField},
-    types::{TypeAnnotation, TypeIdentifier},
+    types::{GenericType, TypeAnnotation, TypeIdentifier},
 };
 
 use super::{
     ast::{
-        BinaryOperator, Block, EnumMemberFieldInitializers, FieldInitializer, Member, Typed,
-        TypedClosureParameter, TypedExpression, TypedMatchArm, TypedStatement, UnaryOperator,
+        BinaryOperator, BinaryOperatorCategory, Block, EnumMemberFieldInitializers,
+        FieldInitializer, Member, Typed, TypedClosureParameter, TypedExpression, TypedMatchArm,
+        TypedStatement, UnaryOperator,
+    },
+    constant,
+    decision_tree::{
+        create_decision_tree, Accessor, Case, Constructor, Decision, FieldPattern, Pattern,
+        Variable,
     },
-    decision_tree::{create_decision_tree, Constructor, Pattern},
-    get_field_by_name,
+    decision_tree_cache::create_cached_decision_tree,
+    get_field_by_name, imports, module_name,
     scope::ScopeType,
     statements::{self, check_type_annotation},
-    type_equals, type_equals_coerce, DiscoveredType, Enum, EnumMember, FullName, Function, Rcrc,
-    Struct, Type, TypeAlias, TypeEnvironment, Union,
+    suggestion::did_you_mean,
+    type_equals, type_equals_coerce, variance, DiscoveredType, Enum, EnumMember, FullName,
+    Function, Newtype, OverflowPolicy, Rcrc, Struct, Type, TypeAlias, TypeEnvironment, Union,
 };
 
 pub fn check_type(
@@ -24,6 +35,25 @@ pub fn check_type(
     discovered_types: &Vec<DiscoveredType>,
     type_environment: Rc<RefCell<TypeEnvironment>>,
     context: Option<Type>,
+) -> Result<TypedExpression, String> {
+    type_environment.borrow().enter_nested()?;
+
+    let typed_expression = check_expression(
+        expression,
+        discovered_types,
+        type_environment.clone(),
+        context,
+    );
+
+    type_environment.borrow().exit_nested();
+    typed_expression
+}
+
+fn check_expression(
+    expression: &Expression,
+    discovered_types: &Vec<DiscoveredType>,
+    type_environment: Rc<RefCell<TypeEnvironment>>,
+    context: Option<Type>,
 ) -> Result<TypedExpression, String> {
     match expression {
         // Expression::None => Ok(TypedExpression::None),
@@ -106,6 +136,7 @@ pub fn check_type(
                             .clone()
                             .or_else(|| Some(type_.clone().into())),
                         type_: Box::new(type_),
+                        span: param.span,
                     })
                 }
                 None => None,
@@ -153,102 +184,22 @@ pub fn check_type(
             })
         }
         Expression::Call(call) => {
-            let callee = check_type(
-                &call.callee,
-                discovered_types,
-                type_environment.clone(),
-                context.clone(),
-            )?;
-
-            let callee_type = callee.get_type();
-
-            if !matches!(&callee_type, &Type::Function(_)) {
-                return Err(format!(
-                    "Expected function type, found {}",
-                    callee.get_type()
-                ));
-            }
-
-            let return_type = match callee_type.clone() {
-                Type::Function(Function { return_type, .. }) => *return_type,
-                _ => {
-                    return Err(format!(
-                        "Expected function type, found {}",
-                        callee.get_type()
-                    ))
-                }
-            };
-
-            let arg_typed_expression = call
-                .argument
-                .clone()
-                .map(|arg| {
-                    let context_type =
-                        if let Type::Function(Function { param, .. }) = callee_type.clone() {
-                            param.map(|p| *p.type_)
-                        } else {
-                            None
-                        };
+            let generic_callee =
+                infer_generic_callee(call, discovered_types, type_environment.clone())?;
 
-                    check_type(
-                        &arg,
-                        discovered_types,
-                        type_environment.clone(),
-                        context_type,
-                    )
-                })
-                .transpose()?;
-
-            let mut callee = callee;
-            let mut return_type = return_type;
-
-            if let Some(arg) = arg_typed_expression.clone() {
-                if let Type::Function(Function {
-                    param: Some(param), ..
-                }) = callee.get_type()
-                {
-                    if !type_equals(&param.type_, &arg.get_type()) {
-                        return Err(format!(
-                            "Argument type {} does not match parameter type {}",
-                            arg.get_type(),
-                            param.type_
-                        ));
-                    }
-                } else if let Type::Function(Function { param: None, .. }) = callee_type {
-                    callee = TypedExpression::Call {
-                        callee: Box::new(callee),
-                        argument: None,
-                        type_: return_type.clone(),
-                    };
-                    return_type = match return_type {
-                        Type::Function(Function { return_type, .. }) => *return_type,
-                        _ => {
-                            return Err(format!(
-                                "Expected function type with a return type, found {}",
-                                callee.get_type()
-                            ));
-                        }
-                    };
-                } else {
-                    return Err(format!(
-                        "Expected function type with a parameter, found {}",
-                        callee.get_type()
-                    ));
-                };
-            }
-
-            let arg = arg_typed_expression.clone().map(Box::new);
-
-            Ok(TypedExpression::Call {
-                callee: Box::new(callee),
-                argument: arg,
-                type_: return_type,
-            })
+            check_type_call(
+                call,
+                generic_callee.as_ref(),
+                discovered_types,
+                type_environment,
+                context,
+            )
         }
         Expression::VariableDeclaration(VariableDeclaration {
             mutable,
             type_annotation,
             pattern,
+            pattern_span,
             initializer,
         }) => {
             let mut type_ = Type::Unknown;
@@ -269,7 +220,15 @@ pub fn check_type(
                         .borrow()
                         .get_type_from_annotation(type_annotation)?;
 
-                    if !type_equals(&type_, &initializer.get_type()) {
+                    check_not_void(&initializer)?;
+
+                    if !type_equals(&type_, &initializer.get_type())
+                        && !variance::check_assignable(
+                            &type_,
+                            &initializer.get_type(),
+                            &type_environment.borrow(),
+                        )?
+                    {
                         return Err(format!(
                             "Initializer type {} does not match variable type {}",
                             initializer.get_type(),
@@ -287,6 +246,8 @@ pub fn check_type(
                         None,
                     )?;
 
+                    check_not_void(&initializer)?;
+
                     type_ = initializer.get_type();
 
                     if *mutable {
@@ -319,9 +280,21 @@ pub fn check_type(
                 Some(type_.clone()),
             )?;
 
+            if let Pattern::Variable(identifier) = pattern {
+                if *mutable {
+                    type_environment.borrow_mut().set_mutable(identifier);
+                }
+
+                if initializer.is_none() {
+                    type_environment.borrow_mut().set_uninitialized(identifier);
+                }
+            }
+
             Ok(TypedExpression::VariableDeclaration {
                 mutable: *mutable,
+                type_annotation: type_annotation.clone(),
                 pattern: pattern.clone(),
+                pattern_span: *pattern_span,
                 initializer: initializer.map(Box::new),
                 type_,
             })
@@ -373,14 +346,12 @@ pub fn check_type(
 
             let type_ = if let Some(else_type) = else_type {
                 if !is_option(&else_type) {
-                    if !type_equals(&if_block_type, &else_type) {
-                        return Err(format!(
+                    Type::unify(&if_block_type, &else_type).map_err(|_| {
+                        format!(
                             "If block type {:?} does not match else block type {:?}",
                             if_block_type, else_type
-                        ));
-                    }
-
-                    if_block_type.clone()
+                        )
+                    })?
                 } else {
                     Type::option_of(if_block_type.clone())
                 }
@@ -395,7 +366,11 @@ pub fn check_type(
                 type_,
             })
         }
-        Expression::Match(Match { expression, arms }) => {
+        Expression::Match(Match {
+            expression,
+            arms,
+            span,
+        }) => {
             let match_environment = Rc::new(RefCell::new(TypeEnvironment::new_parent(
                 type_environment.clone(),
             )));
@@ -424,24 +399,50 @@ pub fn check_type(
                 });
             }
 
-            let decision_tree = create_decision_tree(
-                expression.clone(),
-                typed_arms.clone(),
-                discovered_types,
-                None,
-            )?;
+            let decision_tree_cache = type_environment.borrow().decision_tree_cache();
+
+            let decision_tree = match decision_tree_cache {
+                Some(cache) => create_cached_decision_tree(
+                    &cache,
+                    expression.clone(),
+                    typed_arms.clone(),
+                    discovered_types,
+                    match_environment.clone(),
+                )?,
+                None => create_decision_tree(
+                    expression.clone(),
+                    typed_arms.clone(),
+                    discovered_types,
+                    None,
+                )?,
+            };
 
-            let type_ = decision_tree.get_type();
+            // The match has the common type of its arms, like an if has of its branches
+            let type_ = Type::unify_all(&arm_types(&decision_tree))
+                .map_err(|mismatch| format!("Match arms don't have a common type, {}", mismatch))?;
 
             Ok(TypedExpression::Match {
                 expression: Box::new(expression),
                 arms: typed_arms,
                 decision_tree,
                 type_,
+                span: *span,
             })
         }
         Expression::Assignment(Assignment {
             member,
+            operator: Some(operator),
+            initializer,
+        }) => check_type_compound_assignment(
+            member,
+            operator,
+            initializer,
+            discovered_types,
+            type_environment,
+        ),
+        Expression::Assignment(Assignment {
+            member,
+            operator: None,
             initializer,
         }) => {
             let identifier = member.get_symbol();
@@ -460,6 +461,14 @@ pub fn check_type(
                 None,
             )?;
 
+            check_not_void(&initializer)?;
+
+            let TypedExpression::Member(typed_member) = &member else {
+                return Err("Expected member expression".to_string());
+            };
+
+            check_assignable(typed_member, type_environment.clone())?;
+
             let mut member_type = member.get_type();
 
             if member_type == Type::Unknown {
@@ -467,16 +476,17 @@ pub fn check_type(
 
                 type_environment
                     .borrow_mut()
-                    .add_variable(identifier.clone(), member_type.clone());
+                    .infer_variable_type(&identifier, member_type.clone());
 
                 let TypedExpression::Member(mem) = member else {
                     return Err("Expected member expression".to_string());
                 };
 
                 let mem = match mem {
-                    Member::Identifier { symbol, .. } => Member::Identifier {
+                    Member::Identifier { symbol, span, .. } => Member::Identifier {
                         symbol,
                         type_: member_type.clone(),
+                        span,
                     },
                     ma => ma,
                 };
@@ -484,7 +494,13 @@ pub fn check_type(
                 member = TypedExpression::Member(mem);
             }
 
-            if !type_equals(&member_type, &initializer.get_type()) {
+            if !type_equals(&member_type, &initializer.get_type())
+                && !variance::check_assignable(
+                    &member_type,
+                    &initializer.get_type(),
+                    &type_environment.borrow(),
+                )?
+            {
                 return Err(format!(
                     "Member type {} does not match initializer type {}",
                     member.get_type(),
@@ -503,34 +519,75 @@ pub fn check_type(
             })
         }
         Expression::Member(member) => match member {
-            crate::parser::Member::Identifier { symbol, generics } => {
+            crate::parser::Member::Identifier {
+                symbol,
+                generics,
+                span,
+            } => {
+                type_environment.borrow().check_import_ambiguity(symbol)?;
+
                 let type_ = type_environment
                     .borrow()
                     .get_variable(symbol)
                     .or_else(|| {
-                        let type_ = type_environment.borrow().get_type(member);
-
-                        match (type_, generics) {
-                            (None, _) => None,
-                            (Some(type_), None) => Some(type_),
-                            (Some(type_), Some(generics)) => {
-                                return Some(
-                                    type_
-                                        .clone_with_concrete_types(
-                                            generics.iter().map(|g| g.type_annotation()).collect(),
-                                            type_environment.clone(),
-                                        )
-                                        .expect("Failed to clone type with concrete types"),
-                                );
-                            }
-                        }
+                        let Some(generics) = generics else {
+                            // An imported name refers to the item it was imported from, even if
+                            // another module declared the same name later
+                            return type_environment
+                                .borrow()
+                                .get_imported_type(symbol)
+                                .or_else(|| type_environment.borrow().get_type(member));
+                        };
+
+                        let concrete_types: Vec<TypeAnnotation> =
+                            generics.iter().map(|g| g.type_annotation()).collect();
+
+                        // Generic declarations are keyed by their name and number of generics
+                        let type_ = type_environment.borrow().get_type(member).or_else(|| {
+                            type_environment
+                                .borrow()
+                                .get_type(TypeAnnotation::ConcreteType(
+                                    symbol.clone(),
+                                    concrete_types.clone(),
+                                ))
+                        })?;
+
+                        Some(
+                            type_
+                                .clone_with_concrete_types(concrete_types, type_environment.clone())
+                                .expect("Failed to clone type with concrete types"),
+                        )
                     })
-                    .ok_or_else(|| format!("Unexpected variable: {}", symbol))?
+                    .ok_or_else(|| {
+                        format!(
+                            "Unexpected variable: {}{}",
+                            symbol,
+                            did_you_mean(type_environment.borrow().suggest_variable(symbol))
+                        )
+                    })?
                     .clone();
 
+                if let Some(generics) = generics {
+                    let concrete_types = generics
+                        .iter()
+                        .map(|g| {
+                            check_type_annotation(
+                                &g.type_annotation(),
+                                discovered_types,
+                                type_environment.clone(),
+                            )
+                        })
+                        .collect::<Result<Vec<Type>, String>>()?;
+
+                    type_environment
+                        .borrow()
+                        .check_where_clause(symbol, &concrete_types)?;
+                }
+
                 Ok(TypedExpression::Member(Member::Identifier {
                     symbol: symbol.clone(),
                     type_,
+                    span: *span,
                 }))
             }
             crate::parser::Member::StaticMemberAccess {
@@ -544,6 +601,17 @@ pub fn check_type(
                 member,
                 context,
             ),
+            crate::parser::Member::MemberAccess {
+                object,
+                member,
+                optional: true,
+                ..
+            } => check_type_optional_member_access(
+                object,
+                discovered_types,
+                type_environment,
+                member,
+            ),
             crate::parser::Member::MemberAccess { object, member, .. } => check_type_member_access(
                 object,
                 discovered_types,
@@ -560,15 +628,20 @@ pub fn check_type(
                     context,
                 )
             }
+            crate::parser::Member::Path(path, span) => {
+                check_type_path(path, *span, discovered_types, type_environment, context)
+            }
         },
-        Expression::Literal(l) => match l {
-            parser::Literal::Unit => Ok(TypedExpression::Literal(Literal::Unit)),
-            parser::Literal::Int(v) => Ok(TypedExpression::Literal(Literal::Int(*v))),
-            parser::Literal::UInt(v) => Ok(TypedExpression::Literal(Literal::UInt(*v))),
-            parser::Literal::Float(v) => Ok(TypedExpression::Literal(Literal::Float(*v))),
-            parser::Literal::String(v) => Ok(TypedExpression::Literal(Literal::String(v.clone()))),
-            parser::Literal::Char(v) => Ok(TypedExpression::Literal(Literal::Char(*v))),
-            parser::Literal::Bool(v) => Ok(TypedExpression::Literal(Literal::Bool(*v))),
+        Expression::Literal(l, span) => match l {
+            parser::Literal::Unit => Ok(TypedExpression::Literal(Literal::Unit, *span)),
+            parser::Literal::Int(v) => Ok(TypedExpression::Literal(Literal::Int(*v), *span)),
+            parser::Literal::UInt(v) => Ok(TypedExpression::Literal(Literal::UInt(*v), *span)),
+            parser::Literal::Float(v) => Ok(TypedExpression::Literal(Literal::Float(*v), *span)),
+            parser::Literal::String(v) => {
+                Ok(TypedExpression::Literal(Literal::String(v.clone()), *span))
+            }
+            parser::Literal::Char(v) => Ok(TypedExpression::Literal(Literal::Char(*v), *span)),
+            parser::Literal::Bool(v) => Ok(TypedExpression::Literal(Literal::Bool(*v), *span)),
             parser::Literal::Array(values) => {
                 let v: Result<(Vec<TypedExpression>, Type), String> = {
                     let mut v_: Vec<TypedExpression> = vec![];
@@ -605,14 +678,57 @@ pub fn check_type(
                     }
                 }
 
-                Ok(TypedExpression::Literal(Literal::Array {
-                    values: v.0,
-                    type_: target_type,
-                }))
+                Ok(TypedExpression::Literal(
+                    Literal::Array {
+                        values: v.0,
+                        type_: target_type,
+                    },
+                    *span,
+                ))
+            }
+            parser::Literal::ArrayRepeat { value, length } => {
+                let value = check_type(
+                    value,
+                    discovered_types,
+                    type_environment.clone(),
+                    match context {
+                        Some(Type::Array(inner)) => Some(*inner),
+                        _ => None,
+                    },
+                )?;
+
+                let length = check_type(length, discovered_types, type_environment.clone(), None)?;
+
+                let const_environment =
+                    constant::ConstEnvironment::new(type_environment.borrow().overflow_policy());
+
+                let length = match constant::eval_const(&length, &const_environment) {
+                    Ok(constant::ConstValue::Int(length)) if length >= 0 => length as usize,
+                    Ok(constant::ConstValue::UInt(length)) => length as usize,
+                    Ok(constant::ConstValue::Int(length)) => {
+                        return Err(format!("Array length {} must not be negative", length))
+                    }
+                    Ok(length) => {
+                        return Err(format!("Array length {} must be an integer", length))
+                    }
+                    Err(error) if error.kind == constant::EvalErrorKind::NotConstant => {
+                        return Err(format!("Array length {} is not a constant", length))
+                    }
+                    Err(error) => return Err(error.message),
+                };
+
+                Ok(TypedExpression::Literal(
+                    Literal::Array {
+                        type_: value.get_deep_type(),
+                        values: vec![value; length],
+                    },
+                    *span,
+                ))
             }
             parser::Literal::Struct {
                 type_annotation,
                 field_initializers,
+                spread,
             } => {
                 let field_initializers: Result<Vec<FieldInitializer>, String> = {
                     let mut field_initializers_: Vec<FieldInitializer> = vec![];
@@ -639,9 +755,64 @@ pub fn check_type(
                     Err(format!("{} is not a struct", type_.full_name()))?
                 };
 
-                let field_initializers = field_initializers?;
+                let mut field_initializers = field_initializers?;
+
+                let named = field_initializers
+                    .iter()
+                    .filter(|initializer| initializer.identifier.is_some())
+                    .count();
+
+                if named > 0 && named < field_initializers.len() {
+                    return Err(format!(
+                        "Cannot mix positional and named fields in literal of struct '{}'",
+                        type_
+                    ));
+                }
+
+                // Positional fields are bound in the order they are declared, so all of them
+                // have to be given
+                if named == 0
+                    && !field_initializers.is_empty()
+                    && field_initializers.len() != fields.len()
+                {
+                    return Err(format!(
+                        "Expected {} positional fields in literal of struct '{}' but got {}",
+                        fields.len(),
+                        type_,
+                        field_initializers.len()
+                    ));
+                }
+
+                let mut initialized = vec![];
+                let mut unknown = vec![];
+
+                for (index, initializer) in field_initializers.iter_mut().enumerate() {
+                    let field = match &initializer.identifier {
+                        Some(identifier) => get_field_by_name(&fields, identifier),
+                        None => fields.get(index),
+                    };
+
+                    let Some(field) = field else {
+                        unknown.push(initializer.identifier.clone().unwrap_or(index.to_string()));
+                        continue;
+                    };
+
+                    if initialized.contains(&field.field_name) {
+                        return Err(format!(
+                            "Field '{}' is initialized more than once in literal of struct '{}'",
+                            field.field_name, type_
+                        ));
+                    }
+
+                    initialized.push(field.field_name.clone());
+                    initializer.identifier = Some(field.field_name.clone());
+
+                    type_environment.borrow().check_visibility(&format!(
+                        "{}.{}",
+                        field.struct_name.name(),
+                        field.field_name
+                    ))?;
 
-                for (field, initializer) in fields.iter().zip(field_initializers.iter()) {
                     let field_type = field.field_type.clone();
                     let initializer_type = initializer.initializer.get_type();
 
@@ -653,11 +824,88 @@ pub fn check_type(
                     }
                 }
 
-                Ok(TypedExpression::Literal(Literal::Struct {
-                    type_annotation: type_annotation.clone(),
+                let spread = match spread {
+                    Some(spread) => {
+                        let spread = check_type(
+                            spread,
+                            discovered_types,
+                            type_environment.clone(),
+                            Some(type_.clone()),
+                        )?;
+
+                        if !type_equals(&type_, &spread.get_type()) {
+                            return Err(format!(
+                                "Cannot spread {} into a literal of struct '{}'",
+                                spread.get_type(),
+                                type_
+                            ));
+                        }
+
+                        Some(Box::new(spread))
+                    }
+                    None => None,
+                };
+
+                // Omitted fields are copied from the spread, or else take the default value from
+                // the struct declaration
+                let mut missing = vec![];
+
+                for field in fields.iter() {
+                    if spread.is_some() || initialized.contains(&field.field_name) {
+                        continue;
+                    }
+
+                    let Some(default) = type_environment.borrow().get_field_default(&format!(
+                        "{}.{}",
+                        field.struct_name.name(),
+                        field.field_name
+                    )) else {
+                        missing.push(field.field_name.clone());
+                        continue;
+                    };
+
+                    field_initializers.push(FieldInitializer {
+                        identifier: Some(field.field_name.clone()),
+                        initializer: default,
+                    });
+                }
+
+                // Every missing and unknown field is reported at once
+                let mut message = [("missing", missing), ("unknown", unknown)]
+                    .into_iter()
+                    .filter(|(_, names)| !names.is_empty())
+                    .map(|(problem, names)| format!("{} {}", problem, quoted_fields(&names)))
+                    .collect::<Vec<String>>()
+                    .join(" and ");
+
+                if !message.is_empty() {
+                    message[..1].make_ascii_uppercase();
+                    return Err(format!("{} in literal of struct '{}'", message, type_));
+                }
+
+                Ok(TypedExpression::Literal(
+                    Literal::Struct {
+                        type_annotation: type_annotation.clone(),
+                        field_initializers,
+                        spread,
+                        type_,
+                    },
+                    *span,
+                ))
+            }
+            parser::Literal::Enum {
+                type_annotation,
+                member,
+                field_initializers,
+            } if is_builtin_result_member(type_annotation, &type_environment) => {
+                check_type_result_literal(
+                    type_annotation,
+                    member,
                     field_initializers,
-                    type_,
-                }))
+                    discovered_types,
+                    type_environment,
+                    context,
+                )
             }
             parser::Literal::Enum {
                 type_annotation,
@@ -695,7 +943,12 @@ pub fn check_type(
                     .borrow()
                     .get_type_from_annotation(type_annotation)?;
 
-                let Type::EnumMember(EnumMember { fields, .. }) = &type_ else {
+                let Type::EnumMember(EnumMember {
+                    enum_name,
+                    discriminant_name,
+                    fields,
+                }) = &type_
+                else {
                     Err(format!(
                         "{} is not a member of {}",
                         member,
@@ -705,42 +958,106 @@ pub fn check_type(
 
                 let field_initializers = field_initializers?;
 
-                match field_initializers {
-                    EnumMemberFieldInitializers::None => (),
-                    EnumMemberFieldInitializers::Named(ref field_initializers) => {
-                        for (struct_field, (initializer_field_name, initializer)) in
-                            fields.iter().zip(field_initializers.iter())
-                        {
-                            let field_name = struct_field.field_name.clone();
-                            let field_type = struct_field.field_type.clone();
-
-                            if &field_name != initializer_field_name {
-                                return Err(format!(
-                                    "Field '{}' does not match initializer field '{}'",
-                                    field_name, initializer_field_name
-                                ));
-                            }
+                // Every field of the member is initialized, including the fields it shares with
+                // the other members of the enum
+                let initializers = match &field_initializers {
+                    EnumMemberFieldInitializers::None => None,
+                    EnumMemberFieldInitializers::Named(initializers) => Some(initializers),
+                };
 
-                            let initializer_type = initializer.get_type();
+                let mut missing = vec![];
 
-                            if !type_equals(&field_type, &initializer_type) {
-                                return Err(format!(
-                                    "Field type {} does not match initializer type {}",
-                                    field_type, initializer_type
-                                ));
-                            }
-                        }
+                for field in fields {
+                    let Some(initializer) =
+                        initializers.and_then(|initializers| initializers.get(&field.field_name))
+                    else {
+                        missing.push(field.field_name.clone());
+                        continue;
+                    };
+
+                    let initializer_type = initializer.get_type();
+
+                    if !type_equals(&field.field_type, &initializer_type) {
+                        return Err(format!(
+                            "Field type {} does not match initializer type {}",
+                            field.field_type, initializer_type
+                        ));
                     }
                 }
 
-                Ok(TypedExpression::Literal(Literal::Enum {
-                    type_annotation: type_annotation.clone(),
-                    member: member.clone(),
-                    field_initializers,
-                    type_,
-                }))
+                let mut unknown: Vec<String> = initializers
+                    .into_iter()
+                    .flat_map(|initializers| initializers.keys())
+                    .filter(|name| !fields.iter().any(|field| &field.field_name == *name))
+                    .cloned()
+                    .collect();
+
+                unknown.sort();
+
+                let mut message = [("missing", missing), ("unknown", unknown)]
+                    .into_iter()
+                    .filter(|(_, names)| !names.is_empty())
+                    .map(|(problem, names)| format!("{} {}", problem, quoted_fields(&names)))
+                    .collect::<Vec<String>>()
+                    .join(" and ");
+
+                if !message.is_empty() {
+                    message[..1].make_ascii_uppercase();
+                    return Err(format!(
+                        "{} in literal of enum member '{}::{}'",
+                        message, enum_name, discriminant_name
+                    ));
+                }
+
+                Ok(TypedExpression::Literal(
+                    Literal::Enum {
+                        type_annotation: type_annotation.clone(),
+                        member: member.clone(),
+                        field_initializers,
+                        type_,
+                    },
+                    *span,
+                ))
             }
         },
+        Expression::Record(fields) => {
+            let expected_fields = match context.clone().map(Type::unsubstitute) {
+                Some(Type::Record(expected_fields)) => expected_fields,
+                _ => vec![],
+            };
+
+            let mut typed_fields = vec![];
+            let mut field_types = vec![];
+
+            for field in fields {
+                let Some(identifier) = &field.identifier else {
+                    return Err("Expected field name in record".to_string());
+                };
+
+                let field_context = expected_fields
+                    .iter()
+                    .find(|(name, _)| name == identifier)
+                    .map(|(_, type_)| type_.clone());
+
+                let initializer = check_type(
+                    &field.initializer,
+                    discovered_types,
+                    type_environment.clone(),
+                    field_context,
+                )?;
+
+                field_types.push((identifier.clone(), initializer.get_type()));
+                typed_fields.push(FieldInitializer {
+                    identifier: Some(identifier.clone()),
+                    initializer,
+                });
+            }
+
+            Ok(TypedExpression::Record {
+                fields: typed_fields,
+                type_: Type::Record(field_types),
+            })
+        }
         Expression::Tuple(elements) => {
             let typed_elements = elements
                 .iter()
@@ -761,9 +1078,14 @@ pub fn check_type(
                 type_: Type::Tuple(types),
             })
         }
+        Expression::Index(index) => check_type_index(index, discovered_types, type_environment),
         Expression::Unary(unary) => {
-            let expression =
-                check_type(&unary.expression, discovered_types, type_environment, None)?;
+            let expression = check_type(
+                &unary.expression,
+                discovered_types,
+                type_environment.clone(),
+                None,
+            )?;
             let type_ = expression.get_deep_type();
 
             let operator = match unary.operator {
@@ -775,50 +1097,110 @@ pub fn check_type(
 
             let type_ = get_unop_type(&operator, &type_)?;
 
+            let overflow_policy = type_environment.borrow().overflow_policy();
+            let constant = constant::fold_unary(&operator, &expression, overflow_policy)?;
+
             Ok(TypedExpression::Unary {
                 operator,
                 expression: Box::new(expression),
+                constant,
+                span: unary.span,
                 type_: type_.clone(),
             })
         }
-        Expression::Binary(Binary {
-            left,
-            operator,
-            right,
-        }) => {
-            let left = check_type(left, discovered_types, type_environment.clone(), None)?;
-            let right = check_type(right, discovered_types, type_environment, None)?;
+        Expression::Binary(binary) => {
+            // A chain like `1 + 2 + 3` nests to the left, so it is checked from its innermost
+            // operation outwards instead of recursively, which makes it one level of nesting
+            let mut chain = vec![binary];
+            let mut innermost = binary.left.as_ref();
+
+            while let Expression::Binary(binary) = innermost {
+                chain.push(binary);
+                innermost = binary.left.as_ref();
+            }
 
-            let operator: BinaryOperator = operator.clone().into();
-            let type_ = get_binop_type(&left.get_type(), &operator, &right.get_type())?;
+            let mut left = check_type(innermost, discovered_types, type_environment.clone(), None)?;
 
-            if matches!(
-                operator,
-                BinaryOperator::Range | BinaryOperator::RangeInclusive
-            ) && !type_equals_coerce(&right.get_type(), &left.get_type())
-            {
+            for binary in chain.into_iter().rev() {
+                left = check_type_binary(left, binary, discovered_types, type_environment.clone())?;
+            }
+
+            Ok(left)
+        }
+        Expression::Try(expression) => {
+            check_type_try(expression, discovered_types, type_environment)
+        }
+        Expression::Coalesce(Coalesce { value, default }) => {
+            let value = check_type(value, discovered_types, type_environment.clone(), None)?;
+            let value_type = value.get_type().unsubstitute();
+
+            let Some(inner_type) = value_type.option_inner().cloned() else {
                 return Err(format!(
-                    "Range operator requires both sides to be of the same type, found {} and {}",
-                    left.get_type(),
-                    right.get_type()
+                    "Cannot use ?? on {}, it is not an option",
+                    value_type
+                ));
+            };
+
+            // An option made from a literal like `if b => 1` can default to any Int
+            let inner_type = match inner_type {
+                Type::Literal { type_, .. } => *type_,
+                inner_type => inner_type,
+            };
+
+            let default = check_type(
+                default,
+                discovered_types,
+                type_environment,
+                Some(inner_type.clone()),
+            )?;
+
+            if !type_equals(&inner_type, &default.get_type()) {
+                return Err(format!(
+                    "Default of ?? must have type {}, found {}",
+                    inner_type,
+                    default.get_type()
                 ));
             }
 
-            Ok(TypedExpression::Binary {
-                left: Box::new(left),
-                operator,
-                right: Box::new(right),
+            let some = TypedExpression::Member(Member::Identifier {
+                symbol: INNER_VALUE.to_string(),
+                type_: inner_type.clone(),
+                span: Span::default(),
+            });
+
+            Ok(option_match(value, some, default, inner_type))
+        }
+        Expression::Cast(Cast {
+            expression,
+            target,
+            span,
+        }) => {
+            let expression =
+                check_type(expression, discovered_types, type_environment.clone(), None)?;
+            let type_ = check_type_annotation(target, discovered_types, type_environment.clone())?;
+
+            check_cast(&expression, &type_, &type_environment)?;
+
+            Ok(TypedExpression::Cast {
+                expression: Box::new(expression),
+                target: target.clone(),
+                span: *span,
                 type_,
             })
         }
         Expression::Block(statements) => {
+            // Variables and type aliases declared in the block go out of scope with it
+            let block_environment = Rc::new(RefCell::new(TypeEnvironment::new_parent(
+                type_environment.clone(),
+            )));
+
             let mut typed_statements: Vec<TypedStatement> = vec![];
 
             for statement in statements {
                 typed_statements.push(statements::check_type(
                     statement,
                     discovered_types,
-                    type_environment.clone(),
+                    block_environment.clone(),
                 )?);
             }
 
@@ -844,7 +1226,13 @@ pub fn check_type(
             let type_ = type_environment
                 .borrow()
                 .get_variable(symbol)
-                .ok_or_else(|| format!("Unexpected variable: {}", symbol))?
+                .ok_or_else(|| {
+                    format!(
+                        "Unexpected variable: {}{}",
+                        symbol,
+                        did_you_mean(type_environment.borrow().suggest_variable(symbol))
+                    )
+                })?
                 .clone();
 
             Ok(TypedExpression::Drop {
@@ -906,37 +1294,23 @@ pub fn check_type(
                 Some(else_block) => Some(check_type(
                     else_block,
                     discovered_types,
-                    while_and_else_environment,
+                    while_and_else_environment.clone(),
                     None,
                 )?),
                 None => None,
             };
 
-            let mut type_ = while_environment
-                .borrow()
-                .get_scope(&ScopeType::Break)
-                .map(|scope| scope.fold())
-                .unwrap_or(Ok(Type::Void))?;
-
-            match &else_body {
-                Some(else_body) => {
-                    let else_type = else_body.get_type();
-
-                    if !type_equals(&type_, &Type::Void) && !type_equals(&type_, &else_type) {
-                        return Err(format!("While block breaks with value of type {} which does not match else blocks type {}", type_, else_body.get_type()));
-                    }
+            if else_body.is_some()
+                && matches!(condition, TypedExpression::Literal(Literal::Bool(true), _))
+            {
+                while_and_else_environment.borrow_mut().add_warning(
+                    "unreachable_else",
+                    "The else block of a while loop whose condition is always true is unreachable"
+                        .to_string(),
+                );
+            }
 
-                    type_ = else_type
-                }
-                None => {
-                    if !type_equals(&type_, &Type::Void) {
-                        return Err(
-                            "Must have an else block if the while block breaks with a value"
-                                .to_string(),
-                        );
-                    }
-                }
-            };
+            let type_ = check_loop_else_type("while", &while_environment, else_body.as_ref())?;
 
             Ok(TypedExpression::While {
                 condition: Box::new(condition),
@@ -947,6 +1321,7 @@ pub fn check_type(
         }
         Expression::For(For {
             identifier,
+            identifier_span,
             iterable,
             body,
             else_body,
@@ -959,23 +1334,30 @@ pub fn check_type(
                 ScopeType::Break,
             )));
 
-            let iterable = check_type(
-                iterable,
-                discovered_types,
-                for_and_else_environment.clone(),
-                None,
-            )?;
-
-            let Type::Array(inner_type) = iterable.get_type() else {
-                return Err(format!(
-                    "For iterable must be of type array, found {}",
-                    iterable.get_type()
-                ));
+            let iterable = match iterable.as_ref() {
+                Expression::Binary(
+                    range @ Binary {
+                        operator:
+                            parser::BinaryOperator::Range | parser::BinaryOperator::RangeInclusive,
+                        ..
+                    },
+                ) => {
+                    check_type_for_range(range, discovered_types, for_and_else_environment.clone())?
+                }
+                iterable => check_type(
+                    iterable,
+                    discovered_types,
+                    for_and_else_environment.clone(),
+                    None,
+                )?,
             };
 
+            let (element_type, next) =
+                check_iterable(&iterable, identifier, for_and_else_environment.clone())?;
+
             for_environment
                 .borrow_mut()
-                .add_variable(identifier.clone(), *inner_type);
+                .add_variable(identifier.clone(), element_type);
 
             let body = check_type(body, discovered_types, for_environment.clone(), None)?;
 
@@ -989,37 +1371,13 @@ pub fn check_type(
                 None => None,
             };
 
-            let mut type_ = for_environment
-                .borrow()
-                .get_scope(&ScopeType::Break)
-                .map(|scope| scope.fold())
-                .unwrap_or(Ok(Type::Void))?;
-
-            match &else_body {
-                Some(else_body) => {
-                    let else_type = else_body.get_type();
-
-                    if !type_equals(&type_, &Type::Void)
-                        && !type_equals(&type_, &else_body.get_type())
-                    {
-                        return Err(format!("For block breaks with value of type {} which does not match else blocks type {}", type_, else_body.get_type()));
-                    }
-
-                    type_ = else_type
-                }
-                None => {
-                    if !type_equals(&type_, &Type::Void) {
-                        return Err(
-                            "Must have an else block if the for block breaks with a value"
-                                .to_string(),
-                        );
-                    }
-                }
-            };
+            let type_ = check_loop_else_type("for", &for_environment, else_body.as_ref())?;
 
             Ok(TypedExpression::For {
                 identifier: identifier.clone(),
+                identifier_span: *identifier_span,
                 iterable: Box::new(iterable),
+                next: next.map(Box::new),
                 body: Box::new(body),
                 else_body: else_body.map(Box::new),
                 type_: type_.clone(),
@@ -1028,6 +1386,511 @@ pub fn check_type(
     }
 }
 
+/// An unsuffixed Int literal next to a UInt is read as a UInt, so that `n + 1` adds two UInts
+fn unsigned_literal_operands(
+    left: TypedExpression,
+    right: TypedExpression,
+) -> (TypedExpression, TypedExpression) {
+    let is_uint =
+        |expression: &TypedExpression| matches!(expression.get_type().unsubstitute(), Type::UInt);
+
+    let unsigned = |expression: TypedExpression| match expression {
+        TypedExpression::Literal(Literal::Int(value), span) if value >= 0 => {
+            TypedExpression::Literal(Literal::UInt(value as u64), span)
+        }
+        expression => expression,
+    };
+
+    if is_uint(&left) {
+        (left, unsigned(right))
+    } else if is_uint(&right) {
+        (unsigned(left), right)
+    } else {
+        (left, right)
+    }
+}
+
+/// Find the type of a while or for loop. The else block runs when the loop ends without a break, so
+/// it has to give a value of the same type as the breaks. A loop which breaks without a value can
+/// only have an else block without one.
+fn check_loop_else_type(
+    loop_name: &str,
+    loop_environment: &Rcrc<TypeEnvironment>,
+    else_body: Option<&TypedExpression>,
+) -> Result<Type, String> {
+    let (breaks, break_type) = match loop_environment.borrow().get_own_scope(&ScopeType::Break) {
+        Some(scope) => (scope.active(), scope.fold()?),
+        None => (false, Type::Void),
+    };
+
+    let Some(else_body) = else_body else {
+        if !type_equals(&break_type, &Type::Void) {
+            return Err(format!(
+                "Must have an else block if the {} block breaks with a value",
+                loop_name
+            ));
+        }
+
+        return Ok(break_type);
+    };
+
+    let else_type = else_body.get_type();
+
+    // The loop has the common type of the values it breaks with and its else block
+    if !type_equals(&break_type, &Type::Void) {
+        return Type::unify_all([&break_type, &else_type]).map_err(|_| {
+            format!(
+                "{}{} block breaks with value of type {} which does not match else blocks type {}",
+                loop_name[..1].to_uppercase(),
+                &loop_name[1..],
+                break_type,
+                else_type
+            )
+        });
+    }
+
+    if breaks
+        && type_equals(&break_type, &Type::Void)
+        && !matches!(else_type, Type::Void | Type::Unit)
+    {
+        return Err(format!(
+            "The {} block breaks without a value, so its else block can't have type {}",
+            loop_name, else_type
+        ));
+    }
+
+    Ok(else_type)
+}
+
+/// The types of the arm bodies a decision tree can end in
+fn arm_types(decision: &Decision) -> Vec<Type> {
+    match decision {
+        Decision::Success { type_, .. } => vec![type_.clone()],
+        Decision::Failure { .. } => vec![],
+        Decision::Guard {
+            consequence,
+            alternative,
+            ..
+        } => [arm_types(consequence), arm_types(alternative)].concat(),
+        Decision::Switch {
+            cases, fallback, ..
+        } => cases
+            .iter()
+            .flat_map(|case| arm_types(&case.body))
+            .chain(arm_types(fallback))
+            .collect(),
+    }
+}
+
+/// Check a range iterated by a for loop. The loop variable gets the type both endpoints have once
+/// their literal types are dropped, so `0..10u` is an error rather than a range of either type.
+/// Check a call, which applies one argument. The function of a call to a generic function whose
+/// type arguments are inferred is given as `generic_callee`, and is applied by the innermost call.
+fn check_type_call(
+    call: &parser::Call,
+    generic_callee: Option<&TypedExpression>,
+    discovered_types: &Vec<DiscoveredType>,
+    type_environment: Rc<RefCell<TypeEnvironment>>,
+    context: Option<Type>,
+) -> Result<TypedExpression, String> {
+    let callee = match (call.callee.as_ref(), generic_callee) {
+        (Expression::Call(callee), Some(_)) => check_type_call(
+            callee,
+            generic_callee,
+            discovered_types,
+            type_environment.clone(),
+            context.clone(),
+        )?,
+        (_, Some(generic_callee)) => generic_callee.clone(),
+        (callee, None) => check_type(
+            callee,
+            discovered_types,
+            type_environment.clone(),
+            context.clone(),
+        )?,
+    };
+
+    // The receiver is already applied, so calling a method which takes nothing else is
+    // the same as accessing it
+    if let (
+        Expression::Member(
+            parser::Member::MemberAccess { .. } | parser::Member::ParamPropagation { .. },
+        ),
+        TypedExpression::Call { .. },
+        None,
+    ) = (call.callee.as_ref(), &callee, &call.argument)
+    {
+        return Ok(callee);
+    }
+
+    let callee_type = callee.get_type();
+
+    if let Type::Newtype(Newtype {
+        type_identifier,
+        underlying_type,
+    }) = &callee_type
+    {
+        let Some(argument) = &call.argument else {
+            return Err(format!(
+                "Newtype {} must be constructed from a value of type {}",
+                type_identifier, underlying_type
+            ));
+        };
+
+        let argument = check_type(
+            argument,
+            discovered_types,
+            type_environment.clone(),
+            Some(*underlying_type.clone()),
+        )?;
+
+        if !type_equals(underlying_type, &argument.get_type()) {
+            return Err(format!(
+                "Newtype {} must be constructed from a value of type {}, found {}",
+                type_identifier,
+                underlying_type,
+                argument.get_type()
+            ));
+        }
+
+        return Ok(TypedExpression::Call {
+            callee: Box::new(callee.clone()),
+            argument: Some(Box::new(argument)),
+            tail_call: false,
+            span: call.span,
+            type_: callee_type.clone(),
+        });
+    }
+
+    if !matches!(&callee_type, &Type::Function(_)) {
+        return Err(format!(
+            "Expected function type, found {}",
+            callee.get_type()
+        ));
+    }
+
+    let return_type = match callee_type.clone() {
+        Type::Function(Function { return_type, .. }) => *return_type,
+        _ => {
+            return Err(format!(
+                "Expected function type, found {}",
+                callee.get_type()
+            ))
+        }
+    };
+
+    let arg_typed_expression = call
+        .argument
+        .clone()
+        .map(|arg| {
+            let context_type = if let Type::Function(Function { param, .. }) = callee_type.clone() {
+                param.map(|p| *p.type_)
+            } else {
+                None
+            };
+
+            check_type(
+                &arg,
+                discovered_types,
+                type_environment.clone(),
+                context_type,
+            )
+        })
+        .transpose()?;
+
+    let mut callee = callee;
+    let mut return_type = return_type;
+
+    if let Some(arg) = arg_typed_expression.clone() {
+        if let Type::Function(Function {
+            param: Some(param), ..
+        }) = callee.get_type()
+        {
+            if !type_equals(&param.type_, &arg.get_type()) {
+                return Err(format!(
+                    "Argument type {} does not match parameter type {}",
+                    arg.get_type(),
+                    param.type_
+                ));
+            }
+        } else if let Type::Function(Function { param: None, .. }) = callee_type {
+            callee = TypedExpression::Call {
+                callee: Box::new(callee),
+                argument: None,
+                tail_call: false,
+                span: Span::default(),
+                type_: return_type.clone(),
+            };
+            return_type = match return_type {
+                Type::Function(Function { return_type, .. }) => *return_type,
+                _ => {
+                    return Err(format!(
+                        "Expected function type with a return type, found {}",
+                        callee.get_type()
+                    ));
+                }
+            };
+        } else {
+            return Err(format!(
+                "Expected function type with a parameter, found {}",
+                callee.get_type()
+            ));
+        };
+    }
+
+    let arg = arg_typed_expression.clone().map(Box::new);
+
+    Ok(TypedExpression::Call {
+        callee: Box::new(callee),
+        argument: arg,
+        tail_call: false,
+        span: call.span,
+        type_: return_type,
+    })
+}
+
+/// The function of a call to a generic function written without type arguments, with the type
+/// arguments inferred from the types of the arguments. Calls to anything else are checked as
+/// they are.
+fn infer_generic_callee(
+    call: &parser::Call,
+    discovered_types: &Vec<DiscoveredType>,
+    type_environment: Rc<RefCell<TypeEnvironment>>,
+) -> Result<Option<TypedExpression>, String> {
+    // Every argument is applied by its own call, so the function is the callee of the innermost
+    let mut arguments = vec![call.argument.as_deref()];
+    let mut innermost = call;
+
+    while let Expression::Call(callee) = innermost.callee.as_ref() {
+        arguments.push(callee.argument.as_deref());
+        innermost = callee;
+    }
+
+    arguments.reverse();
+
+    let Expression::Member(parser::Member::Identifier {
+        symbol,
+        generics: None,
+        span,
+    }) = innermost.callee.as_ref()
+    else {
+        return Ok(None);
+    };
+
+    if type_environment.borrow().get_variable(symbol).is_some()
+        || type_environment.borrow().get_type(symbol).is_some()
+    {
+        return Ok(None);
+    }
+
+    let Some(function) = type_environment.borrow().get_generic_function(symbol) else {
+        return Ok(None);
+    };
+
+    let Type::Function(Function {
+        identifier: Some(TypeIdentifier::GenericType(_, generics)),
+        ..
+    }) = &function
+    else {
+        return Ok(None);
+    };
+
+    let mut inferred: Vec<(GenericType, Type)> = vec![];
+    let mut applied = function.clone();
+
+    for argument in arguments.into_iter().flatten() {
+        let Type::Function(Function {
+            param: Some(param),
+            return_type,
+            ..
+        }) = applied
+        else {
+            break;
+        };
+
+        let argument = check_type(argument, discovered_types, type_environment.clone(), None)?;
+
+        // A literal argument stands for any value of its type
+        let argument_type = match argument.get_type() {
+            Type::Literal { type_, .. } => *type_,
+            type_ => type_,
+        };
+
+        infer_generics(symbol, &param.type_, &argument_type, &mut inferred)?;
+        applied = *return_type;
+    }
+
+    let concrete_types = generics
+        .iter()
+        .map(|generic| {
+            inferred
+                .iter()
+                .find(|(inferred, _)| inferred == generic)
+                .map(|(_, type_)| type_.clone())
+                .ok_or_else(|| {
+                    format!(
+                        "Cannot infer generic type '{}' of '{}' from its arguments",
+                        generic, symbol
+                    )
+                })
+        })
+        .collect::<Result<Vec<Type>, String>>()?;
+
+    type_environment
+        .borrow()
+        .check_where_clause(symbol, &concrete_types)?;
+
+    let type_ = function.clone_with_concrete_types(
+        concrete_types.iter().map(Type::type_annotation).collect(),
+        type_environment.clone(),
+    )?;
+
+    Ok(Some(TypedExpression::Member(Member::Identifier {
+        symbol: symbol.clone(),
+        type_,
+        span: *span,
+    })))
+}
+
+/// Solve the generics in the type of a parameter by matching it against the type of the
+/// argument passed to it, with the solutions found so far in `inferred`
+fn infer_generics(
+    function: &str,
+    parameter: &Type,
+    argument: &Type,
+    inferred: &mut Vec<(GenericType, Type)>,
+) -> Result<(), String> {
+    match (parameter, argument) {
+        (Type::Generic(generic), argument) => {
+            match inferred.iter().find(|(inferred, _)| inferred == generic) {
+                Some((_, type_)) if !type_equals(type_, argument) => Err(format!(
+                    "Generic type '{}' of '{}' is inferred as both {} and {}",
+                    generic, function, type_, argument
+                )),
+                Some(_) => Ok(()),
+                None => {
+                    inferred.push((generic.clone(), argument.clone()));
+                    Ok(())
+                }
+            }
+        }
+        (Type::Array(parameter), Type::Array(argument)) => {
+            infer_generics(function, parameter, argument, inferred)
+        }
+        (Type::Tuple(parameters), Type::Tuple(arguments)) => {
+            for (parameter, argument) in parameters.iter().zip(arguments) {
+                infer_generics(function, parameter, argument, inferred)?;
+            }
+
+            Ok(())
+        }
+        (
+            Type::Function(Function {
+                param: parameter_param,
+                return_type: parameter_return,
+                ..
+            }),
+            Type::Function(Function {
+                param: argument_param,
+                return_type: argument_return,
+                ..
+            }),
+        ) => {
+            if let (Some(parameter), Some(argument)) = (parameter_param, argument_param) {
+                infer_generics(function, &parameter.type_, &argument.type_, inferred)?;
+            }
+
+            infer_generics(function, parameter_return, argument_return, inferred)
+        }
+        // Anything else is checked against the parameter once the generics are replaced
+        _ => Ok(()),
+    }
+}
+
+fn check_type_for_range(
+    range: &Binary,
+    discovered_types: &Vec<DiscoveredType>,
+    type_environment: Rcrc<TypeEnvironment>,
+) -> Result<TypedExpression, String> {
+    let left = check_type(
+        &range.left,
+        discovered_types,
+        type_environment.clone(),
+        None,
+    )?;
+
+    let right = check_type(&range.right, discovered_types, type_environment, None)?;
+
+    let endpoint_type = |expression: &TypedExpression| match expression.get_type() {
+        Type::Literal { type_, .. } => *type_,
+        type_ => type_,
+    };
+
+    let element_type = match (endpoint_type(&left), endpoint_type(&right)) {
+        (Type::Int, Type::Int) => Type::Int,
+        (Type::UInt, Type::UInt) => Type::UInt,
+        (Type::Char, Type::Char) => Type::Char,
+        (left_type, right_type) => {
+            return Err(format!(
+                "Range endpoints must have matching Int, UInt or Char types, found {} and {}",
+                left_type, right_type
+            ))
+        }
+    };
+
+    Ok(TypedExpression::Binary {
+        left: Box::new(left),
+        operator: range.operator.clone().into(),
+        right: Box::new(right),
+        constant: None,
+        type_: Type::Array(Box::new(element_type)),
+        span: range.span,
+    })
+}
+
+/// Find the element type of the iterable of a for loop. Arrays, and so ranges, are iterable as they
+/// are. Any other type has to have a `next(self): (T, Self)?` method, which gives the next element
+/// along with an iterator over the elements after it, or None when there are no more.
+fn check_iterable(
+    iterable: &TypedExpression,
+    identifier: &str,
+    type_environment: Rcrc<TypeEnvironment>,
+) -> Result<(Type, Option<TypedExpression>), String> {
+    let iterable_type = iterable.get_type().unsubstitute();
+
+    if let Type::Array(element_type) = iterable_type {
+        return Ok((*element_type, None));
+    }
+
+    let Some((callee, method_type)) =
+        find_method(&iterable_type, "next", Span::default(), type_environment)?
+    else {
+        return Err(format!(
+            "'{}' is not iterable, it has no method 'next'",
+            iterable_type
+        ));
+    };
+
+    let iterator = TypedExpression::Member(Member::Identifier {
+        symbol: identifier.to_owned(),
+        type_: iterable_type.clone(),
+        span: Span::default(),
+    });
+
+    let next = apply_receiver(callee, method_type, &iterable_type, "next", iterator)?;
+    let next_type = next.get_type();
+
+    match next_type.option_inner() {
+        Some(Type::Tuple(types)) if types.len() == 2 && type_equals(&types[1], &iterable_type) => {
+            Ok((types[0].clone(), Some(next)))
+        }
+        _ => Err(format!(
+            "'next' of '{}' must return (T, {})?, found {}",
+            iterable_type, iterable_type, next_type
+        )),
+    }
+}
+
 fn is_option(type_: &Type) -> bool {
     let Type::Enum(Enum {
         type_identifier,
@@ -1048,13 +1911,13 @@ fn is_option(type_: &Type) -> bool {
                 return false;
             }
 
-            return members.get("Some").map_or(false, |member| {
+            members.get("Some").is_some_and(|member| {
                 let Type::EnumMember(EnumMember { fields, .. }) = member else {
                     return false;
                 };
 
-                return get_field_by_name(fields, "f0").map_or(false, |_| true);
-            });
+                get_field_by_name(fields, "f0").is_some()
+            })
         }
         _ => false,
     }
@@ -1070,12 +1933,15 @@ fn check_type_static_member_access(
     let object_type =
         check_type_annotation(type_annotation, discovered_types, type_environment.clone())?;
 
-    return match member.clone() {
-        parser::Member::Identifier { symbol, .. } => match object_type {
+    match member.clone() {
+        parser::Member::Identifier { symbol, span, .. } => match object_type {
             Type::Struct(struct_) => {
-                let Some(static_member_type) = type_environment
-                    .borrow()
-                    .get_static_member(struct_.type_annotation(), &symbol)
+                let Some((static_member, _)) = find_method(
+                    &Type::Struct(struct_.clone()),
+                    &symbol,
+                    span,
+                    type_environment,
+                )?
                 else {
                     return Err(format!(
                         "Struct '{}' does not have a static member called '{}'",
@@ -1083,17 +1949,7 @@ fn check_type_static_member_access(
                     ));
                 };
 
-                let identifier_type = static_member_type.clone();
-
-                Ok(TypedExpression::Member(Member::StaticMemberAccess {
-                    type_annotation: type_annotation.clone(),
-                    member: Box::new(Member::Identifier {
-                        symbol: symbol.clone(),
-                        type_: identifier_type.clone(),
-                    }),
-                    symbol: symbol.clone(),
-                    type_: static_member_type.clone(),
-                }))
+                Ok(static_member)
             }
             Type::EnumMember(enum_member) => {
                 let Some(static_member_type) = type_environment
@@ -1120,6 +1976,7 @@ fn check_type_static_member_access(
                     member: Box::new(Member::Identifier {
                         symbol: symbol.clone(),
                         type_: identifier_type.clone(),
+                        span,
                     }),
                     symbol: symbol.clone(),
                     type_: static_member_type.clone(),
@@ -1150,6 +2007,7 @@ fn check_type_static_member_access(
                     member: Box::new(Member::Identifier {
                         symbol: symbol.clone(),
                         type_: identifier_type.clone(),
+                        span,
                     }),
                     symbol: symbol.clone(),
                     type_: static_member_type.clone(),
@@ -1186,26 +2044,743 @@ fn check_type_static_member_access(
             type_environment,
             context,
         ),
+        parser::Member::Path(path, _) => Err(format!("Unexpected path: {}", path.join("::"))),
+    }
+}
+
+/// Check a path like `math::abs` or `geometry::Point::new` by resolving its module first.
+/// The item after the module is visibility checked, and is either the value itself or the type
+/// owning the static member at the end of the path.
+fn check_type_path(
+    path: &[String],
+    span: Span,
+    discovered_types: &Vec<DiscoveredType>,
+    type_environment: Rcrc<TypeEnvironment>,
+    context: Option<Type>,
+) -> Result<TypedExpression, String> {
+    let (module_path, item_path) = imports::resolve_path(path, type_environment.clone())?;
+
+    match item_path.as_slice() {
+        [item] => {
+            let item_full_path = [module_path.as_slice(), std::slice::from_ref(item)].concat();
+
+            let type_ = type_environment
+                .borrow()
+                .get_module_item_type(&item_full_path)
+                .filter(|_| !module_path.is_empty())
+                .or_else(|| type_environment.borrow().get_variable(item))
+                .or_else(|| type_environment.borrow().get_type(item))
+                .ok_or_else(|| format!("Unexpected variable: {}", path.join("::")))?;
+
+            Ok(TypedExpression::Member(Member::Identifier {
+                symbol: item.clone(),
+                type_,
+                span,
+            }))
+        }
+        [type_name, member] => check_type_static_member_access(
+            &TypeAnnotation::Type(type_name.clone()),
+            discovered_types,
+            type_environment,
+            &parser::Member::Identifier {
+                symbol: member.clone(),
+                generics: None,
+                span,
+            },
+            context,
+        ),
+        _ => Err(format!(
+            "Unresolved path '{}': '{}' in {} is not a module",
+            path.join("::"),
+            item_path[0],
+            module_name(&module_path)
+        )),
+    }
+}
+
+fn check_type_member_access(
+    object: &Expression,
+    discovered_types: &Vec<DiscoveredType>,
+    type_environment: Rcrc<TypeEnvironment>,
+    member: &parser::Member,
+    context: Option<Type>,
+) -> Result<TypedExpression, String> {
+    let object_type_expression =
+        check_type(object, discovered_types, type_environment.clone(), None)?;
+    let object_type = object_type_expression.get_type().unsubstitute();
+    check_type_member_access_recurse(
+        object_type.clone(),
+        member,
+        type_environment,
+        object_type_expression,
+        discovered_types,
+        context,
+    )
+}
+
+/// The binding for the value of an option or result while `?.`, `??` or `?` looks inside it
+const INNER_VALUE: &str = "?value";
+
+/// The binding for the error of a result while `?` returns it
+const RESULT_ERROR: &str = "?error";
+
+/// Check `object?.member`. The object has to be an option, and the member is accessed on its
+/// inner value which is bound to [`INNER_VALUE`]. The result is an option again, but a member
+/// which is an option itself is not wrapped a second time so chains stay flat.
+fn check_type_optional_member_access(
+    object: &Expression,
+    discovered_types: &Vec<DiscoveredType>,
+    type_environment: Rcrc<TypeEnvironment>,
+    member: &parser::Member,
+) -> Result<TypedExpression, String> {
+    let object = check_type(object, discovered_types, type_environment.clone(), None)?;
+    let object_type = object.get_type().unsubstitute();
+
+    let Some(inner_type) = object_type.option_inner().cloned() else {
+        return Err(format!(
+            "Cannot use ?. on {}, it is not an option",
+            object_type
+        ));
+    };
+
+    let inner_value = TypedExpression::Member(Member::Identifier {
+        symbol: INNER_VALUE.to_string(),
+        type_: inner_type.clone(),
+        span: Span::default(),
+    });
+
+    let access = check_type_member_access_recurse(
+        inner_type.clone(),
+        member,
+        type_environment,
+        inner_value,
+        discovered_types,
+        None,
+    )?;
+
+    let access_type = access.get_type().unsubstitute();
+
+    let (some, type_) = if access_type.option_inner().is_some() {
+        (access, access_type)
+    } else {
+        let type_ = Type::option_of(access_type);
+        (option_literal("Some", Some(access), &type_), type_)
+    };
+
+    let none = option_literal("None", None, &type_);
+    Ok(option_match(object, some, none, type_))
+}
+
+/// Check `expression?`. The expression has to be a result, and gives its value when it is `Ok`.
+/// An `Err` is returned from the enclosing function, which therefore has to return a result with
+/// the same error type.
+fn check_type_try(
+    expression: &Expression,
+    discovered_types: &Vec<DiscoveredType>,
+    type_environment: Rcrc<TypeEnvironment>,
+) -> Result<TypedExpression, String> {
+    let expression = check_type(expression, discovered_types, type_environment.clone(), None)?;
+    let expression_type = expression.get_type().unsubstitute();
+
+    let Some((value_type, error_type)) = expression_type.result_types() else {
+        return Err(format!(
+            "Cannot use ? on {}, it is not a result",
+            expression_type
+        ));
+    };
+
+    let Some(return_type) = type_environment.borrow().get_return_type() else {
+        return Err("? can only be used inside a function".to_string());
+    };
+
+    let Some((_, return_error_type)) = return_type.result_types() else {
+        return Err(format!(
+            "? can only be used in a function which returns a result, not {}",
+            return_type
+        ));
     };
+
+    if !type_equals(return_error_type, error_type) {
+        return Err(format!(
+            "Error type {} of ? does not match the error type {} the function returns",
+            error_type, return_error_type
+        ));
+    }
+
+    let value = TypedExpression::Member(Member::Identifier {
+        symbol: INNER_VALUE.to_string(),
+        type_: value_type.clone(),
+        span: Span::default(),
+    });
+
+    let error = TypedExpression::Member(Member::Identifier {
+        symbol: RESULT_ERROR.to_string(),
+        type_: error_type.clone(),
+        span: Span::default(),
+    });
+
+    let early_return = TypedExpression::Return(Some(Box::new(builtin_enum_literal(
+        "Result",
+        "Err",
+        Some(("error", error)),
+        &return_type,
+    ))));
+
+    let value_type = value_type.clone();
+
+    Ok(builtin_enum_match(
+        expression,
+        vec![
+            ("Ok", "value", INNER_VALUE, value),
+            ("Err", "error", RESULT_ERROR, early_return),
+        ],
+        Decision::Failure {
+            error_message: "No match found".to_string(),
+        },
+        value_type,
+    ))
+}
+
+/// Lower `member <op>= value` to `member = member <op> value`. Bitwise operators only take
+/// integers here, even where the operator itself is defined for other types.
+fn check_type_compound_assignment(
+    member: &parser::Member,
+    operator: &parser::BinaryOperator,
+    initializer: &Expression,
+    discovered_types: &Vec<DiscoveredType>,
+    type_environment: Rcrc<TypeEnvironment>,
+) -> Result<TypedExpression, String> {
+    let typed_operator: BinaryOperator = operator.clone().into();
+
+    if let parser::Member::Identifier { symbol, .. } = member {
+        if !type_environment.borrow().is_mutable(symbol) {
+            return Err(immutable_variable(symbol));
+        }
+    }
+
+    if typed_operator.category() == BinaryOperatorCategory::Bitwise {
+        let member_type = check_type(
+            &Expression::Member(member.clone()),
+            discovered_types,
+            type_environment.clone(),
+            None,
+        )?
+        .get_type();
+
+        if !member_type.is_integer() {
+            return Err(format!(
+                "Compound assignment {}= needs an Int or UInt, found {}",
+                typed_operator, member_type
+            ));
+        }
+    }
+
+    check_type(
+        &Expression::Assignment(Assignment {
+            member: Box::new(member.clone()),
+            operator: None,
+            initializer: Box::new(Expression::Binary(Binary {
+                left: Box::new(Expression::Member(member.clone())),
+                operator: operator.clone(),
+                right: Box::new(initializer.clone()),
+                span: Span::default(),
+            })),
+        }),
+        discovered_types,
+        type_environment,
+        None,
+    )
+}
+
+/// Check that every level of an assignment target like `a.b.c` may be assigned to. The variable
+/// at the root has to be `let mut`, or declared without an initializer, and each field on the way
+/// has to be a `mut` struct field.
+fn check_type_binary(
+    left: TypedExpression,
+    binary: &Binary,
+    discovered_types: &Vec<DiscoveredType>,
+    type_environment: Rcrc<TypeEnvironment>,
+) -> Result<TypedExpression, String> {
+    let Binary {
+        operator,
+        right,
+        span,
+        ..
+    } = binary;
+
+    let operator: BinaryOperator = operator.clone().into();
+
+    let right_environment = match operator.category() {
+        BinaryOperatorCategory::Logical => Rc::new(RefCell::new(short_circuit_environment(
+            &left,
+            &operator,
+            type_environment.clone(),
+        ))),
+        _ => type_environment.clone(),
+    };
+
+    let right = check_type(right, discovered_types, right_environment, None)?;
+
+    let (left, right) = match operator.category() {
+        BinaryOperatorCategory::Range => (left, right),
+        _ => unsigned_literal_operands(left, right),
+    };
+
+    check_binop_operands(&left.get_type(), &operator, &right.get_type())?;
+    let type_ = get_binop_type(&left.get_type(), &operator, &right.get_type())?;
+
+    if matches!(
+        operator,
+        BinaryOperator::Range | BinaryOperator::RangeInclusive
+    ) && !type_equals_coerce(&right.get_type(), &left.get_type())
+    {
+        return Err(format!(
+            "Range operator requires both sides to be of the same type, found {} and {}",
+            left.get_type(),
+            right.get_type()
+        ));
+    }
+
+    // The operands were folded when they were checked, so only this operator is evaluated
+    let overflow_policy = type_environment.borrow().overflow_policy();
+    let constant = constant::fold_binary(&left, &operator, &right, overflow_policy)?;
+
+    let binary = TypedExpression::Binary {
+        left: Box::new(left),
+        operator,
+        right: Box::new(right),
+        constant,
+        span: *span,
+        type_,
+    };
+
+    constant::check_division_by_zero(&binary)?;
+
+    Ok(binary)
+}
+
+fn check_assignable(
+    member: &Member,
+    type_environment: Rcrc<TypeEnvironment>,
+) -> Result<(), String> {
+    match member {
+        Member::Identifier { symbol, .. } => {
+            let type_environment = type_environment.borrow();
+
+            if type_environment.is_mutable(symbol) || type_environment.is_uninitialized(symbol) {
+                Ok(())
+            } else {
+                Err(immutable_variable(symbol))
+            }
+        }
+        Member::MemberAccess {
+            object,
+            symbol,
+            type_,
+            ..
+        } => {
+            let Type::Struct(struct_) = object.get_type().unsubstitute() else {
+                return Err(format!(
+                    "Cannot assign to field '{}' of {}, only struct fields can be assigned",
+                    symbol,
+                    object.get_type()
+                ));
+            };
+
+            let struct_name = struct_.type_identifier.name();
+
+            if !type_environment
+                .borrow()
+                .is_mutable_field(&format!("{}.{}", struct_name, symbol))
+            {
+                return Err(format!(
+                    "Cannot assign to immutable field '{}', declare it as '{}: mut {}' in struct '{}'",
+                    symbol, symbol, type_, struct_name
+                ));
+            }
+
+            let TypedExpression::Member(object) = object.as_ref() else {
+                return Err(format!(
+                    "Cannot assign to field '{}' of a temporary value",
+                    symbol
+                ));
+            };
+
+            check_assignable(object, type_environment)
+        }
+        Member::StaticMemberAccess { .. } => Err("Cannot assign to static member".to_owned()),
+    }
+}
+
+fn immutable_variable(symbol: &str) -> String {
+    if symbol == "self" {
+        return "Cannot assign to immutable self, declare the parameter as 'mut self'".to_string();
+    }
+
+    format!(
+        "Cannot assign to immutable variable '{}', declare it with 'let mut {}'",
+        symbol, symbol
+    )
+}
+
+fn check_type_index(
+    index: &parser::Index,
+    discovered_types: &Vec<DiscoveredType>,
+    type_environment: Rcrc<TypeEnvironment>,
+) -> Result<TypedExpression, String> {
+    let callee = check_type(
+        &index.callee,
+        discovered_types,
+        type_environment.clone(),
+        None,
+    )?;
+
+    // Strings are indexed by character, and slicing one gives the string of those characters
+    let (element_type, slice_type) = match callee.get_type().unsubstitute() {
+        Type::Array(element_type) => (*element_type.clone(), Type::Array(element_type)),
+        Type::String => (Type::Char, Type::String),
+        Type::Literal { type_, .. } if *type_ == Type::String => (Type::Char, Type::String),
+        _ => {
+            return Err(format!(
+                "Cannot index {}, it is not an array or a string",
+                callee.get_type()
+            ))
+        }
+    };
+
+    let argument = check_type(
+        &index.argument,
+        discovered_types,
+        type_environment.clone(),
+        None,
+    )?;
+
+    if let TypedExpression::Binary {
+        left,
+        operator: operator @ (BinaryOperator::Range | BinaryOperator::RangeInclusive),
+        right,
+        ..
+    } = &argument
+    {
+        if !left.get_type().is_integer() {
+            return Err(format!(
+                "Slice bounds must be Int or UInt, found {}",
+                left.get_type()
+            ));
+        }
+
+        check_slice_bounds(
+            &callee,
+            left,
+            operator,
+            right,
+            type_environment.borrow().overflow_policy(),
+        )?;
+
+        return Ok(TypedExpression::Index {
+            callee: Box::new(callee),
+            argument: Box::new(argument),
+            type_: slice_type,
+            span: index.span,
+        });
+    }
+
+    if !argument.get_type().is_integer() {
+        return Err(format!(
+            "Index must be an Int or UInt, found {}",
+            argument.get_type()
+        ));
+    }
+
+    check_index_bound(
+        &callee,
+        &argument,
+        type_environment.borrow().overflow_policy(),
+    )?;
+
+    Ok(TypedExpression::Index {
+        callee: Box::new(callee),
+        argument: Box::new(argument),
+        type_: element_type,
+        span: index.span,
+    })
+}
+
+/// The length and kind of an array or string literal. A string is as long as its characters.
+fn literal_length(callee: &TypedExpression) -> Option<(usize, &'static str)> {
+    match callee {
+        TypedExpression::Literal(Literal::Array { values, .. }, _) => {
+            Some((values.len(), "an array"))
+        }
+        TypedExpression::Literal(Literal::String(value), _) => {
+            Some((value.chars().count(), "a string"))
+        }
+        _ => None,
+    }
+}
+
+/// Check a constant index into an array or string literal
+fn check_index_bound(
+    callee: &TypedExpression,
+    argument: &TypedExpression,
+    overflow_policy: OverflowPolicy,
+) -> Result<(), String> {
+    let Some((length, kind)) = literal_length(callee) else {
+        return Ok(());
+    };
+
+    let index = match constant::evaluate_constant(argument, overflow_policy)? {
+        Some(Literal::Int(value)) => value as i128,
+        Some(Literal::UInt(value)) => value as i128,
+        _ => return Ok(()),
+    };
+
+    if index < 0 || index >= length as i128 {
+        return Err(format!(
+            "Index {} is out of range for {} of length {}",
+            index, kind, length
+        ));
+    }
+
+    Ok(())
+}
+
+/// Check the bounds of a slice which are constant. The end can only be checked against the length
+/// of an array or string literal, others are checked when the slice is evaluated.
+fn check_slice_bounds(
+    callee: &TypedExpression,
+    left: &TypedExpression,
+    operator: &BinaryOperator,
+    right: &TypedExpression,
+    overflow_policy: OverflowPolicy,
+) -> Result<(), String> {
+    let bound = |expression| -> Result<Option<i128>, String> {
+        Ok(
+            match constant::evaluate_constant(expression, overflow_policy)? {
+                Some(Literal::Int(value)) => Some(value as i128),
+                Some(Literal::UInt(value)) => Some(value as i128),
+                _ => None,
+            },
+        )
+    };
+
+    let (Some(start), Some(end)) = (bound(left)?, bound(right)?) else {
+        return Ok(());
+    };
+
+    let range = format!("{}{}{}", left, operator, right);
+    let end = match operator {
+        BinaryOperator::RangeInclusive => end + 1,
+        _ => end,
+    };
+
+    if start < 0 {
+        return Err(format!("Slice {} must not start before 0", range));
+    }
+
+    if start > end {
+        return Err(format!("Slice {} ends before it starts", range));
+    }
+
+    if let Some((length, kind)) = literal_length(callee) {
+        if end > length as i128 {
+            return Err(format!(
+                "Slice {} is out of range for {} of length {}",
+                range, kind, length
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether an enum literal like `Result::Ok` is a member of the built-in result, which is only
+/// the case while no enum called Result has been declared
+fn is_builtin_result_member(
+    type_annotation: &TypeAnnotation,
+    type_environment: &Rcrc<TypeEnvironment>,
+) -> bool {
+    type_annotation.name().starts_with("Result::")
+        && type_environment
+            .borrow()
+            .get_type_from_annotation(type_annotation)
+            .is_err()
 }
 
-fn check_type_member_access(
-    object: &Expression,
+/// Check `Result::Ok { value: .. }` or `Result::Err { error: .. }`. The types of the result
+/// are written like `Result<T, E>::Ok`, or are taken from the context when they are left out.
+fn check_type_result_literal(
+    type_annotation: &TypeAnnotation,
+    member: &str,
+    field_initializers: &parser::EnumMemberFieldInitializers,
     discovered_types: &Vec<DiscoveredType>,
     type_environment: Rcrc<TypeEnvironment>,
-    member: &parser::Member,
     context: Option<Type>,
 ) -> Result<TypedExpression, String> {
-    let object_type_expression =
-        check_type(object, discovered_types, type_environment.clone(), None)?;
-    let object_type = object_type_expression.get_type();
-    check_type_member_access_recurse(
-        object_type.clone(),
-        member,
-        type_environment,
-        object_type_expression,
+    let type_ = match type_annotation {
+        TypeAnnotation::ConcreteType(_, concrete_types) => match concrete_types.as_slice() {
+            [value, error] => Type::result_of(
+                check_type_annotation(value, discovered_types, type_environment.clone())?,
+                check_type_annotation(error, discovered_types, type_environment.clone())?,
+            ),
+            _ => {
+                return Err(format!(
+                    "Result takes 2 types but {} were given",
+                    concrete_types.len()
+                ))
+            }
+        },
+        _ => match context.map(Type::unsubstitute) {
+            Some(context) if context.result_types().is_some() => context,
+            _ => {
+                return Err(format!(
+                    "Cannot infer the types of {}, write them like Result<T, E>::{}",
+                    type_annotation, member
+                ))
+            }
+        },
+    };
+
+    let Some((value_type, error_type)) = type_.result_types() else {
+        unreachable!("Result types were just created or checked")
+    };
+
+    let (field, field_type) = match member {
+        "Ok" => ("value", value_type.clone()),
+        "Err" => ("error", error_type.clone()),
+        _ => return Err(format!("{} is not a member of Result", member)),
+    };
+
+    let initializer = match field_initializers {
+        parser::EnumMemberFieldInitializers::Named(field_initializers)
+            if field_initializers.len() == 1 =>
+        {
+            field_initializers.get(field)
+        }
+        _ => None,
+    };
+
+    let Some(initializer) = initializer else {
+        return Err(format!(
+            "Result::{} takes a single field called '{}'",
+            member, field
+        ));
+    };
+
+    let initializer = check_type(
+        initializer,
         discovered_types,
-        context,
+        type_environment,
+        Some(field_type.clone()),
+    )?;
+
+    if !type_equals(&field_type, &initializer.get_type()) {
+        return Err(format!(
+            "Field type {} does not match initializer type {}",
+            field_type,
+            initializer.get_type()
+        ));
+    }
+
+    Ok(builtin_enum_literal(
+        "Result",
+        member,
+        Some((field, initializer)),
+        &type_,
+    ))
+}
+
+/// A match on an option which evaluates `some` with the inner value bound to [`INNER_VALUE`],
+/// and `none` otherwise
+fn option_match(
+    option: TypedExpression,
+    some: TypedExpression,
+    none: TypedExpression,
+    type_: Type,
+) -> TypedExpression {
+    builtin_enum_match(
+        option,
+        vec![("Some", "v", INNER_VALUE, some)],
+        Decision::Success {
+            expression: Box::new(none),
+            type_: type_.clone(),
+        },
+        type_,
+    )
+}
+
+/// A match on a built-in enum like Option or Result. Every case is a member, the field of
+/// the member, the name the field is bound to and the body evaluated with it.
+fn builtin_enum_match(
+    value: TypedExpression,
+    cases: Vec<(&str, &str, &str, TypedExpression)>,
+    fallback: Decision,
+    type_: Type,
+) -> TypedExpression {
+    let decision_tree = Decision::Switch {
+        variable: Box::new(Variable {
+            identifier: INNER_VALUE.to_string(),
+            accessor: Accessor::Environment,
+            type_: value.get_type(),
+            provenance: vec![],
+        }),
+        cases: cases
+            .into_iter()
+            .map(|(member, field, binding, body)| Case {
+                pattern: Pattern::Constructor(Constructor::Struct {
+                    type_annotation: TypeAnnotation::Type(member.to_string()),
+                    field_patterns: vec![FieldPattern {
+                        identifier: field.to_string(),
+                        pattern: Pattern::Variable(binding.to_string()),
+                    }],
+                }),
+                arguments: vec![],
+                body: Decision::Success {
+                    expression: Box::new(body),
+                    type_: type_.clone(),
+                },
+            })
+            .collect(),
+        fallback: Box::new(fallback),
+        type_: type_.clone(),
+    };
+
+    TypedExpression::Match {
+        expression: Box::new(value),
+        arms: vec![],
+        decision_tree,
+        type_,
+        span: Span::default(),
+    }
+}
+
+/// `Some` or `None` of an option, built the same way the interpreter builds the result of an if
+/// without an else
+fn option_literal(member: &str, value: Option<TypedExpression>, type_: &Type) -> TypedExpression {
+    builtin_enum_literal("Option", member, value.map(|value| ("v", value)), type_)
+}
+
+/// A member of a built-in enum. The enum is named without its types, so values compare equal
+/// no matter which types were written for them.
+fn builtin_enum_literal(
+    enum_name: &str,
+    member: &str,
+    field: Option<(&str, TypedExpression)>,
+    type_: &Type,
+) -> TypedExpression {
+    TypedExpression::Literal(
+        Literal::Enum {
+            type_annotation: TypeAnnotation::ConcreteType(enum_name.to_string(), vec![]),
+            member: member.to_string(),
+            field_initializers: match field {
+                Some((field, value)) => {
+                    EnumMemberFieldInitializers::Named(HashMap::from([(field.to_string(), value)]))
+                }
+                None => EnumMemberFieldInitializers::None,
+            },
+            type_: type_.clone(),
+        },
+        Span::default(),
     )
 }
 
@@ -1218,15 +2793,25 @@ fn check_type_member_access_recurse(
     context: Option<Type>,
 ) -> Result<TypedExpression, String> {
     match member.clone() {
-        parser::Member::Identifier { symbol, .. } => match object_type {
+        parser::Member::Identifier { symbol, span, .. } => match object_type {
             Type::Struct(struct_) => {
-                let field_type = get_field_by_name(&struct_.fields, &symbol)
-                    .ok_or(format!(
-                        "Struct '{}' does not have a field called '{}'",
-                        struct_.type_identifier, symbol
-                    ))?
-                    .field_type
-                    .clone();
+                let Some(field) = get_field_by_name(&struct_.fields, &symbol) else {
+                    return check_type_method_access(
+                        Type::Struct(struct_.clone()),
+                        &symbol,
+                        span,
+                        type_environment,
+                        object_typed_expression,
+                    );
+                };
+
+                let field_type = field.field_type.clone();
+
+                type_environment.borrow().check_visibility(&format!(
+                    "{}.{}",
+                    struct_.type_identifier.name(),
+                    symbol
+                ))?;
 
                 if !type_environment.borrow().lookup_type(&field_type) {
                     return Err(format!("Unexpected type: {}", field_type.full_name()));
@@ -1239,6 +2824,27 @@ fn check_type_member_access_recurse(
                     member: Box::new(Member::Identifier {
                         symbol: symbol.clone(),
                         type_: identifier_type.clone(),
+                        span,
+                    }),
+                    symbol: symbol.clone(),
+                    type_: field_type.clone(),
+                }))
+            }
+            Type::Record(fields) => {
+                let Some((_, field_type)) = fields.iter().find(|(name, _)| *name == symbol) else {
+                    return Err(format!(
+                        "Record '{}' does not have a field called '{}'",
+                        Type::Record(fields.clone()),
+                        symbol
+                    ));
+                };
+
+                Ok(TypedExpression::Member(Member::MemberAccess {
+                    object: Box::new(object_typed_expression),
+                    member: Box::new(Member::Identifier {
+                        symbol: symbol.clone(),
+                        type_: field_type.clone(),
+                        span,
                     }),
                     symbol: symbol.clone(),
                     type_: field_type.clone(),
@@ -1266,6 +2872,7 @@ fn check_type_member_access_recurse(
                     member: Box::new(Member::Identifier {
                         symbol: symbol.clone(),
                         type_: identifier_type.clone(),
+                        span,
                     }),
                     symbol: symbol.clone(),
                     type_: field_type.clone(),
@@ -1284,6 +2891,12 @@ fn check_type_member_access_recurse(
                     .field_type
                     .clone();
 
+                type_environment.borrow().check_visibility(&format!(
+                    "{}.{}",
+                    type_identifier.name(),
+                    symbol
+                ))?;
+
                 if !type_environment.borrow().lookup_type(&field_type) {
                     return Err(format!("Unexpected type: {}", field_type.full_name()));
                 }
@@ -1295,11 +2908,41 @@ fn check_type_member_access_recurse(
                     member: Box::new(Member::Identifier {
                         symbol: symbol.clone(),
                         type_: identifier_type.clone(),
+                        span,
                     }),
                     symbol: symbol.clone(),
                     type_: field_type.clone(),
                 }))
             }
+            Type::Newtype(Newtype {
+                type_identifier,
+                underlying_type,
+            }) => {
+                if symbol != "value" {
+                    return Err(format!(
+                        "Newtype '{}' does not have a field called '{}'. Unwrap it with '.value' first",
+                        type_identifier, symbol
+                    ));
+                }
+
+                Ok(TypedExpression::Member(Member::MemberAccess {
+                    object: Box::new(object_typed_expression),
+                    member: Box::new(Member::Identifier {
+                        symbol: symbol.clone(),
+                        type_: *underlying_type.clone(),
+                        span,
+                    }),
+                    symbol: symbol.clone(),
+                    type_: *underlying_type,
+                }))
+            }
+            Type::Generic(generic) => check_type_generic_method_access(
+                generic,
+                &symbol,
+                span,
+                type_environment,
+                object_typed_expression,
+            ),
             _ => Err(format!(
                 "Unexpected member access: {} on type {}",
                 symbol,
@@ -1331,49 +2974,219 @@ fn check_type_member_access_recurse(
             type_environment,
             context,
         ),
+        parser::Member::Path(path, _) => Err(format!("Unexpected path: {}", path.join("::"))),
     }
 }
 
-fn check_type_param_propagation(
-    object: &Expression,
-    member: &parser::Member,
-    discovered_types: &Vec<DiscoveredType>,
+/// Resolve `object.name` when the type of the object has no field called name. The object is
+/// passed as the self receiver of an inherent method of its type, then of a function given to it by
+/// a protocol. Failing both, it becomes the first argument of a free function, the same as
+/// `name(object)` would.
+fn check_type_method_access(
+    object_type: Type,
+    symbol: &str,
+    span: Span,
     type_environment: Rcrc<TypeEnvironment>,
-    context: Option<Type>,
+    object_typed_expression: TypedExpression,
 ) -> Result<TypedExpression, String> {
-    let parser::Member::Identifier { .. } = member.clone() else {
-        return Err("Param propagation must be followed by a member access".to_string());
+    if let Some((callee, method_type)) =
+        find_method(&object_type, symbol, span, type_environment.clone())?
+    {
+        if !matches!(
+            &method_type,
+            Type::Function(Function { param: Some(param), .. }) if param.identifier == "self"
+        ) {
+            return Err(format!(
+                "'{}::{}' does not take self and must be called as a static member",
+                object_type.type_annotation(),
+                symbol
+            ));
+        }
+
+        return apply_receiver(
+            callee,
+            method_type,
+            &object_type,
+            symbol,
+            object_typed_expression,
+        );
+    }
+
+    if let Some((callee, function_type)) = find_function(symbol, span, type_environment) {
+        return apply_receiver(
+            callee,
+            function_type,
+            &object_type,
+            symbol,
+            object_typed_expression,
+        );
+    }
+
+    Err(format!(
+        "'{}' has no field, method or function called '{}'",
+        object_type, symbol
+    ))
+}
+
+/// Resolve `value.name` when the value has a generic type. Nothing is known about the type it
+/// is given except the protocols its constraints require, so only their functions can be called
+/// on it.
+fn check_type_generic_method_access(
+    generic: GenericType,
+    symbol: &str,
+    span: Span,
+    type_environment: Rcrc<TypeEnvironment>,
+    object_typed_expression: TypedExpression,
+) -> Result<TypedExpression, String> {
+    let object_type = Type::Generic(generic.clone());
+
+    if find_method(&object_type, symbol, span, type_environment.clone())?.is_some() {
+        return check_type_method_access(
+            object_type,
+            symbol,
+            span,
+            type_environment,
+            object_typed_expression,
+        );
+    }
+
+    let type_environment = type_environment.borrow();
+
+    let suggestion = match type_environment.get_protocols_with_function(symbol).first() {
+        Some(protocol) => format!(". Add 'where {}: {}'", generic.type_name, protocol),
+        None => String::new(),
     };
 
-    let object_type_expression =
-        check_type(object, discovered_types, type_environment.clone(), context)?;
+    if type_environment
+        .get_static_members(&object_type.type_annotation())
+        .is_empty()
+    {
+        return Err(format!(
+            "Generic type '{}' has no constraints, so it has no method called '{}'{}",
+            generic.type_name, symbol, suggestion
+        ));
+    }
 
-    let object_type = object_type_expression.get_type();
+    Err(format!(
+        "The constraints on generic type '{}' do not guarantee a method called '{}'{}",
+        generic.type_name, symbol, suggestion
+    ))
+}
+
+/// Find an inherent method of a type, or else a function of a protocol the type implements.
+/// The span is where the name of the method is written.
+fn find_method(
+    object_type: &Type,
+    symbol: &str,
+    span: Span,
+    type_environment: Rcrc<TypeEnvironment>,
+) -> Result<Option<(TypedExpression, Type)>, String> {
+    let type_annotation = object_type.type_annotation();
 
-    let member_type = type_environment.borrow().get_type(member).or_else(|| {
-        type_environment
+    let inherent = type_environment
+        .borrow()
+        .get_static_member(type_annotation.clone(), symbol)
+        .map(|method_type| (type_annotation, method_type));
+
+    let method = match inherent {
+        Some(method) => Some(method),
+        None => type_environment
             .borrow()
-            .get_static_member(object_type.type_annotation(), member)
+            .get_protocol_member(object_type, symbol)?,
+    };
+
+    Ok(method.map(|(owner, method_type)| {
+        let callee = TypedExpression::Member(Member::StaticMemberAccess {
+            type_annotation: owner,
+            member: Box::new(Member::Identifier {
+                symbol: symbol.to_owned(),
+                type_: method_type.clone(),
+                span,
+            }),
+            symbol: symbol.to_owned(),
+            type_: method_type.clone(),
+        });
+
+        (callee, method_type)
+    }))
+}
+
+fn find_function(
+    symbol: &str,
+    span: Span,
+    type_environment: Rcrc<TypeEnvironment>,
+) -> Option<(TypedExpression, Type)> {
+    let function_type = type_environment
+        .borrow()
+        .get_variable(symbol)
+        .or_else(|| type_environment.borrow().get_type(symbol))
+        .filter(|type_| matches!(type_, Type::Function(_)))?;
+
+    let callee = TypedExpression::Member(Member::Identifier {
+        symbol: symbol.to_owned(),
+        type_: function_type.clone(),
+        span,
     });
 
-    let Some(Type::Function(Function { param, .. })) = member_type else {
-        return Err(format!("{} is not a function", member));
+    Some((callee, function_type))
+}
+
+/// Call a function with the object as its first argument, leaving the rest of its parameters
+fn apply_receiver(
+    callee: TypedExpression,
+    function_type: Type,
+    object_type: &Type,
+    symbol: &str,
+    object_typed_expression: TypedExpression,
+) -> Result<TypedExpression, String> {
+    let Type::Function(Function {
+        param, return_type, ..
+    }) = function_type
+    else {
+        return Err(format!("{} is not a function", symbol));
     };
 
     let Some(param) = param else {
         Err(format!(
             "Function {} must have at least one parameter",
-            member
+            symbol
         ))?
     };
 
-    if !type_equals(&param.type_, &object_type) {
+    if !type_equals(&param.type_, object_type) {
         Err(format!(
             "Function '{}' must be called on type {}. Found {}",
-            member, param.type_, object_type
+            symbol, param.type_, object_type
         ))?
     }
 
+    Ok(TypedExpression::Call {
+        callee: Box::new(callee),
+        argument: Some(Box::new(object_typed_expression)),
+        tail_call: false,
+        type_: *return_type,
+        span: Span::default(),
+    })
+}
+
+/// Check `object:name`, the explicit form of a uniform function call. Unlike `object.name` it
+/// never reads a field, and it looks for a free function before the methods of the type.
+fn check_type_param_propagation(
+    object: &Expression,
+    member: &parser::Member,
+    discovered_types: &Vec<DiscoveredType>,
+    type_environment: Rcrc<TypeEnvironment>,
+    context: Option<Type>,
+) -> Result<TypedExpression, String> {
+    let parser::Member::Identifier { .. } = member.clone() else {
+        return Err("Param propagation must be followed by a member access".to_string());
+    };
+
+    let object_type_expression =
+        check_type(object, discovered_types, type_environment.clone(), context)?;
+
+    let object_type = object_type_expression.get_type();
+
     check_type_param_propagation_recurse(
         object_type.clone(),
         member,
@@ -1389,58 +3202,32 @@ fn check_type_param_propagation_recurse(
     object_typed_expression: TypedExpression,
 ) -> Result<TypedExpression, String> {
     match member.clone() {
-        parser::Member::Identifier { symbol, .. } => {
-            let type_ = type_environment
-                .borrow()
-                .get_variable(&symbol)
-                .or_else(|| type_environment.borrow().get_type(&symbol))
-                .or_else(|| {
-                    type_environment
-                        .borrow()
-                        .get_static_member(object_type.type_annotation(), &symbol)
-                })
-                .ok_or_else(|| {
-                    format!(
-                        "Unexpected member access: {} on type {}",
-                        symbol,
-                        object_type.full_name()
-                    )
-                })?;
-
-            let Type::Function(Function { param, .. }) = type_.clone() else {
-                return Err(format!("{} is not a function", symbol));
-            };
-
-            let Some(param) = param else {
-                Err(format!(
-                    "Function {} must have at least one parameter",
-                    symbol
-                ))?
+        parser::Member::Identifier { symbol, span, .. } => {
+            let found = match find_function(&symbol, span, type_environment.clone()) {
+                Some(function) => Some(function),
+                None => find_method(&object_type, &symbol, span, type_environment)?,
             };
 
-            if !type_equals(&param.type_, &object_type) {
-                Err(format!(
-                    "Function '{}' must be called on type {}. Found {}",
-                    symbol, param.type_, object_type
-                ))?
-            }
-
-            let Type::Function(Function { return_type, .. }) = type_.clone() else {
-                return Err(format!("{} is not a function", symbol));
+            let Some((callee, function_type)) = found else {
+                return Err(format!(
+                    "Unexpected member access: {} on type {}",
+                    symbol,
+                    object_type.full_name()
+                ));
             };
 
-            Ok(TypedExpression::Call {
-                callee: Box::new(TypedExpression::Member(Member::Identifier {
-                    symbol: symbol.clone(),
-                    type_: type_.clone(),
-                })),
-                argument: Some(Box::new(object_typed_expression)),
-                type_: *return_type,
-            })
+            apply_receiver(
+                callee,
+                function_type,
+                &object_type,
+                &symbol,
+                object_typed_expression,
+            )
         }
         parser::Member::StaticMemberAccess { .. } => todo!("Static member access"),
         parser::Member::MemberAccess { .. } => todo!("Member access"),
         parser::Member::ParamPropagation { .. } => todo!("Param propagation"),
+        parser::Member::Path(path, _) => Err(format!("Unexpected path: {}", path.join("::"))),
     }
 }
 
@@ -1501,13 +3288,12 @@ fn check_type_pattern(
                 }
                 Type::EnumMember(EnumMember {
                     discriminant_name, ..
-                }) => {
-                    if type_annotation_equals(
-                        type_annotation,
-                        &TypeAnnotation::Type(discriminant_name.clone()),
-                    ) {
-                        is_enum_member = true;
-                    }
+                }) if type_annotation_equals(
+                    type_annotation,
+                    &TypeAnnotation::Type(discriminant_name.clone()),
+                ) =>
+                {
+                    is_enum_member = true;
                 }
                 _ => {}
             }
@@ -1574,6 +3360,7 @@ fn check_type_pattern(
                         member: Box::new(Member::Identifier {
                             symbol: field_name.clone(),
                             type_: field_type.clone(),
+                            span: Span::default(),
                         }),
                         symbol: field_name.clone(),
                         type_: field_type.clone(),
@@ -1608,9 +3395,7 @@ fn get_unop_type(operator: &UnaryOperator, operand: &Type) -> Result<Type, Strin
         (UnaryOperator::Negate, Type::Int) => Ok(Type::Int),
         (UnaryOperator::Negate, Type::UInt) => Ok(Type::UInt),
         (UnaryOperator::Negate, Type::Float) => Ok(Type::Float),
-        (UnaryOperator::Negate, Type::Literal { name, type_ })
-            if matches!(**type_, Type::Int | Type::UInt | Type::Float) =>
-        {
+        (UnaryOperator::Negate, Type::Literal { name, type_ }) if type_.is_numeric() => {
             let mut buf = String::new();
             buf.push('-');
             buf.push_str(name);
@@ -1620,6 +3405,7 @@ fn get_unop_type(operator: &UnaryOperator, operand: &Type) -> Result<Type, Strin
                 type_: type_.clone(),
             })
         }
+        (operator, Type::Literal { type_, .. }) => get_unop_type(operator, type_),
         (UnaryOperator::LogicalNot, Type::Bool) => Ok(Type::Bool),
         (UnaryOperator::BitwiseNot, Type::Int) => Ok(Type::Int),
         (UnaryOperator::BitwiseNot, Type::UInt) => Ok(Type::UInt),
@@ -1630,6 +3416,187 @@ fn get_unop_type(operator: &UnaryOperator, operand: &Type) -> Result<Type, Strin
     }
 }
 
+/// `Void` is the absence of a value, so it can never be stored in a variable.
+/// Expressions that produce nothing meaningful but still need a value should produce `Unit`.
+fn check_not_void(initializer: &TypedExpression) -> Result<(), String> {
+    if initializer.get_deep_type() == Type::Void {
+        return Err(format!(
+            "Cannot assign {} to a variable because it does not produce a value",
+            initializer
+        ));
+    }
+
+    Ok(())
+}
+
+/// `field 'x'` or `fields 'x', 'y'`
+fn quoted_fields(names: &[String]) -> String {
+    let quoted = names
+        .iter()
+        .map(|name| format!("'{}'", name))
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    match names.len() {
+        1 => format!("field {}", quoted),
+        _ => format!("fields {}", quoted),
+    }
+}
+
+fn check_cast(
+    expression: &TypedExpression,
+    target: &Type,
+    type_environment: &Rcrc<TypeEnvironment>,
+) -> Result<(), String> {
+    let overflow_policy = type_environment.borrow().overflow_policy();
+
+    let source = match expression.get_deep_type() {
+        Type::Literal { type_, .. } => *type_,
+        type_ => type_,
+    };
+
+    if type_equals(target, &source) {
+        return Ok(());
+    }
+
+    let castable = match target {
+        // A cast can't narrow a value down to a single literal
+        Type::Literal { .. } => false,
+        _ => {
+            (source.is_numeric() && target.is_numeric())
+                || (source == Type::Char && target.is_integer())
+                || (source.is_integer() && *target == Type::Char)
+        }
+    };
+
+    if !castable {
+        return Err(format!("Cannot cast {} to {}", source, target));
+    }
+
+    // Narrowing casts are allowed, but a constant which is known to be lost is warned about
+    let in_range = match (constant_cast_source(expression, overflow_policy)?, target) {
+        (Some(Literal::Int(v)), Type::UInt) => v >= 0,
+        (Some(Literal::Int(v)), Type::Char) => {
+            u32::try_from(v).ok().and_then(char::from_u32).is_some()
+        }
+        (Some(Literal::UInt(v)), Type::Int) => i64::try_from(v).is_ok(),
+        (Some(Literal::UInt(v)), Type::Char) => {
+            u32::try_from(v).ok().and_then(char::from_u32).is_some()
+        }
+        (Some(Literal::Float(v)), Type::Int) => {
+            v.is_finite() && v >= i64::MIN as f64 && v < i64::MAX as f64
+        }
+        (Some(Literal::Float(v)), Type::UInt) => v.is_finite() && v >= 0.0 && v < u64::MAX as f64,
+        _ => true,
+    };
+
+    if !in_range {
+        type_environment.borrow_mut().add_warning(
+            "lossy_cast",
+            format!("Constant {} is out of range for {}", expression, target),
+        );
+    }
+
+    Ok(())
+}
+
+fn constant_cast_source(
+    expression: &TypedExpression,
+    overflow_policy: OverflowPolicy,
+) -> Result<Option<Literal>, String> {
+    match expression {
+        TypedExpression::Literal(literal @ Literal::Float(_), _) => Ok(Some(literal.clone())),
+        _ => constant::evaluate_constant(expression, overflow_policy),
+    }
+}
+
+/// Create the environment the right operand of `&&` or `||` is checked in.
+///
+/// The right operand of `&&` is only evaluated when the left operand is true,
+/// and the right operand of `||` only when it is false.
+/// This is the extension point for flow-sensitive narrowing:
+/// anything the left operand proves about that case, like a variable having a narrower type,
+/// should be added to the returned environment by shadowing it there.
+/// No refinements are derived yet, so the environment starts out empty.
+fn short_circuit_environment(
+    _left: &TypedExpression,
+    _operator: &BinaryOperator,
+    type_environment: Rcrc<TypeEnvironment>,
+) -> TypeEnvironment {
+    TypeEnvironment::new_parent(type_environment)
+}
+
+fn check_binop_operands(
+    left_type: &Type,
+    operator: &BinaryOperator,
+    right_type: &Type,
+) -> Result<(), String> {
+    check_binop_operand(operator, left_type)?;
+
+    // Appending a single element to an array accepts any element type
+    if *operator == BinaryOperator::Add && matches!(left_type, Type::Array(_)) {
+        return Ok(());
+    }
+
+    check_binop_operand(operator, right_type)
+}
+
+fn check_binop_operand(operator: &BinaryOperator, operand: &Type) -> Result<(), String> {
+    match operand {
+        Type::Substitution { actual_type, .. } => {
+            return check_binop_operand(operator, actual_type);
+        }
+        Type::Literal { type_, .. } => return check_binop_operand(operator, type_),
+        Type::Union(Union { literal_type, .. }) => {
+            return check_binop_operand(operator, literal_type);
+        }
+        Type::TypeAlias(TypeAlias { types, .. }) => {
+            for type_ in types {
+                check_binop_operand(operator, type_)?;
+            }
+
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    let category = operator.category();
+
+    let supported = match category {
+        BinaryOperatorCategory::Arithmetic => {
+            operand.is_numeric()
+                || (*operator == BinaryOperator::Add
+                    && matches!(operand, Type::String | Type::Char | Type::Array(_)))
+        }
+        BinaryOperatorCategory::Comparison => operand.is_numeric(),
+        BinaryOperatorCategory::Equality => matches!(
+            operand,
+            Type::Unit
+                | Type::Bool
+                | Type::Int
+                | Type::UInt
+                | Type::Float
+                | Type::Char
+                | Type::String
+                | Type::Struct(_)
+                | Type::Enum(_)
+                | Type::EnumMember(_)
+        ),
+        BinaryOperatorCategory::Logical => matches!(operand, Type::Bool),
+        BinaryOperatorCategory::Bitwise => operand.is_integer(),
+        BinaryOperatorCategory::Range => operand.is_integer() || *operand == Type::Char,
+    };
+
+    if !supported {
+        return Err(format!(
+            "The {} operator '{}' cannot be applied to type {}",
+            category, operator, operand
+        ));
+    }
+
+    Ok(())
+}
+
 fn get_binop_type(
     left_type: &Type,
     operator: &BinaryOperator,
@@ -1694,6 +3661,15 @@ fn get_binop_type(
         (Type::Int, BinaryOperator::Range, Type::Int) => Ok(Type::Array(Box::new(Type::Int))),
         (Type::UInt, BinaryOperator::Range, Type::UInt) => Ok(Type::Array(Box::new(Type::UInt))),
         (Type::Char, BinaryOperator::Range, Type::Char) => Ok(Type::Array(Box::new(Type::Char))),
+        (Type::Int, BinaryOperator::RangeInclusive, Type::Int) => {
+            Ok(Type::Array(Box::new(Type::Int)))
+        }
+        (Type::UInt, BinaryOperator::RangeInclusive, Type::UInt) => {
+            Ok(Type::Array(Box::new(Type::UInt)))
+        }
+        (Type::Char, BinaryOperator::RangeInclusive, Type::Char) => {
+            Ok(Type::Array(Box::new(Type::Char)))
+        }
         (Type::TypeAlias(TypeAlias { types, .. }), operator, right_type) => {
             let mut acc = Type::Unknown;
 
@@ -1746,15 +3722,6 @@ fn get_binop_type(
         // {
         //     Ok(Type::Array(Box::new(Type::Int)))
         // }
-        // (Type::Int, BinaryOperator::RangeInclusive, Type::Int) => {
-        //     Ok(Type::Array(Box::new(Type::Int)))
-        // }
-        // (Type::UInt, BinaryOperator::RangeInclusive, Type::UInt) => {
-        //     Ok(Type::Array(Box::new(Type::UInt)))
-        // }
-        // (Type::Char, BinaryOperator::RangeInclusive, Type::Char) => {
-        //     Ok(Type::Array(Box::new(Type::Char)))
-        // }
         // (Type::Literal { type_, name }, BinaryOperator::RangeInclusive, Type::Int)
         //     if **type_ == Type::UInt && name.parse::<i64>().is_ok() =>
         // {
@@ -1813,6 +3780,12 @@ fn get_binop_type(
         (Type::Array(left), BinaryOperator::Add, right) if type_equals(left, right) => {
             Ok(Type::Array(left.clone()))
         }
+        (left, BinaryOperator::Equal | BinaryOperator::NotEqual, right)
+            if matches!(left, Type::Struct(_) | Type::Enum(_) | Type::EnumMember(_))
+                && (type_equals(left, right) || type_equals(right, left)) =>
+        {
+            Ok(Type::Bool)
+        }
         _ => Err(format!(
             "Unexpected binary operator {:?} for types {:?} and {:?}",
             operator, left_type, right_type,
@@ -1,5 +1,11 @@
+use super::interner::{Interner, Symbol};
+
 pub trait FullName {
     fn full_name(&self) -> String;
+
+    fn full_name_symbol(&self, interner: &mut Interner) -> Symbol {
+        interner.intern(&self.full_name())
+    }
 }
 
 impl FullName for String {
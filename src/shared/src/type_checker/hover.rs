@@ -0,0 +1,63 @@
+use crate::span::Span;
+
+use super::{
+    ast::{Member, Typed, TypedExpression, TypedStatement},
+    visit::{self, TypedVisitor},
+    Type,
+};
+
+/// Find the type of the innermost expression written over the byte `offset`.
+///
+/// Literals, operators, calls, indexing, casts and the names of variables, fields and functions
+/// know where they are written. When several of them cover the offset the one nested deepest
+/// wins, so hovering over `1` in `1 + 2` gives the type of the literal rather than of the sum.
+/// Returns `None` when no such expression covers the offset.
+pub fn type_at(program: &TypedStatement, offset: usize) -> Option<Type> {
+    let mut finder = TypeAt {
+        offset,
+        innermost: None,
+    };
+
+    finder.visit_statement(program);
+    finder.innermost.map(|(_, type_)| type_)
+}
+
+struct TypeAt {
+    offset: usize,
+    /// The span and type of the innermost expression found so far
+    innermost: Option<(Span, Type)>,
+}
+
+impl TypeAt {
+    /// Keep the type if the span covers the offset and is nested in the innermost span so far.
+    /// Nodes are visited before their children, so a child with the same span replaces its parent.
+    fn cover(&mut self, span: Span, type_: impl FnOnce() -> Type) {
+        if span.is_empty() || !span.contains(self.offset) {
+            return;
+        }
+
+        let is_inner = match &self.innermost {
+            Some((innermost, _)) => span.end - span.start <= innermost.end - innermost.start,
+            None => true,
+        };
+
+        if is_inner {
+            self.innermost = Some((span, type_()));
+        }
+    }
+}
+
+impl TypedVisitor for TypeAt {
+    fn visit_expression(&mut self, expression: &TypedExpression) {
+        self.cover(expression.span(), || expression.get_type());
+        visit::walk_expression(self, expression);
+    }
+
+    fn visit_member(&mut self, member: &Member) {
+        if let Member::Identifier { span, .. } = member {
+            self.cover(*span, || member.get_type());
+        }
+
+        visit::walk_member(self, member);
+    }
+}
@@ -0,0 +1,214 @@
+use crate::parser::{Statement, UseItem};
+
+use super::{
+    ast::AccessModifier,
+    type_environment::{module_name, Visibility},
+    Rcrc, TypeEnvironment,
+};
+
+/// A name brought into scope by a `use` statement
+#[derive(Debug, Clone, PartialEq)]
+pub struct Import {
+    pub name: String,
+    pub path: Vec<String>,
+    pub glob: bool,
+}
+
+/// Resolve a use item against the module tree of the type environment.
+///
+/// Every segment but the last has to be a module. The last one is either a module,
+/// an item declared in the module before it or a glob importing its public items.
+pub fn resolve_use_item(
+    use_item: &UseItem,
+    type_environment: Rcrc<TypeEnvironment>,
+) -> Result<Vec<Import>, String> {
+    let mut imports = vec![];
+    resolve(use_item, &[], use_item, &type_environment, &mut imports)?;
+    Ok(imports)
+}
+
+fn resolve(
+    use_item: &UseItem,
+    module_path: &[String],
+    root: &UseItem,
+    type_environment: &Rcrc<TypeEnvironment>,
+    imports: &mut Vec<Import>,
+) -> Result<(), String> {
+    match use_item {
+        UseItem::Item(name) => {
+            let path = child_path(module_path, name);
+
+            if let Some(access_modifier) =
+                type_environment.borrow().get_module_item(module_path, name)
+            {
+                let visibility = Visibility {
+                    module_path: module_path.to_vec(),
+                    access_modifier,
+                };
+
+                let current_module = type_environment.borrow().current_module();
+
+                if !visibility.is_visible_from(&current_module) {
+                    return Err(format!(
+                        "Cannot import '{}', it is not visible from {}",
+                        path.join("::"),
+                        module_name(&current_module)
+                    ));
+                }
+            } else if !type_environment.borrow().is_module(&path) {
+                return Err(unresolved(root, name, module_path));
+            }
+
+            imports.push(Import {
+                name: name.clone(),
+                path,
+                glob: false,
+            });
+
+            Ok(())
+        }
+        UseItem::Glob => {
+            if module_path.is_empty() {
+                return Err(format!(
+                    "Unresolved import '{}': glob imports need a module",
+                    root
+                ));
+            }
+
+            for (name, access_modifier) in type_environment.borrow().get_module_items(module_path) {
+                if access_modifier != Some(AccessModifier::Public) {
+                    continue;
+                }
+
+                imports.push(Import {
+                    path: child_path(module_path, &name),
+                    name,
+                    glob: true,
+                });
+            }
+
+            Ok(())
+        }
+        UseItem::Navigation(name, next) => {
+            let path = child_path(module_path, name);
+
+            if !type_environment.borrow().is_module(&path) {
+                return Err(unresolved(root, name, module_path));
+            }
+
+            resolve(next, &path, root, type_environment, imports)
+        }
+        UseItem::List(use_items) => {
+            for use_item in use_items {
+                resolve(use_item, module_path, root, type_environment, imports)?;
+            }
+
+            Ok(())
+        }
+    }
+}
+
+/// Split a path like `geometry::shapes::Point::new` into its longest module prefix and the item
+/// path after it. A leading segment which is an imported module is expanded first.
+pub fn resolve_path(
+    path: &[String],
+    type_environment: Rcrc<TypeEnvironment>,
+) -> Result<(Vec<String>, Vec<String>), String> {
+    let type_environment = type_environment.borrow();
+    let mut segments = path.to_vec();
+
+    if let Some(import) = type_environment.get_import(&path[0]) {
+        if type_environment.is_module(&import) {
+            segments.splice(0..1, import);
+        }
+    }
+
+    let Some(module_length) = (1..segments.len())
+        .rev()
+        .find(|length| type_environment.is_module(&segments[..*length]))
+    else {
+        return Err(format!(
+            "Unresolved path '{}': '{}' is not a module",
+            path.join("::"),
+            path[0]
+        ));
+    };
+
+    let (module_path, item_path) = segments.split_at(module_length);
+    let item = &item_path[0];
+
+    let Some(access_modifier) = type_environment.get_module_item(module_path, item) else {
+        return Err(format!(
+            "Unresolved path '{}': '{}' not found in {}",
+            path.join("::"),
+            item,
+            module_name(module_path)
+        ));
+    };
+
+    let visibility = Visibility {
+        module_path: module_path.to_vec(),
+        access_modifier,
+    };
+
+    let current_module = type_environment.current_module();
+
+    if !visibility.is_visible_from(&current_module) {
+        return Err(format!(
+            "'{}' is not visible from {}",
+            path.join("::"),
+            module_name(&current_module)
+        ));
+    }
+
+    Ok((module_path.to_vec(), item_path.to_vec()))
+}
+
+fn child_path(module_path: &[String], name: &str) -> Vec<String> {
+    let mut path = module_path.to_vec();
+    path.push(name.to_string());
+    path
+}
+
+fn unresolved(root: &UseItem, segment: &str, module_path: &[String]) -> String {
+    format!(
+        "Unresolved import '{}': '{}' not found in {}",
+        root,
+        segment,
+        module_name(module_path)
+    )
+}
+
+/// The name and access modifier of a top-level type or function declaration
+pub(crate) fn declared_item(statement: &Statement) -> Option<(String, Option<AccessModifier>)> {
+    let (type_identifier, access_modifier) = match statement {
+        Statement::StructDeclaration(declaration) => {
+            (&declaration.type_identifier, &declaration.access_modifier)
+        }
+        Statement::EnumDeclaration(declaration) => {
+            (&declaration.type_identifier, &declaration.access_modifier)
+        }
+        Statement::UnionDeclaration(declaration) => {
+            (&declaration.type_identifier, &declaration.access_modifier)
+        }
+        Statement::TypeAliasDeclaration(declaration) => {
+            (&declaration.type_identifier, &declaration.access_modifier)
+        }
+        Statement::NewtypeDeclaration(declaration) => {
+            (&declaration.type_identifier, &declaration.access_modifier)
+        }
+        Statement::ProtocolDeclaration(declaration) => {
+            (&declaration.type_identifier, &declaration.access_modifier)
+        }
+        Statement::FunctionDeclaration(declaration) => {
+            (&declaration.type_identifier, &declaration.access_modifier)
+        }
+        Statement::Semi(statement) => return declared_item(statement),
+        _ => return None,
+    };
+
+    Some((
+        type_identifier.name().to_string(),
+        access_modifier.clone().map(|a| a.into()),
+    ))
+}
@@ -0,0 +1,678 @@
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    rc::Rc,
+};
+
+use crate::{
+    parser::{self, EnumMemberFieldInitializers, Expression, Literal, Member, Statement},
+    types::{GenericConstraint, TypeAnnotation, TypeIdentifier},
+};
+
+use super::{
+    ast::TypedStatement,
+    decision_tree::{Constructor, Pattern},
+    statements,
+    type_checker::{check_statement, check_statements, configure, finish},
+    DiscoveredType, Rcrc, TypeCheckerConfig, TypeEnvironment,
+};
+
+/// Which top-level declarations each top-level declaration references, by name
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DependencyGraph {
+    dependencies: HashMap<String, HashSet<String>>,
+}
+
+impl DependencyGraph {
+    pub fn dependencies(&self, name: &str) -> Option<&HashSet<String>> {
+        self.dependencies.get(name)
+    }
+
+    /// Get the names of every declaration which depends on `name`, directly or transitively, including `name` itself
+    pub fn dependents(&self, name: &str) -> HashSet<String> {
+        let mut dependents = HashSet::from([name.to_string()]);
+        let mut pending = vec![name.to_string()];
+
+        while let Some(current) = pending.pop() {
+            for (dependent, dependencies) in &self.dependencies {
+                if dependencies.contains(&current) && dependents.insert(dependent.clone()) {
+                    pending.push(dependent.clone());
+                }
+            }
+        }
+
+        dependents
+    }
+
+    fn insert(&mut self, name: String, references: HashSet<String>) {
+        self.dependencies.insert(name, references);
+    }
+
+    /// Drop references to names which aren't top-level declarations, like local variables and primitive types
+    fn retain_declarations(&mut self) {
+        let names = self.dependencies.keys().cloned().collect::<HashSet<_>>();
+
+        for references in self.dependencies.values_mut() {
+            references.retain(|reference| names.contains(reference));
+        }
+    }
+}
+
+pub fn discover_dependencies(program: &Statement) -> DependencyGraph {
+    let mut graph = DependencyGraph::default();
+
+    let Statement::Program { statements } = program else {
+        return graph;
+    };
+
+    for statement in statements {
+        if let Some(name) = declaration_name(statement) {
+            let mut references = HashSet::new();
+            collect_statement(statement, &mut references);
+            references.remove(&name);
+            graph.insert(name, references);
+        }
+    }
+
+    graph.retain_declarations();
+    graph
+}
+
+/// The result of re-checking a changed declaration
+#[derive(Debug, Clone, PartialEq)]
+pub struct Recheck {
+    /// The re-checked declarations in source order
+    pub statements: Vec<TypedStatement>,
+    /// The names of the re-checked declarations
+    pub invalidated: HashSet<String>,
+}
+
+/// A checked program which can re-check a single top-level declaration without re-checking everything.
+///
+/// Only named top-level declarations are tracked.
+/// Top-level expressions and implementation declarations are checked once and never invalidated.
+/// The typed program is the same as the one `create_typed_ast` gives for the current declarations.
+#[derive(Debug, Clone)]
+pub struct IncrementalProgram {
+    statements: Vec<Statement>,
+    typed_statements: Vec<TypedStatement>,
+    discovered_types: Vec<Vec<DiscoveredType>>,
+    dependencies: DependencyGraph,
+    type_environment: Rcrc<TypeEnvironment>,
+    config: TypeCheckerConfig,
+}
+
+impl IncrementalProgram {
+    pub fn new(
+        program: Statement,
+        type_environment: Rcrc<TypeEnvironment>,
+        config: TypeCheckerConfig,
+    ) -> Result<Self, String> {
+        configure(&type_environment, &config);
+
+        let statements = match program {
+            Statement::Program { statements } => statements,
+            statement => vec![statement],
+        };
+
+        let discovered_types = statements
+            .iter()
+            .map(statements::discover_user_defined_types)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let dependencies = discover_dependencies(&Statement::Program {
+            statements: statements.clone(),
+        });
+
+        let flattened = discovered_types.iter().flatten().cloned().collect();
+
+        let typed_statements = check_statements(
+            &statements,
+            &flattened,
+            type_environment.clone(),
+            |_, message| Err(message),
+        )?;
+
+        let typed_statements = finish_statements(typed_statements, &type_environment, &config)?;
+
+        Ok(Self {
+            statements,
+            typed_statements,
+            discovered_types,
+            dependencies,
+            type_environment,
+            config,
+        })
+    }
+
+    pub fn typed_program(&self) -> TypedStatement {
+        TypedStatement::Program {
+            statements: self.typed_statements.clone(),
+        }
+    }
+
+    pub fn discovered_types(&self) -> Vec<DiscoveredType> {
+        self.discovered_types.iter().flatten().cloned().collect()
+    }
+
+    pub fn dependencies(&self) -> &DependencyGraph {
+        &self.dependencies
+    }
+
+    /// Replace the declaration with the same name as `changed` and re-check it along with its dependents.
+    ///
+    /// The whole program is finished again afterwards, like `create_typed_ast` finishes it,
+    /// so its warnings are found again too.
+    /// If re-checking fails the program is left unchanged.
+    pub fn recheck(&mut self, changed: Statement) -> Result<Recheck, String> {
+        let Some(name) = declaration_name(&changed) else {
+            return Err("Only top-level declarations can be re-checked".to_string());
+        };
+
+        let Some(index) = self
+            .statements
+            .iter()
+            .position(|s| declaration_name(s).as_ref() == Some(&name))
+        else {
+            return Err(format!("Declaration {} not found", name));
+        };
+
+        let mut statements = self.statements.clone();
+        statements[index] = changed;
+
+        let mut discovered_types = self.discovered_types.clone();
+        discovered_types[index] = statements::discover_user_defined_types(&statements[index])?;
+
+        let mut references = HashSet::new();
+        collect_statement(&statements[index], &mut references);
+        references.remove(&name);
+
+        let mut dependencies = self.dependencies.clone();
+        dependencies.insert(name.clone(), references);
+        dependencies.retain_declarations();
+
+        let invalidated = dependencies.dependents(&name);
+        let flattened = discovered_types.iter().flatten().cloned().collect();
+
+        // Declarations are re-checked in an isolated child environment so they can replace their
+        // old types, and failing to re-check them doesn't change what the root keeps about them
+        let environment = Rc::new(RefCell::new(TypeEnvironment::new_isolated(
+            self.type_environment.clone(),
+        )));
+
+        let mut typed_statements = self.typed_statements.clone();
+        let mut rechecked = vec![];
+
+        for (index, statement) in statements.iter().enumerate() {
+            if declaration_name(statement).is_some_and(|n| invalidated.contains(&n)) {
+                typed_statements[index] =
+                    check_statement(statement, &flattened, environment.clone())?;
+                rechecked.push(index);
+            }
+        }
+
+        let typed_statements = finish_statements(typed_statements, &environment, &self.config)?;
+
+        environment.borrow().merge_into_parent()?;
+
+        self.statements = statements;
+        self.typed_statements = typed_statements;
+        self.discovered_types = discovered_types;
+        self.dependencies = dependencies;
+
+        Ok(Recheck {
+            statements: rechecked
+                .into_iter()
+                .map(|index| self.typed_statements[index].clone())
+                .collect(),
+            invalidated,
+        })
+    }
+}
+
+fn finish_statements(
+    statements: Vec<TypedStatement>,
+    type_environment: &Rcrc<TypeEnvironment>,
+    config: &TypeCheckerConfig,
+) -> Result<Vec<TypedStatement>, String> {
+    let program = TypedStatement::Program { statements };
+
+    let TypedStatement::Program { statements } =
+        finish(program, type_environment.clone(), config.clone())?
+    else {
+        unreachable!("A program is finished to a program");
+    };
+
+    Ok(statements)
+}
+
+pub fn declaration_name(statement: &Statement) -> Option<String> {
+    match statement {
+        Statement::StructDeclaration(parser::StructDeclaration {
+            type_identifier, ..
+        })
+        | Statement::EnumDeclaration(parser::EnumDeclaration {
+            type_identifier, ..
+        })
+        | Statement::UnionDeclaration(parser::UnionDeclaration {
+            type_identifier, ..
+        })
+        | Statement::TypeAliasDeclaration(parser::TypeAliasDeclaration {
+            type_identifier, ..
+        })
+        | Statement::NewtypeDeclaration(parser::NewtypeDeclaration {
+            type_identifier, ..
+        })
+        | Statement::ProtocolDeclaration(parser::ProtocolDeclaration {
+            type_identifier, ..
+        })
+        | Statement::FunctionDeclaration(parser::FunctionDeclaration {
+            type_identifier, ..
+        }) => Some(type_identifier_name(type_identifier)),
+        Statement::Semi(statement) => declaration_name(statement),
+        _ => None,
+    }
+}
+
+fn type_identifier_name(type_identifier: &TypeIdentifier) -> String {
+    match type_identifier {
+        TypeIdentifier::Type(name)
+        | TypeIdentifier::GenericType(name, _)
+        | TypeIdentifier::ConcreteType(name, _) => name.clone(),
+        TypeIdentifier::MemberType(type_identifier, _) => type_identifier_name(type_identifier),
+    }
+}
+
+pub(crate) fn collect_statement(statement: &Statement, references: &mut HashSet<String>) {
+    match statement {
+        Statement::Program { statements } => {
+            for statement in statements {
+                collect_statement(statement, references);
+            }
+        }
+        Statement::StructDeclaration(parser::StructDeclaration {
+            where_clause,
+            fields,
+            ..
+        }) => {
+            collect_where_clause(where_clause, references);
+
+            for field in fields {
+                collect_type_annotation(&field.type_annotation, references);
+
+                if let Some(default) = &field.default {
+                    collect_expression(default, references);
+                }
+            }
+        }
+        Statement::EnumDeclaration(parser::EnumDeclaration {
+            where_clause,
+            shared_fields,
+            members,
+            ..
+        }) => {
+            collect_where_clause(where_clause, references);
+
+            for field in shared_fields {
+                collect_type_annotation(&field.type_annotation, references);
+            }
+
+            for member in members {
+                for field in &member.fields {
+                    collect_type_annotation(&field.type_annotation, references);
+                }
+            }
+        }
+        Statement::TypeAliasDeclaration(parser::TypeAliasDeclaration {
+            type_annotations, ..
+        }) => {
+            for type_annotation in type_annotations {
+                collect_type_annotation(type_annotation, references);
+            }
+        }
+        Statement::NewtypeDeclaration(parser::NewtypeDeclaration {
+            type_annotation, ..
+        }) => collect_type_annotation(type_annotation, references),
+        Statement::ProtocolDeclaration(parser::ProtocolDeclaration { functions, .. }) => {
+            for function in functions {
+                collect_function(function, references);
+            }
+        }
+        Statement::ImplementationDeclaration(parser::ImplementationDeclaration {
+            protocol_annotation,
+            type_annotation,
+            functions,
+            ..
+        }) => {
+            if let Some(protocol_annotation) = protocol_annotation {
+                collect_type_annotation(protocol_annotation, references);
+            }
+
+            collect_type_annotation(type_annotation, references);
+
+            for function in functions {
+                collect_function(function, references);
+            }
+        }
+        Statement::FunctionDeclaration(function) => collect_function(function, references),
+        Statement::Semi(statement) => collect_statement(statement, references),
+        Statement::Expression(expression) => collect_expression(expression, references),
+        Statement::ModuleDeclaration(_)
+        | Statement::Use(_)
+        | Statement::UnionDeclaration(_)
+        | Statement::Error(_) => {}
+    }
+}
+
+fn collect_function(function: &parser::FunctionDeclaration, references: &mut HashSet<String>) {
+    if let Some(param) = &function.param {
+        collect_type_annotation(&param.type_annotation, references);
+    }
+
+    if let Some(return_type_annotation) = &function.return_type_annotation {
+        collect_type_annotation(return_type_annotation, references);
+    }
+
+    collect_where_clause(&function.where_clause, references);
+
+    if let Some(body) = &function.body {
+        collect_expression(body, references);
+    }
+}
+
+fn collect_where_clause(
+    where_clause: &Option<Vec<GenericConstraint>>,
+    references: &mut HashSet<String>,
+) {
+    for constraint in where_clause.iter().flatten() {
+        for type_annotation in &constraint.constraints {
+            collect_type_annotation(type_annotation, references);
+        }
+    }
+}
+
+fn collect_type_annotation(type_annotation: &TypeAnnotation, references: &mut HashSet<String>) {
+    match type_annotation {
+        TypeAnnotation::Type(name) => {
+            // Enum members are written as Enum::Member
+            let name = name.split("::").next().unwrap_or(name);
+            references.insert(name.to_string());
+        }
+        TypeAnnotation::ConcreteType(name, type_annotations) => {
+            references.insert(name.clone());
+
+            for type_annotation in type_annotations {
+                collect_type_annotation(type_annotation, references);
+            }
+        }
+        TypeAnnotation::Array(type_annotation) => {
+            collect_type_annotation(type_annotation, references)
+        }
+        TypeAnnotation::Literal(_) => {}
+        TypeAnnotation::Tuple(type_annotations) => {
+            for type_annotation in type_annotations {
+                collect_type_annotation(type_annotation, references);
+            }
+        }
+        TypeAnnotation::Record(fields) => {
+            for (_, type_annotation) in fields {
+                collect_type_annotation(type_annotation, references);
+            }
+        }
+        TypeAnnotation::Function(param, return_type) => {
+            for type_annotation in param.iter().chain(return_type.iter()) {
+                collect_type_annotation(type_annotation, references);
+            }
+        }
+    }
+}
+
+fn collect_expression(expression: &Expression, references: &mut HashSet<String>) {
+    match expression {
+        Expression::VariableDeclaration(parser::VariableDeclaration {
+            type_annotation,
+            pattern,
+            initializer,
+            ..
+        }) => {
+            if let Some(type_annotation) = type_annotation {
+                collect_type_annotation(type_annotation, references);
+            }
+
+            collect_pattern(pattern, references);
+
+            if let Some(initializer) = initializer {
+                collect_expression(initializer, references);
+            }
+        }
+        Expression::If(parser::If {
+            condition,
+            true_expression,
+            false_expression,
+        }) => {
+            collect_expression(condition, references);
+            collect_expression(true_expression, references);
+
+            if let Some(false_expression) = false_expression {
+                collect_expression(false_expression, references);
+            }
+        }
+        Expression::Match(parser::Match {
+            expression, arms, ..
+        }) => {
+            collect_expression(expression, references);
+
+            for arm in arms {
+                collect_pattern(&arm.pattern, references);
+                collect_expression(&arm.expression, references);
+            }
+        }
+        Expression::Assignment(parser::Assignment {
+            member,
+            initializer,
+            ..
+        }) => {
+            collect_member(member, references);
+            collect_expression(initializer, references);
+        }
+        Expression::Member(member) => collect_member(member, references),
+        Expression::Literal(literal, _) => collect_literal(literal, references),
+        Expression::Tuple(elements) => {
+            for element in elements {
+                collect_expression(element, references);
+            }
+        }
+        Expression::Record(fields) => {
+            for field in fields {
+                collect_expression(&field.initializer, references);
+            }
+        }
+        Expression::Closure(parser::Closure {
+            param,
+            return_type_annotation,
+            body,
+        }) => {
+            if let Some(type_annotation) = param.as_ref().and_then(|p| p.type_annotation.as_ref()) {
+                collect_type_annotation(type_annotation, references);
+            }
+
+            if let Some(return_type_annotation) = return_type_annotation {
+                collect_type_annotation(return_type_annotation, references);
+            }
+
+            collect_expression(body, references);
+        }
+        Expression::Call(parser::Call {
+            callee, argument, ..
+        }) => {
+            collect_expression(callee, references);
+
+            if let Some(argument) = argument {
+                collect_expression(argument, references);
+            }
+        }
+        Expression::Index(parser::Index {
+            callee, argument, ..
+        }) => {
+            collect_expression(callee, references);
+            collect_expression(argument, references);
+        }
+        Expression::Unary(parser::Unary { expression, .. }) => {
+            collect_expression(expression, references)
+        }
+        Expression::Binary(parser::Binary { left, right, .. }) => {
+            collect_expression(left, references);
+            collect_expression(right, references);
+        }
+        Expression::Coalesce(parser::Coalesce { value, default }) => {
+            collect_expression(value, references);
+            collect_expression(default, references);
+        }
+        Expression::Cast(parser::Cast {
+            expression, target, ..
+        }) => {
+            collect_expression(expression, references);
+            collect_type_annotation(target, references);
+        }
+        Expression::Block(statements) => {
+            for statement in statements {
+                collect_statement(statement, references);
+            }
+        }
+        Expression::Try(expression) | Expression::Loop(expression) => {
+            collect_expression(expression, references)
+        }
+        Expression::While(parser::While {
+            condition,
+            body,
+            else_body,
+        }) => {
+            collect_expression(condition, references);
+            collect_expression(body, references);
+
+            if let Some(else_body) = else_body {
+                collect_expression(else_body, references);
+            }
+        }
+        Expression::For(parser::For {
+            iterable,
+            body,
+            else_body,
+            ..
+        }) => {
+            collect_expression(iterable, references);
+            collect_expression(body, references);
+
+            if let Some(else_body) = else_body {
+                collect_expression(else_body, references);
+            }
+        }
+        Expression::Break(expression) | Expression::Return(expression) => {
+            if let Some(expression) = expression {
+                collect_expression(expression, references);
+            }
+        }
+        Expression::Continue => {}
+        #[cfg(feature = "interpreter")]
+        Expression::Print(expression) => collect_expression(expression, references),
+        #[cfg(feature = "interpreter")]
+        Expression::Drop(_) => {}
+    }
+}
+
+fn collect_member(member: &Member, references: &mut HashSet<String>) {
+    match member {
+        Member::Identifier { symbol, .. } => {
+            references.insert(symbol.clone());
+        }
+        // Without the module tree any segment could be the item, so depend on all of them
+        Member::Path(path, _) => references.extend(path.iter().cloned()),
+        Member::StaticMemberAccess {
+            type_annotation, ..
+        } => collect_type_annotation(type_annotation, references),
+        Member::MemberAccess { object, .. } | Member::ParamPropagation { object, .. } => {
+            collect_expression(object, references)
+        }
+    }
+}
+
+fn collect_literal(literal: &Literal, references: &mut HashSet<String>) {
+    match literal {
+        Literal::Array(values) => {
+            for value in values {
+                collect_expression(value, references);
+            }
+        }
+        Literal::ArrayRepeat { value, length } => {
+            collect_expression(value, references);
+            collect_expression(length, references);
+        }
+        Literal::Struct {
+            type_annotation,
+            field_initializers,
+            spread,
+        } => {
+            collect_type_annotation(type_annotation, references);
+
+            for field_initializer in field_initializers {
+                collect_expression(&field_initializer.initializer, references);
+            }
+
+            if let Some(spread) = spread {
+                collect_expression(spread, references);
+            }
+        }
+        Literal::Enum {
+            type_annotation,
+            field_initializers,
+            ..
+        } => {
+            collect_type_annotation(type_annotation, references);
+
+            if let EnumMemberFieldInitializers::Named(field_initializers) = field_initializers {
+                for initializer in field_initializers.values() {
+                    collect_expression(initializer, references);
+                }
+            }
+        }
+        Literal::Unit
+        | Literal::Int(_)
+        | Literal::UInt(_)
+        | Literal::Float(_)
+        | Literal::String(_)
+        | Literal::Char(_)
+        | Literal::Bool(_) => {}
+    }
+}
+
+fn collect_pattern(pattern: &Pattern, references: &mut HashSet<String>) {
+    match pattern {
+        Pattern::Constructor(Constructor::Struct {
+            type_annotation,
+            field_patterns,
+        }) => {
+            collect_type_annotation(type_annotation, references);
+
+            for field_pattern in field_patterns {
+                collect_pattern(&field_pattern.pattern, references);
+            }
+        }
+        Pattern::LessThan(pattern)
+        | Pattern::GreaterThan(pattern)
+        | Pattern::LessThanOrEqual(pattern)
+        | Pattern::GreaterThanOrEqual(pattern) => collect_pattern(pattern, references),
+        Pattern::Range(start, end, _) => {
+            collect_pattern(start, references);
+            collect_pattern(end, references);
+        }
+        Pattern::Wildcard
+        | Pattern::Unit
+        | Pattern::Bool(_)
+        | Pattern::Int(_)
+        | Pattern::UInt(_)
+        | Pattern::Float(_)
+        | Pattern::Char(_)
+        | Pattern::String(_)
+        | Pattern::Variable(_) => {}
+    }
+}
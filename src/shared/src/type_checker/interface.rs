@@ -0,0 +1,232 @@
+use std::collections::HashSet;
+
+use crate::{
+    format::format,
+    lexer,
+    parser::{self, AccessModifier, FunctionDeclaration, ImplementationDeclaration, Statement},
+    types::{TypeAnnotation, TypeIdentifier},
+};
+
+use super::{create_typed_ast, Rcrc, TypeCheckerConfig, TypeEnvironment};
+
+/// The public declarations of a module, which other modules can be checked against without its
+/// source.
+///
+/// Public types are kept whole along with the `imp` blocks of them, while public functions and
+/// methods are kept as signatures without bodies. Private declarations and everything else in the
+/// module are left out, except for the module declaration and the `use` statements, which the
+/// signatures may need to name their types.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Interface {
+    statements: Vec<Statement>,
+}
+
+impl Interface {
+    /// The interface of a module which was checked, failing if a public function doesn't annotate
+    /// its return type, as a signature without a body can't infer it. A public signature naming a
+    /// private type fails too, as the type isn't part of the interface to be loaded with it.
+    pub fn new(program: &Statement) -> Result<Interface, String> {
+        let Statement::Program { statements } = program else {
+            return Err("Expected a program".to_string());
+        };
+
+        let statements = statements
+            .iter()
+            .map(|statement| match statement {
+                Statement::Semi(statement) => statement,
+                statement => statement,
+            })
+            .collect::<Vec<_>>();
+
+        let public_types = statements
+            .iter()
+            .filter_map(|statement| public_type_name(statement))
+            .collect::<HashSet<_>>();
+
+        let private_types = statements
+            .iter()
+            .filter_map(|statement| declared_type(statement))
+            .map(|(_, type_identifier)| type_identifier.name().to_string())
+            .filter(|name| !public_types.contains(name))
+            .collect::<HashSet<_>>();
+
+        let mut interface = vec![];
+
+        for statement in statements {
+            match statement {
+                Statement::ModuleDeclaration(_) | Statement::Use(_) => {
+                    interface.push(statement.clone())
+                }
+                Statement::FunctionDeclaration(function)
+                    if function.access_modifier == Some(AccessModifier::Public) =>
+                {
+                    interface.push(Statement::FunctionDeclaration(signature(
+                        function,
+                        &private_types,
+                    )?))
+                }
+                Statement::ImplementationDeclaration(implementation)
+                    if public_types.contains(&implementation.type_annotation.name()) =>
+                {
+                    let functions = implementation
+                        .functions
+                        .iter()
+                        .map(|function| signature(function, &private_types))
+                        .collect::<Result<_, _>>()?;
+
+                    interface.push(Statement::ImplementationDeclaration(
+                        ImplementationDeclaration {
+                            functions,
+                            ..implementation.clone()
+                        },
+                    ))
+                }
+                statement if public_type_name(statement).is_some() => {
+                    interface.push(statement.clone())
+                }
+                _ => {}
+            }
+        }
+
+        Ok(Interface {
+            statements: interface,
+        })
+    }
+
+    /// Write the interface as the source of its declarations, which `deserialize` reads back
+    pub fn serialize(&self) -> String {
+        format(&Statement::Program {
+            statements: self.statements.clone(),
+        })
+    }
+
+    pub fn deserialize(source: &str) -> Result<Interface, String> {
+        let tokens = lexer::tokenize(source)?;
+
+        let Statement::Program { statements } = parser::create_ast(tokens, false)? else {
+            return Err("Expected a program".to_string());
+        };
+
+        Ok(Interface { statements })
+    }
+
+    /// Declare everything in the interface in `type_environment`, in the module it was made from.
+    /// The interfaces of the modules it uses have to be loaded first.
+    pub fn load(&self, type_environment: Rcrc<TypeEnvironment>) -> Result<(), String> {
+        let program = Statement::Program {
+            statements: self.statements.clone(),
+        };
+
+        type_environment.borrow_mut().set_interface(true);
+
+        let result = create_typed_ast(
+            program,
+            type_environment.clone(),
+            TypeCheckerConfig::default(),
+        );
+
+        type_environment.borrow_mut().set_interface(false);
+        result.map(|_| ())
+    }
+}
+
+/// The name of a public type a statement declares
+fn public_type_name(statement: &Statement) -> Option<String> {
+    match declared_type(statement)? {
+        (Some(AccessModifier::Public), type_identifier) => Some(type_identifier.name().to_string()),
+        _ => None,
+    }
+}
+
+/// The access modifier and identifier of the type a statement declares
+fn declared_type(statement: &Statement) -> Option<(&Option<AccessModifier>, &TypeIdentifier)> {
+    match statement {
+        Statement::StructDeclaration(declaration) => {
+            Some((&declaration.access_modifier, &declaration.type_identifier))
+        }
+        Statement::EnumDeclaration(declaration) => {
+            Some((&declaration.access_modifier, &declaration.type_identifier))
+        }
+        Statement::UnionDeclaration(declaration) => {
+            Some((&declaration.access_modifier, &declaration.type_identifier))
+        }
+        Statement::TypeAliasDeclaration(declaration) => {
+            Some((&declaration.access_modifier, &declaration.type_identifier))
+        }
+        Statement::NewtypeDeclaration(declaration) => {
+            Some((&declaration.access_modifier, &declaration.type_identifier))
+        }
+        Statement::ProtocolDeclaration(declaration) => {
+            Some((&declaration.access_modifier, &declaration.type_identifier))
+        }
+        _ => None,
+    }
+}
+
+/// The function without its body. Extra parameters are already part of the return type.
+fn signature(
+    function: &FunctionDeclaration,
+    private_types: &HashSet<String>,
+) -> Result<FunctionDeclaration, String> {
+    if function.return_type_annotation.is_none() && function.body.is_some() {
+        return Err(format!(
+            "Function '{}' needs a return type to be part of an interface",
+            function.type_identifier
+        ));
+    }
+
+    let mut names = vec![];
+
+    for type_annotation in function
+        .param
+        .iter()
+        .map(|param| &param.type_annotation)
+        .chain(&function.return_type_annotation)
+    {
+        type_names(type_annotation, &mut names);
+    }
+
+    if let Some(name) = names.into_iter().find(|name| private_types.contains(name)) {
+        return Err(format!(
+            "Function '{}' can't be part of an interface, as its signature names private type '{}'",
+            function.type_identifier, name
+        ));
+    }
+
+    Ok(FunctionDeclaration {
+        body: None,
+        signature_only: true,
+        ..function.clone()
+    })
+}
+
+/// The names of the types a type annotation is made of
+fn type_names(type_annotation: &TypeAnnotation, names: &mut Vec<String>) {
+    match type_annotation {
+        TypeAnnotation::Type(name) => names.push(name.clone()),
+        TypeAnnotation::ConcreteType(name, concrete_types) => {
+            names.push(name.clone());
+
+            for concrete_type in concrete_types {
+                type_names(concrete_type, names);
+            }
+        }
+        TypeAnnotation::Array(element) => type_names(element, names),
+        TypeAnnotation::Literal(_) => {}
+        TypeAnnotation::Tuple(elements) => {
+            for element in elements {
+                type_names(element, names);
+            }
+        }
+        TypeAnnotation::Record(fields) => {
+            for (_, field) in fields {
+                type_names(field, names);
+            }
+        }
+        TypeAnnotation::Function(param, return_type) => {
+            for type_annotation in param.iter().chain(return_type) {
+                type_names(type_annotation, names);
+            }
+        }
+    }
+}
@@ -0,0 +1,306 @@
+use crate::{span::Span, types::TypeAnnotation};
+
+use super::{
+    ast::{Typed, TypedExpression, TypedStatement},
+    decision_tree::{Constructor, Pattern},
+    type_annotation_equals,
+    visit::{self, TypedVisitor},
+    StructField, Type,
+};
+
+/// Which arms of a match some value reaches, and which values no arm matches
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchReport {
+    /// Where the match is written
+    pub span: Span,
+    /// The arms which match some value no earlier arm does, by their index in the match
+    pub reachable: Vec<usize>,
+    /// The arms which only match values an earlier arm already does
+    pub redundant: Vec<usize>,
+    /// The constructors of the matched type, like `false` or `Shape::Circle`, making some value
+    /// no arm matches. A type with too many values to list, like `Int`, is missing `_` when the
+    /// arms don't match all of them.
+    pub missing: Vec<String>,
+}
+
+/// The reports of every match in `program`, in the order they are written.
+///
+/// Arms which are redundant aren't part of the decision tree, so matches nested in them aren't
+/// reported.
+pub fn match_reports(program: &TypedStatement) -> Vec<MatchReport> {
+    let mut reports = Reports { reports: vec![] };
+    reports.visit_statement(program);
+    reports.reports.sort_by_key(|report| report.span.start);
+    reports.reports
+}
+
+/// Report on the arms matching a value of `type_` with `patterns`, in the order they are tried
+pub fn report_match(span: Span, type_: &Type, patterns: &[Pattern]) -> MatchReport {
+    let mut rows: Vec<Vec<Pattern>> = vec![];
+    let mut reachable = vec![];
+    let mut redundant = vec![];
+
+    for (index, pattern) in patterns.iter().enumerate() {
+        let row = vec![pattern.clone()];
+
+        if is_useful(&rows, &row, std::slice::from_ref(type_)) {
+            reachable.push(index);
+        } else {
+            redundant.push(index);
+        }
+
+        rows.push(row);
+    }
+
+    let missing = match constructors(type_) {
+        Some(heads) => heads
+            .iter()
+            .filter(|head| {
+                let fields = vec![Pattern::Wildcard; head.fields.len()];
+                is_useful_for(&rows, head, fields, &[], std::slice::from_ref(type_))
+            })
+            .map(|head| head.name.clone())
+            .collect(),
+        None if is_useful(&rows, &[Pattern::Wildcard], std::slice::from_ref(type_)) => {
+            vec!["_".to_string()]
+        }
+        None => vec![],
+    };
+
+    MatchReport {
+        span,
+        reachable,
+        redundant,
+        missing,
+    }
+}
+
+struct Reports {
+    reports: Vec<MatchReport>,
+}
+
+impl TypedVisitor for Reports {
+    fn visit_expression(&mut self, expression: &TypedExpression) {
+        if let TypedExpression::Match {
+            expression: matchee,
+            arms,
+            span,
+            ..
+        } = expression
+        {
+            let patterns: Vec<Pattern> = arms.iter().map(|arm| arm.pattern.clone()).collect();
+            self.reports
+                .push(report_match(*span, &matchee.get_type(), &patterns));
+        }
+
+        visit::walk_expression(self, expression);
+    }
+}
+
+/// One of the ways a value of a type is made, which patterns take apart into its fields
+#[derive(Debug, Clone)]
+struct Head {
+    /// How the constructor is written, like `true` or `Shape::Circle`
+    name: String,
+    /// The type a constructor pattern names to match it, which literals don't have
+    type_annotation: Option<TypeAnnotation>,
+    fields: Vec<StructField>,
+}
+
+impl Head {
+    fn literal(name: impl Into<String>) -> Head {
+        Head {
+            name: name.into(),
+            type_annotation: None,
+            fields: vec![],
+        }
+    }
+}
+
+/// Every constructor of `type_`, when there are few enough of them to list
+fn constructors(type_: &Type) -> Option<Vec<Head>> {
+    match type_ {
+        Type::Substitution { actual_type, .. } => constructors(actual_type),
+        Type::Literal { type_, .. } => constructors(type_),
+        Type::Unit => Some(vec![Head::literal("()")]),
+        Type::Bool => Some(vec![Head::literal("true"), Head::literal("false")]),
+        Type::Union(union) => Some(
+            union
+                .literals
+                .iter()
+                .map(|literal| match literal {
+                    Type::Literal { name, .. } => Head::literal(name.clone()),
+                    literal => Head::literal(literal.to_string()),
+                })
+                .collect(),
+        ),
+        Type::Struct(struct_) => Some(vec![Head {
+            name: struct_.type_identifier.to_string(),
+            type_annotation: Some(struct_.type_annotation()),
+            fields: struct_.fields.clone(),
+        }]),
+        Type::Enum(enum_) => {
+            let mut members: Vec<Head> = enum_
+                .members
+                .values()
+                .filter_map(|member| match member {
+                    Type::EnumMember(enum_member) => Some(Head {
+                        name: enum_member.type_annotation().to_string(),
+                        type_annotation: Some(enum_member.type_annotation()),
+                        fields: enum_member
+                            .fields
+                            .iter()
+                            .chain(&enum_.shared_fields)
+                            .cloned()
+                            .collect(),
+                    }),
+                    _ => None,
+                })
+                .collect();
+
+            // Members are sorted so the report doesn't depend on the order of the map
+            members.sort_by(|a, b| a.name.cmp(&b.name));
+            Some(members)
+        }
+        _ => None,
+    }
+}
+
+/// How a literal pattern is written, which is the name of its constructor
+fn literal_name(pattern: &Pattern) -> Option<String> {
+    match pattern {
+        Pattern::Unit => Some("()".to_string()),
+        pattern => match pattern.literal_type()? {
+            Type::Literal { name, .. } => Some(name),
+            _ => None,
+        },
+    }
+}
+
+/// The patterns `pattern` matches the fields of values made by `head` with, in the order of the
+/// fields of `head`, or None when it doesn't match those values
+fn specialize(pattern: &Pattern, head: &Head, type_: &Type) -> Option<Vec<Pattern>> {
+    match pattern {
+        Pattern::Wildcard | Pattern::Variable(_) => {
+            Some(vec![Pattern::Wildcard; head.fields.len()])
+        }
+        Pattern::Constructor(Constructor::Struct {
+            type_annotation,
+            field_patterns,
+        }) => {
+            let names_head = head
+                .type_annotation
+                .as_ref()
+                .is_some_and(|head| type_annotation_equals(type_annotation, head));
+
+            // Naming the enum itself matches every member by their shared fields
+            let names_enum = matches!(type_, Type::Enum(enum_) if type_annotation_equals(type_annotation, &enum_.type_annotation()));
+
+            if !names_head && !names_enum {
+                return None;
+            }
+
+            Some(
+                head.fields
+                    .iter()
+                    .map(|field| {
+                        field_patterns
+                            .iter()
+                            .find(|field_pattern| field_pattern.identifier == field.field_name)
+                            .map(|field_pattern| field_pattern.pattern.clone())
+                            .unwrap_or(Pattern::Wildcard)
+                    })
+                    .collect(),
+            )
+        }
+        pattern => (literal_name(pattern)? == head.name).then(Vec::new),
+    }
+}
+
+/// Whether `row` matches some value none of `rows` match, the columns of which hold values of
+/// `types`.
+///
+/// Patterns which test values without taking them apart, like ranges and literals of types with
+/// too many values to list, are only taken to match what the same pattern does. This never calls
+/// an arm redundant which isn't, but can miss an arm covered by several ranges.
+fn is_useful(rows: &[Vec<Pattern>], row: &[Pattern], types: &[Type]) -> bool {
+    if rows.is_empty() {
+        return true;
+    }
+
+    let Some((pattern, rest)) = row.split_first() else {
+        return false;
+    };
+
+    let type_ = &types[0];
+    let heads = constructors(type_);
+
+    if let (Pattern::Wildcard | Pattern::Variable(_), Some(heads)) = (pattern, &heads) {
+        // When some constructor isn't named by any row, only the rows matching anything here can
+        // match the values it makes
+        let all_named = heads.iter().all(|head| {
+            rows.iter().any(|row| {
+                !matches!(row[0], Pattern::Wildcard | Pattern::Variable(_))
+                    && specialize(&row[0], head, type_).is_some()
+            })
+        });
+
+        if all_named {
+            return heads.iter().any(|head| {
+                let fields = vec![Pattern::Wildcard; head.fields.len()];
+                is_useful_for(rows, head, fields, rest, types)
+            });
+        }
+    }
+
+    if !matches!(pattern, Pattern::Wildcard | Pattern::Variable(_)) {
+        if let Some(heads) = &heads {
+            return heads
+                .iter()
+                .any(|head| match specialize(pattern, head, type_) {
+                    Some(fields) => is_useful_for(rows, head, fields, rest, types),
+                    None => false,
+                });
+        }
+    }
+
+    let matching: Vec<Vec<Pattern>> = rows
+        .iter()
+        .filter(|row| {
+            matches!(row[0], Pattern::Wildcard | Pattern::Variable(_)) || row[0] == *pattern
+        })
+        .map(|row| row[1..].to_vec())
+        .collect();
+
+    is_useful(&matching, rest, &types[1..])
+}
+
+/// Whether the values made by `head` whose fields `fields` match, and the rest of which `rest`
+/// matches, are matched by none of `rows`
+fn is_useful_for(
+    rows: &[Vec<Pattern>],
+    head: &Head,
+    fields: Vec<Pattern>,
+    rest: &[Pattern],
+    types: &[Type],
+) -> bool {
+    let specialized: Vec<Vec<Pattern>> = rows
+        .iter()
+        .filter_map(|row| {
+            let mut specialized = specialize(&row[0], head, &types[0])?;
+            specialized.extend_from_slice(&row[1..]);
+            Some(specialized)
+        })
+        .collect();
+
+    let row: Vec<Pattern> = fields.into_iter().chain(rest.iter().cloned()).collect();
+
+    let types: Vec<Type> = head
+        .fields
+        .iter()
+        .map(|field| field.field_type.clone())
+        .chain(types[1..].iter().cloned())
+        .collect();
+
+    is_useful(&specialized, &row, &types)
+}
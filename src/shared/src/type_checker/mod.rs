@@ -1,12 +1,38 @@
 pub mod ast;
+pub mod completion;
+pub mod constant;
 pub mod decision_tree;
+pub mod decision_tree_cache;
 pub mod full_name;
+pub mod hover;
+pub mod imports;
+pub mod incremental;
+pub mod interface;
+pub mod interner;
+pub mod match_report;
+pub mod monomorphization;
+pub mod outline;
+pub mod references;
+pub mod rename;
+pub mod semantic_tokens;
+pub mod session;
+pub mod signature;
+pub mod suggestion;
+pub mod transform;
+#[allow(clippy::module_inception)]
 pub mod type_checker;
 pub mod type_environment;
+pub mod type_id;
+pub mod variance;
+pub mod visit;
 
+mod comparison;
+mod definite_assignment;
 mod expressions;
 mod scope;
 mod statements;
+mod tail_call;
+mod unused;
 
 pub use full_name::*;
 pub use type_checker::*;
@@ -39,9 +65,12 @@ impl FullName for Struct {
     }
 }
 
+/// A struct which refers to itself holds itself without its fields, which is the same struct as
+/// the one with its fields
 impl PartialEq for Struct {
     fn eq(&self, other: &Self) -> bool {
-        self.type_identifier == other.type_identifier && self.fields == other.fields
+        self.type_identifier == other.type_identifier
+            && (self.fields.is_empty() || other.fields.is_empty() || self.fields == other.fields)
     }
 }
 
@@ -54,7 +83,7 @@ pub struct StructField {
 
 pub fn get_field_by_name<'a>(
     struct_fields: &'a [StructField],
-    field_name: &'a str,
+    field_name: &str,
 ) -> Option<&'a StructField> {
     struct_fields.iter().find(|f| f.field_name == field_name)
 }
@@ -102,6 +131,10 @@ impl EnumMember {
 
 impl FullName for EnumMember {
     fn full_name(&self) -> String {
+        if self.fields.is_empty() {
+            return format!("{}::{}", self.enum_name, self.discriminant_name);
+        }
+
         format!(
             "{}::{} {{{}}}",
             self.enum_name,
@@ -189,9 +222,32 @@ impl FullName for TypeAlias {
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct Newtype {
+    pub type_identifier: TypeIdentifier,
+    pub underlying_type: Box<Type>,
+}
+
+impl Newtype {
+    pub fn type_annotation(&self) -> TypeAnnotation {
+        TypeAnnotation::from(self.type_identifier.clone())
+    }
+}
+
+impl FullName for Newtype {
+    fn full_name(&self) -> String {
+        format!(
+            "newtype {} = {}",
+            self.type_identifier,
+            self.underlying_type.full_name()
+        )
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Protocol {
     pub type_identifier: TypeIdentifier,
+    pub associated_types: Vec<parser::AssociatedType>,
     pub functions: Vec<(TypeIdentifier, Type)>,
 }
 
@@ -267,6 +323,7 @@ pub enum Type {
     EnumMember(EnumMember),
     Union(Union),
     TypeAlias(TypeAlias),
+    Newtype(Newtype),
     Protocol(Protocol),
     Function(Function),
     Literal {
@@ -274,9 +331,80 @@ pub enum Type {
         type_: Box<Type>,
     },
     Tuple(Vec<Type>),
+    Record(Vec<(String, Type)>),
 }
 
 impl Type {
+    /// Whether the type is `Int`, `UInt` or `Float`, or a literal of one of them
+    pub fn is_numeric(&self) -> bool {
+        self.is_integer() || self.is_float()
+    }
+
+    /// Whether the type is `Int` or `UInt`, or a literal of one of them
+    pub fn is_integer(&self) -> bool {
+        match self {
+            Type::Literal { type_, .. } => type_.is_integer(),
+            Type::Int | Type::UInt => true,
+            _ => false,
+        }
+    }
+
+    /// Whether the type is `Float`, or a literal of it
+    pub fn is_float(&self) -> bool {
+        match self {
+            Type::Literal { type_, .. } => type_.is_float(),
+            Type::Float => true,
+            _ => false,
+        }
+    }
+
+    /// Whether the type is a number which can be negative, `Int` or `Float`
+    pub fn is_signed(&self) -> bool {
+        match self {
+            Type::Literal { type_, .. } => type_.is_signed(),
+            Type::Int | Type::Float => true,
+            _ => false,
+        }
+    }
+
+    /// Whether the type is a number, `Char` or `Bool`, which are the types held in a single value
+    /// rather than built from others
+    pub fn is_primitive(&self) -> bool {
+        match self {
+            Type::Literal { type_, .. } => type_.is_primitive(),
+            Type::Char | Type::Bool => true,
+            other => other.is_numeric(),
+        }
+    }
+
+    /// Whether the generic appears anywhere in the type, including in the types given to a
+    /// generic struct or enum
+    pub fn mentions(&self, generic: &GenericType) -> bool {
+        match self {
+            Type::Generic(other) => other == generic,
+            Type::Array(element) => element.mentions(generic),
+            Type::Tuple(elements) => elements.iter().any(|e| e.mentions(generic)),
+            Type::Record(fields) => fields.iter().any(|(_, t)| t.mentions(generic)),
+            Type::Function(Function {
+                param, return_type, ..
+            }) => {
+                param.as_ref().is_some_and(|p| p.type_.mentions(generic))
+                    || return_type.mentions(generic)
+            }
+            Type::Struct(Struct {
+                type_identifier: TypeIdentifier::ConcreteType(_, type_annotations),
+                ..
+            })
+            | Type::Enum(Enum {
+                type_identifier: TypeIdentifier::ConcreteType(_, type_annotations),
+                ..
+            }) => type_annotations
+                .iter()
+                .any(|t| annotation_mentions(t, generic)),
+            _ => false,
+        }
+    }
+
     pub fn unsubstitute(self) -> Type {
         match self {
             Type::Substitution { actual_type, .. } => (*actual_type).unsubstitute(),
@@ -380,6 +508,91 @@ impl Type {
         })
     }
 
+    /// The built-in `Result<T, E>` which is either `Ok` with a value or `Err` with an error
+    pub fn result_of(value: Type, error: Type) -> Type {
+        let result_ident = TypeIdentifier::ConcreteType(
+            "Result".to_string(),
+            vec![value.type_annotation(), error.type_annotation()],
+        );
+
+        let member = |name: &str, field_name: &str, field_type: Type| {
+            (
+                name.to_string(),
+                Type::EnumMember(EnumMember {
+                    enum_name: result_ident.clone(),
+                    discriminant_name: name.to_string(),
+                    fields: vec![StructField {
+                        struct_name: TypeIdentifier::MemberType(
+                            Box::new(result_ident.clone()),
+                            name.to_string(),
+                        ),
+                        field_name: field_name.to_string(),
+                        field_type,
+                    }],
+                }),
+            )
+        };
+
+        Type::Enum(Enum {
+            type_identifier: result_ident.clone(),
+            shared_fields: Vec::new(),
+            members: [member("Ok", "value", value), member("Err", "error", error)]
+                .into_iter()
+                .collect(),
+        })
+    }
+
+    /// The value and error types of a result, if this is one
+    pub fn result_types(&self) -> Option<(&Type, &Type)> {
+        let Type::Enum(Enum {
+            type_identifier,
+            members,
+            ..
+        }) = self
+        else {
+            return None;
+        };
+
+        if type_identifier.name() != "Result" {
+            return None;
+        }
+
+        let field = |member: &str, field_name: &str| match members.get(member) {
+            Some(Type::EnumMember(EnumMember { fields, .. })) => fields
+                .iter()
+                .find(|field| field.field_name == field_name)
+                .map(|field| &field.field_type),
+            _ => None,
+        };
+
+        field("Ok", "value").zip(field("Err", "error"))
+    }
+
+    /// The type inside an option, if this is one
+    pub fn option_inner(&self) -> Option<&Type> {
+        let Type::Enum(Enum {
+            type_identifier,
+            members,
+            ..
+        }) = self
+        else {
+            return None;
+        };
+
+        if type_identifier.name() != "Option" {
+            return None;
+        }
+
+        let Some(Type::EnumMember(EnumMember { fields, .. })) = members.get("Some") else {
+            return None;
+        };
+
+        fields
+            .iter()
+            .find(|field| field.field_name == "f0")
+            .map(|field| &field.field_type)
+    }
+
     pub fn from_literal(literal: &parser::Literal) -> Result<Type, String> {
         match literal {
             parser::Literal::Unit => Ok(Type::Literal {
@@ -436,6 +649,7 @@ impl Type {
             ),
             Type::Union(u) => u.type_identifier.clone(),
             Type::TypeAlias(u) => u.type_identifier.clone(),
+            Type::Newtype(n) => n.type_identifier.clone(),
             Type::Protocol(u) => u.type_identifier.clone(),
             Type::Function(f) => f
                 .identifier
@@ -449,7 +663,14 @@ impl Type {
         match self {
             // NOTE: Uncomment this line if you want to see the underlying errors related to unknown types in protocols.
             // Type::Unknown => TypeAnnotation::Type("{unknown}".to_string())
-            Type::Substitution { actual_type, .. } => actual_type.type_annotation(),
+            Type::Substitution {
+                type_identifier,
+                actual_type,
+            } => match actual_type.as_ref() {
+                // Self within a protocol declaration is not substituted yet
+                Type::Unknown => TypeAnnotation::Type(type_identifier.name().to_owned()),
+                _ => actual_type.type_annotation(),
+            },
             Type::Generic(name) => TypeAnnotation::Type(name.type_name.clone()),
             Type::Void => TypeAnnotation::Type("Void".to_string()),
             Type::Unit => TypeAnnotation::Type("Unit".to_string()),
@@ -465,12 +686,23 @@ impl Type {
             Type::EnumMember(em) => em.type_annotation(),
             Type::Union(u) => u.type_annotation(),
             Type::TypeAlias(u) => u.type_annotation(),
+            Type::Newtype(n) => n.type_annotation(),
+            Type::Protocol(p) => TypeAnnotation::Type(p.type_identifier.name().to_owned()),
             Type::Function(f) => f
                 .type_annotation()
                 .unwrap_or_else(|| panic!("Closure has no type annotation")),
             Type::Literal { type_, .. } => {
                 TypeAnnotation::Literal(Box::new((*type_.clone()).into()))
             }
+            Type::Tuple(types) => {
+                TypeAnnotation::Tuple(types.iter().map(|t| t.type_annotation()).collect())
+            }
+            Type::Record(fields) => TypeAnnotation::Record(
+                fields
+                    .iter()
+                    .map(|(n, t)| (n.clone(), t.type_annotation()))
+                    .collect(),
+            ),
             _ => panic!("Cannot get type annotation for type {}", self.full_name()),
         }
     }
@@ -489,44 +721,24 @@ impl Type {
                     ));
                 };
 
-                let mut type_map = HashMap::new();
-
-                for (ta, gt) in concrete_types.iter().zip(generics) {
-                    type_map.insert(gt, ta);
-                }
-
-                let mut fields = Vec::new();
-
-                for StructField {
-                    struct_name,
-                    field_name,
-                    field_type,
-                } in s.fields.iter()
-                {
-                    let Type::Generic(generic) = field_type.clone() else {
-                        fields.push(StructField {
-                            struct_name: struct_name.clone(),
-                            field_name: field_name.clone(),
-                            field_type: field_type.clone(),
-                        });
-
-                        continue;
-                    };
-
-                    let concrete_type = type_map.get(&generic).ok_or(format!(
-                        "No concrete type found for generic type {}",
-                        generic.type_name
-                    ))?;
-
-                    let concrete_type =
-                        check_type_annotation(concrete_type, &vec![], type_environment.clone())?;
-
-                    fields.push(StructField {
-                        struct_name: struct_name.clone(),
-                        field_name: field_name.clone(),
-                        field_type: concrete_type,
-                    });
-                }
+                let type_map: HashMap<&GenericType, &TypeAnnotation> =
+                    generics.iter().zip(concrete_types.iter()).collect();
+
+                let fields = s
+                    .fields
+                    .iter()
+                    .map(|field| {
+                        Ok(StructField {
+                            struct_name: field.struct_name.clone(),
+                            field_name: field.field_name.clone(),
+                            field_type: with_concrete_generics(
+                                &field.field_type,
+                                &type_map,
+                                type_environment.clone(),
+                            )?,
+                        })
+                    })
+                    .collect::<Result<Vec<_>, String>>()?;
 
                 Ok(Type::Struct(Struct {
                     type_identifier: TypeIdentifier::ConcreteType(name, concrete_types),
@@ -723,44 +935,26 @@ impl Type {
                     type_map.insert(gt, ta);
                 }
 
-                let cloned_param = match param {
-                    Some(Parameter { identifier, type_ }) => {
-                        if let Type::Generic(generic) = *type_.clone() {
-                            let concrete_type = type_map.get(&generic).ok_or(format!(
-                                "No concrete type found for generic type {}",
-                                generic.type_name
-                            ))?;
-
-                            let concrete_type = check_type_annotation(
-                                concrete_type,
-                                &vec![],
+                // The parameters after the first are in the function it returns
+                let cloned_param = param
+                    .as_ref()
+                    .map(|Parameter { identifier, type_ }| {
+                        Ok::<_, String>(Parameter {
+                            identifier: identifier.clone(),
+                            type_: Box::new(with_concrete_generics(
+                                type_,
+                                &type_map,
                                 type_environment.clone(),
-                            )?;
-
-                            Some(Parameter {
-                                identifier: identifier.clone(),
-                                type_: Box::new(concrete_type),
-                            })
-                        } else {
-                            None
-                        }
-                    }
-                    None => None,
-                };
+                            )?),
+                        })
+                    })
+                    .transpose()?;
 
-                let cloned_return_type = if let Type::Generic(generic) = *return_type.clone() {
-                    let concrete_type = type_map.get(&generic).ok_or(format!(
-                        "No concrete type found for generic type {}",
-                        generic.type_name
-                    ))?;
-
-                    let concrete_type =
-                        check_type_annotation(concrete_type, &vec![], type_environment.clone())?;
-
-                    Box::new(concrete_type)
-                } else {
-                    return_type.clone()
-                };
+                let cloned_return_type = Box::new(with_concrete_generics(
+                    return_type,
+                    &type_map,
+                    type_environment.clone(),
+                )?);
 
                 Ok(Type::Function(Function {
                     identifier: if identifier.is_some() {
@@ -803,6 +997,7 @@ impl FullName for Type {
             Type::EnumMember(um) => um.full_name(),
             Type::Union(u) => u.full_name(),
             Type::TypeAlias(t) => t.full_name(),
+            Type::Newtype(n) => n.full_name(),
             Type::Function(f) => f.full_name(),
             Type::Literal { name, type_ } => format!("#{}: {}", type_.full_name(), name),
             Type::Tuple(e) => format!(
@@ -812,6 +1007,14 @@ impl FullName for Type {
                     .collect::<Vec<String>>()
                     .join(", ")
             ),
+            Type::Record(fields) => format!(
+                "{{ {} }}",
+                fields
+                    .iter()
+                    .map(|(n, t)| format!("{}: {}", n, t.full_name()))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
             Type::Protocol(t) => t.full_name(),
         }
     }
@@ -851,6 +1054,80 @@ impl FromStr for Type {
     }
 }
 
+fn annotation_mentions(type_annotation: &TypeAnnotation, generic: &GenericType) -> bool {
+    match type_annotation {
+        TypeAnnotation::Type(name) => *name == generic.type_name,
+        TypeAnnotation::ConcreteType(_, type_annotations)
+        | TypeAnnotation::Tuple(type_annotations) => type_annotations
+            .iter()
+            .any(|t| annotation_mentions(t, generic)),
+        TypeAnnotation::Array(element) => annotation_mentions(element, generic),
+        TypeAnnotation::Record(fields) => {
+            fields.iter().any(|(_, t)| annotation_mentions(t, generic))
+        }
+        TypeAnnotation::Function(param, return_type) => param
+            .iter()
+            .chain(return_type.iter())
+            .any(|t| annotation_mentions(t, generic)),
+        TypeAnnotation::Literal(_) => false,
+    }
+}
+
+/// `type_` with the generics in it, however deeply nested, replaced by their concrete types
+fn with_concrete_generics(
+    type_: &Type,
+    type_map: &HashMap<&GenericType, &TypeAnnotation>,
+    type_environment: Rc<RefCell<TypeEnvironment>>,
+) -> Result<Type, String> {
+    match type_ {
+        Type::Generic(generic) => {
+            let concrete_type = type_map.get(generic).ok_or(format!(
+                "No concrete type found for generic type {}",
+                generic.type_name
+            ))?;
+
+            check_type_annotation(concrete_type, &vec![], type_environment)
+        }
+        Type::Function(Function {
+            identifier,
+            param,
+            return_type,
+        }) => Ok(Type::Function(Function {
+            identifier: identifier.clone(),
+            param: param
+                .as_ref()
+                .map(|Parameter { identifier, type_ }| {
+                    Ok::<_, String>(Parameter {
+                        identifier: identifier.clone(),
+                        type_: Box::new(with_concrete_generics(
+                            type_,
+                            type_map,
+                            type_environment.clone(),
+                        )?),
+                    })
+                })
+                .transpose()?,
+            return_type: Box::new(with_concrete_generics(
+                return_type,
+                type_map,
+                type_environment,
+            )?),
+        })),
+        Type::Array(element) => Ok(Type::Array(Box::new(with_concrete_generics(
+            element,
+            type_map,
+            type_environment,
+        )?))),
+        Type::Tuple(elements) => Ok(Type::Tuple(
+            elements
+                .iter()
+                .map(|element| with_concrete_generics(element, type_map, type_environment.clone()))
+                .collect::<Result<_, _>>()?,
+        )),
+        other => Ok(other.clone()),
+    }
+}
+
 impl Display for Type {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         self.full_name().fmt(f)
@@ -859,7 +1136,69 @@ impl Display for Type {
 
 impl ToKey for Type {
     fn to_key(&self) -> String {
-        self.type_annotation().to_key()
+        match self {
+            // A substitution is found by the name it stands in for, like Self
+            Type::Substitution {
+                type_identifier, ..
+            } => type_identifier.to_key(),
+            _ => self.type_annotation().to_key(),
+        }
+    }
+}
+
+/// Two types which no type can hold the values of both of
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeMismatch {
+    pub left: Box<Type>,
+    pub right: Box<Type>,
+}
+
+impl Display for TypeMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} and {} have no common type", self.left, self.right)
+    }
+}
+
+impl Type {
+    /// The common type of two types, which the values of both can be used as, like the type of an
+    /// `if` is given by its branches.
+    ///
+    /// `Unknown`, the type of a branch which is never taken, gives way to the other type. Different
+    /// literals of a type unify to that type, and a type alias unifies with each type it accepts.
+    ///
+    /// # Examples
+    /// ```
+    /// use shared::type_checker::Type;
+    ///
+    /// let literal = |name: &str| Type::Literal {
+    ///     name: name.to_string(),
+    ///     type_: Box::new(Type::Int),
+    /// };
+    ///
+    /// assert_eq!(Type::unify(&literal("1"), &literal("2")), Ok(Type::Int));
+    /// assert_eq!(Type::unify(&Type::Unknown, &Type::Bool), Ok(Type::Bool));
+    /// ```
+    pub fn unify(left: &Type, right: &Type) -> Result<Type, TypeMismatch> {
+        match (left, right) {
+            (Type::Unknown, other) | (other, Type::Unknown) => Ok(other.clone()),
+            (Type::Substitution { actual_type, .. }, other) => Type::unify(actual_type, other),
+            (other, Type::Substitution { actual_type, .. }) => Type::unify(other, actual_type),
+            _ if type_equals(left, right) => Ok(left.clone()),
+            _ if type_equals(right, left) => Ok(right.clone()),
+            (Type::Literal { type_, .. }, _) => Type::unify(type_, right),
+            (_, Type::Literal { type_, .. }) => Type::unify(left, type_),
+            _ => Err(TypeMismatch {
+                left: Box::new(left.clone()),
+                right: Box::new(right.clone()),
+            }),
+        }
+    }
+
+    /// The common type of all the types, or `Unknown` when there are none
+    pub fn unify_all<'a>(types: impl IntoIterator<Item = &'a Type>) -> Result<Type, TypeMismatch> {
+        types
+            .into_iter()
+            .try_fold(Type::Unknown, |common, type_| Type::unify(&common, type_))
     }
 }
 
@@ -870,9 +1209,12 @@ impl ToKey for Type {
 ///
 /// # Examples
 /// ```
-/// use crate::types::{Type, type_equals};
+/// use shared::type_checker::{type_equals, Type};
 ///
-/// let literal_int = Type::Literal(Literal::Int(0));
+/// let literal_int = Type::Literal {
+///     name: "0".to_string(),
+///     type_: Box::new(Type::Int),
+/// };
 /// let int = Type::Int;
 ///
 /// // Left type is stricter than right type.
@@ -882,7 +1224,19 @@ impl ToKey for Type {
 /// // Left type is less strict than right type.
 /// // This causes the function to return true as the left type can be made from the right type
 /// assert_eq!(type_equals(&int, &literal_int), true);
+/// ```
 pub fn type_equals(left: &Type, right: &Type) -> bool {
+    // Options are compared by what they hold, however they were spelled
+    if let (Some(left), Some(right)) = (left.option_inner(), right.option_inner()) {
+        return type_equals(left, right);
+    }
+
+    if let (Some((left_value, left_error)), Some((right_value, right_error))) =
+        (left.result_types(), right.result_types())
+    {
+        return type_equals(left_value, right_value) && type_equals(left_error, right_error);
+    }
+
     match (left, right) {
         (Type::Substitution { actual_type, .. }, right) => type_equals(actual_type, right),
         (left, Type::Substitution { actual_type, .. }) => type_equals(left, actual_type),
@@ -926,7 +1280,7 @@ pub fn type_equals(left: &Type, right: &Type) -> bool {
                     .param
                     .as_ref()
                     .zip(fr.param.as_ref())
-                    .map_or(true, |(p, p2)| type_equals(&p.type_, &p2.type_))
+                    .is_none_or(|(p, p2)| type_equals(&p.type_, &p2.type_))
         }
         (
             Type::Enum(Enum {
@@ -940,10 +1294,23 @@ pub fn type_equals(left: &Type, right: &Type) -> bool {
                 ..
             }),
         ) => type_identifier == enum_name && members.contains_key(discriminant_name),
+        (Type::Record(left), Type::Record(right)) => record_compatible(left, right),
         _ => left == right,
     }
 }
 
+/// Check if a record can be used where another record is expected.
+/// Records are compared by their fields rather than by name, and the 'right' record may have
+/// fields which the 'left' record does not require.
+pub fn record_compatible(left: &[(String, Type)], right: &[(String, Type)]) -> bool {
+    left.iter().all(|(name, type_)| {
+        right
+            .iter()
+            .find(|(other, _)| other == name)
+            .is_some_and(|(_, other_type)| type_equals(type_, other_type))
+    })
+}
+
 pub fn type_equals_coerce(left: &Type, right: &Type) -> bool {
     match (left, right) {
         (Type::UInt, Type::Literal { name, type_ }) if matches!(**type_, Type::Int) => {
@@ -0,0 +1,106 @@
+use crate::types::{TypeAnnotation, TypeIdentifier};
+
+use super::{
+    ast::{Member, TypedExpression, TypedStatement},
+    visit::{self, TypedVisitor},
+    Function, Type,
+};
+
+/// A generic function and the concrete types of one of its instantiations, which a backend emits
+/// a copy of the function for
+#[derive(Debug, Clone, PartialEq)]
+pub struct Monomorphization {
+    pub function: String,
+    pub type_arguments: Vec<TypeAnnotation>,
+}
+
+/// The instantiations of generic functions at every call in a program, in the order they are
+/// first called with.
+///
+/// A function called with the same type arguments twice is only instantiated once. Calls inside a
+/// generic function which pass on its own generics are left out, as they only become concrete
+/// once that function is instantiated.
+pub fn monomorphizations(program: &TypedStatement) -> Vec<Monomorphization> {
+    let mut collector = Monomorphizations::default();
+    collector.visit_statement(program);
+    collector.monomorphizations
+}
+
+#[derive(Default)]
+struct Monomorphizations {
+    /// The generics of the functions being visited, innermost last
+    generics: Vec<Vec<String>>,
+    monomorphizations: Vec<Monomorphization>,
+}
+
+impl Monomorphizations {
+    fn is_generic(&self, type_argument: &TypeAnnotation) -> bool {
+        match type_argument {
+            TypeAnnotation::Type(name) => self.generics.iter().flatten().any(|g| g == name),
+            TypeAnnotation::ConcreteType(_, type_arguments)
+            | TypeAnnotation::Tuple(type_arguments) => {
+                type_arguments.iter().any(|t| self.is_generic(t))
+            }
+            TypeAnnotation::Array(element) => self.is_generic(element),
+            TypeAnnotation::Record(fields) => fields.iter().any(|(_, t)| self.is_generic(t)),
+            TypeAnnotation::Function(param, return_type) => param
+                .iter()
+                .chain(return_type.iter())
+                .any(|t| self.is_generic(t)),
+            TypeAnnotation::Literal(_) => false,
+        }
+    }
+
+    fn record(&mut self, function: &str, type_arguments: &[TypeAnnotation]) {
+        if type_arguments.iter().any(|t| self.is_generic(t)) {
+            return;
+        }
+
+        let monomorphization = Monomorphization {
+            function: function.to_string(),
+            type_arguments: type_arguments.to_vec(),
+        };
+
+        if !self.monomorphizations.contains(&monomorphization) {
+            self.monomorphizations.push(monomorphization);
+        }
+    }
+}
+
+impl TypedVisitor for Monomorphizations {
+    fn visit_statement(&mut self, statement: &TypedStatement) {
+        let TypedStatement::FunctionDeclaration { identifier, .. } = statement else {
+            return visit::walk_statement(self, statement);
+        };
+
+        let generics = match identifier {
+            TypeIdentifier::GenericType(_, generics) => {
+                generics.iter().map(|g| g.type_name.clone()).collect()
+            }
+            _ => vec![],
+        };
+
+        self.generics.push(generics);
+        visit::walk_statement(self, statement);
+        self.generics.pop();
+    }
+
+    fn visit_expression(&mut self, expression: &TypedExpression) {
+        // The first argument is applied to the function itself, the rest to what it returns
+        if let TypedExpression::Call { callee, .. } = expression {
+            if let TypedExpression::Member(Member::Identifier {
+                type_:
+                    Type::Function(Function {
+                        identifier: Some(TypeIdentifier::ConcreteType(name, type_arguments)),
+                        ..
+                    }),
+                ..
+            }) = callee.as_ref()
+            {
+                self.record(name, type_arguments);
+            }
+        }
+
+        visit::walk_expression(self, expression);
+    }
+}
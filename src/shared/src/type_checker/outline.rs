@@ -0,0 +1,156 @@
+use crate::{
+    parser::{self, Statement, StructField},
+    span::Span,
+};
+
+use super::incremental::declaration_name;
+
+/// What kind of declaration a node of an outline is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutlineKind {
+    Module,
+    Struct,
+    Enum,
+    Function,
+    Field,
+    EnumMember,
+}
+
+/// A declaration and the declarations inside it
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymbolNode {
+    pub name: String,
+    pub kind: OutlineKind,
+    /// Where the name is written. The modules of a path like `shapes::flat` are all written at
+    /// the whole path.
+    pub span: Span,
+    pub children: Vec<SymbolNode>,
+}
+
+/// The declarations of a program as a tree, like an editor shows in its outline.
+///
+/// The declarations after a module declaration are in that module, the same way the type checker
+/// sees them, and a program without one has its declarations at the top. Modules contain their
+/// structs, enums and functions, structs contain their fields and enums contain their shared
+/// fields followed by their members. Everything is in source order.
+pub fn outline(program: &Statement) -> Vec<SymbolNode> {
+    let mut roots = vec![];
+
+    let Statement::Program { statements } = program else {
+        return roots;
+    };
+
+    let mut module: Option<(Vec<String>, Span)> = None;
+
+    for statement in statements {
+        let statement = unwrap_semi(statement);
+
+        if let Statement::ModuleDeclaration(parser::ModuleDeclaration {
+            module_path, span, ..
+        }) = statement
+        {
+            module_children(&mut roots, module_path, *span);
+            module = Some((module_path.clone(), *span));
+            continue;
+        }
+
+        let Some(node) = declaration_node(statement) else {
+            continue;
+        };
+
+        match &module {
+            Some((module_path, span)) => module_children(&mut roots, module_path, *span).push(node),
+            None => roots.push(node),
+        }
+    }
+
+    roots
+}
+
+fn unwrap_semi(statement: &Statement) -> &Statement {
+    match statement {
+        Statement::Semi(statement) => unwrap_semi(statement),
+        statement => statement,
+    }
+}
+
+/// Find the children of the module at the path, adding the modules leading to it if they aren't
+/// in the outline yet
+fn module_children<'a>(
+    nodes: &'a mut Vec<SymbolNode>,
+    module_path: &[String],
+    span: Span,
+) -> &'a mut Vec<SymbolNode> {
+    let Some((name, rest)) = module_path.split_first() else {
+        return nodes;
+    };
+
+    let index = match nodes
+        .iter()
+        .position(|node| node.kind == OutlineKind::Module && &node.name == name)
+    {
+        Some(index) => index,
+        None => {
+            nodes.push(SymbolNode {
+                name: name.clone(),
+                kind: OutlineKind::Module,
+                span,
+                children: vec![],
+            });
+
+            nodes.len() - 1
+        }
+    };
+
+    module_children(&mut nodes[index].children, rest, span)
+}
+
+fn declaration_node(statement: &Statement) -> Option<SymbolNode> {
+    let name = declaration_name(statement)?;
+
+    let (kind, span, children) = match statement {
+        Statement::StructDeclaration(parser::StructDeclaration { span, fields, .. }) => {
+            (OutlineKind::Struct, *span, field_nodes(fields))
+        }
+        Statement::EnumDeclaration(parser::EnumDeclaration {
+            span,
+            shared_fields,
+            members,
+            ..
+        }) => {
+            let mut children = field_nodes(shared_fields);
+
+            children.extend(members.iter().map(|member| SymbolNode {
+                name: member.identifier.clone(),
+                kind: OutlineKind::EnumMember,
+                span: member.span,
+                children: vec![],
+            }));
+
+            (OutlineKind::Enum, *span, children)
+        }
+        Statement::FunctionDeclaration(parser::FunctionDeclaration { span, .. }) => {
+            (OutlineKind::Function, *span, vec![])
+        }
+        _ => return None,
+    };
+
+    Some(SymbolNode {
+        name,
+        kind,
+        span,
+        children,
+    })
+}
+
+fn field_nodes(fields: &[StructField]) -> Vec<SymbolNode> {
+    fields
+        .iter()
+        .map(|field| SymbolNode {
+            name: field.identifier.clone(),
+            kind: OutlineKind::Field,
+            span: field.span,
+            children: vec![],
+        })
+        .collect()
+}
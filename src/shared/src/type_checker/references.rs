@@ -0,0 +1,341 @@
+use crate::{
+    span::Span,
+    types::{TypeAnnotation, TypeIdentifier},
+};
+
+use super::{
+    ast::{Literal, Member, TypedExpression, TypedStatement},
+    decision_tree::{Constructor, Decision, Pattern},
+    visit::{self, TypedVisitor},
+    Function, Type,
+};
+
+/// What kind of declaration a name refers to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolKind {
+    Type,
+    Function,
+}
+
+/// A place which uses a type or a function.
+///
+/// Type annotations have no spans, so the sites inside them are described by what they annotate.
+/// `function` is the name of the function a site is in, or `None` at the top level.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Reference {
+    /// The type of a field, like `start` in `struct Line { start: Point }`
+    Field { owner: String, field: String },
+    /// The type of a function or closure parameter, at the parameter name
+    Parameter { identifier: String, span: Span },
+    /// The return type of a function
+    ReturnType { function: String },
+    /// The type annotation of a `let` binding, at the pattern it annotates
+    Variable { span: Span },
+    /// A struct or enum literal, like `Point { x: 1, y: 2 }`
+    Literal { function: Option<String> },
+    /// A match arm pattern which destructures the type
+    Pattern { function: Option<String> },
+    /// The target type of a cast
+    Cast { function: Option<String> },
+    /// A static member reached through the type, like `new` in `Point::new`
+    StaticMember { member: String, span: Span },
+    /// A use of a function by name, for example to call it
+    Use { span: Span },
+}
+
+/// Find every use of the type or function declared as `name` in the program.
+///
+/// Uses are found by the types the type checker resolved, so a variable or a parameter which
+/// happens to have the same name as a function isn't a use of that function.
+pub fn references_to(program: &TypedStatement, name: &str, kind: SymbolKind) -> Vec<Reference> {
+    let mut finder = References {
+        name,
+        kind,
+        functions: vec![],
+        references: vec![],
+    };
+
+    finder.visit_statement(program);
+    finder.references
+}
+
+struct References<'a> {
+    name: &'a str,
+    kind: SymbolKind,
+    /// The functions being searched, innermost last
+    functions: Vec<String>,
+    references: Vec<Reference>,
+}
+
+impl References<'_> {
+    fn function(&self) -> Option<String> {
+        self.functions.last().cloned()
+    }
+
+    /// Whether the type is the type being searched for, or is built from it like `[Point]`
+    fn mentions(&self, type_: &Type) -> bool {
+        if self.kind != SymbolKind::Type {
+            return false;
+        }
+
+        match type_ {
+            Type::Struct(struct_) => self.is_named(&struct_.type_identifier),
+            Type::Enum(enum_) => self.is_named(&enum_.type_identifier),
+            Type::EnumMember(enum_member) => self.is_named(&enum_member.enum_name),
+            Type::Union(union) => self.is_named(&union.type_identifier),
+            Type::TypeAlias(type_alias) => self.is_named(&type_alias.type_identifier),
+            Type::Newtype(newtype) => self.is_named(&newtype.type_identifier),
+            Type::Protocol(protocol) => self.is_named(&protocol.type_identifier),
+            Type::Array(type_) => self.mentions(type_),
+            Type::Tuple(types) => types.iter().any(|type_| self.mentions(type_)),
+            Type::Record(fields) => fields.iter().any(|(_, type_)| self.mentions(type_)),
+            Type::Function(Function {
+                param, return_type, ..
+            }) => {
+                param
+                    .as_ref()
+                    .is_some_and(|param| self.mentions(&param.type_))
+                    || self.mentions(return_type)
+            }
+            Type::Literal { type_, .. } => self.mentions(type_),
+            Type::Substitution { actual_type, .. } => self.mentions(actual_type),
+            Type::Unknown
+            | Type::Generic(_)
+            | Type::Void
+            | Type::Unit
+            | Type::Int
+            | Type::UInt
+            | Type::Float
+            | Type::String
+            | Type::Char
+            | Type::Bool => false,
+        }
+    }
+
+    /// Whether the type identifier names the type, or has it as a type argument like
+    /// `Option<Point>`
+    fn is_named(&self, type_identifier: &TypeIdentifier) -> bool {
+        match type_identifier {
+            TypeIdentifier::ConcreteType(name, type_annotations) => {
+                name == self.name
+                    || type_annotations
+                        .iter()
+                        .any(|type_annotation| self.is_annotated(type_annotation))
+            }
+            TypeIdentifier::MemberType(type_identifier, _) => self.is_named(type_identifier),
+            type_identifier => type_identifier.name() == self.name,
+        }
+    }
+
+    fn is_annotated(&self, type_annotation: &TypeAnnotation) -> bool {
+        match type_annotation {
+            // Enum members are written as Enum::Member
+            TypeAnnotation::Type(name) => name.split("::").next() == Some(self.name),
+            TypeAnnotation::ConcreteType(name, type_annotations) => {
+                name == self.name
+                    || type_annotations
+                        .iter()
+                        .any(|type_annotation| self.is_annotated(type_annotation))
+            }
+            TypeAnnotation::Array(type_annotation) => self.is_annotated(type_annotation),
+            TypeAnnotation::Tuple(type_annotations) => type_annotations
+                .iter()
+                .any(|type_annotation| self.is_annotated(type_annotation)),
+            TypeAnnotation::Record(fields) => fields
+                .iter()
+                .any(|(_, type_annotation)| self.is_annotated(type_annotation)),
+            TypeAnnotation::Function(param, return_type) => param
+                .iter()
+                .chain(return_type.iter())
+                .any(|type_annotation| self.is_annotated(type_annotation)),
+            TypeAnnotation::Literal(_) => false,
+        }
+    }
+
+    /// Whether the pattern, or a pattern nested in its fields, destructures the type
+    fn destructures(&self, pattern: &Pattern) -> bool {
+        match pattern {
+            Pattern::Constructor(Constructor::Struct {
+                type_annotation,
+                field_patterns,
+            }) => {
+                self.is_annotated(type_annotation)
+                    || field_patterns
+                        .iter()
+                        .any(|field_pattern| self.destructures(&field_pattern.pattern))
+            }
+            _ => false,
+        }
+    }
+
+    fn field(&mut self, owner: String, field: &str, type_: &Type) {
+        if self.mentions(type_) {
+            self.references.push(Reference::Field {
+                owner,
+                field: field.to_string(),
+            });
+        }
+    }
+
+    fn parameter(&mut self, identifier: &str, type_: &Type, span: Span) {
+        if self.mentions(type_) {
+            self.references.push(Reference::Parameter {
+                identifier: identifier.to_string(),
+                span,
+            });
+        }
+    }
+}
+
+impl TypedVisitor for References<'_> {
+    fn visit_statement(&mut self, statement: &TypedStatement) {
+        match statement {
+            TypedStatement::StructDeclaration {
+                type_identifier,
+                fields,
+                ..
+            } => {
+                for field in fields {
+                    self.field(type_identifier.to_string(), &field.identifier, &field.type_);
+                }
+            }
+            TypedStatement::EnumDeclaration {
+                type_identifier,
+                shared_fields,
+                members,
+                ..
+            } => {
+                for field in shared_fields {
+                    self.field(type_identifier.to_string(), &field.identifier, &field.type_);
+                }
+
+                for member in members {
+                    let owner = format!("{}::{}", type_identifier, member.discriminant_name);
+
+                    for field in &member.fields {
+                        self.field(owner.clone(), &field.identifier, &field.type_);
+                    }
+                }
+            }
+            TypedStatement::FunctionDeclaration {
+                identifier,
+                param,
+                return_type,
+                ..
+            } => {
+                if let Some(param) = param {
+                    self.parameter(&param.identifier, &param.type_, param.span);
+                }
+
+                if self.mentions(return_type) {
+                    self.references.push(Reference::ReturnType {
+                        function: identifier.name().to_string(),
+                    });
+                }
+
+                self.functions.push(identifier.name().to_string());
+                visit::walk_statement(self, statement);
+                self.functions.pop();
+            }
+            _ => visit::walk_statement(self, statement),
+        }
+    }
+
+    fn visit_expression(&mut self, expression: &TypedExpression) {
+        match expression {
+            TypedExpression::VariableDeclaration {
+                type_annotation: Some(_),
+                pattern_span,
+                type_,
+                ..
+            } if self.mentions(type_) => {
+                self.references.push(Reference::Variable {
+                    span: *pattern_span,
+                });
+            }
+            TypedExpression::Closure {
+                param: Some(param), ..
+            } => self.parameter(&param.identifier, &param.type_, param.span),
+            TypedExpression::Match { arms, .. } => {
+                for arm in arms {
+                    if self.kind == SymbolKind::Type && self.destructures(&arm.pattern) {
+                        self.references.push(Reference::Pattern {
+                            function: self.function(),
+                        });
+                    }
+                }
+            }
+            TypedExpression::Cast { type_, .. } if self.mentions(type_) => {
+                self.references.push(Reference::Cast {
+                    function: self.function(),
+                });
+            }
+            _ => {}
+        }
+
+        visit::walk_expression(self, expression);
+    }
+
+    fn visit_member(&mut self, member: &Member) {
+        match member {
+            Member::Identifier {
+                symbol,
+                type_:
+                    Type::Function(Function {
+                        identifier: Some(identifier),
+                        ..
+                    }),
+                span,
+            } if self.kind == SymbolKind::Function
+                && symbol == self.name
+                && identifier.name() == self.name =>
+            {
+                self.references.push(Reference::Use { span: *span });
+            }
+            Member::StaticMemberAccess {
+                type_annotation,
+                member,
+                symbol,
+                ..
+            } if self.kind == SymbolKind::Type && self.is_annotated(type_annotation) => {
+                if let Member::Identifier { span, .. } = member.as_ref() {
+                    self.references.push(Reference::StaticMember {
+                        member: symbol.clone(),
+                        span: *span,
+                    });
+                }
+            }
+            _ => {}
+        }
+
+        visit::walk_member(self, member);
+    }
+
+    fn visit_literal(&mut self, literal: &Literal) {
+        if let Literal::Struct { type_, .. } | Literal::Enum { type_, .. } = literal {
+            if self.mentions(type_) {
+                self.references.push(Reference::Literal {
+                    function: self.function(),
+                });
+            }
+        }
+
+        visit::walk_literal(self, literal);
+    }
+
+    fn visit_decision(&mut self, decision: &Decision) {
+        match decision {
+            // The conditions of guards compare the matchee to literals made from the patterns,
+            // which are already found through the arms
+            Decision::Guard {
+                consequence,
+                alternative,
+                ..
+            } => {
+                self.visit_decision(consequence);
+                self.visit_decision(alternative);
+            }
+            _ => visit::walk_decision(self, decision),
+        }
+    }
+}
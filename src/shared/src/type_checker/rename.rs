@@ -0,0 +1,191 @@
+use crate::span::Span;
+
+use super::{
+    ast::{Block, Member, TypedExpression, TypedStatement},
+    decision_tree::{Decision, Pattern},
+    visit::{self, TypedVisitor},
+};
+
+/// Find every place which has to change to safely rename the variable defined at `offset`.
+///
+/// The variable is the parameter, `for` variable or single variable `let` binding whose name spans
+/// the byte `offset`. The spans of its definition and of every reference to it are returned in
+/// source order. References to other variables with the same name which shadow it are left out.
+/// Returns `None` when no variable is defined at `offset`.
+pub fn rename_sites(program: &TypedStatement, offset: usize) -> Option<Vec<Span>> {
+    let mut finder = RenameSites {
+        offset,
+        scopes: vec![],
+        next_binding: 0,
+        target: None,
+        sites: vec![],
+    };
+
+    finder.visit_statement(program);
+    finder.target?;
+
+    // Desugared expressions like `a += 1` and the scrutinee of a match refer to the variable
+    // more than once at the same place
+    finder.sites.sort_by_key(|span| (span.start, span.end));
+    finder.sites.dedup_by_key(|span| (span.start, span.end));
+    Some(finder.sites)
+}
+
+/// A variable in scope, identified by the order it was bound in
+struct Binding {
+    name: String,
+    id: usize,
+}
+
+struct RenameSites {
+    offset: usize,
+    /// The bindings of every scope being checked, innermost last
+    scopes: Vec<Vec<Binding>>,
+    next_binding: usize,
+    /// The binding being renamed, once its definition has been found
+    target: Option<usize>,
+    sites: Vec<Span>,
+}
+
+impl RenameSites {
+    /// Check `f` in a new scope which starts with the given bindings
+    fn with_scope(&mut self, bindings: &[(&str, Span)], f: impl FnOnce(&mut Self)) {
+        self.scopes.push(vec![]);
+
+        for (name, span) in bindings {
+            self.bind(name, *span);
+        }
+
+        f(self);
+        self.scopes.pop();
+    }
+
+    /// Bind a variable in the innermost scope. The span is empty for bindings which weren't
+    /// written as a lone name, like the fields of a destructuring pattern.
+    fn bind(&mut self, name: &str, span: Span) {
+        let id = self.next_binding;
+        self.next_binding += 1;
+
+        if self.target.is_none() && !span.is_empty() && span.contains(self.offset) {
+            self.target = Some(id);
+            self.sites.push(span);
+        }
+
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.push(Binding {
+                name: name.to_string(),
+                id,
+            });
+        }
+    }
+
+    fn resolve(&self, symbol: &str) -> Option<usize> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.iter().rev().find(|binding| binding.name == symbol))
+            .map(|binding| binding.id)
+    }
+}
+
+impl TypedVisitor for RenameSites {
+    fn visit_statement(&mut self, statement: &TypedStatement) {
+        match statement {
+            TypedStatement::Program { .. } => {
+                self.with_scope(&[], |finder| visit::walk_statement(finder, statement));
+            }
+            TypedStatement::FunctionDeclaration { param, .. } => {
+                let param = param
+                    .as_ref()
+                    .map(|param| (param.identifier.as_str(), param.span));
+
+                self.with_scope(param.as_slice(), |finder| {
+                    visit::walk_statement(finder, statement)
+                });
+            }
+            _ => visit::walk_statement(self, statement),
+        }
+    }
+
+    fn visit_expression(&mut self, expression: &TypedExpression) {
+        match expression {
+            // The initializer is checked first since it can't see the variables it initializes
+            TypedExpression::VariableDeclaration {
+                pattern,
+                pattern_span,
+                ..
+            } => {
+                visit::walk_expression(self, expression);
+
+                match pattern {
+                    Pattern::Variable(name) => self.bind(name, *pattern_span),
+                    pattern => {
+                        for name in pattern.variables() {
+                            self.bind(name, Span::default());
+                        }
+                    }
+                }
+            }
+            TypedExpression::Closure { param, .. } => {
+                let param = param
+                    .as_ref()
+                    .map(|param| (param.identifier.as_str(), param.span));
+
+                self.with_scope(param.as_slice(), |finder| {
+                    visit::walk_expression(finder, expression)
+                });
+            }
+            TypedExpression::For {
+                identifier,
+                identifier_span,
+                ..
+            } => {
+                self.with_scope(&[(identifier.as_str(), *identifier_span)], |finder| {
+                    visit::walk_expression(finder, expression)
+                });
+            }
+            _ => visit::walk_expression(self, expression),
+        }
+    }
+
+    fn visit_member(&mut self, member: &Member) {
+        match member {
+            Member::Identifier { symbol, span, .. } => {
+                if self.target.is_some() && !span.is_empty() && self.resolve(symbol) == self.target
+                {
+                    self.sites.push(*span);
+                }
+            }
+            // Only the object is a variable, the member is one of its fields or methods
+            Member::MemberAccess { object, .. } => self.visit_expression(object),
+            Member::StaticMemberAccess { .. } => {}
+        }
+    }
+
+    fn visit_block(&mut self, block: &Block) {
+        self.with_scope(&[], |finder| visit::walk_block(finder, block));
+    }
+
+    fn visit_decision(&mut self, decision: &Decision) {
+        match decision {
+            // The variables of a match arm pattern shadow outer variables in the arm
+            Decision::Switch {
+                cases, fallback, ..
+            } => {
+                for case in cases {
+                    let bindings: Vec<(&str, Span)> = case
+                        .pattern
+                        .variables()
+                        .into_iter()
+                        .map(|name| (name, Span::default()))
+                        .collect();
+
+                    self.with_scope(&bindings, |finder| finder.visit_decision(&case.body));
+                }
+
+                self.visit_decision(fallback);
+            }
+            _ => visit::walk_decision(self, decision),
+        }
+    }
+}
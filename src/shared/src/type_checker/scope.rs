@@ -1,6 +1,6 @@
 use std::{fmt::Display, vec};
 
-use super::{type_equals_coerce, Type};
+use super::Type;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Scope {
@@ -14,15 +14,11 @@ impl Scope {
     }
 
     pub fn fold(&self) -> Result<Type, String> {
-        let type_ = self.types.iter().try_fold(Type::Void, |acc, t| {
-            if acc == Type::Void || type_equals_coerce(&acc, t) {
-                Ok(t.clone())
-            } else {
-                Err(format!("Type mismatch in scope: {} != {}", acc, t))
-            }
-        })?;
-
-        Ok(type_)
+        self.types.iter().try_fold(Type::Void, |acc, t| match acc {
+            Type::Void => Ok(t.clone()),
+            acc => Type::unify(&acc, t)
+                .map_err(|_| format!("Type mismatch in scope: {} != {}", acc, t)),
+        })
     }
 }
 
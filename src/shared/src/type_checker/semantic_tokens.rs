@@ -0,0 +1,325 @@
+use crate::{span::Span, types::TypeAnnotation};
+
+use super::{
+    ast::{Block, Literal, Member, TypedExpression, TypedStatement},
+    decision_tree::{Decision, Pattern},
+    visit::{self, TypedVisitor},
+    Type,
+};
+
+/// What a piece of the source is, for highlighting it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    Type,
+    Function,
+    Variable,
+    Parameter,
+    EnumMember,
+    Operator,
+    Literal,
+}
+
+/// Classify the names, operators and literals of a program by what the type checker resolved them
+/// to, so a variable holding a function is a variable while the function itself is a function.
+///
+/// `source` is the text the program was checked from. It is used to find operators and the names
+/// of types inside the expressions they are part of, which don't have spans of their own. Type
+/// annotations don't have spans either, so the types named in them aren't classified.
+/// The tokens are sorted by where they start.
+pub fn semantic_tokens(program: &TypedStatement, source: &str) -> Vec<(Span, TokenKind)> {
+    let mut classifier = SemanticTokens {
+        source,
+        scopes: vec![],
+        tokens: vec![],
+    };
+
+    classifier.visit_statement(program);
+
+    let mut tokens = classifier.tokens;
+    tokens.sort_by_key(|(span, _)| (span.start, span.end));
+    // A match checks its matchee again in every guard of its decision tree
+    tokens.dedup_by(|(a, _), (b, _)| a.start == b.start && a.end == b.end);
+    tokens
+}
+
+struct SemanticTokens<'a> {
+    source: &'a str,
+    /// The variables and parameters of every scope being classified, innermost last
+    scopes: Vec<Vec<(String, TokenKind)>>,
+    tokens: Vec<(Span, TokenKind)>,
+}
+
+impl SemanticTokens<'_> {
+    fn with_scope(&mut self, bindings: &[(&str, TokenKind)], f: impl FnOnce(&mut Self)) {
+        self.scopes.push(vec![]);
+
+        for (name, kind) in bindings {
+            self.bind(name, *kind);
+        }
+
+        f(self);
+        self.scopes.pop();
+    }
+
+    fn bind(&mut self, name: &str, kind: TokenKind) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.push((name.to_string(), kind));
+        }
+    }
+
+    fn resolve(&self, symbol: &str) -> Option<TokenKind> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.iter().rev().find(|(name, _)| name == symbol))
+            .map(|(_, kind)| *kind)
+    }
+
+    fn push(&mut self, span: Span, kind: TokenKind) {
+        if !span.is_empty() && span.end <= self.source.len() {
+            self.tokens.push((span, kind));
+        }
+    }
+
+    /// Where `text` is first written inside `within`
+    fn find(&self, text: &str, within: Span) -> Option<Span> {
+        let start = self.source.get(within.start..within.end)?.find(text)? + within.start;
+        Some(Span::new(start, start + text.len()))
+    }
+
+    /// Where the type is written before the `::` that ends at `member`, like `Point` in
+    /// `Point::new`. Static members reached through a value, like methods, have no such type.
+    fn type_before(&self, member: Span) -> Option<Span> {
+        let before = self.source.get(..member.start)?.trim_end();
+        let before = before.strip_suffix("::")?.trim_end();
+        let name_length = before
+            .chars()
+            .rev()
+            .take_while(|c| c.is_alphanumeric() || *c == '_')
+            .map(char::len_utf8)
+            .sum::<usize>();
+
+        (name_length > 0).then(|| Span::new(before.len() - name_length, before.len()))
+    }
+
+    fn literal(&mut self, literal: &Literal, span: Span) {
+        match literal {
+            Literal::Struct {
+                type_annotation, ..
+            } => {
+                if let Some(name) = self.find(&base_name(type_annotation), span) {
+                    self.push(name, TokenKind::Type);
+                }
+            }
+            Literal::Enum {
+                type_annotation,
+                member,
+                ..
+            } => {
+                let Some(name) = self.find(&base_name(type_annotation), span) else {
+                    return;
+                };
+
+                self.push(name, TokenKind::Type);
+
+                if let Some(member) = self.find(member, Span::new(name.end, span.end)) {
+                    self.push(member, TokenKind::EnumMember);
+                }
+            }
+            Literal::Array { .. } => {}
+            _ => self.push(span, TokenKind::Literal),
+        }
+    }
+
+    /// Classify what a member is named by the type it resolved to
+    fn member_name(&mut self, member: &Member) {
+        if let Member::Identifier { type_, span, .. } = member {
+            let kind = match type_ {
+                Type::Function(_) => TokenKind::Function,
+                Type::EnumMember(_) => TokenKind::EnumMember,
+                _ => TokenKind::Variable,
+            };
+
+            self.push(*span, kind);
+        }
+    }
+}
+
+/// The name of the type an annotation starts with, like `Color` in `Color::Red`
+fn base_name(type_annotation: &TypeAnnotation) -> String {
+    let name = match type_annotation {
+        TypeAnnotation::ConcreteType(name, _) => name.clone(),
+        type_annotation => type_annotation.to_string(),
+    };
+
+    name.split("::").next().unwrap_or_default().to_string()
+}
+
+/// Where an expression is written, falling back to the name of the member it reads
+fn extent(expression: &TypedExpression) -> Span {
+    match expression {
+        TypedExpression::Member(
+            Member::MemberAccess { member, .. } | Member::StaticMemberAccess { member, .. },
+        ) => match member.as_ref() {
+            Member::Identifier { span, .. } => *span,
+            _ => Span::default(),
+        },
+        expression => expression.span(),
+    }
+}
+
+impl TypedVisitor for SemanticTokens<'_> {
+    fn visit_statement(&mut self, statement: &TypedStatement) {
+        match statement {
+            TypedStatement::Program { .. } => {
+                self.with_scope(&[], |classifier| {
+                    visit::walk_statement(classifier, statement)
+                });
+            }
+            TypedStatement::FunctionDeclaration { param, .. } => {
+                let param = param.as_ref().map(|param| {
+                    self.push(param.span, TokenKind::Parameter);
+                    (param.identifier.as_str(), TokenKind::Parameter)
+                });
+
+                self.with_scope(param.as_slice(), |classifier| {
+                    visit::walk_statement(classifier, statement)
+                });
+            }
+            _ => visit::walk_statement(self, statement),
+        }
+    }
+
+    fn visit_expression(&mut self, expression: &TypedExpression) {
+        match expression {
+            TypedExpression::VariableDeclaration {
+                pattern,
+                pattern_span,
+                ..
+            } => {
+                visit::walk_expression(self, expression);
+
+                if let Pattern::Variable(_) = pattern {
+                    self.push(*pattern_span, TokenKind::Variable);
+                }
+
+                for name in pattern.variables() {
+                    self.bind(name, TokenKind::Variable);
+                }
+            }
+            TypedExpression::Closure { param, .. } => {
+                let param = param.as_ref().map(|param| {
+                    self.push(param.span, TokenKind::Parameter);
+                    (param.identifier.as_str(), TokenKind::Parameter)
+                });
+
+                self.with_scope(param.as_slice(), |classifier| {
+                    visit::walk_expression(classifier, expression)
+                });
+            }
+            TypedExpression::For {
+                identifier,
+                identifier_span,
+                ..
+            } => {
+                self.push(*identifier_span, TokenKind::Variable);
+
+                self.with_scope(
+                    &[(identifier.as_str(), TokenKind::Variable)],
+                    |classifier| visit::walk_expression(classifier, expression),
+                );
+            }
+            TypedExpression::Literal(literal, span) => {
+                self.literal(literal, *span);
+                visit::walk_expression(self, expression);
+            }
+            TypedExpression::Unary { operator, span, .. } => {
+                if let Some(operator) = self.find(&operator.to_string(), *span) {
+                    if operator.start == span.start {
+                        self.push(operator, TokenKind::Operator);
+                    }
+                }
+
+                visit::walk_expression(self, expression);
+            }
+            TypedExpression::Binary {
+                left,
+                operator,
+                right,
+                ..
+            } => {
+                let (left, right) = (extent(left), extent(right));
+
+                if !left.is_empty() && !right.is_empty() {
+                    if let Some(operator) =
+                        self.find(&operator.to_string(), Span::new(left.end, right.start))
+                    {
+                        self.push(operator, TokenKind::Operator);
+                    }
+                }
+
+                visit::walk_expression(self, expression);
+            }
+            _ => visit::walk_expression(self, expression),
+        }
+    }
+
+    fn visit_member(&mut self, member: &Member) {
+        match member {
+            Member::Identifier {
+                symbol,
+                type_,
+                span,
+            } => {
+                let kind = self.resolve(symbol).unwrap_or(match type_ {
+                    Type::Function(_) => TokenKind::Function,
+                    _ => TokenKind::Variable,
+                });
+
+                self.push(*span, kind);
+            }
+            Member::MemberAccess { object, member, .. } => {
+                self.visit_expression(object);
+                self.member_name(member);
+            }
+            Member::StaticMemberAccess { member, .. } => {
+                if let Member::Identifier { span, .. } = member.as_ref() {
+                    if let Some(type_name) = self.type_before(*span) {
+                        self.push(type_name, TokenKind::Type);
+                    }
+                }
+
+                self.member_name(member);
+            }
+        }
+    }
+
+    fn visit_block(&mut self, block: &Block) {
+        self.with_scope(&[], |classifier| visit::walk_block(classifier, block));
+    }
+
+    fn visit_decision(&mut self, decision: &Decision) {
+        match decision {
+            // The variables of a match arm pattern are in scope in the arm
+            Decision::Switch {
+                cases, fallback, ..
+            } => {
+                for case in cases {
+                    let bindings: Vec<(&str, TokenKind)> = case
+                        .pattern
+                        .variables()
+                        .into_iter()
+                        .map(|name| (name, TokenKind::Variable))
+                        .collect();
+
+                    self.with_scope(&bindings, |classifier| {
+                        classifier.visit_decision(&case.body)
+                    });
+                }
+
+                self.visit_decision(fallback);
+            }
+            _ => visit::walk_decision(self, decision),
+        }
+    }
+}
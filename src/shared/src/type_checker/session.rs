@@ -0,0 +1,75 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{lexer, parser};
+
+use super::{
+    ast::{Typed, TypedStatement},
+    create_typed_ast, Rcrc, Type, TypeCheckerConfig, TypeEnvironment,
+};
+
+/// Checks source one statement at a time, like a REPL, keeping what each statement declares for
+/// the ones after it.
+///
+/// Every input is checked in an isolated child of the session environment, so declaring a type,
+/// function or variable again shadows the earlier one instead of failing. What an input declares,
+/// including field defaults, mutable fields and implementations, is merged into the session
+/// environment once it checks, so the session never grows a chain of parents. An input which
+/// fails to check leaves the session as it was.
+#[derive(Debug, Clone)]
+pub struct Session {
+    type_environment: Rcrc<TypeEnvironment>,
+    config: TypeCheckerConfig,
+}
+
+impl Session {
+    pub fn new(config: TypeCheckerConfig) -> Session {
+        Session {
+            type_environment: Rc::new(RefCell::new(TypeEnvironment::new(false))),
+            config,
+        }
+    }
+
+    /// The environment holding everything the inputs so far have declared
+    pub fn type_environment(&self) -> Rcrc<TypeEnvironment> {
+        self.type_environment.clone()
+    }
+
+    /// Check `source`, which has to be a single statement or expression, and get its type
+    pub fn eval_statement(&mut self, source: &str) -> Result<(TypedStatement, Type), String> {
+        let tokens = lexer::tokenize(source)?;
+
+        let statement = match parser::create_ast(tokens, false)? {
+            parser::Statement::Program { mut statements } if statements.len() == 1 => {
+                statements.remove(0)
+            }
+            parser::Statement::Program { statements } => {
+                return Err(format!(
+                    "Expected one statement but found {}",
+                    statements.len()
+                ))
+            }
+            statement => statement,
+        };
+
+        let type_environment = Rc::new(RefCell::new(TypeEnvironment::new_isolated(
+            self.type_environment.clone(),
+        )));
+
+        let program = parser::Statement::Program {
+            statements: vec![statement],
+        };
+
+        let typed_statement =
+            match create_typed_ast(program, type_environment.clone(), self.config.clone())? {
+                TypedStatement::Program { mut statements } if statements.len() == 1 => {
+                    statements.remove(0)
+                }
+                typed_statement => typed_statement,
+            };
+
+        type_environment.borrow().merge_into_parent()?;
+
+        let type_ = typed_statement.get_type();
+        Ok((typed_statement, type_))
+    }
+}
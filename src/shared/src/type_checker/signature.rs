@@ -0,0 +1,134 @@
+use crate::types::TypeAnnotation;
+
+use super::{
+    type_environment::{protocol_member_owner, TypeEnvironment},
+    type_equals, Function, Parameter, Type,
+};
+
+/// What an editor shows while the arguments of a call are written
+#[derive(Debug, Clone, PartialEq)]
+pub struct Signature {
+    /// The callee as it is written, like `add` or `Point::new`
+    pub name: String,
+    /// The types of the parameters in order. Functions take one parameter at a time, so a
+    /// function which returns a function has the parameters of both.
+    pub parameters: Vec<Type>,
+    pub return_type: Type,
+    /// The parameter the next argument is given to, or `None` once every parameter has one
+    pub active_parameter: Option<usize>,
+}
+
+/// Find the signatures of the function called `callee` after `arguments` arguments have been
+/// written, like `add(1, ` after one. Static members are found by their path, like `Point::new`.
+pub fn signature_help(
+    callee: &str,
+    arguments: usize,
+    type_environment: &TypeEnvironment,
+) -> Vec<Signature> {
+    let function_type = match callee.rsplit_once("::") {
+        Some((owner, member)) => {
+            type_environment.get_static_member(TypeAnnotation::from(owner), member)
+        }
+        None => type_environment
+            .get_variable(callee)
+            .or_else(|| type_environment.get_type(callee)),
+    };
+
+    function_type
+        .and_then(|function_type| signature(callee.to_owned(), &function_type, arguments))
+        .into_iter()
+        .collect()
+}
+
+/// Find the signatures of `object.name(` after `arguments` arguments have been written. The
+/// object is given to the first parameter, which is left out of the signatures.
+///
+/// A method can be found in the implementation for the type, in several protocols it implements or
+/// as a function taking the object. All of them are returned, inherent methods first, since the
+/// call is ambiguous until the type checker picks one.
+pub fn method_signature_help(
+    object_type: &Type,
+    name: &str,
+    arguments: usize,
+    type_environment: &TypeEnvironment,
+) -> Vec<Signature> {
+    let type_annotation = object_type.type_annotation();
+
+    let inherent = type_environment
+        .get_static_member(type_annotation.clone(), name)
+        .map(|method_type| (format!("{}::{}", type_annotation, name), method_type));
+
+    let mut protocol_names = type_environment.get_implementations(object_type);
+    protocol_names.sort();
+
+    let protocol = protocol_names.into_iter().filter_map(|protocol_name| {
+        let owner = protocol_member_owner(&protocol_name, type_annotation.clone());
+
+        type_environment
+            .get_static_member(owner, name)
+            .map(|method_type| (format!("{}::{}", protocol_name, name), method_type))
+    });
+
+    let function = type_environment
+        .get_variable(name)
+        .or_else(|| type_environment.get_type(name))
+        .map(|function_type| (name.to_owned(), function_type));
+
+    inherent
+        .into_iter()
+        .chain(protocol)
+        .chain(function)
+        .filter(|(_, method_type)| takes_object(method_type, object_type))
+        .filter_map(|(name, method_type)| signature(name, &method_type, arguments + 1))
+        .map(|mut signature| {
+            signature.parameters.remove(0);
+            signature.active_parameter = signature.active_parameter.map(|index| index - 1);
+            signature
+        })
+        .collect()
+}
+
+fn signature(name: String, function_type: &Type, arguments: usize) -> Option<Signature> {
+    let Type::Function(_) = function_type else {
+        return None;
+    };
+
+    let mut parameters = vec![];
+    let mut return_type = function_type.clone();
+
+    while let Type::Function(Function {
+        param: Some(Parameter { type_, .. }),
+        return_type: next,
+        ..
+    }) = return_type
+    {
+        parameters.push(*type_);
+        return_type = *next;
+    }
+
+    // A function without parameters is called with no arguments
+    if let Type::Function(Function {
+        param: None,
+        return_type: next,
+        ..
+    }) = return_type
+    {
+        return_type = *next;
+    }
+
+    let active_parameter = (arguments < parameters.len()).then_some(arguments);
+
+    Some(Signature {
+        name,
+        parameters,
+        return_type,
+        active_parameter,
+    })
+}
+
+fn takes_object(method_type: &Type, object_type: &Type) -> bool {
+    matches!(
+        method_type,
+        Type::Function(Function { param: Some(param), .. }) if type_equals(&param.type_, object_type)
+    )
+}
@@ -2,20 +2,20 @@ use std::{cell::RefCell, collections::HashMap, rc::Rc, vec};
 
 use crate::{
     parser::{
-        self, ImplementationDeclaration, ModuleDeclaration, ProtocolDeclaration, Statement,
-        UnionDeclaration, Use,
+        self, AssociatedType, Attribute, ImplementationDeclaration, ModuleDeclaration,
+        ProtocolDeclaration, Statement, UnionDeclaration, Use,
     },
-    types::{TypeAnnotation, TypeIdentifier},
+    types::{GenericType, TypeAnnotation, TypeIdentifier},
 };
 
 use super::{
     ast::{self, Typed, TypedExpression, TypedParameter, TypedStatement},
-    expressions,
+    expressions, imports,
     scope::ScopeType,
-    type_checker::DiscoveredType,
-    type_environment::TypeEnvironment,
-    type_equals, Enum, EnumMember, Function, Parameter, Protocol, Rcrc, Struct, StructField, Type,
-    TypeAlias, Union,
+    type_checker::{check_statements, DiscoveredType},
+    type_environment::{protocol_member_owner, TypeEnvironment},
+    type_equals, Enum, EnumMember, Function, Newtype, Parameter, Protocol, Rcrc, Struct,
+    StructField, Type, TypeAlias, Union,
 };
 
 pub fn discover_user_defined_types(statement: &Statement) -> Result<Vec<DiscoveredType>, String> {
@@ -91,6 +91,8 @@ pub fn discover_user_defined_types(statement: &Statement) -> Result<Vec<Discover
             Ok(enum_members.into_iter().chain(Some(enum_)).collect())
         }
         Statement::UnionDeclaration(parser::UnionDeclaration {
+            doc: _,
+            attributes: _,
             access_modifier: _,
             type_identifier,
             literals,
@@ -102,6 +104,8 @@ pub fn discover_user_defined_types(statement: &Statement) -> Result<Vec<Discover
                 .collect(),
         )]),
         Statement::TypeAliasDeclaration(parser::TypeAliasDeclaration {
+            doc: _,
+            attributes: _,
             access_modifier: _,
             type_identifier,
             type_annotations,
@@ -109,7 +113,19 @@ pub fn discover_user_defined_types(statement: &Statement) -> Result<Vec<Discover
             type_identifier.clone(),
             type_annotations.clone(),
         )]),
+        Statement::NewtypeDeclaration(parser::NewtypeDeclaration {
+            doc: _,
+            attributes: _,
+            access_modifier: _,
+            type_identifier,
+            type_annotation,
+        }) => Ok(vec![DiscoveredType::Newtype(
+            type_identifier.clone(),
+            type_annotation.clone(),
+        )]),
         Statement::ProtocolDeclaration(ProtocolDeclaration {
+            doc: _,
+            attributes: _,
             access_modifier: _,
             type_identifier,
             associated_types,
@@ -138,10 +154,11 @@ pub fn discover_user_defined_types(statement: &Statement) -> Result<Vec<Discover
             param: param.clone(),
             return_type_annotation: return_type_annotation
                 .clone()
-                .unwrap_or(Type::Void.type_annotation()),
+                .unwrap_or(Type::Unit.type_annotation()),
         }]),
         Statement::Semi(_) => Ok(vec![]),
         Statement::Expression(_) => Ok(vec![]),
+        Statement::Error(_) => Ok(vec![]),
     }
 }
 
@@ -152,35 +169,67 @@ pub fn check_type(
 ) -> Result<TypedStatement, String> {
     match statement {
         Statement::Program { statements } => {
-            let statements: Result<Vec<TypedStatement>, String> = statements
-                .iter()
-                .map(|s| check_type(s, discovered_types, type_environment.clone()))
-                .collect();
+            let statements = check_statements(
+                statements,
+                discovered_types,
+                type_environment,
+                |_, message| Err(message),
+            )?;
 
-            Ok(TypedStatement::Program {
-                statements: statements?,
-            })
+            Ok(TypedStatement::Program { statements })
         }
         Statement::ModuleDeclaration(ModuleDeclaration {
             access_modifier,
             module_path,
-        }) => Ok(TypedStatement::ModuleDeclaration {
-            access_modifier: access_modifier
-                .clone()
-                .map(|access_modifier| access_modifier.into()),
-            module_path: module_path.clone(),
-            type_: Type::Void,
-        }),
-        Statement::Use(Use { use_item }) => Ok(TypedStatement::Use {
-            use_item: use_item.clone(),
-            type_: Type::Void,
-        }),
+            span: _,
+        }) => {
+            type_environment
+                .borrow_mut()
+                .set_current_module(module_path.clone());
+            type_environment
+                .borrow_mut()
+                .add_module(module_path.clone());
+
+            Ok(TypedStatement::ModuleDeclaration {
+                access_modifier: access_modifier
+                    .clone()
+                    .map(|access_modifier| access_modifier.into()),
+                module_path: module_path.clone(),
+                type_: Type::Void,
+            })
+        }
+        Statement::Use(Use { use_item }) => {
+            for import in imports::resolve_use_item(use_item, type_environment.clone())? {
+                type_environment.borrow_mut().add_import(import);
+            }
+
+            Ok(TypedStatement::Use {
+                use_item: use_item.clone(),
+                type_: Type::Void,
+            })
+        }
         Statement::StructDeclaration(parser::StructDeclaration {
+            doc: _,
+            attributes,
             access_modifier: _,
             type_identifier,
+            span: _,
             where_clause,
             fields,
         }) => {
+            check_attributes(attributes)?;
+
+            type_environment
+                .borrow_mut()
+                .forget_redeclared_type(type_identifier);
+
+            for field in fields {
+                type_environment.borrow_mut().add_visibility(
+                    format!("{}.{}", type_identifier.name(), field.identifier),
+                    field.access_modifier.clone().map(|a| a.into()),
+                );
+            }
+
             let struct_type_environment = Rc::new(RefCell::new(TypeEnvironment::new_parent(
                 type_environment.clone(),
             )));
@@ -207,6 +256,10 @@ pub fn check_type(
                 }
             }
 
+            // The fields are resolved like the struct is already being resolved, so a field of the
+            // struct itself doesn't resolve it again
+            let entered = type_environment.borrow().enter_type(type_identifier.name());
+
             let fields: Result<Vec<ast::StructField>, String> = fields
                 .iter()
                 .map(|field| {
@@ -218,6 +271,12 @@ pub fn check_type(
                         Ok(t) => Ok(ast::StructField {
                             mutable: field.mutable,
                             identifier: field.identifier.clone(),
+                            default: check_field_default(
+                                field,
+                                &t,
+                                discovered_types,
+                                struct_type_environment.clone(),
+                            )?,
                             type_: t,
                         }),
                         Err(e) => Err(e),
@@ -225,6 +284,27 @@ pub fn check_type(
                 })
                 .collect();
 
+            if entered {
+                type_environment.borrow().exit_type();
+            }
+
+            for field in fields.clone()? {
+                if field.mutable {
+                    type_environment.borrow_mut().add_mutable_field(format!(
+                        "{}.{}",
+                        type_identifier.name(),
+                        field.identifier
+                    ));
+                }
+
+                if let Some(default) = field.default {
+                    type_environment.borrow_mut().add_field_default(
+                        format!("{}.{}", type_identifier.name(), field.identifier),
+                        default,
+                    );
+                }
+            }
+
             let field_types: Result<Vec<StructField>, String> = fields
                 .clone()?
                 .iter()
@@ -252,12 +332,31 @@ pub fn check_type(
             })
         }
         Statement::EnumDeclaration(parser::EnumDeclaration {
+            doc: _,
+            attributes,
             access_modifier: _,
             type_identifier,
+            span: _,
             where_clause,
             shared_fields,
             members,
         }) => {
+            check_attributes(attributes)?;
+
+            let repr = enum_repr(attributes)?;
+            let discriminants = enum_discriminants(type_identifier, members, &repr)?;
+
+            type_environment
+                .borrow_mut()
+                .forget_redeclared_type(type_identifier);
+
+            for field in shared_fields {
+                type_environment.borrow_mut().add_visibility(
+                    format!("{}.{}", type_identifier.name(), field.identifier),
+                    field.access_modifier.clone().map(|a| a.into()),
+                );
+            }
+
             let enum_type_environment = Rc::new(RefCell::new(TypeEnvironment::new_parent(
                 type_environment.clone(),
             )));
@@ -296,6 +395,7 @@ pub fn check_type(
                             mutable: field.mutable,
                             identifier: field.identifier.clone(),
                             type_: t,
+                            default: None,
                         }),
                         Err(e) => Err(e),
                     }
@@ -304,7 +404,8 @@ pub fn check_type(
 
             let members: Result<Vec<ast::EnumMember>, String> = members
                 .iter()
-                .map(|member| {
+                .zip(discriminants)
+                .map(|(member, discriminant)| {
                     let fields: Result<Vec<ast::EnumMemberField>, String> = member
                         .fields
                         .iter()
@@ -353,6 +454,7 @@ pub fn check_type(
                     Ok(ast::EnumMember {
                         enum_name: type_identifier.clone(),
                         discriminant_name: member.identifier.clone(),
+                        discriminant,
                         fields: fields.clone()?,
                         type_: enum_member,
                     })
@@ -383,14 +485,23 @@ pub fn check_type(
                 type_identifier: type_identifier.clone(),
                 shared_fields: shared_fields?,
                 members: members?,
+                repr,
                 type_: enum_type,
             })
         }
         Statement::UnionDeclaration(UnionDeclaration {
+            doc: _,
+            attributes,
             access_modifier: _,
             type_identifier,
             literals,
         }) => {
+            check_attributes(attributes)?;
+
+            type_environment
+                .borrow_mut()
+                .forget_redeclared_type(type_identifier);
+
             let union_type_environment = Rc::new(RefCell::new(TypeEnvironment::new_parent(
                 type_environment.clone(),
             )));
@@ -414,7 +525,13 @@ pub fn check_type(
                 })
                 .collect::<Result<Vec<Type>, String>>()?;
 
+            // The literals are compared by the type they are a value of, not by their value
             let literal_type = literal_types.iter().try_fold(Type::Void, |acc, t| {
+                let t = match t {
+                    Type::Literal { type_, .. } => type_.as_ref(),
+                    t => t,
+                };
+
                 if type_equals(&acc.clone(), &Type::Void) {
                     Ok(t.clone())
                 } else if !type_equals(&acc.clone(), t) {
@@ -446,10 +563,14 @@ pub fn check_type(
             })
         }
         Statement::TypeAliasDeclaration(parser::TypeAliasDeclaration {
+            doc: _,
+            attributes,
             access_modifier: _,
             type_identifier,
             type_annotations,
         }) => {
+            check_attributes(attributes)?;
+
             let type_decl_type_environment = Rc::new(RefCell::new(TypeEnvironment::new_parent(
                 type_environment.clone(),
             )));
@@ -486,12 +607,41 @@ pub fn check_type(
                 type_,
             })
         }
+        Statement::NewtypeDeclaration(parser::NewtypeDeclaration {
+            doc: _,
+            attributes,
+            access_modifier: _,
+            type_identifier,
+            type_annotation,
+        }) => {
+            check_attributes(attributes)?;
+
+            let underlying_type =
+                check_type_annotation(type_annotation, discovered_types, type_environment.clone())?;
+
+            let type_ = Type::Newtype(Newtype {
+                type_identifier: type_identifier.clone(),
+                underlying_type: Box::new(underlying_type),
+            });
+
+            type_environment.borrow_mut().add_type(type_.clone())?;
+
+            Ok(TypedStatement::NewtypeDeclaration {
+                type_identifier: type_identifier.clone(),
+                type_annotation: type_annotation.clone(),
+                type_,
+            })
+        }
         Statement::ProtocolDeclaration(ProtocolDeclaration {
+            doc: _,
+            attributes,
             access_modifier: _,
             type_identifier,
             associated_types,
             functions,
         }) => {
+            check_attributes(attributes)?;
+
             let protocol_type_environment = Rc::new(RefCell::new(TypeEnvironment::new_parent(
                 type_environment.clone(),
             )));
@@ -503,6 +653,15 @@ pub fn check_type(
                     actual_type: Box::new(Type::Unknown),
                 })?;
 
+            // Associated types stand in for the types each implementation chooses
+            for associated_type in associated_types {
+                protocol_type_environment
+                    .borrow_mut()
+                    .add_type(Type::Generic(GenericType {
+                        type_name: associated_type.type_identifier.name().to_owned(),
+                    }))?;
+            }
+
             let functions: Result<Vec<TypedStatement>, String> = functions
                 .clone()
                 .into_iter()
@@ -542,6 +701,7 @@ pub fn check_type(
 
             let type_ = Type::Protocol(Protocol {
                 type_identifier: type_identifier.clone(),
+                associated_types: associated_types.clone(),
                 functions: function_tuples,
             });
 
@@ -555,12 +715,16 @@ pub fn check_type(
             })
         }
         Statement::ImplementationDeclaration(ImplementationDeclaration {
+            doc: _,
+            attributes,
             scoped_generics,
             protocol_annotation,
             type_annotation,
-            associated_types: _,
+            associated_types,
             functions,
         }) => {
+            check_attributes(attributes)?;
+
             let implementation_type_environment = Rc::new(RefCell::new(
                 TypeEnvironment::new_parent(type_environment.clone()),
             ));
@@ -573,7 +737,7 @@ pub fn check_type(
             }
 
             // check generics in protocol_annotation
-            if let TypeAnnotation::ConcreteType(_, generics) = protocol_annotation {
+            if let Some(TypeAnnotation::ConcreteType(_, generics)) = protocol_annotation {
                 for generic in generics {
                     let generic_type = check_type_annotation(
                         generic,
@@ -612,52 +776,124 @@ pub fn check_type(
                 .borrow_mut()
                 .add_type(Type::Substitution {
                     type_identifier: TypeIdentifier::Type("Self".to_owned()),
-                    actual_type: Box::new(imp_type),
+                    actual_type: Box::new(imp_type.clone()),
                 })?;
 
-            let protocol_type = implementation_type_environment
-                .borrow()
-                .get_type_from_annotation(protocol_annotation)?;
+            let implemented_functions = match protocol_annotation {
+                Some(protocol_annotation) => {
+                    let protocol_type = implementation_type_environment
+                        .borrow()
+                        .get_type_from_annotation(protocol_annotation)?;
 
-            let Type::Protocol(Protocol {
-                functions: protocol_functions,
-                ..
-            }) = protocol_type
-            else {
-                return Err(format!("Expected protocol, found {}", protocol_type));
-            };
+                    let Type::Protocol(protocol) = protocol_type else {
+                        return Err(format!("Expected protocol, found {}", protocol_type));
+                    };
 
-            let mut typed_functions = vec![];
+                    let substitutions = check_associated_types(
+                        &protocol,
+                        &imp_type,
+                        associated_types,
+                        discovered_types,
+                        implementation_type_environment.clone(),
+                    )?;
 
-            for (protocol_function_identifier, _) in protocol_functions {
-                let function = functions
-                    .iter()
-                    .find(|f| f.type_identifier == protocol_function_identifier);
+                    if let Some(function) = functions.iter().find(|f| {
+                        !protocol
+                            .functions
+                            .iter()
+                            .any(|(identifier, _)| *identifier == f.type_identifier)
+                    }) {
+                        return Err(format!(
+                            "'{}' is not a function of protocol '{}'",
+                            function.type_identifier, protocol.type_identifier
+                        ));
+                    }
 
-                let Some(function) = function else {
-                    return Err(format!(
-                        "Protocol function '{}' not implemented",
-                        protocol_function_identifier
-                    ));
-                };
+                    let mut implemented_functions = vec![];
 
-                if function.body.is_none() {
-                    return Err(format!(
-                        "Protocol function '{}' must have a body",
-                        protocol_function_identifier
-                    ));
-                };
+                    for (protocol_function_identifier, protocol_function_type) in
+                        &protocol.functions
+                    {
+                        let function = functions
+                            .iter()
+                            .find(|f| f.type_identifier == *protocol_function_identifier);
+
+                        let Some(function) = function else {
+                            return Err(format!(
+                                "'{}' does not implement '{}' of protocol '{}'",
+                                imp_type, protocol_function_identifier, protocol.type_identifier
+                            ));
+                        };
+
+                        if function.body.is_none() && !type_environment.borrow().is_interface() {
+                            return Err(format!(
+                                "Protocol function '{}' must have a body",
+                                protocol_function_identifier
+                            ));
+                        };
+
+                        let expected_type = substitute(protocol_function_type, &substitutions);
+                        implemented_functions.push((function, Some(expected_type)));
+                    }
 
+                    type_environment
+                        .borrow_mut()
+                        .add_implementation(&imp_type, &protocol)?;
+
+                    implemented_functions
+                }
+                // An inherent imp block attaches every function to the type as a method
+                None => {
+                    let interface = type_environment.borrow().is_interface();
+
+                    if let Some(function) =
+                        functions.iter().find(|f| f.body.is_none() && !interface)
+                    {
+                        return Err(format!(
+                            "Method '{}' must have a body",
+                            function.type_identifier
+                        ));
+                    }
+
+                    functions.iter().map(|function| (function, None)).collect()
+                }
+            };
+
+            let mut typed_functions = vec![];
+
+            for (function, expected_type) in implemented_functions {
                 let typed_function = check_type(
                     &Statement::FunctionDeclaration(function.clone()),
                     discovered_types,
                     implementation_type_environment.clone(),
                 )?;
 
-                let function_name = protocol_function_identifier.name().to_owned();
+                if let (Some(expected_type), Some(protocol_annotation)) =
+                    (expected_type, protocol_annotation)
+                {
+                    if !signature_matches(&expected_type, &typed_function.get_type()) {
+                        return Err(format!(
+                            "'{}' of protocol '{}' must have type {} for '{}', found {}",
+                            function.type_identifier,
+                            protocol_annotation,
+                            expected_type,
+                            imp_type,
+                            typed_function.get_type()
+                        ));
+                    }
+                }
+
+                let function_name = function.type_identifier.name().to_owned();
+
+                let owner = match protocol_annotation {
+                    Some(protocol_annotation) => {
+                        protocol_member_owner(&protocol_annotation.name(), type_annotation.clone())
+                    }
+                    None => type_annotation.clone(),
+                };
 
                 type_environment.borrow_mut().add_static_member(
-                    type_annotation.clone(),
+                    owner,
                     function_name.clone(),
                     typed_function.get_type(),
                 )?;
@@ -669,20 +905,25 @@ pub fn check_type(
                 scoped_generics: scoped_generics.clone(),
                 protocol_annotation: protocol_annotation.clone(),
                 type_annotation: type_annotation.clone(),
-                associated_types: vec![],
+                associated_types: associated_types.clone(),
                 functions: typed_functions,
                 type_: Type::Void,
             })
         }
         Statement::FunctionDeclaration(parser::FunctionDeclaration {
+            doc: _,
+            attributes,
             access_modifier: _,
             type_identifier,
+            span: _,
             param,
             return_type_annotation,
             where_clause,
             body,
             signature_only,
         }) => {
+            check_attributes(attributes)?;
+
             let function_type_environment = Rc::new(RefCell::new(TypeEnvironment::new_parent(
                 type_environment.clone(),
             )));
@@ -713,10 +954,19 @@ pub fn check_type(
                 }
             }
 
+            // A function declared again without a where clause replaces the one it had
+            if let TypeIdentifier::GenericType(name, generics) = type_identifier {
+                type_environment.borrow_mut().add_where_clause(
+                    name.clone(),
+                    generics.clone(),
+                    where_clause.clone().unwrap_or_default(),
+                );
+            }
+
             let return_type = check_type_annotation(
                 &return_type_annotation
                     .clone()
-                    .unwrap_or(TypeAnnotation::Type(Type::Void.to_string())),
+                    .unwrap_or(TypeAnnotation::Type(Type::Unit.to_string())),
                 discovered_types,
                 function_type_environment.clone(),
             )?;
@@ -726,20 +976,42 @@ pub fn check_type(
                 ScopeType::Return,
             )));
 
+            body_environment
+                .borrow_mut()
+                .set_return_type(return_type.clone());
+
+            let param_span = param.as_ref().map(|param| param.span).unwrap_or_default();
+            let param_mutable = param.as_ref().is_some_and(|param| param.mutable);
+
             let param: Option<Parameter> = match param {
                 Some(param) => {
                     let param_type_annotation = param.type_annotation.clone();
                     let param_name = param.identifier.clone();
 
+                    if param_name == "self"
+                        && function_type_environment
+                            .borrow()
+                            .get_type("Self")
+                            .is_none()
+                    {
+                        return Err(format!(
+                            "Function '{}' takes self, but is not declared in an imp block",
+                            type_identifier.name()
+                        ));
+                    }
+
                     let param_type = match check_type_annotation(
                         &param_type_annotation,
                         discovered_types,
                         function_type_environment.clone(),
                     ) {
                         Ok(t) => {
-                            body_environment
-                                .borrow_mut()
-                                .add_variable(param_name.clone(), t.clone());
+                            let mut body_environment = body_environment.borrow_mut();
+                            body_environment.add_variable(param_name.clone(), t.clone());
+
+                            if param.mutable {
+                                body_environment.set_mutable(&param_name);
+                            }
 
                             Ok(t)
                         }
@@ -758,6 +1030,15 @@ pub fn check_type(
                 None => None,
             };
 
+            // The function is visible in its own body so it can call itself
+            function_type_environment
+                .borrow_mut()
+                .add_type(Type::Function(Function {
+                    identifier: Some(type_identifier.clone()),
+                    param: param.clone(),
+                    return_type: Box::new(return_type.clone()),
+                }))?;
+
             let body_typed_expression: Option<TypedExpression> = body
                 .as_ref()
                 .map(|body| {
@@ -780,6 +1061,8 @@ pub fn check_type(
                         identifier: p.identifier,
                         type_annotation: p.type_.type_annotation(),
                         type_: p.type_,
+                        mutable: param_mutable,
+                        span: param_span,
                     }),
                     return_type,
                     body: body_typed_expression,
@@ -802,6 +1085,8 @@ pub fn check_type(
                         identifier: p.identifier,
                         type_annotation: p.type_.type_annotation(),
                         type_: p.type_,
+                        mutable: param_mutable,
+                        span: param_span,
                     }),
                     return_type,
                     body: None,
@@ -813,6 +1098,12 @@ pub fn check_type(
                 .map(|s| s.fold())
                 .unwrap_or_else(|| Ok(body_typed_expression.get_deep_type()))?;
 
+            // A Unit function is allowed to have a body which produces no value
+            let body_type = match (&return_type, body_type) {
+                (Type::Unit, Type::Void) => Type::Unit,
+                (_, body_type) => body_type,
+            };
+
             if !type_equals(&return_type, &Type::Void) && !type_equals(&return_type, &body_type) {
                 return Err(format!(
                     "Function body's return type {} does not match function return type {}",
@@ -828,6 +1119,8 @@ pub fn check_type(
                     identifier: p.identifier,
                     type_annotation: p.type_.type_annotation(),
                     type_: p.type_,
+                    mutable: param_mutable,
+                    span: param_span,
                 }),
                 return_type,
                 body: Some(body_typed_expression),
@@ -845,9 +1138,234 @@ pub fn check_type(
             type_environment,
             None,
         )?)),
+        Statement::Error(_) => Err("Cannot check a statement which failed to parse".to_string()),
+    }
+}
+
+fn check_field_default(
+    field: &parser::StructField,
+    field_type: &Type,
+    discovered_types: &Vec<DiscoveredType>,
+    type_environment: Rcrc<TypeEnvironment>,
+) -> Result<Option<TypedExpression>, String> {
+    let Some(default) = &field.default else {
+        return Ok(None);
+    };
+
+    let default = expressions::check_type(
+        default,
+        discovered_types,
+        type_environment,
+        Some(field_type.clone()),
+    )?;
+
+    if !type_equals(field_type, &default.get_type()) {
+        return Err(format!(
+            "Default value of field '{}' must have type {}, found {}",
+            field.identifier,
+            field_type,
+            default.get_type()
+        ));
+    }
+
+    Ok(Some(default))
+}
+
+/// Resolve the associated types of a protocol for an implementation, from the implementation
+/// itself or the defaults of the protocol. Each one is added to the implementation environment,
+/// and the returned map substitutes them and Self in the protocol function types.
+fn check_associated_types(
+    protocol: &Protocol,
+    imp_type: &Type,
+    associated_types: &[AssociatedType],
+    discovered_types: &Vec<DiscoveredType>,
+    implementation_type_environment: Rcrc<TypeEnvironment>,
+) -> Result<HashMap<String, Type>, String> {
+    if let Some(associated_type) = associated_types.iter().find(|a| {
+        !protocol
+            .associated_types
+            .iter()
+            .any(|p| p.type_identifier == a.type_identifier)
+    }) {
+        return Err(format!(
+            "'{}' is not an associated type of protocol '{}'",
+            associated_type.type_identifier, protocol.type_identifier
+        ));
+    }
+
+    let mut substitutions = HashMap::new();
+    substitutions.insert("Self".to_owned(), imp_type.clone());
+
+    for protocol_associated_type in &protocol.associated_types {
+        let type_annotation = associated_types
+            .iter()
+            .find(|a| a.type_identifier == protocol_associated_type.type_identifier)
+            .and_then(|a| a.default_type_annotation.as_ref())
+            .or(protocol_associated_type.default_type_annotation.as_ref())
+            .ok_or(format!(
+                "'{}' does not define associated type '{}' of protocol '{}'",
+                imp_type, protocol_associated_type.type_identifier, protocol.type_identifier
+            ))?;
+
+        let actual_type = check_type_annotation(
+            type_annotation,
+            discovered_types,
+            implementation_type_environment.clone(),
+        )?;
+
+        implementation_type_environment
+            .borrow_mut()
+            .add_type(Type::Substitution {
+                type_identifier: protocol_associated_type.type_identifier.clone(),
+                actual_type: Box::new(actual_type.clone()),
+            })?;
+
+        substitutions.insert(
+            protocol_associated_type.type_identifier.name().to_owned(),
+            actual_type,
+        );
+    }
+
+    Ok(substitutions)
+}
+
+/// Replace Self and associated types in a protocol function type with the implemented types
+pub(super) fn substitute(type_: &Type, substitutions: &HashMap<String, Type>) -> Type {
+    match type_ {
+        Type::Substitution {
+            type_identifier, ..
+        } => substitutions
+            .get(type_identifier.name())
+            .cloned()
+            .unwrap_or_else(|| type_.clone()),
+        Type::Generic(GenericType { type_name }) => substitutions
+            .get(type_name)
+            .cloned()
+            .unwrap_or_else(|| type_.clone()),
+        Type::Array(type_) => Type::Array(Box::new(substitute(type_, substitutions))),
+        Type::Function(Function {
+            identifier,
+            param,
+            return_type,
+        }) => Type::Function(Function {
+            identifier: identifier.clone(),
+            param: param.as_ref().map(|param| Parameter {
+                identifier: param.identifier.clone(),
+                type_: Box::new(substitute(&param.type_, substitutions)),
+            }),
+            return_type: Box::new(substitute(return_type, substitutions)),
+        }),
+        _ => type_.clone(),
     }
 }
 
+/// Unlike type_equals, every parameter has to be present on both sides
+fn signature_matches(expected: &Type, actual: &Type) -> bool {
+    match (expected, actual) {
+        (Type::Function(expected), Type::Function(actual)) => {
+            let params_match = match (&expected.param, &actual.param) {
+                (Some(expected), Some(actual)) => signature_matches(&expected.type_, &actual.type_),
+                (None, None) => true,
+                _ => false,
+            };
+
+            params_match && signature_matches(&expected.return_type, &actual.return_type)
+        }
+        _ => type_equals(expected, actual) && type_equals(actual, expected),
+    }
+}
+
+/// The attributes with a meaning to the compiler, and the fewest and most arguments they take.
+/// Any other attribute is left for the backends to interpret.
+const KNOWN_ATTRIBUTES: &[(&str, usize, usize)] =
+    &[("inline", 0, 0), ("deprecated", 0, 1), ("repr", 1, 1)];
+
+fn check_attributes(attributes: &[Attribute]) -> Result<(), String> {
+    for attribute in attributes {
+        let Some((_, min, max)) = KNOWN_ATTRIBUTES
+            .iter()
+            .find(|(name, _, _)| *name == attribute.name)
+        else {
+            continue;
+        };
+
+        if attribute.args.len() < *min || attribute.args.len() > *max {
+            let expected = if min == max {
+                min.to_string()
+            } else {
+                format!("{} to {}", min, max)
+            };
+
+            return Err(format!(
+                "Attribute @{} takes {} arguments but {} were given",
+                attribute.name,
+                expected,
+                attribute.args.len()
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// The integer type `@repr("UInt")` stores the discriminants of an enum as, which is `Int`
+/// without the attribute
+fn enum_repr(attributes: &[Attribute]) -> Result<Type, String> {
+    let Some(attribute) = attributes.iter().find(|attribute| attribute.name == "repr") else {
+        return Ok(Type::Int);
+    };
+
+    match attribute.args.first() {
+        Some(parser::Literal::String(name)) if name == "Int" => Ok(Type::Int),
+        Some(parser::Literal::String(name)) if name == "UInt" => Ok(Type::UInt),
+        _ => Err(format!(
+            "Attribute {} must name the integer type Int or UInt",
+            attribute
+        )),
+    }
+}
+
+/// The discriminant of each member, in order. A member without an explicit discriminant gets the
+/// one after the member before it, starting from 0.
+fn enum_discriminants(
+    type_identifier: &TypeIdentifier,
+    members: &[parser::EnumMember],
+    repr: &Type,
+) -> Result<Vec<i64>, String> {
+    let mut discriminants: Vec<i64> = vec![];
+    let mut next = Some(0);
+
+    for member in members {
+        let name = format!("{}::{}", type_identifier.name(), member.identifier);
+
+        let Some(discriminant) = member.discriminant.or(next) else {
+            return Err(format!("Discriminant of '{}' overflows {}", name, repr));
+        };
+
+        if discriminant < 0 && !repr.is_signed() {
+            return Err(format!(
+                "Discriminant {} of '{}' does not fit in {}",
+                discriminant, name, repr
+            ));
+        }
+
+        if let Some(other) = discriminants.iter().position(|d| *d == discriminant) {
+            return Err(format!(
+                "Discriminant {} of '{}' is already used by '{}::{}'",
+                discriminant,
+                name,
+                type_identifier.name(),
+                members[other].identifier
+            ));
+        }
+
+        discriminants.push(discriminant);
+        next = discriminant.checked_add(1);
+    }
+
+    Ok(discriminants)
+}
+
 #[allow(dead_code)]
 fn check_type_identifier(
     type_identifier: &TypeIdentifier,
@@ -869,6 +1387,7 @@ fn check_type_identifier(
             DiscoveredType::EnumMember(name, ..) => name == type_identifier,
             DiscoveredType::Union(name, ..) => name == type_identifier,
             DiscoveredType::TypeAlias(name, ..) => name == type_identifier,
+            DiscoveredType::Newtype(name, ..) => name == type_identifier,
             DiscoveredType::Protocol {
                 type_identifier: name,
                 ..
@@ -1032,12 +1551,29 @@ fn check_type_identifier(
                 types,
             }))
         }
+        Some(DiscoveredType::Newtype(type_identifier, type_annotation)) => {
+            Ok(Type::Newtype(Newtype {
+                type_identifier: type_identifier.clone(),
+                underlying_type: Box::new(check_type_annotation(
+                    type_annotation,
+                    discovered_types,
+                    type_environment.clone(),
+                )?),
+            }))
+        }
         Some(DiscoveredType::Protocol {
             type_identifier,
-            associated_types: _,
+            associated_types,
             function_identifiers,
         }) => Ok(Type::Protocol(Protocol {
             type_identifier: type_identifier.clone(),
+            associated_types: associated_types
+                .iter()
+                .map(|type_identifier| AssociatedType {
+                    type_identifier: type_identifier.clone(),
+                    default_type_annotation: None,
+                })
+                .collect(),
             functions: function_identifiers
                 .iter()
                 .map(|f| {
@@ -1090,11 +1626,94 @@ pub fn check_type_annotation(
     discovered_types: &Vec<DiscoveredType>,
     type_environment: Rcrc<TypeEnvironment>,
 ) -> Result<Type, String> {
-    if let Ok(type_) = type_environment
+    type_environment
         .borrow()
-        .get_type_from_annotation(type_annotation)
-    {
-        return Ok(type_);
+        .check_import_ambiguity(&type_annotation.name())?;
+
+    if let TypeAnnotation::Type(name) = type_annotation {
+        if let Some(type_) = type_environment.borrow().get_imported_type(name) {
+            return Ok(type_);
+        }
+    }
+
+    let type_ = type_environment
+        .borrow()
+        .get_type_from_annotation(type_annotation);
+
+    match type_ {
+        Ok(type_) => return Ok(type_),
+        // Giving types to a generic type which is already declared fails on its own, like when
+        // the instantiation is too deep
+        Err(error) if is_declared_generic(type_annotation, &type_environment.borrow()) => {
+            return Err(error)
+        }
+        Err(_) => {}
+    }
+
+    // The fields of a generic type declared further down are resolved along with it
+    let TypeAnnotation::ConcreteType(..) = type_annotation else {
+        return check_discovered_type_annotation(
+            type_annotation,
+            discovered_types,
+            type_environment,
+        );
+    };
+
+    type_environment
+        .borrow()
+        .enter_instantiation(type_annotation)?;
+
+    let type_ = check_discovered_type_annotation(
+        type_annotation,
+        discovered_types,
+        type_environment.clone(),
+    );
+
+    type_environment.borrow().exit_instantiation();
+    type_
+}
+
+fn is_declared_generic(
+    type_annotation: &TypeAnnotation,
+    type_environment: &TypeEnvironment,
+) -> bool {
+    let TypeAnnotation::ConcreteType(name, concrete_types) = type_annotation else {
+        return false;
+    };
+
+    type_environment
+        .get_type(TypeIdentifier::ConcreteType(
+            name.clone(),
+            concrete_types.clone(),
+        ))
+        .is_some()
+}
+
+/// Resolve an annotation of a type which isn't in the environment yet from its discovered
+/// declaration
+fn check_discovered_type_annotation(
+    type_annotation: &TypeAnnotation,
+    discovered_types: &Vec<DiscoveredType>,
+    type_environment: Rcrc<TypeEnvironment>,
+) -> Result<Type, String> {
+    // An option or result of a type which is declared further down
+    if let TypeAnnotation::ConcreteType(type_name, concrete_types) = type_annotation {
+        match (type_name.as_str(), concrete_types.as_slice()) {
+            ("Option", [inner]) => {
+                return Ok(Type::option_of(check_type_annotation(
+                    inner,
+                    discovered_types,
+                    type_environment,
+                )?));
+            }
+            ("Result", [value, error]) => {
+                return Ok(Type::result_of(
+                    check_type_annotation(value, discovered_types, type_environment.clone())?,
+                    check_type_annotation(error, discovered_types, type_environment)?,
+                ));
+            }
+            _ => {}
+        }
     }
 
     match discovered_types
@@ -1115,6 +1734,9 @@ pub fn check_type_annotation(
             DiscoveredType::TypeAlias(type_identifier, ..) => {
                 type_identifier.name() == type_annotation.name()
             }
+            DiscoveredType::Newtype(type_identifier, ..) => {
+                type_identifier.name() == type_annotation.name()
+            }
             DiscoveredType::Protocol {
                 type_identifier, ..
             } => type_identifier.name() == type_annotation.name(),
@@ -1122,13 +1744,20 @@ pub fn check_type_annotation(
                 type_identifier, ..
             } => type_identifier.name() == type_annotation.name(),
         }) {
-        Some(DiscoveredType::Struct(type_identifier, fields)) => Ok(Type::Struct(Struct {
-            type_identifier: type_identifier.clone(),
-            fields: {
-                let mut map = Vec::new();
+        Some(DiscoveredType::Struct(type_identifier, fields)) => {
+            // A struct which refers to itself, like a linked list node, holds itself without its
+            // fields, as they can't be written out without end
+            if !type_environment.borrow().enter_type(type_identifier.name()) {
+                return Ok(Type::Struct(Struct {
+                    type_identifier: type_identifier.clone(),
+                    fields: vec![],
+                }));
+            }
 
-                for (identifier, type_annotation) in fields {
-                    map.push(StructField {
+            let fields = fields
+                .iter()
+                .map(|(identifier, type_annotation)| {
+                    Ok(StructField {
                         struct_name: type_identifier.clone(),
                         field_name: identifier.clone(),
                         field_type: check_type_annotation(
@@ -1136,12 +1765,17 @@ pub fn check_type_annotation(
                             discovered_types,
                             type_environment.clone(),
                         )?,
-                    });
-                }
+                    })
+                })
+                .collect::<Result<Vec<StructField>, String>>();
 
-                map
-            },
-        })),
+            type_environment.borrow().exit_type();
+
+            Ok(Type::Struct(Struct {
+                type_identifier: type_identifier.clone(),
+                fields: fields?,
+            }))
+        }
         Some(DiscoveredType::Enum(type_identifier, shared_fields, members)) => {
             Ok(Type::Enum(Enum {
                 type_identifier: type_identifier.clone(),
@@ -1237,7 +1871,13 @@ pub fn check_type_annotation(
                 })
                 .collect::<Result<Vec<Type>, String>>()?;
 
+            // The literals are compared by the type they are a value of, not by their value
             let literal_type = literal_types.iter().try_fold(Type::Void, |acc, t| {
+                let t = match t {
+                    Type::Literal { type_, .. } => type_.as_ref(),
+                    t => t,
+                };
+
                 if type_equals(&acc.clone(), &Type::Void) {
                     Ok(t.clone())
                 } else if !type_equals(&acc.clone(), t) {
@@ -1269,12 +1909,29 @@ pub fn check_type_annotation(
                 types,
             }))
         }
+        Some(DiscoveredType::Newtype(type_identifier, type_annotation)) => {
+            Ok(Type::Newtype(Newtype {
+                type_identifier: type_identifier.clone(),
+                underlying_type: Box::new(check_type_annotation(
+                    type_annotation,
+                    discovered_types,
+                    type_environment.clone(),
+                )?),
+            }))
+        }
         Some(DiscoveredType::Protocol {
             type_identifier,
-            associated_types: _,
+            associated_types,
             function_identifiers,
         }) => Ok(Type::Protocol(Protocol {
             type_identifier: type_identifier.clone(),
+            associated_types: associated_types
+                .iter()
+                .map(|type_identifier| AssociatedType {
+                    type_identifier: type_identifier.clone(),
+                    default_type_annotation: None,
+                })
+                .collect(),
             functions: function_identifiers
                 .iter()
                 .map(|f| {
@@ -0,0 +1,43 @@
+/// How many characters have to be inserted, removed or replaced to turn `a` into `b`
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+
+        for (j, b) in b.iter().enumerate() {
+            let replace = previous[j] + usize::from(a != *b);
+            let remove = previous[j + 1] + 1;
+            let insert = current[j] + 1;
+
+            current.push(replace.min(remove).min(insert));
+        }
+
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+/// Find the candidate `name` is most likely a typo of. Only candidates within a third of the length
+/// of `name`, and at least one edit, are close enough. Ties go to the candidate which sorts first.
+pub fn closest<'a>(name: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    let threshold = (name.chars().count() / 3).max(1);
+
+    candidates
+        .into_iter()
+        .filter(|candidate| *candidate != name)
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= threshold)
+        .min()
+        .map(|(_, candidate)| candidate)
+}
+
+/// The end of an error message about a name which wasn't found, suggesting the name in scope it
+/// is closest to if there is one
+pub(crate) fn did_you_mean(suggestion: Option<String>) -> String {
+    suggestion
+        .map(|suggestion| format!(", did you mean `{}`?", suggestion))
+        .unwrap_or_default()
+}
@@ -0,0 +1,238 @@
+use super::{
+    ast::{Block, Member, TypedExpression, TypedStatement},
+    decision_tree::{Case, Decision},
+    transform::{self, TypedTransformer},
+};
+
+/// Mark the calls of every function to itself which are in tail position.
+///
+/// A call is in tail position when it is the value of a `return`, or the value the function body
+/// ends with, looking through the branches of `if` and `match` and the last statement of blocks.
+/// Only calls which apply every parameter are marked, as a partial application returns a closure.
+pub fn mark_tail_calls(program: TypedStatement) -> TypedStatement {
+    TailCalls { function: None }.transform_statement(program)
+}
+
+/// The name of a function being checked and how many parameters it takes
+struct Function {
+    name: String,
+    arity: usize,
+}
+
+struct TailCalls {
+    function: Option<Function>,
+}
+
+impl TailCalls {
+    /// The body of a function with more than one parameter is a closure taking the next one
+    fn function_body(&mut self, body: TypedExpression) -> TypedExpression {
+        match body {
+            TypedExpression::Closure {
+                param,
+                return_type,
+                body,
+                type_,
+            } if param.is_some() => TypedExpression::Closure {
+                param,
+                return_type,
+                body: Box::new(self.function_body(*body)),
+                type_,
+            },
+            body => self.tail(body),
+        }
+    }
+
+    fn tail(&mut self, expression: TypedExpression) -> TypedExpression {
+        match expression {
+            TypedExpression::Call { .. } if self.is_self_call(&expression) => {
+                let TypedExpression::Call {
+                    callee,
+                    argument,
+                    span,
+                    type_,
+                    ..
+                } = transform::fold_expression(self, expression)
+                else {
+                    unreachable!("A call folds to a call");
+                };
+
+                TypedExpression::Call {
+                    callee,
+                    argument,
+                    tail_call: true,
+                    span,
+                    type_,
+                }
+            }
+            TypedExpression::If {
+                condition,
+                true_expression,
+                false_expression,
+                type_,
+            } => TypedExpression::If {
+                condition: Box::new(self.transform_expression(*condition)),
+                true_expression: Box::new(self.tail(*true_expression)),
+                false_expression: false_expression.map(|e| Box::new(self.tail(*e))),
+                type_,
+            },
+            TypedExpression::Match {
+                expression,
+                arms,
+                decision_tree,
+                type_,
+                span,
+            } => TypedExpression::Match {
+                expression: Box::new(self.transform_expression(*expression)),
+                arms,
+                decision_tree: self.tail_decision(decision_tree),
+                type_,
+                span,
+            },
+            TypedExpression::Block(Block {
+                mut statements,
+                type_,
+            }) => {
+                let last = match statements.pop() {
+                    Some(TypedStatement::Expression(last)) => {
+                        Some(TypedStatement::Expression(self.tail(last)))
+                    }
+                    last => last.map(|last| self.transform_statement(last)),
+                };
+
+                let mut statements: Vec<TypedStatement> = statements
+                    .into_iter()
+                    .map(|s| self.transform_statement(s))
+                    .collect();
+
+                statements.extend(last);
+                TypedExpression::Block(Block { statements, type_ })
+            }
+            expression => self.transform_expression(expression),
+        }
+    }
+
+    fn tail_decision(&mut self, decision: Decision) -> Decision {
+        match decision {
+            Decision::Success { expression, type_ } => Decision::Success {
+                expression: Box::new(self.tail(*expression)),
+                type_,
+            },
+            Decision::Failure { .. } => decision,
+            Decision::Guard {
+                condition,
+                consequence,
+                alternative,
+                type_,
+            } => Decision::Guard {
+                condition: Box::new(self.transform_expression(*condition)),
+                consequence: Box::new(self.tail_decision(*consequence)),
+                alternative: Box::new(self.tail_decision(*alternative)),
+                type_,
+            },
+            Decision::Switch {
+                variable,
+                cases,
+                fallback,
+                type_,
+            } => Decision::Switch {
+                variable,
+                cases: cases
+                    .into_iter()
+                    .map(|c| Case {
+                        pattern: c.pattern,
+                        arguments: c.arguments,
+                        body: self.tail_decision(c.body),
+                    })
+                    .collect(),
+                fallback: Box::new(self.tail_decision(*fallback)),
+                type_,
+            },
+        }
+    }
+
+    /// Whether the expression calls the enclosing function with all of its arguments, like
+    /// `f(a, b)` which is `f(a)(b)` for a function `f` of two parameters
+    fn is_self_call(&self, expression: &TypedExpression) -> bool {
+        let Some(function) = &self.function else {
+            return false;
+        };
+
+        let mut arguments = 0;
+        let mut callee = expression;
+
+        while let TypedExpression::Call {
+            callee: inner,
+            argument,
+            ..
+        } = callee
+        {
+            arguments += usize::from(argument.is_some());
+            callee = inner;
+        }
+
+        let TypedExpression::Member(Member::Identifier { symbol, .. }) = callee else {
+            return false;
+        };
+
+        *symbol == function.name && arguments == function.arity
+    }
+}
+
+fn arity(param_count: usize, body: &TypedExpression) -> usize {
+    match body {
+        TypedExpression::Closure {
+            param: Some(_),
+            body,
+            ..
+        } => arity(param_count + 1, body),
+        _ => param_count,
+    }
+}
+
+impl TypedTransformer for TailCalls {
+    fn transform_statement(&mut self, statement: TypedStatement) -> TypedStatement {
+        match statement {
+            TypedStatement::FunctionDeclaration {
+                identifier,
+                param,
+                return_type,
+                body: Some(body),
+                type_,
+            } => {
+                let function = Function {
+                    name: identifier.name().to_string(),
+                    arity: arity(usize::from(param.is_some()), &body),
+                };
+
+                let enclosing = self.function.replace(function);
+                let body = self.function_body(body);
+                self.function = enclosing;
+
+                TypedStatement::FunctionDeclaration {
+                    identifier,
+                    param,
+                    return_type,
+                    body: Some(body),
+                    type_,
+                }
+            }
+            statement => transform::fold_statement(self, statement),
+        }
+    }
+
+    fn transform_expression(&mut self, expression: TypedExpression) -> TypedExpression {
+        match expression {
+            TypedExpression::Return(Some(value)) if self.function.is_some() => {
+                TypedExpression::Return(Some(Box::new(self.tail(*value))))
+            }
+            // A closure is a function of its own, it has no name to call itself by
+            TypedExpression::Closure { .. } => {
+                let enclosing = self.function.take();
+                let expression = transform::fold_expression(self, expression);
+                self.function = enclosing;
+                expression
+            }
+            expression => transform::fold_expression(self, expression),
+        }
+    }
+}
@@ -0,0 +1,473 @@
+use super::{
+    ast::{
+        Block, EnumMemberFieldInitializers, FieldInitializer, Literal, Member, TypedExpression,
+        TypedStatement,
+    },
+    constant::constant_value,
+    decision_tree::{Case, Decision},
+};
+
+/// Rewriting traversal of the typed AST.
+///
+/// Each method takes ownership of a node and returns the node which replaces it.
+/// Every method defaults to the matching `fold_*` function, which rebuilds the node from its transformed children.
+/// Call the `fold_*` function from an overridden method to keep recursing.
+pub trait TypedTransformer {
+    fn transform_statement(&mut self, statement: TypedStatement) -> TypedStatement {
+        fold_statement(self, statement)
+    }
+
+    fn transform_expression(&mut self, expression: TypedExpression) -> TypedExpression {
+        fold_expression(self, expression)
+    }
+
+    fn transform_member(&mut self, member: Member) -> Member {
+        fold_member(self, member)
+    }
+
+    fn transform_literal(&mut self, literal: Literal) -> Literal {
+        fold_literal(self, literal)
+    }
+
+    fn transform_block(&mut self, block: Block) -> Block {
+        fold_block(self, block)
+    }
+
+    fn transform_decision(&mut self, decision: Decision) -> Decision {
+        fold_decision(self, decision)
+    }
+}
+
+fn transform_boxed<T: TypedTransformer + ?Sized>(
+    transformer: &mut T,
+    expression: Box<TypedExpression>,
+) -> Box<TypedExpression> {
+    Box::new(transformer.transform_expression(*expression))
+}
+
+fn transform_optional<T: TypedTransformer + ?Sized>(
+    transformer: &mut T,
+    expression: Option<Box<TypedExpression>>,
+) -> Option<Box<TypedExpression>> {
+    expression.map(|e| transform_boxed(transformer, e))
+}
+
+pub fn fold_statement<T: TypedTransformer + ?Sized>(
+    transformer: &mut T,
+    statement: TypedStatement,
+) -> TypedStatement {
+    match statement {
+        TypedStatement::Program { statements } => TypedStatement::Program {
+            statements: statements
+                .into_iter()
+                .map(|s| transformer.transform_statement(s))
+                .collect(),
+        },
+        TypedStatement::ProtocolDeclaration {
+            type_identifier,
+            associated_types,
+            functions,
+            type_,
+        } => TypedStatement::ProtocolDeclaration {
+            type_identifier,
+            associated_types,
+            functions: functions
+                .into_iter()
+                .map(|f| transformer.transform_statement(f))
+                .collect(),
+            type_,
+        },
+        TypedStatement::ImplementationDeclaration {
+            scoped_generics,
+            protocol_annotation,
+            type_annotation,
+            associated_types,
+            functions,
+            type_,
+        } => TypedStatement::ImplementationDeclaration {
+            scoped_generics,
+            protocol_annotation,
+            type_annotation,
+            associated_types,
+            functions: functions
+                .into_iter()
+                .map(|(name, f)| (name, transformer.transform_statement(f)))
+                .collect(),
+            type_,
+        },
+        TypedStatement::FunctionDeclaration {
+            identifier,
+            param,
+            return_type,
+            body,
+            type_,
+        } => TypedStatement::FunctionDeclaration {
+            identifier,
+            param,
+            return_type,
+            body: body.map(|b| transformer.transform_expression(b)),
+            type_,
+        },
+        TypedStatement::Semi(statement) => {
+            TypedStatement::Semi(Box::new(transformer.transform_statement(*statement)))
+        }
+        TypedStatement::Expression(expression) => {
+            TypedStatement::Expression(transformer.transform_expression(expression))
+        }
+        statement @ (TypedStatement::None
+        | TypedStatement::ModuleDeclaration { .. }
+        | TypedStatement::Use { .. }
+        | TypedStatement::StructDeclaration { .. }
+        | TypedStatement::EnumDeclaration { .. }
+        | TypedStatement::UnionDeclaration { .. }
+        | TypedStatement::TypeAliasDeclaration { .. }
+        | TypedStatement::NewtypeDeclaration { .. }) => statement,
+    }
+}
+
+pub fn fold_expression<T: TypedTransformer + ?Sized>(
+    transformer: &mut T,
+    expression: TypedExpression,
+) -> TypedExpression {
+    match expression {
+        TypedExpression::VariableDeclaration {
+            mutable,
+            type_annotation,
+            pattern,
+            pattern_span,
+            initializer,
+            type_,
+        } => TypedExpression::VariableDeclaration {
+            mutable,
+            type_annotation,
+            pattern,
+            pattern_span,
+            initializer: transform_optional(transformer, initializer),
+            type_,
+        },
+        TypedExpression::If {
+            condition,
+            true_expression,
+            false_expression,
+            type_,
+        } => TypedExpression::If {
+            condition: transform_boxed(transformer, condition),
+            true_expression: transform_boxed(transformer, true_expression),
+            false_expression: transform_optional(transformer, false_expression),
+            type_,
+        },
+        // The arms only hold untyped expressions, their typed bodies live in the decision tree
+        TypedExpression::Match {
+            expression,
+            arms,
+            decision_tree,
+            type_,
+            span,
+        } => TypedExpression::Match {
+            expression: transform_boxed(transformer, expression),
+            arms,
+            decision_tree: transformer.transform_decision(decision_tree),
+            type_,
+            span,
+        },
+        TypedExpression::Assignment {
+            member,
+            initializer,
+            type_,
+        } => TypedExpression::Assignment {
+            member: Box::new(transformer.transform_member(*member)),
+            initializer: transform_boxed(transformer, initializer),
+            type_,
+        },
+        TypedExpression::Member(member) => {
+            TypedExpression::Member(transformer.transform_member(member))
+        }
+        TypedExpression::Literal(literal, span) => {
+            TypedExpression::Literal(transformer.transform_literal(literal), span)
+        }
+        TypedExpression::Tuple { elements, type_ } => TypedExpression::Tuple {
+            elements: elements
+                .into_iter()
+                .map(|e| transformer.transform_expression(e))
+                .collect(),
+            type_,
+        },
+        TypedExpression::Record { fields, type_ } => TypedExpression::Record {
+            fields: fields
+                .into_iter()
+                .map(|f| FieldInitializer {
+                    identifier: f.identifier,
+                    initializer: transformer.transform_expression(f.initializer),
+                })
+                .collect(),
+            type_,
+        },
+        TypedExpression::Closure {
+            param,
+            return_type,
+            body,
+            type_,
+        } => TypedExpression::Closure {
+            param,
+            return_type,
+            body: transform_boxed(transformer, body),
+            type_,
+        },
+        TypedExpression::Call {
+            callee,
+            argument,
+            tail_call,
+            span,
+            type_,
+        } => TypedExpression::Call {
+            callee: transform_boxed(transformer, callee),
+            argument: transform_optional(transformer, argument),
+            tail_call,
+            span,
+            type_,
+        },
+        TypedExpression::Index {
+            callee,
+            argument,
+            span,
+            type_,
+        } => TypedExpression::Index {
+            callee: transform_boxed(transformer, callee),
+            argument: transform_boxed(transformer, argument),
+            span,
+            type_,
+        },
+        TypedExpression::Unary {
+            operator,
+            expression,
+            constant,
+            span,
+            type_,
+        } => {
+            let operand = constant_value(&expression);
+            let expression = transform_boxed(transformer, expression);
+
+            // The constant was folded from the operand, so it only holds while the operand does
+            TypedExpression::Unary {
+                operator,
+                constant: constant.filter(|_| constant_value(&expression) == operand),
+                expression,
+                span,
+                type_,
+            }
+        }
+        TypedExpression::Binary {
+            left,
+            operator,
+            right,
+            constant,
+            span,
+            type_,
+        } => {
+            let operands = (constant_value(&left), constant_value(&right));
+            let left = transform_boxed(transformer, left);
+            let right = transform_boxed(transformer, right);
+
+            // Likewise the constant only holds while both operands do
+            let unchanged = (constant_value(&left), constant_value(&right)) == operands;
+
+            TypedExpression::Binary {
+                left,
+                operator,
+                right,
+                constant: constant.filter(|_| unchanged),
+                span,
+                type_,
+            }
+        }
+        TypedExpression::Cast {
+            expression,
+            target,
+            span,
+            type_,
+        } => TypedExpression::Cast {
+            expression: transform_boxed(transformer, expression),
+            target,
+            span,
+            type_,
+        },
+        TypedExpression::Block(block) => TypedExpression::Block(transformer.transform_block(block)),
+        #[cfg(feature = "interpreter")]
+        TypedExpression::Print { value } => TypedExpression::Print {
+            value: transform_boxed(transformer, value),
+        },
+        #[cfg(feature = "interpreter")]
+        drop @ TypedExpression::Drop { .. } => drop,
+        TypedExpression::Loop { body, type_ } => TypedExpression::Loop {
+            body: transform_boxed(transformer, body),
+            type_,
+        },
+        TypedExpression::While {
+            condition,
+            body,
+            else_body,
+            type_,
+        } => TypedExpression::While {
+            condition: transform_boxed(transformer, condition),
+            body: transform_boxed(transformer, body),
+            else_body: transform_optional(transformer, else_body),
+            type_,
+        },
+        TypedExpression::For {
+            identifier,
+            identifier_span,
+            iterable,
+            next,
+            body,
+            else_body,
+            type_,
+        } => TypedExpression::For {
+            identifier,
+            identifier_span,
+            iterable: transform_boxed(transformer, iterable),
+            next: transform_optional(transformer, next),
+            body: transform_boxed(transformer, body),
+            else_body: transform_optional(transformer, else_body),
+            type_,
+        },
+        TypedExpression::Break(expression) => {
+            TypedExpression::Break(transform_optional(transformer, expression))
+        }
+        TypedExpression::Return(expression) => {
+            TypedExpression::Return(transform_optional(transformer, expression))
+        }
+        TypedExpression::Continue => TypedExpression::Continue,
+    }
+}
+
+pub fn fold_member<T: TypedTransformer + ?Sized>(transformer: &mut T, member: Member) -> Member {
+    match member {
+        Member::Identifier { .. } => member,
+        Member::StaticMemberAccess {
+            type_annotation,
+            member,
+            symbol,
+            type_,
+        } => Member::StaticMemberAccess {
+            type_annotation,
+            member: Box::new(transformer.transform_member(*member)),
+            symbol,
+            type_,
+        },
+        Member::MemberAccess {
+            object,
+            member,
+            symbol,
+            type_,
+        } => Member::MemberAccess {
+            object: transform_boxed(transformer, object),
+            member: Box::new(transformer.transform_member(*member)),
+            symbol,
+            type_,
+        },
+    }
+}
+
+pub fn fold_literal<T: TypedTransformer + ?Sized>(
+    transformer: &mut T,
+    literal: Literal,
+) -> Literal {
+    match literal {
+        Literal::Array { values, type_ } => Literal::Array {
+            values: values
+                .into_iter()
+                .map(|v| transformer.transform_expression(v))
+                .collect(),
+            type_,
+        },
+        Literal::Struct {
+            type_annotation,
+            field_initializers,
+            spread,
+            type_,
+        } => Literal::Struct {
+            type_annotation,
+            field_initializers: field_initializers
+                .into_iter()
+                .map(|f| FieldInitializer {
+                    identifier: f.identifier,
+                    initializer: transformer.transform_expression(f.initializer),
+                })
+                .collect(),
+            spread: spread.map(|s| Box::new(transformer.transform_expression(*s))),
+            type_,
+        },
+        Literal::Enum {
+            type_annotation,
+            member,
+            field_initializers: EnumMemberFieldInitializers::Named(field_initializers),
+            type_,
+        } => Literal::Enum {
+            type_annotation,
+            member,
+            field_initializers: EnumMemberFieldInitializers::Named(
+                field_initializers
+                    .into_iter()
+                    .map(|(name, initializer)| {
+                        (name, transformer.transform_expression(initializer))
+                    })
+                    .collect(),
+            ),
+            type_,
+        },
+        literal => literal,
+    }
+}
+
+pub fn fold_block<T: TypedTransformer + ?Sized>(transformer: &mut T, block: Block) -> Block {
+    Block {
+        statements: block
+            .statements
+            .into_iter()
+            .map(|s| transformer.transform_statement(s))
+            .collect(),
+        type_: block.type_,
+    }
+}
+
+pub fn fold_decision<T: TypedTransformer + ?Sized>(
+    transformer: &mut T,
+    decision: Decision,
+) -> Decision {
+    match decision {
+        Decision::Success { expression, type_ } => Decision::Success {
+            expression: transform_boxed(transformer, expression),
+            type_,
+        },
+        Decision::Failure { .. } => decision,
+        Decision::Guard {
+            condition,
+            consequence,
+            alternative,
+            type_,
+        } => Decision::Guard {
+            condition: transform_boxed(transformer, condition),
+            consequence: Box::new(transformer.transform_decision(*consequence)),
+            alternative: Box::new(transformer.transform_decision(*alternative)),
+            type_,
+        },
+        Decision::Switch {
+            variable,
+            cases,
+            fallback,
+            type_,
+        } => Decision::Switch {
+            variable,
+            cases: cases
+                .into_iter()
+                .map(|c| Case {
+                    pattern: c.pattern,
+                    arguments: c.arguments,
+                    body: transformer.transform_decision(c.body),
+                })
+                .collect(),
+            fallback: Box::new(transformer.transform_decision(*fallback)),
+            type_,
+        },
+    }
+}
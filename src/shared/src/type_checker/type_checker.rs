@@ -1,13 +1,32 @@
-use std::collections::HashMap;
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    rc::Rc,
+};
 
 use crate::{
-    parser::{Parameter, Statement},
+    diagnostic::{Diagnostic, Severity},
+    parser::{self, Expression, Parameter, Statement, DEFAULT_MAX_DEPTH},
+    span::Span,
     types::{TypeAnnotation, TypeIdentifier},
 };
 
-use super::{ast::TypedStatement, statements, type_environment::TypeEnvironment, Rcrc};
+use super::{
+    ast::TypedStatement,
+    comparison::find_constant_comparisons,
+    constant::propagate_constants,
+    decision_tree_cache::DecisionTreeCache,
+    definite_assignment::check_definite_assignment,
+    imports,
+    incremental::{collect_statement, declaration_name},
+    statements,
+    tail_call::mark_tail_calls,
+    type_environment::{TypeEnvironment, DEFAULT_MAX_INSTANTIATION_DEPTH},
+    unused::find_unused,
+    Rcrc, Type,
+};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum DiscoveredType {
     Struct(TypeIdentifier, HashMap<String, TypeAnnotation>),
     Enum(
@@ -18,6 +37,7 @@ pub enum DiscoveredType {
     EnumMember(TypeIdentifier, HashMap<String, TypeAnnotation>),
     Union(TypeIdentifier, Vec<TypeAnnotation>),
     TypeAlias(TypeIdentifier, Vec<TypeAnnotation>),
+    Newtype(TypeIdentifier, TypeAnnotation),
     Protocol {
         type_identifier: TypeIdentifier,
         associated_types: Vec<TypeIdentifier>,
@@ -30,13 +50,324 @@ pub enum DiscoveredType {
     },
 }
 
+/// How integer overflow is handled when evaluating constant expressions
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum OverflowPolicy {
+    /// Overflow is a compile error
+    #[default]
+    Checked,
+    /// Overflow wraps around using two's complement
+    Wrapping,
+    /// Overflow clamps to the bounds of the type
+    Saturating,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TypeCheckerConfig {
+    pub overflow_policy: OverflowPolicy,
+    /// Reuse compiled decision trees for matches with the same shape.
+    /// Off by default so every match is compiled on its own.
+    pub decision_tree_cache: bool,
+    /// Replace reads of immutable bindings to constants with their values once checking is done,
+    /// folding the integer expressions this makes constant.
+    /// Off by default so the typed AST mirrors the source.
+    pub propagate_constants: bool,
+    /// What `check_program` does with the warnings of each category, like `unused_variable`.
+    /// Categories which aren't listed are reported as warnings.
+    pub lint_levels: HashMap<String, LintLevel>,
+    /// How deeply expressions can be nested before checking fails, or `None` for
+    /// `parser::DEFAULT_MAX_DEPTH`
+    pub max_depth: Option<usize>,
+    /// How deeply generic types can be given to each other before checking fails, or `None` for
+    /// `DEFAULT_MAX_INSTANTIATION_DEPTH`
+    pub max_instantiation_depth: Option<usize>,
+}
+
+/// What to do with the warnings of a category
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LintLevel {
+    /// Leave them out
+    Allow,
+    /// Report them as warnings
+    Warn,
+    /// Report them as errors
+    Deny,
+}
+
 pub fn create_typed_ast(
     program: Statement,
     type_environment: Rcrc<TypeEnvironment>,
+    config: TypeCheckerConfig,
 ) -> Result<TypedStatement, String> {
-    // Discover user-defined types. Only store their names and fields with type names.
-    let discovered_types = statements::discover_user_defined_types(&program)?;
+    let discovered_types = discover_types(&program, &type_environment, &config)?;
 
     // Then check the types of the entire AST.
-    statements::check_type(&program, &discovered_types, type_environment)
+    let typed_program =
+        statements::check_type(&program, &discovered_types, type_environment.clone())?;
+
+    finish(typed_program, type_environment, config)
+}
+
+/// An error found while checking a program
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeError {
+    pub message: String,
+    /// Where the declaration the error was found in is named, or the empty span if it has no name
+    pub span: Span,
+}
+
+impl From<TypeError> for Diagnostic {
+    fn from(error: TypeError) -> Self {
+        Diagnostic::new(error.message, error.span)
+    }
+}
+
+/// Check a program like `create_typed_ast`, but keep checking the top-level statements after one
+/// of them fails so every independent error is reported.
+///
+/// The variables a failed statement declares are given the type `Unknown`, so the statements
+/// after it can still use them. A failed statement which uses something declared by an earlier
+/// failed statement is most likely failing because of that, so its error is left out.
+pub fn create_typed_ast_collecting_errors(
+    program: Statement,
+    type_environment: Rcrc<TypeEnvironment>,
+    config: TypeCheckerConfig,
+) -> Result<TypedStatement, Vec<TypeError>> {
+    let error = |message: String| TypeError {
+        message,
+        span: Span::default(),
+    };
+
+    let Statement::Program { statements } = &program else {
+        return create_typed_ast(program, type_environment, config).map_err(|e| vec![error(e)]);
+    };
+
+    let discovered_types =
+        discover_types(&program, &type_environment, &config).map_err(|e| vec![error(e)])?;
+
+    let mut errors = vec![];
+    let mut failed_names = HashSet::new();
+
+    let typed_statements = check_statements(
+        statements,
+        &discovered_types,
+        type_environment.clone(),
+        |statement, message| {
+            let mut references = HashSet::new();
+            collect_statement(statement, &mut references);
+
+            if references.is_disjoint(&failed_names) {
+                errors.push(TypeError {
+                    message,
+                    span: name_span(statement),
+                });
+            }
+
+            failed_names.extend(declaration_name(statement));
+
+            for variable in declared_variables(statement) {
+                type_environment
+                    .borrow_mut()
+                    .add_variable(variable.clone(), Type::Unknown);
+
+                failed_names.insert(variable);
+            }
+
+            Ok(())
+        },
+    )
+    .map_err(|e| vec![error(e)])?;
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    let typed_program = TypedStatement::Program {
+        statements: typed_statements,
+    };
+
+    finish(typed_program, type_environment, config).map_err(|e| vec![error(e)])
+}
+
+/// A program checked by `check_program` and everything found while checking it
+#[derive(Debug, Clone)]
+pub struct CheckedProgram {
+    /// The typed program, or `None` if checking it failed
+    pub typed_program: Option<TypedStatement>,
+    /// The errors followed by the warnings, with the lint levels of the config applied
+    pub diagnostics: Vec<Diagnostic>,
+    /// Whether any of the diagnostics is an error, including warnings the config denies
+    pub has_errors: bool,
+}
+
+/// Check a program like `create_typed_ast_collecting_errors`, returning its errors and warnings
+/// together as diagnostics.
+///
+/// Warnings are promoted to errors or left out by the lint levels of the config. A program whose
+/// only errors are denied warnings is still typed, but it shouldn't be run.
+pub fn check_program(
+    program: Statement,
+    type_environment: Rcrc<TypeEnvironment>,
+    config: TypeCheckerConfig,
+) -> CheckedProgram {
+    let lint_levels = config.lint_levels.clone();
+    let result = create_typed_ast_collecting_errors(program, type_environment.clone(), config);
+
+    let (typed_program, mut diagnostics) = match result {
+        Ok(typed_program) => (Some(typed_program), vec![]),
+        Err(errors) => (None, errors.into_iter().map(Diagnostic::from).collect()),
+    };
+
+    for mut warning in type_environment.borrow_mut().take_warning_diagnostics() {
+        let level = warning
+            .category
+            .as_ref()
+            .and_then(|category| lint_levels.get(category));
+
+        match level {
+            Some(LintLevel::Allow) => continue,
+            Some(LintLevel::Deny) => warning.severity = Severity::Error,
+            Some(LintLevel::Warn) | None => {}
+        }
+
+        diagnostics.push(warning);
+    }
+
+    let has_errors = diagnostics
+        .iter()
+        .any(|diagnostic| diagnostic.severity == Severity::Error);
+
+    CheckedProgram {
+        typed_program,
+        diagnostics,
+        has_errors,
+    }
+}
+
+/// Discover user-defined types. Only store their names and fields with type names.
+fn discover_types(
+    program: &Statement,
+    type_environment: &Rcrc<TypeEnvironment>,
+    config: &TypeCheckerConfig,
+) -> Result<Vec<DiscoveredType>, String> {
+    configure(type_environment, config);
+
+    statements::discover_user_defined_types(program)
+}
+
+/// Give the environment the settings of the config before anything is checked in it
+pub(super) fn configure(type_environment: &Rcrc<TypeEnvironment>, config: &TypeCheckerConfig) {
+    type_environment
+        .borrow_mut()
+        .set_overflow_policy(config.overflow_policy);
+
+    type_environment
+        .borrow_mut()
+        .set_max_depth(config.max_depth.unwrap_or(DEFAULT_MAX_DEPTH));
+
+    type_environment.borrow_mut().set_max_instantiation_depth(
+        config
+            .max_instantiation_depth
+            .unwrap_or(DEFAULT_MAX_INSTANTIATION_DEPTH),
+    );
+
+    if config.decision_tree_cache {
+        type_environment
+            .borrow_mut()
+            .set_decision_tree_cache(Some(Rc::new(RefCell::new(DecisionTreeCache::default()))));
+    }
+}
+
+/// Check a top-level statement and make what it declares visible to imports of the module
+pub(super) fn check_statement(
+    statement: &Statement,
+    discovered_types: &Vec<DiscoveredType>,
+    type_environment: Rcrc<TypeEnvironment>,
+) -> Result<TypedStatement, String> {
+    let typed_statement =
+        statements::check_type(statement, discovered_types, type_environment.clone());
+
+    if let Some((name, access_modifier)) = imports::declared_item(statement) {
+        type_environment
+            .borrow_mut()
+            .add_module_item(name, access_modifier);
+    }
+
+    typed_statement
+}
+
+/// Check the statements of a program in order, handing the error of each failed statement to
+/// `on_error`. Checking stops at the first error `on_error` gives back, otherwise the failed
+/// statement is left out.
+pub(super) fn check_statements(
+    statements: &[Statement],
+    discovered_types: &Vec<DiscoveredType>,
+    type_environment: Rcrc<TypeEnvironment>,
+    mut on_error: impl FnMut(&Statement, String) -> Result<(), String>,
+) -> Result<Vec<TypedStatement>, String> {
+    let mut typed_statements = vec![];
+
+    for statement in statements {
+        match check_statement(statement, discovered_types, type_environment.clone()) {
+            Ok(typed_statement) => typed_statements.push(typed_statement),
+            Err(message) => on_error(statement, message)?,
+        }
+    }
+
+    Ok(typed_statements)
+}
+
+/// Run the checks which need the whole typed program and transform it into its final shape
+pub(super) fn finish(
+    typed_program: TypedStatement,
+    type_environment: Rcrc<TypeEnvironment>,
+    config: TypeCheckerConfig,
+) -> Result<TypedStatement, String> {
+    // Finally make sure no variable is read before it is assigned, and warn about what is unused
+    // and about comparisons which always give the same result.
+    check_definite_assignment(&typed_program)?;
+
+    for (category, warning) in find_unused(&typed_program) {
+        type_environment.borrow_mut().add_warning(category, warning);
+    }
+
+    for warning in find_constant_comparisons(&typed_program, config.overflow_policy) {
+        type_environment
+            .borrow_mut()
+            .add_warning("constant_comparison", warning);
+    }
+
+    let typed_program = mark_tail_calls(typed_program);
+
+    if config.propagate_constants {
+        return propagate_constants(typed_program, config.overflow_policy);
+    }
+
+    Ok(typed_program)
+}
+
+fn name_span(statement: &Statement) -> Span {
+    match statement {
+        Statement::StructDeclaration(parser::StructDeclaration { span, .. })
+        | Statement::EnumDeclaration(parser::EnumDeclaration { span, .. })
+        | Statement::FunctionDeclaration(parser::FunctionDeclaration { span, .. }) => *span,
+        Statement::Expression(Expression::VariableDeclaration(declaration)) => {
+            declaration.pattern_span
+        }
+        Statement::Semi(statement) => name_span(statement),
+        _ => Span::default(),
+    }
+}
+
+fn declared_variables(statement: &Statement) -> Vec<String> {
+    match statement {
+        Statement::Expression(Expression::VariableDeclaration(declaration)) => declaration
+            .pattern
+            .variables()
+            .into_iter()
+            .map(str::to_owned)
+            .collect(),
+        Statement::Semi(statement) => declared_variables(statement),
+        _ => vec![],
+    }
 }
@@ -1,13 +1,28 @@
-use std::{cell::RefCell, collections::HashMap, rc::Rc, str::FromStr};
+use std::{
+    cell::{Cell, RefCell},
+    collections::{HashMap, HashSet},
+    rc::Rc,
+    str::FromStr,
+};
 
 use crate::{
+    diagnostic::Diagnostic,
+    parser::DEFAULT_MAX_DEPTH,
+    span::Span,
     type_checker::Protocol,
     types::{GenericConstraint, GenericType, ToKey, TypeAnnotation, TypeIdentifier},
 };
 
 use super::{
+    ast::{AccessModifier, TypedExpression},
+    decision_tree_cache::DecisionTreeCache,
+    imports::Import,
+    interner::{Interner, Symbol, SymbolMap},
     scope::{Scope, ScopeType},
-    FullName, Parameter, Type,
+    statements,
+    suggestion::{self, did_you_mean},
+    type_id::TypeId,
+    FullName, OverflowPolicy, Parameter, Type,
 };
 
 pub type Rcrc<T> = Rc<RefCell<T>>;
@@ -16,49 +31,212 @@ pub type Rcrc<T> = Rc<RefCell<T>>;
 pub struct TypeEnvironment {
     parent: Option<Rcrc<TypeEnvironment>>,
     modules: Vec<Vec<String>>,
-    types: HashMap<String, Type>,
+    interner: Rcrc<Interner>,
+    types: SymbolMap<Type>,
+    /// The ids of the declared types in `types`
+    type_ids: SymbolMap<TypeId>,
+    /// The modules the declared types in `types` were declared in
+    type_modules: SymbolMap<Vec<String>>,
+    /// The types which were declared by loading an interface
+    interface_types: HashSet<Symbol>,
     static_members: HashMap<TypeAnnotation, HashMap<String, Type>>,
     variables: HashMap<String, Type>,
+    mutable_variables: HashSet<String>,
+    uninitialized_variables: HashSet<String>,
     scopes: Vec<Scope>,
     allow_override_types: bool,
+    overflow_policy: OverflowPolicy,
+    decision_tree_cache: Option<Rcrc<DecisionTreeCache>>,
+    current_module: Vec<String>,
+    visibilities: HashMap<String, Visibility>,
+    module_items: HashMap<Vec<String>, Vec<(String, Option<AccessModifier>)>>,
+    /// The types of the items in `module_items` by their full path, as the same name can be
+    /// declared by more than one module
+    module_item_types: HashMap<Vec<String>, Type>,
+    imports: HashMap<String, Vec<Import>>,
+    implementations: HashMap<String, Vec<String>>,
+    where_clauses: HashMap<String, (Vec<GenericType>, Vec<GenericConstraint>)>,
+    field_defaults: HashMap<String, TypedExpression>,
+    mutable_fields: HashSet<String>,
+    return_type: Option<Type>,
+    warnings: Vec<Diagnostic>,
+    /// How many expressions the one being checked is nested in. Only the root environment counts.
+    depth: Cell<usize>,
+    max_depth: usize,
+    /// How many generic types the one being resolved is given to. Only the root environment counts.
+    instantiation_depth: Cell<usize>,
+    max_instantiation_depth: usize,
+    /// The names of the declared types whose fields are being resolved, innermost last. Only the
+    /// root environment counts.
+    resolving_types: RefCell<Vec<String>>,
+    /// Whether the program being checked is a module interface, whose methods are signatures
+    /// without bodies. Only the root environment counts.
+    interface: bool,
+    /// Whether this environment keeps the modules, visibilities, field defaults, implementations
+    /// and where clauses declared in it like a root environment, until it is merged into its
+    /// parent
+    keeps_declarations: bool,
+}
+
+/// Where a member was declared, and who may access it from there
+#[derive(Debug, Clone, PartialEq)]
+pub struct Visibility {
+    pub module_path: Vec<String>,
+    pub access_modifier: Option<AccessModifier>,
+}
+
+impl Visibility {
+    /// Public members are visible everywhere, super members in the parent module and below it.
+    /// Members without an access modifier are private to their module like module members.
+    pub fn is_visible_from(&self, module_path: &[String]) -> bool {
+        match self.access_modifier {
+            Some(AccessModifier::Public) => true,
+            Some(AccessModifier::Super) => {
+                let parent = &self.module_path[..self.module_path.len().saturating_sub(1)];
+                module_path.starts_with(parent)
+            }
+            Some(AccessModifier::Module) | None => module_path.starts_with(&self.module_path),
+        }
+    }
+}
+
+pub(crate) fn module_name(module_path: &[String]) -> String {
+    if module_path.is_empty() {
+        "the root module".to_string()
+    } else {
+        format!("module '{}'", module_path.join("::"))
+    }
+}
+
+/// The functions of a protocol implementation are static members of `Protocol<Type>`, so they
+/// can't collide with the inherent methods of the type or the functions of other protocols
+/// How many generic types a type can be given to inside each other before resolving it fails
+pub const DEFAULT_MAX_INSTANTIATION_DEPTH: usize = 32;
+
+pub fn protocol_member_owner(
+    protocol_name: &str,
+    type_annotation: TypeAnnotation,
+) -> TypeAnnotation {
+    TypeAnnotation::ConcreteType(protocol_name.to_owned(), vec![type_annotation])
 }
 
 impl TypeEnvironment {
     pub fn new(allow_override_types: bool) -> Self {
+        let mut interner = Interner::new();
+
+        let types = [
+            ("Void", Type::Void),
+            ("Unit", Type::Unit),
+            ("Bool", Type::Bool),
+            ("Int", Type::Int),
+            ("UInt", Type::UInt),
+            ("Float", Type::Float),
+            ("Char", Type::Char),
+            ("String", Type::String),
+        ]
+        .into_iter()
+        .map(|(name, type_)| (interner.intern(name), type_))
+        .collect();
+
         Self {
             parent: None,
             modules: Vec::new(),
-            types: HashMap::from([
-                ("Void".to_string(), Type::Void),
-                ("Unit".to_string(), Type::Unit),
-                ("Bool".to_string(), Type::Bool),
-                ("Int".to_string(), Type::Int),
-                ("UInt".to_string(), Type::UInt),
-                ("Float".to_string(), Type::Float),
-                ("Char".to_string(), Type::Char),
-                ("String".to_string(), Type::String),
-            ]),
+            interner: Rc::new(RefCell::new(interner)),
+            types,
+            type_ids: SymbolMap::default(),
+            type_modules: SymbolMap::default(),
+            interface_types: HashSet::new(),
             static_members: HashMap::new(),
             variables: HashMap::new(),
+            mutable_variables: HashSet::new(),
+            uninitialized_variables: HashSet::new(),
             scopes: Vec::new(),
             allow_override_types,
+            overflow_policy: OverflowPolicy::default(),
+            decision_tree_cache: None,
+            current_module: Vec::new(),
+            visibilities: HashMap::new(),
+            module_items: HashMap::new(),
+            module_item_types: HashMap::new(),
+            imports: HashMap::new(),
+            implementations: HashMap::new(),
+            where_clauses: HashMap::new(),
+            field_defaults: HashMap::new(),
+            mutable_fields: HashSet::new(),
+            return_type: None,
+            warnings: Vec::new(),
+            depth: Cell::new(0),
+            max_depth: DEFAULT_MAX_DEPTH,
+            instantiation_depth: Cell::new(0),
+            max_instantiation_depth: DEFAULT_MAX_INSTANTIATION_DEPTH,
+            resolving_types: RefCell::new(Vec::new()),
+            interface: false,
+            keeps_declarations: false,
         }
     }
 
     pub fn new_parent(parent: Rcrc<Self>) -> Self {
         let allow_override_types = parent.borrow().allow_override_types;
+        let overflow_policy = parent.borrow().overflow_policy;
+        let interner = parent.borrow().interner.clone();
+        let decision_tree_cache = parent.borrow().decision_tree_cache.clone();
 
         Self {
             parent: Some(parent),
             modules: Vec::new(),
-            types: HashMap::new(),
+            interner,
+            types: SymbolMap::default(),
+            type_ids: SymbolMap::default(),
+            type_modules: SymbolMap::default(),
+            interface_types: HashSet::new(),
             static_members: HashMap::new(),
             variables: HashMap::new(),
+            mutable_variables: HashSet::new(),
+            uninitialized_variables: HashSet::new(),
             scopes: Vec::new(),
             allow_override_types,
+            overflow_policy,
+            decision_tree_cache,
+            current_module: Vec::new(),
+            visibilities: HashMap::new(),
+            module_items: HashMap::new(),
+            module_item_types: HashMap::new(),
+            imports: HashMap::new(),
+            implementations: HashMap::new(),
+            where_clauses: HashMap::new(),
+            field_defaults: HashMap::new(),
+            mutable_fields: HashSet::new(),
+            return_type: None,
+            warnings: Vec::new(),
+            depth: Cell::new(0),
+            max_depth: DEFAULT_MAX_DEPTH,
+            instantiation_depth: Cell::new(0),
+            max_instantiation_depth: DEFAULT_MAX_INSTANTIATION_DEPTH,
+            resolving_types: RefCell::new(Vec::new()),
+            interface: false,
+            keeps_declarations: false,
         }
     }
 
+    /// A child of a root environment which starts with copies of what the root keeps about the
+    /// declarations, so checking something in it can't change the root until it is merged
+    pub fn new_isolated(parent: Rcrc<Self>) -> Self {
+        let mut type_environment = Self::new_parent(parent.clone());
+        let parent = parent.borrow();
+
+        type_environment.modules = parent.modules.clone();
+        type_environment.current_module = parent.current_module.clone();
+        type_environment.visibilities = parent.visibilities.clone();
+        type_environment.module_items = parent.module_items.clone();
+        type_environment.module_item_types = parent.module_item_types.clone();
+        type_environment.implementations = parent.implementations.clone();
+        type_environment.where_clauses = parent.where_clauses.clone();
+        type_environment.field_defaults = parent.field_defaults.clone();
+        type_environment.mutable_fields = parent.mutable_fields.clone();
+        type_environment.keeps_declarations = true;
+        type_environment
+    }
+
     pub fn new_scope<T: Into<Scope>>(parent: Rcrc<Self>, scope: T) -> Self {
         Self::new_scopes(parent, [scope])
     }
@@ -68,21 +246,189 @@ impl TypeEnvironment {
         scopes: U,
     ) -> Self {
         let allow_override_types = parent.borrow().allow_override_types;
+        let overflow_policy = parent.borrow().overflow_policy;
+        let interner = parent.borrow().interner.clone();
+        let decision_tree_cache = parent.borrow().decision_tree_cache.clone();
 
         Self {
             parent: Some(parent),
             modules: Vec::new(),
+            interner,
             variables: HashMap::new(),
-            types: HashMap::new(),
+            mutable_variables: HashSet::new(),
+            uninitialized_variables: HashSet::new(),
+            types: SymbolMap::default(),
+            type_ids: SymbolMap::default(),
+            type_modules: SymbolMap::default(),
+            interface_types: HashSet::new(),
             static_members: HashMap::new(),
             scopes: scopes
                 .into_iter()
                 .map(|scope| scope.into())
                 .collect::<Vec<Scope>>(),
             allow_override_types,
+            overflow_policy,
+            decision_tree_cache,
+            current_module: Vec::new(),
+            visibilities: HashMap::new(),
+            module_items: HashMap::new(),
+            module_item_types: HashMap::new(),
+            imports: HashMap::new(),
+            implementations: HashMap::new(),
+            where_clauses: HashMap::new(),
+            field_defaults: HashMap::new(),
+            mutable_fields: HashSet::new(),
+            return_type: None,
+            warnings: Vec::new(),
+            depth: Cell::new(0),
+            max_depth: DEFAULT_MAX_DEPTH,
+            instantiation_depth: Cell::new(0),
+            max_instantiation_depth: DEFAULT_MAX_INSTANTIATION_DEPTH,
+            resolving_types: RefCell::new(Vec::new()),
+            interface: false,
+            keeps_declarations: false,
+        }
+    }
+
+    /// The parent which keeps what is known about the declarations, unless it's this environment
+    fn declarations_parent(&self) -> Option<&Rcrc<Self>> {
+        match self.keeps_declarations {
+            true => None,
+            false => self.parent.as_ref(),
         }
     }
 
+    pub fn interner(&self) -> Rcrc<Interner> {
+        self.interner.clone()
+    }
+
+    pub fn overflow_policy(&self) -> OverflowPolicy {
+        self.overflow_policy
+    }
+
+    pub fn set_overflow_policy(&mut self, overflow_policy: OverflowPolicy) {
+        self.overflow_policy = overflow_policy;
+    }
+
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        self.max_depth = max_depth;
+    }
+
+    /// Enter a nested expression, failing when expressions are nested deeper than the limit so
+    /// checking them can't overflow the stack. Every successful call is followed by `exit_nested`.
+    pub(crate) fn enter_nested(&self) -> Result<(), String> {
+        if let Some(parent) = &self.parent {
+            return parent.borrow().enter_nested();
+        }
+
+        if self.depth.get() >= self.max_depth {
+            return Err(format!(
+                "Expression nesting is too deep, the limit is {} levels",
+                self.max_depth
+            ));
+        }
+
+        self.depth.set(self.depth.get() + 1);
+        Ok(())
+    }
+
+    pub(crate) fn exit_nested(&self) {
+        match &self.parent {
+            Some(parent) => parent.borrow().exit_nested(),
+            None => self.depth.set(self.depth.get().saturating_sub(1)),
+        }
+    }
+
+    pub fn set_max_instantiation_depth(&mut self, max_instantiation_depth: usize) {
+        self.max_instantiation_depth = max_instantiation_depth;
+    }
+
+    /// Enter the instantiation of a generic type, failing when types are given to generic types
+    /// deeper than the limit. A struct like `struct Nest<T> { next: Option<Nest<T>> }` which
+    /// refers to itself is found by its name with `enter_type` instead, so this only stops types
+    /// which are given to each other without end some other way. Every successful call is followed
+    /// by `exit_instantiation`.
+    pub(crate) fn enter_instantiation(
+        &self,
+        type_annotation: &TypeAnnotation,
+    ) -> Result<(), String> {
+        if let Some(parent) = &self.parent {
+            return parent.borrow().enter_instantiation(type_annotation);
+        }
+
+        if self.instantiation_depth.get() >= self.max_instantiation_depth {
+            return Err(format!(
+                "Generic instantiation too deep at {}, the limit is {} levels",
+                type_annotation, self.max_instantiation_depth
+            ));
+        }
+
+        self.instantiation_depth
+            .set(self.instantiation_depth.get() + 1);
+        Ok(())
+    }
+
+    pub(crate) fn exit_instantiation(&self) {
+        match &self.parent {
+            Some(parent) => parent.borrow().exit_instantiation(),
+            None => self
+                .instantiation_depth
+                .set(self.instantiation_depth.get().saturating_sub(1)),
+        }
+    }
+
+    pub(crate) fn set_interface(&mut self, interface: bool) {
+        match &self.parent {
+            Some(parent) => parent.borrow_mut().set_interface(interface),
+            None => self.interface = interface,
+        }
+    }
+
+    pub(crate) fn is_interface(&self) -> bool {
+        match &self.parent {
+            Some(parent) => parent.borrow().is_interface(),
+            None => self.interface,
+        }
+    }
+
+    /// Start resolving the fields of the declared type `name`, unless they are already being
+    /// resolved, which means the type refers to itself. Every call which starts is followed by
+    /// `exit_type`.
+    pub(crate) fn enter_type(&self, name: &str) -> bool {
+        if let Some(parent) = &self.parent {
+            return parent.borrow().enter_type(name);
+        }
+
+        let mut resolving_types = self.resolving_types.borrow_mut();
+
+        if resolving_types.iter().any(|type_name| type_name == name) {
+            return false;
+        }
+
+        resolving_types.push(name.to_string());
+        true
+    }
+
+    pub(crate) fn exit_type(&self) {
+        match &self.parent {
+            Some(parent) => parent.borrow().exit_type(),
+            None => {
+                self.resolving_types.borrow_mut().pop();
+            }
+        }
+    }
+
+    pub fn decision_tree_cache(&self) -> Option<Rcrc<DecisionTreeCache>> {
+        self.decision_tree_cache.clone()
+    }
+
+    pub fn set_decision_tree_cache(
+        &mut self,
+        decision_tree_cache: Option<Rcrc<DecisionTreeCache>>,
+    ) {
+        self.decision_tree_cache = decision_tree_cache;
+    }
+
     pub fn has_scope(&self, scope_type: &ScopeType) -> bool {
         self.scopes.iter().any(|s| s.scope_type == *scope_type)
             || self
@@ -104,6 +450,28 @@ impl TypeEnvironment {
             })
     }
 
+    /// The active scope of this environment itself, leaving out the scopes of its parents
+    pub fn get_own_scope(&self, scope_type: &ScopeType) -> Option<Scope> {
+        self.scopes
+            .iter()
+            .find(|s| s.scope_type == *scope_type && s.active())
+            .cloned()
+    }
+
+    /// Record the declared return type of the function whose body this environment holds
+    pub fn set_return_type(&mut self, return_type: Type) {
+        self.return_type = Some(return_type);
+    }
+
+    /// The declared return type of the innermost function being checked
+    pub fn get_return_type(&self) -> Option<Type> {
+        self.return_type.clone().or_else(|| {
+            self.parent
+                .as_ref()
+                .and_then(|parent| parent.borrow().get_return_type())
+        })
+    }
+
     pub fn activate_scope(&mut self, scope_type: ScopeType, type_: Type) -> Result<(), String> {
         if !self.has_scope(&scope_type) {
             return Err(format!("Scope '{:?}' not found", scope_type));
@@ -123,28 +491,624 @@ impl TypeEnvironment {
     }
 
     pub fn add_module(&mut self, module_path: Vec<String>) {
-        self.modules.push(module_path)
+        if let Some(parent) = self.declarations_parent() {
+            return parent.borrow_mut().add_module(module_path);
+        }
+
+        if !self.modules.contains(&module_path) {
+            self.modules.push(module_path)
+        }
+    }
+
+    /// Whether the path names a known module, or a parent of one
+    pub fn is_module(&self, module_path: &[String]) -> bool {
+        match self.declarations_parent() {
+            Some(parent) => parent.borrow().is_module(module_path),
+            None => self
+                .modules
+                .iter()
+                .any(|module| module.starts_with(module_path)),
+        }
+    }
+
+    /// Record a top-level type or function declared in the current module
+    pub fn add_module_item(&mut self, name: String, access_modifier: Option<AccessModifier>) {
+        let type_ = self.get_type(name.as_str());
+        self.record_module_item(name, access_modifier, type_);
+    }
+
+    fn record_module_item(
+        &mut self,
+        name: String,
+        access_modifier: Option<AccessModifier>,
+        type_: Option<Type>,
+    ) {
+        if let Some(parent) = self.declarations_parent() {
+            return parent
+                .borrow_mut()
+                .record_module_item(name, access_modifier, type_);
+        }
+
+        let mut path = self.current_module.clone();
+        path.push(name.clone());
+
+        match type_ {
+            Some(type_) => self.module_item_types.insert(path, type_),
+            None => self.module_item_types.remove(&path),
+        };
+
+        let items = self
+            .module_items
+            .entry(self.current_module.clone())
+            .or_default();
+
+        items.retain(|(item, _)| *item != name);
+        items.push((name, access_modifier));
+    }
+
+    /// The type of the item declared at the full path
+    pub fn get_module_item_type(&self, path: &[String]) -> Option<Type> {
+        match self.declarations_parent() {
+            Some(parent) => parent.borrow().get_module_item_type(path),
+            None => self.module_item_types.get(path).cloned(),
+        }
+    }
+
+    /// The type of the item an unqualified name is imported as, which is not necessarily the
+    /// latest declaration of that name
+    pub fn get_imported_type(&self, name: &str) -> Option<Type> {
+        self.get_import(name)
+            .and_then(|path| self.get_module_item_type(&path))
+    }
+
+    pub fn get_module_item(
+        &self,
+        module_path: &[String],
+        name: &str,
+    ) -> Option<Option<AccessModifier>> {
+        match self.declarations_parent() {
+            Some(parent) => parent.borrow().get_module_item(module_path, name),
+            None => self
+                .module_items
+                .get(module_path)?
+                .iter()
+                .find(|(item, _)| item == name)
+                .map(|(_, access_modifier)| access_modifier.clone()),
+        }
+    }
+
+    pub fn get_module_items(
+        &self,
+        module_path: &[String],
+    ) -> Vec<(String, Option<AccessModifier>)> {
+        match self.declarations_parent() {
+            Some(parent) => parent.borrow().get_module_items(module_path),
+            None => self
+                .module_items
+                .get(module_path)
+                .cloned()
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Bring a name into this scope as an alias for the item at the full path.
+    /// An explicit import shadows glob imports of the same name, while several glob imports
+    /// of the same name from different paths are all kept to detect ambiguous uses.
+    pub fn add_import(&mut self, import: Import) {
+        let imports = self.imports.entry(import.name.clone()).or_default();
+
+        if !import.glob {
+            *imports = vec![import];
+            return;
+        }
+
+        if imports
+            .iter()
+            .any(|existing| !existing.glob || existing.path == import.path)
+        {
+            return;
+        }
+
+        imports.push(import);
+    }
+
+    /// The imports of the name in the closest scope which imports it
+    pub fn get_imports(&self, name: &str) -> Vec<Import> {
+        match self.imports.get(name) {
+            Some(imports) => imports.clone(),
+            None => self
+                .parent
+                .as_ref()
+                .map(|p| p.borrow().get_imports(name))
+                .unwrap_or_default(),
+        }
+    }
+
+    /// The full path of an imported name, unless it was never imported or is ambiguous
+    pub fn get_import(&self, name: &str) -> Option<Vec<String>> {
+        match self.get_imports(name).as_slice() {
+            [import] => Some(import.path.clone()),
+            _ => None,
+        }
+    }
+
+    /// Fail if the name is brought in by more than one glob import
+    pub fn check_import_ambiguity(&self, name: &str) -> Result<(), String> {
+        let imports = self.get_imports(name);
+
+        if imports.len() < 2 {
+            return Ok(());
+        }
+
+        Err(format!(
+            "'{}' is ambiguous, it is glob imported from {}",
+            name,
+            imports
+                .iter()
+                .map(|import| format!("'{}'", import.path.join("::")))
+                .collect::<Vec<String>>()
+                .join(" and ")
+        ))
+    }
+
+    /// The module being checked, which is kept by the root environment
+    pub fn current_module(&self) -> Vec<String> {
+        match self.declarations_parent() {
+            Some(parent) => parent.borrow().current_module(),
+            None => self.current_module.clone(),
+        }
+    }
+
+    pub fn set_current_module(&mut self, module_path: Vec<String>) {
+        match self.declarations_parent() {
+            Some(parent) => parent.borrow_mut().set_current_module(module_path),
+            None => self.current_module = module_path,
+        }
+    }
+
+    /// Record that a member like `Point.x` was declared in the current module.
+    /// Visibilities are kept by the root environment so they outlive the scope of the declaration.
+    pub fn add_visibility(&mut self, member: String, access_modifier: Option<AccessModifier>) {
+        match self.declarations_parent() {
+            Some(parent) => parent.borrow_mut().add_visibility(member, access_modifier),
+            None => {
+                let visibility = Visibility {
+                    module_path: self.current_module.clone(),
+                    access_modifier,
+                };
+
+                self.visibilities.insert(member, visibility);
+            }
+        }
+    }
+
+    /// Forget what was recorded about the members and implementations of a type which is declared
+    /// again, so the new declaration doesn't inherit them. A type an interface declared is only
+    /// declared again by its definition, which has the same members.
+    pub fn forget_redeclared_type(&mut self, type_identifier: &TypeIdentifier) {
+        let symbol = self.interner.borrow_mut().intern(&type_identifier.to_key());
+
+        if self.get_type_by_symbol(symbol).is_some() && !self.is_interface_type(symbol) {
+            self.forget_type(type_identifier.name());
+        }
+    }
+
+    fn is_interface_type(&self, symbol: Symbol) -> bool {
+        self.interface_types.contains(&symbol)
+            || self
+                .parent
+                .as_ref()
+                .is_some_and(|parent| parent.borrow().is_interface_type(symbol))
+    }
+
+    fn forget_type(&mut self, name: &str) {
+        if let Some(parent) = self.declarations_parent() {
+            return parent.borrow_mut().forget_type(name);
+        }
+
+        let is_member = |member: &String| {
+            member
+                .strip_prefix(name)
+                .is_some_and(|rest| rest.starts_with('.'))
+        };
+
+        self.visibilities.retain(|member, _| !is_member(member));
+        self.field_defaults.retain(|field, _| !is_member(field));
+        self.mutable_fields.retain(|field| !is_member(field));
+        self.implementations.retain(|key, _| {
+            key.strip_prefix(name)
+                .is_none_or(|rest| !rest.is_empty() && !rest.starts_with('<'))
+        });
+    }
+
+    /// Record the default value of a field like `Point.x`, which struct literals omitting the
+    /// field are filled with
+    pub fn add_field_default(&mut self, field: String, default: TypedExpression) {
+        match self.declarations_parent() {
+            Some(parent) => parent.borrow_mut().add_field_default(field, default),
+            None => {
+                self.field_defaults.insert(field, default);
+            }
+        }
+    }
+
+    pub fn get_field_default(&self, field: &str) -> Option<TypedExpression> {
+        match self.declarations_parent() {
+            Some(parent) => parent.borrow().get_field_default(field),
+            None => self.field_defaults.get(field).cloned(),
+        }
+    }
+
+    /// Record that a struct field like `Point.x` was declared `mut`
+    pub fn add_mutable_field(&mut self, field: String) {
+        match self.declarations_parent() {
+            Some(parent) => parent.borrow_mut().add_mutable_field(field),
+            None => {
+                self.mutable_fields.insert(field);
+            }
+        }
+    }
+
+    pub fn is_mutable_field(&self, field: &str) -> bool {
+        match self.declarations_parent() {
+            Some(parent) => parent.borrow().is_mutable_field(field),
+            None => self.mutable_fields.contains(field),
+        }
+    }
+
+    /// Record that a type implements a protocol, which is kept by the root environment
+    pub fn add_implementation(&mut self, type_: &Type, protocol: &Protocol) -> Result<(), String> {
+        if let Some(parent) = self.declarations_parent() {
+            return parent.borrow_mut().add_implementation(type_, protocol);
+        }
+
+        let protocols = self.implementations.entry(type_.to_key()).or_default();
+        let protocol_name = protocol.type_identifier.name().to_owned();
+
+        if protocols.contains(&protocol_name) {
+            return Err(format!(
+                "'{}' already implements protocol '{}'",
+                type_, protocol_name
+            ));
+        }
+
+        protocols.push(protocol_name);
+        Ok(())
+    }
+
+    /// Report something which isn't an error but is likely a mistake, found by the check named
+    /// `category`. Warnings are kept by the root environment until they are taken.
+    pub fn add_warning(&mut self, category: &str, warning: String) {
+        match &self.parent {
+            Some(parent) => parent.borrow_mut().add_warning(category, warning),
+            None => self
+                .warnings
+                .push(Diagnostic::warning(category, warning, Span::default())),
+        }
+    }
+
+    pub fn take_warnings(&mut self) -> Vec<String> {
+        self.take_warning_diagnostics()
+            .into_iter()
+            .map(|warning| warning.message)
+            .collect()
+    }
+
+    /// Take the warnings along with the categories they were reported in
+    pub fn take_warning_diagnostics(&mut self) -> Vec<Diagnostic> {
+        match &self.parent {
+            Some(parent) => parent.borrow_mut().take_warning_diagnostics(),
+            None => std::mem::take(&mut self.warnings),
+        }
+    }
+
+    pub fn implements(&self, type_: &Type, protocol_name: &str) -> bool {
+        self.get_implementations(type_)
+            .iter()
+            .any(|p| p == protocol_name)
+    }
+
+    pub fn get_implementations(&self, type_: &Type) -> Vec<String> {
+        match self.declarations_parent() {
+            Some(parent) => parent.borrow().get_implementations(type_),
+            None => self
+                .implementations
+                .get(&type_.to_key())
+                .cloned()
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Find a function which one of the protocols implemented by a type gives it, along with the
+    /// static member owner it is found under
+    pub fn get_protocol_member(
+        &self,
+        type_: &Type,
+        member: &str,
+    ) -> Result<Option<(TypeAnnotation, Type)>, String> {
+        let mut found = vec![];
+
+        for protocol_name in self.get_implementations(type_) {
+            let owner = protocol_member_owner(&protocol_name, type_.type_annotation());
+
+            if let Some(member_type) = self.get_static_member(owner.clone(), member) {
+                found.push((protocol_name, owner, member_type));
+            }
+        }
+
+        match found.as_slice() {
+            [] => Ok(None),
+            [(_, owner, member_type)] => Ok(Some((owner.clone(), member_type.clone()))),
+            [(first, ..), (second, ..), ..] => Err(format!(
+                "'{}' is ambiguous for '{}', it is implemented by protocols '{}' and '{}'",
+                member, type_, first, second
+            )),
+        }
+    }
+
+    /// Record the constraints on the generics of a declaration, so they can be checked wherever
+    /// the declaration is given concrete types
+    pub fn add_where_clause(
+        &mut self,
+        name: String,
+        generics: Vec<GenericType>,
+        where_clause: Vec<GenericConstraint>,
+    ) {
+        match self.declarations_parent() {
+            Some(parent) => parent
+                .borrow_mut()
+                .add_where_clause(name, generics, where_clause),
+            None => {
+                self.where_clauses.insert(name, (generics, where_clause));
+            }
+        }
+    }
+
+    pub fn check_where_clause(&self, name: &str, concrete_types: &[Type]) -> Result<(), String> {
+        if let Some(parent) = self.declarations_parent() {
+            return parent.borrow().check_where_clause(name, concrete_types);
+        }
+
+        let Some((generics, where_clause)) = self.where_clauses.get(name) else {
+            return Ok(());
+        };
+
+        for GenericConstraint {
+            generic,
+            constraints,
+        } in where_clause
+        {
+            let Some(concrete_type) = generics
+                .iter()
+                .position(|g| g == generic)
+                .and_then(|index| concrete_types.get(index))
+            else {
+                continue;
+            };
+
+            for constraint in constraints {
+                if !self.implements(concrete_type, &constraint.name()) {
+                    return Err(format!(
+                        "'{}' does not implement protocol '{}' required by '{}' of '{}'",
+                        concrete_type, constraint, generic, name
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Whether a member like `Point.x` may be accessed from the module. Members whose visibility
+    /// isn't recorded are visible everywhere.
+    pub fn is_visible_from(&self, member: &str, module_path: &[String]) -> bool {
+        match self.declarations_parent() {
+            Some(parent) => parent.borrow().is_visible_from(member, module_path),
+            None => self
+                .visibilities
+                .get(member)
+                .is_none_or(|visibility| visibility.is_visible_from(module_path)),
+        }
+    }
+
+    pub fn check_visibility(&self, member: &str) -> Result<(), String> {
+        if let Some(parent) = self.declarations_parent() {
+            return parent.borrow().check_visibility(member);
+        }
+
+        let Some(visibility) = self.visibilities.get(member) else {
+            return Ok(());
+        };
+
+        if visibility.is_visible_from(&self.current_module) {
+            return Ok(());
+        }
+
+        Err(format!(
+            "'{}' is declared in {} and is not visible from {}",
+            member,
+            module_name(&visibility.module_path),
+            module_name(&self.current_module)
+        ))
     }
 
     pub fn add_type(&mut self, type_: Type) -> Result<(), String> {
-        if !self.allow_override_types && self.types.contains_key(&type_.to_key()) {
+        let symbol = self.interner.borrow_mut().intern(&type_.to_key());
+        let type_id = TypeId::of(&type_, &self.current_module());
+
+        // A type from an interface and a local one with the same name have to be the same type
+        if let (Some(type_id), Some(existing_id)) = (type_id, self.type_ids.get(&symbol)) {
+            if self.is_interface() || self.interface_types.contains(&symbol) {
+                if type_id == *existing_id {
+                    return Ok(());
+                }
+
+                return Err(format!(
+                    "Type {} in {} is not ABI compatible with the one already declared, as its id is {} instead of {}",
+                    type_.full_name(),
+                    module_name(&self.current_module()),
+                    type_id,
+                    existing_id
+                ));
+            }
+        }
+
+        let current_module = self.current_module();
+
+        // Another module can declare the same name, which is told apart by its module when used
+        if !self.allow_override_types
+            && self.types.contains_key(&symbol)
+            && self.type_modules.get(&symbol).unwrap_or(&current_module) == &current_module
+        {
             return Err(format!("Type {} already exists", type_.full_name()));
         }
 
-        self.types.insert(type_.to_key(), type_);
+        self.type_modules.insert(symbol, current_module);
+
+        if let Some(type_id) = type_id {
+            self.type_ids.insert(symbol, type_id);
+
+            if self.is_interface() {
+                self.interface_types.insert(symbol);
+            }
+        }
+
+        self.types.insert(symbol, type_);
+        Ok(())
+    }
+
+    /// The id of the declared type `key` refers to
+    pub fn get_type_id<K: ToKey>(&self, key: K) -> Option<TypeId> {
+        let symbol = self.interner.borrow().get(&key.to_key())?;
+        self.get_type_id_by_symbol(symbol)
+    }
+
+    fn get_type_id_by_symbol(&self, symbol: Symbol) -> Option<TypeId> {
+        self.type_ids.get(&symbol).copied().or_else(|| {
+            self.parent
+                .as_ref()
+                .and_then(|p| p.borrow().get_type_id_by_symbol(symbol))
+        })
+    }
+
+    /// Move the types, static members, variables and imports of this environment into its
+    /// parent, replacing the ones the parent already has. An isolated environment replaces what
+    /// its parent keeps about the declarations too.
+    pub fn merge_into_parent(&self) -> Result<(), String> {
+        let Some(parent) = &self.parent else {
+            return Err("Environment has no parent to merge into".to_string());
+        };
+
+        let mut parent = parent.borrow_mut();
+
+        if self.keeps_declarations {
+            parent.modules = self.modules.clone();
+            parent.current_module = self.current_module.clone();
+            parent.visibilities = self.visibilities.clone();
+            parent.module_items = self.module_items.clone();
+            parent.module_item_types = self.module_item_types.clone();
+            parent.implementations = self.implementations.clone();
+            parent.where_clauses = self.where_clauses.clone();
+            parent.field_defaults = self.field_defaults.clone();
+            parent.mutable_fields = self.mutable_fields.clone();
+        }
+
+        parent.types.extend(self.types.clone());
+        parent.type_ids.extend(self.type_ids.clone());
+        parent.type_modules.extend(self.type_modules.clone());
+        parent.interface_types.extend(self.interface_types.clone());
+        parent.variables.extend(self.variables.clone());
+        parent.imports.extend(self.imports.clone());
+
+        // A variable declared again is only as mutable and initialized as its latest declaration
+        for name in self.variables.keys() {
+            if self.mutable_variables.contains(name) {
+                parent.mutable_variables.insert(name.clone());
+            } else {
+                parent.mutable_variables.remove(name);
+            }
+
+            if self.uninitialized_variables.contains(name) {
+                parent.uninitialized_variables.insert(name.clone());
+            } else {
+                parent.uninitialized_variables.remove(name);
+            }
+        }
+
+        for (type_annotation, members) in &self.static_members {
+            parent
+                .static_members
+                .entry(type_annotation.clone())
+                .or_default()
+                .extend(members.clone());
+        }
+
         Ok(())
     }
 
     pub fn add_variable(&mut self, name: String, type_: Type) {
+        self.mutable_variables.remove(&name);
+        self.uninitialized_variables.remove(&name);
         self.variables.insert(name, type_);
     }
 
+    /// Give a variable declared without a type the type of its first assignment, in the
+    /// environment which declared it
+    pub fn infer_variable_type(&mut self, name: &str, type_: Type) {
+        if let Some(variable) = self.variables.get_mut(name) {
+            *variable = type_;
+            return;
+        }
+
+        match &self.parent {
+            Some(parent) => parent.borrow_mut().infer_variable_type(name, type_),
+            None => {
+                self.variables.insert(name.to_owned(), type_);
+            }
+        }
+    }
+
+    /// Allow a variable declared in this environment to be assigned to again
+    pub fn set_mutable(&mut self, name: &str) {
+        self.mutable_variables.insert(name.to_owned());
+    }
+
+    pub fn is_mutable(&self, name: &str) -> bool {
+        if self.variables.contains_key(name) {
+            return self.mutable_variables.contains(name);
+        }
+
+        match &self.parent {
+            Some(parent) => parent.borrow().is_mutable(name),
+            None => false,
+        }
+    }
+
+    /// Mark a variable declared in this environment as having no initializer, which lets an
+    /// immutable variable be assigned its value later
+    pub fn set_uninitialized(&mut self, name: &str) {
+        self.uninitialized_variables.insert(name.to_owned());
+    }
+
+    pub fn is_uninitialized(&self, name: &str) -> bool {
+        if self.variables.contains_key(name) {
+            return self.uninitialized_variables.contains(name);
+        }
+
+        match &self.parent {
+            Some(parent) => parent.borrow().is_uninitialized(name),
+            None => false,
+        }
+    }
+
     pub fn add_static_member(
         &mut self,
         type_annotation: TypeAnnotation,
         name: String,
         member_type: Type,
     ) -> Result<(), String> {
+        let type_annotation = type_annotation.canonicalize(self);
+
         if let Some(members) = self.static_members.get_mut(&type_annotation) {
             if !self.allow_override_types && members.contains_key(&name) {
                 return Err(format!(
@@ -172,11 +1136,18 @@ impl TypeEnvironment {
         for constraint in constraints {
             let constraint_type = self.get_type_from_annotation(constraint)?;
             let Some(generic_type) = self.get_type(type_name) else {
-                return Err(format!("Type {} not found", type_name));
+                return Err(format!(
+                    "Type {} not found{}",
+                    type_name,
+                    did_you_mean(self.suggest_type(type_name))
+                ));
             };
 
             let generic_annotation = generic_type.type_annotation();
 
+            // The generic is the Self of every protocol it is constrained to
+            let substitutions = HashMap::from([("Self".to_owned(), generic_type)]);
+
             if let Type::Protocol(Protocol { functions, .. }) = constraint_type {
                 for (function_identifier, function_type) in functions {
                     let name = function_identifier.name();
@@ -184,7 +1155,7 @@ impl TypeEnvironment {
                     self.add_static_member(
                         generic_annotation.clone(),
                         name.to_owned(),
-                        function_type,
+                        statements::substitute(&function_type, &substitutions),
                     )?;
                 }
             }
@@ -194,10 +1165,67 @@ impl TypeEnvironment {
     }
 
     pub fn get_type<K: ToKey>(&self, key: K) -> Option<Type> {
-        self.types
-            .get(&key.to_key())
-            .cloned()
-            .or_else(|| self.parent.as_ref().and_then(|p| p.borrow().get_type(key)))
+        // A key which was never interned can't belong to any type
+        let symbol = self.interner.borrow().get(&key.to_key())?;
+        self.get_type_by_symbol(symbol)
+    }
+
+    /// A generic function, which is keyed by its name and number of generics, found by its name
+    /// alone
+    pub fn get_generic_function(&self, name: &str) -> Option<Type> {
+        let local = self.types.values().find(|type_| {
+            matches!(
+                type_,
+                Type::Function(super::Function {
+                    identifier: Some(TypeIdentifier::GenericType(function_name, _)),
+                    ..
+                }) if function_name == name
+            )
+        });
+
+        local.cloned().or_else(|| {
+            self.parent
+                .as_ref()
+                .and_then(|p| p.borrow().get_generic_function(name))
+        })
+    }
+
+    /// The names of the protocols declaring a function called `name`, sorted
+    pub fn get_protocols_with_function(&self, name: &str) -> Vec<String> {
+        let mut protocols = match &self.parent {
+            Some(parent) => parent.borrow().get_protocols_with_function(name),
+            None => vec![],
+        };
+
+        for type_ in self.types.values() {
+            if let Type::Protocol(Protocol {
+                type_identifier,
+                functions,
+                ..
+            }) = type_
+            {
+                if functions.iter().any(|(f, _)| f.name() == name) {
+                    protocols.push(type_identifier.name().to_owned());
+                }
+            }
+        }
+
+        protocols.sort();
+        protocols.dedup();
+        protocols
+    }
+
+    pub fn get_type_by_symbol(&self, symbol: Symbol) -> Option<Type> {
+        self.types.get(&symbol).cloned().or_else(|| {
+            self.parent
+                .as_ref()
+                .and_then(|p| p.borrow().get_type_by_symbol(symbol))
+        })
+    }
+
+    fn get_local_type(&self, key: &str) -> Option<&Type> {
+        let symbol = self.interner.borrow().get(key)?;
+        self.types.get(&symbol)
     }
 
     pub fn get_type_from_annotation(
@@ -206,42 +1234,75 @@ impl TypeEnvironment {
     ) -> Result<Type, String> {
         match type_annotation {
             TypeAnnotation::Type(type_name) => {
-                if let Some(t) = self.types.get(type_name) {
+                if let Some(t) = self.get_local_type(type_name) {
                     Ok(t.clone())
                 } else if type_name.contains("::") {
                     let parts: Vec<&str> = type_name.split("::").collect();
                     let type_name = parts[0];
                     let variant_name = parts[1];
 
-                    let Some(t) = self.types.get(
+                    let Some(t) = self.get_local_type(
                         &TypeIdentifier::MemberType(
                             Box::new(TypeIdentifier::Type(type_name.to_string())),
                             variant_name.to_string(),
                         )
                         .to_key(),
                     ) else {
-                        return Err(format!("Type {} not found", type_name));
+                        return Err(format!(
+                            "Type {} not found{}",
+                            type_name,
+                            did_you_mean(self.suggest_type(type_name))
+                        ));
                     };
 
                     Ok(t.clone())
                 } else if let Some(parent) = &self.parent {
                     parent.borrow().get_type_from_annotation(type_annotation)
                 } else {
-                    Err(format!("Type {} not found", type_name))
+                    Err(format!(
+                        "Type {} not found{}",
+                        type_name,
+                        did_you_mean(self.suggest_type(type_name))
+                    ))
                 }
             }
+            TypeAnnotation::ConcreteType(type_name, concrete_types)
+                if type_name == "Option" && concrete_types.len() == 1 =>
+            {
+                Ok(Type::option_of(
+                    self.get_type_from_annotation(&concrete_types[0])?,
+                ))
+            }
+            TypeAnnotation::ConcreteType(type_name, concrete_types)
+                if type_name == "Result" && concrete_types.len() == 2 =>
+            {
+                Ok(Type::result_of(
+                    self.get_type_from_annotation(&concrete_types[0])?,
+                    self.get_type_from_annotation(&concrete_types[1])?,
+                ))
+            }
             TypeAnnotation::ConcreteType(type_name, concrete_types) => {
-                if let Some((_, t)) = self.types.iter().find(|(k, _)| {
-                    **k == TypeIdentifier::GenericType(type_name.clone(), vec![]).to_key()
-                }) {
-                    t.clone_with_concrete_types(
+                if let Some(t) = self.get_local_type(
+                    &TypeIdentifier::ConcreteType(type_name.clone(), concrete_types.clone())
+                        .to_key(),
+                ) {
+                    self.enter_instantiation(type_annotation)?;
+
+                    let type_ = t.clone_with_concrete_types(
                         concrete_types.clone(),
                         Rc::new(RefCell::new(self.clone())),
-                    )
+                    );
+
+                    self.exit_instantiation();
+                    type_
                 } else if let Some(parent) = &self.parent {
                     parent.borrow().get_type_from_annotation(type_annotation)
                 } else {
-                    Err(format!("Type {} not found", type_name))
+                    Err(format!(
+                        "Type {} not found{}",
+                        type_name,
+                        did_you_mean(self.suggest_type(type_name))
+                    ))
                 }
             }
             TypeAnnotation::Array(type_annotation) => self
@@ -259,6 +1320,17 @@ impl TypeEnvironment {
 
                 Ok(Type::Tuple(types))
             }
+            TypeAnnotation::Record(fields) => {
+                let fields = fields
+                    .iter()
+                    .map(|(name, annotation)| {
+                        self.get_type_from_annotation(annotation)
+                            .map(|t| (name.clone(), t))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                Ok(Type::Record(fields))
+            }
             TypeAnnotation::Function(param_type_annotation, return_type_annotation) => {
                 let param_type = param_type_annotation
                     .as_ref()
@@ -319,19 +1391,86 @@ impl TypeEnvironment {
         }
     }
 
-    pub fn get_types(&self) -> &HashMap<String, Type> {
-        &self.types
+    pub fn get_types(&self) -> HashMap<String, Type> {
+        let interner = self.interner.borrow();
+
+        self.types
+            .iter()
+            .map(|(symbol, type_)| (interner.resolve(*symbol).to_string(), type_.clone()))
+            .collect()
     }
 
     pub fn get_variables(&self) -> &HashMap<String, Type> {
         &self.variables
     }
 
+    /// Suggest the variable or function in scope which `name` is most likely a typo of
+    pub fn suggest_variable(&self, name: &str) -> Option<String> {
+        let mut names = self.variable_names();
+        names.extend(self.type_names());
+
+        suggestion::closest(name, names.iter().map(String::as_str)).map(str::to_owned)
+    }
+
+    /// Suggest the type in scope which `name` is most likely a typo of
+    pub fn suggest_type(&self, name: &str) -> Option<String> {
+        let names = self.type_names();
+        suggestion::closest(name, names.iter().map(String::as_str)).map(str::to_owned)
+    }
+
+    fn variable_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.variables.keys().cloned().collect();
+
+        if let Some(parent) = &self.parent {
+            names.extend(parent.borrow().variable_names());
+        }
+
+        names
+    }
+
+    /// The names of the types in scope, leaving out the keys of enum members and generic types
+    fn type_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .get_types()
+            .into_keys()
+            .filter(|name| name.chars().all(|c| c.is_alphanumeric() || c == '_'))
+            .collect();
+
+        if let Some(parent) = &self.parent {
+            names.extend(parent.borrow().type_names());
+        }
+
+        names
+    }
+
+    /// Every static member of the type, including the ones added in enclosing environments
+    pub fn get_static_members(&self, type_annotation: &TypeAnnotation) -> Vec<(String, Type)> {
+        let mut members: Vec<(String, Type)> = self
+            .static_members
+            .get(&type_annotation.canonicalize(self))
+            .into_iter()
+            .flatten()
+            .map(|(name, member_type)| (name.clone(), member_type.clone()))
+            .collect();
+
+        if let Some(parent) = &self.parent {
+            for (name, member_type) in parent.borrow().get_static_members(type_annotation) {
+                if !members.iter().any(|(member, _)| *member == name) {
+                    members.push((name, member_type));
+                }
+            }
+        }
+
+        members
+    }
+
     pub fn get_static_member<K: ToKey>(
         &self,
         type_annotation: TypeAnnotation,
         member_key: K,
     ) -> Option<Type> {
+        let type_annotation = type_annotation.canonicalize(self);
+
         self.static_members
             .get(&type_annotation)
             .and_then(|members| members.get(&member_key.to_key()))
@@ -354,11 +1493,20 @@ impl TypeEnvironment {
             })
     }
     pub fn lookup_type(&self, type_: &Type) -> bool {
+        // Option and Result are built in, so they are known whenever what they hold is
+        if let Some(inner) = type_.option_inner() {
+            return self.lookup_type(inner);
+        }
+
+        if let Some((value, error)) = type_.result_types() {
+            return self.lookup_type(value) && self.lookup_type(error);
+        }
+
         self.types.values().any(|t| t == type_)
             || self
                 .parent
                 .as_ref()
-                .map_or(false, |parent| parent.borrow().lookup_type(type_))
+                .is_some_and(|parent| parent.borrow().lookup_type(type_))
     }
 
     pub fn lookup_type_str(&self, type_name: &str) -> bool {
@@ -366,6 +1514,6 @@ impl TypeEnvironment {
             || self
                 .parent
                 .as_ref()
-                .map_or(false, |parent| parent.borrow().lookup_type_str(type_name))
+                .is_some_and(|parent| parent.borrow().lookup_type_str(type_name))
     }
 }
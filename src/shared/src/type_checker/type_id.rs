@@ -0,0 +1,101 @@
+use std::fmt::Display;
+
+use super::{FullName, Type};
+
+/// The identity of a declared type, which is the same wherever the same declaration is checked.
+///
+/// It is a hash of the module the type is declared in, its name and its structure, so a type
+/// loaded from an interface can be matched against one declared locally under the same name. Two
+/// declarations with the same name and different fields have different ids.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TypeId(u64);
+
+impl TypeId {
+    /// The id of `type_` declared in `module_path`, if it is a type which can be declared
+    pub fn of(type_: &Type, module_path: &[String]) -> Option<TypeId> {
+        let structure = structure(type_)?;
+        let mut hash = Fnv::default();
+
+        for segment in module_path {
+            hash.write(segment);
+        }
+
+        hash.write(&type_.to_string());
+        hash.write(&structure);
+        Some(TypeId(hash.0))
+    }
+}
+
+impl Display for TypeId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:016x}", self.0)
+    }
+}
+
+/// What the declaration of a type says about how its values are laid out
+fn structure(type_: &Type) -> Option<String> {
+    let fields = |fields: &[super::StructField]| {
+        fields
+            .iter()
+            .map(|field| format!("{}: {}", field.field_name, field.field_type.full_name()))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    match type_ {
+        Type::Struct(struct_) => Some(fields(&struct_.fields)),
+        Type::Enum(enum_) => {
+            // The members are in a map, so they are sorted to not depend on its order
+            let mut members = enum_
+                .members
+                .values()
+                .map(|member| member.full_name())
+                .collect::<Vec<_>>();
+
+            members.sort();
+            Some(format!(
+                "{} | {}",
+                fields(&enum_.shared_fields),
+                members.join(" | ")
+            ))
+        }
+        Type::EnumMember(member) => Some(fields(&member.fields)),
+        Type::Protocol(protocol) => Some(
+            protocol
+                .associated_types
+                .iter()
+                .map(|associated_type| associated_type.to_string())
+                .chain(
+                    protocol
+                        .functions
+                        .iter()
+                        .map(|(name, type_)| format!("{}: {}", name, type_.full_name())),
+                )
+                .collect::<Vec<_>>()
+                .join(", "),
+        ),
+        // The names of these types already spell out what they stand for
+        Type::Union(_) | Type::TypeAlias(_) | Type::Newtype(_) => Some(type_.full_name()),
+        _ => None,
+    }
+}
+
+/// A 64 bit FNV-1a hash, which unlike the hasher of the standard library is the same in every
+/// build
+struct Fnv(u64);
+
+impl Default for Fnv {
+    fn default() -> Self {
+        Fnv(0xcbf29ce484222325)
+    }
+}
+
+impl Fnv {
+    fn write(&mut self, text: &str) {
+        // A separator after each part keeps `ab` + `c` apart from `a` + `bc`
+        for byte in text.bytes().chain([0]) {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(0x100000001b3);
+        }
+    }
+}
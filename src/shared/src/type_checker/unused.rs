@@ -0,0 +1,252 @@
+use crate::types::TypeIdentifier;
+
+use super::{
+    ast::{Block, Member, Typed, TypedExpression, TypedStatement},
+    decision_tree::Decision,
+    visit::{self, TypedVisitor},
+    Type,
+};
+
+/// Collect warnings for `let` bindings which are never read, unless their name starts with `_`,
+/// and for values of expression statements which are thrown away without a `;`.
+/// Generics of structs, enums and functions which their fields or signature never use are warned
+/// about as well. Each warning comes with its category, `unused_variable`, `unused_result` or
+/// `unused_generic`.
+pub fn find_unused(program: &TypedStatement) -> Vec<(&'static str, String)> {
+    let mut checker = Unused::default();
+    checker.visit_statement(program);
+    checker.warnings
+}
+
+#[derive(Default)]
+struct Unused {
+    /// The bindings of every scope being checked and whether they were read, innermost last
+    scopes: Vec<Vec<(String, bool)>>,
+    /// The functions being checked, innermost last
+    functions: Vec<String>,
+    warnings: Vec<(&'static str, String)>,
+}
+
+impl Unused {
+    fn location(&self) -> String {
+        match self.functions.last() {
+            Some(function) => format!("function '{}'", function),
+            None => "the top level".to_string(),
+        }
+    }
+
+    /// Check `f` in a new scope. Parameters shadow outer bindings but are never warned about.
+    fn with_scope(&mut self, parameters: &[&str], f: impl FnOnce(&mut Self)) {
+        self.scopes.push(
+            parameters
+                .iter()
+                .map(|parameter| (parameter.to_string(), true))
+                .collect(),
+        );
+
+        f(self);
+
+        let location = self.location();
+
+        for (name, read) in self.scopes.pop().unwrap_or_default() {
+            if !read && !name.starts_with('_') {
+                self.warnings.push((
+                    "unused_variable",
+                    format!(
+                        "Variable '{}' in {} is never read, prefix it with '_' if that is intended",
+                        name, location
+                    ),
+                ));
+            }
+        }
+    }
+
+    /// Warn about each generic of a declaration which none of the types it is declared with use
+    fn check_generics(
+        &mut self,
+        kind: &str,
+        identifier: &TypeIdentifier,
+        types: &[&Type],
+        used_in: &str,
+    ) {
+        let TypeIdentifier::GenericType(name, generics) = identifier else {
+            return;
+        };
+
+        for generic in generics {
+            if !types.iter().any(|t| t.mentions(generic)) {
+                self.warnings.push((
+                    "unused_generic",
+                    format!(
+                        "Generic type '{}' of {} '{}' is never used in its {}",
+                        generic, kind, name, used_in
+                    ),
+                ));
+            }
+        }
+    }
+
+    fn check_statements(&mut self, statements: &[TypedStatement]) {
+        let Some((_, discarded)) = statements.split_last() else {
+            return;
+        };
+
+        for statement in discarded {
+            if let TypedStatement::Expression(expression) = statement {
+                self.check_discarded(expression);
+            }
+        }
+
+        for statement in statements {
+            self.visit_statement(statement);
+        }
+    }
+
+    fn check_discarded(&mut self, expression: &TypedExpression) {
+        if matches!(
+            expression,
+            TypedExpression::VariableDeclaration { .. } | TypedExpression::Assignment { .. }
+        ) {
+            return;
+        }
+
+        let type_ = expression.get_type();
+
+        if matches!(type_, Type::Void | Type::Unit | Type::Unknown) {
+            return;
+        }
+
+        self.warnings.push((
+            "unused_result",
+            format!(
+                "Result of type {} in {} is unused, end the expression with ';' to discard it",
+                type_,
+                self.location()
+            ),
+        ));
+    }
+}
+
+impl TypedVisitor for Unused {
+    fn visit_statement(&mut self, statement: &TypedStatement) {
+        match statement {
+            TypedStatement::Program { statements } => {
+                self.with_scope(&[], |checker| checker.check_statements(statements));
+            }
+            TypedStatement::StructDeclaration {
+                type_identifier,
+                fields,
+                ..
+            } => {
+                let field_types: Vec<&Type> = fields.iter().map(|f| &f.type_).collect();
+                self.check_generics("struct", type_identifier, &field_types, "fields");
+            }
+            TypedStatement::EnumDeclaration {
+                type_identifier,
+                shared_fields,
+                members,
+                ..
+            } => {
+                let field_types: Vec<&Type> = shared_fields
+                    .iter()
+                    .map(|f| &f.type_)
+                    .chain(
+                        members
+                            .iter()
+                            .flat_map(|m| m.fields.iter().map(|f| &f.type_)),
+                    )
+                    .collect();
+
+                self.check_generics("enum", type_identifier, &field_types, "fields");
+            }
+            TypedStatement::FunctionDeclaration {
+                identifier,
+                param,
+                type_,
+                ..
+            } => {
+                self.check_generics("function", identifier, &[type_], "signature");
+
+                let param = param.as_ref().map(|param| param.identifier.as_str());
+
+                self.functions.push(identifier.name().to_string());
+                self.with_scope(param.as_slice(), |checker| {
+                    visit::walk_statement(checker, statement)
+                });
+                self.functions.pop();
+            }
+            _ => visit::walk_statement(self, statement),
+        }
+    }
+
+    fn visit_expression(&mut self, expression: &TypedExpression) {
+        match expression {
+            TypedExpression::VariableDeclaration { pattern, .. } => {
+                visit::walk_expression(self, expression);
+
+                if let Some(scope) = self.scopes.last_mut() {
+                    for name in pattern.variables() {
+                        scope.push((name.to_string(), false));
+                    }
+                }
+            }
+            // Assigning to a variable doesn't read it, but reaching a field through it does
+            TypedExpression::Assignment {
+                member,
+                initializer,
+                ..
+            } => {
+                self.visit_expression(initializer);
+
+                if let Member::MemberAccess { object, .. } = member.as_ref() {
+                    self.visit_expression(object);
+                }
+            }
+            TypedExpression::Closure { param, .. } => {
+                let param = param.as_ref().map(|param| param.identifier.as_str());
+
+                self.with_scope(param.as_slice(), |checker| {
+                    visit::walk_expression(checker, expression)
+                });
+            }
+            TypedExpression::For { identifier, .. } => {
+                self.with_scope(&[identifier.as_str()], |checker| {
+                    visit::walk_expression(checker, expression)
+                });
+            }
+            _ => visit::walk_expression(self, expression),
+        }
+    }
+
+    fn visit_member(&mut self, member: &Member) {
+        match member {
+            Member::Identifier { symbol, .. } => {
+                let binding = self
+                    .scopes
+                    .iter_mut()
+                    .rev()
+                    .find_map(|scope| scope.iter_mut().rev().find(|(name, _)| name == symbol));
+
+                if let Some((_, read)) = binding {
+                    *read = true;
+                }
+            }
+            // Only the object is a variable, the member is one of its fields or methods
+            Member::MemberAccess { object, .. } => self.visit_expression(object),
+            Member::StaticMemberAccess { .. } => {}
+        }
+    }
+
+    fn visit_block(&mut self, block: &Block) {
+        self.with_scope(&[], |checker| checker.check_statements(&block.statements));
+    }
+
+    fn visit_decision(&mut self, decision: &Decision) {
+        match decision {
+            Decision::Success { .. } => {
+                self.with_scope(&[], |checker| visit::walk_decision(checker, decision));
+            }
+            _ => visit::walk_decision(self, decision),
+        }
+    }
+}
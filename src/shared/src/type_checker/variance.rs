@@ -0,0 +1,117 @@
+use crate::types::{GenericType, TypeIdentifier};
+
+use super::{type_equals, Function, Struct, Type, TypeEnvironment};
+
+/// How a generic struct given one type relates to the same struct given another
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Variance {
+    /// `Reader<Sub>` can be used as a `Reader<Super>`, as the generic is only ever read
+    Covariant,
+    /// `Reader<Sub>` and `Reader<Super>` are unrelated, as the generic is also taken in
+    Invariant,
+}
+
+/// The variance of each generic of a struct, in the order they are declared in.
+///
+/// A generic is covariant when the fields only hold it directly, in an array or tuple, or as the
+/// return type of a function. Anywhere else it is invariant, which includes being the parameter of
+/// a function field and being given to another generic type.
+pub fn variances(struct_: &Struct) -> Vec<(GenericType, Variance)> {
+    let TypeIdentifier::GenericType(_, generics) = &struct_.type_identifier else {
+        return vec![];
+    };
+
+    generics
+        .iter()
+        .map(|generic| {
+            let covariant = struct_
+                .fields
+                .iter()
+                .all(|field| is_covariant_in(&field.field_type, generic));
+
+            let variance = match covariant {
+                true => Variance::Covariant,
+                false => Variance::Invariant,
+            };
+
+            (generic.clone(), variance)
+        })
+        .collect()
+}
+
+fn is_covariant_in(type_: &Type, generic: &GenericType) -> bool {
+    match type_ {
+        Type::Generic(_) => true,
+        Type::Array(element) => is_covariant_in(element, generic),
+        Type::Tuple(elements) => elements.iter().all(|e| is_covariant_in(e, generic)),
+        Type::Function(Function {
+            param, return_type, ..
+        }) => {
+            param.as_ref().is_none_or(|p| !p.type_.mentions(generic))
+                && is_covariant_in(return_type, generic)
+        }
+        other => !other.mentions(generic),
+    }
+}
+
+/// Check if a value of the generic struct `value` can be assigned where `target`, the same struct
+/// given other types, is expected. Each covariant generic of `value` has to be assignable to the
+/// one of `target`, and each invariant generic has to be the same type.
+///
+/// Types which aren't the same generic struct are never assignable this way, and are left to be
+/// compared by `type_equals`.
+pub(super) fn check_assignable(
+    target: &Type,
+    value: &Type,
+    type_environment: &TypeEnvironment,
+) -> Result<bool, String> {
+    let (
+        Type::Struct(Struct {
+            type_identifier: TypeIdentifier::ConcreteType(name, target_generics),
+            ..
+        }),
+        Type::Struct(Struct {
+            type_identifier: TypeIdentifier::ConcreteType(value_name, value_generics),
+            ..
+        }),
+    ) = (target, value)
+    else {
+        return Ok(false);
+    };
+
+    if name != value_name || target_generics.len() != value_generics.len() {
+        return Ok(false);
+    }
+
+    let Some(Type::Struct(generic_struct)) = type_environment.get_type(
+        TypeIdentifier::ConcreteType(name.clone(), target_generics.clone()),
+    ) else {
+        return Ok(false);
+    };
+
+    let generics = variances(&generic_struct)
+        .into_iter()
+        .zip(target_generics.iter().zip(value_generics));
+
+    for ((generic, variance), (target_generic, value_generic)) in generics {
+        let target_type = type_environment.get_type_from_annotation(target_generic)?;
+        let value_type = type_environment.get_type_from_annotation(value_generic)?;
+
+        let widens = type_equals(&target_type, &value_type);
+        let same = widens && type_equals(&value_type, &target_type);
+
+        match variance {
+            Variance::Covariant if widens => continue,
+            Variance::Invariant if same => continue,
+            Variance::Invariant if widens => {
+                return Err(format!(
+                    "{} cannot be used as {}, because '{}' of '{}' is invariant",
+                    value, target, generic, name
+                ))
+            }
+            _ => return Ok(false),
+        }
+    }
+
+    Ok(true)
+}
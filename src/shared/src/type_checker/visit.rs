@@ -0,0 +1,271 @@
+use super::{
+    ast::{Block, EnumMemberFieldInitializers, Literal, Member, TypedExpression, TypedStatement},
+    decision_tree::Decision,
+};
+
+/// Read-only traversal of the typed AST.
+///
+/// Override the methods for the nodes you care about.
+/// Every method defaults to the matching `walk_*` function, which visits the children of the node.
+/// Call the `walk_*` function from an overridden method to keep recursing.
+pub trait TypedVisitor {
+    fn visit_statement(&mut self, statement: &TypedStatement) {
+        walk_statement(self, statement);
+    }
+
+    fn visit_expression(&mut self, expression: &TypedExpression) {
+        walk_expression(self, expression);
+    }
+
+    fn visit_member(&mut self, member: &Member) {
+        walk_member(self, member);
+    }
+
+    fn visit_literal(&mut self, literal: &Literal) {
+        walk_literal(self, literal);
+    }
+
+    fn visit_block(&mut self, block: &Block) {
+        walk_block(self, block);
+    }
+
+    fn visit_decision(&mut self, decision: &Decision) {
+        walk_decision(self, decision);
+    }
+}
+
+pub fn walk_statement<V: TypedVisitor + ?Sized>(visitor: &mut V, statement: &TypedStatement) {
+    match statement {
+        TypedStatement::Program { statements } => {
+            for statement in statements {
+                visitor.visit_statement(statement);
+            }
+        }
+        TypedStatement::ProtocolDeclaration { functions, .. } => {
+            for function in functions {
+                visitor.visit_statement(function);
+            }
+        }
+        TypedStatement::ImplementationDeclaration { functions, .. } => {
+            for (_, function) in functions {
+                visitor.visit_statement(function);
+            }
+        }
+        TypedStatement::FunctionDeclaration { body, .. } => {
+            if let Some(body) = body {
+                visitor.visit_expression(body);
+            }
+        }
+        TypedStatement::Semi(statement) => visitor.visit_statement(statement),
+        TypedStatement::Expression(expression) => visitor.visit_expression(expression),
+        TypedStatement::None
+        | TypedStatement::ModuleDeclaration { .. }
+        | TypedStatement::Use { .. }
+        | TypedStatement::StructDeclaration { .. }
+        | TypedStatement::EnumDeclaration { .. }
+        | TypedStatement::UnionDeclaration { .. }
+        | TypedStatement::TypeAliasDeclaration { .. }
+        | TypedStatement::NewtypeDeclaration { .. } => {}
+    }
+}
+
+pub fn walk_expression<V: TypedVisitor + ?Sized>(visitor: &mut V, expression: &TypedExpression) {
+    match expression {
+        TypedExpression::VariableDeclaration { initializer, .. } => {
+            if let Some(initializer) = initializer {
+                visitor.visit_expression(initializer);
+            }
+        }
+        TypedExpression::If {
+            condition,
+            true_expression,
+            false_expression,
+            ..
+        } => {
+            visitor.visit_expression(condition);
+            visitor.visit_expression(true_expression);
+
+            if let Some(false_expression) = false_expression {
+                visitor.visit_expression(false_expression);
+            }
+        }
+        // The arms only hold untyped expressions, their typed bodies live in the decision tree
+        TypedExpression::Match {
+            expression,
+            decision_tree,
+            ..
+        } => {
+            visitor.visit_expression(expression);
+            visitor.visit_decision(decision_tree);
+        }
+        TypedExpression::Assignment {
+            member,
+            initializer,
+            ..
+        } => {
+            visitor.visit_member(member);
+            visitor.visit_expression(initializer);
+        }
+        TypedExpression::Member(member) => visitor.visit_member(member),
+        TypedExpression::Literal(literal, _) => visitor.visit_literal(literal),
+        TypedExpression::Tuple { elements, .. } => {
+            for element in elements {
+                visitor.visit_expression(element);
+            }
+        }
+        TypedExpression::Record { fields, .. } => {
+            for field in fields {
+                visitor.visit_expression(&field.initializer);
+            }
+        }
+        TypedExpression::Closure { body, .. } => visitor.visit_expression(body),
+        TypedExpression::Call {
+            callee, argument, ..
+        } => {
+            visitor.visit_expression(callee);
+
+            if let Some(argument) = argument {
+                visitor.visit_expression(argument);
+            }
+        }
+        TypedExpression::Index {
+            callee, argument, ..
+        } => {
+            visitor.visit_expression(callee);
+            visitor.visit_expression(argument);
+        }
+        TypedExpression::Unary { expression, .. } => visitor.visit_expression(expression),
+        TypedExpression::Binary { left, right, .. } => {
+            visitor.visit_expression(left);
+            visitor.visit_expression(right);
+        }
+        TypedExpression::Cast { expression, .. } => visitor.visit_expression(expression),
+        TypedExpression::Block(block) => visitor.visit_block(block),
+        #[cfg(feature = "interpreter")]
+        TypedExpression::Print { value } => visitor.visit_expression(value),
+        #[cfg(feature = "interpreter")]
+        TypedExpression::Drop { .. } => {}
+        TypedExpression::Loop { body, .. } => visitor.visit_expression(body),
+        TypedExpression::While {
+            condition,
+            body,
+            else_body,
+            ..
+        } => {
+            visitor.visit_expression(condition);
+            visitor.visit_expression(body);
+
+            if let Some(else_body) = else_body {
+                visitor.visit_expression(else_body);
+            }
+        }
+        TypedExpression::For {
+            iterable,
+            next,
+            body,
+            else_body,
+            ..
+        } => {
+            visitor.visit_expression(iterable);
+
+            if let Some(next) = next {
+                visitor.visit_expression(next);
+            }
+
+            visitor.visit_expression(body);
+
+            if let Some(else_body) = else_body {
+                visitor.visit_expression(else_body);
+            }
+        }
+        TypedExpression::Break(expression) | TypedExpression::Return(expression) => {
+            if let Some(expression) = expression {
+                visitor.visit_expression(expression);
+            }
+        }
+        TypedExpression::Continue => {}
+    }
+}
+
+pub fn walk_member<V: TypedVisitor + ?Sized>(visitor: &mut V, member: &Member) {
+    match member {
+        Member::Identifier { .. } => {}
+        Member::StaticMemberAccess { member, .. } => visitor.visit_member(member),
+        Member::MemberAccess { object, member, .. } => {
+            visitor.visit_expression(object);
+            visitor.visit_member(member);
+        }
+    }
+}
+
+pub fn walk_literal<V: TypedVisitor + ?Sized>(visitor: &mut V, literal: &Literal) {
+    match literal {
+        Literal::Array { values, .. } => {
+            for value in values {
+                visitor.visit_expression(value);
+            }
+        }
+        Literal::Struct {
+            field_initializers,
+            spread,
+            ..
+        } => {
+            for field_initializer in field_initializers {
+                visitor.visit_expression(&field_initializer.initializer);
+            }
+
+            if let Some(spread) = spread {
+                visitor.visit_expression(spread);
+            }
+        }
+        Literal::Enum {
+            field_initializers: EnumMemberFieldInitializers::Named(field_initializers),
+            ..
+        } => {
+            for initializer in field_initializers.values() {
+                visitor.visit_expression(initializer);
+            }
+        }
+        Literal::Void
+        | Literal::Unit
+        | Literal::Int(_)
+        | Literal::UInt(_)
+        | Literal::Float(_)
+        | Literal::String(_)
+        | Literal::Char(_)
+        | Literal::Bool(_)
+        | Literal::Enum { .. } => {}
+    }
+}
+
+pub fn walk_block<V: TypedVisitor + ?Sized>(visitor: &mut V, block: &Block) {
+    for statement in &block.statements {
+        visitor.visit_statement(statement);
+    }
+}
+
+pub fn walk_decision<V: TypedVisitor + ?Sized>(visitor: &mut V, decision: &Decision) {
+    match decision {
+        Decision::Success { expression, .. } => visitor.visit_expression(expression),
+        Decision::Failure { .. } => {}
+        Decision::Guard {
+            condition,
+            consequence,
+            alternative,
+            ..
+        } => {
+            visitor.visit_expression(condition);
+            visitor.visit_decision(consequence);
+            visitor.visit_decision(alternative);
+        }
+        Decision::Switch {
+            cases, fallback, ..
+        } => {
+            for case in cases {
+                visitor.visit_decision(&case.body);
+            }
+
+            visitor.visit_decision(fallback);
+        }
+    }
+}
@@ -3,7 +3,7 @@ use std::{fmt::Display, hash::Hash, ops::Deref};
 use crate::{
     lexer::token::{self, IdentifierType, IntLiteral, Keyword, TokenKind},
     parser::{cursor::Cursor, Literal},
-    type_checker::{Function, Type},
+    type_checker::{Function, Type, TypeAlias, TypeEnvironment},
 };
 
 pub trait ToKey {
@@ -35,6 +35,7 @@ pub enum TypeAnnotation {
     Array(Box<TypeAnnotation>),
     Literal(Box<Literal>),
     Tuple(Vec<TypeAnnotation>),
+    Record(Vec<(String, TypeAnnotation)>),
     Function(Option<Box<TypeAnnotation>>, Option<Box<TypeAnnotation>>),
 }
 
@@ -96,11 +97,15 @@ impl From<Type> for TypeAnnotation {
                 Some(Box::new(return_type.deref().clone().into())),
             ),
             Type::Tuple(e) => TypeAnnotation::Tuple(e.into_iter().map(|t| t.into()).collect()),
+            Type::Record(fields) => {
+                TypeAnnotation::Record(fields.into_iter().map(|(n, t)| (n, t.into())).collect())
+            }
             Type::Struct(_) => todo!(),
             Type::Enum(_) => todo!(),
             Type::EnumMember(_) => todo!(),
             Type::Union(_) => todo!(),
             Type::TypeAlias(_) => todo!(),
+            Type::Newtype(n) => n.type_annotation(),
             Type::Protocol(_) => todo!(),
             Type::Literal { .. } => todo!(),
         }
@@ -132,6 +137,14 @@ impl ToKey for TypeAnnotation {
                     .collect::<Vec<String>>()
                     .join(", ")
             ),
+            TypeAnnotation::Record(fields) => format!(
+                "{{{}}}",
+                fields
+                    .iter()
+                    .map(|(n, t)| format!("{}: {}", n, t.to_key()))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
             TypeAnnotation::Function(name_annotation, param_annotation) => format!(
                 "fun({}){}",
                 name_annotation
@@ -184,6 +197,7 @@ impl TypeAnnotation {
                     .collect::<Vec<String>>()
                     .join(", ")
             ),
+            TypeAnnotation::Record(_) => self.to_string(),
             TypeAnnotation::Function(type_annotation, return_type_annotation) => {
                 format!(
                     "fun({}): {}",
@@ -201,6 +215,64 @@ impl TypeAnnotation {
     }
 }
 
+impl TypeAnnotation {
+    /// The form two annotations of the same type have in common, which they can be compared by.
+    ///
+    /// An alias of a single type is replaced by that type, a generic type given no types is a
+    /// plain type, and the fields of a record are put in order by name. Generic arguments keep
+    /// their order, as they are given to the generics by position.
+    pub fn canonicalize(&self, type_environment: &TypeEnvironment) -> TypeAnnotation {
+        match self {
+            TypeAnnotation::Type(name) => match type_environment.get_type(name) {
+                Some(Type::TypeAlias(TypeAlias {
+                    type_identifier: TypeIdentifier::Type(_),
+                    types,
+                })) if types.len() == 1 => {
+                    types[0].type_annotation().canonicalize(type_environment)
+                }
+                _ => self.clone(),
+            },
+            TypeAnnotation::ConcreteType(name, concrete_types) if concrete_types.is_empty() => {
+                TypeAnnotation::Type(name.clone()).canonicalize(type_environment)
+            }
+            TypeAnnotation::ConcreteType(name, concrete_types) => TypeAnnotation::ConcreteType(
+                name.clone(),
+                concrete_types
+                    .iter()
+                    .map(|t| t.canonicalize(type_environment))
+                    .collect(),
+            ),
+            TypeAnnotation::Array(element) => {
+                TypeAnnotation::Array(Box::new(element.canonicalize(type_environment)))
+            }
+            TypeAnnotation::Literal(_) => self.clone(),
+            TypeAnnotation::Tuple(elements) => TypeAnnotation::Tuple(
+                elements
+                    .iter()
+                    .map(|e| e.canonicalize(type_environment))
+                    .collect(),
+            ),
+            TypeAnnotation::Record(fields) => {
+                let mut fields: Vec<(String, TypeAnnotation)> = fields
+                    .iter()
+                    .map(|(n, t)| (n.clone(), t.canonicalize(type_environment)))
+                    .collect();
+
+                fields.sort_by(|(a, _), (b, _)| a.cmp(b));
+                TypeAnnotation::Record(fields)
+            }
+            TypeAnnotation::Function(param, return_type) => TypeAnnotation::Function(
+                param
+                    .as_ref()
+                    .map(|p| Box::new(p.canonicalize(type_environment))),
+                return_type
+                    .as_ref()
+                    .map(|r| Box::new(r.canonicalize(type_environment))),
+            ),
+        }
+    }
+}
+
 impl Display for TypeAnnotation {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -247,6 +319,15 @@ impl Display for TypeAnnotation {
                     .collect::<Vec<String>>()
                     .join(", ")
             ),
+            TypeAnnotation::Record(fields) => write!(
+                f,
+                "{{ {} }}",
+                fields
+                    .iter()
+                    .map(|(n, t)| format!("{}: {}", n, t))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
             TypeAnnotation::Function(type_annotation, return_type_annotation) => {
                 write!(
                     f,
@@ -450,6 +531,7 @@ pub(super) fn can_be_type_annotation(cursor: &Cursor) -> bool {
             can_be_type_annotation(&cloned_cursor)
         }
         TokenKind::OpenParen => true,
+        TokenKind::OpenBrace => true,
         TokenKind::Keyword(Keyword::Fun) => true,
         _ => false,
     }
@@ -458,6 +540,26 @@ pub(super) fn can_be_type_annotation(cursor: &Cursor) -> bool {
 pub(super) fn parse_type_annotation(
     cursor: &mut Cursor,
     allow_void: bool,
+) -> Result<TypeAnnotation, String> {
+    let mut type_annotation = parse_non_optional_type_annotation(cursor, allow_void)?;
+
+    // `T?` is sugar for `Option<T>`. A ?? or ?. after a type belongs to the surrounding expression
+    while cursor.first().kind == TokenKind::QuestionMark
+        && !matches!(
+            cursor.second().kind,
+            TokenKind::QuestionMark | TokenKind::Dot
+        )
+    {
+        cursor.bump()?; // Consume the ?
+        type_annotation = TypeAnnotation::ConcreteType("Option".to_string(), vec![type_annotation]);
+    }
+
+    Ok(type_annotation)
+}
+
+fn parse_non_optional_type_annotation(
+    cursor: &mut Cursor,
+    allow_void: bool,
 ) -> Result<TypeAnnotation, String> {
     match cursor.first().kind {
         TokenKind::Literal(token::Literal::Void) => {
@@ -583,6 +685,38 @@ pub(super) fn parse_type_annotation(
             cursor.bump()?; // Consume the )
             Ok(TypeAnnotation::Tuple(annotations))
         }
+        TokenKind::OpenBrace => {
+            cursor.bump()?; // Consume the {
+
+            let mut fields: Vec<(String, TypeAnnotation)> = vec![];
+            let mut has_comma = true;
+
+            while cursor.first().kind != TokenKind::CloseBrace {
+                if !has_comma {
+                    return Err(format!("Expected , but found {:?}", cursor.first().kind));
+                }
+
+                let TokenKind::Identifier(identifier) = cursor.bump()?.kind else {
+                    return Err(format!(
+                        "Expected field name but found {:?}",
+                        cursor.prev().kind
+                    ));
+                };
+
+                if fields.iter().any(|(n, _)| *n == identifier) {
+                    return Err(format!("Duplicate field {} in record type", identifier));
+                }
+
+                cursor.expect(TokenKind::Colon)?;
+                fields.push((identifier, parse_type_annotation(cursor, allow_void)?));
+
+                has_comma = cursor.first().kind == TokenKind::Comma;
+                cursor.optional_bump(TokenKind::Comma)?;
+            }
+
+            cursor.bump()?; // Consume the }
+            Ok(TypeAnnotation::Record(fields))
+        }
         TokenKind::Keyword(Keyword::Fun) => {
             cursor.bump()?; // Consume the fun
 
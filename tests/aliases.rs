@@ -0,0 +1,64 @@
+use shared::{
+    lexer::{self, aliases::Aliases},
+    parser,
+};
+
+#[test]
+fn function_keyword_alias_parses_like_the_keyword() {
+    // Arrange
+    let mut aliases = Aliases::new();
+    aliases.add("func", "fun").unwrap();
+
+    // Act
+    let tokens = lexer::tokenize_with_aliases("func add(a: Int): Int => a + 1", &aliases);
+    let ast = parser::create_ast(tokens.unwrap(), false);
+
+    // Assert
+    let canonical = lexer::tokenize("fun add(a: Int): Int => a + 1").unwrap();
+
+    assert_eq!(ast, parser::create_ast(canonical, false));
+}
+
+#[test]
+fn operator_alias_reads_as_the_operator() {
+    // Arrange
+    let mut aliases = Aliases::new();
+    aliases.add("not", "!").unwrap();
+
+    // Act
+    let tokens = lexer::tokenize_with_aliases("let a = not true;", &aliases);
+
+    // Assert
+    let canonical = lexer::tokenize("let a = ! true;").unwrap();
+    let kinds =
+        |tokens: Vec<lexer::token::Token>| tokens.into_iter().map(|t| t.kind).collect::<Vec<_>>();
+
+    assert_eq!(kinds(tokens.unwrap()), kinds(canonical));
+}
+
+#[test]
+fn alias_which_is_a_keyword_is_rejected() {
+    // Arrange
+    let mut aliases = Aliases::new();
+
+    // Act
+    let result = aliases.add("and", "&&");
+
+    // Assert
+    assert_eq!(result, Err("Alias 'and' is already a keyword".to_string()));
+}
+
+#[test]
+fn alias_of_an_identifier_is_rejected() {
+    // Arrange
+    let mut aliases = Aliases::new();
+
+    // Act
+    let result = aliases.add("func", "function");
+
+    // Assert
+    assert_eq!(
+        result,
+        Err("Alias 'func' has to stand for a keyword or operator, not 'function'".to_string())
+    );
+}
@@ -0,0 +1,127 @@
+mod common;
+
+use common::{evaluate, tokenize};
+
+use interpreter::{value::Number, Value};
+use shared::{
+    parser::{self, Expression, Literal, Statement},
+    types::ToKey,
+};
+
+#[test]
+fn repeat_fills_the_array_with_the_value() {
+    // Arrange
+    let input = "let zeros = [0; 2 + 1]; zeros";
+
+    // Act
+    let value = evaluate(input);
+
+    // Assert
+    assert_eq!(
+        value,
+        Ok(Value::Array(vec![Value::Number(Number::Int(0)); 3]))
+    );
+}
+
+#[test]
+fn repeat_length_must_be_a_constant() {
+    // Arrange
+    let input = "let n = 3; let zeros = [0; n]; zeros";
+
+    // Act
+    let value = evaluate(input);
+
+    // Assert
+    assert_eq!(value, Err("Array length n is not a constant".to_string()));
+}
+
+fn parse_literal(input: &str) -> Literal {
+    let Statement::Program { statements } = parser::create_ast(tokenize(input), false).unwrap()
+    else {
+        panic!("Expected a program");
+    };
+
+    let Some(Statement::Expression(Expression::Literal(literal, _))) = statements.first() else {
+        panic!("Expected a literal");
+    };
+
+    literal.clone()
+}
+
+#[test]
+fn repeat_is_displayed_as_written() {
+    // Arrange
+    let literal = parse_literal("[0; 2 + 1]");
+
+    // Act
+    let display = literal.to_string();
+
+    // Assert
+    assert_eq!(display, "[0; 2 + 1]");
+}
+
+#[test]
+fn repeat_is_keyed_by_its_value_and_length() {
+    // Arrange
+    let literal = parse_literal("[0; 2 + 1]");
+
+    // Act
+    let key = literal.to_key();
+
+    // Assert
+    assert_eq!(key, "[int:0; 2 + 1]");
+}
+
+#[test]
+fn half_open_slice_excludes_the_end() {
+    // Arrange
+    let input = "let values = [1, 2, 3, 4, 5]; values[1..4]";
+
+    // Act
+    let value = evaluate(input);
+
+    // Assert
+    assert_eq!(
+        value,
+        Ok(Value::Array(vec![
+            Value::Number(Number::Int(2)),
+            Value::Number(Number::Int(3)),
+            Value::Number(Number::Int(4)),
+        ]))
+    );
+}
+
+#[test]
+fn inclusive_slice_includes_the_end() {
+    // Arrange
+    let input = "let values = [1, 2, 3, 4, 5]; values[1..=4]";
+
+    // Act
+    let value = evaluate(input);
+
+    // Assert
+    assert_eq!(
+        value,
+        Ok(Value::Array(vec![
+            Value::Number(Number::Int(2)),
+            Value::Number(Number::Int(3)),
+            Value::Number(Number::Int(4)),
+            Value::Number(Number::Int(5)),
+        ]))
+    );
+}
+
+#[test]
+fn constant_slice_out_of_range_is_an_error() {
+    // Arrange
+    let input = "[1, 2, 3][1..=3]";
+
+    // Act
+    let value = evaluate(input);
+
+    // Assert
+    assert_eq!(
+        value,
+        Err("Slice 1..=3 is out of range for an array of length 3".to_string())
+    );
+}
@@ -1,11 +1,19 @@
 mod common;
 
-use common::{create_typed_ast, evaluate_expression, StatementExt, VecStatementExt};
+use common::{
+    create_typed_ast, evaluate_expression, tokenize, try_create_typed_ast, StatementExt,
+    VecStatementExt,
+};
 
 use interpreter::{value, Value};
-use shared::type_checker::{
-    ast::{Literal, Member, TypedExpression},
-    Type,
+use shared::{
+    display::{Indent, IndentDisplay},
+    parser,
+    span::Span,
+    type_checker::{
+        ast::{Literal, Member, TypedExpression},
+        Type,
+    },
 };
 
 use crate::common::create_env;
@@ -32,9 +40,10 @@ fn assignment_is_assignment() {
         TypedExpression::Assignment {
             member: Box::new(Member::Identifier {
                 symbol: "x".to_owned(),
-                type_: Type::Int
+                type_: Type::Int,
+                span: Span::default(),
             }),
-            initializer: Box::new(TypedExpression::Literal(Literal::Int(1))),
+            initializer: Box::new(TypedExpression::Literal(Literal::Int(1), Span::default())),
             type_: Type::Literal {
                 name: "1".to_owned(),
                 type_: Box::new(Type::Int)
@@ -91,3 +100,255 @@ fn assignment_returns_assigned_value() {
     // Assert
     assert_eq!(value, Value::Number(value::Number::Int(1)));
 }
+
+#[test]
+fn add_assignment_adds_to_the_variable() {
+    // Arrange
+    let input = r#"
+        let mut x = 1;
+        x += 2;
+        x
+    "#;
+
+    // Act
+    let value = evaluate_expression(input, create_env(), false);
+
+    // Assert
+    assert_eq!(value, Value::Number(value::Number::Int(3)));
+}
+
+#[test]
+fn multiply_assignment_multiplies_the_variable() {
+    // Arrange
+    let input = r#"
+        let mut x = 3;
+        x *= 4;
+        x
+    "#;
+
+    // Act
+    let value = evaluate_expression(input, create_env(), false);
+
+    // Assert
+    assert_eq!(value, Value::Number(value::Number::Int(12)));
+}
+
+#[test]
+fn compound_assignment_to_an_immutable_variable_is_an_error() {
+    // Arrange
+    let input = "let x = 1; x += 2;";
+
+    // Act
+    let typed_ast = try_create_typed_ast(input);
+
+    // Assert
+    assert_eq!(
+        typed_ast,
+        Err("Cannot assign to immutable variable 'x', declare it with 'let mut x'".to_string())
+    );
+}
+
+#[test]
+fn bitwise_compound_assignment_needs_an_integer() {
+    // Arrange
+    let input = "let mut b = true; b |= false;";
+
+    // Act
+    let typed_ast = try_create_typed_ast(input);
+
+    // Assert
+    assert_eq!(
+        typed_ast,
+        Err("Compound assignment |= needs an Int or UInt, found Bool".to_string())
+    );
+}
+
+#[test]
+fn compound_assignment_is_shown_in_indent_display() {
+    // Arrange
+    let ast = parser::create_ast(tokenize("let mut x = 1; x += 2;"), false).unwrap();
+
+    // Act
+    let display = ast.indent_display(&mut Indent::new());
+
+    // Assert
+    assert!(display.contains("operator: +="), "{}", display);
+}
+
+#[test]
+fn increment_and_decrement_change_the_variable_by_one() {
+    // Arrange
+    let input = r#"
+        let mut x = 1u;
+        x++;
+        x++;
+        x--;
+        x
+    "#;
+
+    // Act
+    let value = evaluate_expression(input, create_env(), false);
+
+    // Assert
+    assert_eq!(value, Value::Number(value::Number::UInt(2)));
+}
+
+#[test]
+fn increment_of_an_immutable_variable_is_an_error() {
+    // Arrange
+    let input = "let x = 1; x++;";
+
+    // Act
+    let typed_ast = try_create_typed_ast(input);
+
+    // Assert
+    assert_eq!(
+        typed_ast,
+        Err("Cannot assign to immutable variable 'x', declare it with 'let mut x'".to_string())
+    );
+}
+
+#[test]
+fn increment_of_a_non_number_is_an_error() {
+    // Arrange
+    let input = "let mut b = true; b++;";
+
+    // Act
+    let typed_ast = try_create_typed_ast(input);
+
+    // Assert
+    assert_eq!(
+        typed_ast,
+        Err("The arithmetic operator '+' cannot be applied to type Bool".to_string())
+    );
+}
+
+#[test]
+fn prefix_increment_suggests_compound_assignment() {
+    // Arrange
+    let input = "let mut x = 1; ++x;";
+
+    // Act
+    let ast = parser::create_ast(tokenize(input), false);
+
+    // Assert
+    assert_eq!(
+        ast,
+        Err("Prefix ++ is not supported, write x += 1 instead".to_string())
+    );
+}
+
+#[test]
+fn assignment_to_an_immutable_variable_is_an_error() {
+    // Arrange
+    let input = "let x = 1; x = 2;";
+
+    // Act
+    let typed_ast = try_create_typed_ast(input);
+
+    // Assert
+    assert_eq!(
+        typed_ast,
+        Err("Cannot assign to immutable variable 'x', declare it with 'let mut x'".to_string())
+    );
+}
+
+#[test]
+fn second_assignment_to_a_deferred_immutable_variable_is_an_error() {
+    // Arrange
+    let input = "let x: Int; x = 1; x = 2;";
+
+    // Act
+    let typed_ast = try_create_typed_ast(input);
+
+    // Assert
+    assert_eq!(
+        typed_ast,
+        Err(
+            "Cannot assign to immutable variable 'x' more than once, declare it with 'let mut x'"
+                .to_string()
+        )
+    );
+}
+
+#[test]
+fn assignment_to_a_deferred_immutable_variable_in_a_loop_is_an_error() {
+    // Arrange
+    let input = r#"
+        let x: Int;
+        let mut i = 0;
+        while i < 2 => {
+            x = i;
+            i += 1;
+        };
+    "#;
+
+    // Act
+    let typed_ast = try_create_typed_ast(input);
+
+    // Assert
+    assert_eq!(
+        typed_ast,
+        Err(
+            "Cannot assign to immutable variable 'x' in a loop, declare it with 'let mut x'"
+                .to_string()
+        )
+    );
+}
+
+#[test]
+fn deferred_immutable_variable_can_be_assigned_once_on_each_path() {
+    // Arrange
+    let input = r#"
+        let b = true;
+        let x: Int;
+        if b => { x = 1; } else => { x = 2; };
+        x
+    "#;
+
+    // Act
+    let value = evaluate_expression(input, create_env(), false);
+
+    // Assert
+    assert_eq!(value, Value::Number(value::Number::Int(1)));
+}
+
+#[test]
+fn assignment_to_an_immutable_field_is_an_error() {
+    // Arrange
+    let input = r#"
+        struct Point { x: Int, y: mut Int }
+        let mut p = Point { x: 1, y: 2 };
+        p.x = 3;
+    "#;
+
+    // Act
+    let typed_ast = try_create_typed_ast(input);
+
+    // Assert
+    assert_eq!(
+        typed_ast,
+        Err(
+            "Cannot assign to immutable field 'x', declare it as 'x: mut Int' in struct 'Point'"
+                .to_string()
+        )
+    );
+}
+
+#[test]
+fn assignment_through_a_mutable_chain_sets_the_field() {
+    // Arrange
+    let input = r#"
+        struct Inner { c: mut Int }
+        struct Outer { b: mut Inner }
+        let mut a = Outer { b: Inner { c: 1 } };
+        a.b.c = 5;
+        a.b.c
+    "#;
+
+    // Act
+    let value = evaluate_expression(input, create_env(), false);
+
+    // Assert
+    assert_eq!(value, Value::Number(value::Number::Int(5)));
+}
@@ -0,0 +1,143 @@
+mod common;
+
+use common::{tokenize, try_create_typed_ast};
+
+use shared::{
+    display::{Indent, IndentDisplay},
+    parser::{self, Attribute, Literal, Statement},
+};
+
+fn parse_statement(input: &str) -> Statement {
+    let Statement::Program { statements } = parser::create_ast(tokenize(input), false).unwrap()
+    else {
+        panic!("Expected a program");
+    };
+
+    statements.into_iter().next().unwrap()
+}
+
+#[test]
+fn attribute_without_arguments_is_parsed() {
+    // Arrange
+    let input = "@inline fun add(a: Int, b: Int): Int => a + b";
+
+    // Act
+    let statement = parse_statement(input);
+
+    // Assert
+    let Statement::FunctionDeclaration(function_declaration) = statement else {
+        panic!("Expected a function declaration");
+    };
+
+    assert_eq!(
+        function_declaration.attributes,
+        vec![Attribute {
+            name: "inline".to_string(),
+            args: vec![],
+        }]
+    );
+}
+
+#[test]
+fn attribute_with_arguments_is_parsed() {
+    // Arrange
+    let input = r#"
+    @deprecated("use Vector")
+    @layout(4, true)
+    struct Point { x: Int, y: Int }
+    "#;
+
+    // Act
+    let statement = parse_statement(input);
+
+    // Assert
+    let Statement::StructDeclaration(struct_declaration) = statement else {
+        panic!("Expected a struct declaration");
+    };
+
+    assert_eq!(
+        struct_declaration.attributes,
+        vec![
+            Attribute {
+                name: "deprecated".to_string(),
+                args: vec![Literal::String("use Vector".to_string())],
+            },
+            Attribute {
+                name: "layout".to_string(),
+                args: vec![Literal::Int(4), Literal::Bool(true)],
+            },
+        ]
+    );
+}
+
+#[test]
+fn attribute_on_expression_is_an_error() {
+    // Arrange
+    let input = "@inline let x = 5;";
+
+    // Act
+    let result = parser::create_ast(tokenize(input), false);
+
+    // Assert
+    assert!(result.is_err());
+}
+
+#[test]
+fn attributes_are_shown_in_indent_display() {
+    // Arrange
+    let statement = parse_statement(r#"@inline @deprecated("use sub") fun f(): Int => 1"#);
+
+    // Act
+    let display = statement.indent_display(&mut Indent::new());
+
+    // Assert
+    assert!(
+        display.contains("attributes: @inline @deprecated(use sub)"),
+        "{}",
+        display
+    );
+}
+
+#[test]
+fn known_attributes_with_valid_arguments_type_check() {
+    // Arrange
+    let input = r#"
+    @inline
+    fun add(a: Int, b: Int): Int => a + b
+    @deprecated("use add")
+    fun plus(a: Int, b: Int): Int => a + b
+    "#;
+
+    // Act
+    let result = try_create_typed_ast(input);
+
+    // Assert
+    assert!(result.is_ok(), "{:?}", result);
+}
+
+#[test]
+fn known_attribute_with_too_many_arguments_is_an_error() {
+    // Arrange
+    let input = "@inline(1) fun one(): Int => 1";
+
+    // Act
+    let result = try_create_typed_ast(input);
+
+    // Assert
+    assert_eq!(
+        result,
+        Err("Attribute @inline takes 0 arguments but 1 were given".to_string())
+    );
+}
+
+#[test]
+fn unknown_attributes_are_ignored() {
+    // Arrange
+    let input = "@backend_directive(1, 2, 3) fun one(): Int => 1";
+
+    // Act
+    let result = try_create_typed_ast(input);
+
+    // Assert
+    assert!(result.is_ok(), "{:?}", result);
+}
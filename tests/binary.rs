@@ -1,6 +1,6 @@
 mod common;
 
-use common::{create_typed_ast, StatementExt, VecStatementExt};
+use common::{create_typed_ast, try_create_typed_ast, StatementExt, VecStatementExt};
 
 use interpreter::{value::Number, Value};
 use shared::type_checker::{
@@ -1025,3 +1025,185 @@ fn greater_than_or_equal_returns_false_if_not_greater_than() {
     // Assert
     assert_eq!(value, Value::Bool(false));
 }
+
+#[test]
+fn binary_operators_accept_supported_operand_types() {
+    // Arrange
+    let inputs = [
+        ("1 + 2", Type::Int),
+        ("1.0 * 2.0", Type::Float),
+        (r#""a" + "b""#, Type::String),
+        ("1 < 2", Type::Bool),
+        ("1.0 >= 2.0", Type::Bool),
+        ("true == false", Type::Bool),
+        (r#""a" != "b""#, Type::Bool),
+        ("true && false", Type::Bool),
+        ("true || false", Type::Bool),
+        ("1 & 2", Type::Int),
+        ("1 << 2", Type::Int),
+    ];
+
+    for (input, expected) in inputs {
+        // Act
+        let typed_ast = create_typed_ast(input);
+
+        // Assert
+        let expression = typed_ast
+            .unwrap_program()
+            .nth_statement(0)
+            .unwrap_expression();
+
+        assert_eq!(expression.get_type(), expected, "{}", input);
+    }
+}
+
+#[test]
+fn binary_operators_reject_unsupported_operand_types() {
+    // Arrange
+    let inputs = [
+        ("true + false", "arithmetic operator '+'", "Bool"),
+        (r#""a" - "b""#, "arithmetic operator '-'", "String"),
+        (r#""a" < "b""#, "comparison operator '<'", "String"),
+        ("true > false", "comparison operator '>'", "Bool"),
+        ("1 && 2", "logical operator '&&'", "Int"),
+        ("true || 1", "logical operator '||'", "Int"),
+        ("1.0 & 2.0", "bitwise operator '&'", "Float"),
+        ("true ^ false", "bitwise operator '^'", "Bool"),
+        ("1.0..2.0", "range operator '..'", "Float"),
+        (
+            "let f = |x: Int|: Int x; f == f",
+            "equality operator '=='",
+            "fun",
+        ),
+    ];
+
+    for (input, operator, type_) in inputs {
+        // Act
+        let error = try_create_typed_ast(input).unwrap_err();
+
+        // Assert
+        assert!(error.contains(operator), "{}: {}", input, error);
+        assert!(error.contains(type_), "{}: {}", input, error);
+    }
+}
+
+#[test]
+fn equality_is_supported_for_structs() {
+    // Arrange
+    let input = r#"
+        struct A { a: Int }
+        let a: A = A { a: 1 };
+        let b: A = A { a: 1 };
+        a == b
+    "#;
+
+    // Act
+    let value = evaluate_expression(input, create_env(), false);
+
+    // Assert
+    assert_eq!(value, Value::Bool(true));
+}
+
+#[test]
+fn logical_and_does_not_evaluate_right_side_when_left_is_false() {
+    // Arrange
+    let input = r#"
+        fun fail(x: Int): Bool => 1 / x == 0
+        false && fail(0)
+    "#;
+
+    // Act
+    let value = evaluate_expression(input, create_env(), false);
+
+    // Assert
+    assert_eq!(value, Value::Bool(false));
+}
+
+#[test]
+fn logical_or_does_not_evaluate_right_side_when_left_is_true() {
+    // Arrange
+    let input = r#"
+        fun fail(x: Int): Bool => 1 / x == 0
+        true || fail(0)
+    "#;
+
+    // Act
+    let value = evaluate_expression(input, create_env(), false);
+
+    // Assert
+    assert_eq!(value, Value::Bool(true));
+}
+
+#[test]
+fn logical_and_evaluates_right_side_when_left_is_true() {
+    // Arrange
+    let input = r#"
+        fun is_zero(x: Int): Bool => x == 0
+        true && is_zero(1)
+    "#;
+
+    // Act
+    let value = evaluate_expression(input, create_env(), false);
+
+    // Assert
+    assert_eq!(value, Value::Bool(false));
+}
+
+#[test]
+fn logical_operators_are_bool() {
+    // Arrange
+    let input = r#"
+        let a = true;
+        a && !a || a
+    "#;
+
+    // Act
+    let typed_ast = create_typed_ast(input);
+
+    // Assert
+    let expression = typed_ast
+        .unwrap_program()
+        .nth_statement(1)
+        .unwrap_expression();
+
+    assert_eq!(expression.get_type(), Type::Bool);
+}
+
+#[test]
+fn division_by_literal_zero_is_an_error() {
+    // Arrange
+    let input = "let x = 4; x / 0";
+
+    // Act
+    let typed_ast = try_create_typed_ast(input);
+
+    // Assert
+    assert_eq!(typed_ast, Err("Cannot divide x by zero".to_string()));
+}
+
+#[test]
+fn modulo_by_literal_zero_is_an_error() {
+    // Arrange
+    let input = "let x = 4; x % 0";
+
+    // Act
+    let typed_ast = try_create_typed_ast(input);
+
+    // Assert
+    assert_eq!(
+        typed_ast,
+        Err("Cannot take the remainder of x divided by zero".to_string())
+    );
+}
+
+#[test]
+fn division_by_non_constant_divisor_is_allowed() {
+    // Arrange
+    let input = "let x = 4; let y = 2; x / y";
+
+    // Act
+    let value = evaluate_expression(input, create_env(), false);
+
+    // Assert
+    assert_eq!(value, Value::Number(Number::Int(2)));
+}
@@ -1,6 +1,11 @@
 mod common;
 
-use common::{create_typed_ast, StatementExt, VecStatementExt};
+use common::{
+    create_env, create_typed_ast, evaluate_expression, try_create_typed_ast, StatementExt,
+    VecStatementExt,
+};
+
+use interpreter::{value::Number, Value};
 
 use shared::type_checker::{
     ast::{Typed, TypedExpression},
@@ -40,3 +45,39 @@ fn block_has_correct_type() {
 
     assert_eq!(expression.get_type(), Type::Int);
 }
+
+#[test]
+fn type_alias_declared_in_a_block_is_used_in_it() {
+    // Arrange
+    let input = "let y = { type N = Int; let x: N = 1; x }; y";
+
+    // Act
+    let value = evaluate_expression(input, create_env(), false);
+
+    // Assert
+    assert_eq!(value, Value::Number(Number::Int(1)));
+}
+
+#[test]
+fn type_alias_declared_in_a_block_is_not_visible_after_it() {
+    // Arrange
+    let input = "let y = { type N = Int; let x: N = 1; x }; let z: N = y;";
+
+    // Act
+    let typed_ast = try_create_typed_ast(input);
+
+    // Assert
+    assert_eq!(typed_ast.err(), Some("Type N not found".to_string()));
+}
+
+#[test]
+fn type_alias_declared_in_a_block_shadows_an_outer_alias() {
+    // Arrange
+    let input = "type N = Int; let y = { type N = Float; let x: N = 1.0; x }; let z: N = 2;";
+
+    // Act
+    let typed_ast = try_create_typed_ast(input);
+
+    // Assert
+    assert!(typed_ast.is_ok(), "{:?}", typed_ast.err());
+}
@@ -0,0 +1,69 @@
+mod common;
+
+use common::{create_type_env, tokenize};
+
+use shared::{
+    parser::{self, build::*, BinaryOperator},
+    type_checker::{
+        self,
+        ast::{Typed, TypedStatement},
+        Type, TypeCheckerConfig,
+    },
+};
+
+#[test]
+fn built_program_type_checks() {
+    // Arrange
+    let program = program(vec![
+        struct_decl("Point", vec![("x", type_("Int")), ("y", type_("Int"))]),
+        function_decl(
+            "add",
+            vec![("a", type_("Int")), ("b", type_("Int"))],
+            type_("Int"),
+            binary(identifier("a"), BinaryOperator::Add, identifier("b")),
+        ),
+        semi(let_decl(
+            "point",
+            struct_literal("Point", vec![("x", int(1)), ("y", int(2))]),
+        )),
+        statement(call(
+            identifier("add"),
+            vec![field(identifier("point"), "x"), int(5)],
+        )),
+    ]);
+
+    let type_environment = create_type_env();
+
+    // Act
+    let typed_program =
+        type_checker::create_typed_ast(program, type_environment, TypeCheckerConfig::default())
+            .unwrap();
+
+    // Assert
+    let TypedStatement::Program { statements } = typed_program else {
+        panic!("Expected a program");
+    };
+
+    let Some(TypedStatement::Expression(last)) = statements.last() else {
+        panic!("Expected the program to end with an expression");
+    };
+
+    assert_eq!(last.get_type(), Type::Int);
+}
+
+#[test]
+fn built_function_matches_parsed_function() {
+    // Arrange
+    let source = "fun add(a: Int, b: Int): Int => a + b";
+
+    // Act
+    let built = program(vec![function_decl(
+        "add",
+        vec![("a", type_("Int")), ("b", type_("Int"))],
+        type_("Int"),
+        binary(identifier("a"), BinaryOperator::Add, identifier("b")),
+    )]);
+
+    // Assert
+    assert_eq!(built, parser::create_ast(tokenize(source), false).unwrap());
+}
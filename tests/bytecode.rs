@@ -0,0 +1,229 @@
+mod common;
+
+use common::{create_typed_ast, try_create_typed_ast};
+
+use shared::{
+    codegen::{
+        bytecode::{compile, disassemble, Constant, Instr},
+        vm::{self, Value},
+    },
+    type_checker::ast::BinaryOperator,
+};
+
+fn run(input: &str) -> Value {
+    let typed_ast = create_typed_ast(input);
+    let instructions = compile(&typed_ast).unwrap();
+
+    vm::run(&instructions).unwrap()
+}
+
+#[test]
+fn addition_compiles_to_push_push_add() {
+    // Arrange
+    let input = "1 + 2";
+    let typed_ast = create_typed_ast(input);
+
+    // Act
+    let instructions = compile(&typed_ast).unwrap();
+
+    // Assert
+    assert_eq!(
+        instructions,
+        vec![
+            Instr::Push(Constant::Int(1)),
+            Instr::Push(Constant::Int(2)),
+            Instr::Binary(BinaryOperator::Add),
+            Instr::Halt,
+        ]
+    );
+}
+
+#[test]
+fn disassembler_lists_addressed_instructions() {
+    // Arrange
+    let typed_ast = create_typed_ast("1 + 2");
+    let instructions = compile(&typed_ast).unwrap();
+
+    // Act
+    let disassembly = disassemble(&instructions);
+
+    // Assert
+    assert_eq!(
+        disassembly,
+        "0000 push 1\n0001 push 2\n0002 binary +\n0003 halt"
+    );
+}
+
+#[test]
+fn vm_evaluates_arithmetic() {
+    // Arrange
+    let input = "(1 + 2) * 4 - 10 / 5";
+
+    // Act
+    let value = run(input);
+
+    // Assert
+    assert_eq!(value, Value::Int(10));
+}
+
+#[test]
+fn if_jumps_to_the_taken_branch() {
+    // Arrange
+    let input = r#"
+        let x = 3;
+        let y = if x > 2 => { x * 2 } else => { x };
+        y
+    "#;
+
+    // Act
+    let value = run(input);
+
+    // Assert
+    assert_eq!(value, Value::Int(6));
+}
+
+#[test]
+fn while_with_continue_and_break_jumps_correctly() {
+    // Arrange
+    let input = r#"
+        let mut i = 0;
+        let mut sum = 0;
+        let result = while true => {
+            i = i + 1;
+            if i == 3 => continue;
+            if i > 5 => break sum;
+            sum = sum + i;
+        } else {
+            0
+        };
+        result
+    "#;
+
+    // Act
+    let value = run(input);
+
+    // Assert
+    assert_eq!(value, Value::Int(1 + 2 + 4 + 5));
+}
+
+#[test]
+fn while_runs_else_when_condition_fails() {
+    // Arrange
+    let input = r#"
+        let mut i = 0;
+        let result = while 3 > i => {
+            i = i + 1;
+        } else {
+            i * 10
+        };
+        result
+    "#;
+
+    // Act
+    let value = run(input);
+
+    // Assert
+    assert_eq!(value, Value::Int(30));
+}
+
+#[test]
+fn loop_breaks_with_value() {
+    // Arrange
+    let input = r#"
+        let mut i = 0;
+        loop {
+            i = i + 1;
+            if i == 4 => break i * 100;
+        }
+    "#;
+
+    // Act
+    let value = run(input);
+
+    // Assert
+    assert_eq!(value, Value::Int(400));
+}
+
+#[test]
+fn function_returns_early() {
+    // Arrange
+    let input = r#"
+        fun clamp(n: Int): Int => {
+            if n > 10 => return 10;
+            n
+        }
+        fun double_clamped(n: Int): Int => clamp(n) * 2
+        double_clamped(3) + double_clamped(50)
+    "#;
+
+    // Act
+    let value = run(input);
+
+    // Assert
+    assert_eq!(value, Value::Int(6 + 20));
+}
+
+#[test]
+fn curried_function_captures_first_argument() {
+    // Arrange
+    let input = r#"
+        fun add(a: Int, b: Int): Int => a + b
+        add(2)(3)
+    "#;
+
+    // Act
+    let value = run(input);
+
+    // Assert
+    assert_eq!(value, Value::Int(5));
+}
+
+#[test]
+fn match_dispatches_through_decision_tree() {
+    // Arrange
+    let input = r#"
+        fun classify(x: Int): Int => x match
+        | 0 => x + 100,
+        | 1..5 => x + 10,
+        | n => n * 2;
+
+        classify(0) + classify(3) + classify(7)
+    "#;
+
+    // Act
+    let value = run(input);
+
+    // Assert
+    assert_eq!(value, Value::Int(100 + 13 + 14));
+}
+
+#[test]
+fn return_outside_function_is_rejected() {
+    // Arrange
+    let typed_ast = try_create_typed_ast("return 1;");
+
+    // Act
+    let result = typed_ast.and_then(|typed_ast| compile(&typed_ast));
+
+    // Assert
+    assert!(result.is_err());
+}
+
+#[test]
+fn match_binds_struct_fields() {
+    // Arrange
+    let input = r#"
+        struct Point { x: Int, y: Int }
+        let p = Point { x: 1, y: 2 };
+        let r = p match
+        | Point { x: pos_x } => pos_x * 10,
+        | _ => p.y;
+        r
+    "#;
+
+    // Act
+    let value = run(input);
+
+    // Assert
+    assert_eq!(value, Value::Int(10));
+}
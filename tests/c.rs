@@ -0,0 +1,156 @@
+mod common;
+
+use common::{create_typed_ast, try_create_typed_ast};
+
+use shared::codegen::c::emit;
+
+fn emit_c(input: &str) -> String {
+    let typed_ast = create_typed_ast(input);
+
+    emit(&typed_ast).unwrap()
+}
+
+#[test]
+fn struct_becomes_c_struct() {
+    // Arrange
+    let input = "struct Point { x: Int, y: Float, visible: Bool }";
+
+    // Act
+    let c = emit_c(input);
+
+    // Assert
+    assert!(c.contains("#include <stdint.h>"));
+    assert!(c.contains(
+        "typedef struct Point {\n    int64_t x;\n    double y;\n    bool visible;\n} Point;"
+    ));
+    assert!(!c.contains("int main(void)"));
+}
+
+#[test]
+fn tuple_struct_fields_get_c_names() {
+    // Arrange
+    let input = "struct Pair(Int, Int) fun first(p: Pair): Int => p.0";
+
+    // Act
+    let c = emit_c(input);
+
+    // Assert
+    assert!(c.contains("typedef struct Pair {\n    int64_t _0;\n    int64_t _1;\n} Pair;"));
+    assert!(c.contains("p._0"), "{}", c);
+}
+
+#[test]
+fn struct_fields_are_declared_before_use() {
+    // Arrange
+    let input = r#"
+        struct Line { start: Point, end: Point }
+        struct Point { x: Int, y: Int }
+    "#;
+
+    // Act
+    let c = emit_c(input);
+
+    // Assert
+    let point = c.find("typedef struct Point {").unwrap();
+    let line = c.find("typedef struct Line {").unwrap();
+
+    assert!(point < line);
+}
+
+#[test]
+fn function_with_several_parameters_is_uncurried() {
+    // Arrange
+    let input = r#"
+        struct Point { x: Int, y: Int }
+        fun add(a: Int, b: Int): Int => a + b
+        fun make(x: Int): Point => Point { x: add(x, 1), y: 0 }
+    "#;
+
+    // Act
+    let c = emit_c(input);
+
+    // Assert
+    assert!(c.contains("int64_t add(int64_t a, int64_t b);"));
+    assert!(c.contains("int64_t add(int64_t a, int64_t b) {\n    return (a + b);\n}"));
+    assert!(c.contains("Point make(int64_t x) {\n    return (Point){ .x = add(x, 1), .y = 0 };\n}"));
+}
+
+#[test]
+fn literal_match_becomes_switch() {
+    // Arrange
+    let input = r#"
+        fun classify(x: Int): Int => x match
+        | 0 => x + 100,
+        | 1 => x + 50,
+        | n => n * 2;
+    "#;
+
+    // Act
+    let c = emit_c(input);
+
+    // Assert
+    assert!(c.contains("int64_t classify(int64_t x) {"));
+    assert!(c.contains("switch (matchee_1) {"));
+    assert!(c.contains("case 0: {\n        return (x + 100);"));
+    assert!(c.contains("case 1: {"));
+    assert!(c.contains("default: {\n        {\n            int64_t n = matchee_1;"));
+}
+
+#[test]
+fn enum_becomes_tagged_union_and_match_switches_on_tag() {
+    // Arrange
+    let input = r#"
+        enum Shape { C { radius: Float }, S { side: Float }, E }
+        fun area(s: Shape): Float => s match
+        | Shape::C { radius } => radius * radius * 3.0,
+        | Shape::S { side } => side * side,
+        | Shape::E => 0.0;
+    "#;
+
+    // Act
+    let c = emit_c(input);
+
+    // Assert
+    assert!(c.contains(
+        "typedef enum Shape_Tag {\n    Shape_C,\n    Shape_S,\n    Shape_E,\n} Shape_Tag;"
+    ));
+    assert!(c.contains("    Shape_Tag tag;\n    union {"));
+    assert!(c.contains("        struct {\n            double radius;\n        } C;"));
+    assert!(c.contains("    } as;\n} Shape;"));
+    assert!(c.contains("switch (matchee_1.tag) {"));
+    assert!(c.contains("case Shape_C: {"));
+    assert!(c.contains("double radius = matchee_1.as.C.radius;"));
+    assert!(c.contains("case Shape_E: {\n        return 0.0;"));
+}
+
+#[test]
+fn top_level_statements_are_placed_in_main() {
+    // Arrange
+    let input = r#"
+        let mut i = 0;
+        while 3 > i => {
+            i = i + 1;
+        };
+        print(i);
+    "#;
+
+    // Act
+    let c = emit_c(input);
+
+    // Assert
+    assert!(c.contains("int main(void) {\n    int64_t i = 0;"));
+    assert!(c.contains("printf(\"%lld\\n\", (long long)i);"));
+    assert!(c.ends_with("    return 0;\n}\n"));
+}
+
+#[test]
+fn closures_are_rejected() {
+    // Arrange
+    let typed_ast = try_create_typed_ast("let f = |x: Int| x + 1;");
+
+    // Act
+    let result = typed_ast.and_then(|typed_ast| emit(&typed_ast));
+
+    // Assert
+    assert!(result.is_err());
+}
@@ -29,7 +29,7 @@ fn call_is_call() {
 }
 
 #[test]
-fn call_has_void_return_type() {
+fn call_has_unit_return_type() {
     // Arrange
     let input = r#"
         fun a() =>  {}
@@ -45,7 +45,7 @@ fn call_has_void_return_type() {
         .nth_statement(1)
         .unwrap_expression();
 
-    assert_eq!(expression.get_type(), Type::Void);
+    assert_eq!(expression.get_type(), Type::Unit);
 }
 
 #[test]
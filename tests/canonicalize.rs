@@ -0,0 +1,93 @@
+mod common;
+
+use common::{check_type_env, try_create_typed_ast};
+
+use shared::types::TypeAnnotation;
+
+fn type_(name: &str) -> TypeAnnotation {
+    TypeAnnotation::Type(name.to_string())
+}
+
+#[test]
+fn records_with_fields_in_another_order_and_aliases_canonicalize_the_same() {
+    // Arrange
+    let type_environment = check_type_env("type Id = Int;");
+    let left = TypeAnnotation::Record(vec![
+        ("b".to_string(), type_("Id")),
+        (
+            "a".to_string(),
+            TypeAnnotation::Array(Box::new(type_("Int"))),
+        ),
+    ]);
+    let right = TypeAnnotation::Record(vec![
+        (
+            "a".to_string(),
+            TypeAnnotation::Array(Box::new(type_("Id"))),
+        ),
+        ("b".to_string(), type_("Int")),
+    ]);
+
+    // Act
+    let left = left.canonicalize(&type_environment.borrow());
+    let right = right.canonicalize(&type_environment.borrow());
+
+    // Assert
+    assert_eq!(left, right);
+    assert_eq!(
+        left,
+        TypeAnnotation::Record(vec![
+            (
+                "a".to_string(),
+                TypeAnnotation::Array(Box::new(type_("Int")))
+            ),
+            ("b".to_string(), type_("Int")),
+        ])
+    );
+}
+
+#[test]
+fn generic_type_given_no_types_canonicalizes_to_a_plain_type() {
+    // Arrange
+    let type_environment = check_type_env("struct Point { x: Int } type Spot = Point;");
+    let annotation = TypeAnnotation::ConcreteType("Spot".to_string(), vec![]);
+
+    // Act
+    let canonical = annotation.canonicalize(&type_environment.borrow());
+
+    // Assert
+    assert_eq!(canonical, type_("Point"));
+}
+
+#[test]
+fn generic_arguments_keep_their_order() {
+    // Arrange
+    let type_environment = check_type_env("type Id = Int; type Number = Int or Float;");
+    let annotation =
+        TypeAnnotation::ConcreteType("Pair".to_string(), vec![type_("Number"), type_("Id")]);
+
+    // Act
+    let canonical = annotation.canonicalize(&type_environment.borrow());
+
+    // Assert
+    assert_eq!(
+        canonical,
+        TypeAnnotation::ConcreteType("Pair".to_string(), vec![type_("Number"), type_("Int")])
+    );
+}
+
+#[test]
+fn static_member_declared_on_an_alias_is_a_member_of_the_aliased_type() {
+    // Arrange
+    let input = r#"
+    struct Point { x: Int }
+    type Spot = Point;
+    imp Spot { fun origin(): Point => Point { x: 0 } }
+    Point::origin()
+    "#;
+
+    // Act
+    let typed_ast = try_create_typed_ast(input);
+
+    // Assert
+    assert!(typed_ast.is_ok(), "{:?}", typed_ast.err());
+}
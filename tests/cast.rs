@@ -0,0 +1,138 @@
+mod common;
+
+use common::{
+    check_with_env, create_env, create_type_env, create_typed_ast, evaluate_expression,
+    StatementExt, VecStatementExt,
+};
+
+use interpreter::{value::Number, Value};
+use shared::type_checker::{
+    ast::{Typed, TypedExpression},
+    Type,
+};
+
+#[test]
+fn cast_is_cast() {
+    // Arrange
+    let input = "1 as Float";
+
+    // Act
+    let typed_ast = create_typed_ast(input);
+
+    // Assert
+    let expression = typed_ast
+        .unwrap_program()
+        .nth_statement(0)
+        .unwrap_expression();
+
+    assert!(matches!(expression, TypedExpression::Cast { .. }));
+    assert_eq!(expression.get_type(), Type::Float);
+}
+
+#[test]
+fn cast_widens_int_to_float() {
+    // Arrange
+    let input = r#"
+        let a = 2;
+        a as Float
+        "#;
+
+    // Act
+    let value = evaluate_expression(input, create_env(), false);
+
+    // Assert
+    assert_eq!(value, Value::Number(Number::Float(2.0)));
+}
+
+#[test]
+fn cast_narrows_float_to_int() {
+    // Arrange
+    let input = r#"
+        let a = 2.75;
+        a as Int
+        "#;
+
+    // Act
+    let value = evaluate_expression(input, create_env(), false);
+
+    // Assert
+    assert_eq!(value, Value::Number(Number::Int(2)));
+}
+
+#[test]
+fn cast_converts_char_to_int() {
+    // Arrange
+    let input = "'a' as Int";
+
+    // Act
+    let value = evaluate_expression(input, create_env(), false);
+
+    // Assert
+    assert_eq!(value, Value::Number(Number::Int(97)));
+}
+
+#[test]
+fn cast_converts_uint_to_char() {
+    // Arrange
+    let input = "97u as Char";
+
+    // Act
+    let value = evaluate_expression(input, create_env(), false);
+
+    // Assert
+    assert_eq!(value, Value::Char('a'));
+}
+
+#[test]
+fn cast_binds_tighter_than_multiplication() {
+    // Arrange
+    let input = "2 * 3u as Int";
+
+    // Act
+    let value = evaluate_expression(input, create_env(), false);
+
+    // Assert
+    assert_eq!(value, Value::Number(Number::Int(6)));
+}
+
+#[test]
+#[should_panic]
+fn cast_rejects_bool_to_string() {
+    // Arrange
+    let input = "true as String";
+
+    // Act
+    create_typed_ast(input); // panics
+}
+
+#[test]
+#[should_panic]
+fn cast_rejects_float_to_char() {
+    // Arrange
+    let input = "1.0 as Char";
+
+    // Act
+    create_typed_ast(input); // panics
+}
+
+#[test]
+fn cast_of_out_of_range_constant_is_kept_and_warned_about() {
+    // Arrange
+    let type_environment = create_type_env();
+    let input = "-1 as UInt";
+
+    // Act
+    let typed_ast = check_with_env(input, type_environment.clone()).unwrap();
+
+    // Assert
+    let expression = typed_ast
+        .unwrap_program()
+        .nth_statement(0)
+        .unwrap_expression();
+
+    assert!(matches!(expression, TypedExpression::Cast { .. }));
+    assert_eq!(
+        type_environment.borrow_mut().take_warnings(),
+        vec!["Constant -1 is out of range for UInt"]
+    );
+}
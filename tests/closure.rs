@@ -185,6 +185,22 @@ fn closure_voids_body_if_return_type_is_void() {
     // Act
     let value = evaluate_expression(input, create_env(), false);
 
+    // Assert
+    assert_eq!(value, Value::Unit);
+}
+
+#[test]
+fn calling_closure_typed_as_void_function_gives_void() {
+    // Arrange
+    // lang=arcana
+    let input = r#"
+        let op: fun(Int) = |x| x;
+        op(0)
+    "#;
+
+    // Act
+    let value = evaluate_expression(input, create_env(), false);
+
     // Assert
     assert_eq!(value, Value::Void);
 }
@@ -0,0 +1,72 @@
+mod common;
+
+use common::{create_type_env, tokenize};
+
+use shared::{
+    parser,
+    type_checker::{self, TypeCheckerConfig, TypeError},
+};
+
+fn check_all(source: &str) -> Result<(), Vec<TypeError>> {
+    let ast = parser::create_ast(tokenize(source), false).unwrap();
+    let type_environment = create_type_env();
+
+    type_checker::create_typed_ast_collecting_errors(
+        ast,
+        type_environment,
+        TypeCheckerConfig::default(),
+    )
+    .map(|_| ())
+}
+
+#[test]
+fn collecting_errors_reports_every_independent_error() {
+    // Arrange
+    let source = r#"
+    let count: Int = "three";
+    let total = missing + 1;
+    fun double(x: Int): Int => x * 2
+    let flag: Bool = double(4);
+    "#;
+
+    // Act
+    let errors = check_all(source).unwrap_err();
+
+    // Assert
+    let names: Vec<&str> = errors
+        .iter()
+        .map(|error| &source[error.span.start..error.span.end])
+        .collect();
+
+    assert_eq!(names, vec!["count", "total", "flag"]);
+    assert_eq!(errors[1].message, "Unexpected variable: missing");
+}
+
+#[test]
+fn collecting_errors_leaves_out_errors_caused_by_an_earlier_one() {
+    // Arrange
+    let source = r#"
+    let count: Int = "three";
+    let twice = count + count;
+    let other: Bool = 1;
+    "#;
+
+    // Act
+    let errors = check_all(source).unwrap_err();
+
+    // Assert
+    assert_eq!(errors.len(), 2);
+    assert_eq!(&source[errors[1].span.start..errors[1].span.end], "other");
+}
+
+#[test]
+fn collecting_errors_checks_valid_program() {
+    // Arrange
+    let source = "let count = 3; count + 1";
+
+    // Act
+    let result = check_all(source);
+
+    // Assert
+    assert_eq!(result, Ok(()));
+}
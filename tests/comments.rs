@@ -60,3 +60,34 @@ fn consecutive_block_comment_is_block_comment() {
 
     assert_eq!(comment.kind, TokenKind::BlockComment);
 }
+
+#[test]
+fn triple_slash_comment_is_doc_comment() {
+    // Arrange
+    let input = "/// This is a doc comment";
+
+    // Act
+    let tokens = common::tokenize(input);
+
+    // Assert
+    let comment = tokens.nth_token(0);
+
+    assert_eq!(
+        comment.kind,
+        TokenKind::DocComment("This is a doc comment".to_string())
+    );
+}
+
+#[test]
+fn quadruple_slash_comment_is_line_comment() {
+    // Arrange
+    let input = "//// This is a comment";
+
+    // Act
+    let tokens = common::tokenize(input);
+
+    // Assert
+    let comment = tokens.nth_token(0);
+
+    assert_eq!(comment.kind, TokenKind::LineComment);
+}
@@ -7,6 +7,7 @@ use shared::{
     type_checker::{
         self,
         ast::{TypedExpression, TypedStatement},
+        TypeCheckerConfig, TypeEnvironment,
     },
 };
 
@@ -15,11 +16,46 @@ pub fn tokenize(input: &str) -> Vec<lexer::token::Token> {
 }
 
 pub fn create_typed_ast(input: &str) -> TypedStatement {
+    try_create_typed_ast(input).unwrap()
+}
+
+pub fn try_create_typed_ast(input: &str) -> Result<TypedStatement, String> {
+    try_create_typed_ast_with_config(input, TypeCheckerConfig::default())
+}
+
+pub fn try_create_typed_ast_with_config(
+    input: &str,
+    config: TypeCheckerConfig,
+) -> Result<TypedStatement, String> {
     let tokens = lexer::tokenize(input).unwrap();
     let ast = parser::create_ast(tokens, false).unwrap();
-    let type_environment = Rc::new(RefCell::new(type_checker::TypeEnvironment::new(false)));
 
-    type_checker::create_typed_ast(ast, type_environment).unwrap()
+    type_checker::create_typed_ast(ast, create_type_env(), config)
+}
+
+/// Type check the input in the given type environment, so the caller can inspect it afterwards
+pub fn check_with_env(
+    input: &str,
+    type_environment: Rcrc<TypeEnvironment>,
+) -> Result<TypedStatement, String> {
+    let ast = parser::create_ast(tokenize(input), false)?;
+    type_checker::create_typed_ast(ast, type_environment, TypeCheckerConfig::default())
+}
+
+/// The type environment a program leaves behind once it is checked
+pub fn check_type_env(input: &str) -> Rcrc<TypeEnvironment> {
+    let type_environment = create_type_env();
+    check_with_env(input, type_environment.clone()).unwrap();
+
+    type_environment
+}
+
+/// Type check the input and give the warnings it raised
+pub fn check_warnings(input: &str) -> Vec<String> {
+    let type_environment = check_type_env(input);
+
+    let mut type_environment = type_environment.borrow_mut();
+    type_environment.take_warnings()
 }
 
 pub fn evaluate_expression(
@@ -29,8 +65,9 @@ pub fn evaluate_expression(
 ) -> Value {
     let tokens = lexer::tokenize(input).unwrap();
     let ast = parser::create_ast(tokens, false).unwrap();
-    let type_environment = Rc::new(RefCell::new(type_checker::TypeEnvironment::new(false)));
-    let typed_ast = type_checker::create_typed_ast(ast, type_environment).unwrap();
+    let typed_ast =
+        type_checker::create_typed_ast(ast, create_type_env(), TypeCheckerConfig::default())
+            .unwrap();
 
     if unwrap_semi {
         interpreter::evaluate(typed_ast.unwrap_semi(), environment).unwrap()
@@ -39,6 +76,15 @@ pub fn evaluate_expression(
     }
 }
 
+pub fn evaluate(input: &str) -> Result<Value, String> {
+    evaluate_with("", input)
+}
+
+pub fn evaluate_with(fixture: &str, input: &str) -> Result<Value, String> {
+    let typed_ast = try_create_typed_ast(&format!("{}{}", fixture, input))?;
+    interpreter::evaluate(typed_ast, create_env())
+}
+
 pub trait TokenExt {
     fn nth_token(&self, n: usize) -> lexer::token::Token;
 }
@@ -93,3 +139,7 @@ pub type Rcrc<T> = Rc<RefCell<T>>;
 pub fn create_env() -> Rcrc<Environment> {
     Rc::new(RefCell::new(Environment::new()))
 }
+
+pub fn create_type_env() -> Rcrc<TypeEnvironment> {
+    Rc::new(RefCell::new(TypeEnvironment::new(false)))
+}
@@ -0,0 +1,45 @@
+mod common;
+
+use common::check_warnings;
+
+#[test]
+fn constant_comparison_is_a_warning() {
+    // Arrange
+    let input = "fun f(): Bool => 10 >= 5";
+
+    // Act
+    let warnings = check_warnings(input);
+
+    // Assert
+    assert_eq!(
+        warnings,
+        vec!["Comparison 10 >= 5 in function 'f' is always true"]
+    );
+}
+
+#[test]
+fn self_comparison_is_a_warning() {
+    // Arrange
+    let input = "fun f(a: Int): Bool => a > a";
+
+    // Act
+    let warnings = check_warnings(input);
+
+    // Assert
+    assert_eq!(
+        warnings,
+        vec!["Comparison a > a in function 'f' is always false"]
+    );
+}
+
+#[test]
+fn comparison_of_different_or_float_operands_is_not_a_warning() {
+    // Arrange
+    let input = "fun f(a: Int, b: Int, c: Float): Bool => b > a && c == c";
+
+    // Act
+    let warnings = check_warnings(input);
+
+    // Assert
+    assert_eq!(warnings, Vec::<String>::new());
+}
@@ -0,0 +1,59 @@
+mod common;
+
+use common::check_type_env;
+
+use shared::type_checker::{completion::member_completions, Type};
+
+/// Type check the source and complete after a dot on a value of the named type, as if the dot
+/// was written in the given module
+fn complete(source: &str, type_name: &str, module_path: &[&str]) -> Vec<(String, Type)> {
+    let type_environment = check_type_env(source);
+    let type_environment = type_environment.borrow();
+    let object_type = type_environment.get_type(type_name).unwrap();
+    let module_path: Vec<String> = module_path.iter().map(|name| name.to_string()).collect();
+
+    member_completions(&object_type, &type_environment, &module_path)
+}
+
+#[test]
+fn member_completions_of_struct_include_fields_and_methods() {
+    // Arrange
+    let source = r#"
+    struct Point { x: Int, label: String }
+    imp Point { fun origin(): Point => Point { x: 0, label: "o" } fun double(self): Int => self.x * 2 }
+    "#;
+
+    // Act
+    let completions = complete(source, "Point", &[]);
+
+    // Assert
+    let [(x, x_type), (label, label_type), (double, double_type)] = completions.as_slice() else {
+        panic!("Expected two fields and a method, found {:?}", completions);
+    };
+
+    assert_eq!((x.as_str(), x_type), ("x", &Type::Int));
+    assert_eq!((label.as_str(), label_type), ("label", &Type::String));
+    assert_eq!(double, "double");
+    assert!(matches!(double_type, Type::Function(_)));
+}
+
+#[test]
+fn member_completions_leave_out_fields_not_visible_from_module() {
+    // Arrange
+    let source = r#"
+    pub mod shapes;
+    pub struct Point { pub x: Int, y: Int }
+    "#;
+
+    // Act
+    let inside = complete(source, "Point", &["shapes"]);
+    let outside = complete(source, "Point", &["app"]);
+
+    // Assert
+    let names = |completions: &[(String, Type)]| -> Vec<String> {
+        completions.iter().map(|(name, _)| name.clone()).collect()
+    };
+
+    assert_eq!(names(&inside), vec!["x", "y"]);
+    assert_eq!(names(&outside), vec!["x"]);
+}
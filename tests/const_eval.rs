@@ -0,0 +1,66 @@
+mod common;
+
+use common::{create_typed_ast, StatementExt, VecStatementExt};
+
+use shared::type_checker::{
+    ast::TypedExpression,
+    constant::{eval_const, ConstEnvironment, ConstValue, EvalErrorKind},
+};
+
+fn nth_expression(input: &str, n: usize) -> TypedExpression {
+    create_typed_ast(input)
+        .unwrap_program()
+        .nth_statement(n)
+        .unwrap_expression()
+}
+
+#[test]
+fn arithmetic_is_folded() {
+    // Arrange
+    let expression = nth_expression("(1 + 2) * 3 - 8 / 4", 0);
+
+    // Act
+    let value = eval_const(&expression, &ConstEnvironment::default());
+
+    // Assert
+    assert_eq!(value, Ok(ConstValue::Int(7)));
+}
+
+#[test]
+fn boolean_expression_is_evaluated() {
+    // Arrange
+    let expression = nth_expression("1 < 2 && !(3 == 4) || false", 0);
+
+    // Act
+    let value = eval_const(&expression, &ConstEnvironment::default());
+
+    // Assert
+    assert_eq!(value, Ok(ConstValue::Bool(true)));
+}
+
+#[test]
+fn declared_constant_is_read() {
+    // Arrange
+    let expression = nth_expression("let x = 1;\nx + 2", 1);
+    let mut environment = ConstEnvironment::default();
+    environment.declare("x", ConstValue::Int(40));
+
+    // Act
+    let value = eval_const(&expression, &environment);
+
+    // Assert
+    assert_eq!(value, Ok(ConstValue::Int(42)));
+}
+
+#[test]
+fn variable_which_is_not_a_constant_is_rejected() {
+    // Arrange
+    let expression = nth_expression("let x = 1;\nx + 2", 1);
+
+    // Act
+    let error = eval_const(&expression, &ConstEnvironment::default()).unwrap_err();
+
+    // Assert
+    assert_eq!(error.kind, EvalErrorKind::NotConstant);
+    assert_eq!(error.message, "x is not a constant");
+}
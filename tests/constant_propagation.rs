@@ -0,0 +1,65 @@
+mod common;
+
+use common::{try_create_typed_ast_with_config, StatementExt, VecStatementExt};
+
+use shared::{
+    span::Span,
+    type_checker::{
+        ast::{BinaryOperator, Literal, TypedExpression},
+        TypeCheckerConfig,
+    },
+};
+
+fn initializer(input: &str, statement: usize) -> TypedExpression {
+    let config = TypeCheckerConfig {
+        propagate_constants: true,
+        ..Default::default()
+    };
+
+    let TypedExpression::VariableDeclaration {
+        initializer: Some(initializer),
+        ..
+    } = try_create_typed_ast_with_config(input, config)
+        .unwrap()
+        .unwrap_program()
+        .nth_statement(statement)
+        .unwrap_semi()
+        .unwrap_expression()
+    else {
+        panic!("Expected a variable declaration with an initializer");
+    };
+
+    *initializer
+}
+
+#[test]
+fn immutable_constant_is_propagated_and_folded() {
+    // Arrange
+    let input = "let a = 2; let b = a + 3;";
+
+    // Act
+    let initializer = initializer(input, 1);
+
+    // Assert
+    assert_eq!(
+        initializer,
+        TypedExpression::Literal(Literal::Int(5), Span::default())
+    );
+}
+
+#[test]
+fn mutable_binding_is_not_propagated() {
+    // Arrange
+    let input = "let mut a = 2; a = 4; let b = a + 3;";
+
+    // Act
+    let initializer = initializer(input, 2);
+
+    // Assert
+    let TypedExpression::Binary { operator, left, .. } = initializer else {
+        panic!("Expected a binary expression");
+    };
+
+    assert_eq!(operator, BinaryOperator::Add);
+    assert!(matches!(*left, TypedExpression::Member(_)));
+}
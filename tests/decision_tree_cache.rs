@@ -0,0 +1,172 @@
+use std::{cell::RefCell, rc::Rc};
+
+use shared::{
+    lexer, parser,
+    type_checker::{
+        self,
+        ast::{TypedExpression, TypedStatement},
+        decision_tree::Decision,
+        visit::{walk_expression, TypedVisitor},
+        Rcrc, TypeCheckerConfig, TypeEnvironment,
+    },
+};
+
+fn check(input: &str, decision_tree_cache: bool) -> (TypedStatement, Rcrc<TypeEnvironment>) {
+    let tokens = lexer::tokenize(input).unwrap();
+    let ast = parser::create_ast(tokens, false).unwrap();
+    let type_environment = Rc::new(RefCell::new(TypeEnvironment::new(false)));
+
+    let config = TypeCheckerConfig {
+        decision_tree_cache,
+        ..Default::default()
+    };
+
+    let typed_ast = type_checker::create_typed_ast(ast, type_environment.clone(), config).unwrap();
+    (typed_ast, type_environment)
+}
+
+#[derive(Default)]
+struct DecisionTreeCollector {
+    decision_trees: Vec<Decision>,
+}
+
+impl TypedVisitor for DecisionTreeCollector {
+    fn visit_expression(&mut self, expression: &TypedExpression) {
+        if let TypedExpression::Match { decision_tree, .. } = expression {
+            self.decision_trees.push(decision_tree.clone());
+        }
+
+        walk_expression(self, expression);
+    }
+}
+
+fn decision_trees(typed_ast: &TypedStatement) -> Vec<Decision> {
+    let mut collector = DecisionTreeCollector::default();
+    collector.visit_statement(typed_ast);
+    collector.decision_trees
+}
+
+const SAME_SHAPED_MATCHES: &str = r#"
+    fun a(x: Int): Int => x match
+    | 0 => x + 1,
+    | 1 => x * 2,
+    | _ => x;
+    fun b(y: Int): Int => y match
+    | 0 => y - 1,
+    | 1 => y / 2,
+    | _ => 0 - y;
+    fun c(z: Int): Bool => z match
+    | 0 => z == 0,
+    | 1 => 0 > z,
+    | _ => z > 2;
+"#;
+
+#[test]
+fn cache_is_off_by_default() {
+    // Arrange
+    let type_environment = Rc::new(RefCell::new(TypeEnvironment::new(false)));
+    let ast = parser::create_ast(lexer::tokenize(SAME_SHAPED_MATCHES).unwrap(), false).unwrap();
+
+    // Act
+    type_checker::create_typed_ast(ast, type_environment.clone(), Default::default()).unwrap();
+
+    // Assert
+    assert!(type_environment.borrow().decision_tree_cache().is_none());
+}
+
+#[test]
+fn same_shaped_matches_reuse_skeleton() {
+    // Arrange
+    let input = SAME_SHAPED_MATCHES;
+
+    // Act
+    let (_, type_environment) = check(input, true);
+
+    // Assert
+    let cache = type_environment.borrow().decision_tree_cache().unwrap();
+    let cache = cache.borrow();
+
+    assert_eq!(cache.len(), 1);
+    assert_eq!(cache.misses(), 1);
+    assert_eq!(cache.hits(), 2);
+}
+
+#[test]
+fn cached_decision_trees_match_uncached() {
+    // Arrange
+    let input = SAME_SHAPED_MATCHES;
+
+    // Act
+    let (uncached, _) = check(input, false);
+    let (cached, _) = check(input, true);
+
+    // Assert
+    assert_eq!(decision_trees(&cached), decision_trees(&uncached));
+}
+
+#[test]
+fn matches_with_bindings_are_not_cached() {
+    // Arrange
+    let input = r#"
+        fun a(x: Int): Int => x match
+        | 0 => 1,
+        | n => n;
+    "#;
+
+    // Act
+    let (_, type_environment) = check(input, true);
+
+    // Assert
+    let cache = type_environment.borrow().decision_tree_cache().unwrap();
+    assert!(cache.borrow().is_empty());
+}
+
+#[test]
+fn cached_match_still_checks_bodies() {
+    // Arrange
+    let input = r#"
+        fun a(x: Int): Int => x match
+        | 0 => 1,
+        | _ => x;
+        fun b(x: Int): Int => x match
+        | 0 => 1,
+        | _ => "not an int";
+    "#;
+
+    let ast = parser::create_ast(lexer::tokenize(input).unwrap(), false).unwrap();
+    let type_environment = Rc::new(RefCell::new(TypeEnvironment::new(false)));
+
+    let config = TypeCheckerConfig {
+        decision_tree_cache: true,
+        ..Default::default()
+    };
+
+    // Act
+    let result = type_checker::create_typed_ast(ast, type_environment, config);
+
+    // Assert
+    assert!(result.is_err());
+}
+
+#[test]
+fn cached_union_literal_match_is_typed_by_its_bodies() {
+    // Arrange
+    let input = r#"
+        union Small { 1, 2, 3 }
+        fun f(d: Small): Int => d match
+        | 1 => 10,
+        | 2 => 20,
+        | 3 => 30;
+    "#;
+
+    // Act
+    let (uncached, _) = check(input, false);
+    let (cached, _) = check(input, true);
+
+    // Assert
+    let cached_trees = decision_trees(&cached);
+
+    assert_eq!(cached_trees.len(), 1);
+    assert!(matches!(cached_trees[0], Decision::Switch { .. }));
+    assert_eq!(cached_trees, decision_trees(&uncached));
+}
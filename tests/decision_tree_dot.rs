@@ -0,0 +1,115 @@
+mod common;
+
+use common::create_typed_ast;
+
+use shared::type_checker::{
+    ast::{TypedExpression, TypedStatement},
+    decision_tree::Decision,
+};
+
+fn decision_tree(input: &str) -> Decision {
+    let typed_ast = create_typed_ast(input);
+
+    let TypedStatement::Program { statements } = typed_ast else {
+        panic!("Expected a program");
+    };
+
+    let Some(TypedStatement::Semi(statement)) = statements.last() else {
+        panic!("Expected a statement");
+    };
+
+    let TypedStatement::Expression(TypedExpression::VariableDeclaration {
+        initializer: Some(initializer),
+        ..
+    }) = statement.as_ref()
+    else {
+        panic!("Expected a variable declaration");
+    };
+
+    let TypedExpression::Match { decision_tree, .. } = initializer.as_ref() else {
+        panic!("Expected a match");
+    };
+
+    decision_tree.clone()
+}
+
+fn count_nodes(dot: &str) -> usize {
+    dot.lines()
+        .filter(|line| line.contains("[label=") && !line.contains("->"))
+        .count()
+}
+
+fn count_edges(dot: &str) -> usize {
+    dot.lines().filter(|line| line.contains("->")).count()
+}
+
+#[test]
+fn dot_has_one_node_per_decision() {
+    // Arrange
+    let decision_tree = decision_tree(
+        r#"
+        let x = 3;
+        let r = x match
+        | 0 => x + 1,
+        | n => n * 2;
+        "#,
+    );
+
+    // Act
+    let dot = decision_tree.to_dot();
+
+    // Assert
+    // Guard on 0, its success, the switch binding n, its success and the failure fallback
+    assert!(dot.starts_with("digraph decision_tree {\n"));
+    assert!(dot.ends_with("\n}"));
+    assert_eq!(count_nodes(&dot), 5);
+    assert_eq!(count_edges(&dot), 4);
+}
+
+#[test]
+fn dot_labels_nodes_and_edges() {
+    // Arrange
+    let decision_tree = decision_tree(
+        r#"
+        let x = 3;
+        let r = x match
+        | 0 => x + 1,
+        | n => n * 2;
+        "#,
+    );
+
+    // Act
+    let dot = decision_tree.to_dot();
+
+    // Assert
+    assert!(dot.contains("n0 [label=\"if "));
+    assert!(dot.contains("shape=diamond"));
+    assert!(dot.contains("[label=\"switch n\", shape=ellipse];"));
+    assert!(dot.contains("[label=\"No match found\", shape=octagon];"));
+    assert!(dot.contains("n0 -> n1 [label=\"true\"];"));
+    assert!(dot.contains("n0 -> n2 [label=\"false\"];"));
+    assert!(dot.contains("[label=\"n\"];"));
+    assert!(dot.contains("[label=\"fallback\"];"));
+}
+
+#[test]
+fn dot_edges_connect_every_node_to_its_parent() {
+    // Arrange
+    let decision_tree = decision_tree(
+        r#"
+        let x = 3;
+        let r = x match
+        | 0 => x * 2,
+        | 1..5 => x + 10,
+        | >=10 => x + 20,
+        | 7 => x + 30,
+        | _ => x + 40;
+        "#,
+    );
+
+    // Act
+    let dot = decision_tree.to_dot();
+
+    // Assert
+    assert_eq!(count_edges(&dot), count_nodes(&dot) - 1);
+}
@@ -0,0 +1,93 @@
+mod common;
+
+use common::create_typed_ast;
+
+use shared::{
+    display::{Indent, IndentDisplay},
+    type_checker::{
+        ast::{TypedExpression, TypedStatement},
+        decision_tree::Decision,
+    },
+};
+
+const NESTED_MATCH: &str = r#"
+struct Point { x: Int, y: Int }
+enum Shape { Circle { center: Point }, Empty }
+let shape = Shape::Circle { center: Point { x: 0, y: 1 } };
+let r = shape match
+| Shape::Circle { center: Point { x: 0 } } => 1,
+| _ => 0;
+"#;
+
+fn decision_tree(input: &str) -> Decision {
+    let typed_ast = create_typed_ast(input);
+
+    let TypedStatement::Program { statements } = typed_ast else {
+        panic!("Expected a program");
+    };
+
+    let Some(TypedStatement::Semi(statement)) = statements.last() else {
+        panic!("Expected a statement");
+    };
+
+    let TypedStatement::Expression(TypedExpression::VariableDeclaration {
+        initializer: Some(initializer),
+        ..
+    }) = statement.as_ref()
+    else {
+        panic!("Expected a variable declaration");
+    };
+
+    let TypedExpression::Match { decision_tree, .. } = initializer.as_ref() else {
+        panic!("Expected a match");
+    };
+
+    decision_tree.clone()
+}
+
+/// The body of the first case of a switch
+fn first_case(decision: &Decision) -> &Decision {
+    let Decision::Switch { cases, .. } = decision else {
+        panic!("Expected a switch but got {:?}", decision);
+    };
+
+    &cases[0].body
+}
+
+#[test]
+fn switch_on_nested_field_has_its_provenance() {
+    // Arrange
+    let decision_tree = decision_tree(NESTED_MATCH);
+
+    // Act
+    let Decision::Switch { variable, .. } = first_case(&decision_tree) else {
+        panic!("Expected a switch on the field of the center");
+    };
+
+    // Assert
+    assert_eq!(
+        variable.provenance_path(),
+        "scrutinee.Circle.center.Point.x"
+    );
+}
+
+#[test]
+fn provenance_is_shown_in_indent_display() {
+    // Arrange
+    let decision_tree = decision_tree(NESTED_MATCH);
+
+    // Act
+    let display = decision_tree.indent_display(&mut Indent::new());
+
+    // Assert
+    assert!(
+        display.contains("provenance: scrutinee.Circle.center\n"),
+        "{}",
+        display
+    );
+    assert!(
+        display.contains("provenance: scrutinee.Circle.center.Point.x\n"),
+        "{}",
+        display
+    );
+}
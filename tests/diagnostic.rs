@@ -0,0 +1,39 @@
+use shared::{
+    diagnostic::{render_diagnostic, Diagnostic},
+    span::Span,
+};
+
+#[test]
+fn render_diagnostic_underlines_span_on_its_line() {
+    // Arrange
+    let source = "let length = 5;\nlet total = lenth + 1;";
+    let start = source.find("lenth").unwrap();
+    let diagnostic = Diagnostic::new("Unexpected variable: lenth", Span::new(start, start + 5));
+
+    // Act
+    let rendered = render_diagnostic(source, &diagnostic);
+
+    // Assert
+    assert_eq!(
+        rendered,
+        "error: Unexpected variable: lenth\n --> 2:13\n  |\n2 | let total = lenth + 1;\n  |             ^^^^^"
+    );
+}
+
+#[test]
+fn render_diagnostic_marks_span_continuing_on_next_line() {
+    // Arrange
+    let source = "let point = Point {\n    x: 1,\n};";
+    let start = source.find("Point").unwrap();
+    let diagnostic = Diagnostic::new("Type Point not found", Span::new(start, source.len() - 1));
+
+    // Act
+    let rendered = render_diagnostic(source, &diagnostic);
+
+    // Assert
+    assert!(
+        rendered.ends_with("1 | let point = Point {\n  |             ^^^^^^^..."),
+        "{}",
+        rendered
+    );
+}
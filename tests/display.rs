@@ -0,0 +1,181 @@
+// Output of the typed tree display. The golden output of whole programs is stored under
+// tests/snapshots
+
+mod common;
+
+use common::create_typed_ast;
+use insta::assert_snapshot;
+
+use shared::display::{Indent, IndentDisplay};
+
+fn display(source: &str) -> String {
+    create_typed_ast(source).indent_display(&mut Indent::new())
+}
+
+#[test]
+fn struct_display() {
+    // Arrange
+    let source = "struct Point { x: Int, y: mut Float = 0.5 }";
+
+    // Act
+    let display = display(source);
+
+    // Assert
+    assert_snapshot!(display);
+}
+
+#[test]
+fn enum_with_fields_display() {
+    // Arrange
+    let source =
+        "enum Shape { origin: Int, Circle { radius: Float }, Square { side: Float }, Empty }";
+
+    // Act
+    let display = display(source);
+
+    // Assert
+    assert_snapshot!(display);
+}
+
+#[test]
+fn match_with_decision_tree_display() {
+    // Arrange
+    let source = r#"
+    enum Shape { Circle { radius: Float }, Square { side: Float }, Empty }
+    fun area(shape: Shape): Float => shape match
+    | Shape::Circle { radius } => radius * radius * 3.14,
+    | Shape::Square { side } => side * side,
+    | _ => 0.0
+    "#;
+
+    // Act
+    let display = display(source);
+
+    // Assert
+    assert_snapshot!(display);
+}
+
+#[test]
+fn match_on_fieldless_enum_members_display() {
+    // Arrange
+    let source = r#"
+    enum Color { Red, Green, Blue }
+    fun rank(color: Color): Int => color match
+    | Color::Red => 1,
+    | Color::Green => 2,
+    | Color::Blue => 3
+    "#;
+
+    // Act
+    let display = display(source);
+
+    // Assert
+    assert_snapshot!(display);
+}
+
+#[test]
+fn protocol_display() {
+    // Arrange
+    let source = r#"
+    proto Show { type T; fun show(self): T; }
+    struct Point { x: Int, y: Int }
+    imp Show for Point { type T = Int; fun show(self): Int => self.x + self.y }
+    "#;
+
+    // Act
+    let display = display(source);
+
+    // Assert
+    assert_snapshot!(display);
+}
+
+#[test]
+fn generic_function_display() {
+    // Arrange
+    let source = "fun pair<T, U>(first: T, second: U): T => first";
+
+    // Act
+    let display = display(source);
+
+    // Assert
+    assert_snapshot!(display);
+}
+
+#[test]
+fn whole_float_is_displayed_with_a_decimal_point() {
+    // Arrange
+    let source = "1.0";
+
+    // Act
+    let display = display(source);
+
+    // Assert
+    assert_eq!(display, "1.0");
+}
+
+#[test]
+fn fractional_float_is_displayed_as_is() {
+    // Arrange
+    let source = "0.5";
+
+    // Act
+    let display = display(source);
+
+    // Assert
+    assert_eq!(display, "0.5");
+}
+
+#[test]
+fn large_integer_is_displayed_with_thousands_separators() {
+    // Arrange
+    let typed_ast = create_typed_ast("1234567");
+
+    // Act
+    let plain = typed_ast.indent_display(&mut Indent::new());
+    let separated = typed_ast.indent_display(&mut Indent::new().with_thousands_separators(true));
+
+    // Assert
+    assert_eq!(plain, "1234567");
+    assert_eq!(separated, "1_234_567");
+}
+
+/// The line of a display which names the declared type
+fn type_name_line(display: &str) -> &str {
+    display
+        .lines()
+        .find(|line| line.contains("type_name:"))
+        .unwrap()
+}
+
+#[test]
+fn same_named_types_of_different_modules_are_told_apart_by_module_paths() {
+    // Arrange
+    let shapes = create_typed_ast("pub mod geometry::shapes; pub struct Point { x: Int }");
+    let grid = create_typed_ast("pub mod grid; pub struct Point { row: Int }");
+
+    // Act
+    let shapes = shapes.indent_display(&mut Indent::new().with_module_paths(true));
+    let grid = grid.indent_display(&mut Indent::new().with_module_paths(true));
+
+    // Assert
+    assert_eq!(
+        type_name_line(&shapes),
+        "├─type_name: <type name>: geometry::shapes::Point"
+    );
+    assert_eq!(
+        type_name_line(&grid),
+        "├─type_name: <type name>: grid::Point"
+    );
+}
+
+#[test]
+fn type_names_are_short_by_default() {
+    // Arrange
+    let typed_ast = create_typed_ast("pub mod grid; pub struct Point { row: Int }");
+
+    // Act
+    let display = typed_ast.indent_display(&mut Indent::new());
+
+    // Assert
+    assert_eq!(type_name_line(&display), "├─type_name: <type name>: Point");
+}
@@ -0,0 +1,117 @@
+mod common;
+
+use common::tokenize;
+
+use shared::{
+    display::{Indent, IndentDisplay},
+    parser::{self, Statement},
+};
+
+fn parse_statement(input: &str) -> Statement {
+    let Statement::Program { statements } = parser::create_ast(tokenize(input), false).unwrap()
+    else {
+        panic!("Expected a program");
+    };
+
+    statements.into_iter().next().unwrap()
+}
+
+#[test]
+fn function_doc_comment_is_captured_verbatim() {
+    // Arrange
+    let input = r#"
+    /// Adds two numbers.
+    ///
+    ///   Indented `code`, // and a slash comment.
+    fun add(a: Int, b: Int): Int => a + b
+    "#;
+
+    // Act
+    let statement = parse_statement(input);
+
+    // Assert
+    let Statement::FunctionDeclaration(function_declaration) = statement else {
+        panic!("Expected a function declaration");
+    };
+
+    assert_eq!(
+        function_declaration.doc,
+        Some("Adds two numbers.\n\n  Indented `code`, // and a slash comment.".to_string())
+    );
+}
+
+#[test]
+fn struct_doc_comment_skips_regular_comments() {
+    // Arrange
+    let input = r#"
+    /// A point in space.
+    // Not part of the doc.
+    struct Point { x: Int, y: Int }
+    "#;
+
+    // Act
+    let statement = parse_statement(input);
+
+    // Assert
+    let Statement::StructDeclaration(struct_declaration) = statement else {
+        panic!("Expected a struct declaration");
+    };
+
+    assert_eq!(
+        struct_declaration.doc,
+        Some("A point in space.".to_string())
+    );
+}
+
+#[test]
+fn doc_comment_belongs_to_the_next_declaration_only() {
+    // Arrange
+    let input = r#"
+    /// The first.
+    fun first(): Int => 1
+    fun second(): Int => 2
+    "#;
+
+    // Act
+    let Statement::Program { statements } = parser::create_ast(tokenize(input), false).unwrap()
+    else {
+        panic!("Expected a program");
+    };
+
+    // Assert
+    let [Statement::FunctionDeclaration(first), Statement::FunctionDeclaration(second)] =
+        statements.as_slice()
+    else {
+        panic!("Expected two function declarations");
+    };
+
+    assert_eq!(first.doc, Some("The first.".to_string()));
+    assert_eq!(second.doc, None);
+}
+
+#[test]
+fn doc_comment_before_expression_is_ignored() {
+    // Arrange
+    let input = r#"
+    /// Not a declaration.
+    let x = 5;
+    "#;
+
+    // Act
+    let statement = parse_statement(input);
+
+    // Assert
+    assert!(matches!(statement, Statement::Semi(_)));
+}
+
+#[test]
+fn doc_comment_is_shown_in_indent_display() {
+    // Arrange
+    let statement = parse_statement("/// A point.\nstruct Point { x: Int }");
+
+    // Act
+    let display = statement.indent_display(&mut Indent::new());
+
+    // Assert
+    assert!(display.contains("doc: \"A point.\""), "{}", display);
+}
@@ -0,0 +1,89 @@
+mod common;
+
+use common::{create_typed_ast, try_create_typed_ast, StatementExt, VecStatementExt};
+
+use shared::{
+    codegen::c::emit,
+    type_checker::{ast::TypedStatement, Type},
+};
+
+/// The discriminants of the members of the enum the input starts with, and its representation
+fn discriminants(input: &str) -> (Vec<i64>, Type) {
+    let statement = create_typed_ast(input).unwrap_program().nth_statement(0);
+
+    let TypedStatement::EnumDeclaration { members, repr, .. } = statement else {
+        panic!("Expected an enum declaration");
+    };
+
+    (members.iter().map(|m| m.discriminant).collect(), repr)
+}
+
+#[test]
+fn members_without_discriminants_count_up_from_zero() {
+    // Arrange
+    let input = "enum Direction { North, East, South, West }";
+
+    // Act
+    let (discriminants, repr) = discriminants(input);
+
+    // Assert
+    assert_eq!(discriminants, vec![0, 1, 2, 3]);
+    assert_eq!(repr, Type::Int);
+}
+
+#[test]
+fn members_count_up_from_an_explicit_discriminant() {
+    // Arrange
+    let input = r#"@repr("UInt") enum Level { Low = 1, Mid, High { boost: Int } = 10, Max }"#;
+
+    // Act
+    let (discriminants, repr) = discriminants(input);
+
+    // Assert
+    assert_eq!(discriminants, vec![1, 2, 10, 11]);
+    assert_eq!(repr, Type::UInt);
+}
+
+#[test]
+fn duplicate_discriminant_is_an_error() {
+    // Arrange
+    let input = "enum Code { Ok = 2, Warning = 1, Error }";
+
+    // Act
+    let typed_ast = try_create_typed_ast(input);
+
+    // Assert
+    assert_eq!(
+        typed_ast.err(),
+        Some("Discriminant 2 of 'Code::Error' is already used by 'Code::Ok'".to_string())
+    );
+}
+
+#[test]
+fn negative_discriminant_does_not_fit_in_uint() {
+    // Arrange
+    let input = r#"@repr("UInt") enum Sign { Negative = -1, Zero, Positive }"#;
+
+    // Act
+    let typed_ast = try_create_typed_ast(input);
+
+    // Assert
+    assert_eq!(
+        typed_ast.err(),
+        Some("Discriminant -1 of 'Sign::Negative' does not fit in UInt".to_string())
+    );
+}
+
+#[test]
+fn c_tags_have_the_discriminants_c_does_not_count_to() {
+    // Arrange
+    let input = "enum Level { Low = 1, Mid, High = 10 }";
+
+    // Act
+    let c = emit(&create_typed_ast(input)).unwrap();
+
+    // Assert
+    assert!(c.contains(
+        "typedef enum Level_Tag {\n    Level_Low = 1,\n    Level_Mid,\n    Level_High = 10,\n} Level_Tag;"
+    ));
+}
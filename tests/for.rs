@@ -1,6 +1,6 @@
 mod common;
 
-use common::{create_env, create_typed_ast, evaluate_expression, StatementExt, VecStatementExt};
+use common::{create_env, create_typed_ast, evaluate_expression, evaluate_with};
 
 use interpreter::{value::Number, Value};
 use shared::type_checker::{
@@ -154,3 +154,123 @@ fn for_returns_else_value_after_iteration() {
     // Assert
     assert_eq!(value, Value::Number(Number::Int(4)));
 }
+
+const COUNTER: &str = r#"
+struct Counter { n: Int, end: Int }
+imp Counter {
+    fun next(self): (Int, Counter)? => if self.n < self.end => (self.n, Counter { n: self.n + 1, end: self.end })
+}
+"#;
+
+#[test]
+fn for_iterates_an_array() {
+    // Arrange
+    let input = "let mut total = 0; for x in [1, 2, 3] => total = total + x; total";
+
+    // Act
+    let value = evaluate_with(COUNTER, input);
+
+    // Assert
+    assert_eq!(value, Ok(Value::Number(Number::Int(6))));
+}
+
+#[test]
+fn for_iterates_a_range() {
+    // Arrange
+    let input = "let mut total = 0; for x in 1..=4 => total = total + x; total";
+
+    // Act
+    let value = evaluate_with(COUNTER, input);
+
+    // Assert
+    assert_eq!(value, Ok(Value::Number(Number::Int(10))));
+}
+
+#[test]
+fn for_iterates_a_type_with_next() {
+    // Arrange
+    let input = "let mut total = 0; for x in Counter { n: 2, end: 5 } => total = total + x; total";
+
+    // Act
+    let value = evaluate_with(COUNTER, input);
+
+    // Assert
+    assert_eq!(value, Ok(Value::Number(Number::Int(9))));
+}
+
+#[test]
+fn for_rejects_a_type_without_next() {
+    // Arrange
+    let input = "struct Point { x: Int } for x in Point { x: 1 } => {}";
+
+    // Act
+    let value = evaluate_with(COUNTER, input);
+
+    // Assert
+    assert_eq!(
+        value,
+        Err("'Point' is not iterable, it has no method 'next'".to_string())
+    );
+}
+
+#[test]
+fn for_binds_the_element_type_of_a_range() {
+    // Arrange
+    let input = "for i in 0..10 => i";
+
+    // Act
+    let typed_ast = create_typed_ast(input);
+
+    // Assert
+    let TypedExpression::For { body, .. } = typed_ast
+        .unwrap_program()
+        .nth_statement(0)
+        .unwrap_expression()
+    else {
+        panic!("Expected for");
+    };
+
+    assert_eq!(body.get_type(), Type::Int);
+}
+
+#[test]
+fn for_iterates_an_inclusive_range_of_uints() {
+    // Arrange
+    let input = "let n = 3u; let mut total = 0u; for i in 1u..=n => total = total + i; total";
+
+    // Act
+    let value = evaluate_with(COUNTER, input);
+
+    // Assert
+    assert_eq!(value, Ok(Value::Number(Number::UInt(6))));
+}
+
+#[test]
+fn for_rejects_a_range_with_mismatched_endpoints() {
+    // Arrange
+    let input = "for i in 0..10u => {}";
+
+    // Act
+    let value = evaluate_with(COUNTER, input);
+
+    // Assert
+    assert_eq!(
+        value,
+        Err(
+            "Range endpoints must have matching Int, UInt or Char types, found Int and UInt"
+                .to_string()
+        )
+    );
+}
+
+#[test]
+fn for_else_gives_the_value_when_the_loop_does_not_break() {
+    // Arrange
+    let input = "for x in [1, 2, 3] => { if x > 5 => break x } else 0";
+
+    // Act
+    let value = evaluate_with(COUNTER, input);
+
+    // Assert
+    assert_eq!(value, Ok(Value::Number(Number::Int(0))));
+}
@@ -0,0 +1,215 @@
+mod common;
+
+use common::tokenize;
+
+use shared::{
+    format::{format, format_with_comments},
+    parser::{self, comments::CommentKind, Statement},
+};
+
+const PROGRAM: &str = r#"
+    pub mod shapes;
+    /// A point on the grid.
+    ///
+    /// Both axes are whole numbers.
+    struct Point { x: Int, y: mut Int = 0 }
+    imp Point { fun sum(self): Int => self.x + self.y }
+    enum Shape { origin: Point, C { radius: Float }, S { side: Float }, E }
+    union Small { 1, 2, 3 }
+    type Number = Int or Float;
+    /// Add twice the second number
+    @inline
+    @deprecated("use \"sum\"")
+    fun add(a: Int, b: Int): Int => a + b * 2
+    fun area(s: Shape): Float => s match
+    | Shape::C { radius } => radius * radius * 3.14,
+    | Shape::S { side: width } => width * width,
+    | _ => 0.0;
+    let mut total = 0;
+    let apply = |f: fun(Int): Int, v| f(v);
+    let double = |x: Int|: Int => x * 2;
+    while 10 > total => {
+        total = total + add(1, 2);
+        if total == 5 => { continue } else => { print("five\n\t\"quoted\"") };
+    };
+    for i in 0..10 => total -= i;
+    let r = (total + 1) * -3 match
+    | 0 => "zero",
+    | 1..=9 => "small",
+    | >=10 => "big",
+    | _ => "negative";
+    let p = Point { x: 1, y: 2 };
+    let q = Point { x: 3, ..p };
+    let origin: { x: Int, y: Int } = { x: 0, y: 0 };
+    let c = Shape::C { radius: 1.5 };
+    let b = !(total > 2 && total < 10) || (1 & 2) == 0;
+    let nested = if b => if total > 1 => 'a' else => '\'' else => 'c';
+    let value = loop { break 5; };
+    let grid = [[0; 3]; 2 * 2];
+    let row = grid[1][0..=1];
+    let maybe: Point? = if b => p;
+    let px = maybe?.x ?? 0;
+    fun half(n: Int): Result<Int, String> => if n % 2 == 0 => Result<Int, String>::Ok { value: n / 2 } else => Result<Int, String>::Err { error: "odd" }
+    fun quarter(n: Int): Result<Int, String> => half(half(n)?)
+"#;
+
+fn parse(input: &str) -> Statement {
+    parser::create_ast(tokenize(input), false).unwrap()
+}
+
+#[test]
+fn formatted_program_parses_to_the_same_ast() {
+    // Arrange
+    let ast = parse(PROGRAM);
+
+    // Act
+    let formatted = format(&ast);
+
+    // Assert
+    assert_eq!(parse(&formatted), ast, "Formatted source:\n{}", formatted);
+}
+
+#[test]
+fn formatting_formatted_program_is_a_no_op() {
+    // Arrange
+    let formatted = format(&parse(PROGRAM));
+
+    // Act
+    let reformatted = format(&parse(&formatted));
+
+    // Assert
+    assert_eq!(reformatted, formatted);
+}
+
+#[test]
+fn operators_are_spaced_and_parenthesized_only_when_needed() {
+    // Arrange
+    let ast = parse("let x = (1+2)*3-(4*5);");
+
+    // Act
+    let formatted = format(&ast);
+
+    // Assert
+    assert_eq!(formatted, "let x = (1 + 2) * 3 - 4 * 5;\n");
+}
+
+#[test]
+fn blocks_and_declarations_are_indented() {
+    // Arrange
+    let ast = parse(
+        "struct Point { x: Int, y: Int } fun f(p: Point, n: Int): Int => { let y = p.x; if y > n => { y } else => { n } }",
+    );
+
+    // Act
+    let formatted = format(&ast);
+
+    // Assert
+    assert_eq!(
+        formatted,
+        r#"struct Point {
+    x: Int,
+    y: Int,
+}
+
+fun f(p: Point, n: Int): Int => {
+    let y = p.x;
+    if y > n => {
+        y
+    } else => {
+        n
+    }
+}
+"#
+    );
+}
+
+#[test]
+fn match_arms_are_placed_on_their_own_lines() {
+    // Arrange
+    let ast = parse("let r = x match | 0 => |y| y + 1, | n => |y| y * n;");
+
+    // Act
+    let formatted = format(&ast);
+
+    // Assert
+    assert_eq!(
+        formatted,
+        "let r = x match\n    | 0 => |y| y + 1,\n    | n => |y| y * n;\n"
+    );
+}
+
+#[test]
+fn bitwise_left_operand_starting_with_parentheses_is_closed_off() {
+    // Arrange
+    let ast = parse("let x = ((1 == 2) ^ true) != false;");
+
+    // Act
+    let formatted = format(&ast);
+
+    // Assert
+    assert_eq!(parse(&formatted), ast, "Formatted source:\n{}", formatted);
+}
+
+#[test]
+fn comment_in_front_of_function_is_kept() {
+    // Arrange
+    let source = "// Adds one\nfun inc(x: Int): Int => x + 1 // tail\nlet a = inc(1);";
+    let (ast, comments) = parser::create_ast_with_comments(source, false).unwrap();
+
+    // Act
+    let formatted = format_with_comments(&ast, &comments);
+
+    // Assert
+    assert_eq!(
+        formatted,
+        "// Adds one\nfun inc(x: Int): Int => x + 1 // tail\n\nlet a = inc(1);\n"
+    );
+}
+
+#[test]
+fn comments_in_block_stay_in_block() {
+    // Arrange
+    let source = "fun f(): Int => {\n/- first -/\nlet a = 1;\na\n// last\n}\nlet b = f();";
+    let (ast, comments) = parser::create_ast_with_comments(source, false).unwrap();
+
+    // Act
+    let formatted = format_with_comments(&ast, &comments);
+
+    // Assert
+    assert_eq!(
+        formatted,
+        "fun f(): Int => {\n    /- first -/\n    let a = 1;\n    a\n    // last\n}\n\nlet b = f();\n"
+    );
+}
+
+#[test]
+fn comment_inside_struct_is_an_error() {
+    // Arrange
+    let source = "struct P {\n    // the x\n    x: Int\n}";
+
+    // Act
+    let result = parser::create_ast_with_comments(source, false);
+
+    // Assert
+    assert_eq!(
+        result.err(),
+        Some(
+            "Comment at 15..23 is inside of a statement, comments can only be kept between statements"
+                .to_string()
+        )
+    );
+}
+
+#[test]
+fn line_and_block_comments_are_told_apart() {
+    // Arrange
+    let source = "/- block -/\n// line\nlet a = 1;";
+
+    // Act
+    let (_, comments) = parser::create_ast_with_comments(source, false).unwrap();
+
+    // Assert
+    let kinds: Vec<CommentKind> = comments.leading[&0].iter().map(|c| c.kind).collect();
+
+    assert_eq!(kinds, vec![CommentKind::Block, CommentKind::Line]);
+}
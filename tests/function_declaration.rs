@@ -43,7 +43,7 @@ pub fn function_declaration_has_correct_type() {
         Type::Function(Function {
             identifier: Some(TypeIdentifier::Type("foo".to_string())),
             param: None,
-            return_type: Box::new(Type::Void),
+            return_type: Box::new(Type::Unit),
         })
     );
 }
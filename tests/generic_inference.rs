@@ -0,0 +1,101 @@
+mod common;
+
+use common::{
+    create_env, create_typed_ast, evaluate_expression, try_create_typed_ast, StatementExt,
+    VecStatementExt,
+};
+
+use interpreter::{value::Number, Value};
+use shared::type_checker::{ast::Typed, Type};
+
+/// The type of the call a program ends with
+fn call_type(input: &str) -> Type {
+    create_typed_ast(input)
+        .unwrap_program()
+        .nth_statement(1)
+        .unwrap_expression()
+        .get_type()
+}
+
+#[test]
+fn single_generic_is_inferred_from_the_argument() {
+    // Arrange
+    let input = r#"
+        fun id<T>(x: T): T => x
+        id(5)
+        "#;
+
+    // Act
+    let type_ = call_type(input);
+    let result = evaluate_expression(input, create_env(), false);
+
+    // Assert
+    assert_eq!(type_, Type::Int);
+    assert_eq!(result, Value::Number(Number::Int(5)));
+}
+
+#[test]
+fn several_generics_are_inferred_from_the_arguments() {
+    // Arrange
+    let input = r#"
+        fun second<T, U>(first: T, second: U): U => second
+        second(1, "two")
+        "#;
+
+    // Act
+    let type_ = call_type(input);
+
+    // Assert
+    assert_eq!(type_, Type::String);
+}
+
+#[test]
+fn conflicting_arguments_are_an_error() {
+    // Arrange
+    let input = r#"
+        fun same<T>(a: T, b: T): T => a
+        same(1, true)
+        "#;
+
+    // Act
+    let result = try_create_typed_ast(input);
+
+    // Assert
+    assert_eq!(
+        result,
+        Err("Generic type 'T' of 'same' is inferred as both Int and Bool".to_string())
+    );
+}
+
+#[test]
+fn generic_missing_from_the_arguments_is_an_error() {
+    // Arrange
+    let input = r#"
+        fun pair<T, U>(first: T, second: U): T => first
+        pair(1)
+        "#;
+
+    // Act
+    let result = try_create_typed_ast(input);
+
+    // Assert
+    assert_eq!(
+        result,
+        Err("Cannot infer generic type 'U' of 'pair' from its arguments".to_string())
+    );
+}
+
+#[test]
+fn explicit_type_arguments_override_inference() {
+    // Arrange
+    let input = r#"
+        fun id<T>(x: T): T => x
+        id::<UInt>(5)
+        "#;
+
+    // Act
+    let type_ = call_type(input);
+
+    // Assert
+    assert_eq!(type_, Type::UInt);
+}
@@ -0,0 +1,52 @@
+mod common;
+
+use common::create_typed_ast;
+
+use shared::type_checker::{hover::type_at, Type};
+
+#[test]
+fn type_at_literal_is_the_literal_type() {
+    // Arrange
+    let input = "let y = 2; let x = 7 * y; x";
+
+    // Act
+    let type_ = type_at(&create_typed_ast(input), input.find('7').unwrap());
+
+    // Assert
+    assert_eq!(
+        type_,
+        Some(Type::Literal {
+            name: "7".to_owned(),
+            type_: Box::new(Type::Int)
+        })
+    );
+}
+
+#[test]
+fn type_at_binary_operator_is_the_result_type() {
+    // Arrange
+    let input = "let x = 1.5 + 2.0; x";
+
+    // Act
+    let type_ = type_at(&create_typed_ast(input), input.find('+').unwrap());
+
+    // Assert
+    assert_eq!(type_, Some(Type::Float));
+}
+
+#[test]
+fn type_at_call_is_the_return_type() {
+    // Arrange
+    let input = "fun add(a: Int, b: Int): Int => a + b\nlet s = add(1, 2) == 3; s";
+    let program = create_typed_ast(input);
+
+    // Act
+    let call = type_at(&program, input.find("(1").unwrap());
+    let callee = type_at(&program, input.rfind("add").unwrap());
+    let outside = type_at(&program, input.find("fun").unwrap());
+
+    // Assert
+    assert_eq!(call, Some(Type::Int));
+    assert!(matches!(callee, Some(Type::Function(_))));
+    assert_eq!(outside, None);
+}
@@ -0,0 +1,147 @@
+mod common;
+
+use common::{evaluate_with, try_create_typed_ast};
+
+use interpreter::{value::Number, Value};
+
+const POINT: &str = r#"
+struct Point { x: Int, y: Int }
+imp Point {
+    fun sum(self): Int => self.x + self.y
+    fun scale(self, factor: Int): Point => Point { x: self.x * factor, y: self.y * factor }
+    fun new(x: Int, y: Int): Point => Point { x: x, y: y }
+}
+"#;
+
+#[test]
+fn method_is_called_on_an_instance() {
+    // Arrange
+    let input = "let p = Point { x: 1, y: 2 }; p.sum()";
+
+    // Act
+    let value = evaluate_with(POINT, input);
+
+    // Assert
+    assert_eq!(value, Ok(Value::Number(Number::Int(3))));
+}
+
+#[test]
+fn method_arguments_follow_the_receiver() {
+    // Arrange
+    let input = "let p = Point { x: 1, y: 2 }; let q = p.scale(3); q.sum()";
+
+    // Act
+    let value = evaluate_with(POINT, input);
+
+    // Assert
+    assert_eq!(value, Ok(Value::Number(Number::Int(9))));
+}
+
+#[test]
+fn method_does_not_shadow_global_function() {
+    // Arrange
+    let input = "fun sum(a: Int): Int => a let p = Point { x: 1, y: 2 }; p.sum() + sum(10)";
+
+    // Act
+    let value = evaluate_with(POINT, input);
+
+    // Assert
+    assert_eq!(value, Ok(Value::Number(Number::Int(13))));
+}
+
+#[test]
+fn function_without_self_is_a_static_member() {
+    // Arrange
+    let input = "let p = Point::new(1, 2); p.new(3, 4)";
+
+    // Act
+    let value = evaluate_with(POINT, input);
+
+    // Assert
+    assert_eq!(
+        value,
+        Err("'Point::new' does not take self and must be called as a static member".to_string())
+    );
+}
+
+#[test]
+fn unknown_method_is_an_error() {
+    // Arrange
+    let input = "let p = Point { x: 1, y: 2 }; p.area()";
+
+    // Act
+    let value = evaluate_with(POINT, input);
+
+    // Assert
+    assert_eq!(
+        value,
+        Err("'Point' has no field, method or function called 'area'".to_string())
+    );
+}
+
+const COUNTER: &str = r#"
+struct Counter { count: mut Int }
+imp Counter {
+    fun increment(mut self, by: Int): Int => {
+        self.count = self.count + by;
+        self.count
+    }
+}
+"#;
+
+#[test]
+fn mut_self_method_assigns_to_a_field() {
+    // Arrange
+    let input = "let c = Counter { count: 1 }; c.increment(2)";
+
+    // Act
+    let value = evaluate_with(COUNTER, input);
+
+    // Assert
+    assert_eq!(value, Ok(Value::Number(Number::Int(3))));
+}
+
+#[test]
+fn mut_self_method_changes_a_copy_of_the_receiver() {
+    // Arrange
+    let input = "let c = Counter { count: 1 }; c.increment(2); c.count";
+
+    // Act
+    let value = evaluate_with(COUNTER, input);
+
+    // Assert
+    assert_eq!(value, Ok(Value::Number(Number::Int(1))));
+}
+
+#[test]
+fn self_method_cannot_assign_to_a_field() {
+    // Arrange
+    let input = r#"
+    struct Counter { count: mut Int }
+    imp Counter { fun reset(self): Int => { self.count = 0; 0 } }
+    "#;
+
+    // Act
+    let typed_ast = try_create_typed_ast(input);
+
+    // Assert
+    assert_eq!(
+        typed_ast.err(),
+        Some("Cannot assign to immutable self, declare the parameter as 'mut self'".to_string())
+    );
+}
+
+#[test]
+fn self_parameter_outside_an_imp_block_is_an_error() {
+    // Arrange
+    let input = "fun double(mut self): Int => 2";
+
+    // Act
+    let typed_ast = try_create_typed_ast(input);
+
+    // Assert
+    assert_eq!(
+        typed_ast.err(),
+        Some("Function 'double' takes self, but is not declared in an imp block".to_string())
+    );
+}
@@ -0,0 +1,165 @@
+use std::{cell::RefCell, collections::HashSet, rc::Rc};
+
+use shared::{
+    lexer, parser,
+    parser::Statement,
+    type_checker::{self, incremental::IncrementalProgram, TypeCheckerConfig, TypeEnvironment},
+};
+
+fn parse(input: &str) -> Statement {
+    let tokens = lexer::tokenize(input).unwrap();
+    parser::create_ast(tokens, false).unwrap()
+}
+
+fn parse_declaration(input: &str) -> Statement {
+    let Statement::Program { mut statements } = parse(input) else {
+        panic!("Expected a program");
+    };
+
+    statements.remove(0)
+}
+
+fn create_program(input: &str) -> IncrementalProgram {
+    let type_environment = Rc::new(RefCell::new(TypeEnvironment::new(false)));
+    IncrementalProgram::new(parse(input), type_environment, TypeCheckerConfig::default()).unwrap()
+}
+
+const PROGRAM: &str = r#"
+    struct Point { x: Int, y: Int }
+    fun double(x: Int): Int => x * 2
+    fun quadruple(x: Int): Int => double(double(x))
+    fun origin(): Point => Point { x: 0, y: 0 }
+    fun unrelated(x: Int): Int => x - 1
+"#;
+
+#[test]
+fn dependencies_are_discovered() {
+    // Arrange
+    let program = create_program(PROGRAM);
+
+    // Act
+    let dependencies = program.dependencies();
+
+    // Assert
+    assert_eq!(
+        dependencies.dependencies("quadruple"),
+        Some(&HashSet::from(["double".to_string()]))
+    );
+    assert_eq!(
+        dependencies.dependencies("origin"),
+        Some(&HashSet::from(["Point".to_string()]))
+    );
+    assert_eq!(
+        dependencies.dependencies("unrelated"),
+        Some(&HashSet::new())
+    );
+}
+
+#[test]
+fn recheck_only_checks_changed_declaration_and_dependents() {
+    // Arrange
+    let mut program = create_program(PROGRAM);
+    let changed = parse_declaration("fun double(x: Int): Int => x + x");
+
+    // Act
+    let recheck = program.recheck(changed).unwrap();
+
+    // Assert
+    assert_eq!(
+        recheck.invalidated,
+        HashSet::from(["double".to_string(), "quadruple".to_string()])
+    );
+    assert_eq!(recheck.statements.len(), 2);
+    assert!(!recheck.invalidated.contains("unrelated"));
+    assert!(!recheck.invalidated.contains("origin"));
+}
+
+#[test]
+fn recheck_replaces_typed_declaration() {
+    // Arrange
+    let mut program = create_program(PROGRAM);
+    let changed = parse_declaration("fun unrelated(x: Int): Int => x + 1");
+    let expected = create_program(&PROGRAM.replace("x - 1", "x + 1")).typed_program();
+
+    // Act
+    program.recheck(changed).unwrap();
+
+    // Assert
+    assert_eq!(program.typed_program(), expected);
+}
+
+#[test]
+fn failed_recheck_leaves_program_unchanged() {
+    // Arrange
+    let mut program = create_program(PROGRAM);
+    let before = program.typed_program();
+    let changed = parse_declaration(r#"fun double(x: Int): String => "two""#);
+
+    // Act
+    let result = program.recheck(changed);
+
+    // Assert
+    assert!(result.is_err());
+    assert_eq!(program.typed_program(), before);
+}
+
+#[test]
+fn recheck_rejects_unknown_declaration() {
+    // Arrange
+    let mut program = create_program(PROGRAM);
+    let changed = parse_declaration("fun missing(x: Int): Int => x");
+
+    // Act
+    let result = program.recheck(changed);
+
+    // Assert
+    assert!(result.is_err());
+}
+
+#[test]
+fn typed_program_is_the_same_as_create_typed_ast() {
+    // Arrange
+    let input = r#"
+        fun add(a: Int, b: Int): Int => if a == 0 => b else => add(a - 1, b + 1)
+        fun twice(x: Int): Int => add(x, x)
+    "#;
+
+    let type_environment = Rc::new(RefCell::new(TypeEnvironment::new(false)));
+    let expected =
+        type_checker::create_typed_ast(parse(input), type_environment, Default::default()).unwrap();
+
+    // Act
+    let program = create_program(input);
+
+    // Assert
+    assert_eq!(program.typed_program(), expected);
+}
+
+#[test]
+fn variable_read_before_assignment_is_rejected() {
+    // Arrange
+    let input = "fun f(): Int => { let x: Int; x }";
+    let type_environment = Rc::new(RefCell::new(TypeEnvironment::new(false)));
+
+    // Act
+    let result =
+        IncrementalProgram::new(parse(input), type_environment, TypeCheckerConfig::default());
+
+    // Assert
+    assert!(result.is_err());
+}
+
+#[test]
+fn recheck_rejects_variable_read_before_assignment() {
+    // Arrange
+    let mut program = create_program(PROGRAM);
+    let before = program.typed_program();
+    let changed = parse_declaration("fun unrelated(x: Int): Int => { let y: Int; y }");
+
+    // Act
+    let result = program.recheck(changed);
+
+    // Assert
+    assert!(result.is_err());
+    assert_eq!(program.typed_program(), before);
+}
@@ -0,0 +1,202 @@
+mod common;
+
+use common::{check_with_env, create_type_env, tokenize};
+
+use shared::{parser, type_checker::interface::Interface};
+
+const GEOMETRY: &str = r#"
+pub mod geometry::shapes;
+pub struct Point { pub x: Int, pub y: Int }
+imp Point { fun sum(self): Int => self.x + self.y }
+pub fun origin(): Point => Point { x: 0, y: 0 }
+pub fun scale(p: Point, by: Int): Point => Point { x: p.x * by, y: p.y * by }
+struct Secret { value: Int }
+fun hidden(): Int => 1
+"#;
+
+/// Check the geometry module and give the source of its interface
+fn geometry_interface() -> String {
+    check_with_env(GEOMETRY, create_type_env()).unwrap();
+
+    let ast = parser::create_ast(tokenize(GEOMETRY), false).unwrap();
+    Interface::new(&ast).unwrap().serialize()
+}
+
+/// Check `source` against the loaded geometry interface
+fn check_against_interface(source: &str) -> Result<(), String> {
+    let type_environment = create_type_env();
+
+    Interface::deserialize(&geometry_interface())?.load(type_environment.clone())?;
+
+    check_with_env(source, type_environment)?;
+    Ok(())
+}
+
+#[test]
+fn module_checks_against_loaded_interface() {
+    // Arrange
+    let app = r#"
+    pub mod app;
+    use geometry::shapes::Point;
+    use geometry::shapes::origin;
+    use geometry::shapes::scale;
+    let p: Point = scale(origin(), 2);
+    let total = p.sum() + p.x;
+    "#;
+
+    // Act
+    let result = check_against_interface(app);
+
+    // Assert
+    assert_eq!(result, Ok(()));
+}
+
+#[test]
+fn interface_leaves_out_bodies_and_private_items() {
+    // Act
+    let interface = geometry_interface();
+
+    // Assert
+    assert_eq!(
+        interface,
+        r#"pub mod geometry::shapes;
+
+pub struct Point {
+    pub x: Int,
+    pub y: Int,
+}
+
+imp Point {
+    fun sum(self: Self): Int;
+}
+
+pub fun origin(): Point;
+
+pub fun scale(p: Point): fun(Int): Point;
+"#
+    );
+}
+
+#[test]
+fn private_item_is_not_in_interface() {
+    // Arrange
+    let app = r#"
+    pub mod app;
+    use geometry::shapes::hidden;
+    "#;
+
+    // Act
+    let result = check_against_interface(app);
+
+    // Assert
+    assert!(result.is_err());
+}
+
+#[test]
+fn public_function_without_return_type_fails() {
+    // Arrange
+    let ast = parser::create_ast(tokenize("pub fun one() => 1"), false).unwrap();
+
+    // Act
+    let result = Interface::new(&ast);
+
+    // Assert
+    assert_eq!(
+        result,
+        Err("Function 'one' needs a return type to be part of an interface".to_string())
+    );
+}
+
+#[test]
+fn public_function_naming_private_type_fails() {
+    // Arrange
+    let source = "struct Hidden { a: Int } pub fun f(h: Hidden): Int => h.a";
+    let ast = parser::create_ast(tokenize(source), false).unwrap();
+
+    // Act
+    let result = Interface::new(&ast);
+
+    // Assert
+    assert_eq!(
+        result,
+        Err(
+            "Function 'f' can't be part of an interface, as its signature names private type 'Hidden'"
+                .to_string()
+        )
+    );
+}
+
+#[test]
+fn public_function_naming_public_type_loads() {
+    // Arrange
+    let source = "pub mod shown; pub struct Shown { a: Int } pub fun f(s: Shown): Int => s.a";
+    let ast = parser::create_ast(tokenize(source), false).unwrap();
+    let serialized = Interface::new(&ast).unwrap().serialize();
+
+    // Act
+    let result =
+        Interface::deserialize(&serialized).and_then(|interface| interface.load(create_type_env()));
+
+    // Assert
+    assert_eq!(result, Ok(()));
+}
+
+#[test]
+fn same_declaration_gets_same_type_id() {
+    // Arrange
+    let declare = |source: &str| {
+        let type_environment = create_type_env();
+        check_with_env(source, type_environment.clone()).unwrap();
+
+        let type_id = type_environment.borrow().get_type_id("Point");
+        type_id
+    };
+
+    // Act
+    let first = declare(GEOMETRY);
+    let second = declare(GEOMETRY);
+    let elsewhere = declare("pub mod drawing; pub struct Point { pub x: Int, pub y: Int }");
+
+    // Assert
+    assert!(first.is_some());
+    assert_eq!(first, second);
+    assert_ne!(first, elsewhere);
+}
+
+#[test]
+fn matching_local_declaration_is_the_interface_type() {
+    // Arrange
+    let app = r#"
+    pub mod geometry::shapes;
+    pub struct Point { pub x: Int, pub y: Int }
+    let p: Point = origin();
+    "#;
+
+    // Act
+    let result = check_against_interface(app);
+
+    // Assert
+    assert_eq!(result, Ok(()));
+}
+
+#[test]
+fn incompatible_local_declaration_fails() {
+    // Arrange
+    let app = r#"
+    pub mod geometry::shapes;
+    pub struct Point { pub x: Int }
+    "#;
+
+    // Act
+    let result = check_against_interface(app);
+
+    // Assert
+    let error = result.unwrap_err();
+    assert!(
+        error.starts_with(
+            "Type Point in module 'geometry::shapes' is not ABI compatible with the one already declared"
+        ),
+        "{}",
+        error
+    );
+}
@@ -0,0 +1,48 @@
+use std::{cell::RefCell, rc::Rc};
+
+use shared::type_checker::{interner::Interner, Type, TypeEnvironment};
+
+#[test]
+fn interning_twice_yields_same_symbol() {
+    // Arrange
+    let mut interner = Interner::new();
+
+    // Act
+    let first = interner.intern("Point");
+    let second = interner.intern("Point");
+    let other = interner.intern("Line");
+
+    // Assert
+    assert_eq!(first, second);
+    assert_ne!(first, other);
+    assert_eq!(interner.resolve(first), "Point");
+    assert_eq!(interner.len(), 2);
+}
+
+#[test]
+fn get_does_not_intern() {
+    // Arrange
+    let interner = Interner::new();
+
+    // Act
+    let symbol = interner.get("Point");
+
+    // Assert
+    assert_eq!(symbol, None);
+    assert!(interner.is_empty());
+}
+
+#[test]
+fn child_environments_share_interner() {
+    // Arrange
+    let root = Rc::new(RefCell::new(TypeEnvironment::new(false)));
+    let child = TypeEnvironment::new_parent(root.clone());
+
+    // Act
+    let symbol = child.interner().borrow().get("Int").unwrap();
+
+    // Assert
+    assert_eq!(root.borrow().get_type_by_symbol(symbol), Some(Type::Int));
+    assert_eq!(child.get_type_by_symbol(symbol), Some(Type::Int));
+    assert_eq!(child.get_type("Missing"), None);
+}
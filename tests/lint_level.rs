@@ -0,0 +1,82 @@
+mod common;
+
+use std::collections::HashMap;
+
+use common::{create_type_env, tokenize};
+
+use shared::{
+    diagnostic::Severity,
+    parser,
+    type_checker::{self, CheckedProgram, LintLevel, TypeCheckerConfig},
+};
+
+fn check(input: &str, lint_levels: &[(&str, LintLevel)]) -> CheckedProgram {
+    let type_environment = create_type_env();
+    let ast = parser::create_ast(tokenize(input), false).unwrap();
+
+    let config = TypeCheckerConfig {
+        lint_levels: lint_levels
+            .iter()
+            .map(|(category, level)| (category.to_string(), *level))
+            .collect::<HashMap<_, _>>(),
+        ..Default::default()
+    };
+
+    type_checker::check_program(ast, type_environment, config)
+}
+
+#[test]
+fn unused_variable_is_a_warning_by_default() {
+    // Arrange
+    let input = "fun f(a: Int): Int => { let b = a * 2; a }";
+
+    // Act
+    let checked = check(input, &[]);
+
+    // Assert
+    assert!(!checked.has_errors);
+    assert!(checked.typed_program.is_some());
+    assert_eq!(checked.diagnostics.len(), 1);
+    assert_eq!(checked.diagnostics[0].severity, Severity::Warning);
+    assert_eq!(
+        checked.diagnostics[0].category.as_deref(),
+        Some("unused_variable")
+    );
+}
+
+#[test]
+fn denied_unused_variable_is_an_error() {
+    // Arrange
+    let input = "fun f(a: Int): Int => { let b = a * 2; a }";
+
+    // Act
+    let checked = check(input, &[("unused_variable", LintLevel::Deny)]);
+
+    // Assert
+    assert!(checked.has_errors);
+    assert_eq!(checked.diagnostics.len(), 1);
+    assert_eq!(checked.diagnostics[0].severity, Severity::Error);
+    assert_eq!(
+        checked.diagnostics[0].message,
+        "Variable 'b' in function 'f' is never read, prefix it with '_' if that is intended"
+    );
+}
+
+#[test]
+fn allowed_warnings_are_left_out() {
+    // Arrange
+    let input = "fun f(a: Int): Int => { let b = a * 2; a + 1 a }";
+
+    // Act
+    let checked = check(input, &[("unused_variable", LintLevel::Allow)]);
+
+    // Assert
+    let categories: Vec<Option<&str>> = checked
+        .diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic.category.as_deref())
+        .collect();
+
+    assert!(!checked.has_errors);
+    assert_eq!(categories, vec![Some("unused_result")]);
+}
@@ -3,7 +3,10 @@ mod common;
 use common::{create_typed_ast, evaluate_expression, StatementExt, VecStatementExt};
 
 use interpreter::{value, Value};
-use shared::type_checker::ast::{Literal, TypedExpression};
+use shared::{
+    lexer,
+    type_checker::ast::{EnumMemberFieldInitializers, Literal, TypedExpression},
+};
 
 use crate::common::create_env;
 
@@ -39,7 +42,7 @@ fn literal_has_correct_type() {
         .unwrap_expression();
 
     match expression {
-        TypedExpression::Literal(literal) => {
+        TypedExpression::Literal(literal, _) => {
             assert!(matches!(literal, Literal::Int(_)));
         }
         _ => panic!("Expected a literal expression, but found {:?}", expression),
@@ -57,3 +60,80 @@ fn literal_returns_correct_value() {
     // Assert
     assert_eq!(value, Value::Number(value::Number::Int(1)));
 }
+
+#[test]
+fn raw_string_keeps_backslashes() {
+    // Arrange
+    let input = r#"r"C:\new\table""#;
+
+    // Act
+    let result = evaluate_expression(input, create_env(), false);
+
+    // Assert
+    assert_eq!(result, Value::String(r"C:\new\table".to_string()));
+}
+
+#[test]
+fn multi_line_string_keeps_line_breaks() {
+    // Arrange
+    let input = "\"\"\"\nfirst \"line\"\nsecond line\"\"\"";
+
+    // Act
+    let result = evaluate_expression(input, create_env(), false);
+
+    // Assert
+    assert_eq!(
+        result,
+        Value::String("first \"line\"\nsecond line".to_string())
+    );
+}
+
+#[test]
+fn unterminated_string_is_an_error() {
+    // Arrange
+    let input = "let a = 1;\nlet b = \"abc;";
+
+    // Act
+    let tokens = lexer::tokenize(input);
+
+    // Assert
+    assert_eq!(
+        tokens,
+        Err("Unterminated string starting at line 2, column 9".to_string())
+    );
+}
+
+#[test]
+fn fieldless_enum_member_literal_has_no_field_initializers() {
+    // Arrange
+    let input = r#"
+        enum Color { Red, Green, Blue }
+        Color::Red
+        "#;
+
+    // Act
+    let typed_ast = create_typed_ast(input);
+
+    // Assert
+    let expression = typed_ast
+        .unwrap_program()
+        .nth_statement(1)
+        .unwrap_expression();
+
+    let TypedExpression::Literal(
+        Literal::Enum {
+            member,
+            field_initializers,
+            type_,
+            ..
+        },
+        _,
+    ) = expression
+    else {
+        panic!("Expected an enum literal but got {:?}", expression);
+    };
+
+    assert_eq!(member, "Red");
+    assert_eq!(field_initializers, EnumMemberFieldInitializers::None);
+    assert_eq!(type_.to_string(), "Color::Red");
+}
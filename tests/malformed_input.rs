@@ -0,0 +1,54 @@
+// Inputs the `parse` fuzz target found to panic or hang, which should be errors instead
+
+use shared::lexer;
+
+#[test]
+fn unrecognized_character_is_an_error() {
+    // Arrange
+    let input = "let a = `b`";
+
+    // Act
+    let tokens = lexer::tokenize(input);
+
+    // Assert
+    assert_eq!(tokens, Err("Unrecognized character: `".to_string()));
+}
+
+#[test]
+fn nul_character_is_an_error() {
+    // Arrange
+    let input = "g\0\0";
+
+    // Act
+    let tokens = lexer::tokenize(input);
+
+    // Assert
+    assert_eq!(tokens, Err("Unrecognized character: \0".to_string()));
+}
+
+#[test]
+fn base_prefix_without_digits_is_an_error() {
+    // Arrange
+    let input = "let a = 0x;";
+
+    // Act
+    let tokens = lexer::tokenize(input);
+
+    // Assert
+    assert_eq!(tokens, Err("Invalid numeric literal: 0x".to_string()));
+}
+
+#[test]
+fn unterminated_character_is_an_error() {
+    // Arrange
+    let input = "let c = '";
+
+    // Act
+    let tokens = lexer::tokenize(input);
+
+    // Assert
+    assert_eq!(
+        tokens,
+        Err("Unterminated character starting at line 1, column 9".to_string())
+    );
+}
@@ -1,6 +1,8 @@
 mod common;
 
-use common::{create_typed_ast, evaluate_expression, StatementExt, VecStatementExt};
+use common::{
+    create_typed_ast, evaluate_expression, try_create_typed_ast, StatementExt, VecStatementExt,
+};
 
 use interpreter::{value, Value};
 use shared::type_checker::{
@@ -265,3 +267,157 @@ fn match_variable() {
     // Assert
     assert_eq!(result, Value::Number(value::Number::Int(1)));
 }
+
+#[test]
+fn match_on_union_literals_is_exhaustive() {
+    // Arrange
+    let input = r#"
+        union Small { 1, 2, 3 }
+        let s: Small = 3;
+        s match
+        | 1 => 10,
+        | 2 => 20,
+        | 3 => 30
+        "#;
+
+    // Act
+    let result = evaluate_expression(input, create_env(), false);
+
+    // Assert
+    assert_eq!(result, Value::Number(value::Number::Int(30)));
+}
+
+#[test]
+fn match_on_literal_not_in_union_is_an_error() {
+    // Arrange
+    let input = r#"
+        union Small { 1, 2, 3 }
+        let s: Small = 3;
+        s match
+        | 1 => 10,
+        | 5 => 50,
+        | _ => 0
+        "#;
+
+    // Act
+    let result = try_create_typed_ast(input);
+
+    // Assert
+    assert_eq!(
+        result,
+        Err("Pattern 5 is not a member of union 'Small'".to_string())
+    );
+}
+
+#[test]
+fn match_on_negative_int() {
+    // Arrange
+    let input = r#"
+        let x = -1;
+        x match
+        | 1 => 1,
+        | -1 => 2,
+        | _ => 3
+        "#;
+
+    // Act
+    let result = evaluate_expression(input, create_env(), false);
+
+    // Assert
+    assert_eq!(result, Value::Number(value::Number::Int(2)));
+}
+
+#[test]
+fn match_on_negative_float() {
+    // Arrange
+    let input = r#"
+        let x = -1.5;
+        x match
+        | 1.5 => 1,
+        | -1.5 => 2,
+        | _ => 3
+        "#;
+
+    // Act
+    let result = evaluate_expression(input, create_env(), false);
+
+    // Assert
+    assert_eq!(result, Value::Number(value::Number::Int(2)));
+}
+
+#[test]
+fn match_on_fieldless_enum_members() {
+    // Arrange
+    let input = r#"
+        enum Color { Red, Green, Blue }
+        fun rank(c: Color): Int => c match
+        | Color::Red => 1,
+        | Color::Green => 2,
+        | Color::Blue => 3;
+        rank(Color::Blue)
+        "#;
+
+    // Act
+    let result = evaluate_expression(input, create_env(), false);
+
+    // Assert
+    assert_eq!(result, Value::Number(value::Number::Int(3)));
+}
+
+#[test]
+fn match_on_fieldless_enum_member_literal() {
+    // Arrange
+    let input = r#"
+        enum Color { Red, Green, Blue }
+        let c = Color::Green;
+        c match
+        | Color::Green => 2,
+        | _ => 0
+        "#;
+
+    // Act
+    let result = evaluate_expression(input, create_env(), false);
+
+    // Assert
+    assert_eq!(result, Value::Number(value::Number::Int(2)));
+}
+
+#[test]
+fn match_on_fieldless_and_field_enum_members() {
+    // Arrange
+    let input = r#"
+        enum Shape { Circle { radius: Int }, Empty }
+        fun size(s: Shape): Int => s match
+        | Shape::Empty => 0,
+        | Shape::Circle { radius } => radius;
+        size(Shape::Circle { radius: 4 }) + size(Shape::Empty)
+        "#;
+
+    // Act
+    let result = evaluate_expression(input, create_env(), false);
+
+    // Assert
+    assert_eq!(result, Value::Number(value::Number::Int(4)));
+}
+
+#[test]
+fn match_on_enum_missing_member_is_an_error() {
+    // Arrange
+    let input = r#"
+        enum Color { Red, Green, Blue }
+        fun rank(c: Color): Int => c match
+        | Color::Green => 2;
+        "#;
+
+    // Act
+    let result = try_create_typed_ast(input);
+
+    // Assert
+    assert_eq!(
+        result,
+        Err(
+            "Match on enum 'Color' is not exhaustive, Color::Blue, Color::Red not matched"
+                .to_string()
+        )
+    );
+}
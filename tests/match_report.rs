@@ -0,0 +1,67 @@
+mod common;
+
+use common::create_typed_ast;
+
+use shared::type_checker::match_report::{match_reports, MatchReport};
+
+fn only_report(input: &str) -> MatchReport {
+    let typed_ast = create_typed_ast(input);
+
+    let mut reports = match_reports(&typed_ast);
+    assert_eq!(reports.len(), 1, "Expected one match but got {:?}", reports);
+
+    reports.remove(0)
+}
+
+#[test]
+fn redundant_arm_and_missing_constructor_are_reported() {
+    // Arrange
+    let input = r#"
+        let b: Bool = true;
+        let r = b match
+        | true => 1,
+        | true => 2;
+        "#;
+
+    // Act
+    let report = only_report(input);
+
+    // Assert
+    assert_eq!(report.reachable, vec![0]);
+    assert_eq!(report.redundant, vec![1]);
+    assert_eq!(report.missing, vec!["false".to_string()]);
+}
+
+#[test]
+fn exhaustive_match_has_no_missing_constructors() {
+    // Arrange
+    let input = r#"
+        let b: Bool = true;
+        let r = b match
+        | true => 1,
+        | _ => 2;
+        "#;
+
+    // Act
+    let report = only_report(input);
+
+    // Assert
+    assert_eq!(report.reachable, vec![0, 1]);
+    assert!(report.redundant.is_empty());
+    assert!(report.missing.is_empty());
+}
+
+#[test]
+fn report_is_keyed_by_the_span_of_the_match() {
+    // Arrange
+    let input = "let r = 1 match | 1 => 1, | _ => 2;";
+
+    // Act
+    let report = only_report(input);
+
+    // Assert
+    assert_eq!(
+        &input[report.span.start..report.span.end],
+        "1 match | 1 => 1, | _ => 2"
+    );
+}
@@ -0,0 +1,116 @@
+mod common;
+
+use common::evaluate_with;
+
+use interpreter::{value::Number, Value};
+
+const DECLARATIONS: &str = r#"
+proto Size { fun size(self): Int; }
+proto Weight { fun weight(self): Int; }
+proto Heavy { fun weight(self): Int; }
+struct Crate { size: Int, items: Int }
+imp Crate { fun label(self): Int => 100 }
+imp Size for Crate { fun size(self): Int => 200 }
+imp Weight for Crate { fun weight(self): Int => self.items * 2 }
+fun total(c: Crate, extra: Int): Int => c.items + extra
+fun label(c: Crate): Int => 300
+"#;
+
+#[test]
+fn field_is_found_before_methods() {
+    // Arrange
+    let input = "let c = Crate { size: 1, items: 2 }; c.size";
+
+    // Act
+    let value = evaluate_with(DECLARATIONS, input);
+
+    // Assert
+    assert_eq!(value, Ok(Value::Number(Number::Int(1))));
+}
+
+#[test]
+fn inherent_method_is_found_before_protocol_methods_and_free_functions() {
+    // Arrange
+    let input = r#"
+    proto Label { fun label(self): Int; }
+    imp Label for Crate { fun label(self): Int => 0 }
+    let c = Crate { size: 1, items: 2 };
+    c.label()
+    "#;
+
+    // Act
+    let value = evaluate_with(DECLARATIONS, input);
+
+    // Assert
+    assert_eq!(value, Ok(Value::Number(Number::Int(100))));
+}
+
+#[test]
+fn protocol_method_is_found_when_the_type_has_no_inherent_method() {
+    // Arrange
+    let input = "let c = Crate { size: 1, items: 2 }; c.weight()";
+
+    // Act
+    let value = evaluate_with(DECLARATIONS, input);
+
+    // Assert
+    assert_eq!(value, Ok(Value::Number(Number::Int(4))));
+}
+
+#[test]
+fn method_call_falls_back_to_a_uniform_function_call() {
+    // Arrange
+    let input = "let c = Crate { size: 1, items: 2 }; c.total(5) == total(c, 5)";
+
+    // Act
+    let value = evaluate_with(DECLARATIONS, input);
+
+    // Assert
+    assert_eq!(value, Ok(Value::Bool(true)));
+}
+
+#[test]
+fn param_propagation_looks_for_free_functions_first() {
+    // Arrange
+    let input = "let c = Crate { size: 1, items: 2 }; c:label() + c:weight";
+
+    // Act
+    let value = evaluate_with(DECLARATIONS, input);
+
+    // Assert
+    assert_eq!(value, Ok(Value::Number(Number::Int(304))));
+}
+
+#[test]
+fn protocol_methods_with_the_same_name_are_ambiguous() {
+    // Arrange
+    let input = r#"
+    imp Heavy for Crate { fun weight(self): Int => 0 }
+    let c = Crate { size: 1, items: 2 };
+    c.weight()
+    "#;
+
+    // Act
+    let value = evaluate_with(DECLARATIONS, input);
+
+    // Assert
+    assert_eq!(
+        value,
+        Err("'weight' is ambiguous for 'Crate', it is implemented by protocols 'Weight' and 'Heavy'".to_string())
+    );
+}
+
+#[test]
+fn no_matching_member_is_an_error() {
+    // Arrange
+    let input = "let c = Crate { size: 1, items: 2 }; c.open()";
+
+    // Act
+    let value = evaluate_with(DECLARATIONS, input);
+
+    // Assert
+    assert_eq!(
+        value,
+        Err("'Crate' has no field, method or function called 'open'".to_string())
+    );
+}
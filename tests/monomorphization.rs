@@ -0,0 +1,78 @@
+mod common;
+
+use common::create_typed_ast;
+
+use shared::{
+    type_checker::monomorphization::{monomorphizations, Monomorphization},
+    types::TypeAnnotation,
+};
+
+fn instantiation(function: &str, type_arguments: &[&str]) -> Monomorphization {
+    Monomorphization {
+        function: function.to_string(),
+        type_arguments: type_arguments
+            .iter()
+            .map(|t| TypeAnnotation::Type(t.to_string()))
+            .collect(),
+    }
+}
+
+#[test]
+fn generic_function_called_with_two_types_is_instantiated_twice() {
+    // Arrange
+    let input = r#"
+        fun id<T>(x: T): T => x
+        id::<Int>(1);
+        id::<String>("a");
+        "#;
+
+    let typed_ast = create_typed_ast(input);
+
+    // Act
+    let monomorphizations = monomorphizations(&typed_ast);
+
+    // Assert
+    assert_eq!(
+        monomorphizations,
+        vec![
+            instantiation("id", &["Int"]),
+            instantiation("id", &["String"])
+        ]
+    );
+}
+
+#[test]
+fn identical_instantiations_are_recorded_once() {
+    // Arrange
+    let input = r#"
+        fun id<T>(x: T): T => x
+        fun twice(x: Int): Int => id::<Int>(id::<Int>(x))
+        id::<Int>(1);
+        "#;
+
+    let typed_ast = create_typed_ast(input);
+
+    // Act
+    let monomorphizations = monomorphizations(&typed_ast);
+
+    // Assert
+    assert_eq!(monomorphizations, vec![instantiation("id", &["Int"])]);
+}
+
+#[test]
+fn calls_passing_on_generics_are_not_instantiations() {
+    // Arrange
+    let input = r#"
+        fun id<T>(x: T): T => x
+        fun wrap<T>(x: T): T => id::<T>(x)
+        wrap::<Bool>(true);
+        "#;
+
+    let typed_ast = create_typed_ast(input);
+
+    // Act
+    let monomorphizations = monomorphizations(&typed_ast);
+
+    // Assert
+    assert_eq!(monomorphizations, vec![instantiation("wrap", &["Bool"])]);
+}
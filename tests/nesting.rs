@@ -0,0 +1,186 @@
+mod common;
+
+use common::{check_with_env, create_type_env, tokenize};
+
+use shared::{
+    display::{Indent, IndentDisplay},
+    parser::{
+        self,
+        build::{block, int, program, statement, unary},
+        UnaryOperator,
+    },
+    type_checker::{self, TypeCheckerConfig},
+};
+
+#[test]
+fn parsing_deeply_nested_parentheses_fails_gracefully() {
+    // Arrange
+    let source = format!("{}1{}", "(".repeat(10_000), ")".repeat(10_000));
+
+    // Act
+    let result = parser::create_ast_with_max_depth(tokenize(&source), false, 10);
+
+    // Assert
+    assert_eq!(
+        result,
+        Err("Expression nesting is too deep, the limit is 10 levels".to_string())
+    );
+}
+
+#[test]
+fn parsing_nesting_within_the_limit_succeeds() {
+    // Arrange
+    let source = format!("{}1{}", "(".repeat(5), ")".repeat(5));
+
+    // Act
+    let result = parser::create_ast_with_max_depth(tokenize(&source), false, 10);
+
+    // Assert
+    assert!(result.is_ok());
+}
+
+#[test]
+fn checking_deeply_nested_expression_fails_gracefully() {
+    // Arrange
+    let mut expression = int(1);
+
+    for _ in 0..10_000 {
+        expression = unary(UnaryOperator::Negate, expression);
+    }
+
+    let program = program(vec![statement(expression)]);
+    let type_environment = create_type_env();
+
+    let config = TypeCheckerConfig {
+        max_depth: Some(5),
+        ..Default::default()
+    };
+
+    // Act
+    let result = type_checker::create_typed_ast(program, type_environment, config);
+
+    // Assert
+    assert_eq!(
+        result.unwrap_err(),
+        "Expression nesting is too deep, the limit is 5 levels"
+    );
+}
+
+#[test]
+fn checking_long_flat_chain_succeeds() {
+    // Arrange
+    let source = vec!["1"; 128].join(" + ");
+    let program = parser::create_ast(tokenize(&source), false).unwrap();
+    let type_environment = create_type_env();
+
+    let config = TypeCheckerConfig {
+        max_depth: Some(5),
+        ..Default::default()
+    };
+
+    // Act
+    let result = type_checker::create_typed_ast(program, type_environment, config);
+
+    // Assert
+    assert!(result.is_ok(), "{:?}", result.err());
+}
+
+#[test]
+fn displaying_deeply_nested_block_succeeds() {
+    // Arrange
+    let mut expression = int(1);
+
+    for _ in 0..10_000 {
+        expression = block(vec![statement(expression)]);
+    }
+
+    // Act
+    let display = expression.indent_display(&mut Indent::new());
+
+    // Assert
+    let lines: Vec<&str> = display.lines().collect();
+
+    assert_eq!(lines.len(), 10_001);
+    assert_eq!(lines[0], "<block>");
+    assert_eq!(lines[1], "╰─<block>");
+    assert_eq!(lines[10_000], format!("{}╰─1", "  ".repeat(9_999)));
+}
+
+#[test]
+fn checking_self_referring_generic_succeeds() {
+    // Arrange
+    let source = "struct Nest<T> { value: T, next: Option<Nest<T>> }";
+
+    // Act
+    let result = check_with_env(source, create_type_env());
+
+    // Assert
+    assert!(result.is_ok());
+}
+
+#[test]
+fn checking_linked_list_succeeds() {
+    // Arrange
+    let source = r#"
+    struct Node { value: Int, next: Option<Node> }
+    fun single(value: Int): Node => Node { value: value, next: if false => single(0) }
+    let first = Node { value: 1, next: if true => single(2) };
+    "#;
+
+    // Act
+    let result = check_with_env(source, create_type_env());
+
+    // Assert
+    assert!(result.is_ok());
+}
+
+#[test]
+fn checking_nested_generics_within_the_limit_succeeds() {
+    // Arrange
+    let source = r#"
+    struct List<T> { value: T }
+    let list: List<List<Int>> = List<List<Int>> { value: List<Int> { value: 1 } };
+    "#;
+
+    let program = parser::create_ast(tokenize(source), false).unwrap();
+    let type_environment = create_type_env();
+
+    let config = TypeCheckerConfig {
+        max_instantiation_depth: Some(2),
+        ..Default::default()
+    };
+
+    // Act
+    let result = type_checker::create_typed_ast(program, type_environment, config);
+
+    // Assert
+    assert!(result.is_ok(), "{:?}", result);
+}
+
+#[test]
+fn checking_nested_generics_beyond_the_limit_fails() {
+    // Arrange
+    let source = r#"
+    struct List<T> { value: T }
+    let list: List<List<List<Int>>> = List<List<List<Int>>> {
+        value: List<List<Int>> { value: List<Int> { value: 1 } }
+    };
+    "#;
+
+    let program = parser::create_ast(tokenize(source), false).unwrap();
+    let type_environment = create_type_env();
+
+    let config = TypeCheckerConfig {
+        max_instantiation_depth: Some(2),
+        ..Default::default()
+    };
+
+    // Act
+    let result = type_checker::create_typed_ast(program, type_environment, config);
+
+    // Assert
+    assert_eq!(
+        result.unwrap_err(),
+        "Generic instantiation too deep at List<Int>, the limit is 2 levels"
+    );
+}
@@ -0,0 +1,149 @@
+mod common;
+
+use common::{create_env, create_typed_ast, evaluate_expression, StatementExt, VecStatementExt};
+
+use interpreter::{value::Number, Value};
+use shared::{
+    type_checker::{ast::Typed, Newtype, Type},
+    types::TypeIdentifier,
+};
+
+#[test]
+fn newtype_declaration_has_newtype_type() {
+    // Arrange
+    let input = r#"
+        newtype Meters = Float;
+        "#;
+
+    // Act
+    let typed_ast = create_typed_ast(input);
+
+    // Assert
+    let statement = typed_ast.unwrap_program().nth_statement(0);
+
+    assert_eq!(
+        statement.get_type(),
+        Type::Newtype(Newtype {
+            type_identifier: TypeIdentifier::Type("Meters".to_owned()),
+            underlying_type: Box::new(Type::Float),
+        })
+    );
+}
+
+#[test]
+fn newtype_can_be_wrapped_from_underlying_type() {
+    // Arrange
+    let input = r#"
+        newtype Meters = Float;
+        let m: Meters = Meters(1.5);
+        "#;
+
+    // Act
+    let typed_ast = create_typed_ast(input);
+
+    // Assert
+    let expression = typed_ast
+        .unwrap_program()
+        .nth_statement(1)
+        .unwrap_semi()
+        .unwrap_expression();
+
+    assert_eq!(
+        expression.get_type(),
+        Type::Newtype(Newtype {
+            type_identifier: TypeIdentifier::Type("Meters".to_owned()),
+            underlying_type: Box::new(Type::Float),
+        })
+    );
+}
+
+#[test]
+fn newtype_can_be_unwrapped_to_underlying_type() {
+    // Arrange
+    let input = r#"
+        newtype Meters = Float;
+        let m = Meters(1.5);
+        let f: Float = m.value;
+        "#;
+
+    // Act
+    let typed_ast = create_typed_ast(input);
+
+    // Assert
+    let expression = typed_ast
+        .unwrap_program()
+        .nth_statement(2)
+        .unwrap_semi()
+        .unwrap_expression();
+
+    assert_eq!(expression.get_type(), Type::Float);
+}
+
+#[test]
+#[should_panic]
+fn newtype_is_not_assignable_from_underlying_type() {
+    // Arrange
+    let input = r#"
+        newtype Meters = Float;
+        let m: Meters = 1.5;
+        "#;
+
+    // Act
+    create_typed_ast(input); // panics
+}
+
+#[test]
+#[should_panic]
+fn underlying_type_is_not_assignable_from_newtype() {
+    // Arrange
+    let input = r#"
+        newtype Meters = Float;
+        let f: Float = Meters(1.5);
+        "#;
+
+    // Act
+    create_typed_ast(input); // panics
+}
+
+#[test]
+#[should_panic]
+fn newtype_arithmetic_requires_unwrapping() {
+    // Arrange
+    let input = r#"
+        newtype Meters = Float;
+        let m = Meters(1.5);
+        m + 1.0
+        "#;
+
+    // Act
+    create_typed_ast(input); // panics
+}
+
+#[test]
+#[should_panic]
+fn newtype_cannot_be_wrapped_from_a_different_type() {
+    // Arrange
+    let input = r#"
+        newtype Meters = Float;
+        Meters("1.5")
+        "#;
+
+    // Act
+    create_typed_ast(input); // panics
+}
+
+#[test]
+fn newtype_unwraps_to_the_wrapped_value() {
+    // Arrange
+    let input = r#"
+        newtype Meters = Float;
+        let m = Meters(1.5);
+        m.value + 1.0
+        "#;
+
+    // Act
+    let value = evaluate_expression(input, create_env(), false);
+
+    // Assert
+    assert_eq!(value, Value::Number(Number::Float(2.5)));
+}
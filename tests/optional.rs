@@ -0,0 +1,85 @@
+mod common;
+
+use common::evaluate_with;
+
+use interpreter::{value::Number, Value};
+
+const NESTED: &str = r#"
+struct Inner { value: Int }
+struct Outer { inner: Inner? }
+"#;
+
+#[test]
+fn chaining_through_two_optionals_reaches_the_value() {
+    // Arrange
+    let input = r#"
+    let o: Outer? = if true => Outer { inner: if true => Inner { value: 5 } };
+    o?.inner?.value ?? 0
+    "#;
+
+    // Act
+    let value = evaluate_with(NESTED, input);
+
+    // Assert
+    assert_eq!(value, Ok(Value::Number(Number::Int(5))));
+}
+
+#[test]
+fn chaining_stops_at_the_first_none() {
+    // Arrange
+    let input = r#"
+    let o: Outer? = if true => Outer { inner: if false => Inner { value: 5 } };
+    o?.inner?.value ?? 7
+    "#;
+
+    // Act
+    let value = evaluate_with(NESTED, input);
+
+    // Assert
+    assert_eq!(value, Ok(Value::Number(Number::Int(7))));
+}
+
+#[test]
+fn coalescing_takes_the_value_or_the_default() {
+    // Arrange
+    let input = r#"
+    fun or_zero(n: Int?): Int => n ?? 0
+    or_zero(if true => 2) * 10 + or_zero(if false => 2)
+    "#;
+
+    // Act
+    let value = evaluate_with(NESTED, input);
+
+    // Assert
+    assert_eq!(value, Ok(Value::Number(Number::Int(20))));
+}
+
+#[test]
+fn coalescing_default_must_match_the_inner_type() {
+    // Arrange
+    let input = "let n: Int? = if true => 1; n ?? 1 > 2";
+
+    // Act
+    let value = evaluate_with(NESTED, input);
+
+    // Assert
+    assert_eq!(
+        value,
+        Err("Default of ?? must have type Int, found Bool".to_string())
+    );
+}
+
+#[test]
+fn coalescing_a_value_which_is_not_an_option_is_an_error() {
+    // Arrange
+    let input = "let i = Inner { value: 1 }; i ?? i";
+
+    // Act
+    let value = evaluate_with(NESTED, input);
+
+    // Assert
+    assert_eq!(
+        value,
+        Err("Cannot use ?? on Inner, it is not an option".to_string())
+    );
+}
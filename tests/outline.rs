@@ -0,0 +1,92 @@
+mod common;
+
+use common::tokenize;
+
+use shared::{
+    parser,
+    type_checker::outline::{outline, OutlineKind, SymbolNode},
+};
+
+/// The name, kind and written text of every node, with the children of a node indented under it
+fn describe(nodes: &[SymbolNode], source: &str, depth: usize) -> Vec<String> {
+    nodes
+        .iter()
+        .flat_map(|node| {
+            let line = format!(
+                "{}{} {:?} {}",
+                "  ".repeat(depth),
+                node.name,
+                node.kind,
+                &source[node.span.start..node.span.end]
+            );
+
+            std::iter::once(line).chain(describe(&node.children, source, depth + 1))
+        })
+        .collect()
+}
+
+#[test]
+fn outline_nests_declarations_in_their_module() {
+    // Arrange
+    let source = r#"
+    pub mod shapes::flat;
+    struct Point { x: Int, y: Int }
+    enum Shape { Dot, Line { length: Int } }
+    fun area(point: Point): Int => point.x * point.y
+    "#;
+
+    let ast = parser::create_ast(tokenize(source), false).unwrap();
+
+    // Act
+    let outline = outline(&ast);
+
+    // Assert
+    assert_eq!(
+        describe(&outline, source, 0),
+        vec![
+            "shapes Module shapes::flat",
+            "  flat Module shapes::flat",
+            "    Point Struct Point",
+            "      x Field x",
+            "      y Field y",
+            "    Shape Enum Shape",
+            "      Dot EnumMember Dot",
+            "      Line EnumMember Line",
+            "    area Function area",
+        ]
+    );
+}
+
+#[test]
+fn outline_without_module_lists_declarations_at_the_top() {
+    // Arrange
+    let source = "enum Shape { size: Int, Dot, Square }\nfun main(): Int => 1";
+    let ast = parser::create_ast(tokenize(source), false).unwrap();
+
+    // Act
+    let outline = outline(&ast);
+
+    // Assert
+    let kinds = |nodes: &[SymbolNode]| -> Vec<(String, OutlineKind)> {
+        nodes
+            .iter()
+            .map(|node| (node.name.clone(), node.kind))
+            .collect()
+    };
+
+    assert_eq!(
+        kinds(&outline),
+        vec![
+            ("Shape".to_string(), OutlineKind::Enum),
+            ("main".to_string(), OutlineKind::Function),
+        ]
+    );
+    assert_eq!(
+        kinds(&outline[0].children),
+        vec![
+            ("size".to_string(), OutlineKind::Field),
+            ("Dot".to_string(), OutlineKind::EnumMember),
+            ("Square".to_string(), OutlineKind::EnumMember),
+        ]
+    );
+}
@@ -0,0 +1,167 @@
+mod common;
+
+use common::{try_create_typed_ast_with_config, StatementExt, VecStatementExt};
+
+use shared::type_checker::{
+    ast::{Literal, TypedExpression},
+    OverflowPolicy, TypeCheckerConfig,
+};
+
+fn config(overflow_policy: OverflowPolicy) -> TypeCheckerConfig {
+    TypeCheckerConfig {
+        overflow_policy,
+        ..Default::default()
+    }
+}
+
+/// The value folded into the binary expression of the last statement
+fn folded(input: &str, overflow_policy: OverflowPolicy) -> Option<Literal> {
+    let statements = try_create_typed_ast_with_config(input, config(overflow_policy))
+        .unwrap()
+        .unwrap_program();
+
+    let last = statements.len() - 1;
+
+    let TypedExpression::Binary { constant, .. } =
+        statements.nth_statement(last).unwrap_expression()
+    else {
+        panic!("Expected a binary expression");
+    };
+
+    constant
+}
+
+#[test]
+fn checked_overflow_is_an_error() {
+    // Arrange
+    let input = "9223372036854775807 + 1";
+
+    // Act
+    let error = try_create_typed_ast_with_config(input, config(OverflowPolicy::Checked));
+
+    // Assert
+    assert!(error.unwrap_err().contains("overflows Int"));
+}
+
+#[test]
+fn checked_underflow_is_an_error() {
+    // Arrange
+    let input = "0u - 1u";
+
+    // Act
+    let error = try_create_typed_ast_with_config(input, config(OverflowPolicy::Checked));
+
+    // Assert
+    assert!(error.unwrap_err().contains("overflows UInt"));
+}
+
+#[test]
+fn checked_is_the_default_policy() {
+    // Arrange
+    let input = "9223372036854775807 * 2";
+
+    // Act
+    let error = try_create_typed_ast_with_config(input, TypeCheckerConfig::default());
+
+    // Assert
+    assert!(error.is_err());
+}
+
+#[test]
+fn checked_evaluates_at_the_boundary() {
+    // Arrange
+    let input = "9223372036854775806 + 1";
+
+    // Act
+    let value = folded(input, OverflowPolicy::Checked);
+
+    // Assert
+    assert_eq!(value, Some(Literal::Int(i64::MAX)));
+}
+
+#[test]
+fn wrapping_overflow_wraps_around() {
+    // Arrange
+    let input = "9223372036854775807 + 1";
+
+    // Act
+    let value = folded(input, OverflowPolicy::Wrapping);
+
+    // Assert
+    assert_eq!(value, Some(Literal::Int(i64::MIN)));
+}
+
+#[test]
+fn wrapping_underflow_wraps_around() {
+    // Arrange
+    let input = "0u - 1u";
+
+    // Act
+    let value = folded(input, OverflowPolicy::Wrapping);
+
+    // Assert
+    assert_eq!(value, Some(Literal::UInt(u64::MAX)));
+}
+
+#[test]
+fn saturating_overflow_clamps_to_max() {
+    // Arrange
+    let input = "9223372036854775807 + 1";
+
+    // Act
+    let value = folded(input, OverflowPolicy::Saturating);
+
+    // Assert
+    assert_eq!(value, Some(Literal::Int(i64::MAX)));
+}
+
+#[test]
+fn saturating_underflow_clamps_to_min() {
+    // Arrange
+    let input = "0u - 1u";
+
+    // Act
+    let value = folded(input, OverflowPolicy::Saturating);
+
+    // Assert
+    assert_eq!(value, Some(Literal::UInt(0)));
+}
+
+#[test]
+fn non_constant_expressions_are_not_evaluated() {
+    // Arrange
+    let input = r#"
+        let a = 9223372036854775807;
+        a + 1
+        "#;
+
+    // Act
+    let value = folded(input, OverflowPolicy::Checked);
+
+    // Assert
+    assert_eq!(value, None);
+}
+
+#[test]
+fn wrapped_operand_is_folded_into_enclosing_expression() {
+    // Arrange
+    let input = "(9223372036854775807 + 1) - 1";
+
+    // Act
+    let value = folded(input, OverflowPolicy::Wrapping);
+
+    // Assert
+    assert_eq!(value, Some(Literal::Int(i64::MAX)));
+}
+
+#[test]
+fn negated_constant_is_folded() {
+    // Arrange
+    let input = "-9223372036854775807 - 1";
+
+    // Act
+    let value = folded(input, OverflowPolicy::Checked);
+
+    // Assert
+    assert_eq!(value, Some(Literal::Int(i64::MIN)));
+}
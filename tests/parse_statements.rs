@@ -0,0 +1,98 @@
+use shared::parser::{self, FunctionDeclaration, ModuleDeclaration, Statement};
+
+/// The name of each declared function, or the text the parse error was found at
+fn outcomes(source: &str) -> Vec<Result<String, String>> {
+    parser::parse_statements(source)
+        .map(|result| match result {
+            Ok(Statement::FunctionDeclaration(FunctionDeclaration {
+                type_identifier, ..
+            })) => Ok(type_identifier.to_string()),
+            Ok(statement) => panic!("Expected a function declaration but got {:?}", statement),
+            Err(error) => Err(source[error.span.start..error.span.end].to_string()),
+        })
+        .collect()
+}
+
+#[test]
+fn parsing_continues_after_a_broken_declaration() {
+    // Arrange
+    let source = r#"
+    fun broken(): Int => (1 + ;
+    fun first(): Int => 1
+    fun second(): Int => 2
+    "#;
+
+    // Act
+    let outcomes = outcomes(source);
+
+    // Assert
+    assert_eq!(
+        outcomes,
+        vec![
+            Err(";".to_string()),
+            Ok("first".to_string()),
+            Ok("second".to_string())
+        ]
+    );
+}
+
+#[test]
+fn declarations_nested_in_a_broken_one_are_skipped() {
+    // Arrange
+    let source = r#"
+    imp Broken {
+        fun inner(): Int => 1 +
+    }
+    fun outer(): Int => 2
+    "#;
+
+    // Act
+    let outcomes = outcomes(source);
+
+    // Assert
+    assert_eq!(outcomes.len(), 2, "{:?}", outcomes);
+    assert_eq!(outcomes[1], Ok("outer".to_string()));
+}
+
+#[test]
+fn module_declaration_is_the_first_statement() {
+    // Arrange
+    let source = "pub mod shapes::circle; pub fun area(): Float => 3.14";
+
+    // Act
+    let statements: Vec<_> = parser::parse_statements(source).collect();
+
+    // Assert
+    let Some(Ok(Statement::ModuleDeclaration(ModuleDeclaration { module_path, .. }))) =
+        statements.first()
+    else {
+        panic!("Expected a module declaration but got {:?}", statements);
+    };
+
+    assert_eq!(
+        module_path,
+        &vec!["shapes".to_string(), "circle".to_string()]
+    );
+    assert!(matches!(
+        statements[1..],
+        [Ok(Statement::FunctionDeclaration(_))]
+    ));
+}
+
+#[test]
+fn statements_are_the_same_as_the_parsed_program() {
+    // Arrange
+    let source = "pub fun double(x: Int): Int => x * 2 let four = double(2); four";
+    let tokens = shared::lexer::tokenize(source).unwrap();
+
+    // Act
+    let statements: Result<Vec<_>, _> = parser::parse_statements(source).collect();
+
+    // Assert
+    assert_eq!(
+        parser::create_ast(tokens, false),
+        Ok(Statement::Program {
+            statements: statements.unwrap()
+        })
+    );
+}
@@ -0,0 +1,163 @@
+mod common;
+
+use common::{check_with_env, create_env, create_type_env, tokenize};
+
+use interpreter::{value::Number, Value};
+use shared::{
+    display::{Indent, IndentDisplay},
+    parser::{self, Expression, Member, Statement},
+    span::Span,
+};
+
+const MATH: &str = r#"
+pub mod math;
+pub fun double(x: Int): Int => x * 2
+"#;
+
+const GEOMETRY: &str = r#"
+pub mod geometry::shapes;
+pub struct Point { pub x: Int, pub y: Int }
+pub fun origin(): Point => Point { x: 3, y: 4 }
+fun secret(): Int => 42
+"#;
+
+/// Type check and evaluate the sources in turn with shared environments, returning the last value
+fn evaluate_modules(sources: &[&str]) -> Result<Value, String> {
+    let type_environment = create_type_env();
+    let environment = create_env();
+    let mut value = Value::Void;
+
+    for source in sources {
+        let typed_ast = check_with_env(source, type_environment.clone())?;
+        value = interpreter::evaluate(typed_ast, environment.clone())?;
+    }
+
+    Ok(value)
+}
+
+fn parse_expression(input: &str) -> Expression {
+    let Statement::Program { statements } = parser::create_ast(tokenize(input), false).unwrap()
+    else {
+        panic!("Expected a program");
+    };
+
+    let Some(Statement::Expression(expression)) = statements.into_iter().next() else {
+        panic!("Expected an expression");
+    };
+
+    expression
+}
+
+#[test]
+fn two_segment_path_is_parsed() {
+    // Arrange
+    let input = "math::double";
+
+    // Act
+    let expression = parse_expression(input);
+
+    // Assert
+    assert_eq!(
+        expression,
+        Expression::Member(Member::Path(
+            vec!["math".to_string(), "double".to_string()],
+            Span::default()
+        ))
+    );
+}
+
+#[test]
+fn three_segment_path_is_shown_in_indent_display() {
+    // Arrange
+    let expression = parse_expression("geometry::shapes::origin");
+
+    // Act
+    let display = expression.indent_display(&mut Indent::new());
+
+    // Assert
+    assert_eq!(display, "<path>\n├─geometry\n├─shapes\n╰─origin");
+}
+
+#[test]
+fn two_segment_path_calls_the_function() {
+    // Arrange
+    let app = r#"
+    pub mod app;
+    math::double(21)
+    "#;
+
+    // Act
+    let value = evaluate_modules(&[MATH, app]);
+
+    // Assert
+    assert_eq!(value, Ok(Value::Number(Number::Int(42))));
+}
+
+#[test]
+fn three_segment_path_calls_the_function() {
+    // Arrange
+    let app = r#"
+    pub mod app;
+    let p = geometry::shapes::origin();
+    p.x + p.y
+    "#;
+
+    // Act
+    let value = evaluate_modules(&[GEOMETRY, app]);
+
+    // Assert
+    assert_eq!(value, Ok(Value::Number(Number::Int(7))));
+}
+
+#[test]
+fn path_through_imported_module_is_resolved() {
+    // Arrange
+    let app = r#"
+    pub mod app;
+    use geometry::shapes;
+    let p = shapes::origin();
+    p.y
+    "#;
+
+    // Act
+    let value = evaluate_modules(&[GEOMETRY, app]);
+
+    // Assert
+    assert_eq!(value, Ok(Value::Number(Number::Int(4))));
+}
+
+#[test]
+fn path_with_missing_item_is_an_error() {
+    // Arrange
+    let app = r#"
+    pub mod app;
+    math::triple(1)
+    "#;
+
+    // Act
+    let value = evaluate_modules(&[MATH, app]);
+
+    // Assert
+    assert_eq!(
+        value,
+        Err("Unresolved path 'math::triple': 'triple' not found in module 'math'".to_string())
+    );
+}
+
+#[test]
+fn path_to_private_item_is_an_error() {
+    // Arrange
+    let app = r#"
+    pub mod app;
+    geometry::shapes::secret()
+    "#;
+
+    // Act
+    let value = evaluate_modules(&[GEOMETRY, app]);
+
+    // Assert
+    assert_eq!(
+        value,
+        Err("'geometry::shapes::secret' is not visible from module 'app'".to_string())
+    );
+}
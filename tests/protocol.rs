@@ -0,0 +1,189 @@
+mod common;
+
+use common::{check_with_env, create_type_env, evaluate_with};
+
+use interpreter::{value::Number, Value};
+
+const SHOW: &str = r#"
+proto Show { type T; fun show(self): T; }
+struct Point { x: Int, y: Int }
+struct Line { length: Int }
+"#;
+
+#[test]
+fn conforming_implementation_is_callable() {
+    // Arrange
+    let input = r#"
+    imp Show for Point { type T = Int; fun show(self): Int => self.x + self.y }
+    let p = Point { x: 1, y: 2 };
+    p.show() + Point::show(p)
+    "#;
+
+    // Act
+    let value = evaluate_with(SHOW, input);
+
+    // Assert
+    assert_eq!(value, Ok(Value::Number(Number::Int(6))));
+}
+
+#[test]
+fn missing_function_is_an_error() {
+    // Arrange
+    let input = "imp Show for Point { type T = Int; }";
+
+    // Act
+    let value = evaluate_with(SHOW, input);
+
+    // Assert
+    assert_eq!(
+        value,
+        Err("'Point' does not implement 'show' of protocol 'Show'".to_string())
+    );
+}
+
+#[test]
+fn mismatched_function_is_an_error() {
+    // Arrange
+    let input = "imp Show for Point { type T = Int; fun show(self): Bool => true }";
+
+    // Act
+    let value = evaluate_with(SHOW, input);
+
+    // Assert
+    assert_eq!(
+        value,
+        Err(
+            "'show' of protocol 'Show' must have type fun(Point): Int for 'Point', found fun(Point): Bool"
+                .to_string()
+        )
+    );
+}
+
+#[test]
+fn missing_associated_type_is_an_error() {
+    // Arrange
+    let input = "imp Show for Point { fun show(self): Int => self.x }";
+
+    // Act
+    let value = evaluate_with(SHOW, input);
+
+    // Assert
+    assert_eq!(
+        value,
+        Err("'Point' does not define associated type 'T' of protocol 'Show'".to_string())
+    );
+}
+
+#[test]
+fn function_outside_the_protocol_is_an_error() {
+    // Arrange
+    let input = r#"
+    imp Show for Point {
+        type T = Int;
+        fun show(self): Int => self.x
+        fun hide(self): Int => 0
+    }
+    "#;
+
+    // Act
+    let value = evaluate_with(SHOW, input);
+
+    // Assert
+    assert_eq!(
+        value,
+        Err("'hide' is not a function of protocol 'Show'".to_string())
+    );
+}
+
+#[test]
+fn implementations_satisfy_where_clauses() {
+    // Arrange
+    let type_environment = create_type_env();
+    let input = format!(
+        "{}{}",
+        SHOW,
+        r#"
+        imp Show for Point { type T = Int; fun show(self): Int => self.x }
+        fun describe<T>(value: T): Int where T: Show => 0
+        "#
+    );
+
+    check_with_env(&input, type_environment.clone()).unwrap();
+
+    let point = type_environment.borrow().get_type("Point").unwrap();
+    let line = type_environment.borrow().get_type("Line").unwrap();
+
+    // Act
+    let with_point = type_environment
+        .borrow()
+        .check_where_clause("describe", &[point]);
+    let with_line = type_environment
+        .borrow()
+        .check_where_clause("describe", &[line]);
+
+    // Assert
+    assert_eq!(with_point, Ok(()));
+    assert_eq!(
+        with_line,
+        Err("'Line' does not implement protocol 'Show' required by 'T' of 'describe'".to_string())
+    );
+}
+
+#[test]
+fn constrained_generic_calls_protocol_functions() {
+    // Arrange
+    let input = r#"
+    proto Area { fun area(self): Int; }
+    imp Area for Point { fun area(self): Int => self.x * self.y }
+    fun double_area<T>(value: T): Int where T: Area => value.area() * 2
+    double_area(Point { x: 2, y: 3 })
+    "#;
+
+    // Act
+    let value = evaluate_with(SHOW, input);
+
+    // Assert
+    assert_eq!(value, Ok(Value::Number(Number::Int(12))));
+}
+
+#[test]
+fn unconstrained_generic_method_call_suggests_a_constraint() {
+    // Arrange
+    let input = r#"
+    proto Area { fun area(self): Int; }
+    fun double_area<T>(value: T): Int => value.area() * 2
+    "#;
+
+    // Act
+    let value = evaluate_with(SHOW, input);
+
+    // Assert
+    assert_eq!(
+        value,
+        Err(
+            "Generic type 'T' has no constraints, so it has no method called 'area'. Add 'where T: Area'"
+                .to_string()
+        )
+    );
+}
+
+#[test]
+fn method_outside_the_constraints_is_an_error() {
+    // Arrange
+    let input = r#"
+    proto Area { fun area(self): Int; }
+    fun double_area<T>(value: T): Int where T: Show => value.area() * 2
+    "#;
+
+    // Act
+    let value = evaluate_with(SHOW, input);
+
+    // Assert
+    assert_eq!(
+        value,
+        Err(
+            "The constraints on generic type 'T' do not guarantee a method called 'area'. Add 'where T: Area'"
+                .to_string()
+        )
+    );
+}
@@ -0,0 +1,51 @@
+mod common;
+
+use common::evaluate;
+
+use interpreter::{value::Number, Value};
+
+#[test]
+fn record_with_the_same_fields_is_assignable() {
+    // Arrange
+    let input = "let p: { x: Int, y: Int } = { y: 2, x: 1 }; p.x * 10 + p.y";
+
+    // Act
+    let value = evaluate(input);
+
+    // Assert
+    assert_eq!(value, Ok(Value::Number(Number::Int(12))));
+}
+
+#[test]
+fn record_with_extra_fields_is_assignable() {
+    // Arrange
+    let input = r#"
+    fun get_x(p: { x: Int }): Int => p.x
+    let point = { x: 3, y: 4 };
+    get_x(point)
+    "#;
+
+    // Act
+    let value = evaluate(input);
+
+    // Assert
+    assert_eq!(value, Ok(Value::Number(Number::Int(3))));
+}
+
+#[test]
+fn record_with_a_missing_field_is_rejected() {
+    // Arrange
+    let input = "let p: { x: Int, y: Int } = { x: 1 };";
+
+    // Act
+    let value = evaluate(input);
+
+    // Assert
+    assert_eq!(
+        value,
+        Err(
+            "Initializer type { x: #Int: 1 } does not match variable type { x: Int, y: Int }"
+                .to_string()
+        )
+    );
+}
@@ -0,0 +1,53 @@
+mod common;
+
+use common::create_typed_ast;
+
+use shared::type_checker::references::{references_to, Reference, SymbolKind};
+
+#[test]
+fn references_to_struct_in_field_annotation_and_literal() {
+    // Arrange
+    let input = r#"
+        struct Point { x: Int, y: Int }
+        struct Line { start: Point, end: Point }
+        fun origin(): Point => Point { x: 0, y: 0 }
+        let p: Point = origin();
+    "#;
+
+    // Act
+    let references = references_to(&create_typed_ast(input), "Point", SymbolKind::Type);
+
+    // Assert
+    assert!(matches!(
+        references.as_slice(),
+        [
+            Reference::Field { owner: start_owner, field: start },
+            Reference::Field { owner: end_owner, field: end },
+            Reference::ReturnType { function },
+            Reference::Literal { function: Some(literal_function) },
+            Reference::Variable { span },
+        ] if start_owner == "Line"
+            && start == "start"
+            && end_owner == "Line"
+            && end == "end"
+            && function == "origin"
+            && literal_function == "origin"
+            && &input[span.start..span.end] == "p"
+    ));
+}
+
+#[test]
+fn references_to_function_skip_variables_with_the_same_name() {
+    // Arrange
+    let input = "fun double(x: Int): Int => x * 2\nfun apply(double: Int): Int => double + 1\ndouble(apply(1))";
+
+    // Act
+    let references = references_to(&create_typed_ast(input), "double", SymbolKind::Function);
+
+    // Assert
+    let [Reference::Use { span }] = references.as_slice() else {
+        panic!("Expected a single use, found {:?}", references);
+    };
+
+    assert_eq!(span.start, input.rfind("double(").unwrap());
+}
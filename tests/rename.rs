@@ -0,0 +1,84 @@
+mod common;
+
+use common::create_typed_ast;
+
+use shared::type_checker::rename::rename_sites;
+
+/// The start of every site which would be renamed for the variable defined at the `n`th
+/// occurrence of `definition` in the input, along with the text at each site
+fn rename<'a>(input: &'a str, definition: &str, n: usize) -> Option<Vec<(usize, &'a str)>> {
+    let (offset, _) = input.match_indices(definition).nth(n)?;
+    let sites = rename_sites(&create_typed_ast(input), offset)?;
+
+    Some(
+        sites
+            .into_iter()
+            .map(|span| (span.start, &input[span.start..span.end]))
+            .collect(),
+    )
+}
+
+#[test]
+fn rename_shadowed_variable_only_renames_outer_scope() {
+    // Arrange
+    let input = "fun f(x: Int): Int => { let y = x + 1; { let x = y * 2; x }; x * y }";
+
+    // Act
+    let sites = rename(input, "x", 0);
+
+    // Assert
+    assert_eq!(sites, Some(vec![(6, "x"), (32, "x"), (61, "x")]));
+}
+
+#[test]
+fn rename_shadowing_variable_only_renames_inner_scope() {
+    // Arrange
+    let input = "fun f(x: Int): Int => { let y = x + 1; { let x = y * 2; x }; x * y }";
+
+    // Act
+    let sites = rename(input, "x", 2);
+
+    // Assert
+    assert_eq!(sites, Some(vec![(45, "x"), (56, "x")]));
+}
+
+#[test]
+fn rename_let_includes_its_own_initializer_only_when_shadowing() {
+    // Arrange
+    let input = "let a = 1; let a = a + 1; a";
+
+    // Act
+    let first = rename(input, "a", 0);
+    let second = rename(input, "a", 1);
+
+    // Assert
+    assert_eq!(first, Some(vec![(4, "a"), (19, "a")]));
+    assert_eq!(second, Some(vec![(15, "a"), (26, "a")]));
+}
+
+#[test]
+fn rename_without_variable_at_offset() {
+    // Arrange
+    let input = "let a = 1; a + 2";
+
+    // Act
+    let sites = rename(input, "2", 0);
+
+    // Assert
+    assert_eq!(sites, None);
+}
+
+#[test]
+fn rename_desugared_references_once() {
+    // Arrange
+    let input = "let mut a = 1; a += 2; a++; a match | 3 => a | _ => 0";
+
+    // Act
+    let sites = rename(input, "a", 0);
+
+    // Assert
+    assert_eq!(
+        sites,
+        Some(vec![(8, "a"), (15, "a"), (23, "a"), (28, "a"), (43, "a")])
+    );
+}
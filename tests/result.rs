@@ -0,0 +1,88 @@
+mod common;
+
+use common::evaluate_with;
+
+use interpreter::{value::Number, Value};
+
+const PARSE: &str = r#"
+fun positive(n: Int): Result<Int, String> => if n > 0 => Result<Int, String>::Ok { value: n } else => Result<Int, String>::Err { error: "not positive" }
+"#;
+
+#[test]
+fn try_chain_gives_the_values() {
+    // Arrange
+    let input = r#"
+    fun sum(n: Int): Result<Int, String> => {
+        let a = positive(n)?;
+        let b = positive(a * 2)?;
+        Result<Int, String>::Ok { value: a + b }
+    }
+    sum(2)
+    "#;
+
+    // Act
+    let value = evaluate_with(PARSE, input);
+
+    // Assert
+    assert_eq!(value, Ok(Value::result_ok(Value::Number(Number::Int(6)))));
+}
+
+#[test]
+fn try_returns_the_first_error() {
+    // Arrange
+    let input = r#"
+    fun sum(n: Int): Result<Int, String> => {
+        let a = positive(n)?;
+        let b = positive(a * 2)?;
+        Result<Int, String>::Ok { value: a + b }
+    }
+    sum(-2)
+    "#;
+
+    // Act
+    let value = evaluate_with(PARSE, input);
+
+    // Assert
+    assert_eq!(
+        value,
+        Ok(Value::result_err(Value::String("not positive".to_string())))
+    );
+}
+
+#[test]
+fn try_with_a_mismatched_error_type_is_an_error() {
+    // Arrange
+    let input = r#"
+    fun check(n: Int): Result<Int, Bool> => {
+        let a = positive(n)?;
+        Result<Int, Bool>::Ok { value: a }
+    }
+    "#;
+
+    // Act
+    let value = evaluate_with(PARSE, input);
+
+    // Assert
+    assert_eq!(
+        value,
+        Err(
+            "Error type String of ? does not match the error type Bool the function returns"
+                .to_string()
+        )
+    );
+}
+
+#[test]
+fn try_in_a_function_which_does_not_return_a_result_is_an_error() {
+    // Arrange
+    let input = "fun check(n: Int): Int => positive(n)?";
+
+    // Act
+    let value = evaluate_with(PARSE, input);
+
+    // Assert
+    assert_eq!(
+        value,
+        Err("? can only be used in a function which returns a result, not Int".to_string())
+    );
+}
@@ -0,0 +1,185 @@
+mod common;
+
+use std::thread;
+
+use common::{create_type_env, tokenize};
+use proptest::{prelude::*, sample::select};
+
+use shared::{
+    format::format,
+    parser::{self, build::*, BinaryOperator, Expression, Literal, Statement, UnaryOperator},
+    type_checker::{self, OverflowPolicy, TypeCheckerConfig},
+};
+
+/// The parameters of every generated function, which are also declared at the top level
+const VARIABLES: [&str; 3] = ["a", "b", "c"];
+
+const INT_OPERATORS: [BinaryOperator; 6] = [
+    BinaryOperator::Add,
+    BinaryOperator::Subtract,
+    BinaryOperator::Multiply,
+    BinaryOperator::BitwiseAnd,
+    BinaryOperator::BitwiseOr,
+    BinaryOperator::BitwiseXor,
+];
+
+const COMPARISON_OPERATORS: [BinaryOperator; 6] = [
+    BinaryOperator::Equal,
+    BinaryOperator::NotEqual,
+    BinaryOperator::LessThan,
+    BinaryOperator::LessThanOrEqual,
+    BinaryOperator::GreaterThan,
+    BinaryOperator::GreaterThanOrEqual,
+];
+
+/// Checking the deepest generated programs needs more than the stack of a test thread in debug
+/// builds
+const STACK_SIZE: usize = 16 * 1024 * 1024;
+
+/// An `Int` expression, which reads the variables when `variables` is set
+fn int_expression(variables: bool) -> BoxedStrategy<Expression> {
+    let literal = (-1000..1000i64).prop_map(int);
+
+    let leaf = if variables {
+        prop_oneof![literal, select(&VARIABLES[..]).prop_map(identifier)].boxed()
+    } else {
+        literal.boxed()
+    };
+
+    leaf.prop_recursive(4, 24, 2, |inner| {
+        prop_oneof![
+            (inner.clone(), select(&INT_OPERATORS[..]), inner.clone())
+                .prop_map(|(left, operator, right)| binary(left, operator, right)),
+            (
+                select(&[UnaryOperator::Negate, UnaryOperator::BitwiseNot][..]),
+                inner.clone()
+            )
+                .prop_map(|(operator, expression)| unary_expression(operator, expression)),
+            (inner.clone(), inner).prop_map(|(first, last)| block(vec![
+                semi(let_decl("_unused", first)),
+                statement(last)
+            ])),
+        ]
+    })
+    .boxed()
+}
+
+/// The parser reads a negated literal as a negative literal, so a negated literal is never
+/// generated either
+fn unary_expression(operator: UnaryOperator, expression: Expression) -> Expression {
+    match (&operator, expression) {
+        (UnaryOperator::Negate, Expression::Literal(Literal::Int(value), _)) => int(-value),
+        (_, expression) => unary(operator, expression),
+    }
+}
+
+/// A `Bool` expression comparing `Int` expressions which read the variables
+fn bool_expression() -> impl Strategy<Value = Expression> {
+    let comparison = (
+        int_expression(true),
+        select(&COMPARISON_OPERATORS[..]),
+        int_expression(true),
+    )
+        .prop_map(|(left, operator, right)| binary(left, operator, right));
+
+    comparison.prop_recursive(2, 8, 2, |inner| {
+        prop_oneof![
+            (
+                inner.clone(),
+                select(&[BinaryOperator::LogicalAnd, BinaryOperator::LogicalOr][..]),
+                inner.clone()
+            )
+                .prop_map(|(left, operator, right)| binary(left, operator, right)),
+            inner.prop_map(|expression| unary(UnaryOperator::LogicalNot, expression)),
+        ]
+    })
+}
+
+/// A program declaring a struct, some functions and the variables, which ends with a call
+fn generated_program() -> impl Strategy<Value = Statement> {
+    (
+        prop::collection::vec(int_expression(true), 1..4),
+        prop::collection::vec(int_expression(false), VARIABLES.len()),
+        (int_expression(true), int_expression(true)),
+        bool_expression(),
+    )
+        .prop_map(|(bodies, values, (x, y), flag)| {
+            let mut statements = vec![struct_decl(
+                "Point",
+                vec![("x", type_("Int")), ("y", type_("Int"))],
+            )];
+
+            for (i, body) in bodies.into_iter().enumerate() {
+                let parameters = VARIABLES.iter().map(|v| (*v, type_("Int"))).collect();
+                statements.push(function_decl(
+                    &format!("f{}", i),
+                    parameters,
+                    type_("Int"),
+                    body,
+                ));
+            }
+
+            for (variable, value) in VARIABLES.iter().zip(values) {
+                statements.push(semi(let_decl(variable, value)));
+            }
+
+            statements.push(semi(let_decl(
+                "point",
+                struct_literal("Point", vec![("x", x), ("y", y)]),
+            )));
+
+            statements.push(semi(let_decl("flag", flag)));
+
+            statements.push(statement(call(
+                identifier("f0"),
+                vec![
+                    field(identifier("point"), "x"),
+                    field(identifier("point"), "y"),
+                    identifier("c"),
+                ],
+            )));
+
+            program(statements)
+        })
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    #[test]
+    fn formatted_program_parses_to_the_same_ast(program in generated_program()) {
+        // Act
+        let formatted = format(&program);
+        let parsed = parser::create_ast(tokenize(&formatted), false);
+
+        // Assert
+        prop_assert_eq!(parsed, Ok(program), "Formatted source:\n{}", formatted);
+    }
+
+    #[test]
+    fn generated_program_type_checks(program in generated_program()) {
+        // Arrange
+        let check = move || {
+            let type_environment = create_type_env();
+
+            // The values are random, so constant arithmetic may overflow
+            let config = TypeCheckerConfig {
+                overflow_policy: OverflowPolicy::Wrapping,
+                ..Default::default()
+            };
+
+            type_checker::create_typed_ast(program, type_environment, config).map(|_| ())
+        };
+
+        // Act
+        let result = thread::Builder::new()
+            .stack_size(STACK_SIZE)
+            .spawn(check)
+            .unwrap()
+            .join()
+            .unwrap();
+
+        // Assert
+        prop_assert_eq!(result, Ok(()));
+    }
+}
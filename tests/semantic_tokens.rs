@@ -0,0 +1,64 @@
+mod common;
+
+use common::create_typed_ast;
+
+use shared::type_checker::semantic_tokens::{semantic_tokens, TokenKind};
+
+/// The text and kind of every semantic token of the input
+fn tokens(input: &str) -> Vec<(&str, TokenKind)> {
+    semantic_tokens(&create_typed_ast(input), input)
+        .into_iter()
+        .map(|(span, kind)| (&input[span.start..span.end], kind))
+        .collect()
+}
+
+#[test]
+fn semantic_tokens_classify_by_resolved_meaning() {
+    // Arrange
+    let input = "fun double(x: Int): Int => x * 2\nlet f = double; f(3) + double(4)";
+
+    // Act
+    let tokens = tokens(input);
+
+    // Assert
+    assert_eq!(
+        tokens,
+        vec![
+            ("x", TokenKind::Parameter),
+            ("x", TokenKind::Parameter),
+            ("*", TokenKind::Operator),
+            ("2", TokenKind::Literal),
+            ("f", TokenKind::Variable),
+            ("double", TokenKind::Function),
+            ("f", TokenKind::Variable),
+            ("3", TokenKind::Literal),
+            ("+", TokenKind::Operator),
+            ("double", TokenKind::Function),
+            ("4", TokenKind::Literal),
+        ]
+    );
+}
+
+#[test]
+fn semantic_tokens_classify_type_and_enum_member_names() {
+    // Arrange
+    let input = "enum Color { Red, Green }\nstruct Point { x: Int }\nlet c = Color::Green; let p = Point { x: 1 }; p.x";
+
+    // Act
+    let tokens = tokens(input);
+
+    // Assert
+    let kind_of = |text: &str| {
+        tokens
+            .iter()
+            .find(|(token, _)| *token == text)
+            .map(|(_, kind)| *kind)
+    };
+
+    assert_eq!(kind_of("Color"), Some(TokenKind::Type));
+    assert_eq!(kind_of("Green"), Some(TokenKind::EnumMember));
+    assert_eq!(kind_of("Point"), Some(TokenKind::Type));
+    assert_eq!(kind_of("p"), Some(TokenKind::Variable));
+    assert_eq!(kind_of("x"), Some(TokenKind::Variable));
+    assert_eq!(kind_of("Red"), None);
+}
@@ -0,0 +1,102 @@
+mod common;
+
+use common::tokenize;
+
+use shared::parser::{self, Statement};
+
+fn explicit(source: &str) -> Statement {
+    parser::create_ast(tokenize(source), false).unwrap()
+}
+
+#[test]
+fn newline_ends_statement() {
+    // Arrange
+    let source = "let a = 1\nlet b = a + 2\nb = b * a\n";
+
+    // Act
+    let ast = parser::create_ast_inferring_semicolons(source, false);
+
+    // Assert
+    assert_eq!(ast, Ok(explicit("let a = 1; let b = a + 2; b = b * a;")));
+}
+
+#[test]
+fn binary_expression_continued_on_next_line_is_not_split() {
+    // Arrange
+    let source = "let a = 1 +\n    2\nlet b = a\n    * 3\n";
+
+    // Act
+    let ast = parser::create_ast_inferring_semicolons(source, false);
+
+    // Assert
+    assert_eq!(ast, Ok(explicit("let a = 1 + 2; let b = a * 3;")));
+}
+
+#[test]
+fn last_expression_of_block_is_still_its_value() {
+    // Arrange
+    let source = "fun f(): Int => {\n    let a = 1\n    a\n}\n";
+
+    // Act
+    let ast = parser::create_ast_inferring_semicolons(source, false);
+
+    // Assert
+    assert_eq!(ast, Ok(explicit("fun f(): Int => { let a = 1; a }")));
+}
+
+#[test]
+fn return_and_break_at_end_of_line_have_no_value() {
+    // Arrange
+    let source = "fun f() => {\n    loop {\n        break\n    }\n    return\n    g()\n}\n";
+
+    // Act
+    let ast = parser::create_ast_inferring_semicolons(source, false);
+
+    // Assert
+    assert_eq!(
+        ast,
+        Ok(explicit("fun f() => { loop { break; }; return; g() }"))
+    );
+}
+
+#[test]
+fn line_starting_with_paren_starts_new_statement() {
+    // Arrange
+    let source = "let a = 1\n(a)\n";
+
+    // Act
+    let ast = parser::create_ast_inferring_semicolons(source, false);
+
+    // Assert
+    assert_eq!(ast, Ok(explicit("let a = 1; (a);")));
+}
+
+#[test]
+fn line_starting_with_bracket_starts_new_statement() {
+    // Arrange
+    let source = "let a = [1, 2]\n[0]\n";
+
+    // Act
+    let ast = parser::create_ast_inferring_semicolons(source, false);
+
+    // Assert
+    assert_eq!(ast, Ok(explicit("let a = [1, 2]; [0];")));
+}
+
+#[test]
+fn line_starting_with_minus_is_ambiguous() {
+    // Arrange
+    let source = "let a = 1\n-a\n";
+
+    // Act
+    let ast = parser::create_ast_inferring_semicolons(source, false);
+
+    // Assert
+    assert_eq!(
+        ast,
+        Err(
+            "Line starting with - is ambiguous, end the line before it with ; or with - instead"
+                .to_string()
+        )
+    );
+}
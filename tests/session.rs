@@ -0,0 +1,149 @@
+use std::rc::Rc;
+
+use shared::type_checker::{session::Session, Type, TypeCheckerConfig};
+
+#[test]
+fn statement_uses_binding_of_earlier_input() {
+    // Arrange
+    let mut session = Session::new(TypeCheckerConfig::default());
+    session.eval_statement("let a = 1;").unwrap();
+
+    // Act
+    let result = session.eval_statement("a + 2");
+
+    // Assert
+    let (_, type_) = result.unwrap();
+
+    assert_eq!(type_, Type::Int);
+}
+
+#[test]
+fn redefined_function_shadows_earlier_one() {
+    // Arrange
+    let mut session = Session::new(TypeCheckerConfig::default());
+    session.eval_statement("fun f(): Int => 1").unwrap();
+    session
+        .eval_statement("fun f(): String => \"one\"")
+        .unwrap();
+
+    // Act
+    let result = session.eval_statement("f()");
+
+    // Assert
+    let (_, type_) = result.unwrap();
+
+    assert_eq!(type_, Type::String);
+}
+
+#[test]
+fn failed_input_leaves_session_unchanged() {
+    // Arrange
+    let mut session = Session::new(TypeCheckerConfig::default());
+    session.eval_statement("let a = 1;").unwrap();
+    session.eval_statement("let a = b;").unwrap_err();
+
+    // Act
+    let result = session.eval_statement("a");
+
+    // Assert
+    let (_, type_) = result.unwrap();
+
+    assert_eq!(
+        type_,
+        Type::Literal {
+            name: "1".to_string(),
+            type_: Box::new(Type::Int),
+        }
+    );
+}
+
+#[test]
+fn more_than_one_statement_is_an_error() {
+    // Arrange
+    let mut session = Session::new(TypeCheckerConfig::default());
+
+    // Act
+    let result = session.eval_statement("let a = 1; let b = 2;");
+
+    // Assert
+    assert_eq!(
+        result,
+        Err("Expected one statement but found 2".to_string())
+    );
+}
+
+#[test]
+fn inputs_are_merged_into_one_environment() {
+    // Arrange
+    let mut session = Session::new(TypeCheckerConfig::default());
+    let type_environment = session.type_environment();
+
+    // Act
+    session.eval_statement("let a = 1;").unwrap();
+    session.eval_statement("let b = a;").unwrap();
+
+    // Assert
+    assert!(Rc::ptr_eq(&type_environment, &session.type_environment()));
+    assert!(type_environment.borrow().get_variable("b").is_some());
+}
+
+#[test]
+fn redeclared_variable_is_only_as_mutable_as_its_latest_declaration() {
+    // Arrange
+    let mut session = Session::new(TypeCheckerConfig::default());
+    session.eval_statement("let mut a = 1;").unwrap();
+    session.eval_statement("let a = 2;").unwrap();
+
+    // Act
+    let result = session.eval_statement("a = 3;");
+
+    // Assert
+    assert!(result.is_err());
+}
+
+#[test]
+fn redeclared_struct_does_not_keep_mutable_fields() {
+    // Arrange
+    let mut session = Session::new(TypeCheckerConfig::default());
+    session.eval_statement("struct P { x: mut Int }").unwrap();
+    session.eval_statement("struct P { x: Int }").unwrap();
+    session.eval_statement("let mut p = P { x: 1 };").unwrap();
+
+    // Act
+    let result = session.eval_statement("p.x = 5;");
+
+    // Assert
+    assert!(result.is_err());
+}
+
+#[test]
+fn redeclared_struct_does_not_keep_field_defaults() {
+    // Arrange
+    let mut session = Session::new(TypeCheckerConfig::default());
+    session.eval_statement("struct P { x: Int = 1 }").unwrap();
+    session.eval_statement("struct P { x: Int }").unwrap();
+
+    // Act
+    let result = session.eval_statement("P { }");
+
+    // Assert
+    assert!(result.is_err());
+}
+
+#[test]
+fn failed_declaration_leaves_no_field_defaults_behind() {
+    // Arrange
+    let mut session = Session::new(TypeCheckerConfig::default());
+    session
+        .eval_statement("struct P { x: Int = 1, y: Foo }")
+        .unwrap_err();
+    session
+        .eval_statement("struct P { x: Int, y: Int }")
+        .unwrap();
+
+    // Act
+    let result = session.eval_statement("P { y: 2 }");
+
+    // Assert
+    assert!(result.is_err());
+}
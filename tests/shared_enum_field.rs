@@ -0,0 +1,134 @@
+mod common;
+
+use common::evaluate_with;
+
+use interpreter::{value::Number, Value};
+
+const SHAPE: &str = r#"
+enum Shape { id: Int, Circle { radius: Float }, Square { side: Float }, Empty }
+fun id_of(shape: Shape): Int => shape.id
+"#;
+
+#[test]
+fn shared_field_is_accessed_on_every_member() {
+    // Arrange
+    let input = r#"
+    let circle = Shape::Circle { id: 1, radius: 2.0 };
+    let square = Shape::Square { side: 3.0, id: 20 };
+    id_of(circle) + id_of(square) + id_of(Shape::Empty { id: 300 })
+    "#;
+
+    // Act
+    let value = evaluate_with(SHAPE, input);
+
+    // Assert
+    assert_eq!(value, Ok(Value::Number(Number::Int(321))));
+}
+
+#[test]
+fn shared_field_is_accessed_on_a_member_value() {
+    // Arrange
+    let input = "let square = Shape::Square { id: 4, side: 3.0 }; square.id";
+
+    // Act
+    let value = evaluate_with(SHAPE, input);
+
+    // Assert
+    assert_eq!(value, Ok(Value::Number(Number::Int(4))));
+}
+
+#[test]
+fn enum_literal_without_a_shared_field_is_an_error() {
+    // Arrange
+    let input = "let circle = Shape::Circle { radius: 2.0 };";
+
+    // Act
+    let value = evaluate_with(SHAPE, input);
+
+    // Assert
+    assert_eq!(
+        value,
+        Err("Missing field 'id' in literal of enum member 'Shape::Circle'".to_string())
+    );
+}
+
+#[test]
+fn enum_literal_with_an_unknown_field_is_an_error() {
+    // Arrange
+    let input = "let empty = Shape::Empty { id: 1, radius: 2.0 };";
+
+    // Act
+    let value = evaluate_with(SHAPE, input);
+
+    // Assert
+    assert_eq!(
+        value,
+        Err("Unknown field 'radius' in literal of enum member 'Shape::Empty'".to_string())
+    );
+}
+
+#[test]
+fn shared_field_is_bound_in_one_arm_and_member_field_in_another() {
+    // Arrange
+    let input = r#"
+    fun size(shape: Shape): Float => shape match
+    | Shape::Circle { radius } => radius,
+    | Shape { id } => id as Float;
+    size(Shape::Square { id: 6, side: 1.0 }) + size(Shape::Circle { id: 6, radius: 0.5 })
+    "#;
+
+    // Act
+    let value = evaluate_with(SHAPE, input);
+
+    // Assert
+    assert_eq!(value, Ok(Value::Number(Number::Float(6.5))));
+}
+
+#[test]
+fn shared_field_is_tested_in_enum_pattern() {
+    // Arrange
+    let input = r#"
+    fun code(shape: Shape): Int => shape match
+    | Shape { id: 7 } => 0,
+    | Shape { id } => id;
+    code(Shape::Square { id: 6, side: 1.0 }) + code(Shape::Empty { id: 7 })
+    "#;
+
+    // Act
+    let value = evaluate_with(SHAPE, input);
+
+    // Assert
+    assert_eq!(value, Ok(Value::Number(Number::Int(6))));
+}
+
+#[test]
+fn shared_field_is_bound_after_a_member_field_pattern() {
+    // Arrange
+    let input = r#"
+    fun code(shape: Shape): Int => shape match
+    | Shape::Circle { radius: 1.5, id } => id,
+    | _ => 0;
+    code(Shape::Circle { id: 5, radius: 1.5 }) + code(Shape::Circle { id: 8, radius: 2.5 })
+    "#;
+
+    // Act
+    let value = evaluate_with(SHAPE, input);
+
+    // Assert
+    assert_eq!(value, Ok(Value::Number(Number::Int(5))));
+}
+
+#[test]
+fn enum_pattern_with_a_member_field_is_an_error() {
+    // Arrange
+    let input = "fun radius(shape: Shape): Float => shape match | Shape { radius } => radius;";
+
+    // Act
+    let value = evaluate_with(SHAPE, input);
+
+    // Assert
+    assert_eq!(
+        value,
+        Err("Field 'radius' is not shared by every member of enum 'Shape'".to_string())
+    );
+}
@@ -0,0 +1,74 @@
+mod common;
+
+use common::check_type_env;
+
+use shared::type_checker::{
+    signature::{method_signature_help, signature_help},
+    Type,
+};
+
+#[test]
+fn signature_help_for_two_parameter_function() {
+    // Arrange
+    let type_environment = check_type_env("fun repeat(text: String, times: Int): String => text");
+
+    // Act
+    let before = signature_help("repeat", 0, &type_environment.borrow());
+    let after_one = signature_help("repeat", 1, &type_environment.borrow());
+    let after_both = signature_help("repeat", 2, &type_environment.borrow());
+
+    // Assert
+    let [signature] = before.as_slice() else {
+        panic!("Expected one signature, found {:?}", before);
+    };
+
+    assert_eq!(signature.name, "repeat");
+    assert_eq!(signature.parameters, vec![Type::String, Type::Int]);
+    assert_eq!(signature.return_type, Type::String);
+    assert_eq!(signature.active_parameter, Some(0));
+    assert_eq!(after_one[0].active_parameter, Some(1));
+    assert_eq!(after_both[0].active_parameter, None);
+}
+
+#[test]
+fn signature_help_without_function() {
+    // Arrange
+    let type_environment = check_type_env("let x = 1;");
+
+    // Act
+    let signatures = signature_help("missing", 0, &type_environment.borrow());
+
+    // Assert
+    assert_eq!(signatures, vec![]);
+}
+
+#[test]
+fn method_signature_help_returns_every_ambiguous_candidate() {
+    // Arrange
+    let type_environment = check_type_env(
+        r#"
+        proto Weight { fun weight(self, scale: Int): Int; }
+        proto Heavy { fun weight(self, scale: Int): Int; }
+        struct Crate { items: Int }
+        imp Weight for Crate { fun weight(self, scale: Int): Int => self.items * scale }
+        imp Heavy for Crate { fun weight(self, scale: Int): Int => 0 }
+        "#,
+    );
+
+    let crate_type = type_environment.borrow().get_type("Crate").unwrap();
+
+    // Act
+    let signatures = method_signature_help(&crate_type, "weight", 0, &type_environment.borrow());
+
+    // Assert
+    let names: Vec<&str> = signatures
+        .iter()
+        .map(|signature| signature.name.as_str())
+        .collect();
+
+    assert_eq!(names, vec!["Heavy::weight", "Weight::weight"]);
+    assert!(signatures
+        .iter()
+        .all(|signature| signature.parameters == vec![Type::Int]
+            && signature.active_parameter == Some(0)));
+}
@@ -0,0 +1,44 @@
+mod common;
+
+use common::evaluate;
+
+use interpreter::Value;
+
+#[test]
+fn index_is_a_char_by_codepoint() {
+    // Arrange
+    let input = r#"let word = "héllo"; word[2]"#;
+
+    // Act
+    let value = evaluate(input);
+
+    // Assert
+    assert_eq!(value, Ok(Value::Char('l')));
+}
+
+#[test]
+fn slice_is_a_substring() {
+    // Arrange
+    let input = r#"let word = "héllo"; word[1..=3]"#;
+
+    // Act
+    let value = evaluate(input);
+
+    // Assert
+    assert_eq!(value, Ok(Value::String("éll".to_string())));
+}
+
+#[test]
+fn constant_index_out_of_range_is_an_error() {
+    // Arrange
+    let input = r#""héllo"[5]"#;
+
+    // Act
+    let value = evaluate(input);
+
+    // Assert
+    assert_eq!(
+        value,
+        Err("Index 5 is out of range for a string of length 5".to_string())
+    );
+}
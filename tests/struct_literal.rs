@@ -0,0 +1,170 @@
+mod common;
+
+use common::evaluate_with;
+
+use interpreter::{value::Number, Value};
+
+const POINT: &str = r#"
+struct Point { x: Int, y: Int = 10, z: Int = 20 }
+"#;
+
+#[test]
+fn omitted_field_takes_its_default() {
+    // Arrange
+    let input = "let p = Point { x: 1, z: 2 }; p.x + p.y + p.z";
+
+    // Act
+    let value = evaluate_with(POINT, input);
+
+    // Assert
+    assert_eq!(value, Ok(Value::Number(Number::Int(13))));
+}
+
+#[test]
+fn omitted_field_without_default_is_an_error() {
+    // Arrange
+    let input = "Point { y: 1 }";
+
+    // Act
+    let value = evaluate_with(POINT, input);
+
+    // Assert
+    assert_eq!(
+        value,
+        Err("Missing field 'x' in literal of struct 'Point'".to_string())
+    );
+}
+
+#[test]
+fn default_must_match_the_field_type() {
+    // Arrange
+    let input = "struct Line { length: Int = 1 > 2 }";
+
+    // Act
+    let value = evaluate_with(POINT, input);
+
+    // Assert
+    assert_eq!(
+        value,
+        Err("Default value of field 'length' must have type Int, found Bool".to_string())
+    );
+}
+
+#[test]
+fn listed_fields_override_the_spread() {
+    // Arrange
+    let input =
+        "let base = Point { x: 1, y: 2, z: 3 }; let p = Point { y: 5, ..base }; p.x + p.y + p.z";
+
+    // Act
+    let value = evaluate_with(POINT, input);
+
+    // Assert
+    assert_eq!(value, Ok(Value::Number(Number::Int(9))));
+}
+
+#[test]
+fn spread_of_another_struct_is_an_error() {
+    // Arrange
+    let input = r#"
+    struct Line { length: Int }
+    let line = Line { length: 1 };
+    Point { x: 1, ..line }
+    "#;
+
+    // Act
+    let value = evaluate_with(POINT, input);
+
+    // Assert
+    assert_eq!(
+        value,
+        Err("Cannot spread Line into a literal of struct 'Point'".to_string())
+    );
+}
+
+#[test]
+fn every_missing_field_is_listed() {
+    // Arrange
+    let input = "struct Line { start: Int, end: Int, width: Int = 1 } Line { width: 2 }";
+
+    // Act
+    let value = evaluate_with(POINT, input);
+
+    // Assert
+    assert_eq!(
+        value,
+        Err("Missing fields 'start', 'end' in literal of struct 'Line'".to_string())
+    );
+}
+
+#[test]
+fn unknown_field_is_listed_with_missing_fields() {
+    // Arrange
+    let input = "Point { w: 1, y: 2 }";
+
+    // Act
+    let value = evaluate_with(POINT, input);
+
+    // Assert
+    assert_eq!(
+        value,
+        Err("Missing field 'x' and unknown field 'w' in literal of struct 'Point'".to_string())
+    );
+}
+
+#[test]
+fn duplicate_field_is_an_error() {
+    // Arrange
+    let input = "Point { x: 1, x: 2 }";
+
+    // Act
+    let value = evaluate_with(POINT, input);
+
+    // Assert
+    assert_eq!(
+        value,
+        Err("Field 'x' is initialized more than once in literal of struct 'Point'".to_string())
+    );
+}
+
+#[test]
+fn mixing_positional_and_named_fields_is_an_error() {
+    // Arrange
+    let input = "Point { 1, y: 2 }";
+
+    // Act
+    let value = evaluate_with(POINT, input);
+
+    // Assert
+    assert_eq!(
+        value,
+        Err("Cannot mix positional and named fields in literal of struct 'Point'".to_string())
+    );
+}
+
+#[test]
+fn positional_fields_bind_in_declared_order() {
+    // Arrange
+    let input = "let p = Point { 1, 2, 3 }; p.x * 100 + p.y * 10 + p.z";
+
+    // Act
+    let value = evaluate_with(POINT, input);
+
+    // Assert
+    assert_eq!(value, Ok(Value::Number(Number::Int(123))));
+}
+
+#[test]
+fn positional_literal_with_wrong_arity_is_an_error() {
+    // Arrange
+    let input = "Point { 1, 2 }";
+
+    // Act
+    let value = evaluate_with(POINT, input);
+
+    // Assert
+    assert_eq!(
+        value,
+        Err("Expected 3 positional fields in literal of struct 'Point' but got 2".to_string())
+    );
+}
@@ -0,0 +1,63 @@
+mod common;
+
+use common::try_create_typed_ast;
+
+use shared::type_checker::suggestion::{closest, edit_distance};
+
+#[test]
+fn unknown_variable_with_one_character_typo_suggests_the_variable() {
+    // Arrange
+    let input = "let length = 5; lenth + 1";
+
+    // Act
+    let result = try_create_typed_ast(input);
+
+    // Assert
+    assert_eq!(
+        result.unwrap_err(),
+        "Unexpected variable: lenth, did you mean `length`?"
+    );
+}
+
+#[test]
+fn unknown_type_with_one_character_typo_suggests_the_type() {
+    // Arrange
+    let input = "struct Point { x: Int }\nfun origin(): Poimt => Point { x: 0 }";
+
+    // Act
+    let result = try_create_typed_ast(input);
+
+    // Assert
+    let error = result.unwrap_err();
+    assert!(
+        error.ends_with("Type Poimt not found, did you mean `Point`?"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn unknown_variable_unlike_any_name_suggests_nothing() {
+    // Arrange
+    let input = "let length = 5; quux + 1";
+
+    // Act
+    let result = try_create_typed_ast(input);
+
+    // Assert
+    assert_eq!(result.unwrap_err(), "Unexpected variable: quux");
+}
+
+#[test]
+fn closest_picks_the_fewest_edits_within_the_threshold() {
+    // Arrange
+    let candidates = ["count", "counter", "amount"];
+
+    // Act
+    let suggestion = closest("coutn", candidates);
+
+    // Assert
+    assert_eq!(edit_distance("coutn", "count"), 2);
+    assert_eq!(suggestion, None);
+    assert_eq!(closest("counte", candidates), Some("count"));
+}
@@ -0,0 +1,78 @@
+mod common;
+
+use common::tokenize;
+
+use shared::parser::{self, Expression, ParseError, Statement};
+
+fn parse_all(source: &str) -> (Statement, Vec<ParseError>) {
+    parser::create_ast_collecting_errors(tokenize(source), false)
+}
+
+#[test]
+fn two_syntax_errors_are_both_reported() {
+    // Arrange
+    let source = r#"
+    let a = (1 + ;
+    let b = 2;
+    fun f(): Int => { let c = ; b }
+    "#;
+
+    // Act
+    let (_, errors) = parse_all(source);
+
+    // Assert
+    let found_at: Vec<&str> = errors
+        .iter()
+        .map(|error| &source[error.span.start..error.span.end])
+        .collect();
+
+    assert_eq!(found_at, vec![";", ";"], "{:?}", errors);
+}
+
+#[test]
+fn failed_statements_are_replaced_with_placeholders() {
+    // Arrange
+    let source = "let a = (1 + ; let b = 2; fun f(): Int => { let c = ; b }";
+
+    // Act
+    let (program, _) = parse_all(source);
+
+    // Assert
+    let Statement::Program { statements } = program else {
+        panic!("Expected a program");
+    };
+
+    let [Statement::Error(span), Statement::Semi(_), Statement::FunctionDeclaration(function)] =
+        &statements[..]
+    else {
+        panic!(
+            "Expected a placeholder, a statement and a function: {:?}",
+            statements
+        );
+    };
+
+    assert_eq!(&source[span.start..span.end], "let a = (1 + ;");
+
+    let Some(Expression::Block(body)) = &function.body else {
+        panic!("Expected a block but got {:?}", function.body);
+    };
+
+    assert!(
+        matches!(body[..], [Statement::Error(_), Statement::Expression(_)]),
+        "{:?}",
+        body
+    );
+}
+
+#[test]
+fn program_without_errors_is_parsed_like_create_ast() {
+    // Arrange
+    let source = "fun double(x: Int): Int => { let y = x; y * 2 } double(4)";
+
+    // Act
+    let (program, errors) = parse_all(source);
+
+    // Assert
+    assert_eq!(errors, vec![]);
+    assert_eq!(parser::create_ast(tokenize(source), false), Ok(program));
+}
@@ -0,0 +1,82 @@
+mod common;
+
+use common::{create_env, create_typed_ast, evaluate_expression};
+
+use interpreter::{value::Number, Value};
+use shared::type_checker::{
+    ast::TypedExpression,
+    visit::{walk_expression, TypedVisitor},
+};
+
+/// Whether each call in the program is marked as a tail call, outermost first
+#[derive(Default)]
+struct TailCalls {
+    marks: Vec<bool>,
+}
+
+impl TypedVisitor for TailCalls {
+    fn visit_expression(&mut self, expression: &TypedExpression) {
+        if let TypedExpression::Call { tail_call, .. } = expression {
+            self.marks.push(*tail_call);
+        }
+
+        walk_expression(self, expression);
+    }
+}
+
+fn tail_calls(input: &str) -> Vec<bool> {
+    let mut tail_calls = TailCalls::default();
+    tail_calls.visit_statement(&create_typed_ast(input));
+    tail_calls.marks
+}
+
+#[test]
+fn tail_recursive_call_is_marked() {
+    // Arrange
+    let input = "fun add(a: Int, b: Int): Int => if a == 0 => b else => add(a - 1, b + 1)";
+
+    // Act
+    let marks = tail_calls(input);
+
+    // Assert
+    assert_eq!(marks, vec![true, false]);
+}
+
+#[test]
+fn returned_call_is_marked() {
+    // Arrange
+    let input = "fun count(n: Int): Int => { if n > 0 => { return count(n - 1); }; 0 }";
+
+    // Act
+    let marks = tail_calls(input);
+
+    // Assert
+    assert_eq!(marks, vec![true]);
+}
+
+#[test]
+fn non_tail_recursive_call_is_not_marked() {
+    // Arrange
+    let input = "fun factorial(n: Int): Int => if n == 0 => 1 else => n * factorial(n - 1)";
+
+    // Act
+    let marks = tail_calls(input);
+
+    // Assert
+    assert_eq!(marks, vec![false]);
+}
+
+#[test]
+fn recursive_function_is_evaluated() {
+    // Arrange
+    let input = r#"
+        fun factorial(n: Int): Int => if n == 0 => 1 else => n * factorial(n - 1)
+        factorial(5)
+    "#;
+
+    // Act
+    let value = evaluate_expression(input, create_env(), false);
+
+    // Assert
+    assert_eq!(value, Value::Number(Number::Int(120)));
+}
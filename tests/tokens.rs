@@ -0,0 +1,75 @@
+use shared::lexer::{
+    self,
+    token::{Keyword, SpannedToken, TokenKind},
+    LexError,
+};
+
+/// The kind of each token and the source it spans
+fn kinds_and_text<'a>(source: &'a str, tokens: &[SpannedToken]) -> Vec<(TokenKind, &'a str)> {
+    tokens
+        .iter()
+        .map(|token| {
+            (
+                token.kind.clone(),
+                &source[token.span.start..token.span.end],
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn tokens_have_the_spans_they_are_written_at() {
+    // Arrange
+    let source = "let count = 10; // ten";
+
+    // Act
+    let tokens = lexer::tokenize_with_spans(source, false).unwrap();
+
+    // Assert
+    let kinds_and_text = kinds_and_text(source, &tokens);
+
+    assert_eq!(kinds_and_text.len(), 5, "{:?}", kinds_and_text);
+    assert_eq!(kinds_and_text[0], (TokenKind::Keyword(Keyword::Let), "let"));
+    assert_eq!(
+        kinds_and_text[1],
+        (TokenKind::Identifier("count".to_string()), "count")
+    );
+    assert_eq!(kinds_and_text[4], (TokenKind::Semicolon, ";"));
+    assert_eq!((tokens[3].span.start, tokens[3].span.end), (12, 14));
+}
+
+#[test]
+fn trivia_is_kept_when_asked_for() {
+    // Arrange
+    let source = "a // note\nb";
+
+    // Act
+    let tokens = lexer::tokenize_with_spans(source, true).unwrap();
+
+    // Assert
+    assert_eq!(
+        kinds_and_text(source, &tokens),
+        vec![
+            (TokenKind::Identifier("a".to_string()), "a"),
+            (TokenKind::WhiteSpace, " "),
+            (TokenKind::LineComment, "// note"),
+            (TokenKind::WhiteSpace, "\n"),
+            (TokenKind::Identifier("b".to_string()), "b"),
+        ]
+    );
+}
+
+#[test]
+fn lex_error_has_the_span_it_failed_at() {
+    // Arrange
+    let source = "let a = `b`";
+
+    // Act
+    let error = lexer::tokenize_with_spans(source, false).unwrap_err();
+
+    // Assert
+    let LexError { message, span } = error;
+
+    assert_eq!(message, "Unrecognized character: `");
+    assert_eq!(&source[span.start..span.end], "`");
+}
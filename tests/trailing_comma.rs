@@ -0,0 +1,80 @@
+mod common;
+
+use common::tokenize;
+
+use shared::parser::{self, Statement};
+
+fn parse(source: &str) -> Result<Statement, String> {
+    parser::create_ast(tokenize(source), false)
+}
+
+#[test]
+fn trailing_comma_in_struct_literal() {
+    // Arrange
+    let source = "let p = Point { x: 1, y: 2, };";
+
+    // Act
+    let ast = parse(source);
+
+    // Assert
+    assert_eq!(ast, parse("let p = Point { x: 1, y: 2 };"));
+}
+
+#[test]
+fn trailing_comma_in_argument_list() {
+    // Arrange
+    let source = "let a = add(1, 2,);";
+
+    // Act
+    let ast = parse(source);
+
+    // Assert
+    assert_eq!(ast, parse("let a = add(1, 2);"));
+}
+
+#[test]
+fn trailing_comma_after_match_arms() {
+    // Arrange
+    let source = "let r = x match | 0 => 1, | _ => 2,;";
+
+    // Act
+    let ast = parse(source);
+
+    // Assert
+    assert_eq!(ast, parse("let r = x match | 0 => 1, | _ => 2;"));
+}
+
+#[test]
+fn trailing_comma_in_array_literal() {
+    // Arrange
+    let source = "let a = [1, 2, 3,];";
+
+    // Act
+    let ast = parse(source);
+
+    // Assert
+    assert_eq!(ast, parse("let a = [1, 2, 3];"));
+}
+
+#[test]
+fn double_trailing_comma_is_an_error() {
+    // Arrange
+    let sources = [
+        "let p = Point { x: 1,, };",
+        "let a = add(1, 2,,);",
+        "let a = [1, 2,,];",
+    ];
+
+    // Act
+    let results: Vec<Result<Statement, String>> = sources.iter().map(|s| parse(s)).collect();
+
+    // Assert
+    assert_eq!(
+        results,
+        vec![
+            Err("Expected CloseBrace after trailing , but found ,".to_string()),
+            Err("Expected CloseParen after trailing , but found ,".to_string()),
+            Err("Expected CloseBracket after trailing , but found ,".to_string()),
+        ]
+    );
+}
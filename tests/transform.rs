@@ -0,0 +1,125 @@
+mod common;
+
+use common::{create_env, create_typed_ast, StatementExt, VecStatementExt};
+
+use interpreter::{value::Number, Value};
+use shared::type_checker::{
+    ast::{Literal, TypedExpression},
+    transform::{fold_expression, fold_literal, TypedTransformer},
+    visit::{walk_literal, TypedVisitor},
+};
+
+struct ZeroToOne;
+
+impl TypedTransformer for ZeroToOne {
+    fn transform_literal(&mut self, literal: Literal) -> Literal {
+        match literal {
+            Literal::Int(0) => Literal::Int(1),
+            literal => fold_literal(self, literal),
+        }
+    }
+}
+
+#[derive(Default)]
+struct IntCounter {
+    zeros: usize,
+    ones: usize,
+}
+
+impl TypedVisitor for IntCounter {
+    fn visit_literal(&mut self, literal: &Literal) {
+        match literal {
+            Literal::Int(0) => self.zeros += 1,
+            Literal::Int(1) => self.ones += 1,
+            _ => {}
+        }
+
+        walk_literal(self, literal);
+    }
+}
+
+#[test]
+fn transformer_replaces_zero_throughout_tree() {
+    // Arrange
+    let input = r#"
+        fun pick(x: Int): Int => if x > 0 => x else => 0
+        let a = pick(0) + 0;
+        let c = [0, 2];
+        let b = a match
+        | 1 => a - 0,
+        | _ => a * 0;
+    "#;
+
+    let typed_ast = create_typed_ast(input);
+    let mut before = IntCounter::default();
+    before.visit_statement(&typed_ast);
+
+    // Act
+    let typed_ast = ZeroToOne.transform_statement(typed_ast);
+
+    // Assert
+    let mut after = IntCounter::default();
+    after.visit_statement(&typed_ast);
+
+    assert_eq!(before.zeros, 7);
+    assert_eq!(after.zeros, 0);
+    assert_eq!(after.ones, before.ones + before.zeros);
+}
+
+#[test]
+fn transformer_recurses_past_overridden_variant() {
+    // Arrange
+    let input = r#"
+        fun add(x: Int): Int => x + 0
+        add(0) * 5
+    "#;
+
+    struct LiteralZeroToOne;
+
+    impl TypedTransformer for LiteralZeroToOne {
+        fn transform_expression(&mut self, expression: TypedExpression) -> TypedExpression {
+            match expression {
+                TypedExpression::Literal(Literal::Int(0), span) => {
+                    TypedExpression::Literal(Literal::Int(1), span)
+                }
+                expression => fold_expression(self, expression),
+            }
+        }
+    }
+
+    let typed_ast = create_typed_ast(input);
+
+    // Act
+    let typed_ast = LiteralZeroToOne.transform_statement(typed_ast);
+
+    // Assert
+    let value = interpreter::evaluate(typed_ast, create_env()).unwrap();
+
+    assert_eq!(value, Value::Number(Number::Int(10)));
+}
+
+#[test]
+fn transformer_clears_constants_of_rewritten_operands() {
+    // Arrange
+    let constant = |input: &str| {
+        let typed_ast = ZeroToOne.transform_statement(create_typed_ast(input));
+
+        let TypedExpression::Binary { constant, .. } = typed_ast
+            .unwrap_program()
+            .nth_statement(0)
+            .unwrap_expression()
+        else {
+            panic!("Expected a binary expression");
+        };
+
+        constant
+    };
+
+    // Act
+    let rewritten = constant("2 + 0");
+    let kept = constant("2 + 3");
+
+    // Assert
+    assert_eq!(rewritten, None);
+    assert_eq!(kept, Some(Literal::Int(5)));
+}
@@ -0,0 +1,79 @@
+mod common;
+
+use common::{create_typed_ast, evaluate_with, tokenize};
+
+use interpreter::{value::Number, Value};
+use shared::{
+    display::{Indent, IndentDisplay},
+    format::format,
+    parser::{self, Statement},
+};
+
+const PAIR: &str = r#"
+struct Pair(Int, mut Int)
+"#;
+
+#[test]
+fn tuple_struct_fields_are_named_by_their_index() {
+    // Arrange
+    let tokens = tokenize(PAIR);
+
+    // Act
+    let ast = parser::create_ast(tokens, false).unwrap();
+
+    // Assert
+    let Statement::Program { statements } = &ast else {
+        panic!("Expected a program");
+    };
+
+    let Some(Statement::StructDeclaration(pair)) = statements.first() else {
+        panic!("Expected a struct declaration");
+    };
+
+    let identifiers: Vec<&str> = pair.fields.iter().map(|f| f.identifier.as_str()).collect();
+
+    assert_eq!(identifiers, vec!["0", "1"]);
+    assert!(pair.fields[1].mutable);
+    assert_eq!(format(&ast), "struct Pair(Int, mut Int)\n");
+}
+
+#[test]
+fn tuple_struct_is_constructed_positionally_and_indexed() {
+    // Arrange
+    let input = "let mut p = Pair { 7, 2 }; p.1 = 3; p.0 - p.1";
+
+    // Act
+    let value = evaluate_with(PAIR, input);
+
+    // Assert
+    assert_eq!(value, Ok(Value::Number(Number::Int(4))));
+}
+
+#[test]
+fn nested_tuple_struct_is_indexed_twice() {
+    // Arrange
+    let input = "struct Wrap(Pair) let w = Wrap { Pair { 7, 2 } }; w.0.1";
+
+    // Act
+    let value = evaluate_with(PAIR, input);
+
+    // Assert
+    assert_eq!(value, Ok(Value::Number(Number::Int(2))));
+}
+
+#[test]
+fn tuple_struct_is_shown_in_indent_display() {
+    // Arrange
+    let typed_ast = create_typed_ast(PAIR);
+
+    // Act
+    let display = typed_ast.indent_display(&mut Indent::new());
+
+    // Assert
+    assert!(
+        display.starts_with("<tuple struct declaration> Pair\n"),
+        "{}",
+        display
+    );
+    assert!(display.contains("<struct field> 1: Int"), "{}", display);
+}
@@ -0,0 +1,85 @@
+use shared::type_checker::Type;
+
+/// Every primitive type, then a literal of each number type and a type which isn't primitive
+fn types() -> Vec<Type> {
+    let literal = |type_: Type| Type::Literal {
+        name: "1".to_string(),
+        type_: Box::new(type_),
+    };
+
+    vec![
+        Type::Int,
+        Type::UInt,
+        Type::Float,
+        Type::Char,
+        Type::Bool,
+        literal(Type::Int),
+        literal(Type::UInt),
+        literal(Type::Float),
+        Type::String,
+    ]
+}
+
+fn classify(predicate: fn(&Type) -> bool) -> Vec<bool> {
+    types().iter().map(predicate).collect()
+}
+
+#[test]
+fn is_numeric_holds_for_numbers() {
+    // Act
+    let numeric = classify(Type::is_numeric);
+
+    // Assert
+    assert_eq!(
+        numeric,
+        vec![true, true, true, false, false, true, true, true, false]
+    );
+}
+
+#[test]
+fn is_integer_holds_for_int_and_uint() {
+    // Act
+    let integer = classify(Type::is_integer);
+
+    // Assert
+    assert_eq!(
+        integer,
+        vec![true, true, false, false, false, true, true, false, false]
+    );
+}
+
+#[test]
+fn is_float_holds_for_float() {
+    // Act
+    let float = classify(Type::is_float);
+
+    // Assert
+    assert_eq!(
+        float,
+        vec![false, false, true, false, false, false, false, true, false]
+    );
+}
+
+#[test]
+fn is_signed_holds_for_int_and_float() {
+    // Act
+    let signed = classify(Type::is_signed);
+
+    // Assert
+    assert_eq!(
+        signed,
+        vec![true, false, true, false, false, true, false, true, false]
+    );
+}
+
+#[test]
+fn is_primitive_holds_for_numbers_chars_and_bools() {
+    // Act
+    let primitive = classify(Type::is_primitive);
+
+    // Assert
+    assert_eq!(
+        primitive,
+        vec![true, true, true, true, true, true, true, true, false]
+    );
+}
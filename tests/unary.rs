@@ -1,11 +1,15 @@
 mod common;
 
-use common::{create_typed_ast, StatementExt, VecStatementExt};
+use common::{create_typed_ast, tokenize, StatementExt, VecStatementExt};
 
 use interpreter::{value::Number, Value};
-use shared::type_checker::{
-    ast::{Literal, Typed, TypedExpression, UnaryOperator},
-    Type,
+use shared::{
+    parser,
+    span::Span,
+    type_checker::{
+        ast::{Literal, Typed, TypedExpression, UnaryOperator},
+        Type,
+    },
 };
 
 #[test]
@@ -45,7 +49,10 @@ fn negate_is_negate() {
         .nth_statement(0)
         .unwrap_expression();
 
-    assert_eq!(expression, TypedExpression::Literal(Literal::Int(-1)));
+    assert_eq!(
+        expression,
+        TypedExpression::Literal(Literal::Int(-1), Span::default())
+    );
 }
 
 #[test]
@@ -215,3 +222,18 @@ fn bitwise_not_returns_correct_value() {
     // Assert
     assert_eq!(value, Value::Number(Number::Int(-2)));
 }
+
+#[test]
+fn negating_an_unsigned_literal_is_an_error() {
+    // Arrange
+    let input = "-1u";
+
+    // Act
+    let ast = parser::create_ast(tokenize(input), false);
+
+    // Assert
+    assert_eq!(
+        ast,
+        Err("Cannot negate the unsigned literal 1u, write -1 for a negative int".to_string())
+    );
+}
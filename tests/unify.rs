@@ -0,0 +1,98 @@
+mod common;
+
+use common::{create_typed_ast, StatementExt, VecStatementExt};
+
+use shared::type_checker::{ast::Typed, Type, TypeMismatch};
+
+fn literal(name: &str, type_: Type) -> Type {
+    Type::Literal {
+        name: name.to_string(),
+        type_: Box::new(type_),
+    }
+}
+
+#[test]
+fn unify_widens_different_literals_to_their_type() {
+    // Act
+    let common = Type::unify(&literal("1", Type::Int), &literal("2", Type::Int));
+
+    // Assert
+    assert_eq!(common, Ok(Type::Int));
+}
+
+#[test]
+fn unify_gives_way_to_the_other_type_for_unknown() {
+    // Act
+    let left = Type::unify(&Type::Unknown, &Type::String);
+    let right = Type::unify(&Type::String, &Type::Unknown);
+
+    // Assert
+    assert_eq!(left, Ok(Type::String));
+    assert_eq!(right, Ok(Type::String));
+}
+
+#[test]
+fn unify_all_fails_at_the_first_mismatch() {
+    // Act
+    let common = Type::unify_all(&[Type::Int, literal("1", Type::Int), Type::Float, Type::Bool]);
+
+    // Assert
+    assert_eq!(
+        common,
+        Err(TypeMismatch {
+            left: Box::new(Type::Int),
+            right: Box::new(Type::Float),
+        })
+    );
+}
+
+#[test]
+fn if_with_literal_branches_has_their_common_type() {
+    // Arrange
+    let input = "let c = true; if c => 1 else 2";
+
+    // Act
+    let typed_ast = create_typed_ast(input);
+
+    // Assert
+    let expression = typed_ast
+        .unwrap_program()
+        .nth_statement(1)
+        .unwrap_expression();
+
+    assert_eq!(expression.get_type(), Type::Int);
+}
+
+#[test]
+fn match_with_literal_arms_has_their_common_type() {
+    // Arrange
+    let input = "let x = 1; x match | 1 => 10 | _ => 20";
+
+    // Act
+    let typed_ast = create_typed_ast(input);
+
+    // Assert
+    let expression = typed_ast
+        .unwrap_program()
+        .nth_statement(1)
+        .unwrap_expression();
+
+    assert_eq!(expression.get_type(), Type::Int);
+}
+
+#[test]
+fn loop_breaking_with_a_literal_has_the_common_type_of_break_and_else() {
+    // Arrange
+    let input = "let c = true; while c => { break 2 } else 0";
+
+    // Act
+    let typed_ast = create_typed_ast(input);
+
+    // Assert
+    let expression = typed_ast
+        .unwrap_program()
+        .nth_statement(1)
+        .unwrap_expression();
+
+    assert_eq!(expression.get_type(), Type::Int);
+}
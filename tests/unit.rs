@@ -0,0 +1,118 @@
+mod common;
+
+use common::{create_env, create_typed_ast, evaluate_expression, StatementExt, VecStatementExt};
+
+use interpreter::Value;
+use shared::type_checker::{ast::Typed, Type};
+
+#[test]
+fn unit_literal_is_unit() {
+    // Arrange
+    let input = "unit";
+
+    // Act
+    let typed_ast = create_typed_ast(input);
+
+    // Assert
+    let expression = typed_ast
+        .unwrap_program()
+        .nth_statement(0)
+        .unwrap_expression();
+
+    assert_eq!(expression.get_deep_type(), Type::Unit);
+}
+
+#[test]
+fn expression_statement_is_void() {
+    // Arrange
+    let input = "1;";
+
+    // Act
+    let typed_ast = create_typed_ast(input);
+
+    // Assert
+    let statement = typed_ast.unwrap_program().nth_statement(0);
+
+    assert_eq!(statement.get_type(), Type::Void);
+}
+
+#[test]
+fn function_without_return_type_returns_unit() {
+    // Arrange
+    let input = r#"
+        fun a() => {}
+        a()
+    "#;
+
+    // Act
+    let typed_ast = create_typed_ast(input);
+
+    // Assert
+    let expression = typed_ast
+        .unwrap_program()
+        .nth_statement(1)
+        .unwrap_expression();
+
+    assert_eq!(expression.get_type(), Type::Unit);
+}
+
+#[test]
+fn function_without_return_type_evaluates_to_unit() {
+    // Arrange
+    let input = r#"
+        fun a() => {}
+        a()
+    "#;
+
+    // Act
+    let value = evaluate_expression(input, create_env(), false);
+
+    // Assert
+    assert_eq!(value, Value::Unit);
+}
+
+#[test]
+fn unit_function_can_be_assigned_to_a_variable() {
+    // Arrange
+    let input = r#"
+        fun a() => {}
+        let x = a();
+        x
+    "#;
+
+    // Act
+    let value = evaluate_expression(input, create_env(), false);
+
+    // Assert
+    assert_eq!(value, Value::Unit);
+}
+
+#[test]
+#[should_panic]
+fn function_without_return_type_cannot_return_a_value() {
+    // Arrange
+    let input = "fun a() => { 1 }";
+
+    // Act
+    create_typed_ast(input); // panics
+}
+
+#[test]
+#[should_panic]
+fn void_cannot_be_assigned_to_a_variable() {
+    // Arrange
+    let input = "let x = while false => {};";
+
+    // Act
+    create_typed_ast(input); // panics
+}
+
+#[test]
+#[should_panic]
+fn void_cannot_be_assigned_to_an_annotated_variable() {
+    // Arrange
+    let input = "let x: Int = while false => {};";
+
+    // Act
+    create_typed_ast(input); // panics
+}
@@ -0,0 +1,82 @@
+mod common;
+
+use common::check_warnings;
+
+#[test]
+fn unused_let_is_a_warning() {
+    // Arrange
+    let input = "fun f(a: Int): Int => { let b = a * 2; a }";
+
+    // Act
+    let warnings = check_warnings(input);
+
+    // Assert
+    assert_eq!(
+        warnings,
+        vec!["Variable 'b' in function 'f' is never read, prefix it with '_' if that is intended"]
+    );
+}
+
+#[test]
+fn read_and_underscored_lets_are_not_warnings() {
+    // Arrange
+    let input = "let x = 1; let _y = 2; let z = x + 1; z";
+
+    // Act
+    let warnings = check_warnings(input);
+
+    // Assert
+    assert_eq!(warnings, Vec::<String>::new());
+}
+
+#[test]
+fn discarded_result_is_a_warning() {
+    // Arrange
+    let input = "fun f(a: Int): Int => { a + 1 a }";
+
+    // Act
+    let warnings = check_warnings(input);
+
+    // Assert
+    assert_eq!(
+        warnings,
+        vec!["Result of type Int in function 'f' is unused, end the expression with ';' to discard it"]
+    );
+}
+
+#[test]
+fn used_generics_are_not_warnings() {
+    // Arrange
+    let input = r#"
+    struct Reader<T> { value: T }
+    enum Either<L, R> { Left { value: L }, Right { value: R } }
+    fun first<T, U>(first: T, second: U): T => first
+    "#;
+
+    // Act
+    let warnings = check_warnings(input);
+
+    // Assert
+    assert_eq!(warnings, Vec::<String>::new());
+}
+
+#[test]
+fn unused_generics_are_warnings() {
+    // Arrange
+    let input = r#"
+    struct Tag<T> { id: Int }
+    fun length<T>(value: String): Int => 0
+    "#;
+
+    // Act
+    let warnings = check_warnings(input);
+
+    // Assert
+    assert_eq!(
+        warnings,
+        vec![
+            "Generic type 'T' of struct 'Tag' is never used in its fields",
+            "Generic type 'T' of function 'length' is never used in its signature",
+        ]
+    );
+}
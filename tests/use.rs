@@ -0,0 +1,297 @@
+mod common;
+
+use common::{check_with_env, create_type_env, Rcrc};
+
+use shared::type_checker::{Type, TypeEnvironment};
+
+const GEOMETRY: &str = r#"
+pub mod geometry::shapes;
+pub struct Point { pub x: Int, pub y: Int }
+pub struct Line { pub from: Point, pub to: Point }
+pub fun origin(): Point => Point { x: 0, y: 0 }
+struct Secret { value: Int }
+"#;
+
+/// Type check the sources in turn with a shared type environment, returning the environment
+fn check_modules(sources: &[&str]) -> Result<Rcrc<TypeEnvironment>, String> {
+    let type_environment = create_type_env();
+
+    for source in sources {
+        check_with_env(source, type_environment.clone())?;
+    }
+
+    Ok(type_environment)
+}
+
+fn path(path: &str) -> Option<Vec<String>> {
+    Some(path.split("::").map(|s| s.to_string()).collect())
+}
+
+#[test]
+fn navigation_import_is_resolved() {
+    // Arrange
+    let app = r#"
+    pub mod app;
+    use geometry::shapes::Point;
+    "#;
+
+    // Act
+    let type_environment = check_modules(&[GEOMETRY, app]).unwrap();
+
+    // Assert
+    assert_eq!(
+        type_environment.borrow().get_import("Point"),
+        path("geometry::shapes::Point")
+    );
+}
+
+#[test]
+fn list_import_brings_in_every_item() {
+    // Arrange
+    let app = r#"
+    pub mod app;
+    use geometry::shapes::{Line, origin};
+    "#;
+
+    // Act
+    let type_environment = check_modules(&[GEOMETRY, app]).unwrap();
+
+    // Assert
+    let type_environment = type_environment.borrow();
+
+    assert_eq!(
+        type_environment.get_import("Line"),
+        path("geometry::shapes::Line")
+    );
+    assert_eq!(
+        type_environment.get_import("origin"),
+        path("geometry::shapes::origin")
+    );
+    assert_eq!(type_environment.get_import("Point"), None);
+}
+
+#[test]
+fn module_can_be_imported() {
+    // Arrange
+    let app = r#"
+    pub mod app;
+    use geometry::shapes;
+    "#;
+
+    // Act
+    let type_environment = check_modules(&[GEOMETRY, app]).unwrap();
+
+    // Assert
+    assert_eq!(
+        type_environment.borrow().get_import("shapes"),
+        path("geometry::shapes")
+    );
+}
+
+#[test]
+fn unresolved_import_names_the_missing_segment() {
+    // Arrange
+    let app = r#"
+    pub mod app;
+    use geometry::lines::Line;
+    "#;
+
+    // Act
+    let result = check_modules(&[GEOMETRY, app]);
+
+    // Assert
+    assert_eq!(
+        result.err(),
+        Some(
+            "Unresolved import 'geometry::lines::Line': 'lines' not found in module 'geometry'"
+                .to_string()
+        )
+    );
+}
+
+#[test]
+fn unresolved_item_in_list_is_an_error() {
+    // Arrange
+    let app = r#"
+    pub mod app;
+    use geometry::shapes::{Point, Circle};
+    "#;
+
+    // Act
+    let result = check_modules(&[GEOMETRY, app]);
+
+    // Assert
+    assert_eq!(
+        result.err(),
+        Some(
+            "Unresolved import 'geometry::shapes::{Point, Circle}': 'Circle' not found in module 'geometry::shapes'"
+                .to_string()
+        )
+    );
+}
+
+#[test]
+fn private_item_cannot_be_imported_from_another_module() {
+    // Arrange
+    let app = r#"
+    pub mod app;
+    use geometry::shapes::Secret;
+    "#;
+
+    // Act
+    let result = check_modules(&[GEOMETRY, app]);
+
+    // Assert
+    assert_eq!(
+        result.err(),
+        Some(
+            "Cannot import 'geometry::shapes::Secret', it is not visible from module 'app'"
+                .to_string()
+        )
+    );
+}
+
+#[test]
+fn glob_import_brings_in_public_items_only() {
+    // Arrange
+    let app = r#"
+    pub mod app;
+    use geometry::shapes::*;
+    let p = origin();
+    "#;
+
+    // Act
+    let type_environment = check_modules(&[GEOMETRY, app]).unwrap();
+
+    // Assert
+    let type_environment = type_environment.borrow();
+
+    assert_eq!(
+        type_environment.get_import("Point"),
+        path("geometry::shapes::Point")
+    );
+    assert_eq!(
+        type_environment.get_import("origin"),
+        path("geometry::shapes::origin")
+    );
+    assert_eq!(type_environment.get_import("Secret"), None);
+}
+
+const COUNTERS: [&str; 2] = [
+    "pub mod first; pub fun start(): Int => 0 pub fun step(): Int => 1",
+    "pub mod second; pub fun start(): Int => 10",
+];
+
+#[test]
+fn ambiguous_glob_import_is_an_error_when_used() {
+    // Arrange
+    let app = r#"
+    pub mod app;
+    use first::*;
+    use second::*;
+    let s = start();
+    "#;
+
+    // Act
+    let result = check_modules(&[COUNTERS[0], COUNTERS[1], app]);
+
+    // Assert
+    assert_eq!(
+        result.err(),
+        Some(
+            "'start' is ambiguous, it is glob imported from 'first::start' and 'second::start'"
+                .to_string()
+        )
+    );
+}
+
+#[test]
+fn ambiguous_glob_import_is_allowed_when_unused() {
+    // Arrange
+    let app = r#"
+    pub mod app;
+    use first::*;
+    use second::*;
+    let s = step();
+    "#;
+
+    // Act
+    let type_environment = check_modules(&[COUNTERS[0], COUNTERS[1], app]).unwrap();
+
+    // Assert
+    assert_eq!(type_environment.borrow().get_import("start"), None);
+    assert_eq!(
+        type_environment.borrow().get_import("step"),
+        path("first::step")
+    );
+}
+
+#[test]
+fn explicit_import_shadows_glob_imports() {
+    // Arrange
+    let app = r#"
+    pub mod app;
+    use first::*;
+    use second::*;
+    use second::start;
+    let s = start();
+    "#;
+
+    // Act
+    let type_environment = check_modules(&[COUNTERS[0], COUNTERS[1], app]).unwrap();
+
+    // Assert
+    assert_eq!(
+        type_environment.borrow().get_import("start"),
+        path("second::start")
+    );
+}
+
+#[test]
+fn explicit_import_resolves_to_the_imported_item_when_declared_first() {
+    // Arrange
+    let first = "pub mod first; pub fun start(): Int => 0";
+    let second = "pub mod second; pub fun start(): Bool => true";
+    let app = r#"
+    pub mod app;
+    use first::*;
+    use second::*;
+    use first::start;
+    let s = start();
+    "#;
+
+    // Act
+    let type_environment = check_modules(&[first, second, app]).unwrap();
+
+    // Assert
+    assert_eq!(type_environment.borrow().get_variable("s"), Some(Type::Int));
+}
+
+#[test]
+fn path_resolves_to_the_item_of_its_module() {
+    // Arrange
+    let first = "pub mod first; pub fun start(): Int => 0";
+    let second = "pub mod second; pub fun start(): Bool => true";
+    let app = r#"
+    pub mod app;
+    let s = first::start();
+    "#;
+
+    // Act
+    let type_environment = check_modules(&[first, second, app]).unwrap();
+
+    // Assert
+    assert_eq!(type_environment.borrow().get_variable("s"), Some(Type::Int));
+}
+
+#[test]
+fn same_name_cannot_be_declared_twice_in_one_module() {
+    // Arrange
+    let first = "pub mod first; pub fun start(): Int => 0 pub fun start(): Int => 1";
+
+    // Act
+    let result = check_modules(&[first]);
+
+    // Assert
+    assert!(result.is_err());
+}
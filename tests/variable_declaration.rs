@@ -1,12 +1,18 @@
 mod common;
 
-use common::{create_env, create_typed_ast, evaluate_expression, StatementExt, VecStatementExt};
+use common::{
+    create_env, create_typed_ast, evaluate_expression, try_create_typed_ast, StatementExt,
+    VecStatementExt,
+};
 
-use interpreter::Value;
-use shared::type_checker::{
-    ast::{Literal, Typed, TypedExpression},
-    decision_tree::Pattern,
-    Type,
+use interpreter::{value::Number, Value};
+use shared::{
+    span::Span,
+    type_checker::{
+        ast::{Literal, Typed, TypedExpression},
+        decision_tree::Pattern,
+        Type,
+    },
 };
 
 #[test]
@@ -151,7 +157,10 @@ fn variable_declaration_has_value() {
             assert!(initializer.is_some());
 
             let initializer = *initializer.unwrap();
-            assert_eq!(initializer, TypedExpression::Literal(Literal::Bool(true)));
+            assert_eq!(
+                initializer,
+                TypedExpression::Literal(Literal::Bool(true), Span::default())
+            );
         }
         _ => panic!(
             "Expected a variable declaration, but found {:?}",
@@ -286,3 +295,55 @@ fn variable_declaration_function_type_is_used_to_infer_closure_parameter_types()
         })
     );
 }
+
+#[test]
+fn use_before_assignment_is_an_error() {
+    // Arrange
+    let input = "let x: Int; x + 1";
+
+    // Act
+    let typed_ast = try_create_typed_ast(input);
+
+    // Assert
+    assert_eq!(
+        typed_ast,
+        Err("Variable 'x' is used before it is assigned".to_string())
+    );
+}
+
+#[test]
+fn assignment_on_every_branch_initializes() {
+    // Arrange
+    let input = r#"
+        let c = false;
+        let x: Int;
+        if c => { x = 1; } else => { x = 2; };
+        x
+    "#;
+
+    // Act
+    let value = evaluate_expression(input, create_env(), false);
+
+    // Assert
+    assert_eq!(value, Value::Number(Number::Int(2)));
+}
+
+#[test]
+fn assignment_on_one_branch_is_an_error() {
+    // Arrange
+    let input = r#"
+        let c = true;
+        let x: Int;
+        if c => { x = 1; };
+        x
+    "#;
+
+    // Act
+    let typed_ast = try_create_typed_ast(input);
+
+    // Assert
+    assert_eq!(
+        typed_ast,
+        Err("Variable 'x' is used before it is assigned on every path".to_string())
+    );
+}
@@ -0,0 +1,70 @@
+mod common;
+
+use common::try_create_typed_ast;
+
+const NUMBER: &str = "type Number = Int or Float;\n";
+
+#[test]
+fn covariant_generic_accepts_a_narrower_type() {
+    // Arrange
+    let input = format!(
+        "{}{}",
+        NUMBER,
+        r#"
+        struct Reader<T> { value: T, read: fun(): T }
+        let reader: Reader<Number> = Reader<Int> { value: 5, read: || 5 };
+        "#
+    );
+
+    // Act
+    let result = try_create_typed_ast(&input);
+
+    // Assert
+    assert!(result.is_ok(), "{:?}", result);
+}
+
+#[test]
+fn covariant_generic_rejects_a_wider_type() {
+    // Arrange
+    let input = format!(
+        "{}{}",
+        NUMBER,
+        r#"
+        struct Reader<T> { value: T }
+        let number = Reader<Number> { value: 5 };
+        let mut reader = Reader<Int> { value: 1 };
+        reader = number;
+        "#
+    );
+
+    // Act
+    let result = try_create_typed_ast(&input);
+
+    // Assert
+    assert_eq!(
+        result.unwrap_err(),
+        "Member type Reader<Int> does not match initializer type Reader<Number>"
+    );
+}
+
+#[test]
+fn invariant_generic_rejects_a_narrower_type() {
+    // Arrange
+    let input = format!(
+        "{}{}",
+        NUMBER,
+        r#"
+        struct Sink<T> { write: fun(T): Int }
+        let sink: Sink<Number> = Sink<Int> { write: |x: Int| x };
+        "#
+    );
+
+    // Act
+    let result = try_create_typed_ast(&input);
+
+    // Assert
+    assert_eq!(
+        result.unwrap_err(),
+        "Sink<Int> cannot be used as Sink<Number>, because 'T' of 'Sink' is invariant"
+    );
+}
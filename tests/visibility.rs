@@ -0,0 +1,131 @@
+mod common;
+
+use common::{check_with_env, create_type_env};
+
+const SHAPES: &str = r#"
+pub mod shapes;
+pub struct Point { pub x: Int, mod y: Int, sup z: Int, w: Int }
+"#;
+
+/// Type check each source in turn with a shared type environment, like the files of a project
+fn check_modules(sources: &[&str]) -> Result<(), String> {
+    let type_environment = create_type_env();
+
+    for source in sources {
+        check_with_env(source, type_environment.clone())?;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn struct_literal_cannot_set_module_private_fields_from_another_module() {
+    // Arrange
+    let app = r#"
+    pub mod app;
+    let p = Point { x: 1, y: 2, z: 3, w: 4 };
+    "#;
+
+    // Act
+    let result = check_modules(&[SHAPES, app]);
+
+    // Assert
+    assert_eq!(
+        result,
+        Err(
+            "'Point.y' is declared in module 'shapes' and is not visible from module 'app'"
+                .to_string()
+        )
+    );
+}
+
+#[test]
+fn public_field_access_from_another_module_is_allowed() {
+    // Arrange
+    let app = r#"
+    pub mod app;
+    fun get_x(p: Point): Int => p.x
+    "#;
+
+    // Act
+    let result = check_modules(&[SHAPES, app]);
+
+    // Assert
+    assert_eq!(result, Ok(()));
+}
+
+#[test]
+fn module_private_field_access_from_another_module_is_rejected() {
+    // Arrange
+    let app = r#"
+    pub mod app;
+    fun get_y(p: Point): Int => p.y
+    "#;
+
+    // Act
+    let result = check_modules(&[SHAPES, app]);
+
+    // Assert
+    assert_eq!(
+        result,
+        Err(
+            "'Point.y' is declared in module 'shapes' and is not visible from module 'app'"
+                .to_string()
+        )
+    );
+}
+
+#[test]
+fn field_without_access_modifier_is_module_private() {
+    // Arrange
+    let app = r#"
+    pub mod app;
+    fun get_w(p: Point): Int => p.w
+    "#;
+
+    // Act
+    let result = check_modules(&[SHAPES, app]);
+
+    // Assert
+    assert!(result.is_err());
+}
+
+#[test]
+fn module_private_field_is_visible_from_a_submodule() {
+    // Arrange
+    let submodule = r#"
+    pub mod shapes::circle;
+    fun get_y(p: Point): Int => p.y
+    "#;
+
+    // Act
+    let result = check_modules(&[SHAPES, submodule]);
+
+    // Assert
+    assert_eq!(result, Ok(()));
+}
+
+#[test]
+fn super_field_is_visible_from_a_sibling_module() {
+    // Arrange
+    let shapes = r#"
+    pub mod geometry::shapes;
+    pub struct Point { sup z: Int }
+    "#;
+    let sibling = r#"
+    pub mod geometry::lines;
+    fun get_z(p: Point): Int => p.z
+    "#;
+    let outside = r#"
+    pub mod app;
+    fun get_z(p: Point): Int => p.z
+    "#;
+
+    // Act
+    let sibling_result = check_modules(&[shapes, sibling]);
+    let outside_result = check_modules(&[shapes, outside]);
+
+    // Assert
+    assert_eq!(sibling_result, Ok(()));
+    assert!(outside_result.is_err());
+}
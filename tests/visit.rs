@@ -0,0 +1,77 @@
+mod common;
+
+use common::create_typed_ast;
+
+use shared::type_checker::{
+    ast::TypedExpression,
+    visit::{walk_expression, TypedVisitor},
+};
+
+#[derive(Default)]
+struct CallCounter {
+    calls: usize,
+}
+
+impl TypedVisitor for CallCounter {
+    fn visit_expression(&mut self, expression: &TypedExpression) {
+        if let TypedExpression::Call { .. } = expression {
+            self.calls += 1;
+        }
+
+        walk_expression(self, expression);
+    }
+}
+
+#[test]
+fn visitor_counts_calls() {
+    // Arrange
+    let input = r#"
+        fun double(x: Int): Int => x * 2
+        fun quadruple(x: Int): Int => double(double(x))
+        let a = quadruple(1) + double(2);
+        let b = if a > 4 => double(a) else => 0;
+        let c = a match
+        | 1 => double(1),
+        | _ => 0;
+    "#;
+
+    let typed_ast = create_typed_ast(input);
+    let mut counter = CallCounter::default();
+
+    // Act
+    counter.visit_statement(&typed_ast);
+
+    // Assert
+    assert_eq!(counter.calls, 6);
+}
+
+#[test]
+fn visitor_stops_recursing_without_walk() {
+    // Arrange
+    let input = "fun double(x: Int): Int => x * 2\ndouble(double(1))";
+
+    let typed_ast = create_typed_ast(input);
+
+    struct ShallowCallCounter {
+        calls: usize,
+    }
+
+    impl TypedVisitor for ShallowCallCounter {
+        fn visit_expression(&mut self, expression: &TypedExpression) {
+            if let TypedExpression::Call { .. } = expression {
+                self.calls += 1;
+                return;
+            }
+
+            walk_expression(self, expression);
+        }
+    }
+
+    let mut counter = ShallowCallCounter { calls: 0 };
+
+    // Act
+    counter.visit_statement(&typed_ast);
+
+    // Assert
+    assert_eq!(counter.calls, 1);
+}
@@ -1,6 +1,9 @@
 mod common;
 
-use common::{create_env, create_typed_ast, evaluate_expression, StatementExt, VecStatementExt};
+use common::{
+    check_warnings, create_env, create_typed_ast, evaluate_expression, try_create_typed_ast,
+    StatementExt, VecStatementExt,
+};
 
 use interpreter::{value::Number, Value};
 use shared::type_checker::{
@@ -152,3 +155,54 @@ fn while_returns_else_body() {
     // Assert
     assert_eq!(value, Value::Number(Number::Int(4)));
 }
+
+#[test]
+fn while_else_without_a_value_is_unit() {
+    // Arrange
+    let input = r#"
+        let mut i = 0;
+        while 3 > i => {
+            if i > 5 => break;
+            i = i + 1
+        } else unit
+        "#;
+
+    // Act
+    let value = evaluate_expression(input, create_env(), false);
+
+    // Assert
+    assert_eq!(value, Value::Unit);
+}
+
+#[test]
+fn while_else_must_not_have_a_value_when_breaking_without_one() {
+    // Arrange
+    let input = "let b = false; while b => { break; } else 1";
+
+    // Act
+    let typed_ast = try_create_typed_ast(input);
+
+    // Assert
+    assert_eq!(
+        typed_ast,
+        Err(
+            "The while block breaks without a value, so its else block can't have type #Int: 1"
+                .to_string()
+        )
+    );
+}
+
+#[test]
+fn while_else_of_an_endless_loop_is_unreachable() {
+    // Arrange
+    let input = "while true => { break; } else {}";
+
+    // Act
+    let warnings = check_warnings(input);
+
+    // Assert
+    assert_eq!(
+        warnings,
+        vec!["The else block of a while loop whose condition is always true is unreachable"]
+    );
+}